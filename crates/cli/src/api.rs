@@ -0,0 +1,303 @@
+use chrono::NaiveDate;
+use db::models::{
+    integration::{IntegrationCheck, IntegrationResponse},
+    label::Label,
+    project::Project,
+    project_archive::{ImportOptions, ImportReport, NameConflict, ProjectArchive},
+    quick_add::QuickAddResult,
+    sync_job::SyncJob,
+    task::{CreateTask, Task, TaskFilter, TaskStatus, TaskWithAttemptStatus},
+    task_event::TaskEvent,
+    user::User,
+};
+use reqwest::{Client, Method, RequestBuilder, StatusCode, header};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::json;
+use utils::{port_file::read_port_file, response::ApiResponse};
+use uuid::Uuid;
+
+use crate::{
+    backend::{BulkOperation, BulkTaskResult},
+    error::CliError,
+};
+
+/// Most tasks `POST /api/tasks/bulk` takes in one request
+const MAX_BULK_TASKS: usize = 500;
+
+/// Client for the Vibe Kanban REST API, sending the API key, if any, as a bearer token.
+pub struct ApiClient {
+    client: Client,
+    base_url: String,
+}
+
+impl ApiClient {
+    pub fn new(base_url: &str, api_key: Option<&str>) -> Self {
+        let mut headers = header::HeaderMap::new();
+        if let Some(key) = api_key
+            && let Ok(value) = header::HeaderValue::from_str(&format!("Bearer {key}"))
+        {
+            headers.insert(header::AUTHORIZATION, value);
+        }
+        let client = Client::builder()
+            .default_headers(headers)
+            .user_agent(concat!("vk/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default();
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// The server given, or else the one running locally, found the way the MCP server
+    /// finds it: `HOST` and `BACKEND_PORT`/`PORT`, then the port file it writes.
+    pub async fn discover_url(server: Option<&str>) -> Result<String, CliError> {
+        if let Some(server) = server {
+            return Ok(server.to_string());
+        }
+        let host = std::env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+        let port = match std::env::var("BACKEND_PORT").or_else(|_| std::env::var("PORT")) {
+            Ok(port) => port.trim().parse::<u16>().ok(),
+            Err(_) => read_port_file("vibe-kanban").await.ok(),
+        };
+        let port = port.ok_or(CliError::ServerNotFound)?;
+        Ok(format!("http://{host}:{port}"))
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client.request(
+            method,
+            format!("{}/api/{}", self.base_url, path.trim_start_matches('/')),
+        )
+    }
+
+    /// Send the request and read the whole body, turning error statuses into errors.
+    async fn fetch(&self, request: RequestBuilder) -> Result<(StatusCode, String), CliError> {
+        let response = request.send().await.map_err(|source| CliError::Connect {
+            url: self.base_url.clone(),
+            source,
+        })?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| CliError::InvalidResponse(e.to_string()))?;
+        if status.is_success() {
+            return Ok((status, body));
+        }
+        let message = serde_json::from_str::<ApiResponse<serde_json::Value>>(&body)
+            .ok()
+            .and_then(|envelope| envelope.message().map(str::to_string))
+            .or_else(|| status.canonical_reason().map(str::to_string))
+            .unwrap_or_else(|| "request failed".to_string());
+        Err(CliError::Api {
+            status: status.as_u16(),
+            message,
+        })
+    }
+
+    /// Send the request and unwrap the `ApiResponse` envelope.
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, CliError> {
+        let (status, body) = self.fetch(request).await?;
+        let envelope = serde_json::from_str::<ApiResponse<T, serde_json::Value>>(&body)
+            .map_err(|e| CliError::InvalidResponse(e.to_string()))?;
+        if !envelope.is_success() {
+            return Err(CliError::Api {
+                status: status.as_u16(),
+                message: envelope.message().unwrap_or("request failed").to_string(),
+            });
+        }
+        envelope
+            .into_data()
+            .ok_or_else(|| CliError::InvalidResponse("response has no data".to_string()))
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, CliError> {
+        self.send(self.request(Method::GET, path)).await
+    }
+
+    pub async fn post<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T, CliError> {
+        self.send(self.request(Method::POST, path).json(body)).await
+    }
+
+    pub async fn put<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T, CliError> {
+        self.send(self.request(Method::PUT, path).json(body)).await
+    }
+
+    pub async fn list_projects(&self) -> Result<Vec<Project>, CliError> {
+        self.get("projects").await
+    }
+
+    pub async fn list_tasks(
+        &self,
+        project_id: Uuid,
+        status: Option<TaskStatus>,
+    ) -> Result<Vec<TaskWithAttemptStatus>, CliError> {
+        let mut path = format!("tasks?project_id={project_id}");
+        if let Some(status) = status {
+            path.push_str(&format!("&status={status}"));
+        }
+        self.get(&path).await
+    }
+
+    pub async fn find_tasks(
+        &self,
+        project_id: Uuid,
+        filter: &TaskFilter,
+    ) -> Result<Vec<TaskWithAttemptStatus>, CliError> {
+        let mut query = vec![("project_id", project_id.to_string())];
+        let mut add = |key, value: Option<String>| {
+            if let Some(value) = value {
+                query.push((key, value));
+            }
+        };
+        add("status", filter.status.as_ref().map(ToString::to_string));
+        add("q", filter.query.clone());
+        add("label_id", filter.label_id.map(|id| id.to_string()));
+        add(
+            "priority",
+            filter.priority.as_ref().map(ToString::to_string),
+        );
+        add("assignee_id", filter.assignee_id.map(|id| id.to_string()));
+        add("unassigned", filter.unassigned.map(|b| b.to_string()));
+        add("overdue", filter.overdue.map(|b| b.to_string()));
+        self.send(self.request(Method::GET, "tasks").query(&query))
+            .await
+    }
+
+    pub async fn list_labels(&self, project_id: Uuid) -> Result<Vec<Label>, CliError> {
+        self.get(&format!("projects/{project_id}/labels")).await
+    }
+
+    pub async fn list_users(&self) -> Result<Vec<User>, CliError> {
+        self.get("users").await
+    }
+
+    /// Apply one operation to many tasks, in batches the server accepts.
+    pub async fn bulk_update(
+        &self,
+        task_ids: &[Uuid],
+        operation: &BulkOperation,
+    ) -> Result<Vec<BulkTaskResult>, CliError> {
+        let mut results = Vec::with_capacity(task_ids.len());
+        for batch in task_ids.chunks(MAX_BULK_TASKS) {
+            let body = json!({ "task_ids": batch, "operation": operation });
+            results.extend(
+                self.post::<Vec<BulkTaskResult>, _>("tasks/bulk", &body)
+                    .await?,
+            );
+        }
+        Ok(results)
+    }
+
+    pub async fn create_task(&self, task: &CreateTask) -> Result<Task, CliError> {
+        self.post("tasks", task).await
+    }
+
+    pub async fn quick_add(
+        &self,
+        project_id: Uuid,
+        text: &str,
+        today: NaiveDate,
+        dry_run: bool,
+    ) -> Result<QuickAddResult, CliError> {
+        let body = json!({
+            "project_id": project_id,
+            "text": text,
+            "today": today,
+            "dry_run": dry_run,
+        });
+        self.post("tasks/quick-add", &body).await
+    }
+
+    /// Change only the task's status, whatever else changed since; the board column
+    /// follows it.
+    pub async fn set_task_status(
+        &self,
+        task_id: Uuid,
+        status: TaskStatus,
+    ) -> Result<Task, CliError> {
+        let body = json!({
+            "title": null,
+            "description": null,
+            "status": status,
+            "parent_workspace_id": null,
+            "image_ids": null,
+        });
+        let request = self
+            .request(Method::PUT, &format!("tasks/{task_id}"))
+            .header(header::IF_MATCH, "*")
+            .json(&body);
+        self.send(request).await
+    }
+
+    /// Everything that happened to the task, oldest first.
+    pub async fn task_activity(&self, task_id: Uuid) -> Result<Vec<TaskEvent>, CliError> {
+        self.get(&format!("tasks/{task_id}/activity")).await
+    }
+
+    pub async fn list_integrations(&self) -> Result<Vec<IntegrationResponse>, CliError> {
+        self.get("integrations").await
+    }
+
+    /// Queue a sync run, or get the one already queued or running.
+    pub async fn trigger_sync(&self, integration_id: Uuid) -> Result<SyncJob, CliError> {
+        self.post(&format!("integrations/{integration_id}/sync"), &json!({}))
+            .await
+    }
+
+    pub async fn get_sync_job(&self, job_id: Uuid) -> Result<SyncJob, CliError> {
+        self.get(&format!("integrations/jobs/{job_id}")).await
+    }
+
+    /// Check the integration's config, credentials and scope, enabled or not.
+    pub async fn check_integration(
+        &self,
+        integration_id: Uuid,
+    ) -> Result<IntegrationCheck, CliError> {
+        self.post(&format!("integrations/{integration_id}/check"), &json!({}))
+            .await
+    }
+
+    /// The project's archive, which the server sends bare rather than in an envelope.
+    pub async fn export_project(&self, project_id: Uuid) -> Result<ProjectArchive, CliError> {
+        let (_, body) = self
+            .fetch(self.request(Method::GET, &format!("projects/{project_id}/export")))
+            .await?;
+        serde_json::from_str(&body).map_err(|e| CliError::InvalidResponse(e.to_string()))
+    }
+
+    pub async fn import_project(
+        &self,
+        archive: &ProjectArchive,
+        options: &ImportOptions,
+    ) -> Result<ImportReport, CliError> {
+        let mut query = vec![
+            ("dry_run", options.dry_run.to_string()),
+            ("on_conflict", name_conflict(options.on_conflict)),
+        ];
+        if let Some(name) = &options.name {
+            query.push(("name", name.clone()));
+        }
+        let request = self
+            .request(Method::POST, "projects/import")
+            .query(&query)
+            .json(archive);
+        self.send(request).await
+    }
+}
+
+fn name_conflict(value: NameConflict) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use db::{
+    DBService,
+    models::{
+        label::Label,
+        project::Project,
+        project_archive::{ImportOptions, ImportReport, ProjectArchive},
+        quick_add::QuickAddResult,
+        task::{CreateTask, Task, TaskFilter, TaskStatus, TaskWithAttemptStatus},
+        task_event::{TaskEvent, TaskEventSource},
+        user::User,
+    },
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{api::ApiClient, error::CliError};
+
+/// A change `POST /api/tasks/bulk` applies to many tasks at once.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BulkOperation {
+    SetStatus {
+        status: TaskStatus,
+    },
+    Archive,
+    /// Moves the tasks to the trash
+    Delete,
+}
+
+/// How a bulk change went for one task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkTaskResult {
+    pub task_id: Uuid,
+    pub ok: bool,
+    /// Why the task was left as it was
+    pub error: Option<String>,
+}
+
+/// Where commands read and change the board: a server's API, or, with `--local`, the
+/// database of the Vibe Kanban install on this machine, opened directly.
+pub enum Backend {
+    Api(ApiClient),
+    Local(DBService),
+}
+
+impl Backend {
+    pub async fn connect(
+        local: bool,
+        server: Option<&str>,
+        api_key: Option<&str>,
+    ) -> Result<Self, CliError> {
+        if local {
+            tracing::debug!("Using the local database");
+            return Ok(Self::Local(DBService::new().await?));
+        }
+        let url = ApiClient::discover_url(server).await?;
+        tracing::debug!("Using server {url}");
+        Ok(Self::Api(ApiClient::new(&url, api_key)))
+    }
+
+    pub async fn list_projects(&self) -> Result<Vec<Project>, CliError> {
+        match self {
+            Self::Api(client) => client.list_projects().await,
+            Self::Local(db) => Ok(Project::find_all(&db.pool).await?),
+        }
+    }
+
+    pub async fn list_tasks(
+        &self,
+        project_id: Uuid,
+        status: Option<TaskStatus>,
+    ) -> Result<Vec<TaskWithAttemptStatus>, CliError> {
+        match self {
+            Self::Api(client) => client.list_tasks(project_id, status).await,
+            Self::Local(db) => {
+                let tasks =
+                    Task::find_by_project_id_with_attempt_status(&db.pool, project_id).await?;
+                Ok(tasks
+                    .into_iter()
+                    .filter(|task| status.as_ref().is_none_or(|status| task.status == *status))
+                    .collect())
+            }
+        }
+    }
+
+    pub async fn create_task(&self, payload: &CreateTask) -> Result<Task, CliError> {
+        match self {
+            Self::Api(client) => client.create_task(payload).await,
+            Self::Local(db) => {
+                let task = Task::create(&db.pool, payload, Uuid::new_v4()).await?;
+                TaskEvent::record_created(&db.pool, &task, TaskEventSource::User, None).await?;
+                Ok(task)
+            }
+        }
+    }
+
+    /// Create a task from a quick-add line such as `Fix login bug #bug @alice !high ^friday`.
+    pub async fn quick_add(
+        &self,
+        project_id: Uuid,
+        text: &str,
+        today: NaiveDate,
+        dry_run: bool,
+    ) -> Result<QuickAddResult, CliError> {
+        match self {
+            Self::Api(client) => client.quick_add(project_id, text, today, dry_run).await,
+            Self::Local(db) => {
+                Ok(QuickAddResult::create(&db.pool, project_id, text, today, dry_run).await?)
+            }
+        }
+    }
+
+    /// Change only the task's status; the board column follows it.
+    pub async fn set_task_status(
+        &self,
+        task_id: Uuid,
+        status: TaskStatus,
+    ) -> Result<Task, CliError> {
+        match self {
+            Self::Api(client) => client.set_task_status(task_id, status).await,
+            Self::Local(db) => {
+                let before = Task::find_by_id(&db.pool, task_id)
+                    .await?
+                    .ok_or(CliError::TaskNotFound(task_id))?;
+                Task::update_status(&db.pool, task_id, status).await?;
+                let after = Task::find_by_id(&db.pool, task_id)
+                    .await?
+                    .ok_or(CliError::TaskNotFound(task_id))?;
+                TaskEvent::record_changes(&db.pool, &before, &after, TaskEventSource::User, None)
+                    .await?;
+                Ok(after)
+            }
+        }
+    }
+
+    /// The project's tasks matching `filter`, all of them rather than a page.
+    pub async fn find_tasks(
+        &self,
+        project_id: Uuid,
+        filter: &TaskFilter,
+    ) -> Result<Vec<TaskWithAttemptStatus>, CliError> {
+        match self {
+            Self::Api(client) => client.find_tasks(project_id, filter).await,
+            Self::Local(db) => Ok(Task::find_page_with_attempt_status(
+                &db.pool, project_id, filter, None, None, 0,
+            )
+            .await?
+            .items),
+        }
+    }
+
+    /// When each of the tasks last moved to done, as recorded in their activity; tasks
+    /// with no such change recorded are left out.
+    pub async fn done_at(
+        &self,
+        project_id: Uuid,
+        task_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, DateTime<Utc>>, CliError> {
+        let events = match self {
+            Self::Api(client) => {
+                let mut events = Vec::new();
+                for &task_id in task_ids {
+                    events.extend(client.task_activity(task_id).await?);
+                }
+                events
+            }
+            Self::Local(db) => {
+                TaskEvent::find_field_changes_by_project_id(&db.pool, project_id, &["status"])
+                    .await?
+            }
+        };
+        let done = TaskStatus::Done.to_string();
+        let mut done_at = HashMap::new();
+        for event in events {
+            if task_ids.contains(&event.task_id)
+                && event.field.as_deref() == Some("status")
+                && event.new_value.as_deref() == Some(done.as_str())
+            {
+                done_at
+                    .entry(event.task_id)
+                    .and_modify(|at: &mut DateTime<Utc>| *at = (*at).max(event.created_at))
+                    .or_insert(event.created_at);
+            }
+        }
+        Ok(done_at)
+    }
+
+    pub async fn list_labels(&self, project_id: Uuid) -> Result<Vec<Label>, CliError> {
+        match self {
+            Self::Api(client) => client.list_labels(project_id).await,
+            Self::Local(db) => Ok(Label::find_by_project_id(&db.pool, project_id).await?),
+        }
+    }
+
+    pub async fn list_users(&self) -> Result<Vec<User>, CliError> {
+        match self {
+            Self::Api(client) => client.list_users().await,
+            Self::Local(db) => Ok(User::find_all(&db.pool).await?),
+        }
+    }
+
+    /// Apply `operation` to every task. A server checks permissions, WIP limits and
+    /// running processes and skips the tasks that fail them; locally each task is simply
+    /// changed on its own.
+    pub async fn bulk_update(
+        &self,
+        task_ids: &[Uuid],
+        operation: &BulkOperation,
+    ) -> Result<Vec<BulkTaskResult>, CliError> {
+        match self {
+            Self::Api(client) => client.bulk_update(task_ids, operation).await,
+            Self::Local(db) => {
+                let mut results = Vec::with_capacity(task_ids.len());
+                for &task_id in task_ids {
+                    let outcome = Self::apply_locally(db, task_id, operation).await;
+                    results.push(BulkTaskResult {
+                        task_id,
+                        ok: outcome.is_ok(),
+                        error: outcome.err().map(|e| e.to_string()),
+                    });
+                }
+                Ok(results)
+            }
+        }
+    }
+
+    async fn apply_locally(
+        db: &DBService,
+        task_id: Uuid,
+        operation: &BulkOperation,
+    ) -> Result<(), CliError> {
+        let before = Task::find_by_id(&db.pool, task_id)
+            .await?
+            .ok_or(CliError::TaskNotFound(task_id))?;
+        match operation {
+            BulkOperation::SetStatus { status } => {
+                Task::update_status(&db.pool, task_id, status.clone()).await?;
+            }
+            BulkOperation::Archive => {
+                Task::archive(&db.pool, task_id).await?;
+            }
+            BulkOperation::Delete => {
+                Task::trash(&db.pool, task_id).await?;
+            }
+        }
+        let after = Task::find_by_id(&db.pool, task_id)
+            .await?
+            .ok_or(CliError::TaskNotFound(task_id))?;
+        TaskEvent::record_changes(&db.pool, &before, &after, TaskEventSource::User, None).await?;
+        Ok(())
+    }
+
+    pub async fn export_project(&self, project: &Project) -> Result<ProjectArchive, CliError> {
+        match self {
+            Self::Api(client) => client.export_project(project.id).await,
+            Self::Local(db) => Ok(ProjectArchive::export(&db.pool, project).await?),
+        }
+    }
+
+    pub async fn import_project(
+        &self,
+        archive: &ProjectArchive,
+        options: &ImportOptions,
+    ) -> Result<ImportReport, CliError> {
+        match self {
+            Self::Api(client) => client.import_project(archive, options).await,
+            Self::Local(db) => Ok(archive.import(&db.pool, options).await?),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use chrono::Local;
+use clap::Args;
+
+use crate::{
+    backend::Backend,
+    commands::resolve_project,
+    error::CliError,
+    output::{OutputFormat, Table},
+};
+
+#[derive(Args, Debug)]
+pub struct AddArgs {
+    /// The task as one line, e.g. 'Fix login bug #bug @alice !high ^friday'. `#label`,
+    /// `@user`, `!priority` and `^due` (today, tomorrow, a weekday, YYYY-MM-DD, 3d or 2w)
+    /// are taken out; the rest is the title
+    #[arg(required = true)]
+    text: Vec<String>,
+    /// Project id or name
+    #[arg(short, long, env = "VK_PROJECT")]
+    project: Option<String>,
+    /// Show how the line is read without creating the task
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// Create a task from a quick-add line, counting `^` dates from today on this machine.
+pub async fn run(
+    backend: &Backend,
+    args: AddArgs,
+    default_project: Option<&str>,
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    let reference = args
+        .project
+        .as_deref()
+        .or(default_project)
+        .ok_or(CliError::NoProject)?;
+    let project = resolve_project(backend, reference).await?;
+    let text = args.text.join(" ");
+    let today = Local::now().date_naive();
+    let result = backend
+        .quick_add(project.id, &text, today, args.dry_run)
+        .await?;
+
+    output.print(&result, || {
+        let parsed = &result.parsed;
+        let labels: Vec<&str> = parsed
+            .labels
+            .iter()
+            .filter(|label| !result.unmatched_labels.contains(label))
+            .map(String::as_str)
+            .collect();
+        let mut table = Table::new(["ID", "PRIORITY", "DUE", "ASSIGNEE", "LABELS", "TITLE"]);
+        table.row([
+            result
+                .task
+                .as_ref()
+                .map_or("(dry run)".to_string(), |task| task.id.to_string()),
+            parsed
+                .priority
+                .map_or("-".to_string(), |priority| priority.to_string()),
+            parsed
+                .due_at
+                .map_or("-".to_string(), |due| due.format("%Y-%m-%d").to_string()),
+            parsed
+                .assignee
+                .as_ref()
+                .filter(|_| result.unmatched_assignee.is_none())
+                .map_or("-".to_string(), |handle| format!("@{handle}")),
+            if labels.is_empty() {
+                "-".to_string()
+            } else {
+                labels.join(", ")
+            },
+            parsed.title.clone(),
+        ]);
+        print!("{table}");
+        for label in &result.unmatched_labels {
+            println!("No label named '{label}' in {}; left off", project.name);
+        }
+        if let Some(handle) = &result.unmatched_assignee {
+            println!("No single user goes by @{handle}; left unassigned");
+        }
+    })
+}
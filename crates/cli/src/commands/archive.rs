@@ -0,0 +1,167 @@
+use std::{
+    fs::File,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use clap::Args;
+use db::models::project_archive::{ImportOptions, NameConflict, ProjectArchive};
+use serde_json::json;
+use zip::{ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+use crate::{backend::Backend, commands::resolve_project, error::CliError, output::OutputFormat};
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    /// Project id or name
+    #[arg(short, long, env = "VK_PROJECT")]
+    project: Option<String>,
+    /// File to write; a `.zip` path gets the archive zipped. Printed when not given
+    #[arg(short, long)]
+    out: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub struct ImportArgs {
+    /// Archive made by `vk export`, as JSON or zipped
+    file: PathBuf,
+    /// Name of the new project, the archived name when not given
+    #[arg(long)]
+    name: Option<String>,
+    /// When a project already has the name: rename the new one, or fail
+    #[arg(long, default_value = "rename", value_parser = parse_name_conflict)]
+    on_conflict: NameConflict,
+    /// Only check the archive and report what would be created
+    #[arg(long)]
+    dry_run: bool,
+}
+
+fn parse_name_conflict(value: &str) -> Result<NameConflict, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_lowercase()))
+        .map_err(|_| format!("'{value}' is not a conflict policy; use rename or fail"))
+}
+
+fn is_zip(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("zip"))
+}
+
+fn zip_error(e: zip::result::ZipError) -> CliError {
+    CliError::InvalidArchive(e.to_string())
+}
+
+fn write_archive(path: &Path, json: &[u8]) -> Result<(), CliError> {
+    let mut file = File::create(path)?;
+    if !is_zip(path) {
+        file.write_all(json)?;
+        return Ok(());
+    }
+    let mut zip = ZipWriter::new(file);
+    zip.start_file("project.vk.json", SimpleFileOptions::default())
+        .map_err(zip_error)?;
+    zip.write_all(json)?;
+    zip.finish().map_err(zip_error)?;
+    Ok(())
+}
+
+/// The archive in `path`: the file itself, or the first JSON file in it when zipped.
+fn read_archive(path: &Path) -> Result<ProjectArchive, CliError> {
+    let mut json = String::new();
+    if is_zip(path) {
+        let mut zip = ZipArchive::new(File::open(path)?).map_err(zip_error)?;
+        let name = zip
+            .file_names()
+            .find(|name| name.ends_with(".json"))
+            .map(str::to_string)
+            .ok_or_else(|| CliError::InvalidArchive("the zip holds no .json file".to_string()))?;
+        zip.by_name(&name)
+            .map_err(zip_error)?
+            .read_to_string(&mut json)?;
+    } else {
+        File::open(path)?.read_to_string(&mut json)?;
+    }
+    serde_json::from_str(&json).map_err(|e| CliError::InvalidArchive(e.to_string()))
+}
+
+pub async fn export(
+    backend: &Backend,
+    args: ExportArgs,
+    default_project: Option<&str>,
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    let reference = args
+        .project
+        .as_deref()
+        .or(default_project)
+        .ok_or(CliError::NoProject)?;
+    let project = resolve_project(backend, reference).await?;
+    let archive = backend.export_project(&project).await?;
+    let json = serde_json::to_vec_pretty(&archive)
+        .map_err(|e| CliError::InvalidResponse(e.to_string()))?;
+
+    // Without --out the archive is the output, whatever the format
+    let Some(out) = args.out else {
+        std::io::stdout().write_all(&json)?;
+        return Ok(());
+    };
+    write_archive(&out, &json)?;
+    let tasks = archive.tasks.len();
+    let exported = json!({ "project_id": project.id, "file": out, "tasks": tasks });
+    output.print(&exported, || {
+        println!(
+            "Exported {} ({tasks} tasks) to {}",
+            project.name,
+            out.display()
+        );
+    })
+}
+
+pub async fn import(
+    backend: &Backend,
+    args: ImportArgs,
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    let archive = read_archive(&args.file)?;
+    let options = ImportOptions {
+        name: args.name,
+        on_conflict: args.on_conflict,
+        team_id: None,
+        dry_run: args.dry_run,
+    };
+    let report = backend.import_project(&archive, &options).await?;
+
+    output.print(&report, || {
+        match &report.project {
+            Some(project) => println!("Imported {} as {}", report.project_name, project.id),
+            None => println!("Would import {}", report.project_name),
+        }
+        if report.renamed {
+            println!("  renamed, as another project had the name");
+        }
+        println!(
+            "  {} tasks, {} columns, {} labels, {} custom fields, {} checklist items, {} comments, {} links",
+            report.tasks,
+            report.columns,
+            report.labels,
+            report.custom_fields,
+            report.checklist_items,
+            report.comments,
+            report.links
+        );
+        if report.integrations > 0 {
+            println!(
+                "  {} integrations, disabled until their secrets are entered again",
+                report.integrations
+            );
+        }
+        if !report.unmatched_assignees.is_empty() {
+            println!(
+                "  left unassigned, no matching user: {}",
+                report.unmatched_assignees.join(", ")
+            );
+        }
+        for skipped in &report.skipped {
+            println!("  skipped: {skipped}");
+        }
+    })
+}
@@ -0,0 +1,67 @@
+use std::{path::PathBuf, time::Duration};
+
+use clap::Args;
+use db::{DBService, backup};
+use serde_json::json;
+use utils::port_file::read_port_file;
+
+use crate::{error::CliError, output::OutputFormat};
+
+#[derive(Args, Debug)]
+pub struct BackupArgs {
+    /// File to write, gzipped when it ends in `.gz`; `vk-<timestamp>.db.gz` when not given
+    #[arg(short, long)]
+    out: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub struct RestoreArgs {
+    /// Backup made by `vk backup`, plain or gzipped
+    file: PathBuf,
+    /// Restore even though a server seems to be running on this machine
+    #[arg(long)]
+    force: bool,
+}
+
+/// Whether the server on this machine answers on the port it last wrote down.
+async fn server_running() -> bool {
+    let Ok(port) = read_port_file("vibe-kanban").await else {
+        return false;
+    };
+    let connect = tokio::net::TcpStream::connect(("127.0.0.1", port));
+    matches!(
+        tokio::time::timeout(Duration::from_secs(1), connect).await,
+        Ok(Ok(_))
+    )
+}
+
+pub async fn backup(args: BackupArgs, output: OutputFormat) -> Result<(), CliError> {
+    let out = args.out.unwrap_or_else(|| {
+        PathBuf::from(format!(
+            "vk-{}.db.gz",
+            chrono::Utc::now().format("%Y%m%d-%H%M%S")
+        ))
+    });
+    let bytes = DBService::open().await?.backup_to(&out).await?;
+    output.print(&json!({ "file": out, "bytes": bytes }), || {
+        println!(
+            "Backed up the database to {} ({bytes} bytes)",
+            out.display()
+        );
+    })
+}
+
+pub async fn restore(args: RestoreArgs, output: OutputFormat) -> Result<(), CliError> {
+    if !args.force && server_running().await {
+        return Err(CliError::ServerRunning);
+    }
+    let previous = backup::restore_from(&args.file).await?;
+    let database = backup::database_path();
+    let result = json!({ "file": args.file, "database": database, "previous": previous });
+    output.print(&result, || {
+        println!("Restored {} to {}", args.file.display(), database.display());
+        if let Some(previous) = &previous {
+            println!("The database it replaced is at {}", previous.display());
+        }
+    })
+}
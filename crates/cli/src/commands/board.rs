@@ -0,0 +1,346 @@
+use clap::Args;
+use db::models::{
+    project::Project,
+    task::{CreateTask, TaskPriority, TaskStatus, TaskWithAttemptStatus},
+};
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, List, ListItem, ListState},
+};
+use strum::IntoEnumIterator;
+use uuid::Uuid;
+
+use crate::{backend::Backend, commands::resolve_project, error::CliError};
+
+const HELP: &str =
+    "←/→ column  ↑/↓ task  shift+←/→ move  d done  a add  tab project  r refresh  q quit";
+
+#[derive(Args, Debug)]
+pub struct BoardArgs {
+    /// Project id or name; the first project when not given
+    #[arg(short, long, env = "VK_PROJECT")]
+    project: Option<String>,
+}
+
+enum Mode {
+    Browse,
+    /// Typing the title of a task to add to the selected column
+    Add(String),
+}
+
+struct Board {
+    projects: Vec<Project>,
+    project: usize,
+    columns: Vec<(TaskStatus, Vec<TaskWithAttemptStatus>)>,
+    column: usize,
+    row: usize,
+    mode: Mode,
+    message: Option<String>,
+    quit: bool,
+}
+
+fn label(status: &TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Todo => "To do",
+        TaskStatus::InProgress => "In progress",
+        TaskStatus::InReview => "In review",
+        TaskStatus::Done => "Done",
+        TaskStatus::Cancelled => "Cancelled",
+    }
+}
+
+fn card(task: &TaskWithAttemptStatus) -> Line<'static> {
+    let mut line = Line::default();
+    match task.priority {
+        TaskPriority::Urgent => line.push_span("!! ".red().bold()),
+        TaskPriority::High => line.push_span("! ".yellow().bold()),
+        TaskPriority::Low | TaskPriority::Medium => {}
+    }
+    line.push_span(task.title.clone());
+    if task.is_blocked {
+        line.push_span(" [blocked]".dark_gray());
+    }
+    line
+}
+
+impl Board {
+    fn project_id(&self) -> Uuid {
+        self.projects[self.project].id
+    }
+
+    async fn load(&mut self, backend: &Backend) -> Result<(), CliError> {
+        let tasks = backend.list_tasks(self.project_id(), None).await?;
+        self.columns = TaskStatus::iter()
+            .map(|status| {
+                let column = tasks
+                    .iter()
+                    .filter(|task| task.status == status)
+                    .cloned()
+                    .collect();
+                (status, column)
+            })
+            .collect();
+        self.clamp_row();
+        Ok(())
+    }
+
+    fn clamp_row(&mut self) {
+        let len = self
+            .columns
+            .get(self.column)
+            .map_or(0, |(_, tasks)| tasks.len());
+        self.row = self.row.min(len.saturating_sub(1));
+    }
+
+    fn selected(&self) -> Option<&TaskWithAttemptStatus> {
+        self.columns.get(self.column)?.1.get(self.row)
+    }
+
+    /// Put the selection on the task, wherever it now sits.
+    fn follow(&mut self, task_id: Uuid) {
+        for (column, (_, tasks)) in self.columns.iter().enumerate() {
+            if let Some(row) = tasks.iter().position(|task| task.id == task_id) {
+                self.column = column;
+                self.row = row;
+                return;
+            }
+        }
+    }
+
+    fn select_column(&mut self, offset: isize) {
+        if let Some(column) = self
+            .column
+            .checked_add_signed(offset)
+            .filter(|column| *column < self.columns.len())
+        {
+            self.column = column;
+            self.clamp_row();
+        }
+    }
+
+    async fn select_project(&mut self, backend: &Backend, offset: isize) -> Result<(), CliError> {
+        let count = self.projects.len() as isize;
+        self.project = (self.project as isize + offset).rem_euclid(count) as usize;
+        self.column = 0;
+        self.row = 0;
+        self.load(backend).await
+    }
+
+    async fn move_selected(
+        &mut self,
+        backend: &Backend,
+        status: TaskStatus,
+    ) -> Result<(), CliError> {
+        let Some(task) = self.selected() else {
+            return Ok(());
+        };
+        if task.status == status {
+            return Ok(());
+        }
+        let task = backend.set_task_status(task.id, status).await?;
+        self.load(backend).await?;
+        self.follow(task.id);
+        self.message = Some(format!("Moved '{}' to {}", task.title, label(&task.status)));
+        Ok(())
+    }
+
+    async fn shift_selected(&mut self, backend: &Backend, offset: isize) -> Result<(), CliError> {
+        let target = self
+            .column
+            .checked_add_signed(offset)
+            .and_then(|column| self.columns.get(column))
+            .map(|(status, _)| status.clone());
+        match target {
+            Some(status) => self.move_selected(backend, status).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn add_task(&mut self, backend: &Backend, title: String) -> Result<(), CliError> {
+        let Some((status, _)) = self.columns.get(self.column) else {
+            return Ok(());
+        };
+        let mut payload = CreateTask::from_title_description(self.project_id(), title, None);
+        payload.status = Some(status.clone());
+        let task = backend.create_task(&payload).await?;
+        self.load(backend).await?;
+        self.follow(task.id);
+        self.message = Some(format!("Added '{}'", task.title));
+        Ok(())
+    }
+
+    async fn handle_key(&mut self, backend: &Backend, key: KeyEvent) -> Result<(), CliError> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+            return Ok(());
+        }
+        let Mode::Add(title) = &mut self.mode else {
+            return self.handle_browse_key(backend, key).await;
+        };
+        match key.code {
+            KeyCode::Esc => self.mode = Mode::Browse,
+            KeyCode::Enter => {
+                let title = std::mem::take(title).trim().to_string();
+                self.mode = Mode::Browse;
+                if !title.is_empty() {
+                    self.add_task(backend, title).await?;
+                }
+            }
+            KeyCode::Backspace => {
+                title.pop();
+            }
+            KeyCode::Char(c) => title.push(c),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn handle_browse_key(
+        &mut self,
+        backend: &Backend,
+        key: KeyEvent,
+    ) -> Result<(), CliError> {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Left if shift => self.shift_selected(backend, -1).await?,
+            KeyCode::Right if shift => self.shift_selected(backend, 1).await?,
+            KeyCode::Char('H') => self.shift_selected(backend, -1).await?,
+            KeyCode::Char('L') => self.shift_selected(backend, 1).await?,
+            KeyCode::Left | KeyCode::Char('h') => self.select_column(-1),
+            KeyCode::Right | KeyCode::Char('l') => self.select_column(1),
+            KeyCode::Up | KeyCode::Char('k') => self.row = self.row.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.row += 1;
+                self.clamp_row();
+            }
+            KeyCode::Char('d') => self.move_selected(backend, TaskStatus::Done).await?,
+            KeyCode::Char('a') => self.mode = Mode::Add(String::new()),
+            KeyCode::Char('r') => self.load(backend).await?,
+            KeyCode::Tab => self.select_project(backend, 1).await?,
+            KeyCode::BackTab => self.select_project(backend, -1).await?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let project = &self.projects[self.project];
+        frame.render_widget(
+            Line::from(vec![
+                project.name.clone().bold(),
+                format!("  {}/{}", self.project + 1, self.projects.len()).dark_gray(),
+            ]),
+            header,
+        );
+
+        let areas = Layout::horizontal(vec![Constraint::Fill(1); self.columns.len()]).split(body);
+        for (index, ((status, tasks), area)) in self.columns.iter().zip(areas.iter()).enumerate() {
+            let focused = index == self.column;
+            let border = if focused {
+                Style::new().fg(Color::Cyan)
+            } else {
+                Style::new().dark_gray()
+            };
+            let block = Block::bordered()
+                .title(format!(" {} ({}) ", label(status), tasks.len()))
+                .border_style(border);
+            let list = List::new(tasks.iter().map(|task| ListItem::new(card(task))))
+                .block(block)
+                .highlight_style(Style::new().reversed());
+            let mut state = ListState::default();
+            if focused && !tasks.is_empty() {
+                state.select(Some(self.row));
+            }
+            frame.render_stateful_widget(list, *area, &mut state);
+        }
+
+        match (&self.mode, &self.message) {
+            (Mode::Add(title), _) => {
+                let prompt = match self.columns.get(self.column) {
+                    Some((status, _)) => format!("New task in {}: ", label(status)),
+                    None => "New task: ".to_string(),
+                };
+                let cursor = footer.x + (prompt.chars().count() + title.chars().count()) as u16;
+                frame.render_widget(
+                    Line::from(vec![prompt.bold(), title.clone().into()]),
+                    footer,
+                );
+                frame.set_cursor_position((cursor.min(footer.right()), footer.y));
+            }
+            (Mode::Browse, Some(message)) => {
+                frame.render_widget(Line::from(message.as_str()), footer);
+            }
+            (Mode::Browse, None) => frame.render_widget(Line::from(HELP).dark_gray(), footer),
+        }
+    }
+
+    async fn run(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        backend: &Backend,
+    ) -> Result<(), CliError> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                self.message = None;
+                // Keep the board up when the server refuses a change; say why instead
+                if let Err(e) = self.handle_key(backend, key).await {
+                    self.message = Some(e.to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub async fn run(
+    backend: &Backend,
+    args: BoardArgs,
+    default_project: Option<&str>,
+) -> Result<(), CliError> {
+    let projects = backend.list_projects().await?;
+    if projects.is_empty() {
+        println!("No projects");
+        return Ok(());
+    }
+    let project = match args.project.as_deref().or(default_project) {
+        Some(reference) => {
+            let wanted = resolve_project(backend, reference).await?;
+            projects
+                .iter()
+                .position(|project| project.id == wanted.id)
+                .unwrap_or_default()
+        }
+        None => 0,
+    };
+    let mut board = Board {
+        projects,
+        project,
+        columns: Vec::new(),
+        column: 0,
+        row: 0,
+        mode: Mode::Browse,
+        message: None,
+        quit: false,
+    };
+    board.load(backend).await?;
+
+    let mut terminal = ratatui::init();
+    let result = board.run(&mut terminal, backend).await;
+    ratatui::restore();
+    result
+}
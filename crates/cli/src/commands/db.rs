@@ -0,0 +1,127 @@
+use std::path::PathBuf;
+
+use clap::Subcommand;
+use db::DBService;
+use serde_json::json;
+
+use crate::{
+    error::CliError,
+    output::{OutputFormat, Table},
+};
+
+#[derive(Subcommand, Debug)]
+pub enum DbCommand {
+    /// Apply every pending migration to this machine's database
+    Migrate,
+    /// List the migrations this build ships and which ones are applied
+    Status,
+    /// Revert the latest migration, or every one applied after --to
+    Rollback {
+        /// Version to go back to, e.g. 20260207000000
+        #[arg(long)]
+        to: Option<i64>,
+    },
+    /// Add an empty migration to a source checkout
+    Create {
+        /// What the migration does, e.g. `add_task_colors`
+        name: String,
+        /// Migrations directory of the db crate
+        #[arg(long, default_value = "crates/db/migrations")]
+        dir: PathBuf,
+        /// Also write a down script, so the migration can be rolled back
+        #[arg(long)]
+        reversible: bool,
+    },
+}
+
+/// `Add task colors` becomes `add_task_colors`.
+fn file_stem(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn create(name: &str, dir: PathBuf, reversible: bool) -> Result<Vec<PathBuf>, CliError> {
+    if !dir.is_dir() {
+        return Err(CliError::MigrationsDirNotFound(dir));
+    }
+    let stem = format!(
+        "{}_{}",
+        chrono::Utc::now().format("%Y%m%d%H%M%S"),
+        file_stem(name)
+    );
+    let files = if reversible {
+        vec![
+            dir.join(format!("{stem}.up.sql")),
+            dir.join(format!("{stem}.down.sql")),
+        ]
+    } else {
+        vec![dir.join(format!("{stem}.sql"))]
+    };
+    for file in &files {
+        std::fs::write(file, format!("-- {name}\n"))?;
+    }
+    Ok(files)
+}
+
+pub async fn run(command: DbCommand, output: OutputFormat) -> Result<(), CliError> {
+    match command {
+        DbCommand::Migrate => {
+            let applied = DBService::open().await?.migrate().await?;
+            output.print(&json!({ "applied": applied }), || {
+                if applied.is_empty() {
+                    println!("Database is up to date");
+                }
+                for version in &applied {
+                    println!("Applied {version}");
+                }
+            })
+        }
+        DbCommand::Status => {
+            let migrations = DBService::open().await?.migration_status().await?;
+            output.print(&migrations, || {
+                let mut table = Table::new(["VERSION", "APPLIED", "DESCRIPTION"]);
+                for migration in &migrations {
+                    let applied = match migration.applied_at {
+                        Some(at) => at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                        None => "pending".to_string(),
+                    };
+                    let mut description = migration.description.clone();
+                    if migration.checksum_mismatch {
+                        description.push_str("  (changed since it was applied)");
+                    }
+                    if migration.reversible {
+                        description.push_str("  (reversible)");
+                    }
+                    table.row([migration.version.to_string(), applied, description]);
+                }
+                print!("{table}");
+            })
+        }
+        DbCommand::Rollback { to } => {
+            let reverted = DBService::open().await?.rollback(to).await?;
+            output.print(&json!({ "reverted": reverted }), || {
+                if reverted.is_empty() {
+                    println!("Nothing to roll back");
+                }
+                for version in &reverted {
+                    println!("Reverted {version}");
+                }
+            })
+        }
+        DbCommand::Create {
+            name,
+            dir,
+            reversible,
+        } => {
+            let files = create(&name, dir, reversible)?;
+            output.print(&json!({ "created": files }), || {
+                for file in &files {
+                    println!("Created {}", file.display());
+                }
+            })
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use db::models::task::{TaskFilter, TaskPriority, TaskStatus};
+use uuid::Uuid;
+
+use crate::{
+    backend::Backend,
+    commands::task::{parse_priority, parse_status},
+    error::CliError,
+};
+
+/// A task filter typed as `key:value` terms, e.g. `label:hotfix status:inreview`. The
+/// keys are `status`, `label`, `priority`, `assignee` (a name, or `none`) and `is:overdue`;
+/// other words are looked for in titles and descriptions.
+#[derive(Debug, Clone, Default)]
+pub struct FilterExpr {
+    status: Option<TaskStatus>,
+    label: Option<String>,
+    priority: Option<TaskPriority>,
+    assignee: Option<String>,
+    overdue: bool,
+    text: Vec<String>,
+}
+
+pub fn parse_filter(value: &str) -> Result<FilterExpr, String> {
+    let mut filter = FilterExpr::default();
+    for term in value.split_whitespace() {
+        let Some((key, term_value)) = term.split_once(':') else {
+            filter.text.push(term.to_string());
+            continue;
+        };
+        if term_value.is_empty() {
+            return Err(format!("'{term}' has no value"));
+        }
+        let repeated = || format!("'{key}' is given twice");
+        match key.to_lowercase().as_str() {
+            "status" if filter.status.is_none() => filter.status = Some(parse_status(term_value)?),
+            "label" if filter.label.is_none() => filter.label = Some(term_value.to_string()),
+            "priority" if filter.priority.is_none() => {
+                filter.priority = Some(parse_priority(term_value)?)
+            }
+            "assignee" if filter.assignee.is_none() => {
+                filter.assignee = Some(term_value.to_string())
+            }
+            "is" if term_value.eq_ignore_ascii_case("overdue") => filter.overdue = true,
+            "status" | "label" | "priority" | "assignee" => return Err(repeated()),
+            _ => {
+                return Err(format!(
+                    "'{term}' is not a filter; use status:, label:, priority:, assignee: or is:overdue"
+                ));
+            }
+        }
+    }
+    Ok(filter)
+}
+
+impl FilterExpr {
+    /// The filter with label and assignee names looked up in the project.
+    pub async fn resolve(
+        &self,
+        backend: &Backend,
+        project_id: Uuid,
+    ) -> Result<TaskFilter, CliError> {
+        let label_id = match &self.label {
+            Some(name) => Some(
+                backend
+                    .list_labels(project_id)
+                    .await?
+                    .into_iter()
+                    .find(|label| label.name.eq_ignore_ascii_case(name))
+                    .ok_or_else(|| CliError::LabelNotFound(name.clone()))?
+                    .id,
+            ),
+            None => None,
+        };
+        let unassigned = self
+            .assignee
+            .as_deref()
+            .is_some_and(|name| name.eq_ignore_ascii_case("none"));
+        let assignee_id = match &self.assignee {
+            Some(name) if !unassigned => Some(
+                backend
+                    .list_users()
+                    .await?
+                    .into_iter()
+                    .find(|user| {
+                        user.name.eq_ignore_ascii_case(name)
+                            || user
+                                .email
+                                .as_deref()
+                                .is_some_and(|email| email.eq_ignore_ascii_case(name))
+                    })
+                    .ok_or_else(|| CliError::UserNotFound(name.clone()))?
+                    .id,
+            ),
+            _ => None,
+        };
+        Ok(TaskFilter {
+            status: self.status.clone(),
+            query: (!self.text.is_empty()).then(|| self.text.join(" ")),
+            label_id,
+            priority: self.priority,
+            assignee_id,
+            unassigned: unassigned.then_some(true),
+            overdue: self.overdue.then_some(true),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filter_reads_keys_and_text() {
+        let filter = parse_filter(
+            "login label:hotfix status:in-review priority:HIGH assignee:none bug is:overdue",
+        )
+        .unwrap();
+        assert_eq!(filter.status, Some(TaskStatus::InReview));
+        assert_eq!(filter.label.as_deref(), Some("hotfix"));
+        assert_eq!(filter.priority, Some(TaskPriority::High));
+        assert_eq!(filter.assignee.as_deref(), Some("none"));
+        assert!(filter.overdue);
+        assert_eq!(filter.text, ["login", "bug"]);
+    }
+
+    #[test]
+    fn test_parse_filter_rejects_bad_terms() {
+        assert!(parse_filter("label:").unwrap_err().contains("has no value"));
+        assert!(
+            parse_filter("label:a label:b")
+                .unwrap_err()
+                .contains("given twice")
+        );
+        assert!(parse_filter("status:later").is_err());
+        assert!(
+            parse_filter("owner:alice")
+                .unwrap_err()
+                .contains("not a filter")
+        );
+        assert!(parse_filter("is:stale").is_err());
+    }
+
+    #[test]
+    fn test_parse_filter_empty_matches_everything() {
+        let filter = parse_filter("  ").unwrap();
+        assert!(filter.status.is_none() && filter.label.is_none() && !filter.overdue);
+        assert!(filter.text.is_empty());
+    }
+}
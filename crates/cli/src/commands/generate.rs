@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use clap_complete::Shell;
+
+use crate::error::CliError;
+
+#[derive(Args, Debug)]
+pub struct GenerateArgs {
+    /// Directory to write `completions/` and `man/` into; created if missing
+    #[arg(long, default_value = "target/vk")]
+    out: PathBuf,
+}
+
+/// Print `command`'s completion script for `shell`.
+pub fn completions(shell: Shell, mut command: clap::Command) {
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
+}
+
+/// Write `command`'s completion script for every supported shell, and a man page for it
+/// and each of its subcommands.
+pub fn run(args: GenerateArgs, mut command: clap::Command) -> Result<(), CliError> {
+    let name = command.get_name().to_string();
+    let completions = args.out.join("completions");
+    let man = args.out.join("man");
+    std::fs::create_dir_all(&completions)?;
+    std::fs::create_dir_all(&man)?;
+
+    for shell in Shell::value_variants() {
+        clap_complete::generate_to(*shell, &mut command, &name, &completions)?;
+    }
+    clap_mangen::generate_to(command, &man)?;
+
+    println!(
+        "Wrote completions to {} and man pages to {}",
+        completions.display(),
+        man.display()
+    );
+    Ok(())
+}
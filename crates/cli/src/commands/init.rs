@@ -0,0 +1,278 @@
+use std::path::Path;
+
+use db::{
+    DBService,
+    models::{
+        integration::{CreateIntegration, Integration, IntegrationProvider},
+        project::{CreateProject, Project},
+    },
+};
+use reqwest::{Client, Url};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    commands::{confirm, prompt},
+    config::{Config, Profile},
+    error::CliError,
+};
+
+/// What a YouTrack URL pasted from the browser points at.
+struct YouTrackLocation {
+    base_url: String,
+    board_id: Option<String>,
+    project: Option<String>,
+}
+
+/// Split a board (`.../agiles/<id>/...`) or project (`.../projects/<name>`) URL into the
+/// instance's base URL and what it names. Any other URL is taken as the base URL.
+fn parse_youtrack_url(value: &str) -> Result<YouTrackLocation, String> {
+    let url = Url::parse(value.trim()).map_err(|e| format!("'{value}' is not a URL: {e}"))?;
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+    let marker = segments
+        .iter()
+        .position(|segment| matches!(*segment, "agiles" | "projects"));
+    let prefix = &segments[..marker.unwrap_or(segments.len())];
+    let mut base_url = url.origin().ascii_serialization();
+    for segment in prefix {
+        base_url.push('/');
+        base_url.push_str(segment);
+    }
+    let named = |index: usize| segments.get(index + 1).map(|s| s.to_string());
+    Ok(match marker {
+        Some(index) if segments[index] == "agiles" => YouTrackLocation {
+            base_url,
+            board_id: named(index),
+            project: None,
+        },
+        Some(index) => YouTrackLocation {
+            base_url,
+            board_id: None,
+            project: named(index),
+        },
+        None => YouTrackLocation {
+            base_url,
+            board_id: None,
+            project: None,
+        },
+    })
+}
+
+#[derive(Deserialize)]
+struct YouTrackUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct YouTrackProject {
+    short_name: String,
+}
+
+#[derive(Deserialize)]
+struct YouTrackBoard {
+    name: String,
+    #[serde(default)]
+    projects: Vec<YouTrackProject>,
+}
+
+/// A GET against the YouTrack API, failing with the response body on an error status.
+async fn youtrack_get<T: for<'de> Deserialize<'de>>(
+    client: &Client,
+    base_url: &str,
+    path: &str,
+    token: &str,
+    query: &[(&str, &str)],
+) -> Result<T, String> {
+    let response = client
+        .get(format!("{base_url}/api/{path}"))
+        .bearer_auth(token)
+        .header("Accept", "application/json")
+        .query(query)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("YouTrack answered {status}: {body}"));
+    }
+    response.json().await.map_err(|e| e.to_string())
+}
+
+/// Check the token against the instance and read the projects the board or project URL
+/// points at, the way a sync would query them. Returns the integration's config.
+async fn verify_youtrack(
+    location: &YouTrackLocation,
+    token: &str,
+) -> Result<serde_json::Value, String> {
+    let client = Client::builder()
+        .user_agent(concat!("vk/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let base_url = location.base_url.as_str();
+    let user: YouTrackUser =
+        youtrack_get(&client, base_url, "users/me", token, &[("fields", "login")]).await?;
+    println!("  Token accepted for {}", user.login);
+
+    let projects = match (&location.board_id, &location.project) {
+        (Some(board_id), _) => {
+            let board: YouTrackBoard = youtrack_get(
+                &client,
+                base_url,
+                &format!("agiles/{board_id}"),
+                token,
+                &[("fields", "name,projects(shortName)")],
+            )
+            .await?;
+            println!("  Found board {}", board.name);
+            board
+                .projects
+                .into_iter()
+                .map(|project| project.short_name)
+                .collect()
+        }
+        (None, Some(project)) => vec![project.clone()],
+        (None, None) => {
+            vec![prompt("YouTrack project short name", None).map_err(|e| e.to_string())?]
+        }
+    };
+    if projects.is_empty() {
+        return Err("the board has no projects".to_string());
+    }
+
+    let query = format!(
+        "project: {}",
+        projects
+            .iter()
+            .map(|project| format!("{{{project}}}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let _: Vec<serde_json::Value> = youtrack_get(
+        &client,
+        base_url,
+        "issues",
+        token,
+        &[("query", query.as_str()), ("fields", "id"), ("$top", "1")],
+    )
+    .await?;
+    println!("  Issues of {} are readable", projects.join(", "));
+
+    Ok(if let [project] = projects.as_slice() {
+        json!({ "project": project })
+    } else {
+        json!({ "project": projects[0], "query": query })
+    })
+}
+
+/// Ask for a YouTrack board and token until they check out, or the user gives up.
+async fn setup_youtrack(db: &DBService, project: &Project) -> Result<Option<Profile>, CliError> {
+    loop {
+        let url = prompt(
+            "YouTrack board URL, e.g. https://example.youtrack.cloud/agiles/123-4",
+            None,
+        )?;
+        let location = match parse_youtrack_url(&url) {
+            Ok(location) => location,
+            Err(message) => {
+                eprintln!("  {message}");
+                continue;
+            }
+        };
+        let token = rpassword::prompt_password("YouTrack permanent token: ")?;
+        match verify_youtrack(&location, token.trim()).await {
+            Ok(config) => {
+                let name = prompt("Integration name", Some("YouTrack"))?;
+                let integration = Integration::create(
+                    &db.pool,
+                    &CreateIntegration {
+                        project_id: project.id,
+                        provider: IntegrationProvider::YouTrack,
+                        name: name.clone(),
+                        base_url: location.base_url,
+                        config: Some(config),
+                        secrets: Some([("token".to_string(), token.trim().to_string())].into()),
+                        enabled: Some(true),
+                        field_mapping: None,
+                    },
+                )
+                .await?;
+                println!(
+                    "Created integration {} ({})",
+                    integration.name, integration.id
+                );
+                return Ok(Some(Profile {
+                    integration: name,
+                    ..Default::default()
+                }));
+            }
+            Err(message) => {
+                eprintln!("  Couldn't verify the board: {message}");
+                if !confirm("Try again?", true)? {
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
+
+/// Set up a new install: the database, a first project, optionally a YouTrack
+/// integration, and a config file pointing `vk` at them.
+pub async fn run(config_path: Option<&Path>) -> Result<(), CliError> {
+    // A config that exists but doesn't parse is better reported than overwritten
+    let mut config = Config::load(config_path)?;
+    let db = DBService::new().await?;
+    println!(
+        "Database ready at {}",
+        db::backup::database_path().display()
+    );
+
+    let projects = Project::find_all(&db.pool).await?;
+    let name = prompt(
+        "Project name",
+        Some(projects.first().map_or("My project", |p| p.name.as_str())),
+    )?;
+    let existing = projects
+        .iter()
+        .find(|project| project.name.eq_ignore_ascii_case(&name));
+    let project = match existing {
+        Some(project) if confirm(&format!("Use the existing project {}?", project.name), true)? => {
+            project.clone()
+        }
+        _ => {
+            let project = Project::create(
+                &db.pool,
+                &CreateProject {
+                    name,
+                    repositories: Vec::new(),
+                    team_id: None,
+                },
+                Uuid::new_v4(),
+            )
+            .await?;
+            println!("Created project {} ({})", project.name, project.id);
+            project
+        }
+    };
+    config.project = Some(project.name.clone());
+
+    let mut syncing = false;
+    if confirm("Sync issues from YouTrack?", false)?
+        && let Some(profile) = setup_youtrack(&db, &project).await?
+    {
+        config.profiles.insert("youtrack".to_string(), profile);
+        syncing = true;
+    }
+
+    let path = config.save(config_path)?;
+    println!("Wrote {}", path.display());
+    if syncing {
+        println!("Start the server, then run `vk sync --profile youtrack`");
+    }
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use clap::{Args, Subcommand};
+use db::models::integration::IntegrationCheck;
+
+use crate::{
+    backend::Backend, commands::sync::resolve_integration, config::Profile, error::CliError,
+    output::OutputFormat,
+};
+
+#[derive(Subcommand, Debug)]
+pub enum IntegrationsCommand {
+    /// Test integrations' credentials and project access, e.g. in CI before enabling
+    /// scheduled syncs; exits non-zero when any check fails
+    Check(CheckArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct CheckArgs {
+    /// Integration id or name; every integration on the server when not given
+    integration: Option<String>,
+    /// Profile from the config file naming the integration, and its server
+    #[arg(long, conflicts_with = "integration")]
+    pub profile: Option<String>,
+}
+
+fn print_check(check: &IntegrationCheck) {
+    let state = if check.enabled { "" } else { "  (disabled)" };
+    println!(
+        "{}  {:24}  {}{}",
+        if check.passed { "PASS" } else { "FAIL" },
+        check.name,
+        check.provider,
+        state
+    );
+    for step in &check.steps {
+        match &step.error {
+            Some(error) => println!("      {} failed: {error}", step.name),
+            None => println!("      {} ok", step.name),
+        }
+    }
+}
+
+async fn check(
+    backend: &Backend,
+    args: CheckArgs,
+    profile: Option<&Profile>,
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    let Backend::Api(client) = backend else {
+        return Err(CliError::NeedsServer("vk integrations check"));
+    };
+    let reference = args
+        .integration
+        .as_deref()
+        .or(profile.map(|profile| profile.integration.as_str()));
+    let integrations = match reference {
+        Some(reference) => vec![resolve_integration(client, reference).await?],
+        None => client.list_integrations().await?,
+    };
+
+    let mut checks = Vec::new();
+    for integration in &integrations {
+        checks.push(client.check_integration(integration.id).await?);
+    }
+    output.print(&checks, || {
+        if checks.is_empty() {
+            println!("No integrations");
+        }
+        checks.iter().for_each(print_check);
+    })?;
+
+    let failed = checks.iter().filter(|check| !check.passed).count();
+    if failed > 0 {
+        return Err(CliError::ChecksFailed {
+            failed,
+            checked: checks.len(),
+        });
+    }
+    Ok(())
+}
+
+pub async fn run(
+    backend: &Backend,
+    command: IntegrationsCommand,
+    profile: Option<&Profile>,
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    match command {
+        IntegrationsCommand::Check(args) => check(backend, args, profile, output).await,
+    }
+}
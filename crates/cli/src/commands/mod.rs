@@ -0,0 +1,75 @@
+pub mod add;
+pub mod archive;
+pub mod backup;
+pub mod board;
+pub mod db;
+pub mod filter;
+pub mod generate;
+pub mod init;
+pub mod integrations;
+pub mod project;
+pub mod sync;
+pub mod task;
+
+use std::io::{self, BufRead, Write};
+
+use db::models::project::Project;
+use uuid::Uuid;
+
+use crate::{backend::Backend, error::CliError};
+
+/// The project `reference` names: its id, its name (ignoring case), or the start of
+/// either when only one project matches.
+pub async fn resolve_project(backend: &Backend, reference: &str) -> Result<Project, CliError> {
+    let projects = backend.list_projects().await?;
+    if let Ok(id) = Uuid::parse_str(reference) {
+        return projects
+            .into_iter()
+            .find(|project| project.id == id)
+            .ok_or_else(|| CliError::ProjectNotFound(reference.to_string()));
+    }
+    let wanted = reference.to_lowercase();
+    if let Some(project) = projects
+        .iter()
+        .find(|project| project.name.to_lowercase() == wanted)
+    {
+        return Ok(project.clone());
+    }
+    let mut matches = projects.into_iter().filter(|project| {
+        project.name.to_lowercase().starts_with(&wanted)
+            || project.id.to_string().starts_with(&wanted)
+    });
+    match (matches.next(), matches.next()) {
+        (Some(project), None) => Ok(project),
+        (Some(_), Some(_)) => Err(CliError::AmbiguousProject(reference.to_string())),
+        (None, _) => Err(CliError::ProjectNotFound(reference.to_string())),
+    }
+}
+
+/// Ask on the terminal and read a line, giving `default` when it's left empty. Questions
+/// go to stderr, so they stay out of JSON output.
+pub fn prompt(label: &str, default: Option<&str>) -> Result<String, CliError> {
+    match default {
+        Some(default) => eprint!("{label} [{default}]: "),
+        None => eprint!("{label}: "),
+    }
+    io::stderr().flush()?;
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "input ended").into());
+    }
+    let line = line.trim();
+    Ok(match default {
+        Some(default) if line.is_empty() => default.to_string(),
+        _ => line.to_string(),
+    })
+}
+
+pub fn confirm(question: &str, default: bool) -> Result<bool, CliError> {
+    let hint = if default { "Y/n" } else { "y/N" };
+    let answer = prompt(&format!("{question} [{hint}]"), None)?;
+    Ok(match answer.to_lowercase().as_str() {
+        "" => default,
+        answer => answer.starts_with('y'),
+    })
+}
@@ -0,0 +1,36 @@
+use clap::Subcommand;
+
+use crate::{
+    backend::Backend,
+    error::CliError,
+    output::{OutputFormat, Table},
+};
+
+#[derive(Subcommand, Debug)]
+pub enum ProjectCommand {
+    /// List the projects you can see
+    List,
+}
+
+pub async fn run(
+    backend: &Backend,
+    command: ProjectCommand,
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    match command {
+        ProjectCommand::List => {
+            let projects = backend.list_projects().await?;
+            output.print(&projects, || {
+                if projects.is_empty() {
+                    println!("No projects");
+                    return;
+                }
+                let mut table = Table::new(["ID", "NAME"]);
+                for project in &projects {
+                    table.row([project.id.to_string(), project.name.clone()]);
+                }
+                print!("{table}");
+            })
+        }
+    }
+}
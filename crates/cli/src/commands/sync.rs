@@ -0,0 +1,353 @@
+use std::time::{Duration, Instant};
+
+use clap::Args;
+use db::models::{
+    integration::IntegrationResponse,
+    sync_job::{SyncJob, SyncJobStatus},
+};
+use futures::{StreamExt, stream};
+use rand::Rng;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    api::ApiClient,
+    backend::Backend,
+    config::Profile,
+    error::CliError,
+    output::{OutputFormat, Table},
+};
+
+/// How often a running job is checked on
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Args, Debug)]
+pub struct SyncArgs {
+    /// Integration id or name
+    #[arg(required_unless_present_any = ["profile", "all"])]
+    integration: Option<String>,
+    /// Profile from the config file naming the integration, and its server
+    #[arg(long, conflicts_with = "integration")]
+    pub profile: Option<String>,
+    /// Sync every enabled integration of every project, then print a summary
+    #[arg(long, conflicts_with_all = ["integration", "profile", "watch"])]
+    all: bool,
+    /// How many integrations `--all` syncs at once
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..), requires = "all")]
+    parallel: u16,
+    /// Keep running, syncing again every `--interval`
+    #[arg(long)]
+    watch: bool,
+    /// Time between runs in watch mode, e.g. `90s`, `5m` or `1h`, 5m by default; each
+    /// wait varies by up to a tenth either way so several daemons don't hit the provider
+    /// together
+    #[arg(long, value_parser = parse_interval, requires = "watch")]
+    interval: Option<Duration>,
+}
+
+/// A number of seconds, minutes, hours or days: `30s`, `5m`, `1h`, `1d`, or plain seconds.
+pub fn parse_interval(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let invalid = || format!("'{value}' is not an interval; use e.g. 30s, 5m or 1h");
+    let (number, unit) = match value.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => value.split_at(index),
+        None => (value, "s"),
+    };
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    match number.parse::<u64>() {
+        Ok(count) if count > 0 => Ok(Duration::from_secs(count * seconds)),
+        _ => Err(invalid()),
+    }
+}
+
+fn jittered(interval: Duration) -> Duration {
+    let spread = interval.as_secs_f64() / 10.0;
+    let offset = rand::thread_rng().gen_range(-spread..=spread);
+    Duration::from_secs_f64((interval.as_secs_f64() + offset).max(1.0))
+}
+
+pub async fn resolve_integration(
+    client: &ApiClient,
+    reference: &str,
+) -> Result<IntegrationResponse, CliError> {
+    let integrations = client.list_integrations().await?;
+    if let Ok(id) = Uuid::parse_str(reference) {
+        return integrations
+            .into_iter()
+            .find(|integration| integration.id == id)
+            .ok_or_else(|| CliError::IntegrationNotFound(reference.to_string()));
+    }
+    let mut matches = integrations
+        .into_iter()
+        .filter(|integration| integration.name.eq_ignore_ascii_case(reference));
+    match (matches.next(), matches.next()) {
+        (Some(integration), None) => Ok(integration),
+        (Some(_), Some(_)) => Err(CliError::AmbiguousIntegration(reference.to_string())),
+        (None, _) => Err(CliError::IntegrationNotFound(reference.to_string())),
+    }
+}
+
+/// Queue a run and wait for the server to finish it.
+async fn sync_once(client: &ApiClient, integration_id: Uuid) -> Result<SyncJob, CliError> {
+    let mut job = client.trigger_sync(integration_id).await?;
+    while matches!(job.status, SyncJobStatus::Queued | SyncJobStatus::Running) {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        job = client.get_sync_job(job.id).await?;
+    }
+    if job.status == SyncJobStatus::Failed {
+        return Err(CliError::SyncFailed {
+            job_id: job.id,
+            message: job.error.unwrap_or_else(|| "no error given".to_string()),
+        });
+    }
+    Ok(job)
+}
+
+fn count(job: &SyncJob, field: &str) -> u64 {
+    job.result
+        .as_ref()
+        .and_then(|result| result.0.get(field))
+        .and_then(|value| value.as_u64())
+        .unwrap_or(0)
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to install Ctrl+C handler: {e}");
+        }
+    };
+
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let terminate = async {
+            if let Ok(mut sigterm) = signal(SignalKind::terminate()) {
+                sigterm.recv().await;
+            } else {
+                tracing::error!("Failed to install SIGTERM handler");
+                std::future::pending::<()>().await;
+            }
+        };
+
+        tokio::select! {
+            _ = ctrl_c => {},
+            _ = terminate => {},
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        ctrl_c.await;
+    }
+}
+
+async fn watch(
+    client: &ApiClient,
+    integration: &IntegrationResponse,
+    interval: Duration,
+) -> Result<(), CliError> {
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+    tracing::info!(
+        integration = %integration.name,
+        integration_id = %integration.id,
+        interval_secs = interval.as_secs(),
+        "watching integration"
+    );
+
+    loop {
+        let started = Instant::now();
+        tokio::select! {
+            result = sync_once(client, integration.id) => match result {
+                Ok(job) => tracing::info!(
+                    integration = %integration.name,
+                    job_id = %job.id,
+                    duration_ms = started.elapsed().as_millis() as u64,
+                    fetched = count(&job, "fetched"),
+                    created = count(&job, "created"),
+                    updated = count(&job, "updated"),
+                    unchanged = count(&job, "unchanged"),
+                    failed = count(&job, "failed"),
+                    "sync run finished"
+                ),
+                // A failed run doesn't end the daemon; the next one may well succeed
+                Err(e) => tracing::error!(
+                    integration = %integration.name,
+                    duration_ms = started.elapsed().as_millis() as u64,
+                    error = %e,
+                    "sync run failed"
+                ),
+            },
+            _ = &mut shutdown => {
+                // The queued job belongs to the server, which finishes it on its own
+                tracing::info!(integration = %integration.name, "stopping during a sync run");
+                return Ok(());
+            }
+        }
+
+        let delay = jittered(interval);
+        tracing::debug!(next_run_secs = delay.as_secs(), "waiting for the next run");
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = &mut shutdown => {
+                tracing::info!(integration = %integration.name, "stopping");
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// One row of the `--all` summary.
+#[derive(Serialize)]
+struct BatchResult {
+    project: String,
+    integration: String,
+    duration_ms: u64,
+    job: Option<SyncJob>,
+    error: Option<String>,
+}
+
+/// Sync every enabled integration, `parallel` at a time, and print how each went.
+async fn sync_all(
+    client: &ApiClient,
+    parallel: usize,
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    let projects = client.list_projects().await?;
+    let (integrations, disabled): (Vec<_>, Vec<_>) = client
+        .list_integrations()
+        .await?
+        .into_iter()
+        .partition(|integration| integration.enabled);
+
+    let results: Vec<BatchResult> = stream::iter(integrations)
+        .map(|integration| {
+            let project = projects
+                .iter()
+                .find(|project| project.id == integration.project_id)
+                .map_or_else(|| integration.project_id.to_string(), |p| p.name.clone());
+            async move {
+                let started = Instant::now();
+                let outcome = sync_once(client, integration.id).await;
+                BatchResult {
+                    project,
+                    integration: integration.name,
+                    duration_ms: started.elapsed().as_millis() as u64,
+                    error: outcome.as_ref().err().map(|e| e.to_string()),
+                    job: outcome.ok(),
+                }
+            }
+        })
+        .buffered(parallel)
+        .collect()
+        .await;
+
+    output.print(&results, || {
+        if results.is_empty() {
+            println!("No enabled integrations");
+            return;
+        }
+        let mut table = Table::new([
+            "PROJECT",
+            "INTEGRATION",
+            "RESULT",
+            "FETCHED",
+            "CREATED",
+            "UPDATED",
+            "UNCHANGED",
+            "FAILED",
+            "TIME",
+        ]);
+        for result in &results {
+            let mut row = vec![result.project.clone(), result.integration.clone()];
+            match &result.job {
+                Some(job) => {
+                    row.push("ok".to_string());
+                    row.extend(
+                        ["fetched", "created", "updated", "unchanged", "failed"]
+                            .map(|field| count(job, field).to_string()),
+                    );
+                }
+                None => row.extend(["failed", "-", "-", "-", "-", "-"].map(str::to_string)),
+            }
+            row.push(format!("{:.1}s", result.duration_ms as f64 / 1000.0));
+            table.row(row);
+        }
+        print!("{table}");
+    })?;
+
+    for result in &results {
+        if let Some(error) = &result.error {
+            eprintln!("{}: {error}", result.integration);
+        }
+    }
+    if !disabled.is_empty() {
+        output.note(format!("Skipped {} disabled integrations", disabled.len()));
+    }
+    let failed = results
+        .iter()
+        .filter(|result| result.error.is_some())
+        .count();
+    if failed > 0 {
+        return Err(CliError::SyncsFailed {
+            failed,
+            total: results.len(),
+        });
+    }
+    Ok(())
+}
+
+pub async fn run(
+    backend: &Backend,
+    args: SyncArgs,
+    profile: Option<&Profile>,
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    let Backend::Api(client) = backend else {
+        return Err(CliError::NeedsServer("vk sync"));
+    };
+    if args.all {
+        return sync_all(client, usize::from(args.parallel), output).await;
+    }
+    let reference = args
+        .integration
+        .as_deref()
+        .or(profile.map(|profile| profile.integration.as_str()))
+        .ok_or(CliError::NoIntegration)?;
+    let integration = resolve_integration(client, reference).await?;
+    if args.watch {
+        let interval = match (args.interval, profile.and_then(|p| p.interval.as_deref())) {
+            (Some(interval), _) => interval,
+            (None, Some(interval)) => {
+                parse_interval(interval).map_err(|message| CliError::InvalidProfile {
+                    name: args.profile.clone().unwrap_or_default(),
+                    message,
+                })?
+            }
+            (None, None) => DEFAULT_INTERVAL,
+        };
+        return watch(client, &integration, interval).await;
+    }
+
+    let job = sync_once(client, integration.id).await?;
+    output.print(&job, || {
+        println!(
+            "Synced {}: fetched {}, created {}, updated {}, unchanged {}, failed {}",
+            integration.name,
+            count(&job, "fetched"),
+            count(&job, "created"),
+            count(&job, "updated"),
+            count(&job, "unchanged"),
+            count(&job, "failed"),
+        );
+    })
+}
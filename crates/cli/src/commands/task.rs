@@ -0,0 +1,311 @@
+use std::{collections::HashMap, str::FromStr, time::Duration};
+
+use clap::Subcommand;
+use db::models::task::{
+    CreateTask, Task, TaskFilter, TaskPriority, TaskStatus, TaskWithAttemptStatus,
+};
+use uuid::Uuid;
+
+use crate::{
+    backend::{Backend, BulkOperation, BulkTaskResult},
+    commands::{
+        confirm,
+        filter::{FilterExpr, parse_filter},
+        resolve_project,
+        sync::parse_interval,
+    },
+    error::CliError,
+    output::{OutputFormat, Table},
+};
+
+#[derive(Subcommand, Debug)]
+pub enum TaskCommand {
+    /// List a project's tasks
+    List {
+        /// Project id or name
+        #[arg(short, long, env = "VK_PROJECT")]
+        project: Option<String>,
+        /// Only tasks with this status
+        #[arg(short, long, value_parser = parse_status)]
+        status: Option<TaskStatus>,
+    },
+    /// Create a task
+    Create {
+        title: String,
+        /// Project id or name
+        #[arg(short, long, env = "VK_PROJECT")]
+        project: Option<String>,
+        #[arg(short, long)]
+        description: Option<String>,
+        /// To do when not given
+        #[arg(short, long, value_parser = parse_status)]
+        status: Option<TaskStatus>,
+        /// low, medium, high or urgent
+        #[arg(long, value_parser = parse_priority)]
+        priority: Option<TaskPriority>,
+    },
+    /// Move a task to another status, and the matching board column
+    Move {
+        task_id: Uuid,
+        /// todo, in-progress, in-review, done or cancelled
+        #[arg(value_parser = parse_status)]
+        status: TaskStatus,
+    },
+    /// Mark a task done
+    Done { task_id: Uuid },
+    /// Move every task matching a filter to another status
+    BulkMove {
+        /// Project id or name
+        #[arg(short, long, env = "VK_PROJECT")]
+        project: Option<String>,
+        /// Terms such as `label:hotfix status:inreview priority:high assignee:alice` or
+        /// `is:overdue`; other words are looked for in titles and descriptions
+        #[arg(long, value_parser = parse_filter)]
+        filter: FilterExpr,
+        /// Status to move them to
+        #[arg(long, value_parser = parse_status)]
+        to: TaskStatus,
+        /// Only list the tasks that would move
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Move tasks that have been done for a while to the trash, or archive them
+    PurgeDone {
+        /// Project id or name
+        #[arg(short, long, env = "VK_PROJECT")]
+        project: Option<String>,
+        /// Only tasks finished longer ago than this, e.g. `30d` or `12h`
+        #[arg(long, value_parser = parse_interval)]
+        older_than: Duration,
+        /// Archive the tasks instead of moving them to the trash
+        #[arg(long)]
+        archive_only: bool,
+        /// Only list the tasks that would be purged
+        #[arg(long)]
+        dry_run: bool,
+        /// Don't ask before purging
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+/// Task statuses as people type them: `in-progress`, `in_progress` and `InProgress` all
+/// mean `inprogress`.
+pub fn parse_status(value: &str) -> Result<TaskStatus, String> {
+    let normalized: String = value
+        .chars()
+        .filter(|c| !matches!(c, '-' | '_' | ' '))
+        .collect::<String>()
+        .to_lowercase();
+    TaskStatus::from_str(&normalized).map_err(|_| {
+        format!("'{value}' is not a status; use todo, in-progress, in-review, done or cancelled")
+    })
+}
+
+pub fn parse_priority(value: &str) -> Result<TaskPriority, String> {
+    TaskPriority::from_str(&value.to_lowercase())
+        .map_err(|_| format!("'{value}' is not a priority; use low, medium, high or urgent"))
+}
+
+async fn project_id(backend: &Backend, project: Option<&str>) -> Result<Uuid, CliError> {
+    let project = project.ok_or(CliError::NoProject)?;
+    Ok(resolve_project(backend, project).await?.id)
+}
+
+fn task_table(tasks: &[TaskWithAttemptStatus]) -> Table {
+    let mut table = Table::new(["ID", "STATUS", "UPDATED", "TITLE"]);
+    for task in tasks {
+        table.row([
+            task.id.to_string(),
+            task.status.to_string(),
+            task.updated_at.format("%Y-%m-%d").to_string(),
+            task.title.clone(),
+        ]);
+    }
+    table
+}
+
+/// Report how a bulk change went, failing when any task was left unchanged.
+fn print_bulk_results(
+    results: &[BulkTaskResult],
+    titles: &HashMap<Uuid, String>,
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    output.print(results, || {
+        let mut table = Table::new(["ID", "RESULT", "TITLE"]);
+        for result in results {
+            let outcome = match &result.error {
+                Some(error) => format!("skipped: {error}"),
+                None => "ok".to_string(),
+            };
+            let title = titles.get(&result.task_id).cloned().unwrap_or_default();
+            table.row([result.task_id.to_string(), outcome, title]);
+        }
+        print!("{table}");
+    })?;
+    let failed = results.iter().filter(|result| !result.ok).count();
+    if failed > 0 {
+        return Err(CliError::TasksUnchanged {
+            failed,
+            total: results.len(),
+        });
+    }
+    Ok(())
+}
+
+fn print_task(task: &Task, output: OutputFormat) -> Result<(), CliError> {
+    output.print(task, || {
+        let mut table = Table::new(["ID", "STATUS", "TITLE"]);
+        table.row([
+            task.id.to_string(),
+            task.status.to_string(),
+            task.title.clone(),
+        ]);
+        print!("{table}");
+    })
+}
+
+pub async fn run(
+    backend: &Backend,
+    command: TaskCommand,
+    default_project: Option<&str>,
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    match command {
+        TaskCommand::List { project, status } => {
+            let project_id = project_id(backend, project.as_deref().or(default_project)).await?;
+            let tasks = backend.list_tasks(project_id, status).await?;
+            output.print(&tasks, || {
+                if tasks.is_empty() {
+                    println!("No tasks");
+                    return;
+                }
+                let mut table = Table::new(["ID", "STATUS", "PRIORITY", "TITLE"]);
+                for task in &tasks {
+                    table.row([
+                        task.id.to_string(),
+                        task.status.to_string(),
+                        task.priority.to_string(),
+                        task.title.clone(),
+                    ]);
+                }
+                print!("{table}");
+            })
+        }
+        TaskCommand::Create {
+            title,
+            project,
+            description,
+            status,
+            priority,
+        } => {
+            let project_id = project_id(backend, project.as_deref().or(default_project)).await?;
+            let mut payload = CreateTask::from_title_description(project_id, title, description);
+            payload.status = status.or(payload.status);
+            payload.priority = priority;
+            let task = backend.create_task(&payload).await?;
+            print_task(&task, output)
+        }
+        TaskCommand::Move { task_id, status } => {
+            let task = backend.set_task_status(task_id, status).await?;
+            print_task(&task, output)
+        }
+        TaskCommand::Done { task_id } => {
+            let task = backend.set_task_status(task_id, TaskStatus::Done).await?;
+            print_task(&task, output)
+        }
+        TaskCommand::BulkMove {
+            project,
+            filter,
+            to,
+            dry_run,
+        } => {
+            let project_id = project_id(backend, project.as_deref().or(default_project)).await?;
+            let filter = filter.resolve(backend, project_id).await?;
+            let tasks: Vec<_> = backend
+                .find_tasks(project_id, &filter)
+                .await?
+                .into_iter()
+                .filter(|task| task.status != to)
+                .collect();
+            if dry_run || tasks.is_empty() {
+                return output.print(&tasks, || {
+                    if tasks.is_empty() {
+                        println!("No tasks to move");
+                        return;
+                    }
+                    print!("{}", task_table(&tasks));
+                    println!("Would move {} tasks to {to}", tasks.len());
+                });
+            }
+            let task_ids: Vec<Uuid> = tasks.iter().map(|task| task.id).collect();
+            let titles = tasks
+                .into_iter()
+                .map(|task| (task.id, task.task.title))
+                .collect();
+            let results = backend
+                .bulk_update(&task_ids, &BulkOperation::SetStatus { status: to })
+                .await?;
+            print_bulk_results(&results, &titles, output)
+        }
+        TaskCommand::PurgeDone {
+            project,
+            older_than,
+            archive_only,
+            dry_run,
+            yes,
+        } => {
+            let project_id = project_id(backend, project.as_deref().or(default_project)).await?;
+            let cutoff = chrono::Duration::from_std(older_than)
+                .ok()
+                .and_then(|age| chrono::Utc::now().checked_sub_signed(age));
+            let filter = TaskFilter {
+                status: Some(TaskStatus::Done),
+                ..Default::default()
+            };
+            let done: Vec<_> = backend.find_tasks(project_id, &filter).await?;
+            let done_ids: Vec<Uuid> = done.iter().map(|task| task.id).collect();
+            let done_at = backend.done_at(project_id, &done_ids).await?;
+            // Tasks finished before their activity was recorded fall back to their last change
+            let tasks: Vec<_> = done
+                .into_iter()
+                .filter(|task| {
+                    let finished = done_at.get(&task.id).copied().unwrap_or(task.updated_at);
+                    cutoff.is_some_and(|cutoff| finished < cutoff)
+                })
+                .collect();
+            let action = if archive_only {
+                "archive"
+            } else {
+                "move to the trash"
+            };
+            if dry_run || tasks.is_empty() {
+                return output.print(&tasks, || {
+                    if tasks.is_empty() {
+                        println!("No done tasks older than that");
+                        return;
+                    }
+                    print!("{}", task_table(&tasks));
+                    println!("Would {action} {} tasks", tasks.len());
+                });
+            }
+            let question = format!("{} done tasks: {action}?", tasks.len());
+            if !yes && !confirm(&question, false)? {
+                return Err(CliError::Cancelled);
+            }
+
+            let task_ids: Vec<Uuid> = tasks.iter().map(|task| task.id).collect();
+            let titles = tasks
+                .into_iter()
+                .map(|task| (task.id, task.task.title))
+                .collect();
+            let operation = if archive_only {
+                BulkOperation::Archive
+            } else {
+                BulkOperation::Delete
+            };
+            let results = backend.bulk_update(&task_ids, &operation).await?;
+            print_bulk_results(&results, &titles, output)
+        }
+    }
+}
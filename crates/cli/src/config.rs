@@ -0,0 +1,95 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::CliError;
+
+/// Defaults for `vk`, read from `~/.config/vibe-kanban/config.toml` or `--config`.
+/// Command-line flags and environment variables win over anything set here.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Config {
+    /// Server to talk to instead of the one running on this machine
+    #[serde(default)]
+    pub server: Option<String>,
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Project id or name used when a command isn't given `--project`
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Named integrations to sync, as `[profiles.<name>]` tables
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// An integration to sync with `vk sync --profile <name>`, and the server holding it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Profile {
+    /// Integration id or name on the server
+    pub integration: String,
+    #[serde(default)]
+    pub server: Option<String>,
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Time between runs with `--watch`, e.g. `10m`
+    #[serde(default)]
+    pub interval: Option<String>,
+}
+
+impl Config {
+    /// Get the path to the config file (~/.config/vibe-kanban/config.toml)
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|p| p.join("vibe-kanban").join("config.toml"))
+    }
+
+    /// Load the config at `path`, or the default one. A missing default file means no
+    /// config; a missing `--config` file, or one that doesn't parse, is an error.
+    pub fn load(path: Option<&Path>) -> Result<Self, CliError> {
+        let (path, explicit) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match Self::default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Self::default()),
+            },
+        };
+        if !explicit && !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(&path).map_err(|e| CliError::Config {
+            path: path.clone(),
+            message: e.to_string(),
+        })?;
+        toml::from_str(&contents).map_err(|e| CliError::Config {
+            path,
+            message: e.to_string(),
+        })
+    }
+
+    /// Write the config to `path`, or the default one, creating its directory. Returns
+    /// where it was written.
+    pub fn save(&self, path: Option<&Path>) -> Result<PathBuf, CliError> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => Self::default_path().ok_or(CliError::NoConfigDir)?,
+        };
+        let error = |message: String| CliError::Config {
+            path: path.clone(),
+            message,
+        };
+        let contents = toml::to_string_pretty(self).map_err(|e| error(e.to_string()))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| error(e.to_string()))?;
+        }
+        std::fs::write(&path, contents).map_err(|e| error(e.to_string()))?;
+        Ok(path)
+    }
+
+    pub fn profile(&self, name: &str) -> Result<&Profile, CliError> {
+        self.profiles
+            .get(name)
+            .ok_or_else(|| CliError::ProfileNotFound(name.to_string()))
+    }
+}
@@ -0,0 +1,115 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("Could not reach the Vibe Kanban server at {url}: {source}")]
+    Connect {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    #[error("Could not find a running Vibe Kanban server. Start it, or pass --server")]
+    ServerNotFound,
+
+    #[error("The server refused the request ({status}): {message}")]
+    Api { status: u16, message: String },
+
+    #[error("Unexpected response from the server: {0}")]
+    InvalidResponse(String),
+
+    #[error("No project matches '{0}'")]
+    ProjectNotFound(String),
+
+    #[error("'{0}' matches several projects; use the project's id")]
+    AmbiguousProject(String),
+
+    #[error(
+        "No project given. Pass --project, set VK_PROJECT, or set `project` in the config file"
+    )]
+    NoProject,
+
+    #[error("No integration matches '{0}'")]
+    IntegrationNotFound(String),
+
+    #[error("'{0}' matches several integrations; use the integration's id")]
+    AmbiguousIntegration(String),
+
+    #[error("Sync run {job_id} failed: {message}")]
+    SyncFailed { job_id: uuid::Uuid, message: String },
+
+    #[error("{failed} of {total} syncs failed")]
+    SyncsFailed { failed: usize, total: usize },
+
+    #[error("{failed} of {checked} integration checks failed")]
+    ChecksFailed { failed: usize, checked: usize },
+
+    #[error("`{0}` needs a server; drop --local")]
+    NeedsServer(&'static str),
+
+    #[error("Invalid config file {}: {message}", path.display())]
+    Config {
+        path: std::path::PathBuf,
+        message: String,
+    },
+
+    #[error("No config directory on this system; pass --config")]
+    NoConfigDir,
+
+    #[error("No profile named '{0}' in the config file")]
+    ProfileNotFound(String),
+
+    #[error("Invalid profile '{name}': {message}")]
+    InvalidProfile { name: String, message: String },
+
+    #[error("No integration given. Pass one, or --profile")]
+    NoIntegration,
+
+    #[error(transparent)]
+    Import(#[from] db::models::project_archive::ArchiveImportError),
+
+    #[error(transparent)]
+    QuickAdd(#[from] db::models::quick_add::QuickAddError),
+
+    #[error(transparent)]
+    Integration(#[from] db::models::integration::IntegrationError),
+
+    #[error("Could not read the archive: {0}")]
+    InvalidArchive(String),
+
+    #[error(transparent)]
+    Migration(#[from] db::migrations::MigrationError),
+
+    #[error("No migrations directory at {}; pass --dir", .0.display())]
+    MigrationsDirNotFound(std::path::PathBuf),
+
+    #[error(transparent)]
+    Backup(#[from] db::backup::BackupError),
+
+    #[error("A Vibe Kanban server is running on this machine. Stop it first, or pass --force")]
+    ServerRunning,
+
+    #[error("No task with id {0}")]
+    TaskNotFound(uuid::Uuid),
+
+    #[error("No label named '{0}' in the project")]
+    LabelNotFound(String),
+
+    #[error("No user named '{0}'")]
+    UserNotFound(String),
+
+    #[error("{failed} of {total} tasks were left unchanged")]
+    TasksUnchanged { failed: usize, total: usize },
+
+    #[error("Cancelled")]
+    Cancelled,
+
+    #[error("Couldn't write JSON output: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Local database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
@@ -0,0 +1,175 @@
+mod api;
+mod backend;
+mod commands;
+mod config;
+mod error;
+mod output;
+
+use std::path::PathBuf;
+
+use backend::Backend;
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use commands::{
+    add::AddArgs,
+    archive::{ExportArgs, ImportArgs},
+    backup::{BackupArgs, RestoreArgs},
+    board::BoardArgs,
+    db::DbCommand,
+    generate::GenerateArgs,
+    integrations::IntegrationsCommand,
+    project::ProjectCommand,
+    sync::SyncArgs,
+    task::TaskCommand,
+};
+use config::Config;
+use error::CliError;
+use output::OutputFormat;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Parser, Debug)]
+#[command(name = "vk")]
+#[command(about = "Manage a Vibe Kanban board from the terminal")]
+#[command(version)]
+struct Args {
+    /// Server to talk to; defaults to the one running on this machine
+    #[arg(long, global = true, env = "VK_SERVER_URL")]
+    server: Option<String>,
+
+    /// API key, for servers that require one
+    #[arg(long, global = true, env = "VK_API_KEY", hide_env_values = true)]
+    api_key: Option<String>,
+
+    /// Config file to read instead of ~/.config/vibe-kanban/config.toml
+    #[arg(long, global = true, env = "VK_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Work on this machine's database directly instead of through a server
+    #[arg(long, global = true, conflicts_with = "server")]
+    local: bool,
+
+    /// How to print results: aligned tables, JSON for scripts, or nothing
+    #[arg(long, global = true, value_enum, default_value_t, env = "VK_OUTPUT")]
+    output: OutputFormat,
+
+    /// Enable verbose output
+    #[arg(short, long, global = true, default_value_t = false)]
+    verbose: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Set up the database, a first project and the config file, step by step
+    Init,
+    /// Open the board in the terminal
+    Board(BoardArgs),
+    /// List, create and move tasks
+    #[command(subcommand)]
+    Task(TaskCommand),
+    /// Create a task from one line, e.g. 'Fix login bug #bug @alice !high ^friday'
+    Add(AddArgs),
+    /// List projects
+    #[command(subcommand)]
+    Project(ProjectCommand),
+    /// Save a project as an archive file
+    Export(ExportArgs),
+    /// Create a project from an archive file
+    Import(ImportArgs),
+    /// Sync an integration now, keep syncing it with --watch, or sync them all with --all
+    Sync(SyncArgs),
+    /// Check integrations before syncing them
+    #[command(subcommand)]
+    Integrations(IntegrationsCommand),
+    /// Copy this machine's database to a file, safely while the server runs
+    Backup(BackupArgs),
+    /// Replace this machine's database with a backup
+    Restore(RestoreArgs),
+    /// Manage this machine's database migrations
+    #[command(subcommand)]
+    Db(DbCommand),
+    /// Print the completion script for a shell
+    Completions { shell: Shell },
+    /// Write completion scripts for every shell, and man pages
+    #[command(name = "gen")]
+    Generate(GenerateArgs),
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse();
+
+    let filter = if args.verbose {
+        EnvFilter::new("debug")
+    } else {
+        // Sync runs are reported at info, so `vk sync --watch` logs them by default
+        EnvFilter::new("warn,cli=info")
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .init();
+
+    if let Err(e) = run(args).await {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(args: Args) -> Result<(), CliError> {
+    let output = args.output;
+    // These only describe the CLI or work on the local database, so they never connect to
+    // a server
+    let command = match args.command {
+        Command::Init => return commands::init::run(args.config.as_deref()).await,
+        Command::Backup(args) => return commands::backup::backup(args, output).await,
+        Command::Restore(args) => return commands::backup::restore(args, output).await,
+        Command::Db(command) => return commands::db::run(command, output).await,
+        Command::Completions { shell } => {
+            commands::generate::completions(shell, Args::command());
+            return Ok(());
+        }
+        Command::Generate(generate) => return commands::generate::run(generate, Args::command()),
+        command => command,
+    };
+
+    let config = Config::load(args.config.as_deref())?;
+    let profile_name = match &command {
+        Command::Sync(sync) => sync.profile.as_deref(),
+        Command::Integrations(IntegrationsCommand::Check(check)) => check.profile.as_deref(),
+        _ => None,
+    };
+    let profile = profile_name.map(|name| config.profile(name)).transpose()?;
+    // Flags and environment variables first, then the profile, then the config file
+    let server = args
+        .server
+        .or_else(|| profile.and_then(|profile| profile.server.clone()))
+        .or_else(|| config.server.clone());
+    let api_key = args
+        .api_key
+        .or_else(|| profile.and_then(|profile| profile.api_key.clone()))
+        .or_else(|| config.api_key.clone());
+
+    let backend = Backend::connect(args.local, server.as_deref(), api_key.as_deref()).await?;
+    let project = config.project.as_deref();
+    match command {
+        Command::Board(board) => commands::board::run(&backend, board, project).await,
+        Command::Task(command) => commands::task::run(&backend, command, project, output).await,
+        Command::Add(args) => commands::add::run(&backend, args, project, output).await,
+        Command::Project(command) => commands::project::run(&backend, command, output).await,
+        Command::Export(args) => commands::archive::export(&backend, args, project, output).await,
+        Command::Import(args) => commands::archive::import(&backend, args, output).await,
+        Command::Sync(args) => commands::sync::run(&backend, args, profile, output).await,
+        Command::Integrations(command) => {
+            commands::integrations::run(&backend, command, profile, output).await
+        }
+        Command::Init
+        | Command::Backup(_)
+        | Command::Restore(_)
+        | Command::Db(_)
+        | Command::Completions { .. }
+        | Command::Generate(_) => unreachable!(),
+    }
+}
@@ -0,0 +1,105 @@
+use std::fmt::{self, Display};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::error::CliError;
+
+/// How commands print their results, chosen with `--output`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned tables and short messages, for people
+    #[default]
+    Table,
+    /// The result as JSON on stdout, for scripts
+    Json,
+    /// Nothing but errors; the exit status tells how it went
+    Quiet,
+}
+
+impl OutputFormat {
+    /// Print a command's result: `human` writes it for people in table mode, JSON mode
+    /// serializes `value` instead, and quiet mode prints nothing.
+    pub fn print<T: Serialize + ?Sized>(
+        self,
+        value: &T,
+        human: impl FnOnce(),
+    ) -> Result<(), CliError> {
+        match self {
+            Self::Table => human(),
+            Self::Json => println!("{}", serde_json::to_string_pretty(value)?),
+            Self::Quiet => {}
+        }
+        Ok(())
+    }
+
+    /// A message that isn't part of the result, such as "No tasks"; table mode only.
+    pub fn note(self, message: impl Display) {
+        if self == Self::Table {
+            println!("{message}");
+        }
+    }
+}
+
+/// Rows printed with every column as wide as its widest cell.
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new<const N: usize>(headers: [&str; N]) -> Self {
+        Self {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn row<T: ToString>(&mut self, cells: impl IntoIterator<Item = T>) {
+        self.rows
+            .push(cells.into_iter().map(|cell| cell.to_string()).collect());
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        for row in std::iter::once(&self.headers).chain(&self.rows) {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .collect::<Vec<_>>()
+                .join("  ");
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_pads_columns_to_widest_cell() {
+        let mut table = Table::new(["ID", "TITLE"]);
+        table.row(["1", "Fix login"]);
+        table.row(["1234", "Ünïcode"]);
+        assert_eq!(
+            table.to_string(),
+            "ID    TITLE\n1     Fix login\n1234  Ünïcode\n"
+        );
+    }
+
+    #[test]
+    fn test_table_without_rows_prints_headers() {
+        let table = Table::new(["ID", "STATUS", "TITLE"]);
+        assert_eq!(table.to_string(), "ID  STATUS  TITLE\n");
+    }
+}
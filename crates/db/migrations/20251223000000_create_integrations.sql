@@ -0,0 +1,19 @@
+-- Provider configurations for external issue trackers (YouTrack, Jira, GitHub)
+-- config:  non-sensitive provider settings as a JSON object (remote project, query, ...)
+-- secrets: credentials as a JSON object of name -> value; never returned unredacted by the API
+CREATE TABLE integrations (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    provider    TEXT NOT NULL
+                   CHECK (provider IN ('youtrack','jira','github')),
+    name        TEXT NOT NULL CHECK (name != ''),
+    base_url    TEXT NOT NULL,
+    config      TEXT NOT NULL DEFAULT '{}',
+    secrets     TEXT NOT NULL DEFAULT '{}',
+    enabled     INTEGER NOT NULL DEFAULT 1,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_integrations_project_id ON integrations(project_id);
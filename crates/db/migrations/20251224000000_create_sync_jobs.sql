@@ -0,0 +1,35 @@
+-- Links local tasks to the remote issues they were imported from
+CREATE TABLE integration_links (
+    id                BLOB PRIMARY KEY,
+    integration_id    BLOB NOT NULL,
+    task_id           BLOB NOT NULL,
+    external_id       TEXT NOT NULL,
+    external_url      TEXT,
+    remote_updated_at TEXT,
+    created_at        TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at        TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (integration_id) REFERENCES integrations(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    UNIQUE (integration_id, external_id)
+);
+
+CREATE INDEX idx_integration_links_task_id ON integration_links(task_id);
+
+-- Queue of sync runs; rows left in 'running' by a crashed server are requeued on startup
+CREATE TABLE sync_jobs (
+    id              BLOB PRIMARY KEY,
+    integration_id  BLOB NOT NULL,
+    status          TEXT NOT NULL DEFAULT 'queued'
+                       CHECK (status IN ('queued','running','succeeded','failed')),
+    attempts        INTEGER NOT NULL DEFAULT 0,
+    result          TEXT,
+    error           TEXT,
+    started_at      TEXT,
+    finished_at     TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (integration_id) REFERENCES integrations(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_sync_jobs_status_created_at ON sync_jobs(status, created_at);
+CREATE INDEX idx_sync_jobs_integration_id_created_at ON sync_jobs(integration_id, created_at DESC);
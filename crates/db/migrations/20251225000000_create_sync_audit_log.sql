@@ -0,0 +1,19 @@
+-- Field-level record of every change a sync run applied to a task
+CREATE TABLE sync_audit_log (
+    id              BLOB PRIMARY KEY,
+    integration_id  BLOB NOT NULL,
+    run_id          BLOB,
+    task_id         BLOB NOT NULL,
+    provider        TEXT NOT NULL,
+    external_id     TEXT NOT NULL,
+    field           TEXT NOT NULL,
+    old_value       TEXT,
+    new_value       TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (integration_id) REFERENCES integrations(id) ON DELETE CASCADE,
+    FOREIGN KEY (run_id) REFERENCES sync_jobs(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_sync_audit_log_integration_id_created_at ON sync_audit_log(integration_id, created_at DESC);
+CREATE INDEX idx_sync_audit_log_task_id ON sync_audit_log(task_id);
+CREATE INDEX idx_sync_audit_log_run_id ON sync_audit_log(run_id);
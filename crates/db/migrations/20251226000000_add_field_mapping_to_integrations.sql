@@ -0,0 +1,3 @@
+-- Add field_mapping column to integrations table
+-- Declarative remote field -> task field rules applied to every synced issue
+ALTER TABLE integrations ADD COLUMN field_mapping TEXT NOT NULL DEFAULT '{}';
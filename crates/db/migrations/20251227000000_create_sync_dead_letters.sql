@@ -0,0 +1,20 @@
+-- Remote issues a sync run could not import, kept with their raw payload for retry
+-- external_id is NULL when the payload could not be parsed far enough to identify the issue
+CREATE TABLE sync_dead_letters (
+    id              BLOB PRIMARY KEY,
+    integration_id  BLOB NOT NULL,
+    run_id          BLOB,
+    external_id     TEXT,
+    payload         TEXT NOT NULL,
+    error           TEXT NOT NULL,
+    attempts        INTEGER NOT NULL DEFAULT 1,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (integration_id) REFERENCES integrations(id) ON DELETE CASCADE,
+    FOREIGN KEY (run_id) REFERENCES sync_jobs(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_sync_dead_letters_integration_id ON sync_dead_letters(integration_id, created_at DESC);
+CREATE UNIQUE INDEX idx_sync_dead_letters_external_id
+    ON sync_dead_letters(integration_id, external_id)
+    WHERE external_id IS NOT NULL;
@@ -0,0 +1,14 @@
+-- Dry-run sync plans: what a sync would create, update, close or skip as a conflict,
+-- stored with the remote payloads so the plan can later be applied as reviewed
+CREATE TABLE sync_plans (
+    id              BLOB PRIMARY KEY,
+    integration_id  BLOB NOT NULL,
+    status          TEXT NOT NULL DEFAULT 'pending'
+                       CHECK (status IN ('pending','applied','discarded')),
+    items           TEXT NOT NULL DEFAULT '[]',
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (integration_id) REFERENCES integrations(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_sync_plans_integration_id_created_at ON sync_plans(integration_id, created_at DESC);
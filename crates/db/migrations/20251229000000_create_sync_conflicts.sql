@@ -0,0 +1,26 @@
+-- Remote field values as of the last sync, the common base for telling local edits,
+-- remote edits and conflicting edits apart. NULL for links created before this column.
+ALTER TABLE integration_links ADD COLUMN remote_values TEXT;
+
+-- Fields edited both locally and remotely since the last sync, awaiting a manual decision
+CREATE TABLE sync_conflicts (
+    id              BLOB PRIMARY KEY,
+    integration_id  BLOB NOT NULL,
+    task_id         BLOB NOT NULL,
+    external_id     TEXT NOT NULL,
+    fields          TEXT NOT NULL DEFAULT '[]',
+    status          TEXT NOT NULL DEFAULT 'open'
+                       CHECK (status IN ('open','resolved')),
+    resolution      TEXT CHECK (resolution IN ('local','remote')),
+    resolved_at     TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (integration_id) REFERENCES integrations(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX idx_sync_conflicts_open
+    ON sync_conflicts(integration_id, external_id)
+    WHERE status = 'open';
+CREATE INDEX idx_sync_conflicts_status_created_at ON sync_conflicts(status, created_at DESC);
+CREATE INDEX idx_sync_conflicts_task_id ON sync_conflicts(task_id);
@@ -0,0 +1,19 @@
+-- Discussion on tasks, written locally or imported from an integration
+-- integration_id/external_id identify imported comments so re-imports update in place
+CREATE TABLE task_comments (
+    id              BLOB PRIMARY KEY,
+    task_id         BLOB NOT NULL,
+    author          TEXT NOT NULL,
+    body            TEXT NOT NULL,
+    integration_id  BLOB,
+    external_id     TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (integration_id) REFERENCES integrations(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_task_comments_task_id ON task_comments(task_id, created_at);
+CREATE UNIQUE INDEX idx_task_comments_external_id
+    ON task_comments(integration_id, external_id)
+    WHERE external_id IS NOT NULL;
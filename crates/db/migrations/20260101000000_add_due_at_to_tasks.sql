@@ -0,0 +1,7 @@
+-- Add due_at column to tasks table
+-- Nullable: tasks without a deadline are never overdue
+ALTER TABLE tasks ADD COLUMN due_at TEXT;
+
+CREATE INDEX idx_tasks_project_id_due_at
+ON tasks (project_id, due_at)
+WHERE due_at IS NOT NULL;
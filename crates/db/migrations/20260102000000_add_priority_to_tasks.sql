@@ -0,0 +1,3 @@
+-- Add priority column to tasks table
+ALTER TABLE tasks ADD COLUMN priority TEXT NOT NULL DEFAULT 'medium'
+    CHECK (priority IN ('low','medium','high','urgent'));
@@ -0,0 +1,15 @@
+-- People tasks can be assigned to. Email, when set, is how assignees imported from
+-- external trackers are matched to local users.
+CREATE TABLE users (
+    id          BLOB PRIMARY KEY,
+    name        TEXT NOT NULL CHECK (name != ''),
+    email       TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE UNIQUE INDEX idx_users_email ON users(email COLLATE NOCASE) WHERE email IS NOT NULL;
+
+ALTER TABLE tasks ADD COLUMN assignee_id BLOB REFERENCES users(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_tasks_assignee_id ON tasks(assignee_id) WHERE assignee_id IS NOT NULL;
@@ -0,0 +1,13 @@
+-- Checklist items break a task down without creating separate board cards
+CREATE TABLE task_checklist_items (
+    id          BLOB PRIMARY KEY,
+    task_id     BLOB NOT NULL,
+    title       TEXT NOT NULL CHECK (title != ''),
+    done        BOOLEAN NOT NULL DEFAULT FALSE,
+    position    INTEGER NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_checklist_items_task_id ON task_checklist_items(task_id, position);
@@ -0,0 +1,15 @@
+-- Dependencies and relations between tasks. "blocked by" is stored as the reverse
+-- "blocks" link, so every dependency has a single row.
+CREATE TABLE task_links (
+    id              BLOB PRIMARY KEY,
+    source_task_id  BLOB NOT NULL,
+    target_task_id  BLOB NOT NULL,
+    kind            TEXT NOT NULL CHECK (kind IN ('blocks', 'relates_to')),
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (source_task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (target_task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    CHECK (source_task_id != target_task_id),
+    UNIQUE (source_task_id, target_task_id, kind)
+);
+
+CREATE INDEX idx_task_links_target_task_id ON task_links(target_task_id);
@@ -0,0 +1,23 @@
+-- Arbitrary files attached to a task. Unlike images these are owned by one task and
+-- never deduplicated, so deleting the task removes them.
+CREATE TABLE task_attachments (
+    id              BLOB PRIMARY KEY,
+    task_id         BLOB NOT NULL,
+    file_path       TEXT NOT NULL,
+    original_name   TEXT NOT NULL,
+    mime_type       TEXT NOT NULL,
+    size_bytes      INTEGER NOT NULL,
+    hash            TEXT NOT NULL,
+    integration_id  BLOB,
+    external_id     TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (integration_id) REFERENCES integrations(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_task_attachments_task_id ON task_attachments(task_id);
+
+CREATE UNIQUE INDEX idx_task_attachments_external
+    ON task_attachments(integration_id, external_id)
+    WHERE external_id IS NOT NULL;
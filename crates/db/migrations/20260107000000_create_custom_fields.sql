@@ -0,0 +1,30 @@
+-- Per-project custom field definitions and their values on tasks
+-- field_type: 'text' | 'number' | 'select' | 'date'
+-- options: JSON array of allowed values for 'select' fields, empty otherwise
+CREATE TABLE custom_fields (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    name        TEXT NOT NULL CHECK (name != ''),
+    field_type  TEXT NOT NULL CHECK (field_type IN ('text', 'number', 'select', 'date')),
+    options     TEXT NOT NULL DEFAULT '[]',
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX idx_custom_fields_project_name
+    ON custom_fields(project_id, name COLLATE NOCASE);
+
+-- value is stored normalized for its field's type: numbers in canonical form,
+-- dates as YYYY-MM-DD, select values spelled as in the field's options
+CREATE TABLE task_custom_field_values (
+    task_id     BLOB NOT NULL,
+    field_id    BLOB NOT NULL,
+    value       TEXT NOT NULL,
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_id, field_id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (field_id) REFERENCES custom_fields(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_custom_field_values_field ON task_custom_field_values(field_id, value);
@@ -0,0 +1,17 @@
+-- Per-project templates for creating tasks
+-- title_pattern / description: may contain {placeholders} filled in at creation
+-- label_ids: JSON array of label ids applied to created tasks
+-- checklist: JSON array of checklist item titles added to created tasks
+CREATE TABLE task_templates (
+    id             BLOB PRIMARY KEY,
+    project_id     BLOB NOT NULL,
+    name           TEXT NOT NULL CHECK (name != ''),
+    title_pattern  TEXT NOT NULL CHECK (title_pattern != ''),
+    description    TEXT,
+    label_ids      TEXT NOT NULL DEFAULT '[]',
+    checklist      TEXT NOT NULL DEFAULT '[]',
+    created_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, name)
+);
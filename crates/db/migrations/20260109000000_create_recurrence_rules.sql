@@ -0,0 +1,18 @@
+-- Recurrence rules (a subset of iCalendar RRULE) attached to either a task or a template.
+-- On a task, the next occurrence is created once the task is closed and the rule moves
+-- to it. On a template, a task is created from the template at next_run_at.
+CREATE TABLE recurrence_rules (
+    id           BLOB PRIMARY KEY,
+    task_id      BLOB UNIQUE,
+    template_id  BLOB UNIQUE,
+    rrule        TEXT NOT NULL CHECK (rrule != ''),
+    next_run_at  TEXT,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (template_id) REFERENCES task_templates(id) ON DELETE CASCADE,
+    CHECK ((task_id IS NULL) != (template_id IS NULL))
+);
+
+CREATE INDEX idx_recurrence_rules_next_run_at ON recurrence_rules(next_run_at)
+    WHERE next_run_at IS NOT NULL;
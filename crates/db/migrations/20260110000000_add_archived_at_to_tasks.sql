@@ -0,0 +1,7 @@
+-- Add archived_at column to tasks table
+-- Archived tasks are hidden from the board but kept, searchable, and can be restored
+ALTER TABLE tasks ADD COLUMN archived_at TEXT;
+
+CREATE INDEX idx_tasks_project_id_archived_at
+ON tasks (project_id, archived_at)
+WHERE archived_at IS NOT NULL;
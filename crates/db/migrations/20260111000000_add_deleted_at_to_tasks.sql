@@ -0,0 +1,8 @@
+-- Add deleted_at column to tasks table
+-- Deleting a task moves it to the trash; it is purged for good once the retention
+-- period has passed
+ALTER TABLE tasks ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX idx_tasks_deleted_at
+ON tasks (deleted_at)
+WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,56 @@
+-- Full-text index over task titles, descriptions and comments, kept in sync by triggers.
+-- Rows are keyed by task_id rather than rowid because VACUUM may renumber the rowids of
+-- tasks, which has no INTEGER PRIMARY KEY.
+CREATE VIRTUAL TABLE task_search USING fts5(
+    task_id UNINDEXED,
+    title,
+    description,
+    comments,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+INSERT INTO task_search (task_id, title, description, comments)
+SELECT t.id,
+       t.title,
+       COALESCE(t.description, ''),
+       COALESCE((SELECT group_concat(c.body, char(10)) FROM task_comments c WHERE c.task_id = t.id), '')
+  FROM tasks t;
+
+CREATE TRIGGER task_search_after_task_insert AFTER INSERT ON tasks
+BEGIN
+    INSERT INTO task_search (task_id, title, description, comments)
+    VALUES (NEW.id, NEW.title, COALESCE(NEW.description, ''), '');
+END;
+
+CREATE TRIGGER task_search_after_task_update AFTER UPDATE OF title, description ON tasks
+BEGIN
+    UPDATE task_search
+       SET title = NEW.title, description = COALESCE(NEW.description, '')
+     WHERE task_id = NEW.id;
+END;
+
+CREATE TRIGGER task_search_after_task_delete AFTER DELETE ON tasks
+BEGIN
+    DELETE FROM task_search WHERE task_id = OLD.id;
+END;
+
+CREATE TRIGGER task_search_after_comment_insert AFTER INSERT ON task_comments
+BEGIN
+    UPDATE task_search
+       SET comments = COALESCE((SELECT group_concat(body, char(10)) FROM task_comments WHERE task_id = NEW.task_id), '')
+     WHERE task_id = NEW.task_id;
+END;
+
+CREATE TRIGGER task_search_after_comment_update AFTER UPDATE OF body ON task_comments
+BEGIN
+    UPDATE task_search
+       SET comments = COALESCE((SELECT group_concat(body, char(10)) FROM task_comments WHERE task_id = NEW.task_id), '')
+     WHERE task_id = NEW.task_id;
+END;
+
+CREATE TRIGGER task_search_after_comment_delete AFTER DELETE ON task_comments
+BEGIN
+    UPDATE task_search
+       SET comments = COALESCE((SELECT group_concat(body, char(10)) FROM task_comments WHERE task_id = OLD.task_id), '')
+     WHERE task_id = OLD.task_id;
+END;
@@ -0,0 +1,18 @@
+-- Activity log of changes to a task: who (or what) changed which field, and how
+CREATE TABLE task_events (
+    id              BLOB PRIMARY KEY,
+    task_id         BLOB NOT NULL,
+    kind            TEXT NOT NULL
+                    CHECK (kind IN ('created','status_changed','edited','assignee_changed','archived','unarchived','deleted','restored')),
+    source          TEXT NOT NULL
+                    CHECK (source IN ('user','agent','sync','system')),
+    integration_id  BLOB,
+    field           TEXT,
+    old_value       TEXT,
+    new_value       TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (integration_id) REFERENCES integrations(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_task_events_task_id_created_at ON task_events(task_id, created_at);
@@ -0,0 +1,20 @@
+-- Snapshots of a task's title and description, one per edit, numbered from 1
+CREATE TABLE task_revisions (
+    id              BLOB PRIMARY KEY,
+    task_id         BLOB NOT NULL,
+    revision        INTEGER NOT NULL,
+    title           TEXT NOT NULL,
+    description     TEXT,
+    source          TEXT NOT NULL
+                    CHECK (source IN ('user','agent','sync','system')),
+    integration_id  BLOB,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (integration_id) REFERENCES integrations(id) ON DELETE SET NULL,
+    UNIQUE (task_id, revision)
+);
+
+-- Existing tasks start from their current text
+INSERT INTO task_revisions (id, task_id, revision, title, description, source, created_at)
+SELECT randomblob(16), id, 1, title, description, 'system', updated_at
+FROM tasks;
@@ -0,0 +1,20 @@
+-- Time spent on tasks. An entry without ended_at is a running timer.
+CREATE TABLE time_entries (
+    id          BLOB PRIMARY KEY,
+    task_id     BLOB NOT NULL,
+    user_id     BLOB NOT NULL,
+    started_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    ended_at    TEXT,
+    note        TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_time_entries_task_id ON time_entries(task_id, started_at);
+CREATE INDEX idx_time_entries_user_id ON time_entries(user_id, started_at);
+
+-- A user has at most one running timer per task
+CREATE UNIQUE INDEX idx_time_entries_running ON time_entries(task_id, user_id)
+    WHERE ended_at IS NULL;
@@ -0,0 +1,2 @@
+-- Task size in the project's estimation unit, e.g. story points
+ALTER TABLE tasks ADD COLUMN estimate REAL CHECK (estimate IS NULL OR estimate >= 0);
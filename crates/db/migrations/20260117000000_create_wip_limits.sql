@@ -0,0 +1,13 @@
+-- Work-in-progress limits per board column. Enforced limits reject moves into a full
+-- column; the others only warn.
+CREATE TABLE project_wip_limits (
+    project_id  BLOB NOT NULL,
+    status      TEXT NOT NULL
+                CHECK (status IN ('todo','inprogress','inreview','done','cancelled')),
+    max_tasks   INTEGER NOT NULL CHECK (max_tasks > 0),
+    enforced    BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (project_id, status),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
@@ -0,0 +1,50 @@
+-- Workflow columns a project's board is laid out in. Each column maps to one task
+-- status, its category, so a board can have several columns per status while status
+-- keeps meaning the same thing everywhere else.
+CREATE TABLE project_columns (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    name        TEXT NOT NULL CHECK (name != ''),
+    category    TEXT NOT NULL
+                CHECK (category IN ('todo','inprogress','inreview','done','cancelled')),
+    position    INTEGER NOT NULL DEFAULT 0,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_project_columns_project_id ON project_columns(project_id, position);
+
+-- Every project starts out with one column per status.
+INSERT INTO project_columns (id, project_id, name, category, position)
+SELECT randomblob(16), p.id, d.name, d.category, d.position
+FROM projects p
+CROSS JOIN (
+    SELECT 'To Do' AS name, 'todo' AS category, 0 AS position
+    UNION ALL SELECT 'In Progress', 'inprogress', 1
+    UNION ALL SELECT 'In Review', 'inreview', 2
+    UNION ALL SELECT 'Done', 'done', 3
+    UNION ALL SELECT 'Cancelled', 'cancelled', 4
+) d;
+
+CREATE TRIGGER project_columns_after_project_insert AFTER INSERT ON projects BEGIN
+    INSERT INTO project_columns (id, project_id, name, category, position) VALUES
+        (randomblob(16), NEW.id, 'To Do', 'todo', 0),
+        (randomblob(16), NEW.id, 'In Progress', 'inprogress', 1),
+        (randomblob(16), NEW.id, 'In Review', 'inreview', 2),
+        (randomblob(16), NEW.id, 'Done', 'done', 3),
+        (randomblob(16), NEW.id, 'Cancelled', 'cancelled', 4);
+END;
+
+-- The column a task sits in. Writes that change a task's status also move it to the
+-- first column of the new status unless its column already has that category.
+ALTER TABLE tasks ADD COLUMN column_id BLOB REFERENCES project_columns(id) ON DELETE SET NULL;
+
+UPDATE tasks SET column_id = (
+    SELECT c.id FROM project_columns c
+    WHERE c.project_id = tasks.project_id AND c.category = tasks.status
+    ORDER BY c.position ASC
+    LIMIT 1
+);
+
+CREATE INDEX idx_tasks_column_id ON tasks(column_id);
@@ -0,0 +1,14 @@
+-- How a project's board is split into horizontal lanes. Projects without a row show a
+-- single lane.
+CREATE TABLE project_swimlanes (
+    project_id      BLOB PRIMARY KEY,
+    group_by        TEXT NOT NULL
+                    CHECK (group_by IN ('assignee','label','custom_field')),
+    custom_field_id BLOB,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    CHECK ((group_by = 'custom_field') = (custom_field_id IS NOT NULL)),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    -- Deleting the field the lanes come from leaves the board without lanes
+    FOREIGN KEY (custom_field_id) REFERENCES custom_fields(id) ON DELETE CASCADE
+);
@@ -0,0 +1,11 @@
+-- Manual order of tasks within a board column, lowest first. Ranks are fractional so a
+-- task can be dropped between two others without renumbering the column.
+ALTER TABLE tasks ADD COLUMN rank REAL NOT NULL DEFAULT 0;
+
+-- Keep the order boards showed so far, newest first
+UPDATE tasks SET rank = (
+    SELECT COUNT(*) FROM tasks newer
+    WHERE newer.column_id IS tasks.column_id AND newer.created_at > tasks.created_at
+);
+
+CREATE INDEX idx_tasks_column_id_rank ON tasks(column_id, rank);
@@ -0,0 +1,28 @@
+-- Users following a task. Assignees and commenters start watching automatically.
+CREATE TABLE task_watchers (
+    task_id     BLOB NOT NULL,
+    user_id     BLOB NOT NULL,
+    reason      TEXT NOT NULL DEFAULT 'manual'
+                CHECK (reason IN ('manual','assigned','commented')),
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_id, user_id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_watchers_user_id ON task_watchers(user_id);
+
+-- What watchers are told about the tasks they follow, per user.
+CREATE TABLE notifications (
+    id          BLOB PRIMARY KEY,
+    user_id     BLOB NOT NULL,
+    task_id     BLOB NOT NULL,
+    kind        TEXT NOT NULL CHECK (kind IN ('status_changed','commented')),
+    message     TEXT NOT NULL,
+    read_at     TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_notifications_user_id ON notifications(user_id, created_at);
@@ -0,0 +1,39 @@
+-- Users @mentioned in a task's description (comment_id NULL) or in one of its comments.
+CREATE TABLE task_mentions (
+    id          BLOB PRIMARY KEY,
+    task_id     BLOB NOT NULL,
+    comment_id  BLOB,
+    user_id     BLOB NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (comment_id) REFERENCES task_comments(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- A user is mentioned at most once per description and per comment, so editing a
+-- description does not notify people it already mentioned.
+CREATE UNIQUE INDEX idx_task_mentions_unique
+    ON task_mentions(task_id, IFNULL(comment_id, x''), user_id);
+CREATE INDEX idx_task_mentions_user_id ON task_mentions(user_id, created_at);
+
+-- Allow 'mentioned' notifications. SQLite can't alter a CHECK constraint, so rebuild
+-- the table; nothing references it.
+CREATE TABLE notifications_new (
+    id          BLOB PRIMARY KEY,
+    user_id     BLOB NOT NULL,
+    task_id     BLOB NOT NULL,
+    kind        TEXT NOT NULL CHECK (kind IN ('status_changed','commented','mentioned')),
+    message     TEXT NOT NULL,
+    read_at     TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+INSERT INTO notifications_new (id, user_id, task_id, kind, message, read_at, created_at)
+SELECT id, user_id, task_id, kind, message, read_at, created_at FROM notifications;
+
+DROP TABLE notifications;
+ALTER TABLE notifications_new RENAME TO notifications;
+
+CREATE INDEX idx_notifications_user_id ON notifications(user_id, created_at);
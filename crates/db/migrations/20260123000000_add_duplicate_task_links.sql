@@ -0,0 +1,22 @@
+-- Allow 'duplicates' links. "is duplicated by" is stored as the reverse "duplicates"
+-- link, like "blocked by". SQLite can't alter a CHECK constraint, so rebuild the table;
+-- nothing references it.
+CREATE TABLE task_links_new (
+    id              BLOB PRIMARY KEY,
+    source_task_id  BLOB NOT NULL,
+    target_task_id  BLOB NOT NULL,
+    kind            TEXT NOT NULL CHECK (kind IN ('blocks', 'relates_to', 'duplicates')),
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (source_task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (target_task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    CHECK (source_task_id != target_task_id),
+    UNIQUE (source_task_id, target_task_id, kind)
+);
+
+INSERT INTO task_links_new (id, source_task_id, target_task_id, kind, created_at)
+SELECT id, source_task_id, target_task_id, kind, created_at FROM task_links;
+
+DROP TABLE task_links;
+ALTER TABLE task_links_new RENAME TO task_links;
+
+CREATE INDEX idx_task_links_target_task_id ON task_links(target_task_id);
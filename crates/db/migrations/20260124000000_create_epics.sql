@@ -0,0 +1,27 @@
+-- Epics and milestones group tasks of a project across statuses.
+-- kind: 'epic' for an initiative, 'milestone' for a target date; both track progress
+-- the same way.
+CREATE TABLE epics (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    kind        TEXT NOT NULL DEFAULT 'epic' CHECK (kind IN ('epic','milestone')),
+    title       TEXT NOT NULL CHECK (title != ''),
+    description TEXT,
+    due_at      TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_epics_project_id ON epics(project_id);
+
+-- A task belongs to at most one epic.
+CREATE TABLE epic_tasks (
+    task_id     BLOB PRIMARY KEY,
+    epic_id     BLOB NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (epic_id) REFERENCES epics(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_epic_tasks_epic_id ON epic_tasks(epic_id);
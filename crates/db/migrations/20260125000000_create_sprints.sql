@@ -0,0 +1,34 @@
+-- Time-boxed iterations of a project. A project has at most one active sprint; closing
+-- it records what happened to each of its tasks and rolls unfinished ones forward.
+CREATE TABLE sprints (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    name        TEXT NOT NULL CHECK (name != ''),
+    goal        TEXT,
+    status      TEXT NOT NULL DEFAULT 'planned'
+                CHECK (status IN ('planned','active','closed')),
+    starts_at   TEXT NOT NULL,
+    ends_at     TEXT NOT NULL,
+    started_at  TEXT,
+    closed_at   TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    CHECK (ends_at > starts_at)
+);
+
+CREATE INDEX idx_sprints_project_id ON sprints(project_id, starts_at);
+CREATE UNIQUE INDEX idx_sprints_one_active ON sprints(project_id) WHERE status = 'active';
+
+-- outcome: set when the sprint closes; NULL while it is planned or active
+CREATE TABLE sprint_tasks (
+    sprint_id   BLOB NOT NULL,
+    task_id     BLOB NOT NULL,
+    outcome     TEXT CHECK (outcome IN ('completed','carried_over','dropped')),
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (sprint_id, task_id),
+    FOREIGN KEY (sprint_id) REFERENCES sprints(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_sprint_tasks_task_id ON sprint_tasks(task_id);
@@ -0,0 +1,9 @@
+-- Per-project defaults stored as one JSON document. Keys missing from the document fall
+-- back to their defaults, so new settings need no migration.
+CREATE TABLE project_settings (
+    project_id  BLOB PRIMARY KEY,
+    settings    TEXT NOT NULL DEFAULT '{}' CHECK (json_valid(settings)),
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
@@ -0,0 +1,13 @@
+-- Named task list filters saved per project. `filter` holds the criteria as JSON in the
+-- same shape as the task list's query parameters.
+CREATE TABLE views (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    name        TEXT NOT NULL CHECK (name != ''),
+    filter      TEXT NOT NULL DEFAULT '{}' CHECK (json_valid(filter)),
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX idx_views_project_name ON views(project_id, name COLLATE NOCASE);
@@ -0,0 +1,25 @@
+-- Allow 'merged' events, recorded on both sides when tasks are folded into another.
+-- SQLite can't alter a CHECK constraint, so rebuild the table; nothing references it.
+CREATE TABLE task_events_new (
+    id              BLOB PRIMARY KEY,
+    task_id         BLOB NOT NULL,
+    kind            TEXT NOT NULL
+                    CHECK (kind IN ('created','status_changed','edited','assignee_changed','archived','unarchived','deleted','restored','merged')),
+    source          TEXT NOT NULL
+                    CHECK (source IN ('user','agent','sync','system')),
+    integration_id  BLOB,
+    field           TEXT,
+    old_value       TEXT,
+    new_value       TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (integration_id) REFERENCES integrations(id) ON DELETE SET NULL
+);
+
+INSERT INTO task_events_new (id, task_id, kind, source, integration_id, field, old_value, new_value, created_at)
+SELECT id, task_id, kind, source, integration_id, field, old_value, new_value, created_at FROM task_events;
+
+DROP TABLE task_events;
+ALTER TABLE task_events_new RENAME TO task_events;
+
+CREATE INDEX idx_task_events_task_id_created_at ON task_events(task_id, created_at);
@@ -0,0 +1,4 @@
+-- Board card decoration: a color stored as lowercase #rrggbb and an image attachment
+ALTER TABLE tasks ADD COLUMN cover_color TEXT
+    CHECK (cover_color IS NULL OR cover_color GLOB '#[0-9a-f][0-9a-f][0-9a-f][0-9a-f][0-9a-f][0-9a-f]');
+ALTER TABLE tasks ADD COLUMN cover_attachment_id BLOB REFERENCES task_attachments(id) ON DELETE SET NULL;
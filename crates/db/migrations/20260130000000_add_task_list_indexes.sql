@@ -0,0 +1,12 @@
+-- Indexes for filtering and sorting a project's task list. The list leaves out archived
+-- and trashed tasks, so only the rest are indexed.
+CREATE INDEX idx_tasks_project_id_created_at ON tasks(project_id, created_at)
+    WHERE archived_at IS NULL AND deleted_at IS NULL;
+CREATE INDEX idx_tasks_project_id_status ON tasks(project_id, status)
+    WHERE archived_at IS NULL AND deleted_at IS NULL;
+CREATE INDEX idx_tasks_project_id_priority ON tasks(project_id, priority)
+    WHERE archived_at IS NULL AND deleted_at IS NULL;
+-- Replaces the due date index, which only left out tasks without a due date
+DROP INDEX IF EXISTS idx_tasks_project_id_due_at;
+CREATE INDEX idx_tasks_project_id_due_at ON tasks(project_id, due_at)
+    WHERE archived_at IS NULL AND deleted_at IS NULL;
@@ -0,0 +1,17 @@
+-- Keys scripts and CI jobs authenticate with. Only a SHA-256 hash of a key is stored;
+-- `prefix` is its first characters, kept so people can tell their keys apart.
+CREATE TABLE api_keys (
+    id            BLOB PRIMARY KEY,
+    name          TEXT NOT NULL CHECK (name != ''),
+    prefix        TEXT NOT NULL,
+    key_hash      TEXT NOT NULL UNIQUE,
+    scopes        TEXT NOT NULL DEFAULT '[]' CHECK (json_valid(scopes)),
+    user_id       BLOB,
+    project_id    BLOB,
+    expires_at    TEXT,
+    last_used_at  TEXT,
+    revoked_at    TEXT,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
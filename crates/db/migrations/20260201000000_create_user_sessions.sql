@@ -0,0 +1,25 @@
+-- Accounts at an OpenID Connect provider, linked to the local user they sign in as.
+CREATE TABLE user_identities (
+    id          BLOB PRIMARY KEY,
+    user_id     BLOB NOT NULL,
+    issuer      TEXT NOT NULL,
+    subject     TEXT NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    UNIQUE (issuer, subject)
+);
+
+CREATE INDEX idx_user_identities_user_id ON user_identities(user_id);
+
+-- Browser sessions started by signing in. Like API keys, only a SHA-256 hash of the
+-- session cookie is stored.
+CREATE TABLE user_sessions (
+    id          BLOB PRIMARY KEY,
+    user_id     BLOB NOT NULL,
+    token_hash  TEXT NOT NULL UNIQUE,
+    expires_at  TEXT NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_user_sessions_user_id ON user_sessions(user_id);
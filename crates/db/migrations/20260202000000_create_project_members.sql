@@ -0,0 +1,14 @@
+-- Who may do what on a project. A project without members is open to everyone; once it
+-- has members, other signed-in users are refused.
+CREATE TABLE project_members (
+    project_id  BLOB NOT NULL,
+    user_id     BLOB NOT NULL,
+    role        TEXT NOT NULL CHECK (role IN ('viewer', 'member', 'admin')),
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (project_id, user_id),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_project_members_user_id ON project_members(user_id);
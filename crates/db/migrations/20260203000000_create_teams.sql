@@ -0,0 +1,29 @@
+-- Teams group projects and the people working on them, so one server can be shared by
+-- several teams. Projects and API keys outside a team keep working as before.
+CREATE TABLE teams (
+    id          BLOB PRIMARY KEY,
+    name        TEXT NOT NULL CHECK (name != ''),
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE TABLE team_members (
+    team_id     BLOB NOT NULL,
+    user_id     BLOB NOT NULL,
+    role        TEXT NOT NULL CHECK (role IN ('member', 'admin')),
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (team_id, user_id),
+    FOREIGN KEY (team_id) REFERENCES teams(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_team_members_user_id ON team_members(user_id);
+
+-- Only people in a project's team can reach it. A team can't be deleted while it still
+-- has projects.
+ALTER TABLE projects ADD COLUMN team_id BLOB REFERENCES teams(id);
+CREATE INDEX idx_projects_team_id ON projects(team_id);
+
+-- A key limited to a team reaches only that team's projects.
+ALTER TABLE api_keys ADD COLUMN team_id BLOB REFERENCES teams(id) ON DELETE CASCADE;
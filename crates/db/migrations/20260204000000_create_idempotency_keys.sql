@@ -0,0 +1,15 @@
+-- Replies to requests sent with an `Idempotency-Key` header, so a retried request gets the
+-- first reply instead of doing the work again. Keys are per client; a row without a status
+-- is a request still being handled.
+CREATE TABLE idempotency_keys (
+    principal       TEXT NOT NULL,
+    key             TEXT NOT NULL,
+    request_hash    TEXT NOT NULL,
+    status_code     INTEGER,
+    content_type    TEXT,
+    response_body   BLOB,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (principal, key)
+);
+
+CREATE INDEX idx_idempotency_keys_created_at ON idempotency_keys(created_at);
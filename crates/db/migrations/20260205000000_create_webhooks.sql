@@ -0,0 +1,31 @@
+-- Outgoing webhooks: a project's activity is POSTed to each subscribed URL, signed with
+-- the webhook's secret, and every attempt is logged with the reply it got.
+CREATE TABLE webhooks (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    url         TEXT NOT NULL,
+    secret      TEXT NOT NULL,
+    -- JSON array of event names to send; every event when empty
+    events      TEXT NOT NULL DEFAULT '[]',
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_webhooks_project_id ON webhooks(project_id);
+
+CREATE TABLE webhook_deliveries (
+    id             BLOB PRIMARY KEY,
+    webhook_id     BLOB NOT NULL,
+    event          TEXT NOT NULL,
+    payload        TEXT NOT NULL,
+    -- Null when no reply came back; see error
+    status_code    INTEGER,
+    response_body  TEXT,
+    error          TEXT,
+    duration_ms    INTEGER NOT NULL,
+    created_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (webhook_id) REFERENCES webhooks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_webhook_deliveries_webhook_id_created_at ON webhook_deliveries(webhook_id, created_at);
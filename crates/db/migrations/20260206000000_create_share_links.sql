@@ -0,0 +1,18 @@
+-- Read-only links to a project's board for people without an account. Only a hash of the
+-- token is kept, like API keys.
+CREATE TABLE share_links (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    prefix      TEXT NOT NULL,
+    token_hash  TEXT NOT NULL UNIQUE,
+    mode        TEXT NOT NULL CHECK (mode IN ('live', 'snapshot')),
+    -- JSON array of the task fields shown besides title and status
+    fields      TEXT NOT NULL DEFAULT '[]',
+    -- The board as it was when the link was made, for snapshot links
+    snapshot    TEXT,
+    expires_at  TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_share_links_project_id ON share_links(project_id);
@@ -0,0 +1,20 @@
+-- Every API request that changes something, refused ones included, for teams that have to
+-- show who did what. Users, keys and projects aren't foreign keys so the trail outlives
+-- them; the user's name and key's prefix are kept for the same reason.
+CREATE TABLE audit_log (
+    id              BLOB PRIMARY KEY,
+    method          TEXT NOT NULL,
+    path            TEXT NOT NULL,
+    status          INTEGER NOT NULL,
+    principal       TEXT NOT NULL CHECK (principal IN ('session', 'api_key', 'anonymous')),
+    user_id         BLOB,
+    user_name       TEXT,
+    api_key_id      BLOB,
+    api_key_prefix  TEXT,
+    project_id      BLOB,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE INDEX idx_audit_log_created_at ON audit_log(created_at DESC);
+CREATE INDEX idx_audit_log_user_id ON audit_log(user_id);
+CREATE INDEX idx_audit_log_project_id ON audit_log(project_id);
@@ -0,0 +1,34 @@
+-- Background work that has to survive a restart: sync runs, webhook deliveries and the
+-- periodic upkeep. Failed attempts are retried at run_at until max_attempts; rows left in
+-- 'running' by a crashed server are requeued on startup.
+CREATE TABLE jobs (
+    id            BLOB PRIMARY KEY,
+    kind          TEXT NOT NULL,
+    payload       TEXT NOT NULL DEFAULT '{}',
+    status        TEXT NOT NULL DEFAULT 'queued'
+                     CHECK (status IN ('queued','running','succeeded','failed')),
+    attempts      INTEGER NOT NULL DEFAULT 0,
+    max_attempts  INTEGER NOT NULL DEFAULT 5,
+    -- At most one queued or running job per key, e.g. one pending run of a schedule
+    unique_key    TEXT,
+    run_at        TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    last_error    TEXT,
+    started_at    TEXT,
+    finished_at   TEXT,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE INDEX idx_jobs_status_run_at ON jobs(status, run_at);
+CREATE INDEX idx_jobs_finished_at ON jobs(finished_at);
+CREATE UNIQUE INDEX idx_jobs_pending_unique_key ON jobs(unique_key)
+    WHERE unique_key IS NOT NULL AND status IN ('queued', 'running');
+
+-- Sync runs queued before the runner existed still get run
+INSERT INTO jobs (id, kind, payload, max_attempts)
+SELECT randomblob(16),
+       'integration_sync',
+       json_object('sync_job_id', lower(hex(id))),
+       3
+FROM sync_jobs
+WHERE status IN ('queued', 'running');
@@ -0,0 +1,3 @@
+-- A request holds its key until locked_until. A key left in progress by a crashed server
+-- can be claimed again once its lease lapses, rather than refusing retries for a day.
+ALTER TABLE idempotency_keys ADD COLUMN locked_until TEXT;
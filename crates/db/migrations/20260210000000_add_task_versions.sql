@@ -0,0 +1,9 @@
+-- A version per task for If-Match on updates. Writes that don't move the version
+-- themselves, like tracker syncs and reordering, are counted by the trigger.
+ALTER TABLE tasks ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+
+CREATE TRIGGER tasks_bump_version AFTER UPDATE ON tasks
+WHEN NEW.version = OLD.version
+BEGIN
+    UPDATE tasks SET version = OLD.version + 1 WHERE id = NEW.id;
+END;
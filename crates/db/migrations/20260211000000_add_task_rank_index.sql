@@ -0,0 +1,3 @@
+-- Index for a project's task list in board order, like the other task list indexes.
+CREATE INDEX idx_tasks_project_id_rank ON tasks(project_id, rank)
+    WHERE archived_at IS NULL AND deleted_at IS NULL;
@@ -0,0 +1,206 @@
+use std::{
+    fs::{File, OpenOptions, TryLockError},
+    io::{self, BufReader, BufWriter},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use flate2::{Compression, bufread::GzDecoder, write::GzEncoder};
+use sqlx::{
+    SqlitePool,
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+};
+use thiserror::Error;
+use utils::assets::asset_dir;
+
+use crate::DBService;
+
+#[derive(Debug, Error)]
+pub enum BackupError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("{} already exists", .0.display())]
+    Exists(PathBuf),
+    #[error("Not a usable Vibe Kanban database: {0}")]
+    Invalid(String),
+    #[error("The database is in use; stop the server first")]
+    InUse,
+}
+
+/// Where the server keeps its database.
+pub fn database_path() -> PathBuf {
+    asset_dir().join("db.sqlite")
+}
+
+fn is_gzip(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("gz"))
+}
+
+/// A sibling of `path` to write to before moving the result into place.
+fn partial_path(path: &Path, suffix: &str) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{name}.{suffix}"))
+}
+
+fn open_lock(database: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(partial_path(database, "lock"))
+}
+
+fn locked(attempt: Result<(), TryLockError>) -> Result<(), BackupError> {
+    match attempt {
+        Ok(()) => Ok(()),
+        Err(TryLockError::WouldBlock) => Err(BackupError::InUse),
+        Err(TryLockError::Error(e)) => Err(e.into()),
+    }
+}
+
+/// Marks the database as in use for as long as it is held.
+pub struct DatabaseLock {
+    _file: File,
+}
+
+/// Mark the database as in use until the lock is dropped, so a restore refuses to
+/// replace it meanwhile. Any number of processes may hold it at once.
+pub fn lock_database() -> Result<DatabaseLock, BackupError> {
+    let file = open_lock(&database_path())?;
+    locked(file.try_lock_shared())?;
+    Ok(DatabaseLock { _file: file })
+}
+
+async fn in_background<T: Send + 'static>(
+    work: impl FnOnce() -> io::Result<T> + Send + 'static,
+) -> io::Result<T> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(io::Error::other)?
+}
+
+fn gzip(from: &Path, to: &Path) -> io::Result<()> {
+    let mut reader = BufReader::new(File::open(from)?);
+    let mut encoder = GzEncoder::new(BufWriter::new(File::create(to)?), Compression::default());
+    io::copy(&mut reader, &mut encoder)?;
+    encoder.finish()?;
+    Ok(())
+}
+
+fn gunzip(from: &Path, to: &Path) -> io::Result<()> {
+    let mut decoder = GzDecoder::new(BufReader::new(File::open(from)?));
+    let mut writer = BufWriter::new(File::create(to)?);
+    io::copy(&mut decoder, &mut writer)?;
+    Ok(())
+}
+
+impl DBService {
+    /// Write a consistent copy of the database to `path` with `VACUUM INTO`, gzipped when
+    /// the path ends in `.gz`. Safe to run while the server is using the database.
+    pub async fn backup_to(&self, path: &Path) -> Result<u64, BackupError> {
+        if path.exists() {
+            return Err(BackupError::Exists(path.to_path_buf()));
+        }
+        let copy = if is_gzip(path) {
+            partial_path(path, "partial")
+        } else {
+            path.to_path_buf()
+        };
+        let _ = std::fs::remove_file(&copy);
+        sqlx::query("VACUUM INTO $1")
+            .bind(copy.to_string_lossy().into_owned())
+            .execute(&self.pool)
+            .await?;
+
+        if copy != path {
+            let (from, to) = (copy.clone(), path.to_path_buf());
+            let compressed = in_background(move || gzip(&from, &to)).await;
+            let _ = std::fs::remove_file(&copy);
+            compressed?;
+        }
+        Ok(std::fs::metadata(path)?.len())
+    }
+}
+
+/// Check that `path` holds an intact database that has been migrated at least once.
+async fn verify(path: &Path) -> Result<(), BackupError> {
+    let options = SqliteConnectOptions::from_str(&format!("sqlite://{}", path.to_string_lossy()))?
+        .read_only(true);
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await
+        .map_err(|e| BackupError::Invalid(e.to_string()))?;
+    let integrity: String = sqlx::query_scalar("PRAGMA integrity_check")
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| BackupError::Invalid(e.to_string()))?;
+    let migrated: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')",
+    )
+    .fetch_one(&pool)
+    .await?;
+    pool.close().await;
+
+    if integrity != "ok" {
+        return Err(BackupError::Invalid(integrity));
+    }
+    if !migrated {
+        return Err(BackupError::Invalid(
+            "it has no migrations table".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Replace the server's database with the backup at `path`, plain or gzipped, once it
+/// checks out. The current database is kept next to it and its path returned. Refuses
+/// while a server holds the database lock.
+pub async fn restore_from(path: &Path) -> Result<Option<PathBuf>, BackupError> {
+    let target = database_path();
+    let lock = open_lock(&target)?;
+    locked(lock.try_lock())?;
+    let staged = partial_path(&target, "restore");
+    let _ = std::fs::remove_file(&staged);
+    if is_gzip(path) {
+        let (from, to) = (path.to_path_buf(), staged.clone());
+        in_background(move || gunzip(&from, &to)).await?;
+    } else {
+        std::fs::copy(path, &staged)?;
+    }
+    if let Err(e) = verify(&staged).await {
+        let _ = std::fs::remove_file(&staged);
+        return Err(e);
+    }
+
+    let previous = if target.exists() {
+        let name = target
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let kept = target.with_file_name(format!(
+            "{name}.{}.bak",
+            chrono::Utc::now().format("%Y%m%d%H%M%S")
+        ));
+        std::fs::rename(&target, &kept)?;
+        // The write-ahead log belongs with the file it was written for
+        for suffix in ["-wal", "-shm"] {
+            let sidecar = PathBuf::from(format!("{}{suffix}", target.display()));
+            if sidecar.exists() {
+                std::fs::rename(&sidecar, format!("{}{suffix}", kept.display()))?;
+            }
+        }
+        Some(kept)
+    } else {
+        None
+    };
+    std::fs::rename(&staged, &target)?;
+    drop(lock);
+    Ok(previous)
+}
@@ -0,0 +1,81 @@
+//! Keyset pagination. A cursor is the sort key of the last row a client has seen, encoded
+//! so clients treat it as an opaque token; the next page starts right after that key
+//! instead of scanning past an offset.
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+/// Sort key of rows listed oldest or newest first: the raw `created_at` text, so it
+/// compares the way SQLite stored it, and the rowid to order rows created together.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreatedKey {
+    pub created_at: String,
+    pub rowid: i64,
+}
+
+pub fn encode<K: Serialize>(key: &K) -> String {
+    URL_SAFE_NO_PAD.encode(serde_json::to_vec(key).unwrap_or_default())
+}
+
+/// `None` when the cursor wasn't made by [`encode`] for this kind of key.
+pub fn decode<K: DeserializeOwned>(cursor: &str) -> Option<K> {
+    let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// One page of rows and the cursor to fetch the next one with.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Build a page from rows fetched with their sort keys, asking the database for one
+    /// row more than `limit` so a full page can tell whether anything follows it.
+    pub fn from_rows<K: Serialize>(mut rows: Vec<(T, K)>, limit: Option<i64>) -> Self {
+        let more = limit.is_some_and(|limit| rows.len() as i64 > limit);
+        if let Some(limit) = limit {
+            rows.truncate(limit.max(0) as usize);
+        }
+        let next_cursor = more
+            .then(|| rows.last().map(|(_, key)| encode(key)))
+            .flatten();
+        Self {
+            items: rows.into_iter().map(|(item, _)| item).collect(),
+            next_cursor,
+        }
+    }
+}
+
+/// The row count to ask the database for when building a [`Page`] of `limit` rows.
+pub fn fetch_limit(limit: Option<i64>) -> Option<i64> {
+    limit.map(|limit| limit + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_links_to_the_row_after_its_last() {
+        let key = |rowid| CreatedKey {
+            created_at: "2026-01-01 00:00:00.000".to_string(),
+            rowid,
+        };
+        let rows = (1..=3).map(|rowid| (rowid, key(rowid))).collect::<Vec<_>>();
+
+        let page = Page::from_rows(rows.clone(), Some(2));
+        assert_eq!(page.items, vec![1, 2]);
+        let cursor = page.next_cursor.unwrap();
+        assert_eq!(decode::<CreatedKey>(&cursor), Some(key(2)));
+
+        let last = Page::from_rows(rows.clone(), Some(3));
+        assert_eq!(last.items, vec![1, 2, 3]);
+        assert!(last.next_cursor.is_none());
+        assert!(Page::from_rows(rows, None).next_cursor.is_none());
+
+        assert!(decode::<CreatedKey>("not a cursor").is_none());
+    }
+}
@@ -1,12 +1,26 @@
 use std::{str::FromStr, sync::Arc};
 
+use pool::PoolConfig;
 use sqlx::{
-    Error, Pool, Sqlite, SqlitePool,
-    sqlite::{SqliteConnectOptions, SqliteConnection, SqlitePoolOptions},
+    Error, Pool, Sqlite,
+    sqlite::{SqliteConnectOptions, SqliteConnection},
 };
 use utils::assets::asset_dir;
 
+pub mod backup;
+pub mod cursor;
+pub mod migrations;
 pub mod models;
+pub mod pool;
+
+fn connect_options(config: &PoolConfig) -> Result<SqliteConnectOptions, Error> {
+    let database_url = format!(
+        "sqlite://{}",
+        asset_dir().join("db.sqlite").to_string_lossy()
+    );
+    let options = SqliteConnectOptions::from_str(&database_url)?.create_if_missing(true);
+    Ok(config.apply(options))
+}
 
 #[derive(Clone)]
 pub struct DBService {
@@ -15,13 +29,22 @@ pub struct DBService {
 
 impl DBService {
     pub async fn new() -> Result<DBService, Error> {
-        let database_url = format!(
-            "sqlite://{}",
-            asset_dir().join("db.sqlite").to_string_lossy()
-        );
-        let options = SqliteConnectOptions::from_str(&database_url)?.create_if_missing(true);
-        let pool = SqlitePool::connect_with(options).await?;
-        sqlx::migrate!("./migrations").run(&pool).await?;
+        let config = PoolConfig::from_env();
+        let pool = config
+            .pool_options()
+            .connect_with(connect_options(&config)?)
+            .await?;
+        migrations::run_on_open(&pool).await?;
+        Ok(DBService { pool })
+    }
+
+    /// Connect without migrating, for managing migrations explicitly.
+    pub async fn open() -> Result<DBService, Error> {
+        let config = PoolConfig::from_env();
+        let pool = config
+            .pool_options()
+            .connect_with(connect_options(&config)?)
+            .await?;
         Ok(DBService { pool })
     }
 
@@ -49,14 +72,13 @@ impl DBService {
             + Sync
             + 'static,
     {
-        let database_url = format!(
-            "sqlite://{}",
-            asset_dir().join("db.sqlite").to_string_lossy()
-        );
-        let options = SqliteConnectOptions::from_str(&database_url)?.create_if_missing(true);
+        let config = PoolConfig::from_env();
+        tracing::debug!("Opening the database with {config:?}");
+        let options = connect_options(&config)?;
 
         let pool = if let Some(hook) = after_connect {
-            SqlitePoolOptions::new()
+            config
+                .pool_options()
                 .after_connect(move |conn, _meta| {
                     let hook = hook.clone();
                     Box::pin(async move {
@@ -67,10 +89,10 @@ impl DBService {
                 .connect_with(options)
                 .await?
         } else {
-            SqlitePool::connect_with(options).await?
+            config.pool_options().connect_with(options).await?
         };
 
-        sqlx::migrate!("./migrations").run(&pool).await?;
+        migrations::run_on_open(&pool).await?;
         Ok(pool)
     }
 }
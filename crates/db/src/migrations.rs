@@ -0,0 +1,179 @@
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{
+    Error, SqlitePool,
+    migrate::{MigrateError, Migrator},
+};
+use thiserror::Error;
+
+use crate::DBService;
+
+/// The migrations this build ships.
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Set `VK_MANUAL_MIGRATIONS=1` to leave migrating to `vk db migrate`; opening a
+/// database with pending migrations then fails instead of applying them.
+static MANUAL_MIGRATIONS: LazyLock<bool> =
+    LazyLock::new(|| utils::env_flag("VK_MANUAL_MIGRATIONS"));
+
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    #[error(transparent)]
+    Database(#[from] Error),
+    #[error(transparent)]
+    Migrate(#[from] MigrateError),
+    #[error("Migration {0} has no down script, so it can't be rolled back")]
+    Irreversible(i64),
+}
+
+/// One migration this build ships, and whether the database has it.
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    /// When it was applied; `None` while pending
+    pub applied_at: Option<DateTime<Utc>>,
+    /// The database applied a different script under this version
+    pub checksum_mismatch: bool,
+    /// It has a down script `vk db rollback` can run
+    pub reversible: bool,
+}
+
+struct AppliedMigration {
+    version: i64,
+    installed_on: DateTime<Utc>,
+    checksum: Vec<u8>,
+}
+
+/// Migrations the database applied successfully, oldest first; none before the first
+/// migration creates the bookkeeping table.
+async fn applied(pool: &SqlitePool) -> Result<Vec<AppliedMigration>, Error> {
+    let has_table: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')",
+    )
+    .fetch_one(pool)
+    .await?;
+    if !has_table {
+        return Ok(Vec::new());
+    }
+    let rows: Vec<(i64, DateTime<Utc>, Vec<u8>)> = sqlx::query_as(
+        "SELECT version, installed_on, checksum FROM _sqlx_migrations WHERE success = 1 ORDER BY version",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(version, installed_on, checksum)| AppliedMigration {
+            version,
+            installed_on,
+            checksum,
+        })
+        .collect())
+}
+
+fn shipped_versions() -> impl Iterator<Item = i64> {
+    MIGRATOR
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .map(|migration| migration.version)
+}
+
+/// Bring the database up to date when opening it, unless migrations are managed by hand.
+pub(crate) async fn run_on_open(pool: &SqlitePool) -> Result<(), Error> {
+    if !*MANUAL_MIGRATIONS {
+        MIGRATOR.run(pool).await?;
+        return Ok(());
+    }
+    let applied: Vec<i64> = applied(pool).await?.iter().map(|m| m.version).collect();
+    let pending = shipped_versions()
+        .filter(|version| !applied.contains(version))
+        .count();
+    if pending > 0 {
+        return Err(Error::Configuration(
+            format!(
+                "{pending} pending migration(s) and VK_MANUAL_MIGRATIONS is set; run `vk db migrate`"
+            )
+            .into(),
+        ));
+    }
+    Ok(())
+}
+
+impl DBService {
+    /// Versions of the migrations this build ships that the database hasn't applied, or
+    /// failed applying. Empty once startup has migrated the database.
+    pub async fn pending_migrations(&self) -> Result<Vec<i64>, Error> {
+        let applied: Vec<i64> = applied(&self.pool)
+            .await?
+            .iter()
+            .map(|migration| migration.version)
+            .collect();
+        Ok(shipped_versions()
+            .filter(|version| !applied.contains(version))
+            .collect())
+    }
+
+    /// Every migration this build ships, oldest first, with whether it has been applied.
+    pub async fn migration_status(&self) -> Result<Vec<MigrationStatus>, Error> {
+        let applied = applied(&self.pool).await?;
+        let reversible: Vec<i64> = MIGRATOR
+            .iter()
+            .filter(|migration| migration.migration_type.is_down_migration())
+            .map(|migration| migration.version)
+            .collect();
+        Ok(MIGRATOR
+            .iter()
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .map(|migration| {
+                let record = applied.iter().find(|m| m.version == migration.version);
+                MigrationStatus {
+                    version: migration.version,
+                    description: migration.description.to_string(),
+                    applied_at: record.map(|m| m.installed_on),
+                    checksum_mismatch: record
+                        .is_some_and(|m| m.checksum.as_slice() != &*migration.checksum),
+                    reversible: reversible.contains(&migration.version),
+                }
+            })
+            .collect())
+    }
+
+    /// Apply every pending migration, returning their versions.
+    pub async fn migrate(&self) -> Result<Vec<i64>, MigrationError> {
+        let pending = self.pending_migrations().await?;
+        MIGRATOR.run(&self.pool).await?;
+        Ok(pending)
+    }
+
+    /// Revert the migrations applied after `target`, newest first, or only the latest one
+    /// when no target is given. Every one of them needs a down script.
+    pub async fn rollback(&self, target: Option<i64>) -> Result<Vec<i64>, MigrationError> {
+        let applied: Vec<i64> = applied(&self.pool)
+            .await?
+            .iter()
+            .map(|migration| migration.version)
+            .collect();
+        let target = match target {
+            Some(target) => target,
+            None => applied.iter().rev().nth(1).copied().unwrap_or(0),
+        };
+        let reverted: Vec<i64> = applied
+            .iter()
+            .rev()
+            .copied()
+            .filter(|version| *version > target)
+            .collect();
+        for version in &reverted {
+            let has_down = MIGRATOR.iter().any(|migration| {
+                migration.version == *version && migration.migration_type.is_down_migration()
+            });
+            if !has_down {
+                return Err(MigrationError::Irreversible(*version));
+            }
+        }
+        MIGRATOR.undo(&self.pool, target).await?;
+        Ok(reverted)
+    }
+}
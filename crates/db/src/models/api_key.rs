@@ -0,0 +1,193 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, EnumString, Display, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ApiKeyScope {
+    /// Read anything
+    Read,
+    /// Create, change and delete tasks and everything else a board is made of
+    Write,
+    /// Everything, including managing API keys
+    Admin,
+}
+
+impl ApiKeyScope {
+    /// Whether holding this scope grants `required`.
+    pub fn grants(self, required: ApiKeyScope) -> bool {
+        match self {
+            Self::Admin => true,
+            Self::Write => required != Self::Admin,
+            Self::Read => required == Self::Read,
+        }
+    }
+}
+
+/// A key a script or CI job authenticates with. The key itself is only shown when it is
+/// created.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    /// The first characters of the key, e.g. `vk_3fZk9q`
+    pub prefix: String,
+    #[ts(type = "Array<ApiKeyScope>")]
+    #[schema(value_type = Vec<ApiKeyScope>)]
+    pub scopes: Json<Vec<ApiKeyScope>>,
+    /// The user requests made with the key act as. Without one, the key is held to the
+    /// project role its scopes imply
+    pub user_id: Option<Uuid>,
+    /// The only project the key can reach; all projects when null
+    pub project_id: Option<Uuid>,
+    /// The only team whose projects the key can reach; all teams when null
+    pub team_id: Option<Uuid>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateApiKey {
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+    /// Only the caller's own id; keys created by a signed-in user act as them anyway
+    #[serde(default)]
+    #[ts(optional)]
+    pub user_id: Option<Uuid>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub project_id: Option<Uuid>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub team_id: Option<Uuid>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    pub fn allows(&self, required: ApiKeyScope) -> bool {
+        self.scopes.iter().any(|scope| scope.grants(required))
+    }
+
+    /// Neither revoked nor expired.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApiKey,
+            r#"SELECT id as "id!: Uuid", name, prefix, scopes as "scopes!: Json<Vec<ApiKeyScope>>", user_id as "user_id: Uuid", project_id as "project_id: Uuid", team_id as "team_id: Uuid", expires_at as "expires_at: DateTime<Utc>", last_used_at as "last_used_at: DateTime<Utc>", revoked_at as "revoked_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>"
+               FROM api_keys
+               ORDER BY created_at DESC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApiKey,
+            r#"SELECT id as "id!: Uuid", name, prefix, scopes as "scopes!: Json<Vec<ApiKeyScope>>", user_id as "user_id: Uuid", project_id as "project_id: Uuid", team_id as "team_id: Uuid", expires_at as "expires_at: DateTime<Utc>", last_used_at as "last_used_at: DateTime<Utc>", revoked_at as "revoked_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>"
+               FROM api_keys
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Find the key with this hash, whether or not it is still active.
+    pub async fn find_by_hash(
+        pool: &SqlitePool,
+        key_hash: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApiKey,
+            r#"SELECT id as "id!: Uuid", name, prefix, scopes as "scopes!: Json<Vec<ApiKeyScope>>", user_id as "user_id: Uuid", project_id as "project_id: Uuid", team_id as "team_id: Uuid", expires_at as "expires_at: DateTime<Utc>", last_used_at as "last_used_at: DateTime<Utc>", revoked_at as "revoked_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>"
+               FROM api_keys
+               WHERE key_hash = $1"#,
+            key_hash
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateApiKey,
+        prefix: &str,
+        key_hash: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let scopes = Json(&data.scopes);
+        sqlx::query_as!(
+            ApiKey,
+            r#"INSERT INTO api_keys (id, name, prefix, key_hash, scopes, user_id, project_id, team_id, expires_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+               RETURNING id as "id!: Uuid", name, prefix, scopes as "scopes!: Json<Vec<ApiKeyScope>>", user_id as "user_id: Uuid", project_id as "project_id: Uuid", team_id as "team_id: Uuid", expires_at as "expires_at: DateTime<Utc>", last_used_at as "last_used_at: DateTime<Utc>", revoked_at as "revoked_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            data.name,
+            prefix,
+            key_hash,
+            scopes,
+            data.user_id,
+            data.project_id,
+            data.team_id,
+            data.expires_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn touch(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query!(
+            "UPDATE api_keys SET last_used_at = $2 WHERE id = $1",
+            id,
+            now
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Stop accepting the key. The row is kept so lists still show it.
+    pub async fn revoke(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query_as!(
+            ApiKey,
+            r#"UPDATE api_keys
+               SET revoked_at = COALESCE(revoked_at, $2)
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", name, prefix, scopes as "scopes!: Json<Vec<ApiKeyScope>>", user_id as "user_id: Uuid", project_id as "project_id: Uuid", team_id as "team_id: Uuid", expires_at as "expires_at: DateTime<Utc>", last_used_at as "last_used_at: DateTime<Utc>", revoked_at as "revoked_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            now
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scopes_grant_lower_scopes() {
+        assert!(ApiKeyScope::Admin.grants(ApiKeyScope::Write));
+        assert!(ApiKeyScope::Write.grants(ApiKeyScope::Read));
+        assert!(!ApiKeyScope::Write.grants(ApiKeyScope::Admin));
+        assert!(!ApiKeyScope::Read.grants(ApiKeyScope::Write));
+    }
+}
@@ -0,0 +1,171 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::cursor::{self, CreatedKey, Page};
+
+/// How the request that made a change was authenticated.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "audit_principal", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AuditPrincipal {
+    /// A signed-in user's session
+    Session,
+    ApiKey,
+    /// Neither, as on a server that doesn't require signing in
+    Anonymous,
+}
+
+/// One API request that changed, or tried to change, something.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub method: String,
+    pub path: String,
+    /// HTTP status of the response; refused requests are kept too
+    pub status: i64,
+    pub principal: AuditPrincipal,
+    pub user_id: Option<Uuid>,
+    /// The user's name at the time, kept after the user is deleted
+    pub user_name: Option<String>,
+    pub api_key_id: Option<Uuid>,
+    pub api_key_prefix: Option<String>,
+    /// The project the request touched, when its path or query names one
+    pub project_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateAuditEntry {
+    pub method: String,
+    pub path: String,
+    pub status: i64,
+    pub principal: AuditPrincipal,
+    pub user_id: Option<Uuid>,
+    pub user_name: Option<String>,
+    pub api_key_id: Option<Uuid>,
+    pub api_key_prefix: Option<String>,
+    pub project_id: Option<Uuid>,
+}
+
+/// Criteria for listing the audit log; every one is optional and combined with AND.
+#[derive(Debug, Default, Clone)]
+pub struct AuditFilter {
+    pub user_id: Option<Uuid>,
+    pub api_key_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub principal: Option<AuditPrincipal>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// Only entries about these projects; entries without a project are left out too
+    pub projects: Option<Vec<Uuid>>,
+}
+
+impl AuditEntry {
+    pub async fn create(pool: &SqlitePool, data: &CreateAuditEntry) -> Result<(), sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO audit_log (id, method, path, status, principal, user_id, user_name, api_key_id, api_key_prefix, project_id)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)"#,
+            id,
+            data.method,
+            data.path,
+            data.status,
+            data.principal,
+            data.user_id,
+            data.user_name,
+            data.api_key_id,
+            data.api_key_prefix,
+            data.project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Matching entries, newest first, a page at a time starting right after `after`.
+    pub async fn find_page(
+        pool: &SqlitePool,
+        filter: &AuditFilter,
+        after: Option<&CreatedKey>,
+        limit: i64,
+    ) -> Result<Page<Self>, sqlx::Error> {
+        let fetch_limit = cursor::fetch_limit(Some(limit));
+        let after_created_at = after.map(|key| key.created_at.as_str());
+        let after_rowid = after.map(|key| key.rowid);
+        let projects = filter
+            .projects
+            .as_ref()
+            .map(|projects| serde_json::to_string(projects).unwrap_or_default());
+        let records = sqlx::query!(
+            r#"SELECT id as "id!: Uuid", method, path, status as "status!: i64", principal as "principal!: AuditPrincipal", user_id as "user_id: Uuid", user_name, api_key_id as "api_key_id: Uuid", api_key_prefix, project_id as "project_id: Uuid", created_at as "created_at!: DateTime<Utc>", created_at as "created_at_key!: String", rowid as "rowid!: i64"
+               FROM audit_log
+               WHERE ($1 IS NULL OR user_id = $1)
+                 AND ($2 IS NULL OR api_key_id = $2)
+                 AND ($3 IS NULL OR project_id = $3)
+                 AND ($4 IS NULL OR principal = $4)
+                 AND ($5 IS NULL OR datetime(created_at) >= datetime($5))
+                 AND ($6 IS NULL OR datetime(created_at) < datetime($6))
+                 AND ($7 IS NULL OR lower(hex(project_id)) IN (
+                     SELECT lower(replace(value, '-', '')) FROM json_each($7)
+                 ))
+                 AND ($8 IS NULL OR (created_at, rowid) < ($8, $9))
+               ORDER BY created_at DESC, rowid DESC
+               LIMIT $10"#,
+            filter.user_id,
+            filter.api_key_id,
+            filter.project_id,
+            filter.principal,
+            filter.since,
+            filter.until,
+            projects,
+            after_created_at,
+            after_rowid,
+            fetch_limit
+        )
+        .fetch_all(pool)
+        .await?;
+        let rows = records
+            .into_iter()
+            .map(|rec| {
+                let key = CreatedKey {
+                    created_at: rec.created_at_key,
+                    rowid: rec.rowid,
+                };
+                let entry = AuditEntry {
+                    id: rec.id,
+                    method: rec.method,
+                    path: rec.path,
+                    status: rec.status,
+                    principal: rec.principal,
+                    user_id: rec.user_id,
+                    user_name: rec.user_name,
+                    api_key_id: rec.api_key_id,
+                    api_key_prefix: rec.api_key_prefix,
+                    project_id: rec.project_id,
+                    created_at: rec.created_at,
+                };
+                (entry, key)
+            })
+            .collect();
+        Ok(Page::from_rows(rows, Some(limit)))
+    }
+}
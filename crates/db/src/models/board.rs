@@ -0,0 +1,402 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    custom_field::{CustomField, CustomFieldType},
+    label::Label,
+    project_column::ProjectColumn,
+    task::{Task, TaskWithAttemptStatus},
+    user::User,
+};
+
+/// What a project's board lanes are drawn from.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "swimlane_group_by", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SwimlaneGroupBy {
+    Assignee,
+    /// A task with several labels shows up in each of their lanes
+    Label,
+    CustomField,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectSwimlane {
+    pub project_id: Uuid,
+    pub group_by: SwimlaneGroupBy,
+    /// The field lanes are drawn from when grouping by custom field
+    pub custom_field_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct SetProjectSwimlane {
+    pub group_by: SwimlaneGroupBy,
+    #[serde(default)]
+    #[ts(optional)]
+    pub custom_field_id: Option<Uuid>,
+}
+
+/// The tasks of one lane in one board column.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct BoardCell {
+    pub column_id: Uuid,
+    pub tasks: Vec<TaskWithAttemptStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct BoardLane {
+    /// The user id, label id or field value the lane stands for; `None` for the lane of
+    /// tasks without one, and for the only lane of a board without swimlanes
+    pub key: Option<String>,
+    pub name: String,
+    /// One cell per board column, in column order
+    pub cells: Vec<BoardCell>,
+}
+
+/// A project's board: its columns, and its tasks laid out by lane and column.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct Board {
+    pub columns: Vec<ProjectColumn>,
+    pub swimlane: Option<ProjectSwimlane>,
+    pub lanes: Vec<BoardLane>,
+}
+
+/// The board column a task shows up in: its own column, or the first one of its status
+/// when its column is gone or belongs to another status.
+pub fn column_for<'a>(columns: &'a [ProjectColumn], task: &Task) -> Option<&'a ProjectColumn> {
+    columns
+        .iter()
+        .find(|column| Some(column.id) == task.column_id && column.category == task.status)
+        .or_else(|| columns.iter().find(|column| column.category == task.status))
+}
+
+/// Lay tasks out into `lanes`, given as `(key, name)`, using the lane keys of each task.
+/// Tasks without a key of one of the lanes go in a trailing lane named `unset_name`,
+/// which is left out when empty unless there are no other lanes.
+pub fn build_lanes(
+    columns: &[ProjectColumn],
+    lanes: &[(String, String)],
+    tasks: &[TaskWithAttemptStatus],
+    keys: &HashMap<Uuid, Vec<String>>,
+    unset_name: &str,
+) -> Vec<BoardLane> {
+    let empty_cells = || {
+        columns
+            .iter()
+            .map(|column| BoardCell {
+                column_id: column.id,
+                tasks: Vec::new(),
+            })
+            .collect::<Vec<_>>()
+    };
+    let mut board: Vec<BoardLane> = lanes
+        .iter()
+        .map(|(key, name)| BoardLane {
+            key: Some(key.clone()),
+            name: name.clone(),
+            cells: empty_cells(),
+        })
+        .chain(std::iter::once(BoardLane {
+            key: None,
+            name: unset_name.to_string(),
+            cells: empty_cells(),
+        }))
+        .collect();
+    let unset = board.len() - 1;
+
+    for task in tasks {
+        let Some(column) = column_for(columns, task)
+            .and_then(|column| columns.iter().position(|c| c.id == column.id))
+        else {
+            continue;
+        };
+        let mut placed = false;
+        for key in keys.get(&task.id).into_iter().flatten() {
+            if let Some(lane) = lanes.iter().position(|(lane_key, _)| lane_key == key) {
+                board[lane].cells[column].tasks.push(task.clone());
+                placed = true;
+            }
+        }
+        if !placed {
+            board[unset].cells[column].tasks.push(task.clone());
+        }
+    }
+
+    if !lanes.is_empty() && board[unset].cells.iter().all(|cell| cell.tasks.is_empty()) {
+        board.pop();
+    }
+    board
+}
+
+/// Sort field values for lanes, numbers by value and everything else as text.
+fn sort_values(values: &mut [String]) {
+    values.sort_by(|a, b| match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        _ => a.cmp(b),
+    });
+}
+
+impl ProjectSwimlane {
+    pub async fn find(pool: &SqlitePool, project_id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectSwimlane,
+            r#"SELECT project_id as "project_id!: Uuid", group_by as "group_by!: SwimlaneGroupBy", custom_field_id as "custom_field_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_swimlanes
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn set(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &SetProjectSwimlane,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectSwimlane,
+            r#"INSERT INTO project_swimlanes (project_id, group_by, custom_field_id)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   group_by = excluded.group_by,
+                   custom_field_id = excluded.custom_field_id,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid", group_by as "group_by!: SwimlaneGroupBy", custom_field_id as "custom_field_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            data.group_by,
+            data.custom_field_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_swimlanes WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl Board {
+    /// The board of a project's tasks, archived and trashed ones aside.
+    pub async fn for_project(pool: &SqlitePool, project_id: Uuid) -> Result<Self, sqlx::Error> {
+        let columns = ProjectColumn::find_by_project_id(pool, project_id).await?;
+        let tasks = Task::find_by_project_id_with_attempt_status(pool, project_id).await?;
+        let swimlane = ProjectSwimlane::find(pool, project_id).await?;
+
+        let mut keys: HashMap<Uuid, Vec<String>> = HashMap::new();
+        let (lanes, unset_name) = match &swimlane {
+            None => (Vec::new(), "All tasks".to_string()),
+            Some(swimlane) => match swimlane.group_by {
+                SwimlaneGroupBy::Assignee => {
+                    for task in &tasks {
+                        if let Some(assignee_id) = task.assignee_id {
+                            keys.insert(task.id, vec![assignee_id.to_string()]);
+                        }
+                    }
+                    let lanes = User::find_all(pool)
+                        .await?
+                        .into_iter()
+                        .filter(|user| tasks.iter().any(|t| t.assignee_id == Some(user.id)))
+                        .map(|user| (user.id.to_string(), user.name))
+                        .collect();
+                    (lanes, "Unassigned".to_string())
+                }
+                SwimlaneGroupBy::Label => {
+                    let assignments = sqlx::query!(
+                        r#"SELECT tl.task_id as "task_id!: Uuid", tl.label_id as "label_id!: Uuid"
+                           FROM task_labels tl
+                           JOIN tasks t ON t.id = tl.task_id
+                           WHERE t.project_id = $1"#,
+                        project_id
+                    )
+                    .fetch_all(pool)
+                    .await?;
+                    for rec in assignments {
+                        keys.entry(rec.task_id)
+                            .or_default()
+                            .push(rec.label_id.to_string());
+                    }
+                    let lanes = Label::find_by_project_id(pool, project_id)
+                        .await?
+                        .into_iter()
+                        .map(|label| (label.id.to_string(), label.name))
+                        .collect();
+                    (lanes, "No label".to_string())
+                }
+                SwimlaneGroupBy::CustomField => {
+                    let field = match swimlane.custom_field_id {
+                        Some(field_id) => CustomField::find_by_id(pool, field_id).await?,
+                        None => None,
+                    }
+                    .ok_or(sqlx::Error::RowNotFound)?;
+                    let values = sqlx::query!(
+                        r#"SELECT task_id as "task_id!: Uuid", value
+                           FROM task_custom_field_values
+                           WHERE field_id = $1"#,
+                        field.id
+                    )
+                    .fetch_all(pool)
+                    .await?;
+                    // Select fields keep their options' order, followed by any values
+                    // that are no longer among the options
+                    let on_board: HashSet<Uuid> = tasks.iter().map(|task| task.id).collect();
+                    let mut extra: Vec<String> = Vec::new();
+                    for rec in values
+                        .into_iter()
+                        .filter(|rec| on_board.contains(&rec.task_id))
+                    {
+                        let known = field.field_type == CustomFieldType::Select
+                            && field.options.contains(&rec.value);
+                        if !known && !extra.contains(&rec.value) {
+                            extra.push(rec.value.clone());
+                        }
+                        keys.insert(rec.task_id, vec![rec.value]);
+                    }
+                    sort_values(&mut extra);
+                    let lanes = match field.field_type {
+                        CustomFieldType::Select => field.options.0.clone(),
+                        _ => Vec::new(),
+                    }
+                    .into_iter()
+                    .chain(extra)
+                    .map(|value| (value.clone(), value))
+                    .collect();
+                    (lanes, format!("No {}", field.name))
+                }
+            },
+        };
+
+        let lanes = build_lanes(&columns, &lanes, &tasks, &keys, &unset_name);
+        Ok(Self {
+            columns,
+            swimlane,
+            lanes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::task::{TaskPriority, TaskStatus};
+
+    fn column(category: TaskStatus) -> ProjectColumn {
+        let now = Utc::now();
+        ProjectColumn {
+            id: Uuid::new_v4(),
+            project_id: Uuid::nil(),
+            name: category.to_string(),
+            category,
+            position: 0,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn task(status: TaskStatus, column_id: Option<Uuid>) -> TaskWithAttemptStatus {
+        let now = Utc::now();
+        TaskWithAttemptStatus {
+            task: Task {
+                id: Uuid::new_v4(),
+                project_id: Uuid::nil(),
+                title: "Task".to_string(),
+                description: None,
+                status,
+                column_id,
+                parent_workspace_id: None,
+                shared_task_id: None,
+                due_at: None,
+                priority: TaskPriority::Medium,
+                estimate: None,
+                assignee_id: None,
+                cover_color: None,
+                cover_attachment_id: None,
+                archived_at: None,
+                deleted_at: None,
+                created_at: now,
+                updated_at: now,
+                version: 1,
+            },
+            has_in_progress_attempt: false,
+            last_attempt_failed: false,
+            executor: String::new(),
+            checklist_total: 0,
+            checklist_done: 0,
+            comment_count: 0,
+            attachment_count: 0,
+            is_blocked: false,
+        }
+    }
+
+    #[test]
+    fn test_build_lanes() {
+        let columns = vec![column(TaskStatus::Todo), column(TaskStatus::Done)];
+        let lanes = vec![
+            ("bug".to_string(), "Bug".to_string()),
+            ("ui".to_string(), "UI".to_string()),
+        ];
+        // Labelled twice, labelled with an unknown key, and with a stale column
+        let both = task(TaskStatus::Todo, Some(columns[0].id));
+        let unknown = task(TaskStatus::Done, Some(columns[1].id));
+        let stale = task(TaskStatus::Done, Some(columns[0].id));
+        let keys = HashMap::from([
+            (both.id, vec!["bug".to_string(), "ui".to_string()]),
+            (unknown.id, vec!["gone".to_string()]),
+            (stale.id, vec!["ui".to_string()]),
+        ]);
+
+        let board = build_lanes(
+            &columns,
+            &lanes,
+            &[both.clone(), unknown.clone(), stale.clone()],
+            &keys,
+            "No label",
+        );
+        let ids = |lane: &BoardLane, column: usize| {
+            lane.cells[column]
+                .tasks
+                .iter()
+                .map(|t| t.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(board.len(), 3);
+        assert_eq!(ids(&board[0], 0), vec![both.id]);
+        assert_eq!(ids(&board[1], 0), vec![both.id]);
+        assert_eq!(ids(&board[1], 1), vec![stale.id]);
+        assert_eq!(board[2].key, None);
+        assert_eq!(ids(&board[2], 1), vec![unknown.id]);
+
+        let single = build_lanes(&columns, &[], &[both], &HashMap::new(), "All tasks");
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].cells[0].tasks.len(), 1);
+    }
+}
@@ -0,0 +1,266 @@
+use std::{collections::HashMap, str::FromStr};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    sprint::Sprint,
+    task::TaskStatus,
+    task_event::{self, TaskEvent},
+};
+
+/// Longest range a burndown can cover.
+pub const MAX_RANGE_DAYS: i64 = 365;
+
+/// Outstanding work of a project or sprint at one point in time.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, ToSchema)]
+pub struct BurndownPoint {
+    pub at: DateTime<Utc>,
+    /// Estimate of the tasks that were not done or cancelled yet
+    pub remaining: f64,
+    /// Estimate of every task that existed, finished or not
+    pub total: f64,
+    /// Tasks not done or cancelled yet
+    pub remaining_count: i64,
+    /// Every task that existed, finished or not
+    pub total_count: i64,
+}
+
+/// What the burndown needs to know about a task as it is now.
+#[derive(Debug, Clone)]
+pub struct BurndownTask {
+    pub id: Uuid,
+    pub status: TaskStatus,
+    pub estimate: Option<f64>,
+    /// When the task started counting: its creation, or when it joined the sprint
+    pub created_at: DateTime<Utc>,
+}
+
+/// Parse a range like `14d` or `2w`.
+pub fn parse_range(range: &str) -> Option<Duration> {
+    let range = range.trim();
+    let unit = range.chars().last()?;
+    let count: i64 = range[..range.len() - unit.len_utf8()]
+        .parse()
+        .ok()
+        .filter(|count| *count > 0)?;
+    let days = match unit {
+        'd' => count,
+        'w' => count.checked_mul(7)?,
+        _ => return None,
+    };
+    (days <= MAX_RANGE_DAYS).then(|| Duration::days(days))
+}
+
+/// One point per day from `since`, plus one at `until`.
+pub fn sample_times(since: DateTime<Utc>, until: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+    let mut times = Vec::new();
+    let mut at = since;
+    while at < until {
+        times.push(at);
+        at += Duration::days(1);
+    }
+    times.push(until);
+    times
+}
+
+/// Replay status and estimate changes to find the outstanding work at each time.
+/// Tasks count from when they were created; done and cancelled tasks only count toward
+/// the total.
+pub fn compute(
+    tasks: &[BurndownTask],
+    changes: &[TaskEvent],
+    times: &[DateTime<Utc>],
+) -> Vec<BurndownPoint> {
+    let mut by_task: HashMap<(Uuid, &str), Vec<&TaskEvent>> = HashMap::new();
+    for change in changes {
+        if let Some(field) = change.field.as_deref() {
+            by_task
+                .entry((change.task_id, field))
+                .or_default()
+                .push(change);
+        }
+    }
+    let history = |task_id: Uuid, field: &'static str| {
+        by_task
+            .get(&(task_id, field))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    };
+
+    times
+        .iter()
+        .map(|&at| {
+            let mut point = BurndownPoint {
+                at,
+                remaining: 0.0,
+                total: 0.0,
+                remaining_count: 0,
+                total_count: 0,
+            };
+            for task in tasks.iter().filter(|task| task.created_at <= at) {
+                let estimate = task_event::value_at(
+                    history(task.id, "estimate"),
+                    at,
+                    task.estimate.map(|e| e.to_string()),
+                )
+                .and_then(|e| e.parse::<f64>().ok())
+                .unwrap_or(0.0);
+                let status = task_event::value_at(
+                    history(task.id, "status"),
+                    at,
+                    Some(task.status.to_string()),
+                )
+                .and_then(|s| TaskStatus::from_str(&s).ok())
+                .unwrap_or_else(|| task.status.clone());
+
+                point.total += estimate;
+                point.total_count += 1;
+                if !matches!(status, TaskStatus::Done | TaskStatus::Cancelled) {
+                    point.remaining += estimate;
+                    point.remaining_count += 1;
+                }
+            }
+            point
+        })
+        .collect()
+}
+
+impl BurndownPoint {
+    /// Burndown of a project's tasks, trashed ones aside, over `since..=until`.
+    pub async fn for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let tasks = sqlx::query_as!(
+            BurndownTask,
+            r#"SELECT id as "id!: Uuid", status as "status!: TaskStatus", estimate as "estimate: f64", created_at as "created_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE project_id = $1 AND deleted_at IS NULL"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+        let changes =
+            TaskEvent::find_field_changes_by_project_id(pool, project_id, &["status", "estimate"])
+                .await?;
+
+        Ok(compute(&tasks, &changes, &sample_times(since, until)))
+    }
+
+    /// Burndown of a sprint's tasks over `since..=until`. Tasks count from when they
+    /// joined the sprint, so scope added mid-sprint shows up as it was added.
+    pub async fn for_sprint(
+        pool: &SqlitePool,
+        sprint: &Sprint,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let tasks = sqlx::query_as!(
+            BurndownTask,
+            r#"SELECT t.id as "id!: Uuid", t.status as "status!: TaskStatus", t.estimate as "estimate: f64", MAX(t.created_at, st.created_at) as "created_at!: DateTime<Utc>"
+               FROM sprint_tasks st
+               JOIN tasks t ON t.id = st.task_id
+               WHERE st.sprint_id = $1 AND t.deleted_at IS NULL"#,
+            sprint.id
+        )
+        .fetch_all(pool)
+        .await?;
+        let changes = TaskEvent::find_field_changes_by_project_id(
+            pool,
+            sprint.project_id,
+            &["status", "estimate"],
+        )
+        .await?;
+
+        Ok(compute(&tasks, &changes, &sample_times(since, until)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::task_event::{TaskEventKind, TaskEventSource};
+
+    fn change(
+        task_id: Uuid,
+        field: &str,
+        old: &str,
+        new: &str,
+        created_at: DateTime<Utc>,
+    ) -> TaskEvent {
+        TaskEvent {
+            id: Uuid::new_v4(),
+            task_id,
+            kind: if field == "status" {
+                TaskEventKind::StatusChanged
+            } else {
+                TaskEventKind::Edited
+            },
+            source: TaskEventSource::User,
+            integration_id: None,
+            field: Some(field.to_string()),
+            old_value: Some(old.to_string()),
+            new_value: Some(new.to_string()),
+            created_at,
+        }
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("14d"), Some(Duration::days(14)));
+        assert_eq!(parse_range("2w"), Some(Duration::days(14)));
+        assert_eq!(parse_range("0d"), None);
+        assert_eq!(parse_range("2y"), None);
+        assert_eq!(parse_range("400d"), None);
+        assert_eq!(parse_range(""), None);
+    }
+
+    #[test]
+    fn test_compute_replays_status_and_estimate_changes() {
+        let start = Utc::now() - Duration::days(3);
+        let day = |n: i64| start + Duration::days(n);
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let tasks = vec![
+            // Estimated 3, re-estimated to 5 on day 1, done on day 2
+            BurndownTask {
+                id: a,
+                status: TaskStatus::Done,
+                estimate: Some(5.0),
+                created_at: start,
+            },
+            // Added to the project on day 1
+            BurndownTask {
+                id: b,
+                status: TaskStatus::InProgress,
+                estimate: Some(2.0),
+                created_at: day(1) - Duration::hours(1),
+            },
+        ];
+        let changes = vec![
+            change(a, "estimate", "3", "5", day(1) - Duration::minutes(30)),
+            change(
+                a,
+                "status",
+                "inprogress",
+                "done",
+                day(2) - Duration::minutes(30),
+            ),
+        ];
+
+        let points = compute(&tasks, &changes, &sample_times(start, day(3)));
+        let series: Vec<_> = points.iter().map(|p| (p.remaining, p.total)).collect();
+        assert_eq!(series, vec![(3.0, 3.0), (7.0, 7.0), (2.0, 7.0), (2.0, 7.0)]);
+        let counts: Vec<_> = points
+            .iter()
+            .map(|p| (p.remaining_count, p.total_count))
+            .collect();
+        assert_eq!(counts, vec![(1, 1), (2, 2), (1, 2), (1, 2)]);
+    }
+}
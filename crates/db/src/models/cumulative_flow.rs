@@ -0,0 +1,133 @@
+use std::{collections::HashMap, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    flow_metrics::FlowTask,
+    task::TaskStatus,
+    task_event::{self, TaskEvent},
+};
+
+/// How many of a project's tasks were in each status at one point in time.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS, ToSchema)]
+pub struct CumulativeFlowPoint {
+    pub at: DateTime<Utc>,
+    pub todo: i64,
+    pub inprogress: i64,
+    pub inreview: i64,
+    pub done: i64,
+    pub cancelled: i64,
+}
+
+/// Replay status changes to count the tasks in each status at each time. Tasks count
+/// from when they were created.
+pub fn compute(
+    tasks: &[FlowTask],
+    changes: &[TaskEvent],
+    times: &[DateTime<Utc>],
+) -> Vec<CumulativeFlowPoint> {
+    let mut by_task: HashMap<Uuid, Vec<&TaskEvent>> = HashMap::new();
+    for change in changes
+        .iter()
+        .filter(|change| change.field.as_deref() == Some("status"))
+    {
+        by_task.entry(change.task_id).or_default().push(change);
+    }
+
+    times
+        .iter()
+        .map(|&at| {
+            let mut point = CumulativeFlowPoint {
+                at,
+                ..Default::default()
+            };
+            for task in tasks.iter().filter(|task| task.created_at <= at) {
+                let history = by_task.get(&task.id).map(Vec::as_slice).unwrap_or_default();
+                let status = task_event::value_at(history, at, Some(task.status.to_string()))
+                    .and_then(|s| TaskStatus::from_str(&s).ok())
+                    .unwrap_or_else(|| task.status.clone());
+                *match status {
+                    TaskStatus::Todo => &mut point.todo,
+                    TaskStatus::InProgress => &mut point.inprogress,
+                    TaskStatus::InReview => &mut point.inreview,
+                    TaskStatus::Done => &mut point.done,
+                    TaskStatus::Cancelled => &mut point.cancelled,
+                } += 1;
+            }
+            point
+        })
+        .collect()
+}
+
+impl CumulativeFlowPoint {
+    /// Status counts of a project's tasks, trashed ones aside, at `times`.
+    pub async fn for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        times: &[DateTime<Utc>],
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let tasks = sqlx::query_as!(
+            FlowTask,
+            r#"SELECT id as "id!: Uuid", status as "status!: TaskStatus", created_at as "created_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE project_id = $1 AND deleted_at IS NULL"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+        let changes =
+            TaskEvent::find_field_changes_by_project_id(pool, project_id, &["status"]).await?;
+
+        Ok(compute(&tasks, &changes, times))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::models::task_event::{TaskEventKind, TaskEventSource};
+
+    #[test]
+    fn test_compute_counts_tasks_by_status_at_each_time() {
+        let start = Utc::now() - Duration::days(2);
+        let day = |n: i64| start + Duration::days(n);
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let tasks = vec![
+            FlowTask {
+                id: a,
+                status: TaskStatus::Done,
+                created_at: start,
+            },
+            FlowTask {
+                id: b,
+                status: TaskStatus::Todo,
+                created_at: day(1) - Duration::hours(1),
+            },
+        ];
+        let changes = vec![TaskEvent {
+            id: Uuid::new_v4(),
+            task_id: a,
+            kind: TaskEventKind::StatusChanged,
+            source: TaskEventSource::User,
+            integration_id: None,
+            field: Some("status".to_string()),
+            old_value: Some("inprogress".to_string()),
+            new_value: Some("done".to_string()),
+            created_at: day(2) - Duration::hours(1),
+        }];
+
+        let points = compute(&tasks, &changes, &[start, day(1), day(2)]);
+        let series: Vec<_> = points
+            .iter()
+            .map(|p| (p.todo, p.inprogress, p.done))
+            .collect();
+        assert_eq!(series, vec![(0, 1, 0), (1, 1, 0), (1, 0, 1)]);
+    }
+}
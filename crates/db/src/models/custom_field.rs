@@ -0,0 +1,302 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "custom_field_type", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum CustomFieldType {
+    Text,
+    Number,
+    Select,
+    Date,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct CustomField {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub field_type: CustomFieldType,
+    /// Allowed values of a `select` field; empty for other types
+    #[ts(type = "Array<string>")]
+    #[schema(value_type = Vec<String>)]
+    pub options: Json<Vec<String>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateCustomField {
+    pub name: String,
+    pub field_type: CustomFieldType,
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+/// The type of a field cannot change, since existing values would no longer fit it.
+/// Values no longer among a `select` field's options are kept until changed.
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct UpdateCustomField {
+    pub name: Option<String>,
+    pub options: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct SetCustomFieldValue {
+    pub value: String,
+}
+
+/// A custom field value set on a task, with its field's definition.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskCustomFieldValue {
+    pub field_id: Uuid,
+    pub name: String,
+    pub field_type: CustomFieldType,
+    pub value: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CustomField {
+    /// Normalize a raw value for this field: numbers in canonical form, dates as
+    /// `YYYY-MM-DD` and select values spelled as in the options. Returns a description
+    /// of the expected format when the value does not fit.
+    pub fn normalize_value(&self, raw: &str) -> Result<String, String> {
+        let raw = raw.trim();
+        if raw.is_empty() {
+            return Err("value must not be empty".to_string());
+        }
+        match self.field_type {
+            CustomFieldType::Text => Ok(raw.to_string()),
+            CustomFieldType::Number => raw
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite())
+                .map(|n| n.to_string())
+                .ok_or_else(|| "expected a number".to_string()),
+            CustomFieldType::Date => NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                .or_else(|_| DateTime::parse_from_rfc3339(raw).map(|d| d.date_naive()))
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .map_err(|_| "expected a date as YYYY-MM-DD".to_string()),
+            CustomFieldType::Select => self
+                .options
+                .iter()
+                .find(|option| option.eq_ignore_ascii_case(raw))
+                .cloned()
+                .ok_or_else(|| format!("expected one of: {}", self.options.join(", "))),
+        }
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CustomField,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, field_type as "field_type!: CustomFieldType", options as "options!: Json<Vec<String>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM custom_fields
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CustomField,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, field_type as "field_type!: CustomFieldType", options as "options!: Json<Vec<String>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM custom_fields
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Names are unique per project, ignoring case.
+    pub async fn find_by_name(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CustomField,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, field_type as "field_type!: CustomFieldType", options as "options!: Json<Vec<String>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM custom_fields
+               WHERE project_id = $1 AND name = $2 COLLATE NOCASE"#,
+            project_id,
+            name
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateCustomField,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let options = Json(&data.options);
+        sqlx::query_as!(
+            CustomField,
+            r#"INSERT INTO custom_fields (id, project_id, name, field_type, options)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, field_type as "field_type!: CustomFieldType", options as "options!: Json<Vec<String>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.name,
+            data.field_type,
+            options
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateCustomField,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let options = Json(data.options.as_ref().unwrap_or(&existing.options.0));
+
+        sqlx::query_as!(
+            CustomField,
+            r#"UPDATE custom_fields
+               SET name = $2, options = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, field_type as "field_type!: CustomFieldType", options as "options!: Json<Vec<String>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            options
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM custom_fields WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn find_values_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<TaskCustomFieldValue>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskCustomFieldValue,
+            r#"SELECT f.id as "field_id!: Uuid", f.name, f.field_type as "field_type!: CustomFieldType", v.value, v.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_custom_field_values v
+               JOIN custom_fields f ON f.id = v.field_id
+               WHERE v.task_id = $1
+               ORDER BY f.name ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Set the task's value for this field. `value` must already be normalized.
+    pub async fn set_value(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        field_id: Uuid,
+        value: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO task_custom_field_values (task_id, field_id, value)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(task_id, field_id) DO UPDATE SET
+                   value = excluded.value,
+                   updated_at = datetime('now', 'subsec')"#,
+            task_id,
+            field_id,
+            value
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn clear_value(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        field_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM task_custom_field_values WHERE task_id = $1 AND field_id = $2",
+            task_id,
+            field_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(field_type: CustomFieldType, options: &[&str]) -> CustomField {
+        CustomField {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            name: "Field".to_string(),
+            field_type,
+            options: Json(options.iter().map(|o| o.to_string()).collect()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_value_by_type() {
+        let number = field(CustomFieldType::Number, &[]);
+        assert_eq!(number.normalize_value(" 3.50 ").unwrap(), "3.5");
+        assert_eq!(number.normalize_value("7").unwrap(), "7");
+        assert!(number.normalize_value("NaN").is_err());
+
+        let date = field(CustomFieldType::Date, &[]);
+        assert_eq!(date.normalize_value("2026-01-07").unwrap(), "2026-01-07");
+        assert_eq!(
+            date.normalize_value("2026-01-07T23:00:00+00:00").unwrap(),
+            "2026-01-07"
+        );
+        assert!(date.normalize_value("07/01/2026").is_err());
+
+        let select = field(CustomFieldType::Select, &["Frontend", "Backend"]);
+        assert_eq!(select.normalize_value("backend").unwrap(), "Backend");
+        assert!(select.normalize_value("Infra").is_err());
+
+        let text = field(CustomFieldType::Text, &[]);
+        assert!(text.normalize_value("   ").is_err());
+    }
+}
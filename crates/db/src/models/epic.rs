@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::task::{Task, TaskPriority, TaskStatus};
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "epic_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum EpicKind {
+    /// A larger initiative spanning many tasks
+    Epic,
+    /// A set of tasks due together
+    Milestone,
+}
+
+/// Groups tasks of a project across statuses.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct Epic {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub kind: EpicKind,
+    pub title: String,
+    pub description: Option<String>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// How far an epic's tasks have got. Cancelled tasks are left out entirely.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS, ToSchema)]
+pub struct EpicProgress {
+    pub total: i64,
+    pub done: i64,
+    /// Sum of the tasks' estimates
+    pub estimate_total: f64,
+    /// Estimate of the tasks that are not done yet
+    pub estimate_remaining: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct EpicWithProgress {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub epic: Epic,
+    pub progress: EpicProgress,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateEpic {
+    #[serde(default)]
+    #[ts(optional)]
+    pub kind: Option<EpicKind>,
+    pub title: String,
+    #[serde(default)]
+    #[ts(optional)]
+    pub description: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct UpdateEpic {
+    #[serde(default)]
+    #[ts(optional)]
+    pub kind: Option<EpicKind>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub title: Option<String>,
+    /// An empty description removes it
+    #[serde(default)]
+    #[ts(optional)]
+    pub description: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub due_at: Option<DateTime<Utc>>,
+    /// Remove the due date; takes precedence over `due_at`
+    #[serde(default)]
+    #[ts(optional)]
+    pub clear_due_at: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct SetTaskEpic {
+    pub epic_id: Uuid,
+}
+
+impl EpicProgress {
+    /// Roll up the status and estimate of an epic's tasks.
+    pub fn from_tasks(tasks: impl IntoIterator<Item = (TaskStatus, Option<f64>)>) -> Self {
+        let mut progress = Self::default();
+        for (status, estimate) in tasks {
+            let estimate = estimate.unwrap_or(0.0);
+            match status {
+                TaskStatus::Cancelled => continue,
+                TaskStatus::Done => progress.done += 1,
+                _ => progress.estimate_remaining += estimate,
+            }
+            progress.total += 1;
+            progress.estimate_total += estimate;
+        }
+        progress
+    }
+}
+
+impl Epic {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Epic,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", kind as "kind!: EpicKind", title, description, due_at as "due_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM epics
+               WHERE project_id = $1
+               ORDER BY due_at IS NULL, due_at ASC, created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Epic,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", kind as "kind!: EpicKind", title, description, due_at as "due_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM epics
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Epic,
+            r#"SELECT e.id as "id!: Uuid", e.project_id as "project_id!: Uuid", e.kind as "kind!: EpicKind", e.title, e.description, e.due_at as "due_at: DateTime<Utc>", e.created_at as "created_at!: DateTime<Utc>", e.updated_at as "updated_at!: DateTime<Utc>"
+               FROM epics e
+               JOIN epic_tasks et ON et.epic_id = e.id
+               WHERE et.task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Progress of every epic in the project. Archived and trashed tasks don't count.
+    pub async fn find_progress_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<HashMap<Uuid, EpicProgress>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT et.epic_id as "epic_id!: Uuid", t.status as "status!: TaskStatus", t.estimate as "estimate: f64"
+               FROM epic_tasks et
+               JOIN tasks t ON t.id = et.task_id
+               WHERE t.project_id = $1
+                 AND t.archived_at IS NULL
+                 AND t.deleted_at IS NULL"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut by_epic: HashMap<Uuid, Vec<(TaskStatus, Option<f64>)>> = HashMap::new();
+        for row in rows {
+            by_epic
+                .entry(row.epic_id)
+                .or_default()
+                .push((row.status, row.estimate));
+        }
+        Ok(by_epic
+            .into_iter()
+            .map(|(epic_id, tasks)| (epic_id, EpicProgress::from_tasks(tasks)))
+            .collect())
+    }
+
+    /// The epic's tasks, newest first. Archived and trashed tasks are left out.
+    pub async fn find_tasks(pool: &SqlitePool, epic_id: Uuid) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.column_id as "column_id: Uuid", t.parent_workspace_id as "parent_workspace_id: Uuid", t.shared_task_id as "shared_task_id: Uuid", t.due_at as "due_at: DateTime<Utc>", t.priority as "priority!: TaskPriority", t.estimate as "estimate: f64", t.assignee_id as "assignee_id: Uuid", t.cover_color, t.cover_attachment_id as "cover_attachment_id: Uuid", t.archived_at as "archived_at: DateTime<Utc>", t.deleted_at as "deleted_at: DateTime<Utc>", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>", t.version as "version!: i64"
+               FROM tasks t
+               JOIN epic_tasks et ON et.task_id = t.id
+               WHERE et.epic_id = $1
+                 AND t.archived_at IS NULL
+                 AND t.deleted_at IS NULL
+               ORDER BY t.created_at DESC"#,
+            epic_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateEpic,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let kind = data.kind.unwrap_or(EpicKind::Epic);
+        let description = data
+            .description
+            .as_deref()
+            .filter(|description| !description.trim().is_empty());
+        sqlx::query_as!(
+            Epic,
+            r#"INSERT INTO epics (id, project_id, kind, title, description, due_at)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", kind as "kind!: EpicKind", title, description, due_at as "due_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            kind,
+            data.title,
+            description,
+            data.due_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateEpic,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let kind = data.kind.unwrap_or(existing.kind);
+        let title = data.title.as_ref().unwrap_or(&existing.title);
+        let description = match &data.description {
+            Some(description) => Some(description).filter(|d| !d.trim().is_empty()),
+            None => existing.description.as_ref(),
+        };
+        let due_at = if data.clear_due_at.unwrap_or(false) {
+            None
+        } else {
+            data.due_at.or(existing.due_at)
+        };
+
+        sqlx::query_as!(
+            Epic,
+            r#"UPDATE epics
+               SET kind = $2, title = $3, description = $4, due_at = $5, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", kind as "kind!: EpicKind", title, description, due_at as "due_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            kind,
+            title,
+            description,
+            due_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Delete the epic. Its tasks are kept and no longer belong to an epic.
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM epics WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Put a task in the epic, taking it out of any other.
+    pub async fn add_task(
+        pool: &SqlitePool,
+        epic_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO epic_tasks (task_id, epic_id)
+               VALUES ($1, $2)
+               ON CONFLICT(task_id) DO UPDATE SET epic_id = excluded.epic_id, created_at = datetime('now', 'subsec')"#,
+            task_id,
+            epic_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn remove_task(pool: &SqlitePool, task_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM epic_tasks WHERE task_id = $1", task_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_from_tasks() {
+        let tasks = [
+            (TaskStatus::Done, Some(3.0)),
+            (TaskStatus::InProgress, Some(5.0)),
+            (TaskStatus::Todo, None),
+            (TaskStatus::Cancelled, Some(8.0)),
+        ];
+        assert_eq!(
+            EpicProgress::from_tasks(tasks),
+            EpicProgress {
+                total: 3,
+                done: 1,
+                estimate_total: 8.0,
+                estimate_remaining: 5.0,
+            }
+        );
+    }
+}
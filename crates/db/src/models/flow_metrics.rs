@@ -0,0 +1,257 @@
+use std::{collections::HashMap, str::FromStr};
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{task::TaskStatus, task_event::TaskEvent};
+
+/// How long a set of tasks took, in hours.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, TS, ToSchema)]
+pub struct DurationStats {
+    /// Tasks measured
+    pub count: i64,
+    /// `None` when no task was measured, as are the percentiles
+    pub mean_hours: Option<f64>,
+    pub p50_hours: Option<f64>,
+    pub p75_hours: Option<f64>,
+    pub p85_hours: Option<f64>,
+    pub p95_hours: Option<f64>,
+}
+
+/// Tasks finished in the week starting Monday `week_start`, UTC.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, ToSchema)]
+pub struct ThroughputWeek {
+    pub week_start: DateTime<Utc>,
+    pub completed: i64,
+}
+
+/// Flow metrics of the tasks finished in `since..=until`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, ToSchema)]
+pub struct FlowMetrics {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    /// From creation to done
+    pub lead_time: DurationStats,
+    /// From work first starting, i.e. moving to in progress or in review, to done. Tasks
+    /// that went straight to done are left out.
+    pub cycle_time: DurationStats,
+    /// One entry per week overlapping the range, oldest first
+    pub throughput: Vec<ThroughputWeek>,
+}
+
+/// What flow metrics need to know about a task as it is now.
+#[derive(Debug, Clone)]
+pub struct FlowTask {
+    pub id: Uuid,
+    pub status: TaskStatus,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Midnight UTC of the Monday starting the week of `at`.
+pub fn week_start(at: DateTime<Utc>) -> DateTime<Utc> {
+    let date = at.date_naive() - Duration::days(at.weekday().num_days_from_monday() as i64);
+    date.and_time(NaiveTime::MIN).and_utc()
+}
+
+/// The `percentile`th smallest of the sorted `values`, by nearest rank.
+fn percentile(sorted: &[f64], percentile: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (percentile / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted.get(rank.clamp(1, sorted.len()) - 1).copied()
+}
+
+fn stats(mut hours: Vec<f64>) -> DurationStats {
+    hours.sort_by(f64::total_cmp);
+    let count = hours.len();
+    DurationStats {
+        count: count as i64,
+        mean_hours: (count > 0).then(|| hours.iter().sum::<f64>() / count as f64),
+        p50_hours: percentile(&hours, 50.0),
+        p75_hours: percentile(&hours, 75.0),
+        p85_hours: percentile(&hours, 85.0),
+        p95_hours: percentile(&hours, 95.0),
+    }
+}
+
+fn new_status(change: &TaskEvent) -> Option<TaskStatus> {
+    change
+        .new_value
+        .as_deref()
+        .and_then(|value| TaskStatus::from_str(value).ok())
+}
+
+fn hours(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).num_seconds().max(0) as f64 / 3600.0
+}
+
+/// Replay status changes to find when each done task was finished, and when work on it
+/// started. A task reopened and finished again counts from its last move to done.
+pub fn compute(
+    tasks: &[FlowTask],
+    changes: &[TaskEvent],
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> FlowMetrics {
+    let mut by_task: HashMap<Uuid, Vec<&TaskEvent>> = HashMap::new();
+    for change in changes
+        .iter()
+        .filter(|change| change.field.as_deref() == Some("status"))
+    {
+        by_task.entry(change.task_id).or_default().push(change);
+    }
+
+    let mut lead_times = Vec::new();
+    let mut cycle_times = Vec::new();
+    let mut completions = Vec::new();
+    for task in tasks.iter().filter(|task| task.status == TaskStatus::Done) {
+        let history = by_task.get(&task.id).map(Vec::as_slice).unwrap_or_default();
+        let Some(completed_at) = history
+            .iter()
+            .rev()
+            .find(|change| new_status(change) == Some(TaskStatus::Done))
+            .map(|change| change.created_at)
+            .filter(|at| (since..=until).contains(at))
+        else {
+            continue;
+        };
+        completions.push(completed_at);
+        lead_times.push(hours(task.created_at, completed_at));
+        let started_at = history
+            .iter()
+            .filter(|change| change.created_at <= completed_at)
+            .find(|change| {
+                matches!(
+                    new_status(change),
+                    Some(TaskStatus::InProgress | TaskStatus::InReview)
+                )
+            })
+            .map(|change| change.created_at);
+        if let Some(started_at) = started_at {
+            cycle_times.push(hours(started_at, completed_at));
+        }
+    }
+
+    let mut throughput = Vec::new();
+    let mut week = week_start(since);
+    while week <= until {
+        let next = week + Duration::days(7);
+        throughput.push(ThroughputWeek {
+            week_start: week,
+            completed: completions
+                .iter()
+                .filter(|at| (week..next).contains(*at))
+                .count() as i64,
+        });
+        week = next;
+    }
+
+    FlowMetrics {
+        since,
+        until,
+        lead_time: stats(lead_times),
+        cycle_time: stats(cycle_times),
+        throughput,
+    }
+}
+
+impl FlowMetrics {
+    /// Flow metrics of a project's tasks, trashed ones aside, finished in `since..=until`.
+    pub async fn for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Self, sqlx::Error> {
+        let tasks = sqlx::query_as!(
+            FlowTask,
+            r#"SELECT id as "id!: Uuid", status as "status!: TaskStatus", created_at as "created_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE project_id = $1 AND deleted_at IS NULL"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+        let changes =
+            TaskEvent::find_field_changes_by_project_id(pool, project_id, &["status"]).await?;
+
+        Ok(compute(&tasks, &changes, since, until))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::models::task_event::{TaskEventKind, TaskEventSource};
+
+    fn moved(task_id: Uuid, old: &str, new: &str, created_at: DateTime<Utc>) -> TaskEvent {
+        TaskEvent {
+            id: Uuid::new_v4(),
+            task_id,
+            kind: TaskEventKind::StatusChanged,
+            source: TaskEventSource::User,
+            integration_id: None,
+            field: Some("status".to_string()),
+            old_value: Some(old.to_string()),
+            new_value: Some(new.to_string()),
+            created_at,
+        }
+    }
+
+    #[test]
+    fn test_percentile_by_nearest_rank() {
+        let values = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(percentile(&values, 50.0), Some(2.0));
+        assert_eq!(percentile(&values, 95.0), Some(4.0));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+
+    #[test]
+    fn test_compute_measures_finished_tasks() {
+        // A Monday
+        let start = Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap();
+        let at = |hours: i64| start + Duration::hours(hours);
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let tasks = vec![
+            FlowTask {
+                id: a,
+                status: TaskStatus::Done,
+                created_at: start,
+            },
+            // Went straight to done
+            FlowTask {
+                id: b,
+                status: TaskStatus::Done,
+                created_at: start,
+            },
+            // Still open
+            FlowTask {
+                id: c,
+                status: TaskStatus::InProgress,
+                created_at: start,
+            },
+        ];
+        let changes = vec![
+            moved(a, "todo", "inprogress", at(24)),
+            moved(a, "inprogress", "done", at(48)),
+            moved(b, "todo", "done", at(24 * 8)),
+            moved(c, "todo", "inprogress", at(1)),
+        ];
+
+        let metrics = compute(&tasks, &changes, start, at(24 * 10));
+        assert_eq!(metrics.lead_time.count, 2);
+        assert_eq!(metrics.lead_time.p50_hours, Some(48.0));
+        assert_eq!(metrics.lead_time.p95_hours, Some(192.0));
+        assert_eq!(metrics.cycle_time.count, 1);
+        assert_eq!(metrics.cycle_time.mean_hours, Some(24.0));
+        let weekly: Vec<i64> = metrics.throughput.iter().map(|w| w.completed).collect();
+        assert_eq!(weekly, vec![1, 1]);
+    }
+}
@@ -0,0 +1,124 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+
+/// A request made with an `Idempotency-Key` header, and its reply once there is one.
+#[derive(Debug, Clone, FromRow)]
+pub struct IdempotencyKey {
+    /// Who sent the request, e.g. `api_key:<id>`; clients can't see each other's keys
+    pub principal: String,
+    pub key: String,
+    /// Hash of the method, path and body, to catch a key reused for another request
+    pub request_hash: String,
+    /// `None` while the first request is still being handled
+    pub status_code: Option<i64>,
+    pub content_type: Option<String>,
+    pub response_body: Option<Vec<u8>>,
+    /// While in progress, when the request's claim lapses and a retry may take the key over
+    pub locked_until: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl IdempotencyKey {
+    pub async fn find(
+        pool: &SqlitePool,
+        principal: &str,
+        key: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            IdempotencyKey,
+            r#"SELECT principal, key, request_hash, status_code, content_type, response_body, locked_until as "locked_until: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>"
+               FROM idempotency_keys
+               WHERE principal = $1 AND key = $2"#,
+            principal,
+            key
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Claim the key for a request about to be handled, until `locked_until`. A claim whose
+    /// request never finished is taken over once it lapses. `false` when the key is taken,
+    /// e.g. by a concurrent retry, or already has a reply.
+    pub async fn begin(
+        pool: &SqlitePool,
+        principal: &str,
+        key: &str,
+        request_hash: &str,
+        locked_until: DateTime<Utc>,
+    ) -> Result<bool, sqlx::Error> {
+        let now = Utc::now();
+        let result = sqlx::query!(
+            r#"INSERT INTO idempotency_keys (principal, key, request_hash, locked_until)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(principal, key) DO UPDATE
+               SET request_hash = excluded.request_hash,
+                   locked_until = excluded.locked_until,
+                   created_at = datetime('now', 'subsec')
+               WHERE idempotency_keys.status_code IS NULL
+                 AND (idempotency_keys.locked_until IS NULL
+                      OR datetime(idempotency_keys.locked_until) <= datetime($5))"#,
+            principal,
+            key,
+            request_hash,
+            locked_until,
+            now
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Store the reply to replay for retries.
+    pub async fn complete(
+        pool: &SqlitePool,
+        principal: &str,
+        key: &str,
+        status_code: i64,
+        content_type: Option<&str>,
+        response_body: &[u8],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE idempotency_keys
+               SET status_code = $3, content_type = $4, response_body = $5, locked_until = NULL
+               WHERE principal = $1 AND key = $2"#,
+            principal,
+            key,
+            status_code,
+            content_type,
+            response_body
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Give back the claim [`begin`](Self::begin) made until `locked_until`, so the request
+    /// can be retried for real. A claim another request has since taken over is left alone.
+    pub async fn release(
+        pool: &SqlitePool,
+        principal: &str,
+        key: &str,
+        locked_until: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"DELETE FROM idempotency_keys
+               WHERE principal = $1 AND key = $2 AND status_code IS NULL AND locked_until = $3"#,
+            principal,
+            key,
+            locked_until
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Forget keys older than a day; retries come long before that.
+    pub async fn delete_expired(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM idempotency_keys WHERE created_at < datetime('now', '-24 hours')"
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,364 @@
+use std::{collections::HashMap, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use strum_macros::{Display, EnumString};
+use thiserror::Error;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::task::{TaskPriority, TaskStatus};
+
+/// Placeholder returned instead of stored secret values.
+pub const REDACTED_SECRET: &str = "********";
+
+#[derive(Debug, Error)]
+pub enum IntegrationError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Integration not found")]
+    NotFound,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "integration_provider", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum IntegrationProvider {
+    YouTrack,
+    Jira,
+    GitHub,
+}
+
+/// Task field a [`FieldMappingRule`] writes to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, Display, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum MappedField {
+    Title,
+    Description,
+    Status,
+    Priority,
+    /// A user name or email, matched against local users
+    Assignee,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct FieldMappingRule {
+    /// Dot-separated path into the raw provider payload, e.g. `fields.customfield_10010.value`.
+    /// A `key=value` segment selects the first array element whose `key` equals `value`.
+    pub source: String,
+    pub target: MappedField,
+    /// Remote value -> VK value translations; unlisted values pass through unchanged
+    #[serde(default)]
+    pub values: HashMap<String, String>,
+    /// Used when the remote value is missing or empty
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+/// Per-integration rules applied to every synced issue after the provider's built-in
+/// parsing. Later rules win when several target the same field.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, ToSchema)]
+pub struct FieldMapping {
+    #[serde(default)]
+    pub rules: Vec<FieldMappingRule>,
+}
+
+impl FieldMapping {
+    /// Check that every rule has a source and that status and priority rules only produce
+    /// valid values.
+    pub fn validate(&self) -> Result<(), String> {
+        for rule in &self.rules {
+            if rule.source.trim().is_empty() {
+                return Err(format!("Mapping for '{}' has an empty source", rule.target));
+            }
+            for value in rule.values.values().chain(rule.default.as_ref()) {
+                let valid = match rule.target {
+                    MappedField::Status => TaskStatus::from_str(value).is_ok(),
+                    MappedField::Priority => TaskPriority::from_str(value).is_ok(),
+                    MappedField::Title | MappedField::Description | MappedField::Assignee => true,
+                };
+                if !valid {
+                    return Err(format!(
+                        "'{value}' is not a valid task {} in mapping for '{}'",
+                        rule.target, rule.source
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Stored provider configuration. Secrets are held in plain form, so this type is
+/// never serialized directly; use [`Integration::redacted`] for API responses.
+#[derive(Clone, FromRow)]
+pub struct Integration {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub provider: IntegrationProvider,
+    pub name: String,
+    pub base_url: String,
+    pub config: Json<Value>,
+    pub secrets: Json<HashMap<String, String>>,
+    pub enabled: bool,
+    pub field_mapping: Json<FieldMapping>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl std::fmt::Debug for Integration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Integration")
+            .field("id", &self.id)
+            .field("project_id", &self.project_id)
+            .field("provider", &self.provider)
+            .field("name", &self.name)
+            .field("base_url", &self.base_url)
+            .field("secrets", &self.secrets.keys().collect::<Vec<_>>())
+            .field("enabled", &self.enabled)
+            .finish_non_exhaustive()
+    }
+}
+
+/// API representation of an integration with every secret value replaced by
+/// [`REDACTED_SECRET`].
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct IntegrationResponse {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub provider: IntegrationProvider,
+    pub name: String,
+    pub base_url: String,
+    #[schema(value_type = Object)]
+    pub config: Value,
+    pub secrets: HashMap<String, String>,
+    pub enabled: bool,
+    pub field_mapping: FieldMapping,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateIntegration {
+    pub project_id: Uuid,
+    pub provider: IntegrationProvider,
+    pub name: String,
+    pub base_url: String,
+    #[schema(value_type = Option<Object>)]
+    pub config: Option<Value>,
+    pub secrets: Option<HashMap<String, String>>,
+    pub enabled: Option<bool>,
+    pub field_mapping: Option<FieldMapping>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct UpdateIntegration {
+    pub name: Option<String>,
+    pub base_url: Option<String>,
+    #[schema(value_type = Option<Object>)]
+    pub config: Option<Value>,
+    /// Secrets to change. A `null` value removes the secret and a value equal to the
+    /// redaction placeholder keeps the stored one, so redacted responses can be sent back as-is.
+    pub secrets: Option<HashMap<String, Option<String>>>,
+    pub enabled: Option<bool>,
+    /// Replaces the whole mapping when set
+    pub field_mapping: Option<FieldMapping>,
+}
+
+/// One step of an integration check: `config`, `credentials` or `scope`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct IntegrationCheckStep {
+    pub name: String,
+    pub passed: bool,
+    pub error: Option<String>,
+}
+
+/// Whether an integration is set up well enough to sync, disabled or not.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct IntegrationCheck {
+    pub integration_id: Uuid,
+    pub name: String,
+    pub provider: IntegrationProvider,
+    pub enabled: bool,
+    /// In the order they ran; the steps after a failed one are left out
+    pub steps: Vec<IntegrationCheckStep>,
+    pub passed: bool,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl Integration {
+    pub fn redacted(&self) -> IntegrationResponse {
+        IntegrationResponse {
+            id: self.id,
+            project_id: self.project_id,
+            provider: self.provider,
+            name: self.name.clone(),
+            base_url: self.base_url.clone(),
+            config: self.config.0.clone(),
+            secrets: self
+                .secrets
+                .keys()
+                .map(|key| (key.clone(), REDACTED_SECRET.to_string()))
+                .collect(),
+            enabled: self.enabled,
+            field_mapping: self.field_mapping.0.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+
+    pub fn secret(&self, key: &str) -> Option<&str> {
+        self.secrets
+            .get(key)
+            .map(String::as_str)
+            .filter(|s| !s.is_empty())
+    }
+
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Integration,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", provider as "provider!: IntegrationProvider", name, base_url, config as "config!: Json<Value>", secrets as "secrets!: Json<HashMap<String, String>>", enabled as "enabled!: bool", field_mapping as "field_mapping!: Json<FieldMapping>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM integrations
+               ORDER BY created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Integration,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", provider as "provider!: IntegrationProvider", name, base_url, config as "config!: Json<Value>", secrets as "secrets!: Json<HashMap<String, String>>", enabled as "enabled!: bool", field_mapping as "field_mapping!: Json<FieldMapping>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM integrations
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Integration,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", provider as "provider!: IntegrationProvider", name, base_url, config as "config!: Json<Value>", secrets as "secrets!: Json<HashMap<String, String>>", enabled as "enabled!: bool", field_mapping as "field_mapping!: Json<FieldMapping>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM integrations
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateIntegration,
+    ) -> Result<Self, IntegrationError> {
+        let id = Uuid::new_v4();
+        let config = Json(data.config.clone().unwrap_or_else(|| serde_json::json!({})));
+        let secrets = Json(data.secrets.clone().unwrap_or_default());
+        let enabled = data.enabled.unwrap_or(true);
+        let field_mapping = Json(data.field_mapping.clone().unwrap_or_default());
+        let integration = sqlx::query_as!(
+            Integration,
+            r#"INSERT INTO integrations (id, project_id, provider, name, base_url, config, secrets, enabled, field_mapping)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", provider as "provider!: IntegrationProvider", name, base_url, config as "config!: Json<Value>", secrets as "secrets!: Json<HashMap<String, String>>", enabled as "enabled!: bool", field_mapping as "field_mapping!: Json<FieldMapping>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.provider,
+            data.name,
+            data.base_url,
+            config,
+            secrets,
+            enabled,
+            field_mapping
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(integration)
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateIntegration,
+    ) -> Result<Self, IntegrationError> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(IntegrationError::NotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let base_url = data.base_url.as_ref().unwrap_or(&existing.base_url);
+        let config = Json(data.config.clone().unwrap_or(existing.config.0));
+        let enabled = data.enabled.unwrap_or(existing.enabled);
+        let field_mapping = Json(
+            data.field_mapping
+                .clone()
+                .unwrap_or(existing.field_mapping.0),
+        );
+
+        let mut secrets = existing.secrets.0;
+        if let Some(changes) = &data.secrets {
+            for (key, value) in changes {
+                match value {
+                    Some(value) if value == REDACTED_SECRET => {}
+                    Some(value) => {
+                        secrets.insert(key.clone(), value.clone());
+                    }
+                    None => {
+                        secrets.remove(key);
+                    }
+                }
+            }
+        }
+        let secrets = Json(secrets);
+
+        let integration = sqlx::query_as!(
+            Integration,
+            r#"UPDATE integrations
+               SET name = $2, base_url = $3, config = $4, secrets = $5, enabled = $6, field_mapping = $7, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", provider as "provider!: IntegrationProvider", name, base_url, config as "config!: Json<Value>", secrets as "secrets!: Json<HashMap<String, String>>", enabled as "enabled!: bool", field_mapping as "field_mapping!: Json<FieldMapping>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            base_url,
+            config,
+            secrets,
+            enabled,
+            field_mapping
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(integration)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM integrations WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
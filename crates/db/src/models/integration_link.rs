@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Task field name -> remote value as a string.
+pub type RemoteValues = HashMap<String, Option<String>>;
+
+/// Association between a local task and the remote issue it mirrors.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct IntegrationLink {
+    pub id: Uuid,
+    pub integration_id: Uuid,
+    pub task_id: Uuid,
+    pub external_id: String,
+    pub external_url: Option<String>,
+    pub remote_updated_at: Option<DateTime<Utc>>,
+    /// Remote field values as of the last sync, the base for three-way conflict detection
+    #[ts(type = "{ [key in string]?: string | null } | null")]
+    pub remote_values: Option<Json<RemoteValues>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl IntegrationLink {
+    pub async fn find_by_external_id(
+        pool: &SqlitePool,
+        integration_id: Uuid,
+        external_id: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            IntegrationLink,
+            r#"SELECT id as "id!: Uuid", integration_id as "integration_id!: Uuid", task_id as "task_id!: Uuid", external_id, external_url, remote_updated_at as "remote_updated_at: DateTime<Utc>", remote_values as "remote_values: Json<RemoteValues>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM integration_links
+               WHERE integration_id = $1 AND external_id = $2"#,
+            integration_id,
+            external_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            IntegrationLink,
+            r#"SELECT id as "id!: Uuid", integration_id as "integration_id!: Uuid", task_id as "task_id!: Uuid", external_id, external_url, remote_updated_at as "remote_updated_at: DateTime<Utc>", remote_values as "remote_values: Json<RemoteValues>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM integration_links
+               WHERE task_id = $1
+               ORDER BY created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_integration_id(
+        pool: &SqlitePool,
+        integration_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            IntegrationLink,
+            r#"SELECT id as "id!: Uuid", integration_id as "integration_id!: Uuid", task_id as "task_id!: Uuid", external_id, external_url, remote_updated_at as "remote_updated_at: DateTime<Utc>", remote_values as "remote_values: Json<RemoteValues>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM integration_links
+               WHERE integration_id = $1
+               ORDER BY created_at ASC"#,
+            integration_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Insert a link, or refresh the remote metadata and baseline of an existing one.
+    pub async fn upsert<'e, E>(
+        executor: E,
+        integration_id: Uuid,
+        task_id: Uuid,
+        external_id: &str,
+        external_url: Option<&str>,
+        remote_updated_at: Option<DateTime<Utc>>,
+        remote_values: &RemoteValues,
+    ) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let id = Uuid::new_v4();
+        let remote_values = Json(remote_values);
+        sqlx::query_as!(
+            IntegrationLink,
+            r#"INSERT INTO integration_links (id, integration_id, task_id, external_id, external_url, remote_updated_at, remote_values)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               ON CONFLICT(integration_id, external_id) DO UPDATE SET
+                   task_id = excluded.task_id,
+                   external_url = excluded.external_url,
+                   remote_updated_at = excluded.remote_updated_at,
+                   remote_values = excluded.remote_values,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid", integration_id as "integration_id!: Uuid", task_id as "task_id!: Uuid", external_id, external_url, remote_updated_at as "remote_updated_at: DateTime<Utc>", remote_values as "remote_values: Json<RemoteValues>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            integration_id,
+            task_id,
+            external_id,
+            external_url,
+            remote_updated_at,
+            remote_values
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    pub async fn update_remote_values(
+        pool: &SqlitePool,
+        id: Uuid,
+        remote_values: &RemoteValues,
+    ) -> Result<(), sqlx::Error> {
+        let remote_values = Json(remote_values);
+        sqlx::query!(
+            r#"UPDATE integration_links
+               SET remote_values = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            remote_values
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM integration_links WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
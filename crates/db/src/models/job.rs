@@ -0,0 +1,200 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type, types::Json};
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, EnumString, Display)]
+#[sqlx(type_name = "job_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    /// Out of attempts
+    Failed,
+}
+
+/// A unit of background work, kept until it has run so a restart doesn't lose it.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct Job {
+    pub id: Uuid,
+    /// Which handler runs the job, e.g. `integration_sync`
+    pub kind: String,
+    pub payload: Json<Value>,
+    pub status: JobStatus,
+    pub attempts: i64,
+    pub max_attempts: i64,
+    pub unique_key: Option<String>,
+    /// When the job is due, or its next attempt after a failure
+    pub run_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateJob {
+    pub kind: String,
+    pub payload: Value,
+    pub max_attempts: i64,
+    /// Skip the job while another with the same key is queued or running
+    pub unique_key: Option<String>,
+    /// Now when `None`
+    pub run_at: Option<DateTime<Utc>>,
+}
+
+impl CreateJob {
+    pub fn new(kind: impl Into<String>, payload: Value) -> Self {
+        Self {
+            kind: kind.into(),
+            payload,
+            max_attempts: 5,
+            unique_key: None,
+            run_at: None,
+        }
+    }
+}
+
+impl Job {
+    /// Whether a failure of the running attempt is the last one.
+    pub fn is_last_attempt(&self) -> bool {
+        self.attempts >= self.max_attempts
+    }
+
+    /// Queue a job. Returns `None`, queuing nothing, when a job with the same
+    /// `unique_key` is already queued or running.
+    pub async fn create<'e, E>(executor: E, data: &CreateJob) -> Result<Option<Self>, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let id = Uuid::new_v4();
+        let payload = Json(&data.payload);
+        let run_at = data.run_at.unwrap_or_else(Utc::now);
+        sqlx::query_as!(
+            Job,
+            r#"INSERT INTO jobs (id, kind, payload, max_attempts, unique_key, run_at)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               ON CONFLICT DO NOTHING
+               RETURNING id as "id!: Uuid", kind, payload as "payload!: Json<Value>", status as "status!: JobStatus", attempts as "attempts!: i64", max_attempts as "max_attempts!: i64", unique_key, run_at as "run_at!: DateTime<Utc>", last_error, started_at as "started_at: DateTime<Utc>", finished_at as "finished_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.kind,
+            payload,
+            data.max_attempts,
+            data.unique_key,
+            run_at
+        )
+        .fetch_optional(executor)
+        .await
+    }
+
+    /// Atomically claim the job that has been due the longest and mark it as running
+    pub async fn claim_next(pool: &SqlitePool) -> Result<Option<Self>, sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query_as!(
+            Job,
+            r#"UPDATE jobs
+               SET status = 'running', attempts = attempts + 1, started_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec')
+               WHERE id = (
+                   SELECT id FROM jobs
+                   WHERE status = 'queued' AND datetime(run_at) <= datetime($1)
+                   ORDER BY datetime(run_at) ASC
+                   LIMIT 1
+               )
+               RETURNING id as "id!: Uuid", kind, payload as "payload!: Json<Value>", status as "status!: JobStatus", attempts as "attempts!: i64", max_attempts as "max_attempts!: i64", unique_key, run_at as "run_at!: DateTime<Utc>", last_error, started_at as "started_at: DateTime<Utc>", finished_at as "finished_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            now
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn mark_succeeded(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE jobs
+               SET status = 'succeeded', last_error = NULL, finished_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Put a failed job back in the queue for another attempt at `run_at`.
+    pub async fn retry_at(
+        pool: &SqlitePool,
+        id: Uuid,
+        error: &str,
+        run_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE jobs
+               SET status = 'queued', last_error = $2, run_at = $3, started_at = NULL, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            error,
+            run_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn mark_failed(pool: &SqlitePool, id: Uuid, error: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE jobs
+               SET status = 'failed', last_error = $2, finished_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            error
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Requeue jobs that were interrupted by a server shutdown. The interrupted attempt
+    /// still counts.
+    pub async fn requeue_interrupted(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE jobs
+               SET status = 'queued', started_at = NULL, updated_at = datetime('now', 'subsec')
+               WHERE status = 'running'"#
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// When the next queued job is due, for sleeping until then.
+    pub async fn next_run_at(pool: &SqlitePool) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT run_at as "run_at!: DateTime<Utc>"
+               FROM jobs
+               WHERE status = 'queued'
+               ORDER BY datetime(run_at) ASC
+               LIMIT 1"#
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Delete succeeded and failed jobs that finished before `cutoff`.
+    pub async fn delete_finished_before(
+        pool: &SqlitePool,
+        cutoff: DateTime<Utc>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"DELETE FROM jobs
+               WHERE status IN ('succeeded', 'failed') AND datetime(finished_at) < datetime($1)"#,
+            cutoff
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
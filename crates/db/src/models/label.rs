@@ -0,0 +1,215 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct Label {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    /// Hex color, `#rrggbb`
+    pub color: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateLabel {
+    pub name: String,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct UpdateLabel {
+    pub name: Option<String>,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct SetTaskLabels {
+    pub label_ids: Vec<Uuid>,
+}
+
+impl Label {
+    pub const DEFAULT_COLOR: &'static str = "#6b7280";
+
+    /// Whether `color` is a `#rrggbb` hex color.
+    pub fn is_valid_color(color: &str) -> bool {
+        color.len() == 7
+            && color.starts_with('#')
+            && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_name(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels
+               WHERE project_id = $1 AND name = $2"#,
+            project_id,
+            name
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT l.id as "id!: Uuid", l.project_id as "project_id!: Uuid", l.name, l.color, l.created_at as "created_at!: DateTime<Utc>", l.updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels l
+               JOIN task_labels tl ON tl.label_id = l.id
+               WHERE tl.task_id = $1
+               ORDER BY l.name ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateLabel,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let color = data.color.as_deref().unwrap_or(Self::DEFAULT_COLOR);
+        sqlx::query_as!(
+            Label,
+            r#"INSERT INTO labels (id, project_id, name, color)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.name,
+            color
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateLabel,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let color = data.color.as_ref().unwrap_or(&existing.color);
+
+        sqlx::query_as!(
+            Label,
+            r#"UPDATE labels
+               SET name = $2, color = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            color
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM labels WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Add a label to a task, keeping its other labels.
+    pub async fn add_to_task<'e, E>(
+        executor: E,
+        task_id: Uuid,
+        label_id: Uuid,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query!(
+            "INSERT OR IGNORE INTO task_labels (task_id, label_id) VALUES ($1, $2)",
+            task_id,
+            label_id
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Replace the labels on a task.
+    pub async fn set_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        label_ids: &[Uuid],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!("DELETE FROM task_labels WHERE task_id = $1", task_id)
+            .execute(&mut *tx)
+            .await?;
+        for label_id in label_ids {
+            sqlx::query!(
+                "INSERT OR IGNORE INTO task_labels (task_id, label_id) VALUES ($1, $2)",
+                task_id,
+                label_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_color() {
+        assert!(Label::is_valid_color("#1a2B3c"));
+        assert!(!Label::is_valid_color("1a2b3c"));
+        assert!(!Label::is_valid_color("#1a2b3"));
+        assert!(!Label::is_valid_color("#gggggg"));
+    }
+}
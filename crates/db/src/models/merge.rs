@@ -2,9 +2,10 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool, Type};
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS, Type)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, Type, ToSchema)]
 #[sqlx(type_name = "merge_status", rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
 pub enum MergeStatus {
@@ -14,14 +15,14 @@ pub enum MergeStatus {
     Unknown,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Merge {
     Direct(DirectMerge),
     Pr(PrMerge),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct DirectMerge {
     pub id: Uuid,
     pub workspace_id: Uuid,
@@ -32,7 +33,7 @@ pub struct DirectMerge {
 }
 
 /// PR merge - represents a pull request merge
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct PrMerge {
     pub id: Uuid,
     pub workspace_id: Uuid,
@@ -42,7 +43,7 @@ pub struct PrMerge {
     pub pr_info: PullRequestInfo,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct PullRequestInfo {
     pub number: i64,
     pub url: String,
@@ -1,15 +1,63 @@
+pub mod api_key;
+pub mod audit_log;
+pub mod board;
+pub mod burndown;
 pub mod coding_agent_turn;
+pub mod cumulative_flow;
+pub mod custom_field;
+pub mod epic;
 pub mod execution_process;
 pub mod execution_process_logs;
 pub mod execution_process_repo_state;
+pub mod flow_metrics;
+pub mod idempotency_key;
 pub mod image;
+pub mod integration;
+pub mod integration_link;
+pub mod job;
+pub mod label;
 pub mod merge;
+pub mod notification;
 pub mod project;
+pub mod project_archive;
+pub mod project_column;
+pub mod project_member;
 pub mod project_repo;
+pub mod project_settings;
+pub mod project_summary;
+pub mod quick_add;
+pub mod recurrence_rule;
 pub mod repo;
+pub mod saved_view;
 pub mod scratch;
 pub mod session;
+pub mod share_link;
+pub mod sprint;
+pub mod sync_audit;
+pub mod sync_conflict;
+pub mod sync_dead_letter;
+pub mod sync_job;
+pub mod sync_plan;
 pub mod tag;
 pub mod task;
+pub mod task_attachment;
+pub mod task_checklist_item;
+pub mod task_comment;
+pub mod task_diff;
+pub mod task_event;
+pub mod task_link;
+pub mod task_mention;
+pub mod task_merge;
+pub mod task_revision;
+pub mod task_search;
+pub mod task_template;
+pub mod task_watcher;
+pub mod team;
+pub mod time_entry;
+pub mod user;
+pub mod user_identity;
+pub mod user_session;
+pub mod webhook;
+pub mod wip_limit;
 pub mod workspace;
 pub mod workspace_repo;
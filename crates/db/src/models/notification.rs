@@ -0,0 +1,205 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Longest comment excerpt quoted in a notification.
+const EXCERPT_CHARS: usize = 140;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "notification_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum NotificationKind {
+    StatusChanged,
+    Commented,
+    Mentioned,
+}
+
+impl NotificationKind {
+    /// Where project settings turn this kind of notification off.
+    fn preference_path(&self) -> &'static str {
+        match self {
+            Self::StatusChanged => "$.notifications.status_changes",
+            Self::Commented => "$.notifications.comments",
+            Self::Mentioned => "$.notifications.mentions",
+        }
+    }
+}
+
+/// Something that happened on a task a user watches.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub kind: NotificationKind,
+    pub message: String,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct MarkNotificationsRead {
+    /// Notifications to mark; all of the user's when omitted
+    #[serde(default)]
+    #[ts(optional)]
+    pub ids: Option<Vec<Uuid>>,
+}
+
+/// The first line of a comment, cut short for a notification.
+pub fn excerpt(body: &str) -> String {
+    let line = body.trim().lines().next().unwrap_or_default().trim();
+    match line.char_indices().nth(EXCERPT_CHARS) {
+        Some((end, _)) => format!("{}…", line[..end].trim_end()),
+        None => line.to_string(),
+    }
+}
+
+impl Notification {
+    /// Newest first.
+    pub async fn find_by_user_id(
+        pool: &SqlitePool,
+        user_id: Uuid,
+        unread_only: bool,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Notification,
+            r#"SELECT n.id as "id!: Uuid", n.user_id as "user_id!: Uuid", n.task_id as "task_id!: Uuid", t.title as "task_title!: String", n.kind as "kind!: NotificationKind", n.message, n.read_at as "read_at: DateTime<Utc>", n.created_at as "created_at!: DateTime<Utc>"
+               FROM notifications n
+               JOIN tasks t ON t.id = n.task_id
+               WHERE n.user_id = $1 AND ($2 = 0 OR n.read_at IS NULL)
+               ORDER BY n.created_at DESC"#,
+            user_id,
+            unread_only
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Notify a user, unless the task's project has this kind of notification turned off.
+    /// Returns whether the notification was sent.
+    pub async fn create<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        task_id: Uuid,
+        kind: NotificationKind,
+        message: &str,
+    ) -> Result<bool, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let id = Uuid::new_v4();
+        let preference = kind.preference_path();
+        let result = sqlx::query!(
+            r#"INSERT INTO notifications (id, user_id, task_id, kind, message)
+               SELECT $1, $2, $3, $4, $5
+               WHERE COALESCE((
+                   SELECT json_extract(ps.settings, $6)
+                   FROM tasks t
+                   JOIN project_settings ps ON ps.project_id = t.project_id
+                   WHERE t.id = $3
+               ), 1) != 0"#,
+            id,
+            user_id,
+            task_id,
+            kind,
+            message,
+            preference
+        )
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Notify everyone watching the task, except the users in `except`, such as whoever
+    /// caused it or was already told another way. Nobody is notified when the task's
+    /// project has this kind of notification turned off.
+    pub async fn notify_watchers<'e, E>(
+        executor: E,
+        task_id: Uuid,
+        kind: NotificationKind,
+        message: &str,
+        except: &[Uuid],
+    ) -> Result<u64, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let except = serde_json::to_string(except).unwrap_or_default();
+        let preference = kind.preference_path();
+        let result = sqlx::query!(
+            r#"INSERT INTO notifications (id, user_id, task_id, kind, message)
+               SELECT randomblob(16), w.user_id, w.task_id, $2, $3
+               FROM task_watchers w
+               JOIN tasks t ON t.id = w.task_id
+               LEFT JOIN project_settings ps ON ps.project_id = t.project_id
+               WHERE w.task_id = $1
+                 AND COALESCE(json_extract(ps.settings, $5), 1) != 0
+                 AND lower(hex(w.user_id)) NOT IN (
+                     SELECT lower(replace(value, '-', '')) FROM json_each($4)
+                 )"#,
+            task_id,
+            kind,
+            message,
+            except,
+            preference
+        )
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Mark the user's notifications read, or only those listed.
+    pub async fn mark_read(
+        pool: &SqlitePool,
+        user_id: Uuid,
+        ids: Option<&[Uuid]>,
+    ) -> Result<u64, sqlx::Error> {
+        let ids = ids.map(|ids| serde_json::to_string(ids).unwrap_or_default());
+        let result = sqlx::query!(
+            r#"UPDATE notifications
+               SET read_at = datetime('now', 'subsec')
+               WHERE user_id = $1
+                 AND read_at IS NULL
+                 AND ($2 IS NULL OR lower(hex(id)) IN (
+                     SELECT lower(replace(value, '-', '')) FROM json_each($2)
+                 ))"#,
+            user_id,
+            ids
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_excerpt() {
+        assert_eq!(excerpt("  Looks good\n\nMerging now"), "Looks good");
+        let long = "é".repeat(EXCERPT_CHARS + 10);
+        let cut = excerpt(&long);
+        assert_eq!(cut.chars().count(), EXCERPT_CHARS + 1);
+        assert!(cut.ends_with('…'));
+    }
+}
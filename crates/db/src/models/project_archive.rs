@@ -0,0 +1,892 @@
+//! A project as one portable JSON document, for backups and for moving projects between
+//! instances. Ids in an archive only tie its parts together; importing gives everything
+//! new ids. Integration secrets, attachments and activity history are not included.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{SqlitePool, types::Json};
+use thiserror::Error;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    custom_field::{CustomField, CustomFieldType},
+    integration::{FieldMapping, Integration, IntegrationProvider},
+    label::Label,
+    project::{CreateProject, Project},
+    project_column::ProjectColumn,
+    project_settings::ProjectSettings,
+    task::{TaskPriority, TaskStatus},
+    task_link::{TaskLink, TaskLinkKind},
+    user::User,
+};
+
+/// Marks a document as a project archive.
+pub const ARCHIVE_FORMAT: &str = "vibe-kanban.project";
+
+/// Version of the archive layout written by this build. Bumped whenever a change would
+/// keep older builds from reading new archives correctly.
+pub const ARCHIVE_VERSION: i64 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ArchivedProject {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(default)]
+    pub dev_script: Option<String>,
+    #[serde(default)]
+    pub dev_script_working_dir: Option<String>,
+    #[serde(default)]
+    pub default_agent_working_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ArchivedColumn {
+    pub id: Uuid,
+    pub name: String,
+    pub category: TaskStatus,
+    pub position: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ArchivedLabel {
+    pub id: Uuid,
+    pub name: String,
+    pub color: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ArchivedCustomField {
+    pub id: Uuid,
+    pub name: String,
+    pub field_type: CustomFieldType,
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ArchivedFieldValue {
+    pub field_id: Uuid,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ArchivedChecklistItem {
+    pub title: String,
+    pub done: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ArchivedComment {
+    pub author: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ArchivedTask {
+    pub id: Uuid,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    #[serde(default)]
+    pub column_id: Option<Uuid>,
+    #[serde(default)]
+    pub due_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub priority: TaskPriority,
+    #[serde(default)]
+    pub estimate: Option<f64>,
+    /// Order on the board within the task's column
+    #[serde(default)]
+    pub rank: f64,
+    /// Users are matched by email, then by name, when importing
+    #[serde(default)]
+    pub assignee_email: Option<String>,
+    #[serde(default)]
+    pub assignee_name: Option<String>,
+    #[serde(default)]
+    pub cover_color: Option<String>,
+    #[serde(default)]
+    pub archived_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub label_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub custom_fields: Vec<ArchivedFieldValue>,
+    #[serde(default)]
+    pub checklist: Vec<ArchivedChecklistItem>,
+    #[serde(default)]
+    pub comments: Vec<ArchivedComment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ArchivedTaskLink {
+    pub source_task_id: Uuid,
+    pub target_task_id: Uuid,
+    pub kind: TaskLinkKind,
+}
+
+/// An integration without its secrets; they have to be entered again after importing.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ArchivedIntegration {
+    pub id: Uuid,
+    pub provider: IntegrationProvider,
+    pub name: String,
+    pub base_url: String,
+    #[schema(value_type = Object)]
+    pub config: Value,
+    #[serde(default)]
+    pub field_mapping: FieldMapping,
+    pub enabled: bool,
+    /// Names of the secrets the integration had
+    #[serde(default)]
+    pub secret_keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectArchive {
+    /// Always `vibe-kanban.project`
+    pub format: String,
+    pub version: i64,
+    pub exported_at: DateTime<Utc>,
+    pub project: ArchivedProject,
+    #[serde(default)]
+    pub settings: ProjectSettings,
+    #[serde(default)]
+    pub columns: Vec<ArchivedColumn>,
+    #[serde(default)]
+    pub labels: Vec<ArchivedLabel>,
+    #[serde(default)]
+    pub custom_fields: Vec<ArchivedCustomField>,
+    /// Archived tasks included; trashed ones left out
+    #[serde(default)]
+    pub tasks: Vec<ArchivedTask>,
+    #[serde(default)]
+    pub links: Vec<ArchivedTaskLink>,
+    #[serde(default)]
+    pub integrations: Vec<ArchivedIntegration>,
+}
+
+#[derive(Debug, Error)]
+pub enum ArchiveImportError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Invalid archive: {0}")]
+    Invalid(String),
+    #[error("A project named '{0}' already exists")]
+    NameTaken(String),
+}
+
+/// What to do when a project already has the name the import would give.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, TS, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NameConflict {
+    /// Add a number to the name, e.g. `Website (2)`
+    #[default]
+    Rename,
+    Fail,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    /// Name of the new project; the archived name when `None`
+    pub name: Option<String>,
+    pub on_conflict: NameConflict,
+    pub team_id: Option<Uuid>,
+    /// Check the archive and report what would be created, without keeping anything
+    pub dry_run: bool,
+}
+
+/// What an import created, or would create on a dry run.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    /// The new project; `None` on a dry run
+    pub project: Option<Project>,
+    pub project_name: String,
+    /// Whether the name was changed because another project had it
+    pub renamed: bool,
+    pub columns: i64,
+    pub labels: i64,
+    pub custom_fields: i64,
+    pub tasks: i64,
+    pub checklist_items: i64,
+    pub comments: i64,
+    pub links: i64,
+    /// Integrations are created disabled, as their secrets have to be entered again
+    pub integrations: i64,
+    /// Assignees no user matched; their tasks are left unassigned
+    pub unmatched_assignees: Vec<String>,
+    /// References the archive made to things it doesn't contain, which were left out
+    pub skipped: Vec<String>,
+}
+
+/// Group `(task_id, item)` rows by task, keeping their order.
+fn by_task<T>(rows: impl IntoIterator<Item = (Uuid, T)>) -> HashMap<Uuid, Vec<T>> {
+    let mut grouped: HashMap<Uuid, Vec<T>> = HashMap::new();
+    for (task_id, item) in rows {
+        grouped.entry(task_id).or_default().push(item);
+    }
+    grouped
+}
+
+impl ProjectArchive {
+    pub async fn export(pool: &SqlitePool, project: &Project) -> Result<Self, sqlx::Error> {
+        let project_id = project.id;
+        let settings = ProjectSettings::find(pool, project_id).await?;
+        let columns = ProjectColumn::find_by_project_id(pool, project_id).await?;
+        let labels = Label::find_by_project_id(pool, project_id).await?;
+        let custom_fields = CustomField::find_by_project_id(pool, project_id).await?;
+        let integrations = Integration::find_by_project_id(pool, project_id).await?;
+
+        let mut task_labels = by_task(
+            sqlx::query!(
+                r#"SELECT tl.task_id as "task_id!: Uuid", tl.label_id as "label_id!: Uuid"
+                   FROM task_labels tl
+                   JOIN tasks t ON t.id = tl.task_id
+                   WHERE t.project_id = $1 AND t.deleted_at IS NULL"#,
+                project_id
+            )
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(|rec| (rec.task_id, rec.label_id)),
+        );
+        let mut field_values = by_task(
+            sqlx::query!(
+                r#"SELECT v.task_id as "task_id!: Uuid", v.field_id as "field_id!: Uuid", v.value
+                   FROM task_custom_field_values v
+                   JOIN tasks t ON t.id = v.task_id
+                   WHERE t.project_id = $1 AND t.deleted_at IS NULL"#,
+                project_id
+            )
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(|rec| {
+                let value = ArchivedFieldValue {
+                    field_id: rec.field_id,
+                    value: rec.value,
+                };
+                (rec.task_id, value)
+            }),
+        );
+        let mut checklists = by_task(
+            sqlx::query!(
+                r#"SELECT c.task_id as "task_id!: Uuid", c.title, c.done as "done!: bool"
+                   FROM task_checklist_items c
+                   JOIN tasks t ON t.id = c.task_id
+                   WHERE t.project_id = $1 AND t.deleted_at IS NULL
+                   ORDER BY c.position ASC"#,
+                project_id
+            )
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(|rec| {
+                let item = ArchivedChecklistItem {
+                    title: rec.title,
+                    done: rec.done,
+                };
+                (rec.task_id, item)
+            }),
+        );
+        let mut comments = by_task(
+            sqlx::query!(
+                r#"SELECT c.task_id as "task_id!: Uuid", c.author, c.body, c.created_at as "created_at!: DateTime<Utc>"
+                   FROM task_comments c
+                   JOIN tasks t ON t.id = c.task_id
+                   WHERE t.project_id = $1 AND t.deleted_at IS NULL
+                   ORDER BY c.created_at ASC, c.rowid ASC"#,
+                project_id
+            )
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(|rec| {
+                let comment = ArchivedComment {
+                    author: rec.author,
+                    body: rec.body,
+                    created_at: rec.created_at,
+                };
+                (rec.task_id, comment)
+            }),
+        );
+
+        let tasks = sqlx::query!(
+            r#"SELECT t.id as "id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.column_id as "column_id: Uuid", t.due_at as "due_at: DateTime<Utc>", t.priority as "priority!: TaskPriority", t.estimate as "estimate: f64", t.rank as "rank!: f64", u.email as "assignee_email?: String", u.name as "assignee_name?: String", t.cover_color, t.archived_at as "archived_at: DateTime<Utc>", t.created_at as "created_at!: DateTime<Utc>"
+               FROM tasks t
+               LEFT JOIN users u ON u.id = t.assignee_id
+               WHERE t.project_id = $1 AND t.deleted_at IS NULL
+               ORDER BY t.created_at ASC, t.rowid ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|rec| ArchivedTask {
+            id: rec.id,
+            title: rec.title,
+            description: rec.description,
+            status: rec.status,
+            column_id: rec.column_id,
+            due_at: rec.due_at,
+            priority: rec.priority,
+            estimate: rec.estimate,
+            rank: rec.rank,
+            assignee_email: rec.assignee_email,
+            assignee_name: rec.assignee_name,
+            cover_color: rec.cover_color,
+            archived_at: rec.archived_at,
+            created_at: rec.created_at,
+            label_ids: task_labels.remove(&rec.id).unwrap_or_default(),
+            custom_fields: field_values.remove(&rec.id).unwrap_or_default(),
+            checklist: checklists.remove(&rec.id).unwrap_or_default(),
+            comments: comments.remove(&rec.id).unwrap_or_default(),
+        })
+        .collect();
+
+        let links = sqlx::query_as!(
+            ArchivedTaskLink,
+            r#"SELECT l.source_task_id as "source_task_id!: Uuid", l.target_task_id as "target_task_id!: Uuid", l.kind as "kind!: TaskLinkKind"
+               FROM task_links l
+               JOIN tasks s ON s.id = l.source_task_id
+               JOIN tasks t ON t.id = l.target_task_id
+               WHERE s.project_id = $1 AND t.project_id = $1
+                 AND s.deleted_at IS NULL AND t.deleted_at IS NULL
+               ORDER BY l.created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(Self {
+            format: ARCHIVE_FORMAT.to_string(),
+            version: ARCHIVE_VERSION,
+            exported_at: Utc::now(),
+            project: ArchivedProject {
+                id: project.id,
+                name: project.name.clone(),
+                dev_script: project.dev_script.clone(),
+                dev_script_working_dir: project.dev_script_working_dir.clone(),
+                default_agent_working_dir: project.default_agent_working_dir.clone(),
+            },
+            settings,
+            columns: columns
+                .into_iter()
+                .map(|column| ArchivedColumn {
+                    id: column.id,
+                    name: column.name,
+                    category: column.category,
+                    position: column.position,
+                })
+                .collect(),
+            labels: labels
+                .into_iter()
+                .map(|label| ArchivedLabel {
+                    id: label.id,
+                    name: label.name,
+                    color: label.color,
+                })
+                .collect(),
+            custom_fields: custom_fields
+                .into_iter()
+                .map(|field| ArchivedCustomField {
+                    id: field.id,
+                    name: field.name,
+                    field_type: field.field_type,
+                    options: field.options.0,
+                })
+                .collect(),
+            tasks,
+            links,
+            integrations: integrations
+                .into_iter()
+                .map(|integration| {
+                    let mut secret_keys: Vec<String> =
+                        integration.secrets.keys().cloned().collect();
+                    secret_keys.sort();
+                    ArchivedIntegration {
+                        id: integration.id,
+                        provider: integration.provider,
+                        name: integration.name,
+                        base_url: integration.base_url,
+                        config: integration.config.0,
+                        field_mapping: integration.field_mapping.0,
+                        enabled: integration.enabled,
+                        secret_keys,
+                    }
+                })
+                .collect(),
+        })
+    }
+
+    /// Check the archive holds together before anything is written.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.format != ARCHIVE_FORMAT {
+            return Err(format!("not a project archive (format '{}')", self.format));
+        }
+        if !(1..=ARCHIVE_VERSION).contains(&self.version) {
+            return Err(format!(
+                "archive version {} is not supported; this build reads up to version {}",
+                self.version, ARCHIVE_VERSION
+            ));
+        }
+        self.settings.validate()?;
+
+        unique_ids("column", self.columns.iter().map(|column| column.id))?;
+        unique_ids("label", self.labels.iter().map(|label| label.id))?;
+        unique_ids(
+            "custom field",
+            self.custom_fields.iter().map(|field| field.id),
+        )?;
+        unique_ids("task", self.tasks.iter().map(|task| task.id))?;
+        unique_ids(
+            "integration",
+            self.integrations.iter().map(|integration| integration.id),
+        )?;
+
+        if self.columns.iter().any(|column| column.name.is_empty()) {
+            return Err("columns need a name".to_string());
+        }
+        let mut label_names = HashSet::new();
+        for label in &self.labels {
+            if label.name.is_empty() || !label_names.insert(label.name.as_str()) {
+                return Err(format!("label name '{}' is empty or repeated", label.name));
+            }
+        }
+        let mut field_names = HashSet::new();
+        for field in &self.custom_fields {
+            if field.name.trim().is_empty() || !field_names.insert(field.name.to_lowercase()) {
+                return Err(format!(
+                    "custom field name '{}' is empty or repeated",
+                    field.name
+                ));
+            }
+        }
+        if self
+            .integrations
+            .iter()
+            .any(|integration| integration.name.is_empty())
+        {
+            return Err("integrations need a name".to_string());
+        }
+        if self
+            .tasks
+            .iter()
+            .flat_map(|task| &task.checklist)
+            .any(|item| item.title.is_empty())
+        {
+            return Err("checklist items need a title".to_string());
+        }
+        Ok(())
+    }
+
+    /// Recreate the archived project under new ids, all at once or not at all. Links,
+    /// labels, custom field values and columns the archive refers to without containing
+    /// are left out and listed in the report. A dry run goes through the same steps and
+    /// then rolls them back.
+    pub async fn import(
+        &self,
+        pool: &SqlitePool,
+        options: &ImportOptions,
+    ) -> Result<ImportReport, ArchiveImportError> {
+        self.validate().map_err(ArchiveImportError::Invalid)?;
+        let requested = options
+            .name
+            .as_deref()
+            .unwrap_or(&self.project.name)
+            .trim()
+            .to_string();
+        if requested.is_empty() {
+            return Err(ArchiveImportError::Invalid(
+                "the project needs a name".to_string(),
+            ));
+        }
+        let taken: HashSet<String> = sqlx::query_scalar!("SELECT name FROM projects")
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(|name| name.to_lowercase())
+            .collect();
+        let project_name = match options.on_conflict {
+            _ if !taken.contains(&requested.to_lowercase()) => requested.clone(),
+            NameConflict::Fail => return Err(ArchiveImportError::NameTaken(requested)),
+            NameConflict::Rename => (2..)
+                .map(|n| format!("{requested} ({n})"))
+                .find(|name| !taken.contains(&name.to_lowercase()))
+                .unwrap_or_default(),
+        };
+
+        let mut skipped = Vec::new();
+        let mut unmatched_assignees = Vec::new();
+        let mut assignees: HashMap<(Option<&str>, Option<&str>), Option<Uuid>> = HashMap::new();
+        for task in &self.tasks {
+            let key = (
+                task.assignee_email.as_deref(),
+                task.assignee_name.as_deref(),
+            );
+            if key == (None, None) || assignees.contains_key(&key) {
+                continue;
+            }
+            let mut user = match key.0 {
+                Some(email) => User::find_by_email(pool, email).await?,
+                None => None,
+            };
+            if user.is_none()
+                && let Some(name) = key.1
+            {
+                user = User::find_by_unique_name(pool, name).await?;
+            }
+            if user.is_none() {
+                unmatched_assignees.push(key.0.or(key.1).unwrap_or_default().to_string());
+            }
+            assignees.insert(key, user.map(|user| user.id));
+        }
+
+        let mut tx = pool.begin().await?;
+        let project = Project::create(
+            &mut *tx,
+            &CreateProject {
+                name: project_name.clone(),
+                repositories: Vec::new(),
+                team_id: options.team_id,
+            },
+            Uuid::new_v4(),
+        )
+        .await?;
+        let project_id = project.id;
+        sqlx::query!(
+            r#"UPDATE projects
+               SET dev_script = $2, dev_script_working_dir = $3, default_agent_working_dir = $4
+               WHERE id = $1"#,
+            project_id,
+            self.project.dev_script,
+            self.project.dev_script_working_dir,
+            self.project.default_agent_working_dir
+        )
+        .execute(&mut *tx)
+        .await?;
+        let settings = Json(&self.settings);
+        sqlx::query!(
+            "INSERT INTO project_settings (project_id, settings) VALUES ($1, $2)",
+            project_id,
+            settings
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // New projects get a column per status; an archive with its own columns replaces them
+        let mut column_ids = HashMap::new();
+        if !self.columns.is_empty() {
+            sqlx::query!(
+                "DELETE FROM project_columns WHERE project_id = $1",
+                project_id
+            )
+            .execute(&mut *tx)
+            .await?;
+            let mut columns: Vec<&ArchivedColumn> = self.columns.iter().collect();
+            columns.sort_by_key(|column| column.position);
+            for (position, column) in columns.into_iter().enumerate() {
+                let id = Uuid::new_v4();
+                let position = position as i64;
+                sqlx::query!(
+                    r#"INSERT INTO project_columns (id, project_id, name, category, position)
+                       VALUES ($1, $2, $3, $4, $5)"#,
+                    id,
+                    project_id,
+                    column.name,
+                    column.category,
+                    position
+                )
+                .execute(&mut *tx)
+                .await?;
+                column_ids.insert(column.id, (id, column.category.clone()));
+            }
+        }
+        let columns = sqlx::query!(
+            r#"SELECT id as "id!: Uuid", category as "category!: TaskStatus"
+               FROM project_columns
+               WHERE project_id = $1
+               ORDER BY position ASC"#,
+            project_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut label_ids = HashMap::new();
+        for label in &self.labels {
+            let id = Uuid::new_v4();
+            sqlx::query!(
+                "INSERT INTO labels (id, project_id, name, color) VALUES ($1, $2, $3, $4)",
+                id,
+                project_id,
+                label.name,
+                label.color
+            )
+            .execute(&mut *tx)
+            .await?;
+            label_ids.insert(label.id, id);
+        }
+
+        let mut field_ids = HashMap::new();
+        for field in &self.custom_fields {
+            let id = Uuid::new_v4();
+            let options = Json(&field.options);
+            sqlx::query!(
+                r#"INSERT INTO custom_fields (id, project_id, name, field_type, options)
+                   VALUES ($1, $2, $3, $4, $5)"#,
+                id,
+                project_id,
+                field.name,
+                field.field_type,
+                options
+            )
+            .execute(&mut *tx)
+            .await?;
+            field_ids.insert(field.id, id);
+        }
+
+        let mut task_ids = HashMap::new();
+        let (mut checklist_items, mut comments) = (0, 0);
+        for task in &self.tasks {
+            let id = Uuid::new_v4();
+            // Keep the task's column when it still matches its status, otherwise use the
+            // first column of that status
+            let column_id = task
+                .column_id
+                .and_then(|column_id| column_ids.get(&column_id))
+                .filter(|(_, category)| *category == task.status)
+                .map(|(id, _)| *id)
+                .or_else(|| {
+                    columns
+                        .iter()
+                        .find(|column| column.category == task.status)
+                        .map(|column| column.id)
+                });
+            let assignee_id = assignees
+                .get(&(
+                    task.assignee_email.as_deref(),
+                    task.assignee_name.as_deref(),
+                ))
+                .copied()
+                .flatten();
+            sqlx::query!(
+                r#"INSERT INTO tasks (id, project_id, title, description, status, column_id, due_at, priority, estimate, assignee_id, rank, cover_color, archived_at, created_at)
+                   VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)"#,
+                id,
+                project_id,
+                task.title,
+                task.description,
+                task.status,
+                column_id,
+                task.due_at,
+                task.priority,
+                task.estimate,
+                assignee_id,
+                task.rank,
+                task.cover_color,
+                task.archived_at,
+                task.created_at
+            )
+            .execute(&mut *tx)
+            .await?;
+            task_ids.insert(task.id, id);
+
+            for label_id in &task.label_ids {
+                let Some(label_id) = label_ids.get(label_id) else {
+                    skipped.push(format!("label {label_id} on task '{}'", task.title));
+                    continue;
+                };
+                sqlx::query!(
+                    "INSERT OR IGNORE INTO task_labels (task_id, label_id) VALUES ($1, $2)",
+                    id,
+                    label_id
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            for value in &task.custom_fields {
+                let Some(field_id) = field_ids.get(&value.field_id) else {
+                    skipped.push(format!(
+                        "custom field {} on task '{}'",
+                        value.field_id, task.title
+                    ));
+                    continue;
+                };
+                sqlx::query!(
+                    r#"INSERT OR REPLACE INTO task_custom_field_values (task_id, field_id, value)
+                       VALUES ($1, $2, $3)"#,
+                    id,
+                    field_id,
+                    value.value
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            for (position, item) in task.checklist.iter().enumerate() {
+                let item_id = Uuid::new_v4();
+                let position = position as i64;
+                sqlx::query!(
+                    r#"INSERT INTO task_checklist_items (id, task_id, title, done, position)
+                       VALUES ($1, $2, $3, $4, $5)"#,
+                    item_id,
+                    id,
+                    item.title,
+                    item.done,
+                    position
+                )
+                .execute(&mut *tx)
+                .await?;
+                checklist_items += 1;
+            }
+            for comment in &task.comments {
+                let comment_id = Uuid::new_v4();
+                sqlx::query!(
+                    r#"INSERT INTO task_comments (id, task_id, author, body, created_at)
+                       VALUES ($1, $2, $3, $4, $5)"#,
+                    comment_id,
+                    id,
+                    comment.author,
+                    comment.body,
+                    comment.created_at
+                )
+                .execute(&mut *tx)
+                .await?;
+                comments += 1;
+            }
+        }
+
+        let mut links = 0;
+        for link in &self.links {
+            let (Some(source), Some(target)) = (
+                task_ids.get(&link.source_task_id),
+                task_ids.get(&link.target_task_id),
+            ) else {
+                skipped.push(format!(
+                    "link between tasks {} and {}",
+                    link.source_task_id, link.target_task_id
+                ));
+                continue;
+            };
+            if source == target {
+                continue;
+            }
+            let (source, target, kind) = TaskLink::orient(*source, *target, link.kind);
+            let link_id = Uuid::new_v4();
+            let inserted = sqlx::query!(
+                r#"INSERT OR IGNORE INTO task_links (id, source_task_id, target_task_id, kind)
+                   VALUES ($1, $2, $3, $4)"#,
+                link_id,
+                source,
+                target,
+                kind
+            )
+            .execute(&mut *tx)
+            .await?;
+            links += inserted.rows_affected() as i64;
+        }
+
+        for integration in &self.integrations {
+            let id = Uuid::new_v4();
+            let config = Json(&integration.config);
+            let field_mapping = Json(&integration.field_mapping);
+            sqlx::query!(
+                r#"INSERT INTO integrations (id, project_id, provider, name, base_url, config, enabled, field_mapping)
+                   VALUES ($1, $2, $3, $4, $5, $6, FALSE, $7)"#,
+                id,
+                project_id,
+                integration.provider,
+                integration.name,
+                integration.base_url,
+                config,
+                field_mapping
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let project = if options.dry_run {
+            tx.rollback().await?;
+            None
+        } else {
+            tx.commit().await?;
+            Project::find_by_id(pool, project_id).await?
+        };
+
+        Ok(ImportReport {
+            dry_run: options.dry_run,
+            project,
+            renamed: project_name != requested,
+            project_name,
+            columns: columns.len() as i64,
+            labels: label_ids.len() as i64,
+            custom_fields: field_ids.len() as i64,
+            tasks: task_ids.len() as i64,
+            checklist_items,
+            comments,
+            links,
+            integrations: self.integrations.len() as i64,
+            unmatched_assignees,
+            skipped,
+        })
+    }
+}
+
+fn unique_ids(kind: &str, ids: impl IntoIterator<Item = Uuid>) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    match ids.into_iter().find(|id| !seen.insert(*id)) {
+        Some(id) => Err(format!("{kind} id {id} appears more than once")),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive() -> ProjectArchive {
+        serde_json::from_value(serde_json::json!({
+            "format": ARCHIVE_FORMAT,
+            "version": ARCHIVE_VERSION,
+            "exported_at": "2026-02-01T00:00:00Z",
+            "project": { "id": Uuid::new_v4(), "name": "Website" },
+            "labels": [{ "id": Uuid::new_v4(), "name": "bug", "color": "#ff0000" }],
+            "tasks": [{
+                "id": Uuid::new_v4(),
+                "title": "Fix the footer",
+                "status": "todo",
+                "created_at": "2026-01-15T00:00:00Z",
+                "checklist": [{ "title": "Check mobile", "done": false }]
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_minimal_archive_is_valid() {
+        assert!(archive().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_unreadable_archives() {
+        let mut newer = archive();
+        newer.version = ARCHIVE_VERSION + 1;
+        assert!(newer.validate().is_err());
+
+        let mut repeated = archive();
+        let task = repeated.tasks[0].clone();
+        repeated.tasks.push(task);
+        assert!(repeated.validate().is_err());
+
+        let mut untitled = archive();
+        untitled.tasks[0].checklist[0].title.clear();
+        assert!(untitled.validate().is_err());
+    }
+}
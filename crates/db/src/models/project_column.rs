@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+/// A column of a project's board. Tasks in the column have its category as their
+/// status, so a project can split a status into as many columns as its workflow needs.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectColumn {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    /// Status of the tasks in this column
+    pub category: TaskStatus,
+    pub position: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateProjectColumn {
+    pub name: String,
+    pub category: TaskStatus,
+}
+
+/// Changing the category changes the status of every task in the column.
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct UpdateProjectColumn {
+    pub name: Option<String>,
+    pub category: Option<TaskStatus>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct ReorderProjectColumns {
+    /// Every column of the project, in the desired order
+    pub column_ids: Vec<Uuid>,
+}
+
+/// Where to drop a task on the board. Without a column the task goes to the first
+/// column of `status`, or stays in its own column when neither is given.
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct MoveTask {
+    #[serde(default)]
+    #[ts(optional)]
+    pub column_id: Option<Uuid>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub status: Option<TaskStatus>,
+    /// Zero-based index among the column's other tasks; the end of the column when
+    /// omitted
+    #[serde(default)]
+    #[ts(optional)]
+    pub position: Option<usize>,
+}
+
+/// Move a task to another project's board.
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct MoveTaskToProject {
+    pub project_id: Uuid,
+    /// Target project column for a column of the task's project. Columns left out go to
+    /// the target column of the same name, or else the first one of the same status
+    #[serde(default)]
+    pub column_mapping: HashMap<Uuid, Uuid>,
+}
+
+/// The rank that puts a task at `position` among tasks ranked `ranks`, in ascending
+/// order. `None` when two neighbours are too close to fit anything between them and
+/// the column needs renumbering first.
+pub fn rank_at(ranks: &[f64], position: usize) -> Option<f64> {
+    let position = position.min(ranks.len());
+    match (
+        position.checked_sub(1).map(|i| ranks[i]),
+        ranks.get(position),
+    ) {
+        (None, None) => Some(0.0),
+        (None, Some(next)) => Some(next - 1.0),
+        (Some(previous), None) => Some(previous + 1.0),
+        (Some(previous), Some(next)) => {
+            let rank = previous + (next - previous) / 2.0;
+            (rank > previous && rank < *next).then_some(rank)
+        }
+    }
+}
+
+impl ProjectColumn {
+    /// Another column of the same category that could take this column's tasks. Every
+    /// category keeps at least one column, so a column without one can't be removed or
+    /// given another category.
+    pub fn sibling<'a>(columns: &'a [Self], column: &Self) -> Option<&'a Self> {
+        columns
+            .iter()
+            .find(|other| other.id != column.id && other.category == column.category)
+    }
+
+    /// The column named like a remote tracker's workflow state, ignoring case.
+    pub fn for_state<'a>(columns: &'a [Self], state: &str) -> Option<&'a Self> {
+        let state = state.trim();
+        columns
+            .iter()
+            .find(|column| column.name.eq_ignore_ascii_case(state))
+    }
+
+    /// The column of another project's board, `target`, that takes a task from `column`
+    /// with `status`.
+    pub fn map_to_project<'a>(
+        target: &'a [Self],
+        column: Option<&Self>,
+        status: &TaskStatus,
+        mapping: &HashMap<Uuid, Uuid>,
+    ) -> Result<&'a Self, String> {
+        if let Some(mapped) = column.and_then(|column| mapping.get(&column.id)) {
+            return target
+                .iter()
+                .find(|candidate| candidate.id == *mapped)
+                .ok_or_else(|| format!("Column {mapped} is not on the target project's board"));
+        }
+        column
+            .and_then(|column| Self::for_state(target, &column.name))
+            .or_else(|| {
+                target
+                    .iter()
+                    .find(|candidate| candidate.category == *status)
+            })
+            .ok_or_else(|| {
+                format!(
+                    "The target project has no '{}' column; map the task's column explicitly",
+                    column.map_or_else(|| status.to_string(), |column| column.name.clone())
+                )
+            })
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectColumn,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, category as "category!: TaskStatus", position, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_columns
+               WHERE project_id = $1
+               ORDER BY position ASC, created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectColumn,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, category as "category!: TaskStatus", position, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_columns
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Add a column at the end of the board.
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateProjectColumn,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            ProjectColumn,
+            r#"INSERT INTO project_columns (id, project_id, name, category, position)
+               VALUES ($1, $2, $3, $4, (SELECT COALESCE(MAX(position) + 1, 0) FROM project_columns WHERE project_id = $2))
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, category as "category!: TaskStatus", position, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.name,
+            data.category
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Rename the column or change its category. Tasks in the column are left as they
+    /// are; moving them along is up to the caller.
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        name: &str,
+        category: &TaskStatus,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectColumn,
+            r#"UPDATE project_columns
+               SET name = $2, category = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, category as "category!: TaskStatus", position, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            category
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Ids of the tasks in the column, trashed and archived ones included.
+    pub async fn find_task_ids(pool: &SqlitePool, id: Uuid) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT id as "id!: Uuid" FROM tasks WHERE column_id = $1"#,
+            id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Delete the column, moving its tasks to `fallback_id`.
+    pub async fn delete(
+        pool: &SqlitePool,
+        id: Uuid,
+        fallback_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            "UPDATE tasks SET column_id = $2 WHERE column_id = $1",
+            id,
+            fallback_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        let result = sqlx::query!("DELETE FROM project_columns WHERE id = $1", id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Tasks on the board in the column other than `except`, in board order, with their
+    /// ranks.
+    pub async fn find_task_ranks(
+        pool: &SqlitePool,
+        id: Uuid,
+        except: Uuid,
+    ) -> Result<Vec<(Uuid, f64)>, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"SELECT id as "id!: Uuid", rank as "rank!: f64"
+               FROM tasks
+               WHERE column_id = $1 AND id != $2 AND archived_at IS NULL AND deleted_at IS NULL
+               ORDER BY rank ASC, created_at DESC"#,
+            id,
+            except
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(records.into_iter().map(|rec| (rec.id, rec.rank)).collect())
+    }
+
+    /// Give the tasks whole-number ranks in the order listed.
+    pub async fn renumber_tasks(pool: &SqlitePool, task_ids: &[Uuid]) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for (rank, task_id) in task_ids.iter().enumerate() {
+            let rank = rank as f64;
+            sqlx::query!("UPDATE tasks SET rank = $2 WHERE id = $1", task_id, rank)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await
+    }
+
+    pub async fn reorder(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        column_ids: &[Uuid],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for (position, column_id) in column_ids.iter().enumerate() {
+            let position = position as i64;
+            sqlx::query!(
+                "UPDATE project_columns SET position = $3 WHERE id = $1 AND project_id = $2",
+                column_id,
+                project_id,
+                position
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, category: TaskStatus) -> ProjectColumn {
+        let now = Utc::now();
+        ProjectColumn {
+            id: Uuid::new_v4(),
+            project_id: Uuid::nil(),
+            name: name.to_string(),
+            category,
+            position: 0,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_sibling_and_state_lookup() {
+        let columns = vec![
+            column("Backlog", TaskStatus::Todo),
+            column("Ready", TaskStatus::Todo),
+            column("Doing", TaskStatus::InProgress),
+        ];
+
+        assert_eq!(
+            ProjectColumn::sibling(&columns, &columns[0]).map(|c| c.id),
+            Some(columns[1].id)
+        );
+        assert!(ProjectColumn::sibling(&columns, &columns[2]).is_none());
+
+        assert_eq!(
+            ProjectColumn::for_state(&columns, " ready ").map(|c| c.id),
+            Some(columns[1].id)
+        );
+        assert!(ProjectColumn::for_state(&columns, "Blocked").is_none());
+    }
+
+    #[test]
+    fn test_map_to_project() {
+        let source = column("Review", TaskStatus::InReview);
+        let target = vec![
+            column("Todo", TaskStatus::Todo),
+            column("QA", TaskStatus::InReview),
+            column("review", TaskStatus::InProgress),
+        ];
+        let status = TaskStatus::InReview;
+
+        let by_name =
+            ProjectColumn::map_to_project(&target, Some(&source), &status, &HashMap::new());
+        assert_eq!(by_name.map(|c| c.id), Ok(target[2].id));
+
+        let mapping = HashMap::from([(source.id, target[1].id)]);
+        let mapped = ProjectColumn::map_to_project(&target, Some(&source), &status, &mapping);
+        assert_eq!(mapped.map(|c| c.id), Ok(target[1].id));
+
+        let by_status = ProjectColumn::map_to_project(&target, None, &status, &HashMap::new());
+        assert_eq!(by_status.map(|c| c.id), Ok(target[1].id));
+
+        let unknown = HashMap::from([(source.id, Uuid::new_v4())]);
+        assert!(ProjectColumn::map_to_project(&target, Some(&source), &status, &unknown).is_err());
+        assert!(
+            ProjectColumn::map_to_project(&target[..1], None, &status, &HashMap::new()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_rank_at() {
+        assert_eq!(rank_at(&[], 3), Some(0.0));
+        assert_eq!(rank_at(&[1.0, 2.0], 0), Some(0.0));
+        assert_eq!(rank_at(&[1.0, 2.0], 1), Some(1.5));
+        assert_eq!(rank_at(&[1.0, 2.0], 9), Some(3.0));
+        assert_eq!(rank_at(&[1.0, 1.0], 1), None);
+        assert_eq!(rank_at(&[1.0, 1.0 + f64::EPSILON], 1), None);
+    }
+}
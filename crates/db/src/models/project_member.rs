@@ -0,0 +1,202 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::team::TeamRole;
+
+/// What a member may do on a project. Each role can do everything the ones before it can.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "project_role", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ProjectRole {
+    /// Read the board
+    Viewer,
+    /// Create, edit and move tasks
+    Member,
+    /// Also manage integrations, settings, columns and members
+    Admin,
+}
+
+impl ProjectRole {
+    /// Whether holding this role grants `required`.
+    pub fn grants(self, required: ProjectRole) -> bool {
+        self >= required
+    }
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectMember {
+    pub project_id: Uuid,
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub role: ProjectRole,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct SetProjectMember {
+    pub role: ProjectRole,
+}
+
+impl ProjectMember {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectMember,
+            r#"SELECT m.project_id as "project_id!: Uuid", m.user_id as "user_id!: Uuid", u.name as "user_name!: String", m.role as "role!: ProjectRole", m.created_at as "created_at!: DateTime<Utc>", m.updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_members m
+               JOIN users u ON u.id = m.user_id
+               WHERE m.project_id = $1
+               ORDER BY u.name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectMember,
+            r#"SELECT m.project_id as "project_id!: Uuid", m.user_id as "user_id!: Uuid", u.name as "user_name!: String", m.role as "role!: ProjectRole", m.created_at as "created_at!: DateTime<Utc>", m.updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_members m
+               JOIN users u ON u.id = m.user_id
+               WHERE m.project_id = $1 AND m.user_id = $2"#,
+            project_id,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// The user's role on the project, or `None` when they may not access it. Projects in a
+    /// team are only open to the team's members, and its admins administer all of them.
+    /// Otherwise, projects without members are open to everyone as admins.
+    pub async fn effective_role(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<ProjectRole>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT
+                   (SELECT role FROM project_members WHERE project_id = $1 AND user_id = $2) as "role: ProjectRole",
+                   EXISTS(SELECT 1 FROM project_members WHERE project_id = $1) as "has_members!: bool",
+                   EXISTS(SELECT 1 FROM projects WHERE id = $1 AND team_id IS NOT NULL) as "in_team!: bool",
+                   (SELECT tm.role
+                      FROM projects p
+                      JOIN team_members tm ON tm.team_id = p.team_id
+                     WHERE p.id = $1 AND tm.user_id = $2) as "team_role: TeamRole""#,
+            project_id,
+            user_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(match (row.in_team, row.team_role, row.role) {
+            (true, None, _) => None,
+            (_, Some(TeamRole::Admin), _) => Some(ProjectRole::Admin),
+            (_, _, Some(role)) => Some(role),
+            (_, _, None) if !row.has_members => Some(ProjectRole::Admin),
+            (_, _, None) => None,
+        })
+    }
+
+    /// Projects the user may access: outside a team or in one of the user's teams, and
+    /// either without members, with the user as a member, or administered by the user's
+    /// team.
+    pub async fn visible_project_ids(
+        pool: &SqlitePool,
+        user_id: Uuid,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT p.id as "id!: Uuid"
+               FROM projects p
+               LEFT JOIN team_members tm ON tm.team_id = p.team_id AND tm.user_id = $1
+               WHERE (p.team_id IS NULL OR tm.user_id IS NOT NULL)
+                 AND (tm.role = 'admin'
+                      OR EXISTS(SELECT 1 FROM project_members m WHERE m.project_id = p.id AND m.user_id = $1)
+                      OR NOT EXISTS(SELECT 1 FROM project_members m WHERE m.project_id = p.id))"#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Add the user to the project, or change their role if they are already a member.
+    pub async fn set<'e, E>(
+        executor: E,
+        project_id: Uuid,
+        user_id: Uuid,
+        role: ProjectRole,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO project_members (project_id, user_id, role)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(project_id, user_id) DO UPDATE SET
+                   role = excluded.role,
+                   updated_at = datetime('now', 'subsec')"#,
+            project_id,
+            user_id,
+            role
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn remove(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_members WHERE project_id = $1 AND user_id = $2",
+            project_id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roles_grant_lower_roles() {
+        assert!(ProjectRole::Admin.grants(ProjectRole::Member));
+        assert!(ProjectRole::Member.grants(ProjectRole::Viewer));
+        assert!(!ProjectRole::Member.grants(ProjectRole::Admin));
+        assert!(!ProjectRole::Viewer.grants(ProjectRole::Member));
+    }
+}
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::repo::Repo;
@@ -19,7 +20,7 @@ pub enum ProjectRepoError {
     AlreadyExists,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct ProjectRepo {
     pub id: Uuid,
     pub project_id: Uuid,
@@ -43,13 +44,13 @@ pub struct ProjectRepoWithName {
     pub parallel_setup_script: bool,
 }
 
-#[derive(Debug, Clone, Deserialize, TS)]
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
 pub struct CreateProjectRepo {
     pub display_name: String,
     pub git_repo_path: String,
 }
 
-#[derive(Debug, Clone, Deserialize, TS)]
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct UpdateProjectRepo {
     pub setup_script: Option<String>,
@@ -0,0 +1,179 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, Sqlite, SqlitePool, types::Json};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{burndown, task::TaskStatus};
+
+/// Longest auto-archive delay, in days.
+const MAX_AUTO_ARCHIVE_DAYS: i64 = 3650;
+
+/// Defaults that apply across a project. Settings left out of a stored document keep
+/// their default, so older documents stay valid as settings are added.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS, ToSchema)]
+#[serde(default)]
+pub struct ProjectSettings {
+    /// Remote workflow states mapped to a task status, matched case-insensitively. Applied
+    /// to every integration of the project before its own field mapping
+    pub state_mappings: BTreeMap<String, TaskStatus>,
+    /// Whether WIP limits set without an explicit `enforced` flag reject moves
+    pub enforce_wip_limits: bool,
+    /// Archive done and cancelled tasks that haven't changed for this many days
+    pub auto_archive_after_days: Option<i64>,
+    pub notifications: NotificationPreferences,
+    /// Range of the burndown report when the request doesn't give one, e.g. `14d` or `2w`
+    pub burndown_range: String,
+}
+
+/// Which task activity notifies the project's watchers and mentioned users.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS, ToSchema)]
+#[serde(default)]
+pub struct NotificationPreferences {
+    pub status_changes: bool,
+    pub comments: bool,
+    pub mentions: bool,
+}
+
+impl Default for ProjectSettings {
+    fn default() -> Self {
+        Self {
+            state_mappings: BTreeMap::new(),
+            enforce_wip_limits: false,
+            auto_archive_after_days: None,
+            notifications: NotificationPreferences::default(),
+            burndown_range: "14d".to_string(),
+        }
+    }
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            status_changes: true,
+            comments: true,
+            mentions: true,
+        }
+    }
+}
+
+impl ProjectSettings {
+    /// Check the settings before storing them.
+    pub fn validate(&self) -> Result<(), String> {
+        if self
+            .state_mappings
+            .keys()
+            .any(|state| state.trim().is_empty())
+        {
+            return Err("state mappings need a non-empty state name".to_string());
+        }
+        let mut seen = HashSet::new();
+        for state in self.state_mappings.keys() {
+            if !seen.insert(state.trim().to_lowercase()) {
+                return Err(format!("state '{}' is mapped more than once", state));
+            }
+        }
+        if let Some(days) = self.auto_archive_after_days
+            && !(1..=MAX_AUTO_ARCHIVE_DAYS).contains(&days)
+        {
+            return Err(format!(
+                "auto_archive_after_days must be between 1 and {}",
+                MAX_AUTO_ARCHIVE_DAYS
+            ));
+        }
+        if burndown::parse_range(&self.burndown_range).is_none() {
+            return Err(format!(
+                "burndown_range must be a number of days or weeks such as 14d or 2w, at most {} days",
+                burndown::MAX_RANGE_DAYS
+            ));
+        }
+        Ok(())
+    }
+
+    /// The status mapped to a remote workflow state, if any.
+    pub fn mapped_status(&self, state: &str) -> Option<&TaskStatus> {
+        let state = state.trim();
+        self.state_mappings
+            .iter()
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case(state))
+            .map(|(_, status)| status)
+    }
+
+    /// The project's settings, or the defaults when none are stored.
+    pub async fn find<'e, E>(executor: E, project_id: Uuid) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let settings = sqlx::query_scalar!(
+            r#"SELECT settings as "settings!: Json<ProjectSettings>"
+               FROM project_settings
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(executor)
+        .await?;
+        Ok(settings.map(|Json(settings)| settings).unwrap_or_default())
+    }
+
+    /// Replace the project's settings.
+    pub async fn set(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        settings: &ProjectSettings,
+    ) -> Result<Self, sqlx::Error> {
+        let document = Json(settings);
+        sqlx::query!(
+            r#"INSERT INTO project_settings (project_id, settings)
+               VALUES ($1, $2)
+               ON CONFLICT(project_id) DO UPDATE
+               SET settings = excluded.settings, updated_at = datetime('now', 'subsec')"#,
+            project_id,
+            document
+        )
+        .execute(pool)
+        .await?;
+        Self::find(pool, project_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_keys_keep_defaults() {
+        let settings: ProjectSettings =
+            serde_json::from_str(r#"{"notifications": {"comments": false}}"#).unwrap();
+        assert_eq!(settings.burndown_range, "14d");
+        assert!(settings.notifications.status_changes);
+        assert!(!settings.notifications.comments);
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_and_mapped_status() {
+        let mut settings = ProjectSettings {
+            state_mappings: BTreeMap::from([("Code Review".to_string(), TaskStatus::InReview)]),
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.mapped_status(" code review"),
+            Some(&TaskStatus::InReview)
+        );
+        assert_eq!(settings.mapped_status("Backlog"), None);
+
+        settings
+            .state_mappings
+            .insert("code review".to_string(), TaskStatus::Done);
+        assert!(settings.validate().is_err());
+        settings.state_mappings.remove("code review");
+
+        settings.auto_archive_after_days = Some(0);
+        assert!(settings.validate().is_err());
+        settings.auto_archive_after_days = Some(30);
+        settings.burndown_range = "soon".to_string();
+        assert!(settings.validate().is_err());
+    }
+}
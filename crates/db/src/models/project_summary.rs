@@ -0,0 +1,233 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    board::column_for,
+    project::Project,
+    project_column::ProjectColumn,
+    task::{Task, TaskPriority, TaskStatus, TaskWithAttemptStatus},
+    task_event::{TaskEventKind, TaskEventSource},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct SummaryColumn {
+    pub column_id: Uuid,
+    pub name: String,
+    pub category: TaskStatus,
+    pub task_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct OverdueTask {
+    pub id: Uuid,
+    pub title: String,
+    pub status: TaskStatus,
+    pub due_at: DateTime<Utc>,
+    pub priority: TaskPriority,
+    pub assignee_id: Option<Uuid>,
+}
+
+/// A change to one of the project's tasks, with the task's title so it reads on its own.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct SummaryActivity {
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub kind: TaskEventKind,
+    pub source: TaskEventSource,
+    pub field: Option<String>,
+    pub new_value: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Where a project stands, in one small payload for dashboards and link previews.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectSummary {
+    pub project_id: Uuid,
+    pub project_name: String,
+    /// Tasks on the board; archived and trashed tasks aside
+    pub task_count: i64,
+    /// Tasks not done or cancelled
+    pub open_count: i64,
+    pub overdue_count: i64,
+    /// Tasks per board column, in column order
+    pub columns: Vec<SummaryColumn>,
+    /// The open tasks whose due date passed longest ago
+    pub overdue: Vec<OverdueTask>,
+    /// The latest task changes, newest first
+    pub recent_activity: Vec<SummaryActivity>,
+    pub generated_at: DateTime<Utc>,
+}
+
+fn is_open(task: &Task) -> bool {
+    !matches!(task.status, TaskStatus::Done | TaskStatus::Cancelled)
+}
+
+/// The open tasks due before `now`, longest overdue first.
+fn overdue_tasks(tasks: &[TaskWithAttemptStatus], now: DateTime<Utc>) -> Vec<OverdueTask> {
+    let mut overdue: Vec<OverdueTask> = tasks
+        .iter()
+        .filter(|task| is_open(task))
+        .filter_map(|task| {
+            let due_at = task.due_at.filter(|due_at| *due_at < now)?;
+            Some(OverdueTask {
+                id: task.id,
+                title: task.title.clone(),
+                status: task.status.clone(),
+                due_at,
+                priority: task.priority,
+                assignee_id: task.assignee_id,
+            })
+        })
+        .collect();
+    overdue.sort_by_key(|task| task.due_at);
+    overdue
+}
+
+impl ProjectSummary {
+    /// Summarize the project, listing at most `overdue_limit` overdue tasks and
+    /// `activity_limit` changes.
+    pub async fn for_project(
+        pool: &SqlitePool,
+        project: &Project,
+        overdue_limit: usize,
+        activity_limit: i64,
+    ) -> Result<Self, sqlx::Error> {
+        let now = Utc::now();
+        let columns = ProjectColumn::find_by_project_id(pool, project.id).await?;
+        let tasks = Task::find_by_project_id_with_attempt_status(pool, project.id).await?;
+
+        let mut summary_columns: Vec<SummaryColumn> = columns
+            .iter()
+            .map(|column| SummaryColumn {
+                column_id: column.id,
+                name: column.name.clone(),
+                category: column.category.clone(),
+                task_count: 0,
+            })
+            .collect();
+        for task in &tasks {
+            if let Some(column) = column_for(&columns, task)
+                && let Some(summary) = summary_columns
+                    .iter_mut()
+                    .find(|summary| summary.column_id == column.id)
+            {
+                summary.task_count += 1;
+            }
+        }
+
+        let mut overdue = overdue_tasks(&tasks, now);
+        let overdue_count = overdue.len() as i64;
+        overdue.truncate(overdue_limit);
+
+        Ok(Self {
+            project_id: project.id,
+            project_name: project.name.clone(),
+            task_count: tasks.len() as i64,
+            open_count: tasks.iter().filter(|task| is_open(task)).count() as i64,
+            overdue_count,
+            columns: summary_columns,
+            overdue,
+            recent_activity: SummaryActivity::find_recent(pool, project.id, activity_limit).await?,
+            generated_at: now,
+        })
+    }
+}
+
+impl SummaryActivity {
+    /// The latest changes to the project's tasks, trashed ones aside, newest first.
+    pub async fn find_recent(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SummaryActivity,
+            r#"SELECT e.task_id as "task_id!: Uuid", t.title as task_title, e.kind as "kind!: TaskEventKind", e.source as "source!: TaskEventSource", e.field, e.new_value, e.created_at as "created_at!: DateTime<Utc>"
+               FROM task_events e
+               JOIN tasks t ON t.id = e.task_id
+               WHERE t.project_id = $1
+                 AND t.deleted_at IS NULL
+               ORDER BY e.created_at DESC, e.rowid DESC
+               LIMIT $2"#,
+            project_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    fn task(
+        title: &str,
+        status: TaskStatus,
+        due_at: Option<DateTime<Utc>>,
+    ) -> TaskWithAttemptStatus {
+        let now = Utc::now();
+        TaskWithAttemptStatus {
+            task: Task {
+                id: Uuid::new_v4(),
+                project_id: Uuid::new_v4(),
+                title: title.to_string(),
+                description: None,
+                status,
+                column_id: None,
+                parent_workspace_id: None,
+                shared_task_id: None,
+                due_at,
+                priority: TaskPriority::default(),
+                estimate: None,
+                assignee_id: None,
+                cover_color: None,
+                cover_attachment_id: None,
+                archived_at: None,
+                deleted_at: None,
+                created_at: now,
+                updated_at: now,
+                version: 1,
+            },
+            has_in_progress_attempt: false,
+            last_attempt_failed: false,
+            executor: String::new(),
+            checklist_total: 0,
+            checklist_done: 0,
+            comment_count: 0,
+            attachment_count: 0,
+            is_blocked: false,
+        }
+    }
+
+    #[test]
+    fn test_overdue_tasks_are_open_and_longest_overdue_first() {
+        let now = Utc::now();
+        let tasks = vec![
+            task("yesterday", TaskStatus::Todo, Some(now - Duration::days(1))),
+            task(
+                "last week",
+                TaskStatus::InProgress,
+                Some(now - Duration::days(7)),
+            ),
+            task(
+                "finished late",
+                TaskStatus::Done,
+                Some(now - Duration::days(3)),
+            ),
+            task("tomorrow", TaskStatus::Todo, Some(now + Duration::days(1))),
+            task("no due date", TaskStatus::Todo, None),
+        ];
+        let titles: Vec<String> = overdue_tasks(&tasks, now)
+            .into_iter()
+            .map(|task| task.title)
+            .collect();
+        assert_eq!(titles, vec!["last week", "yesterday"]);
+    }
+}
@@ -0,0 +1,259 @@
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    label::Label,
+    task::{CreateTask, Task, TaskPriority},
+    task_event::{TaskEvent, TaskEventSource},
+    task_mention::TaskMention,
+};
+
+#[derive(Debug, Error)]
+pub enum QuickAddError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("The task needs a title besides its #label, @user, !priority and ^due tokens")]
+    NoTitle,
+}
+
+/// A single line such as `Fix login bug #bug @alice !high ^friday`, taken apart into the
+/// title and what its tokens say about the task.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS, ToSchema)]
+pub struct QuickAddParse {
+    /// The line with its tokens taken out
+    pub title: String,
+    /// Names from `#label` tokens
+    pub labels: Vec<String>,
+    /// Handle from the first `@user` token
+    pub assignee: Option<String>,
+    /// From the first `!low`, `!medium`, `!high` or `!urgent` token
+    pub priority: Option<TaskPriority>,
+    /// End of the day named by the first `^due` token
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+/// What a quick-add created, and the tokens that matched nothing in the project.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct QuickAddResult {
+    /// The new task; `None` for a dry run
+    pub task: Option<Task>,
+    pub parsed: QuickAddParse,
+    /// Label names the project has no label for; they were left off the task
+    pub unmatched_labels: Vec<String>,
+    /// The handle when it matched no user, or several; the task was left unassigned
+    pub unmatched_assignee: Option<String>,
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// The day a `^` token names: `today`, `tomorrow`, a weekday (the next one after
+/// today), an ISO date, or a number of days or weeks from today such as `3d` or `2w`.
+fn parse_due(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let value = value.to_lowercase();
+    match value.as_str() {
+        "today" => return Some(today),
+        "tomorrow" => return today.succ_opt(),
+        _ => {}
+    }
+    if let Some(weekday) = parse_weekday(&value) {
+        let ahead =
+            (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+        return today.checked_add_days(Days::new(if ahead == 0 { 7 } else { ahead.into() }));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(&value, "%Y-%m-%d") {
+        return Some(date);
+    }
+    let (count, days) = if let Some(count) = value.strip_suffix('d') {
+        (count, 1)
+    } else if let Some(count) = value.strip_suffix('w') {
+        (count, 7)
+    } else {
+        return None;
+    };
+    let count: u64 = count.parse().ok()?;
+    today.checked_add_days(Days::new(count.checked_mul(days)?))
+}
+
+fn is_handle(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+/// Take the tokens out of a quick-add line. Every `#label` counts; of `@user`, `!priority`
+/// and `^due` the first counts. Tokens that don't parse, such as `#123` or a second
+/// `@user`, stay in the title. Due dates are the end of the day, UTC.
+pub fn parse_quick_add(text: &str, today: NaiveDate) -> QuickAddParse {
+    let mut parsed = QuickAddParse::default();
+    let mut title = Vec::new();
+    for word in text.split_whitespace() {
+        let (sigil, rest) = word.split_at(word.chars().next().map_or(0, char::len_utf8));
+        let taken = match sigil {
+            "#" if !rest.is_empty() && !rest.chars().all(|c| c.is_ascii_digit()) => {
+                if !parsed
+                    .labels
+                    .iter()
+                    .any(|label| label.eq_ignore_ascii_case(rest))
+                {
+                    parsed.labels.push(rest.to_string());
+                }
+                true
+            }
+            "@" if parsed.assignee.is_none() && is_handle(rest) => {
+                parsed.assignee = Some(rest.to_ascii_lowercase());
+                true
+            }
+            "!" if parsed.priority.is_none() => {
+                parsed.priority = rest.to_lowercase().parse().ok();
+                parsed.priority.is_some()
+            }
+            "^" if parsed.due_at.is_none() => {
+                parsed.due_at = parse_due(rest, today)
+                    .and_then(|date| date.and_hms_opt(23, 59, 59))
+                    .map(|date_time| date_time.and_utc());
+                parsed.due_at.is_some()
+            }
+            _ => false,
+        };
+        if !taken {
+            title.push(word);
+        }
+    }
+    parsed.title = title.join(" ");
+    parsed
+}
+
+/// A label name as a `#` token can spell it: lowercased, without spaces.
+fn label_handle(name: &str) -> String {
+    name.replace(' ', "").to_lowercase()
+}
+
+impl QuickAddResult {
+    /// Parse `text` and create the task in the project, with the labels it names that
+    /// the project has and the user its handle matches (see
+    /// [`TaskMention::find_mentioned_users`]). A dry run only resolves the tokens.
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        text: &str,
+        today: NaiveDate,
+        dry_run: bool,
+    ) -> Result<Self, QuickAddError> {
+        let parsed = parse_quick_add(text, today);
+        if parsed.title.is_empty() {
+            return Err(QuickAddError::NoTitle);
+        }
+
+        let project_labels = Label::find_by_project_id(pool, project_id).await?;
+        let mut label_ids = Vec::new();
+        let mut unmatched_labels = Vec::new();
+        for name in &parsed.labels {
+            match project_labels
+                .iter()
+                .find(|label| label_handle(&label.name) == name.to_lowercase())
+            {
+                Some(label) => label_ids.push(label.id),
+                None => unmatched_labels.push(name.clone()),
+            }
+        }
+        let assignee_id = match &parsed.assignee {
+            Some(handle) => {
+                let mut conn = pool.acquire().await?;
+                let user_ids =
+                    TaskMention::find_mentioned_users(&mut conn, &format!("@{handle}")).await?;
+                user_ids.first().copied()
+            }
+            None => None,
+        };
+        let unmatched_assignee = parsed.assignee.clone().filter(|_| assignee_id.is_none());
+
+        let task = if dry_run {
+            None
+        } else {
+            let data = CreateTask {
+                due_at: parsed.due_at,
+                priority: parsed.priority,
+                assignee_id,
+                ..CreateTask::from_title_description(project_id, parsed.title.clone(), None)
+            };
+            let mut tx = pool.begin().await?;
+            let task = Task::create(&mut *tx, &data, Uuid::new_v4()).await?;
+            for label_id in &label_ids {
+                Label::add_to_task(&mut *tx, task.id, *label_id).await?;
+            }
+            tx.commit().await?;
+            TaskEvent::record_created(pool, &task, TaskEventSource::User, None).await?;
+            Some(task)
+        };
+
+        Ok(Self {
+            task,
+            parsed,
+            unmatched_labels,
+            unmatched_assignee,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_quick_add() {
+        // A Wednesday
+        let today = date("2026-02-04");
+        let parsed = parse_quick_add("Fix login bug #bug @Alice !high ^friday #ui", today);
+        assert_eq!(parsed.title, "Fix login bug");
+        assert_eq!(parsed.labels, vec!["bug", "ui"]);
+        assert_eq!(parsed.assignee.as_deref(), Some("alice"));
+        assert_eq!(parsed.priority, Some(TaskPriority::High));
+        assert_eq!(
+            parsed.due_at,
+            date("2026-02-06")
+                .and_hms_opt(23, 59, 59)
+                .map(|d| d.and_utc())
+        );
+
+        let parsed = parse_quick_add("Close #123 with @bob and @carol !soon ^later", today);
+        assert_eq!(parsed.title, "Close #123 with and @carol !soon ^later");
+        assert!(parsed.labels.is_empty());
+        assert_eq!(parsed.priority, None);
+        assert_eq!(parsed.due_at, None);
+    }
+
+    #[test]
+    fn test_parse_due() {
+        let today = date("2026-02-04");
+        assert_eq!(parse_due("today", today), Some(today));
+        assert_eq!(parse_due("Tomorrow", today), Some(date("2026-02-05")));
+        assert_eq!(parse_due("wed", today), Some(date("2026-02-11")));
+        assert_eq!(parse_due("mon", today), Some(date("2026-02-09")));
+        assert_eq!(parse_due("2026-03-01", today), Some(date("2026-03-01")));
+        assert_eq!(parse_due("3d", today), Some(date("2026-02-07")));
+        assert_eq!(parse_due("2w", today), Some(date("2026-02-18")));
+        assert_eq!(parse_due("d", today), None);
+        assert_eq!(parse_due("soon", today), None);
+    }
+}
@@ -0,0 +1,433 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, NaiveDateTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A parsed recurrence rule. Supports the RRULE parts `FREQ`, `INTERVAL`, `BYDAY`
+/// (weekly rules only) and `UNTIL`, e.g. `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<Weekday>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+const MAX_INTERVAL: u32 = 1000;
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+/// `UNTIL` is either a date (inclusive) or a UTC date-time like `20260131T090000Z`.
+fn parse_until(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(date_time) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ") {
+        return Some(date_time.and_utc());
+    }
+    NaiveDate::parse_from_str(value, "%Y%m%d")
+        .ok()?
+        .and_hms_opt(23, 59, 59)
+        .map(|date_time| date_time.and_utc())
+}
+
+impl FromStr for Recurrence {
+    type Err = String;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let rule = rule.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut until = None;
+        for part in rule.split(';').filter(|part| !part.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("Malformed RRULE part '{part}'"))?;
+            let value = value.to_ascii_uppercase();
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(format!("Unsupported FREQ '{value}'")),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|n| (1..=MAX_INTERVAL).contains(n))
+                        .ok_or_else(|| format!("INTERVAL must be between 1 and {MAX_INTERVAL}"))?;
+                }
+                "BYDAY" => {
+                    for code in value.split(',') {
+                        let day = parse_weekday(code)
+                            .ok_or_else(|| format!("Unsupported BYDAY value '{code}'"))?;
+                        if !by_day.contains(&day) {
+                            by_day.push(day);
+                        }
+                    }
+                }
+                "UNTIL" => {
+                    until = Some(
+                        parse_until(&value).ok_or_else(|| format!("Invalid UNTIL '{value}'"))?,
+                    );
+                }
+                other => return Err(format!("Unsupported RRULE part '{other}'")),
+            }
+        }
+
+        let frequency = frequency.ok_or("RRULE requires FREQ")?;
+        if !by_day.is_empty() && frequency != Frequency::Weekly {
+            return Err("BYDAY is only supported with FREQ=WEEKLY".to_string());
+        }
+        by_day.sort_by_key(|day| day.num_days_from_monday());
+        Ok(Self {
+            frequency,
+            interval,
+            by_day,
+            until,
+        })
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        };
+        write!(f, "FREQ={frequency}")?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<&str> = self.by_day.iter().copied().map(weekday_code).collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%dT%H%M%SZ"))?;
+        }
+        Ok(())
+    }
+}
+
+impl Recurrence {
+    /// The occurrence following `previous`, keeping its time of day. `None` once the rule
+    /// has ended.
+    pub fn next_after(&self, previous: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let next = match self.frequency {
+            Frequency::Daily => previous + Duration::days(self.interval.into()),
+            Frequency::Weekly if self.by_day.is_empty() => {
+                previous + Duration::weeks(self.interval.into())
+            }
+            Frequency::Weekly => {
+                let weekday = previous.weekday().num_days_from_monday();
+                // A later day in the same week, else the first day `interval` weeks on
+                let later = self
+                    .by_day
+                    .iter()
+                    .find(|day| day.num_days_from_monday() > weekday)
+                    .map(|day| day.num_days_from_monday() - weekday);
+                let offset = match later {
+                    Some(offset) => i64::from(offset),
+                    None => {
+                        i64::from(7 * self.interval) - i64::from(weekday)
+                            + i64::from(self.by_day[0].num_days_from_monday())
+                    }
+                };
+                previous + Duration::days(offset)
+            }
+            Frequency::Monthly => previous.checked_add_months(Months::new(self.interval))?,
+            Frequency::Yearly => previous.checked_add_months(Months::new(12 * self.interval))?,
+        };
+        match self.until {
+            Some(until) if next > until => None,
+            _ => Some(next),
+        }
+    }
+
+    /// The first occurrence after `previous` that is later than `now`, skipping
+    /// occurrences missed in between.
+    pub fn next_after_now(
+        &self,
+        previous: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let mut next = self.next_after(previous)?;
+        while next <= now {
+            next = self.next_after(next)?;
+        }
+        Some(next)
+    }
+}
+
+/// A recurrence rule attached to a task or to a task template.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct RecurrenceRule {
+    pub id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub template_id: Option<Uuid>,
+    pub rrule: String,
+    /// When the next task is created from the template; unused for task rules
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct SetRecurrence {
+    pub rrule: String,
+    /// First run of a template schedule; defaults to the rule's next occurrence from now
+    #[serde(default)]
+    #[ts(optional)]
+    pub start_at: Option<DateTime<Utc>>,
+}
+
+impl RecurrenceRule {
+    pub fn recurrence(&self) -> Result<Recurrence, String> {
+        self.rrule.parse()
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            RecurrenceRule,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id: Uuid", template_id as "template_id: Uuid", rrule, next_run_at as "next_run_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM recurrence_rules
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_template_id(
+        pool: &SqlitePool,
+        template_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            RecurrenceRule,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id: Uuid", template_id as "template_id: Uuid", rrule, next_run_at as "next_run_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM recurrence_rules
+               WHERE template_id = $1"#,
+            template_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Task rules whose task has been closed, so its next occurrence is due. Trashed
+    /// tasks do not recur.
+    pub async fn find_closed_task_rules(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            RecurrenceRule,
+            r#"SELECT r.id as "id!: Uuid", r.task_id as "task_id: Uuid", r.template_id as "template_id: Uuid", r.rrule, r.next_run_at as "next_run_at: DateTime<Utc>", r.created_at as "created_at!: DateTime<Utc>", r.updated_at as "updated_at!: DateTime<Utc>"
+               FROM recurrence_rules r
+               JOIN tasks t ON t.id = r.task_id
+               WHERE t.status IN ('done', 'cancelled') AND t.deleted_at IS NULL"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Template rules whose next run is at or before `now`.
+    pub async fn find_due_template_rules(
+        pool: &SqlitePool,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            RecurrenceRule,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id: Uuid", template_id as "template_id: Uuid", rrule, next_run_at as "next_run_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM recurrence_rules
+               WHERE template_id IS NOT NULL AND next_run_at <= $1
+               ORDER BY next_run_at ASC"#,
+            now
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn set_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        rrule: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            RecurrenceRule,
+            r#"INSERT INTO recurrence_rules (id, task_id, rrule)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(task_id) DO UPDATE SET
+                   rrule = excluded.rrule,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid", task_id as "task_id: Uuid", template_id as "template_id: Uuid", rrule, next_run_at as "next_run_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            rrule
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn set_for_template(
+        pool: &SqlitePool,
+        template_id: Uuid,
+        rrule: &str,
+        next_run_at: DateTime<Utc>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            RecurrenceRule,
+            r#"INSERT INTO recurrence_rules (id, template_id, rrule, next_run_at)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(template_id) DO UPDATE SET
+                   rrule = excluded.rrule,
+                   next_run_at = excluded.next_run_at,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid", task_id as "task_id: Uuid", template_id as "template_id: Uuid", rrule, next_run_at as "next_run_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            template_id,
+            rrule,
+            next_run_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Hand a task rule over to the task's next occurrence.
+    pub async fn move_to_task(
+        pool: &SqlitePool,
+        id: Uuid,
+        task_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE recurrence_rules
+               SET task_id = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_next_run_at(
+        pool: &SqlitePool,
+        id: Uuid,
+        next_run_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE recurrence_rules
+               SET next_run_at = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            next_run_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM recurrence_rules WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 9, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        let rule: Recurrence = "RRULE:freq=weekly;interval=2;byday=TH,MO;until=20260301"
+            .parse()
+            .unwrap();
+        assert_eq!(rule.by_day, vec![Weekday::Mon, Weekday::Thu]);
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;UNTIL=20260301T235959Z"
+        );
+        assert_eq!(rule.to_string().parse::<Recurrence>().unwrap(), rule);
+
+        assert!("INTERVAL=2".parse::<Recurrence>().is_err());
+        assert!("FREQ=HOURLY".parse::<Recurrence>().is_err());
+        assert!("FREQ=DAILY;COUNT=3".parse::<Recurrence>().is_err());
+        assert!("FREQ=MONTHLY;BYDAY=MO".parse::<Recurrence>().is_err());
+    }
+
+    #[test]
+    fn test_next_after() {
+        // 2026-01-05 is a Monday
+        let weekly: Recurrence = "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH".parse().unwrap();
+        assert_eq!(weekly.next_after(at(2026, 1, 5)), Some(at(2026, 1, 8)));
+        assert_eq!(weekly.next_after(at(2026, 1, 8)), Some(at(2026, 1, 19)));
+
+        let monthly: Recurrence = "FREQ=MONTHLY".parse().unwrap();
+        assert_eq!(monthly.next_after(at(2026, 1, 31)), Some(at(2026, 2, 28)));
+
+        let daily: Recurrence = "FREQ=DAILY;UNTIL=20260107".parse().unwrap();
+        assert_eq!(daily.next_after(at(2026, 1, 6)), Some(at(2026, 1, 7)));
+        assert_eq!(daily.next_after(at(2026, 1, 7)), None);
+    }
+
+    #[test]
+    fn test_next_after_now_skips_missed_occurrences() {
+        let weekly: Recurrence = "FREQ=WEEKLY".parse().unwrap();
+        assert_eq!(
+            weekly.next_after_now(at(2026, 1, 5), at(2026, 1, 20)),
+            Some(at(2026, 1, 26))
+        );
+    }
+}
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Error)]
@@ -15,9 +16,10 @@ pub enum RepoError {
     NotFound,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct Repo {
     pub id: Uuid,
+    #[schema(value_type = String)]
     pub path: PathBuf,
     pub name: String,
     pub display_name: String,
@@ -0,0 +1,138 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::task::TaskFilter;
+
+/// A named task list filter of a project.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct SavedView {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    #[ts(type = "TaskFilter")]
+    #[schema(value_type = TaskFilter)]
+    pub filter: Json<TaskFilter>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateSavedView {
+    pub name: String,
+    #[serde(default)]
+    pub filter: TaskFilter,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct UpdateSavedView {
+    #[serde(default)]
+    #[ts(optional)]
+    pub name: Option<String>,
+    /// Replaces the whole filter
+    #[serde(default)]
+    #[ts(optional)]
+    pub filter: Option<TaskFilter>,
+}
+
+impl SavedView {
+    /// Alphabetical.
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SavedView,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, filter as "filter!: Json<TaskFilter>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM views
+               WHERE project_id = $1
+               ORDER BY name COLLATE NOCASE ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SavedView,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, filter as "filter!: Json<TaskFilter>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM views
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Names compare case-insensitively.
+    pub async fn find_by_name(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SavedView,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, filter as "filter!: Json<TaskFilter>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM views
+               WHERE project_id = $1 AND name = $2 COLLATE NOCASE"#,
+            project_id,
+            name
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+        filter: &TaskFilter,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let filter = Json(filter);
+        sqlx::query_as!(
+            SavedView,
+            r#"INSERT INTO views (id, project_id, name, filter)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, filter as "filter!: Json<TaskFilter>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            name,
+            filter
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        name: &str,
+        filter: &TaskFilter,
+    ) -> Result<Self, sqlx::Error> {
+        let filter = Json(filter);
+        sqlx::query_as!(
+            SavedView,
+            r#"UPDATE views
+               SET name = $2, filter = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, filter as "filter!: Json<TaskFilter>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            filter
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM views WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -4,6 +4,7 @@ use sqlx::{FromRow, SqlitePool};
 use strum_macros::{Display, EnumDiscriminants, EnumString};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[derive(Debug, Error)]
@@ -17,7 +18,7 @@ pub enum ScratchError {
 }
 
 /// Data for a draft follow-up scratch
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct DraftFollowUpData {
     pub message: String,
     #[serde(default)]
@@ -36,7 +37,7 @@ pub struct DraftFollowUpData {
 
 /// The payload of a scratch, tagged by type. The type is part of the composite primary key.
 /// Data is stored as markdown string.
-#[derive(Debug, Clone, Serialize, Deserialize, TS, EnumDiscriminants)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, EnumDiscriminants, ToSchema)]
 #[serde(tag = "type", content = "data", rename_all = "SCREAMING_SNAKE_CASE")]
 #[strum_discriminants(name(ScratchType))]
 #[strum_discriminants(derive(Display, EnumString, Serialize, Deserialize, TS))]
@@ -76,7 +77,7 @@ struct ScratchRow {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct Scratch {
     pub id: Uuid,
     pub payload: ScratchPayload,
@@ -111,13 +112,13 @@ impl TryFrom<ScratchRow> for Scratch {
 }
 
 /// Request body for creating a scratch (id comes from URL path, type from payload)
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct CreateScratch {
     pub payload: ScratchPayload,
 }
 
 /// Request body for updating a scratch
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct UpdateScratch {
     pub payload: ScratchPayload,
 }
@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    board::column_for,
+    project::Project,
+    project_column::ProjectColumn,
+    task::{Task, TaskPriority, TaskStatus},
+    user::User,
+};
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    Default,
+    ToSchema,
+)]
+#[sqlx(type_name = "share_mode", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ShareMode {
+    /// The board as it is whenever the link is opened
+    #[default]
+    Live,
+    /// The board as it was when the link was made
+    Snapshot,
+}
+
+/// Task fields a link may show besides the title and status.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, Display, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ShareField {
+    Description,
+    Assignee,
+    DueAt,
+    Priority,
+    Estimate,
+    /// Checklist completion
+    Progress,
+}
+
+/// A read-only link to a project's board. The token is only shown when the link is made.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct ShareLink {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// The first characters of the token, to tell links apart
+    pub prefix: String,
+    pub mode: ShareMode,
+    #[ts(type = "Array<ShareField>")]
+    #[schema(value_type = Vec<ShareField>)]
+    pub fields: Json<Vec<ShareField>>,
+    /// The link stops working after this; never when null
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateShareLink {
+    #[serde(default)]
+    #[ts(optional)]
+    pub mode: Option<ShareMode>,
+    /// Only the title and status are shown when empty
+    #[serde(default)]
+    #[ts(optional)]
+    pub fields: Option<Vec<ShareField>>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// A task as a share link shows it; fields the link doesn't show are null.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct SharedBoardTask {
+    pub id: Uuid,
+    pub title: String,
+    pub status: TaskStatus,
+    pub description: Option<String>,
+    pub assignee_name: Option<String>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub priority: Option<TaskPriority>,
+    pub estimate: Option<f64>,
+    pub checklist_total: Option<i64>,
+    pub checklist_done: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct SharedBoardColumn {
+    pub name: String,
+    pub category: TaskStatus,
+    pub tasks: Vec<SharedBoardTask>,
+}
+
+/// What someone opening a share link sees.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct SharedBoard {
+    pub project_name: String,
+    pub columns: Vec<SharedBoardColumn>,
+    /// When the board was read; the link's creation time for snapshots
+    pub generated_at: DateTime<Utc>,
+}
+
+impl SharedBoard {
+    /// The project's board, archived and trashed tasks aside, with only `fields` shown.
+    pub async fn for_project(
+        pool: &SqlitePool,
+        project: &Project,
+        fields: &[ShareField],
+    ) -> Result<Self, sqlx::Error> {
+        let columns = ProjectColumn::find_by_project_id(pool, project.id).await?;
+        let tasks = Task::find_by_project_id_with_attempt_status(pool, project.id).await?;
+        let names: HashMap<Uuid, String> = if fields.contains(&ShareField::Assignee) {
+            User::find_all(pool)
+                .await?
+                .into_iter()
+                .map(|user| (user.id, user.name))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+        let shows = |field| fields.contains(&field);
+
+        let mut shared: Vec<SharedBoardColumn> = columns
+            .iter()
+            .map(|column| SharedBoardColumn {
+                name: column.name.clone(),
+                category: column.category.clone(),
+                tasks: Vec::new(),
+            })
+            .collect();
+        for task in &tasks {
+            let Some(position) = column_for(&columns, task)
+                .and_then(|column| columns.iter().position(|c| c.id == column.id))
+            else {
+                continue;
+            };
+            shared[position].tasks.push(SharedBoardTask {
+                id: task.id,
+                title: task.title.clone(),
+                status: task.status.clone(),
+                description: task
+                    .description
+                    .clone()
+                    .filter(|_| shows(ShareField::Description)),
+                assignee_name: task.assignee_id.and_then(|id| names.get(&id).cloned()),
+                due_at: task.due_at.filter(|_| shows(ShareField::DueAt)),
+                priority: shows(ShareField::Priority).then_some(task.priority),
+                estimate: task.estimate.filter(|_| shows(ShareField::Estimate)),
+                checklist_total: shows(ShareField::Progress).then_some(task.checklist_total),
+                checklist_done: shows(ShareField::Progress).then_some(task.checklist_done),
+            });
+        }
+        Ok(Self {
+            project_name: project.name.clone(),
+            columns: shared,
+            generated_at: Utc::now(),
+        })
+    }
+}
+
+impl ShareLink {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ShareLink,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", prefix, mode as "mode!: ShareMode", fields as "fields!: Json<Vec<ShareField>>", expires_at as "expires_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>"
+               FROM share_links
+               WHERE project_id = $1
+               ORDER BY created_at DESC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The link with this token hash, and its snapshot if it is a snapshot link.
+    pub async fn find_by_token_hash(
+        pool: &SqlitePool,
+        token_hash: &str,
+    ) -> Result<Option<(Self, Option<SharedBoard>)>, sqlx::Error> {
+        let record = sqlx::query!(
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", prefix, mode as "mode!: ShareMode", fields as "fields!: Json<Vec<ShareField>>", snapshot as "snapshot: Json<SharedBoard>", expires_at as "expires_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>"
+               FROM share_links
+               WHERE token_hash = $1"#,
+            token_hash
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(record.map(|rec| {
+            let link = ShareLink {
+                id: rec.id,
+                project_id: rec.project_id,
+                prefix: rec.prefix,
+                mode: rec.mode,
+                fields: rec.fields,
+                expires_at: rec.expires_at,
+                created_at: rec.created_at,
+            };
+            (link, rec.snapshot.map(|Json(board)| board))
+        }))
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateShareLink,
+        prefix: &str,
+        token_hash: &str,
+        snapshot: Option<&SharedBoard>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let mode = data.mode.unwrap_or_default();
+        let fields = Json(data.fields.clone().unwrap_or_default());
+        let snapshot = snapshot.map(Json);
+        sqlx::query_as!(
+            ShareLink,
+            r#"INSERT INTO share_links (id, project_id, prefix, token_hash, mode, fields, snapshot, expires_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", prefix, mode as "mode!: ShareMode", fields as "fields!: Json<Vec<ShareField>>", expires_at as "expires_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            prefix,
+            token_hash,
+            mode,
+            fields,
+            snapshot,
+            data.expires_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM share_links WHERE project_id = $1 AND id = $2",
+            project_id,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_links_without_expiry_never_expire() {
+        let now = Utc::now();
+        let mut link = ShareLink {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            prefix: "abcd1234".to_string(),
+            mode: ShareMode::Live,
+            fields: Json(Vec::new()),
+            expires_at: None,
+            created_at: now,
+        };
+        assert!(!link.is_expired(now + Duration::days(3650)));
+
+        link.expires_at = Some(now + Duration::hours(1));
+        assert!(!link.is_expired(now));
+        assert!(link.is_expired(now + Duration::hours(1)));
+    }
+}
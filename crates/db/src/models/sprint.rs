@@ -0,0 +1,504 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "sprint_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SprintStatus {
+    Planned,
+    Active,
+    Closed,
+}
+
+/// What became of a task when its sprint closed.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "sprint_task_outcome", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SprintTaskOutcome {
+    Completed,
+    /// Unfinished, moved to the next sprint or back to the backlog
+    CarriedOver,
+    /// Cancelled during the sprint
+    Dropped,
+}
+
+impl SprintTaskOutcome {
+    pub fn for_status(status: &TaskStatus) -> Self {
+        match status {
+            TaskStatus::Done => Self::Completed,
+            TaskStatus::Cancelled => Self::Dropped,
+            _ => Self::CarriedOver,
+        }
+    }
+}
+
+/// A time-boxed iteration of a project.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct Sprint {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub goal: Option<String>,
+    pub status: SprintStatus,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    /// When the sprint was actually started and closed
+    pub started_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateSprint {
+    pub name: String,
+    #[serde(default)]
+    #[ts(optional)]
+    pub goal: Option<String>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct UpdateSprint {
+    #[serde(default)]
+    #[ts(optional)]
+    pub name: Option<String>,
+    /// An empty goal removes it
+    #[serde(default)]
+    #[ts(optional)]
+    pub goal: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub starts_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct AddSprintTasks {
+    pub task_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CloseSprint {
+    /// Planned sprint that receives the unfinished tasks; the project's next planned
+    /// sprint when omitted. Without one they go back to the backlog.
+    #[serde(default)]
+    #[ts(optional)]
+    pub next_sprint_id: Option<Uuid>,
+}
+
+/// A task of a sprint as reported on.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct SprintTask {
+    pub task_id: Uuid,
+    pub title: String,
+    pub status: TaskStatus,
+    pub estimate: Option<f64>,
+    /// Recorded when the sprint closed
+    pub outcome: Option<SprintTaskOutcome>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS, ToSchema)]
+pub struct SprintTotals {
+    pub tasks: i64,
+    pub completed: i64,
+    pub carried_over: i64,
+    pub dropped: i64,
+    pub estimate: f64,
+    pub estimate_completed: f64,
+}
+
+impl SprintTotals {
+    /// Count each task by its recorded outcome, or by its current status while the
+    /// sprint is open.
+    pub fn from_tasks(tasks: &[SprintTask]) -> Self {
+        let mut totals = Self::default();
+        for task in tasks {
+            let estimate = task.estimate.unwrap_or(0.0);
+            totals.tasks += 1;
+            totals.estimate += estimate;
+            match task
+                .outcome
+                .unwrap_or_else(|| SprintTaskOutcome::for_status(&task.status))
+            {
+                SprintTaskOutcome::Completed => {
+                    totals.completed += 1;
+                    totals.estimate_completed += estimate;
+                }
+                SprintTaskOutcome::CarriedOver => totals.carried_over += 1,
+                SprintTaskOutcome::Dropped => totals.dropped += 1,
+            }
+        }
+        totals
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct SprintReport {
+    pub sprint: Sprint,
+    pub totals: SprintTotals,
+    pub tasks: Vec<SprintTask>,
+}
+
+/// Completed estimate of one closed sprint.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct SprintVelocity {
+    pub sprint_id: Uuid,
+    pub name: String,
+    pub ends_at: DateTime<Utc>,
+    pub totals: SprintTotals,
+}
+
+impl Sprint {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Sprint,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, goal, status as "status!: SprintStatus", starts_at as "starts_at!: DateTime<Utc>", ends_at as "ends_at!: DateTime<Utc>", started_at as "started_at: DateTime<Utc>", closed_at as "closed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM sprints
+               WHERE project_id = $1
+               ORDER BY starts_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Sprint,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, goal, status as "status!: SprintStatus", starts_at as "starts_at!: DateTime<Utc>", ends_at as "ends_at!: DateTime<Utc>", started_at as "started_at: DateTime<Utc>", closed_at as "closed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM sprints
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_active(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Sprint,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, goal, status as "status!: SprintStatus", starts_at as "starts_at!: DateTime<Utc>", ends_at as "ends_at!: DateTime<Utc>", started_at as "started_at: DateTime<Utc>", closed_at as "closed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM sprints
+               WHERE project_id = $1 AND status = 'active'"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// The earliest planned sprint of the project other than `except`.
+    pub async fn find_next_planned(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        except: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Sprint,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, goal, status as "status!: SprintStatus", starts_at as "starts_at!: DateTime<Utc>", ends_at as "ends_at!: DateTime<Utc>", started_at as "started_at: DateTime<Utc>", closed_at as "closed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM sprints
+               WHERE project_id = $1 AND status = 'planned' AND id != $2
+               ORDER BY starts_at ASC
+               LIMIT 1"#,
+            project_id,
+            except
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// The planned or active sprint the task is in, if any.
+    pub async fn find_open_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Sprint,
+            r#"SELECT s.id as "id!: Uuid", s.project_id as "project_id!: Uuid", s.name, s.goal, s.status as "status!: SprintStatus", s.starts_at as "starts_at!: DateTime<Utc>", s.ends_at as "ends_at!: DateTime<Utc>", s.started_at as "started_at: DateTime<Utc>", s.closed_at as "closed_at: DateTime<Utc>", s.created_at as "created_at!: DateTime<Utc>", s.updated_at as "updated_at!: DateTime<Utc>"
+               FROM sprints s
+               JOIN sprint_tasks st ON st.sprint_id = s.id
+               WHERE st.task_id = $1 AND s.status != 'closed'"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_tasks(
+        pool: &SqlitePool,
+        sprint_id: Uuid,
+    ) -> Result<Vec<SprintTask>, sqlx::Error> {
+        sqlx::query_as!(
+            SprintTask,
+            r#"SELECT t.id as "task_id!: Uuid", t.title, t.status as "status!: TaskStatus", t.estimate as "estimate: f64", st.outcome as "outcome: SprintTaskOutcome"
+               FROM sprint_tasks st
+               JOIN tasks t ON t.id = st.task_id
+               WHERE st.sprint_id = $1 AND t.deleted_at IS NULL
+               ORDER BY st.created_at ASC"#,
+            sprint_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateSprint,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let goal = data.goal.as_deref().filter(|goal| !goal.trim().is_empty());
+        sqlx::query_as!(
+            Sprint,
+            r#"INSERT INTO sprints (id, project_id, name, goal, starts_at, ends_at)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, goal, status as "status!: SprintStatus", starts_at as "starts_at!: DateTime<Utc>", ends_at as "ends_at!: DateTime<Utc>", started_at as "started_at: DateTime<Utc>", closed_at as "closed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.name,
+            goal,
+            data.starts_at,
+            data.ends_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        existing: &Sprint,
+        data: &UpdateSprint,
+    ) -> Result<Self, sqlx::Error> {
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let goal = match &data.goal {
+            Some(goal) => Some(goal).filter(|g| !g.trim().is_empty()),
+            None => existing.goal.as_ref(),
+        };
+        let starts_at = data.starts_at.unwrap_or(existing.starts_at);
+        let ends_at = data.ends_at.unwrap_or(existing.ends_at);
+        sqlx::query_as!(
+            Sprint,
+            r#"UPDATE sprints
+               SET name = $2, goal = $3, starts_at = $4, ends_at = $5, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, goal, status as "status!: SprintStatus", starts_at as "starts_at!: DateTime<Utc>", ends_at as "ends_at!: DateTime<Utc>", started_at as "started_at: DateTime<Utc>", closed_at as "closed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            existing.id,
+            name,
+            goal,
+            starts_at,
+            ends_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Delete the sprint. Its tasks are kept.
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM sprints WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn add_tasks(
+        pool: &SqlitePool,
+        sprint_id: Uuid,
+        task_ids: &[Uuid],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for task_id in task_ids {
+            sqlx::query!(
+                r#"INSERT INTO sprint_tasks (sprint_id, task_id)
+                   VALUES ($1, $2)
+                   ON CONFLICT(sprint_id, task_id) DO NOTHING"#,
+                sprint_id,
+                task_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    pub async fn remove_task(
+        pool: &SqlitePool,
+        sprint_id: Uuid,
+        task_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM sprint_tasks WHERE sprint_id = $1 AND task_id = $2",
+            sprint_id,
+            task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn start(pool: &SqlitePool, id: Uuid) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Sprint,
+            r#"UPDATE sprints
+               SET status = 'active', started_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, goal, status as "status!: SprintStatus", starts_at as "starts_at!: DateTime<Utc>", ends_at as "ends_at!: DateTime<Utc>", started_at as "started_at: DateTime<Utc>", closed_at as "closed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Close the sprint: record each task's outcome from its status and move the
+    /// unfinished ones into `next_sprint_id`, if given. Returns how many were carried over.
+    pub async fn close(
+        pool: &SqlitePool,
+        id: Uuid,
+        next_sprint_id: Option<Uuid>,
+    ) -> Result<u64, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            r#"UPDATE sprint_tasks
+               SET outcome = CASE (SELECT status FROM tasks WHERE id = sprint_tasks.task_id)
+                   WHEN 'done' THEN 'completed'
+                   WHEN 'cancelled' THEN 'dropped'
+                   ELSE 'carried_over'
+               END
+               WHERE sprint_id = $1"#,
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+        let carried_over = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!: i64" FROM sprint_tasks
+               WHERE sprint_id = $1 AND outcome = 'carried_over'"#,
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await?
+        .count;
+        if let Some(next_sprint_id) = next_sprint_id {
+            sqlx::query!(
+                r#"INSERT INTO sprint_tasks (sprint_id, task_id)
+                   SELECT $2, task_id FROM sprint_tasks
+                   WHERE sprint_id = $1 AND outcome = 'carried_over'
+                   ON CONFLICT(sprint_id, task_id) DO NOTHING"#,
+                id,
+                next_sprint_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query!(
+            r#"UPDATE sprints
+               SET status = 'closed', closed_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(carried_over as u64)
+    }
+
+    /// Totals of the project's closed sprints, oldest first.
+    pub async fn find_velocity(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<SprintVelocity>, sqlx::Error> {
+        let mut velocity = Vec::new();
+        for sprint in Self::find_by_project_id(pool, project_id).await? {
+            if sprint.status != SprintStatus::Closed {
+                continue;
+            }
+            let tasks = Self::find_tasks(pool, sprint.id).await?;
+            velocity.push(SprintVelocity {
+                sprint_id: sprint.id,
+                name: sprint.name,
+                ends_at: sprint.ends_at,
+                totals: SprintTotals::from_tasks(&tasks),
+            });
+        }
+        Ok(velocity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(status: TaskStatus, estimate: f64, outcome: Option<SprintTaskOutcome>) -> SprintTask {
+        SprintTask {
+            task_id: Uuid::new_v4(),
+            title: "Task".to_string(),
+            status,
+            estimate: Some(estimate),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_totals_prefer_recorded_outcome() {
+        let tasks = [
+            task(TaskStatus::Done, 3.0, None),
+            task(TaskStatus::InProgress, 5.0, None),
+            task(TaskStatus::Cancelled, 1.0, None),
+            // Finished after the sprint closed; still counts as carried over
+            task(TaskStatus::Done, 2.0, Some(SprintTaskOutcome::CarriedOver)),
+        ];
+        assert_eq!(
+            SprintTotals::from_tasks(&tasks),
+            SprintTotals {
+                tasks: 4,
+                completed: 1,
+                carried_over: 2,
+                dropped: 1,
+                estimate: 11.0,
+                estimate_completed: 3.0,
+            }
+        );
+    }
+}
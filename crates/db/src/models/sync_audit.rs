@@ -0,0 +1,98 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use super::integration::IntegrationProvider;
+
+/// One field changed on one task by a sync run. Tasks are not a foreign key so the
+/// trail survives task deletion.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct SyncAuditEntry {
+    pub id: Uuid,
+    pub integration_id: Uuid,
+    pub run_id: Option<Uuid>,
+    pub task_id: Uuid,
+    pub provider: IntegrationProvider,
+    pub external_id: String,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateSyncAuditEntry {
+    pub integration_id: Uuid,
+    pub run_id: Option<Uuid>,
+    pub task_id: Uuid,
+    pub provider: IntegrationProvider,
+    pub external_id: String,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SyncAuditFilter {
+    #[serde(default)]
+    pub integration_id: Option<Uuid>,
+    #[serde(default)]
+    pub task_id: Option<Uuid>,
+    #[serde(default)]
+    pub run_id: Option<Uuid>,
+    #[serde(default)]
+    pub limit: Option<i64>,
+}
+
+impl SyncAuditEntry {
+    pub async fn create<'e, E>(executor: E, data: &CreateSyncAuditEntry) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO sync_audit_log (id, integration_id, run_id, task_id, provider, external_id, field, old_value, new_value)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
+            id,
+            data.integration_id,
+            data.run_id,
+            data.task_id,
+            data.provider,
+            data.external_id,
+            data.field,
+            data.old_value,
+            data.new_value
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Most recent entries first; every filter is optional and combined with AND.
+    pub async fn find_filtered(
+        pool: &SqlitePool,
+        filter: &SyncAuditFilter,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let limit = filter.limit.unwrap_or(100).clamp(1, 1000);
+        sqlx::query_as!(
+            SyncAuditEntry,
+            r#"SELECT id as "id!: Uuid", integration_id as "integration_id!: Uuid", run_id as "run_id: Uuid", task_id as "task_id!: Uuid", provider as "provider!: IntegrationProvider", external_id, field, old_value, new_value, created_at as "created_at!: DateTime<Utc>"
+               FROM sync_audit_log
+               WHERE ($1 IS NULL OR integration_id = $1)
+                 AND ($2 IS NULL OR task_id = $2)
+                 AND ($3 IS NULL OR run_id = $3)
+               ORDER BY created_at DESC
+               LIMIT $4"#,
+            filter.integration_id,
+            filter.task_id,
+            filter.run_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
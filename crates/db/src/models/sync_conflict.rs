@@ -0,0 +1,178 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "sync_conflict_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum SyncConflictStatus {
+    Open,
+    Resolved,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "conflict_resolution", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ConflictResolution {
+    /// Keep the local task values
+    Local,
+    /// Overwrite the task with the remote values
+    Remote,
+}
+
+/// A field edited on both sides since the last sync.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+pub struct ConflictField {
+    pub field: String,
+    /// The remote value as of the last sync
+    pub base_value: Option<String>,
+    pub local_value: Option<String>,
+    pub remote_value: Option<String>,
+}
+
+/// Conflicting edits between a task and its linked remote issue, held until a user
+/// picks a side.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct SyncConflict {
+    pub id: Uuid,
+    pub integration_id: Uuid,
+    pub task_id: Uuid,
+    pub external_id: String,
+    #[ts(type = "Array<ConflictField>")]
+    #[schema(value_type = Vec<ConflictField>)]
+    pub fields: Json<Vec<ConflictField>>,
+    pub status: SyncConflictStatus,
+    pub resolution: Option<ConflictResolution>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SyncConflictQuery {
+    pub integration_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub status: Option<SyncConflictStatus>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct ResolveSyncConflict {
+    pub resolution: ConflictResolution,
+}
+
+impl SyncConflict {
+    /// Record conflicting fields for a remote issue. An open conflict for the same issue
+    /// is refreshed with the latest values instead of duplicated.
+    pub async fn record(
+        pool: &SqlitePool,
+        integration_id: Uuid,
+        task_id: Uuid,
+        external_id: &str,
+        fields: &[ConflictField],
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let fields = Json(fields);
+        sqlx::query_as!(
+            SyncConflict,
+            r#"INSERT INTO sync_conflicts (id, integration_id, task_id, external_id, fields)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT(integration_id, external_id) WHERE status = 'open' DO UPDATE SET
+                   task_id = excluded.task_id,
+                   fields = excluded.fields,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid", integration_id as "integration_id!: Uuid", task_id as "task_id!: Uuid", external_id, fields as "fields!: Json<Vec<ConflictField>>", status as "status!: SyncConflictStatus", resolution as "resolution: ConflictResolution", resolved_at as "resolved_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            integration_id,
+            task_id,
+            external_id,
+            fields
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SyncConflict,
+            r#"SELECT id as "id!: Uuid", integration_id as "integration_id!: Uuid", task_id as "task_id!: Uuid", external_id, fields as "fields!: Json<Vec<ConflictField>>", status as "status!: SyncConflictStatus", resolution as "resolution: ConflictResolution", resolved_at as "resolved_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM sync_conflicts
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_filtered(
+        pool: &SqlitePool,
+        query: &SyncConflictQuery,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SyncConflict,
+            r#"SELECT id as "id!: Uuid", integration_id as "integration_id!: Uuid", task_id as "task_id!: Uuid", external_id, fields as "fields!: Json<Vec<ConflictField>>", status as "status!: SyncConflictStatus", resolution as "resolution: ConflictResolution", resolved_at as "resolved_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM sync_conflicts
+               WHERE ($1 IS NULL OR integration_id = $1)
+                 AND ($2 IS NULL OR task_id = $2)
+                 AND ($3 IS NULL OR status = $3)
+               ORDER BY created_at DESC"#,
+            query.integration_id,
+            query.task_id,
+            query.status
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Mark an open conflict resolved. Returns `None` when it was already resolved, so
+    /// concurrent resolutions cannot both apply.
+    pub async fn resolve(
+        pool: &SqlitePool,
+        id: Uuid,
+        resolution: ConflictResolution,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SyncConflict,
+            r#"UPDATE sync_conflicts
+               SET status = 'resolved', resolution = $2, resolved_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND status = 'open'
+               RETURNING id as "id!: Uuid", integration_id as "integration_id!: Uuid", task_id as "task_id!: Uuid", external_id, fields as "fields!: Json<Vec<ConflictField>>", status as "status!: SyncConflictStatus", resolution as "resolution: ConflictResolution", resolved_at as "resolved_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            resolution
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
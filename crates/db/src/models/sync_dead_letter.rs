@@ -0,0 +1,130 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A remote issue that failed to import, held for retry or discard.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct SyncDeadLetter {
+    pub id: Uuid,
+    pub integration_id: Uuid,
+    pub run_id: Option<Uuid>,
+    pub external_id: Option<String>,
+    /// The raw provider payload, replayed as-is on retry
+    #[ts(type = "JsonValue")]
+    #[schema(value_type = Object)]
+    pub payload: Json<Value>,
+    pub error: String,
+    pub attempts: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SyncDeadLetter {
+    /// Record a failed item. An existing dead letter for the same remote issue is
+    /// refreshed instead of duplicated.
+    pub async fn record(
+        pool: &SqlitePool,
+        integration_id: Uuid,
+        run_id: Option<Uuid>,
+        external_id: Option<&str>,
+        payload: &Value,
+        error: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let payload = Json(payload);
+        sqlx::query_as!(
+            SyncDeadLetter,
+            r#"INSERT INTO sync_dead_letters (id, integration_id, run_id, external_id, payload, error)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               ON CONFLICT(integration_id, external_id) WHERE external_id IS NOT NULL DO UPDATE SET
+                   run_id = excluded.run_id,
+                   payload = excluded.payload,
+                   error = excluded.error,
+                   attempts = sync_dead_letters.attempts + 1,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid", integration_id as "integration_id!: Uuid", run_id as "run_id: Uuid", external_id, payload as "payload!: Json<Value>", error, attempts as "attempts!: i64", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            integration_id,
+            run_id,
+            external_id,
+            payload,
+            error
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SyncDeadLetter,
+            r#"SELECT id as "id!: Uuid", integration_id as "integration_id!: Uuid", run_id as "run_id: Uuid", external_id, payload as "payload!: Json<Value>", error, attempts as "attempts!: i64", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM sync_dead_letters
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_integration_id(
+        pool: &SqlitePool,
+        integration_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SyncDeadLetter,
+            r#"SELECT id as "id!: Uuid", integration_id as "integration_id!: Uuid", run_id as "run_id: Uuid", external_id, payload as "payload!: Json<Value>", error, attempts as "attempts!: i64", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM sync_dead_letters
+               WHERE integration_id = $1
+               ORDER BY created_at DESC"#,
+            integration_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Store the error of a failed retry
+    pub async fn record_retry_failure(
+        pool: &SqlitePool,
+        id: Uuid,
+        error: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            SyncDeadLetter,
+            r#"UPDATE sync_dead_letters
+               SET error = $2, attempts = attempts + 1, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", integration_id as "integration_id!: Uuid", run_id as "run_id: Uuid", external_id, payload as "payload!: Json<Value>", error, attempts as "attempts!: i64", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            error
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Clear the dead letter for a remote issue once it has been imported
+    pub async fn resolve(
+        pool: &SqlitePool,
+        integration_id: Uuid,
+        external_id: &str,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM sync_dead_letters WHERE integration_id = $1 AND external_id = $2",
+            integration_id,
+            external_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM sync_dead_letters WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
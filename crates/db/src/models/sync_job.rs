@@ -0,0 +1,227 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type, types::Json};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::cursor::{self, CreatedKey, Page};
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "sync_job_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum SyncJobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct SyncJob {
+    pub id: Uuid,
+    pub integration_id: Uuid,
+    pub status: SyncJobStatus,
+    pub attempts: i64,
+    /// Provider-specific run summary, set once the job succeeds
+    #[ts(type = "JsonValue | null")]
+    #[schema(value_type = Option<Object>)]
+    pub result: Option<Json<Value>>,
+    pub error: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SyncJob {
+    pub async fn enqueue<'e, E>(executor: E, integration_id: Uuid) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            SyncJob,
+            r#"INSERT INTO sync_jobs (id, integration_id)
+               VALUES ($1, $2)
+               RETURNING id as "id!: Uuid", integration_id as "integration_id!: Uuid", status as "status!: SyncJobStatus", attempts as "attempts!: i64", result as "result: Json<Value>", error, started_at as "started_at: DateTime<Utc>", finished_at as "finished_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            integration_id
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SyncJob,
+            r#"SELECT id as "id!: Uuid", integration_id as "integration_id!: Uuid", status as "status!: SyncJobStatus", attempts as "attempts!: i64", result as "result: Json<Value>", error, started_at as "started_at: DateTime<Utc>", finished_at as "finished_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM sync_jobs
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// The integration's runs, newest first, a page at a time starting right after `after`.
+    pub async fn find_by_integration_id(
+        pool: &SqlitePool,
+        integration_id: Uuid,
+        after: Option<&CreatedKey>,
+        limit: i64,
+    ) -> Result<Page<Self>, sqlx::Error> {
+        let fetch_limit = cursor::fetch_limit(Some(limit));
+        let after_created_at = after.map(|key| key.created_at.as_str());
+        let after_rowid = after.map(|key| key.rowid);
+        let records = sqlx::query!(
+            r#"SELECT id as "id!: Uuid", integration_id as "integration_id!: Uuid", status as "status!: SyncJobStatus", attempts as "attempts!: i64", result as "result: Json<Value>", error, started_at as "started_at: DateTime<Utc>", finished_at as "finished_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", created_at as "created_at_key!: String", rowid as "rowid!: i64"
+               FROM sync_jobs
+               WHERE integration_id = $1
+                 AND ($2 IS NULL OR (created_at, rowid) < ($2, $3))
+               ORDER BY created_at DESC, rowid DESC
+               LIMIT $4"#,
+            integration_id,
+            after_created_at,
+            after_rowid,
+            fetch_limit
+        )
+        .fetch_all(pool)
+        .await?;
+        let rows = records
+            .into_iter()
+            .map(|rec| {
+                let key = CreatedKey {
+                    created_at: rec.created_at_key,
+                    rowid: rec.rowid,
+                };
+                let job = SyncJob {
+                    id: rec.id,
+                    integration_id: rec.integration_id,
+                    status: rec.status,
+                    attempts: rec.attempts,
+                    result: rec.result,
+                    error: rec.error,
+                    started_at: rec.started_at,
+                    finished_at: rec.finished_at,
+                    created_at: rec.created_at,
+                    updated_at: rec.updated_at,
+                };
+                (job, key)
+            })
+            .collect();
+        Ok(Page::from_rows(rows, Some(limit)))
+    }
+
+    /// Returns the pending job for an integration, if one is already queued or running
+    pub async fn find_active_for_integration(
+        pool: &SqlitePool,
+        integration_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SyncJob,
+            r#"SELECT id as "id!: Uuid", integration_id as "integration_id!: Uuid", status as "status!: SyncJobStatus", attempts as "attempts!: i64", result as "result: Json<Value>", error, started_at as "started_at: DateTime<Utc>", finished_at as "finished_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM sync_jobs
+               WHERE integration_id = $1 AND status IN ('queued', 'running')
+               ORDER BY created_at ASC
+               LIMIT 1"#,
+            integration_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Most recently finished job for the integration with the given terminal status
+    pub async fn find_last_finished(
+        pool: &SqlitePool,
+        integration_id: Uuid,
+        status: SyncJobStatus,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SyncJob,
+            r#"SELECT id as "id!: Uuid", integration_id as "integration_id!: Uuid", status as "status!: SyncJobStatus", attempts as "attempts!: i64", result as "result: Json<Value>", error, started_at as "started_at: DateTime<Utc>", finished_at as "finished_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM sync_jobs
+               WHERE integration_id = $1 AND status = $2 AND finished_at IS NOT NULL
+               ORDER BY finished_at DESC
+               LIMIT 1"#,
+            integration_id,
+            status
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Mark the job as running another attempt
+    pub async fn mark_running(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE sync_jobs
+               SET status = 'running', attempts = attempts + 1, started_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn mark_succeeded(
+        pool: &SqlitePool,
+        id: Uuid,
+        result: &Value,
+    ) -> Result<(), sqlx::Error> {
+        let result = Json(result);
+        sqlx::query!(
+            r#"UPDATE sync_jobs
+               SET status = 'succeeded', result = $2, error = NULL, finished_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            result
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn mark_failed(pool: &SqlitePool, id: Uuid, error: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE sync_jobs
+               SET status = 'failed', error = $2, finished_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            error
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark the job as queued again after a failed attempt that will be retried
+    pub async fn requeue(pool: &SqlitePool, id: Uuid, error: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE sync_jobs
+               SET status = 'queued', error = $2, started_at = NULL, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            error
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
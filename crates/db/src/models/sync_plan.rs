@@ -0,0 +1,155 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "sync_plan_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum SyncPlanStatus {
+    Pending,
+    Applied,
+    Discarded,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, Display, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum SyncPlanAction {
+    Create,
+    Update,
+    /// An update that moves the task to done or cancelled
+    Close,
+    /// The remote issue changed fields that were also edited locally since the last
+    /// sync; applying the plan records them in the conflict ledger instead of
+    /// overwriting them
+    Conflict,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct PlannedFieldChange {
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct SyncPlanItem {
+    pub action: SyncPlanAction,
+    pub external_id: String,
+    pub task_id: Option<Uuid>,
+    pub title: String,
+    pub changes: Vec<PlannedFieldChange>,
+    /// The raw provider payload the item was planned from, re-applied as-is
+    #[ts(type = "JsonValue")]
+    #[schema(value_type = Object)]
+    pub payload: Value,
+}
+
+/// A persisted dry-run of a sync: every change the run would make, reviewed before it
+/// is applied.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct SyncPlan {
+    pub id: Uuid,
+    pub integration_id: Uuid,
+    pub status: SyncPlanStatus,
+    #[ts(type = "Array<SyncPlanItem>")]
+    #[schema(value_type = Vec<SyncPlanItem>)]
+    pub items: Json<Vec<SyncPlanItem>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SyncPlan {
+    pub fn count(&self, action: SyncPlanAction) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.action == action)
+            .count()
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        integration_id: Uuid,
+        items: &[SyncPlanItem],
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let items = Json(items);
+        sqlx::query_as!(
+            SyncPlan,
+            r#"INSERT INTO sync_plans (id, integration_id, items)
+               VALUES ($1, $2, $3)
+               RETURNING id as "id!: Uuid", integration_id as "integration_id!: Uuid", status as "status!: SyncPlanStatus", items as "items!: Json<Vec<SyncPlanItem>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            integration_id,
+            items
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SyncPlan,
+            r#"SELECT id as "id!: Uuid", integration_id as "integration_id!: Uuid", status as "status!: SyncPlanStatus", items as "items!: Json<Vec<SyncPlanItem>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM sync_plans
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_integration_id(
+        pool: &SqlitePool,
+        integration_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SyncPlan,
+            r#"SELECT id as "id!: Uuid", integration_id as "integration_id!: Uuid", status as "status!: SyncPlanStatus", items as "items!: Json<Vec<SyncPlanItem>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM sync_plans
+               WHERE integration_id = $1
+               ORDER BY created_at DESC"#,
+            integration_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Move a pending plan to `status`. Returns false when the plan was no longer pending,
+    /// so concurrent apply/discard calls cannot both proceed.
+    pub async fn finish(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: SyncPlanStatus,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE sync_plans
+               SET status = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND status = 'pending'"#,
+            id,
+            status
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -1,14 +1,29 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
-use strum_macros::{Display, EnumString};
+use sqlx::{Executor, FromRow, QueryBuilder, Sqlite, SqlitePool, Type};
+use strum_macros::{Display, EnumIter, EnumString};
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-use super::{project::Project, workspace::Workspace};
+use super::{project::Project, task_search::match_query, workspace::Workspace};
+use crate::cursor::{self, Page};
 
 #[derive(
-    Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display, Default,
+    Debug,
+    Clone,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    TS,
+    EnumString,
+    Display,
+    Default,
+    EnumIter,
+    ToSchema,
 )]
 #[sqlx(type_name = "task_status", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
@@ -22,20 +37,84 @@ pub enum TaskStatus {
     Cancelled,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    TS,
+    EnumString,
+    Display,
+    Default,
+    ToSchema,
+)]
+#[sqlx(type_name = "task_priority", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum TaskPriority {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Urgent,
+}
+
+/// Ordering of a project's task list.
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, Display, Default, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TaskSort {
+    /// Board order within each column, as arranged by dragging tasks around
+    #[default]
+    Rank,
+    /// Newest first
+    CreatedAt,
+    /// Most urgent first, newest first within a priority
+    Priority,
+    /// Earliest deadline first; tasks without one last
+    DueAt,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct Task {
     pub id: Uuid,
     pub project_id: Uuid, // Foreign key to Project
     pub title: String,
     pub description: Option<String>,
     pub status: TaskStatus,
+    /// Board column the task sits in; its category always matches `status`
+    pub column_id: Option<Uuid>, // Foreign key to ProjectColumn
     pub parent_workspace_id: Option<Uuid>, // Foreign key to parent Workspace
     pub shared_task_id: Option<Uuid>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub priority: TaskPriority,
+    /// Size of the task in whatever unit the project estimates in, e.g. story points
+    pub estimate: Option<f64>,
+    pub assignee_id: Option<Uuid>, // Foreign key to User
+    /// Card color on the board, as `#rrggbb`
+    pub cover_color: Option<String>,
+    /// Image attachment of the task shown on top of its card
+    pub cover_attachment_id: Option<Uuid>, // Foreign key to TaskAttachment
+    /// Set while the task is archived; archived tasks are left off the board
+    pub archived_at: Option<DateTime<Utc>>,
+    /// Set while the task is in the trash
+    pub deleted_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Goes up with every change to the task; its `ETag`, sent back as `If-Match` to update
+    /// only the version that was read
+    pub version: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct TaskWithAttemptStatus {
     #[serde(flatten)]
     #[ts(flatten)]
@@ -43,6 +122,13 @@ pub struct TaskWithAttemptStatus {
     pub has_in_progress_attempt: bool,
     pub last_attempt_failed: bool,
     pub executor: String,
+    /// Checklist completion rollup
+    pub checklist_total: i64,
+    pub checklist_done: i64,
+    pub comment_count: i64,
+    pub attachment_count: i64,
+    /// Blocked by at least one task that is not done or cancelled
+    pub is_blocked: bool,
 }
 
 impl std::ops::Deref for TaskWithAttemptStatus {
@@ -58,14 +144,14 @@ impl std::ops::DerefMut for TaskWithAttemptStatus {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct TaskRelationships {
     pub parent_task: Option<Task>, // The task that owns the parent workspace
     pub current_workspace: Workspace, // The workspace we're viewing
     pub children: Vec<Task>,       // Tasks created from this workspace
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct CreateTask {
     pub project_id: Uuid,
     pub title: String,
@@ -74,6 +160,18 @@ pub struct CreateTask {
     pub parent_workspace_id: Option<Uuid>,
     pub image_ids: Option<Vec<Uuid>>,
     pub shared_task_id: Option<Uuid>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub due_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub priority: Option<TaskPriority>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub estimate: Option<f64>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub assignee_id: Option<Uuid>,
 }
 
 impl CreateTask {
@@ -90,6 +188,10 @@ impl CreateTask {
             parent_workspace_id: None,
             image_ids: None,
             shared_task_id: None,
+            due_at: None,
+            priority: None,
+            estimate: None,
+            assignee_id: None,
         }
     }
 
@@ -108,17 +210,288 @@ impl CreateTask {
             parent_workspace_id: None,
             image_ids: None,
             shared_task_id: Some(shared_task_id),
+            due_at: None,
+            priority: None,
+            estimate: None,
+            assignee_id: None,
+        }
+    }
+}
+
+/// The synced fields of a task as an integration writes them, for the issue it mirrors.
+#[derive(Debug, Clone)]
+pub struct ExternalTaskUpsert {
+    pub external_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    pub priority: TaskPriority,
+}
+
+/// A task written by [`Task::bulk_upsert_by_external_id`].
+#[derive(Debug, Clone)]
+pub struct UpsertedTask {
+    pub external_id: String,
+    pub task: Task,
+    pub created: bool,
+}
+
+/// Rows per `INSERT` of a bulk upsert, well under SQLite's limit on bound parameters.
+const UPSERT_CHUNK_ROWS: usize = 500;
+
+/// What to bring along when cloning a task. Title, description, priority, estimate, due
+/// date and assignee are always copied; the clone starts out as `todo`.
+#[derive(Debug, Default, Deserialize, TS, ToSchema)]
+pub struct CloneTask {
+    /// Project to create the clone in; the task's own project when omitted
+    #[serde(default)]
+    #[ts(optional)]
+    pub project_id: Option<Uuid>,
+    /// Title of the clone; the task's title when omitted
+    #[serde(default)]
+    #[ts(optional)]
+    pub title: Option<String>,
+    /// Copy the checklist, with every item unchecked
+    #[serde(default)]
+    pub copy_checklist: bool,
+    /// Copy the labels. In another project, labels are matched by name and those it
+    /// doesn't have are skipped
+    #[serde(default)]
+    pub copy_labels: bool,
+    #[serde(default)]
+    pub copy_attachments: bool,
+    /// Shared task to link the clone to; it must not be linked to another task yet
+    #[serde(default)]
+    #[ts(optional)]
+    pub shared_task_id: Option<Uuid>,
+}
+
+/// Optional narrowing of a project's task list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskFilter {
+    pub status: Option<TaskStatus>,
+    /// Only tasks whose title, description or comments contain these words, the last one
+    /// as a prefix; searched through the full-text index
+    pub query: Option<String>,
+    /// Only tasks carrying this label
+    pub label_id: Option<Uuid>,
+    /// Only tasks due strictly before this time
+    pub due_before: Option<DateTime<Utc>>,
+    /// Only open tasks whose due date has passed
+    pub overdue: Option<bool>,
+    pub priority: Option<TaskPriority>,
+    pub assignee_id: Option<Uuid>,
+    /// Only tasks without an assignee
+    pub unassigned: Option<bool>,
+    /// Only tasks with a value for this custom field
+    pub custom_field_id: Option<Uuid>,
+    /// With `custom_field_id`, only tasks whose value equals this normalized value
+    pub custom_field_value: Option<String>,
+    pub sort: Option<TaskSort>,
+}
+
+/// Where a page of a task listing ended, in the terms of the sort it was listed in. The
+/// raw `created_at` text compares the way SQLite stored it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskCursor {
+    pub sort: TaskSort,
+    pub group: i64,
+    pub text: String,
+    pub num: f64,
+    pub created_at: String,
+    pub id: Uuid,
+}
+
+/// The expressions a sort orders by ahead of `created_at DESC, id`, in the terms of
+/// [`TaskCursor`]; `None` for those it leaves constant.
+struct SortKeys {
+    group: Option<&'static str>,
+    text: Option<&'static str>,
+    num: Option<&'static str>,
+}
+
+impl SortKeys {
+    fn exprs(&self) -> Vec<&'static str> {
+        [self.group, self.text, self.num]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Bind the cursor's values for [`Self::exprs`], in the same order.
+    fn push_values(&self, query: &mut QueryBuilder<'_, Sqlite>, after: &TaskCursor) {
+        let mut values = query.separated(", ");
+        if self.group.is_some() {
+            values.push_bind(after.group);
+        }
+        if self.text.is_some() {
+            values.push_bind(after.text.clone());
+        }
+        if self.num.is_some() {
+            values.push_bind(after.num);
+        }
+    }
+}
+
+impl TaskSort {
+    fn keys(self) -> SortKeys {
+        match self {
+            Self::Rank => SortKeys {
+                group: None,
+                text: None,
+                num: Some("t.rank"),
+            },
+            Self::CreatedAt => SortKeys {
+                group: None,
+                text: None,
+                num: None,
+            },
+            Self::Priority => SortKeys {
+                group: Some(
+                    "CASE t.priority WHEN 'urgent' THEN 0 WHEN 'high' THEN 1 WHEN 'medium' THEN 2 ELSE 3 END",
+                ),
+                text: None,
+                num: None,
+            },
+            Self::DueAt => SortKeys {
+                group: Some("(t.due_at IS NULL)"),
+                text: Some("COALESCE(t.due_at, '')"),
+                num: None,
+            },
+        }
+    }
+}
+
+/// A task of a listing page with its rollups and sort key.
+#[derive(FromRow)]
+struct TaskPageRow {
+    #[sqlx(flatten)]
+    task: Task,
+    sort_group: i64,
+    sort_text: String,
+    sort_num: f64,
+    created_at_key: String,
+    has_in_progress_attempt: bool,
+    last_attempt_failed: bool,
+    executor: Option<String>,
+    checklist_total: i64,
+    checklist_done: i64,
+    comment_count: i64,
+    attachment_count: i64,
+    is_blocked: bool,
+}
+
+/// The columns of [`TaskPageRow`] besides the sort key, for tasks aliased `t`.
+const TASK_PAGE_COLUMNS: &str = r#"t.id, t.project_id, t.title, t.description, t.status, t.column_id,
+  t.parent_workspace_id, t.shared_task_id, t.due_at, t.priority, t.estimate, t.assignee_id,
+  t.cover_color, t.cover_attachment_id, t.archived_at, t.deleted_at, t.created_at,
+  t.updated_at, t.version, t.created_at AS created_at_key,
+  EXISTS (
+    SELECT 1
+      FROM workspaces w
+      JOIN sessions s ON s.workspace_id = w.id
+      JOIN execution_processes ep ON ep.session_id = s.id
+     WHERE w.task_id = t.id
+       AND ep.status = 'running'
+       AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+  ) AS has_in_progress_attempt,
+  COALESCE((
+    SELECT ep.status
+      FROM workspaces w
+      JOIN sessions s ON s.workspace_id = w.id
+      JOIN execution_processes ep ON ep.session_id = s.id
+     WHERE w.task_id = t.id
+       AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
+     ORDER BY ep.created_at DESC
+     LIMIT 1
+  ) IN ('failed','killed'), 0) AS last_attempt_failed,
+  ( SELECT s.executor
+      FROM workspaces w
+      JOIN sessions s ON s.workspace_id = w.id
+     WHERE w.task_id = t.id
+     ORDER BY s.created_at DESC
+     LIMIT 1
+  ) AS executor,
+  ( SELECT COUNT(*) FROM task_checklist_items ci WHERE ci.task_id = t.id ) AS checklist_total,
+  ( SELECT COUNT(*) FROM task_checklist_items ci WHERE ci.task_id = t.id AND ci.done ) AS checklist_done,
+  ( SELECT COUNT(*) FROM task_comments c WHERE c.task_id = t.id ) AS comment_count,
+  ( SELECT COUNT(*) FROM task_attachments a WHERE a.task_id = t.id ) AS attachment_count,
+  EXISTS (
+    SELECT 1
+      FROM task_links l
+      JOIN tasks b ON b.id = l.source_task_id
+     WHERE l.target_task_id = t.id
+       AND l.kind = 'blocks'
+       AND b.status NOT IN ('done', 'cancelled')
+       AND b.deleted_at IS NULL
+  ) AS is_blocked"#;
+
+impl TaskFilter {
+    /// Take the criteria this filter leaves out from `base`, such as a saved view.
+    pub fn or(self, base: &TaskFilter) -> Self {
+        Self {
+            status: self.status.or_else(|| base.status.clone()),
+            query: self.query.or_else(|| base.query.clone()),
+            label_id: self.label_id.or(base.label_id),
+            due_before: self.due_before.or(base.due_before),
+            overdue: self.overdue.or(base.overdue),
+            priority: self.priority.or(base.priority),
+            assignee_id: self.assignee_id.or(base.assignee_id),
+            unassigned: self.unassigned.or(base.unassigned),
+            custom_field_id: self.custom_field_id.or(base.custom_field_id),
+            custom_field_value: self
+                .custom_field_value
+                .or_else(|| base.custom_field_value.clone()),
+            sort: self.sort.or(base.sort),
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+/// Deadline counts for a project's open tasks.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct DueDateSummary {
+    pub overdue: i64,
+    /// Due between now and the end of the requested window
+    pub upcoming: i64,
+    pub upcoming_until: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct UpdateTask {
     pub title: Option<String>,
     pub description: Option<String>,
     pub status: Option<TaskStatus>,
     pub parent_workspace_id: Option<Uuid>,
     pub image_ids: Option<Vec<Uuid>>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub due_at: Option<DateTime<Utc>>,
+    /// Remove the due date; takes precedence over `due_at`
+    #[serde(default)]
+    #[ts(optional)]
+    pub clear_due_at: Option<bool>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub priority: Option<TaskPriority>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub estimate: Option<f64>,
+    /// Remove the estimate; takes precedence over `estimate`
+    #[serde(default)]
+    #[ts(optional)]
+    pub clear_estimate: Option<bool>,
+    /// `#rrggbb`
+    #[serde(default)]
+    #[ts(optional)]
+    pub cover_color: Option<String>,
+    /// An image attachment of the task
+    #[serde(default)]
+    #[ts(optional)]
+    pub cover_attachment_id: Option<Uuid>,
+    /// Remove the cover color and image; takes precedence over the cover fields
+    #[serde(default)]
+    #[ts(optional)]
+    pub clear_cover: Option<bool>,
 }
 
 impl Task {
@@ -138,84 +511,166 @@ impl Task {
         pool: &SqlitePool,
         project_id: Uuid,
     ) -> Result<Vec<TaskWithAttemptStatus>, sqlx::Error> {
-        let records = sqlx::query!(
-            r#"SELECT
-  t.id                            AS "id!: Uuid",
-  t.project_id                    AS "project_id!: Uuid",
-  t.title,
-  t.description,
-  t.status                        AS "status!: TaskStatus",
-  t.parent_workspace_id           AS "parent_workspace_id: Uuid",
-  t.shared_task_id                AS "shared_task_id: Uuid",
-  t.created_at                    AS "created_at!: DateTime<Utc>",
-  t.updated_at                    AS "updated_at!: DateTime<Utc>",
-
-  CASE WHEN EXISTS (
-    SELECT 1
-      FROM workspaces w
-      JOIN sessions s ON s.workspace_id = w.id
-      JOIN execution_processes ep ON ep.session_id = s.id
-     WHERE w.task_id       = t.id
-       AND ep.status        = 'running'
-       AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
-     LIMIT 1
-  ) THEN 1 ELSE 0 END            AS "has_in_progress_attempt!: i64",
+        Self::find_filtered_with_attempt_status(pool, project_id, &TaskFilter::default(), None, 0)
+            .await
+    }
 
-  CASE WHEN (
-    SELECT ep.status
-      FROM workspaces w
-      JOIN sessions s ON s.workspace_id = w.id
-      JOIN execution_processes ep ON ep.session_id = s.id
-     WHERE w.task_id       = t.id
-     AND ep.run_reason IN ('setupscript','cleanupscript','codingagent')
-     ORDER BY ep.created_at DESC
-     LIMIT 1
-  ) IN ('failed','killed') THEN 1 ELSE 0 END
-                                 AS "last_attempt_failed!: i64",
+    /// The tasks matching `filter` in its sort order, skipping the first `offset` and
+    /// returning at most `limit` of them.
+    pub async fn find_filtered_with_attempt_status(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        filter: &TaskFilter,
+        limit: Option<i64>,
+        offset: i64,
+    ) -> Result<Vec<TaskWithAttemptStatus>, sqlx::Error> {
+        Ok(
+            Self::find_page_with_attempt_status(pool, project_id, filter, None, limit, offset)
+                .await?
+                .items,
+        )
+    }
 
-  ( SELECT s.executor
-      FROM workspaces w
-      JOIN sessions s ON s.workspace_id = w.id
-      WHERE w.task_id = t.id
-     ORDER BY s.created_at DESC
-      LIMIT 1
-    )                               AS "executor!: String"
+    /// Like [`Self::find_filtered_with_attempt_status`], starting right after `after` when
+    /// given. The cursor must come from a listing with the same sort.
+    pub async fn find_page_with_attempt_status(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        filter: &TaskFilter,
+        after: Option<&TaskCursor>,
+        limit: Option<i64>,
+        offset: i64,
+    ) -> Result<Page<TaskWithAttemptStatus>, sqlx::Error> {
+        let sort_key = filter.sort.unwrap_or_default();
+        let keys = sort_key.keys();
+        let mut query = QueryBuilder::<Sqlite>::new("SELECT ");
+        query.push(format!(
+            "{TASK_PAGE_COLUMNS}, {} AS sort_group, {} AS sort_text, {} AS sort_num FROM tasks t WHERE t.project_id = ",
+            keys.group.unwrap_or("0"),
+            keys.text.unwrap_or("''"),
+            keys.num.unwrap_or("0.0"),
+        ));
+        query.push_bind(project_id);
+        query.push(" AND t.archived_at IS NULL AND t.deleted_at IS NULL");
 
-FROM tasks t
-WHERE t.project_id = $1
-ORDER BY t.created_at DESC"#,
-            project_id
-        )
-        .fetch_all(pool)
-        .await?;
+        // Only the criteria given make it into the statement, so the planner can pick the
+        // index for them
+        if let Some(status) = &filter.status {
+            query.push(" AND t.status = ").push_bind(status.clone());
+        }
+        if let Some(priority) = filter.priority {
+            query.push(" AND t.priority = ").push_bind(priority);
+        }
+        if let Some(assignee_id) = filter.assignee_id {
+            query.push(" AND t.assignee_id = ").push_bind(assignee_id);
+        }
+        if filter.unassigned == Some(true) {
+            query.push(" AND t.assignee_id IS NULL");
+        }
+        if let Some(due_before) = filter.due_before {
+            query.push(" AND t.due_at < ").push_bind(due_before);
+        }
+        if filter.overdue == Some(true) {
+            // Bound rather than computed in SQL so it compares in the same format as due_at
+            query
+                .push(" AND t.due_at < ")
+                .push_bind(Utc::now())
+                .push(" AND t.status NOT IN ('done', 'cancelled')");
+        }
+        if let Some(label_id) = filter.label_id {
+            query
+                .push(" AND EXISTS (SELECT 1 FROM task_labels tl WHERE tl.task_id = t.id AND tl.label_id = ")
+                .push_bind(label_id)
+                .push(")");
+        }
+        if let Some(field_id) = filter.custom_field_id {
+            query
+                .push(" AND EXISTS (SELECT 1 FROM task_custom_field_values cv WHERE cv.task_id = t.id AND cv.field_id = ")
+                .push_bind(field_id);
+            if let Some(value) = &filter.custom_field_value {
+                query.push(" AND cv.value = ").push_bind(value.clone());
+            }
+            query.push(")");
+        }
+        if let Some(text) = filter.query.as_deref().and_then(match_query) {
+            query
+                .push(" AND t.id IN (SELECT task_id FROM task_search WHERE task_search MATCH ")
+                .push_bind(text)
+                .push(")");
+        }
+
+        if let Some(after) = after {
+            let exprs = keys.exprs();
+            query.push(" AND (");
+            if !exprs.is_empty() {
+                let tuple = exprs.join(", ");
+                query.push(format!("({tuple}) > ("));
+                keys.push_values(&mut query, after);
+                query.push(format!(") OR (({tuple}) = ("));
+                keys.push_values(&mut query, after);
+                query.push(") AND ");
+            }
+            query
+                .push("(t.created_at < ")
+                .push_bind(after.created_at.clone())
+                .push(" OR (t.created_at = ")
+                .push_bind(after.created_at.clone())
+                .push(" AND t.id > ")
+                .push_bind(after.id)
+                .push("))");
+            if !exprs.is_empty() {
+                query.push(")");
+            }
+            query.push(")");
+        }
+
+        query.push(" ORDER BY ");
+        for expr in keys.exprs() {
+            query.push(expr).push(", ");
+        }
+        query
+            .push("t.created_at DESC, t.id LIMIT ")
+            .push_bind(cursor::fetch_limit(limit).unwrap_or(-1))
+            .push(" OFFSET ")
+            .push_bind(offset);
 
-        let tasks = records
+        let records = query
+            .build_query_as::<TaskPageRow>()
+            .fetch_all(pool)
+            .await?;
+        let rows = records
             .into_iter()
-            .map(|rec| TaskWithAttemptStatus {
-                task: Task {
-                    id: rec.id,
-                    project_id: rec.project_id,
-                    title: rec.title,
-                    description: rec.description,
-                    status: rec.status,
-                    parent_workspace_id: rec.parent_workspace_id,
-                    shared_task_id: rec.shared_task_id,
-                    created_at: rec.created_at,
-                    updated_at: rec.updated_at,
-                },
-                has_in_progress_attempt: rec.has_in_progress_attempt != 0,
-                last_attempt_failed: rec.last_attempt_failed != 0,
-                executor: rec.executor,
+            .map(|rec| {
+                let key = TaskCursor {
+                    sort: sort_key,
+                    group: rec.sort_group,
+                    text: rec.sort_text,
+                    num: rec.sort_num,
+                    created_at: rec.created_at_key,
+                    id: rec.task.id,
+                };
+                let task = TaskWithAttemptStatus {
+                    task: rec.task,
+                    has_in_progress_attempt: rec.has_in_progress_attempt,
+                    last_attempt_failed: rec.last_attempt_failed,
+                    executor: rec.executor.unwrap_or_default(),
+                    checklist_total: rec.checklist_total,
+                    checklist_done: rec.checklist_done,
+                    comment_count: rec.comment_count,
+                    attachment_count: rec.attachment_count,
+                    is_blocked: rec.is_blocked,
+                };
+                (task, key)
             })
             .collect();
 
-        Ok(tasks)
+        Ok(Page::from_rows(rows, limit))
     }
 
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64"
                FROM tasks
                WHERE id = $1"#,
             id
@@ -227,7 +682,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64"
                FROM tasks
                WHERE rowid = $1"#,
             rowid
@@ -245,7 +700,7 @@ ORDER BY t.created_at DESC"#,
     {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64"
                FROM tasks
                WHERE shared_task_id = $1
                LIMIT 1"#,
@@ -258,7 +713,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_all_shared(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64"
                FROM tasks
                WHERE shared_task_id IS NOT NULL"#
         )
@@ -266,44 +721,58 @@ ORDER BY t.created_at DESC"#,
         .await
     }
 
-    pub async fn create(
-        pool: &SqlitePool,
+    pub async fn create<'e, E>(
+        executor: E,
         data: &CreateTask,
         task_id: Uuid,
-    ) -> Result<Self, sqlx::Error> {
+    ) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
         let status = data.status.clone().unwrap_or_default();
+        let priority = data.priority.unwrap_or_default();
         sqlx::query_as!(
             Task,
-            r#"INSERT INTO tasks (id, project_id, title, description, status, parent_workspace_id, shared_task_id)
-               VALUES ($1, $2, $3, $4, $5, $6, $7)
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO tasks (id, project_id, title, description, status, column_id, parent_workspace_id, shared_task_id, due_at, priority, estimate, assignee_id, rank)
+               VALUES ($1, $2, $3, $4, $5, (SELECT id FROM project_columns WHERE project_id = $2 AND category = $5 ORDER BY position ASC LIMIT 1), $6, $7, $8, $9, $10, $11,
+                       (SELECT COALESCE(MIN(rank) - 1, 0) FROM tasks WHERE project_id = $2 AND status = $5))
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64""#,
             task_id,
             data.project_id,
             data.title,
             data.description,
             status,
             data.parent_workspace_id,
-            data.shared_task_id
+            data.shared_task_id,
+            data.due_at,
+            priority,
+            data.estimate,
+            data.assignee_id
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await
     }
 
-    pub async fn update(
-        pool: &SqlitePool,
+    pub async fn update<'e, E>(
+        executor: E,
         id: Uuid,
         project_id: Uuid,
         title: String,
         description: Option<String>,
         status: TaskStatus,
         parent_workspace_id: Option<Uuid>,
-    ) -> Result<Self, sqlx::Error> {
+    ) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
         sqlx::query_as!(
             Task,
             r#"UPDATE tasks
-               SET title = $3, description = $4, status = $5, parent_workspace_id = $6
+               SET title = $3, description = $4, status = $5, parent_workspace_id = $6, version = version + 1,
+                   column_id = CASE WHEN status = $5 THEN column_id
+                       ELSE (SELECT id FROM project_columns WHERE project_id = $2 AND category = $5 ORDER BY position ASC LIMIT 1) END
                WHERE id = $1 AND project_id = $2
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64""#,
             id,
             project_id,
             title,
@@ -311,25 +780,553 @@ ORDER BY t.created_at DESC"#,
             status,
             parent_workspace_id
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await
     }
 
-    pub async fn update_status(
+    /// Move the task on from one of the `versions` an `If-Match` named, first thing in the
+    /// transaction that updates it. `false` when the task has changed to another version
+    /// since, and nothing was written.
+    pub async fn claim_version<'e, E>(
+        executor: E,
+        id: Uuid,
+        versions: &[i64],
+    ) -> Result<bool, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let versions = serde_json::to_string(versions).unwrap_or_default();
+        let result = sqlx::query!(
+            r#"UPDATE tasks
+               SET version = version + 1
+               WHERE id = $1 AND version IN (SELECT value FROM json_each($2))"#,
+            id,
+            versions
+        )
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Create or update the tasks mirroring an integration's issues in a handful of
+    /// statements: one finds the tasks the external ids are linked to, then a multi-row
+    /// `INSERT ... ON CONFLICT DO UPDATE` per few hundred rows writes them. New tasks go at
+    /// the top of their status' first column; updated ones change column only when their
+    /// status does. Linked tasks that moved to another project are left alone and missing
+    /// from the result. Links for new tasks are the caller's to create.
+    #[tracing::instrument(name = "db.bulk_upsert_tasks", skip_all, fields(rows = rows.len()))]
+    pub async fn bulk_upsert_by_external_id(
         pool: &SqlitePool,
+        integration_id: Uuid,
+        project_id: Uuid,
+        rows: &[ExternalTaskUpsert],
+    ) -> Result<Vec<UpsertedTask>, sqlx::Error> {
+        if rows.is_empty() {
+            return Ok(Vec::new());
+        }
+        let external_ids = serde_json::to_string(
+            &rows
+                .iter()
+                .map(|row| row.external_id.as_str())
+                .collect::<Vec<_>>(),
+        )
+        .unwrap_or_default();
+        let linked: HashMap<String, Uuid> = sqlx::query_as::<_, (String, Uuid)>(
+            r#"SELECT external_id, task_id
+               FROM integration_links
+               WHERE integration_id = $1 AND external_id IN (SELECT value FROM json_each($2))"#,
+        )
+        .bind(integration_id)
+        .bind(external_ids)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .collect();
+
+        // Each row's task id, and which rows are new. An issue given twice gets one task,
+        // which its later row updates
+        let mut ids: HashMap<&str, Uuid> = HashMap::with_capacity(rows.len());
+        let mut by_id = HashMap::with_capacity(rows.len());
+        let keyed: Vec<(Uuid, &ExternalTaskUpsert)> = rows
+            .iter()
+            .map(|row| {
+                let existing = linked.get(&row.external_id).copied();
+                let id = *ids
+                    .entry(row.external_id.as_str())
+                    .or_insert_with(|| existing.unwrap_or_else(Uuid::new_v4));
+                by_id.insert(id, (row.external_id.clone(), existing.is_none()));
+                (id, row)
+            })
+            .collect();
+
+        let mut upserted = Vec::with_capacity(rows.len());
+        let mut tx = pool.begin().await?;
+        for chunk in keyed.chunks(UPSERT_CHUNK_ROWS) {
+            let mut query = QueryBuilder::<Sqlite>::new(
+                "INSERT INTO tasks (id, project_id, title, description, status, priority, column_id, rank) ",
+            );
+            query.push_values(chunk, |mut values, (id, row)| {
+                values
+                    .push_bind(*id)
+                    .push_bind(project_id)
+                    .push_bind(row.title.clone())
+                    .push_bind(row.description.clone())
+                    .push_bind(row.status.clone())
+                    .push_bind(row.priority)
+                    .push("(SELECT id FROM project_columns WHERE project_id = ")
+                    .push_bind_unseparated(project_id)
+                    .push_unseparated(" AND category = ")
+                    .push_bind_unseparated(row.status.clone())
+                    .push_unseparated(" ORDER BY position ASC LIMIT 1)")
+                    .push("(SELECT COALESCE(MIN(rank) - 1, 0) FROM tasks WHERE project_id = ")
+                    .push_bind_unseparated(project_id)
+                    .push_unseparated(" AND status = ")
+                    .push_bind_unseparated(row.status.clone())
+                    .push_unseparated(")");
+            });
+            query.push(
+                r#" ON CONFLICT(id) DO UPDATE SET
+                       title = excluded.title,
+                       description = excluded.description,
+                       priority = excluded.priority,
+                       status = excluded.status,
+                       column_id = CASE WHEN tasks.status = excluded.status THEN tasks.column_id
+                           ELSE excluded.column_id END,
+                       updated_at = CURRENT_TIMESTAMP,
+                       version = tasks.version + 1
+                   WHERE tasks.project_id = excluded.project_id
+                   RETURNING id, project_id, title, description, status, column_id, parent_workspace_id, shared_task_id, due_at, priority, estimate, assignee_id, cover_color, cover_attachment_id, archived_at, deleted_at, created_at, updated_at, version"#,
+            );
+            for task in query.build_query_as::<Task>().fetch_all(&mut *tx).await? {
+                if let Some((external_id, created)) = by_id.get(&task.id) {
+                    upserted.push(UpsertedTask {
+                        external_id: external_id.clone(),
+                        task,
+                        created: *created,
+                    });
+                }
+            }
+        }
+        tx.commit().await?;
+        Ok(upserted)
+    }
+
+    pub async fn update_status<'e, E>(
+        executor: E,
         id: Uuid,
         status: TaskStatus,
-    ) -> Result<(), sqlx::Error> {
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
         sqlx::query!(
-            "UPDATE tasks SET status = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+            r#"UPDATE tasks
+               SET status = $2,
+                   column_id = CASE WHEN status = $2 THEN column_id
+                       ELSE (SELECT c.id FROM project_columns c WHERE c.project_id = tasks.project_id AND c.category = $2 ORDER BY c.position ASC LIMIT 1) END,
+                   updated_at = CURRENT_TIMESTAMP
+               WHERE id = $1"#,
             id,
             status
         )
-        .execute(pool)
+        .execute(executor)
         .await?;
         Ok(())
     }
 
+    /// Put the task in a board column, taking on the column's category as its status.
+    pub async fn move_to_column<'e, E>(
+        executor: E,
+        id: Uuid,
+        column_id: Uuid,
+        status: TaskStatus,
+    ) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query_as!(
+            Task,
+            r#"UPDATE tasks
+               SET column_id = $2, status = $3, updated_at = CURRENT_TIMESTAMP, version = version + 1
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64""#,
+            id,
+            column_id,
+            status
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    /// Move the task onto another project's board, at the top of `column_id`. Comments,
+    /// attachments and history stay with the task. Labels carry over to the target
+    /// project's labels of the same name; custom field values and epic or open sprint
+    /// membership, which only make sense in the old project, are dropped.
+    pub async fn move_to_project(
+        pool: &SqlitePool,
+        id: Uuid,
+        project_id: Uuid,
+        column_id: Uuid,
+        status: TaskStatus,
+    ) -> Result<Self, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            r#"INSERT OR IGNORE INTO task_labels (task_id, label_id)
+               SELECT tl.task_id, target.id
+               FROM task_labels tl
+               JOIN labels l ON l.id = tl.label_id
+               JOIN labels target ON target.project_id = $2 AND target.name = l.name
+               WHERE tl.task_id = $1"#,
+            id,
+            project_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"DELETE FROM task_labels
+               WHERE task_id = $1
+                 AND label_id NOT IN (SELECT id FROM labels WHERE project_id = $2)"#,
+            id,
+            project_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"DELETE FROM task_custom_field_values
+               WHERE task_id = $1
+                 AND field_id NOT IN (SELECT id FROM custom_fields WHERE project_id = $2)"#,
+            id,
+            project_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!("DELETE FROM epic_tasks WHERE task_id = $1", id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query!(
+            r#"DELETE FROM sprint_tasks
+               WHERE task_id = $1
+                 AND sprint_id IN (SELECT id FROM sprints WHERE status != 'closed')"#,
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let task = sqlx::query_as!(
+            Task,
+            r#"UPDATE tasks
+               SET project_id = $2, column_id = $3, status = $4,
+                   rank = (SELECT COALESCE(MIN(rank) - 1, 0) FROM tasks WHERE project_id = $2 AND status = $4),
+                   updated_at = CURRENT_TIMESTAMP, version = version + 1
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64""#,
+            id,
+            project_id,
+            column_id,
+            status
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(task)
+    }
+
+    pub async fn update_rank(pool: &SqlitePool, id: Uuid, rank: f64) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"UPDATE tasks
+               SET rank = $2, updated_at = CURRENT_TIMESTAMP, version = version + 1
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64""#,
+            id,
+            rank
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update_due_at<'e, E>(
+        executor: E,
+        id: Uuid,
+        due_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query_as!(
+            Task,
+            r#"UPDATE tasks
+               SET due_at = $2, updated_at = CURRENT_TIMESTAMP, version = version + 1
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64""#,
+            id,
+            due_at
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    pub async fn update_priority<'e, E>(
+        executor: E,
+        id: Uuid,
+        priority: TaskPriority,
+    ) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query_as!(
+            Task,
+            r#"UPDATE tasks
+               SET priority = $2, updated_at = CURRENT_TIMESTAMP, version = version + 1
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64""#,
+            id,
+            priority
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    pub async fn update_estimate<'e, E>(
+        executor: E,
+        id: Uuid,
+        estimate: Option<f64>,
+    ) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query_as!(
+            Task,
+            r#"UPDATE tasks
+               SET estimate = $2, updated_at = CURRENT_TIMESTAMP, version = version + 1
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64""#,
+            id,
+            estimate
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    pub async fn update_cover<'e, E>(
+        executor: E,
+        id: Uuid,
+        cover_color: Option<&str>,
+        cover_attachment_id: Option<Uuid>,
+    ) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query_as!(
+            Task,
+            r#"UPDATE tasks
+               SET cover_color = $2, cover_attachment_id = $3, updated_at = CURRENT_TIMESTAMP, version = version + 1
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64""#,
+            id,
+            cover_color,
+            cover_attachment_id
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    pub async fn update_assignee<'e, E>(
+        executor: E,
+        id: Uuid,
+        assignee_id: Option<Uuid>,
+    ) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query_as!(
+            Task,
+            r#"UPDATE tasks
+               SET assignee_id = $2, updated_at = CURRENT_TIMESTAMP, version = version + 1
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64""#,
+            id,
+            assignee_id
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    /// A project's archived tasks, most recently archived first. `search` matches the
+    /// title or description, case-insensitively.
+    pub async fn find_archived(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        search: Option<&str>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let pattern = search
+            .map(str::trim)
+            .filter(|search| !search.is_empty())
+            .map(|search| format!("%{search}%"));
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64"
+               FROM tasks
+               WHERE project_id = $1
+                 AND archived_at IS NOT NULL
+                 AND deleted_at IS NULL
+                 AND ($2 IS NULL OR title LIKE $2 OR description LIKE $2)
+               ORDER BY archived_at DESC"#,
+            project_id,
+            pattern
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Archive a task. Archiving an archived task keeps its original archive time.
+    pub async fn archive<'e, E>(executor: E, id: Uuid) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query_as!(
+            Task,
+            r#"UPDATE tasks
+               SET archived_at = COALESCE(archived_at, datetime('now', 'subsec')), updated_at = CURRENT_TIMESTAMP, version = version + 1
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64""#,
+            id
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    pub async fn unarchive(pool: &SqlitePool, id: Uuid) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"UPDATE tasks
+               SET archived_at = NULL, updated_at = CURRENT_TIMESTAMP, version = version + 1
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64""#,
+            id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// A project's trashed tasks, most recently deleted first.
+    pub async fn find_trashed(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64"
+               FROM tasks
+               WHERE project_id = $1 AND deleted_at IS NOT NULL
+               ORDER BY deleted_at DESC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Trashed tasks deleted before `cutoff`, due to be purged.
+    pub async fn find_trashed_before(
+        pool: &SqlitePool,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64"
+               FROM tasks
+               WHERE deleted_at IS NOT NULL AND deleted_at < $1
+               ORDER BY deleted_at ASC"#,
+            cutoff
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Done and cancelled tasks that have gone unchanged for longer than their project's
+    /// `auto_archive_after_days` setting. Projects without the setting are skipped.
+    pub async fn find_auto_archivable(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.column_id as "column_id: Uuid", t.parent_workspace_id as "parent_workspace_id: Uuid", t.shared_task_id as "shared_task_id: Uuid", t.due_at as "due_at: DateTime<Utc>", t.priority as "priority!: TaskPriority", t.estimate as "estimate: f64", t.assignee_id as "assignee_id: Uuid", t.cover_color, t.cover_attachment_id as "cover_attachment_id: Uuid", t.archived_at as "archived_at: DateTime<Utc>", t.deleted_at as "deleted_at: DateTime<Utc>", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>", t.version as "version!: i64"
+               FROM tasks t
+               JOIN project_settings ps ON ps.project_id = t.project_id
+               WHERE t.status IN ('done', 'cancelled')
+                 AND t.archived_at IS NULL
+                 AND t.deleted_at IS NULL
+                 AND json_extract(ps.settings, '$.auto_archive_after_days') > 0
+                 AND datetime(t.updated_at) < datetime(
+                     'now', '-' || json_extract(ps.settings, '$.auto_archive_after_days') || ' days'
+                 )
+               ORDER BY t.updated_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Move a task to the trash.
+    pub async fn trash<'e, E>(executor: E, id: Uuid) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        // Bound rather than computed in SQL so the retention cutoff compares in the same format
+        let now = Utc::now();
+        sqlx::query_as!(
+            Task,
+            r#"UPDATE tasks
+               SET deleted_at = COALESCE(deleted_at, $2), updated_at = CURRENT_TIMESTAMP, version = version + 1
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64""#,
+            id,
+            now
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    pub async fn restore(pool: &SqlitePool, id: Uuid) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"UPDATE tasks
+               SET deleted_at = NULL, updated_at = CURRENT_TIMESTAMP, version = version + 1
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64""#,
+            id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Count a project's open tasks that are overdue or due before `until`.
+    pub async fn due_date_summary(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        until: DateTime<Utc>,
+    ) -> Result<DueDateSummary, sqlx::Error> {
+        let now = Utc::now();
+        let counts = sqlx::query!(
+            r#"SELECT
+                 COALESCE(SUM(CASE WHEN due_at < $2 THEN 1 ELSE 0 END), 0) AS "overdue!: i64",
+                 COALESCE(SUM(CASE WHEN due_at >= $2 AND due_at < $3 THEN 1 ELSE 0 END), 0) AS "upcoming!: i64"
+               FROM tasks
+               WHERE project_id = $1
+                 AND archived_at IS NULL
+                 AND deleted_at IS NULL
+                 AND due_at IS NOT NULL
+                 AND status NOT IN ('done', 'cancelled')"#,
+            project_id,
+            now,
+            until
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(DueDateSummary {
+            overdue: counts.overdue,
+            upcoming: counts.upcoming,
+            upcoming_until: until,
+        })
+    }
+
     /// Update the parent_workspace_id field for a task
     pub async fn update_parent_workspace_id(
         pool: &SqlitePool,
@@ -446,7 +1443,7 @@ ORDER BY t.created_at DESC"#,
         // Find only child tasks that have this workspace as their parent
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", due_at as "due_at: DateTime<Utc>", priority as "priority!: TaskPriority", estimate as "estimate: f64", assignee_id as "assignee_id: Uuid", cover_color, cover_attachment_id as "cover_attachment_id: Uuid", archived_at as "archived_at: DateTime<Utc>", deleted_at as "deleted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>", version as "version!: i64"
                FROM tasks
                WHERE parent_workspace_id = $1
                ORDER BY created_at DESC"#,
@@ -490,3 +1487,188 @@ ORDER BY t.created_at DESC"#,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+    use crate::models::project::CreateProject;
+
+    async fn task() -> (SqlitePool, Task) {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::migrations::MIGRATOR.run(&pool).await.unwrap();
+        let project = Project::create(
+            &pool,
+            &CreateProject {
+                name: "Board".to_string(),
+                repositories: Vec::new(),
+                team_id: None,
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let data = CreateTask::from_title_description(project.id, "Task".to_string(), None);
+        let task = Task::create(&pool, &data, Uuid::new_v4()).await.unwrap();
+        (pool, task)
+    }
+
+    async fn add_task(pool: &SqlitePool, project_id: Uuid, title: &str) -> Task {
+        let data = CreateTask::from_title_description(project_id, title.to_string(), None);
+        Task::create(pool, &data, Uuid::new_v4()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_version_is_claimed_once() {
+        let (pool, task) = task().await;
+        assert_eq!(task.version, 1);
+        assert!(!Task::claim_version(&pool, task.id, &[2]).await.unwrap());
+        assert!(Task::claim_version(&pool, task.id, &[1]).await.unwrap());
+        // A second update made against the same version loses
+        assert!(!Task::claim_version(&pool, task.id, &[1]).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_bulk_upsert_creates_then_updates_linked_tasks() {
+        let (pool, task) = task().await;
+        let integration_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO integrations (id, project_id, provider, name, base_url) VALUES ($1, $2, 'jira', 'Jira', 'https://jira.example')",
+        )
+        .bind(integration_id)
+        .bind(task.project_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let row = |title: &str| ExternalTaskUpsert {
+            external_id: "PROJ-1".to_string(),
+            title: title.to_string(),
+            description: None,
+            status: TaskStatus::Todo,
+            priority: TaskPriority::High,
+        };
+
+        let created = Task::bulk_upsert_by_external_id(
+            &pool,
+            integration_id,
+            task.project_id,
+            &[row("First")],
+        )
+        .await
+        .unwrap();
+        assert_eq!(created.len(), 1);
+        assert!(created[0].created);
+        assert_eq!(created[0].task.version, 1);
+        sqlx::query(
+            "INSERT INTO integration_links (id, integration_id, task_id, external_id) VALUES ($1, $2, $3, 'PROJ-1')",
+        )
+        .bind(Uuid::new_v4())
+        .bind(integration_id)
+        .bind(created[0].task.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let updated = Task::bulk_upsert_by_external_id(
+            &pool,
+            integration_id,
+            task.project_id,
+            &[row("Renamed")],
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated.len(), 1);
+        assert!(!updated[0].created);
+        assert_eq!(updated[0].task.id, created[0].task.id);
+        assert_eq!(updated[0].task.title, "Renamed");
+        assert_eq!(updated[0].task.version, 2);
+    }
+
+    #[tokio::test]
+    async fn test_every_write_moves_the_version() {
+        let (pool, task) = task().await;
+        let task = Task::update_priority(&pool, task.id, TaskPriority::High)
+            .await
+            .unwrap();
+        assert_eq!(task.version, 2);
+
+        sqlx::query("UPDATE tasks SET rank = 5 WHERE id = $1")
+            .bind(task.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let task = Task::find_by_id(&pool, task.id).await.unwrap().unwrap();
+        assert_eq!(task.version, 3);
+    }
+
+    #[tokio::test]
+    async fn test_page_query_uses_full_text_search() {
+        let (pool, task) = task().await;
+        add_task(&pool, task.project_id, "Fix login redirect").await;
+        add_task(&pool, task.project_id, "Write docs").await;
+        add_task(&pool, task.project_id, "Login page styling").await;
+
+        let filter = TaskFilter {
+            query: Some("logi".to_string()),
+            ..Default::default()
+        };
+        let page =
+            Task::find_page_with_attempt_status(&pool, task.project_id, &filter, None, None, 0)
+                .await
+                .unwrap();
+        let mut titles: Vec<_> = page.items.iter().map(|task| task.title.as_str()).collect();
+        titles.sort();
+        assert_eq!(titles, ["Fix login redirect", "Login page styling"]);
+    }
+
+    #[tokio::test]
+    async fn test_pages_cover_every_task_once_in_each_sort() {
+        let (pool, task) = task().await;
+        for title in ["A", "B", "C"] {
+            add_task(&pool, task.project_id, title).await;
+        }
+        for sort in [
+            TaskSort::Rank,
+            TaskSort::CreatedAt,
+            TaskSort::Priority,
+            TaskSort::DueAt,
+        ] {
+            let filter = TaskFilter {
+                sort: Some(sort),
+                ..Default::default()
+            };
+            let all =
+                Task::find_page_with_attempt_status(&pool, task.project_id, &filter, None, None, 0)
+                    .await
+                    .unwrap()
+                    .items;
+            let mut paged = Vec::new();
+            let mut after = None;
+            loop {
+                let page = Task::find_page_with_attempt_status(
+                    &pool,
+                    task.project_id,
+                    &filter,
+                    after.as_ref(),
+                    Some(1),
+                    0,
+                )
+                .await
+                .unwrap();
+                paged.extend(page.items.iter().map(|task| task.id));
+                match page.next_cursor {
+                    Some(next) => after = cursor::decode::<TaskCursor>(&next),
+                    None => break,
+                }
+            }
+            let listed: Vec<_> = all.iter().map(|task| task.id).collect();
+            assert_eq!(listed.len(), 4, "{sort}");
+            assert_eq!(paged, listed, "{sort}");
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskAttachment {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// File name inside the attachment cache directory
+    #[serde(skip)]
+    #[ts(skip)]
+    pub file_path: String,
+    pub original_name: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub hash: String,
+    /// Set for attachments imported from an integration
+    pub integration_id: Option<Uuid>,
+    pub external_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateTaskAttachment {
+    pub task_id: Uuid,
+    pub file_path: String,
+    pub original_name: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub hash: String,
+    pub integration_id: Option<Uuid>,
+    pub external_id: Option<String>,
+}
+
+impl TaskAttachment {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttachment,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", file_path, original_name, mime_type, size_bytes as "size_bytes!: i64", hash, integration_id as "integration_id: Uuid", external_id, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_attachments
+               WHERE task_id = $1
+               ORDER BY created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttachment,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", file_path, original_name, mime_type, size_bytes as "size_bytes!: i64", hash, integration_id as "integration_id: Uuid", external_id, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_attachments
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_external_id(
+        pool: &SqlitePool,
+        integration_id: Uuid,
+        external_id: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttachment,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", file_path, original_name, mime_type, size_bytes as "size_bytes!: i64", hash, integration_id as "integration_id: Uuid", external_id, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_attachments
+               WHERE integration_id = $1 AND external_id = $2"#,
+            integration_id,
+            external_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// File names of every stored attachment, used to find orphaned files.
+    pub async fn find_all_file_paths(pool: &SqlitePool) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar!(r#"SELECT file_path FROM task_attachments"#)
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateTaskAttachment,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskAttachment,
+            r#"INSERT INTO task_attachments (id, task_id, file_path, original_name, mime_type, size_bytes, hash, integration_id, external_id)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", file_path, original_name, mime_type, size_bytes as "size_bytes!: i64", hash, integration_id as "integration_id: Uuid", external_id, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.task_id,
+            data.file_path,
+            data.original_name,
+            data.mime_type,
+            data.size_bytes,
+            data.hash,
+            data.integration_id,
+            data.external_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_attachments WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
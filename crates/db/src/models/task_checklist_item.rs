@@ -0,0 +1,140 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskChecklistItem {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub title: String,
+    pub done: bool,
+    /// Zero-based order within the task's checklist
+    pub position: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateTaskChecklistItem {
+    pub title: String,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct UpdateTaskChecklistItem {
+    pub title: Option<String>,
+    pub done: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct ReorderTaskChecklistItems {
+    /// Every item of the task, in the desired order
+    pub item_ids: Vec<Uuid>,
+}
+
+impl TaskChecklistItem {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskChecklistItem,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", title, done as "done!: bool", position, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_checklist_items
+               WHERE task_id = $1
+               ORDER BY position ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskChecklistItem,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", title, done as "done!: bool", position, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_checklist_items
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Append an item to the end of the task's checklist.
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        data: &CreateTaskChecklistItem,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskChecklistItem,
+            r#"INSERT INTO task_checklist_items (id, task_id, title, position)
+               VALUES ($1, $2, $3, (SELECT COALESCE(MAX(position) + 1, 0) FROM task_checklist_items WHERE task_id = $2))
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", title, done as "done!: bool", position, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            data.title
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateTaskChecklistItem,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let title = data.title.as_ref().unwrap_or(&existing.title);
+        let done = data.done.unwrap_or(existing.done);
+
+        sqlx::query_as!(
+            TaskChecklistItem,
+            r#"UPDATE task_checklist_items
+               SET title = $2, done = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", title, done as "done!: bool", position, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            title,
+            done
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_checklist_items WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Renumber a task's items to follow `item_ids`. Callers check that the ids are
+    /// exactly the task's items.
+    pub async fn reorder(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        item_ids: &[Uuid],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for (position, item_id) in item_ids.iter().enumerate() {
+            let position = position as i64;
+            sqlx::query!(
+                "UPDATE task_checklist_items SET position = $3 WHERE id = $1 AND task_id = $2",
+                item_id,
+                task_id,
+                position
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+}
@@ -0,0 +1,118 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskComment {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub author: String,
+    /// Markdown
+    pub body: String,
+    /// Set for comments imported from an integration
+    pub integration_id: Option<Uuid>,
+    pub external_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateTaskComment {
+    /// Ignored when the request is signed in; the signed-in user is the author
+    pub author: String,
+    pub body: String,
+}
+
+impl TaskComment {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskComment,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", author, body, integration_id as "integration_id: Uuid", external_id, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_comments
+               WHERE task_id = $1
+               ORDER BY created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskComment,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", author, body, integration_id as "integration_id: Uuid", external_id, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_comments
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        data: &CreateTaskComment,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskComment,
+            r#"INSERT INTO task_comments (id, task_id, author, body)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", author, body, integration_id as "integration_id: Uuid", external_id, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            data.author,
+            data.body
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Insert a comment imported from an integration, or refresh the body of one imported
+    /// earlier. The remote creation time is kept so imported threads stay in order.
+    pub async fn upsert_imported(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        integration_id: Uuid,
+        external_id: &str,
+        author: &str,
+        body: &str,
+        created_at: DateTime<Utc>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskComment,
+            r#"INSERT INTO task_comments (id, task_id, author, body, integration_id, external_id, created_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               ON CONFLICT(integration_id, external_id) WHERE external_id IS NOT NULL DO UPDATE SET
+                   task_id = excluded.task_id,
+                   author = excluded.author,
+                   body = excluded.body,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", author, body, integration_id as "integration_id: Uuid", external_id, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            author,
+            body,
+            integration_id,
+            external_id,
+            created_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_comments WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,151 @@
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use super::{custom_field::TaskCustomFieldValue, task::Task, task_revision::TaskRevision};
+
+/// One side of a task diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffSide {
+    /// A numbered revision of the title and description
+    Revision(i64),
+    /// The task as it is now
+    Local,
+    /// The linked external issue as of the last sync
+    Remote,
+}
+
+impl FromStr for DiffSide {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "local" => Ok(Self::Local),
+            "remote" => Ok(Self::Remote),
+            _ => value
+                .parse::<i64>()
+                .ok()
+                .filter(|revision| *revision > 0)
+                .map(Self::Revision)
+                .ok_or_else(|| format!("'{value}' is not a revision number, 'local' or 'remote'")),
+        }
+    }
+}
+
+impl fmt::Display for DiffSide {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Revision(revision) => write!(f, "{revision}"),
+            Self::Local => f.write_str("local"),
+            Self::Remote => f.write_str("remote"),
+        }
+    }
+}
+
+/// Field name -> value, named as in sync baselines: `title`, `description`, `status`,
+/// `priority` and `custom:<field name>`.
+pub type FieldValues = BTreeMap<String, Option<String>>;
+
+/// A field whose value differs between the two sides.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, ToSchema)]
+pub struct FieldChange {
+    pub field: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskDiff {
+    pub from: String,
+    pub to: String,
+    /// Only fields both sides have are compared; revisions hold just the title and
+    /// description
+    pub changes: Vec<FieldChange>,
+}
+
+impl TaskDiff {
+    /// Compare the fields `from` and `to` have in common, in field name order.
+    pub fn between(
+        from: DiffSide,
+        from_values: &FieldValues,
+        to: DiffSide,
+        to_values: &FieldValues,
+    ) -> Self {
+        let changes = from_values
+            .iter()
+            .filter_map(|(field, old)| {
+                let new = to_values.get(field)?;
+                (old != new).then(|| FieldChange {
+                    field: field.clone(),
+                    from: old.clone(),
+                    to: new.clone(),
+                })
+            })
+            .collect();
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            changes,
+        }
+    }
+}
+
+/// The task's current values, named like the sync baseline of a linked issue.
+pub fn task_values(task: &Task, custom_fields: &[TaskCustomFieldValue]) -> FieldValues {
+    let mut values = FieldValues::from([
+        ("title".to_string(), Some(task.title.clone())),
+        ("description".to_string(), task.description.clone()),
+        ("status".to_string(), Some(task.status.to_string())),
+        ("priority".to_string(), Some(task.priority.to_string())),
+    ]);
+    for field in custom_fields {
+        values.insert(format!("custom:{}", field.name), Some(field.value.clone()));
+    }
+    values
+}
+
+pub fn revision_values(revision: &TaskRevision) -> FieldValues {
+    FieldValues::from([
+        ("title".to_string(), Some(revision.title.clone())),
+        ("description".to_string(), revision.description.clone()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_side() {
+        assert_eq!("local".parse::<DiffSide>(), Ok(DiffSide::Local));
+        assert_eq!("remote".parse::<DiffSide>(), Ok(DiffSide::Remote));
+        assert_eq!("3".parse::<DiffSide>(), Ok(DiffSide::Revision(3)));
+        assert!("0".parse::<DiffSide>().is_err());
+        assert!("head".parse::<DiffSide>().is_err());
+    }
+
+    #[test]
+    fn test_between_compares_common_fields() {
+        let revision = FieldValues::from([
+            ("title".to_string(), Some("Fix login".to_string())),
+            ("description".to_string(), None),
+        ]);
+        let local = FieldValues::from([
+            ("title".to_string(), Some("Fix login on Safari".to_string())),
+            ("description".to_string(), None),
+            ("status".to_string(), Some("inprogress".to_string())),
+        ]);
+        let diff = TaskDiff::between(DiffSide::Revision(1), &revision, DiffSide::Local, &local);
+        assert_eq!(diff.from, "1");
+        assert_eq!(
+            diff.changes,
+            vec![FieldChange {
+                field: "title".to_string(),
+                from: Some("Fix login".to_string()),
+                to: Some("Fix login on Safari".to_string()),
+            }]
+        );
+    }
+}
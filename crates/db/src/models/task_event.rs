@@ -0,0 +1,572 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqliteConnection, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    notification::{Notification, NotificationKind},
+    task::{Task, TaskStatus},
+    task_mention::TaskMention,
+    task_revision::TaskRevision,
+    task_watcher::{TaskWatcher, WatchReason},
+};
+use crate::cursor::{self, CreatedKey, Page};
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "task_event_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TaskEventKind {
+    Created,
+    StatusChanged,
+    /// Title, description, due date, priority or estimate; see `field`
+    Edited,
+    AssigneeChanged,
+    Archived,
+    Unarchived,
+    Deleted,
+    Restored,
+    /// Folded into another task or had one folded in; `field` says which and the value
+    /// is the other task's id
+    Merged,
+}
+
+/// What made the change.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "task_event_source", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TaskEventSource {
+    User,
+    /// A coding agent run moving the task through its workflow
+    Agent,
+    /// An integration sync; `integration_id` says which
+    Sync,
+    /// Background jobs such as PR monitoring and recurrence
+    System,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskEvent {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub kind: TaskEventKind,
+    pub source: TaskEventSource,
+    pub integration_id: Option<Uuid>,
+    pub field: Option<String>,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateTaskEvent {
+    pub kind: TaskEventKind,
+    pub field: Option<String>,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+impl CreateTaskEvent {
+    fn new(kind: TaskEventKind) -> Self {
+        Self {
+            kind,
+            field: None,
+            old_value: None,
+            new_value: None,
+        }
+    }
+
+    fn change<T: ToString>(
+        kind: TaskEventKind,
+        field: &str,
+        old_value: Option<T>,
+        new_value: Option<T>,
+    ) -> Self {
+        Self {
+            kind,
+            field: Some(field.to_string()),
+            old_value: old_value.map(|v| v.to_string()),
+            new_value: new_value.map(|v| v.to_string()),
+        }
+    }
+}
+
+const DESCRIPTION_MENTION: &str = "Mentioned you in the description";
+
+/// Text synced from an integration @mentions remote accounts, not local users.
+fn mentions_local(source: TaskEventSource) -> bool {
+    source != TaskEventSource::Sync
+}
+
+fn status_message(from: &TaskStatus, to: &TaskStatus) -> String {
+    format!("Status changed from {from} to {to}")
+}
+
+/// The events that turn `before` into `after`, in a stable order.
+pub fn diff_tasks(before: &Task, after: &Task) -> Vec<CreateTaskEvent> {
+    use TaskEventKind::*;
+
+    let mut events = Vec::new();
+    if before.status != after.status {
+        events.push(CreateTaskEvent::change(
+            StatusChanged,
+            "status",
+            Some(&before.status),
+            Some(&after.status),
+        ));
+    } else if before.column_id != after.column_id {
+        // A status change already says which way the task moved on the board
+        events.push(CreateTaskEvent::change(
+            Edited,
+            "column_id",
+            before.column_id,
+            after.column_id,
+        ));
+    }
+    if before.title != after.title {
+        events.push(CreateTaskEvent::change(
+            Edited,
+            "title",
+            Some(&before.title),
+            Some(&after.title),
+        ));
+    }
+    if before.description != after.description {
+        events.push(CreateTaskEvent::change(
+            Edited,
+            "description",
+            before.description.as_ref(),
+            after.description.as_ref(),
+        ));
+    }
+    if before.due_at != after.due_at {
+        events.push(CreateTaskEvent::change(
+            Edited,
+            "due_at",
+            before.due_at.map(|d| d.to_rfc3339()),
+            after.due_at.map(|d| d.to_rfc3339()),
+        ));
+    }
+    if before.priority != after.priority {
+        events.push(CreateTaskEvent::change(
+            Edited,
+            "priority",
+            Some(before.priority),
+            Some(after.priority),
+        ));
+    }
+    if before.estimate != after.estimate {
+        events.push(CreateTaskEvent::change(
+            Edited,
+            "estimate",
+            before.estimate,
+            after.estimate,
+        ));
+    }
+    if before.assignee_id != after.assignee_id {
+        events.push(CreateTaskEvent::change(
+            AssigneeChanged,
+            "assignee_id",
+            before.assignee_id,
+            after.assignee_id,
+        ));
+    }
+    match (before.archived_at, after.archived_at) {
+        (None, Some(_)) => events.push(CreateTaskEvent::new(Archived)),
+        (Some(_), None) => events.push(CreateTaskEvent::new(Unarchived)),
+        _ => {}
+    }
+    match (before.deleted_at, after.deleted_at) {
+        (None, Some(_)) => events.push(CreateTaskEvent::new(Deleted)),
+        (Some(_), None) => events.push(CreateTaskEvent::new(Restored)),
+        _ => {}
+    }
+    events
+}
+
+/// The value a field had at `at`, worked out from the task's changes to that field
+/// (oldest first) and the value it has now.
+pub fn value_at(
+    changes: &[&TaskEvent],
+    at: DateTime<Utc>,
+    current: Option<String>,
+) -> Option<String> {
+    match changes.iter().rev().find(|event| event.created_at <= at) {
+        Some(event) => event.new_value.clone(),
+        // Before the first change the field still had that change's old value
+        None => match changes.first() {
+            Some(event) => event.old_value.clone(),
+            None => current,
+        },
+    }
+}
+
+impl TaskEvent {
+    /// Oldest first, so the feed reads in the order things happened.
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskEvent,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", kind as "kind!: TaskEventKind", source as "source!: TaskEventSource", integration_id as "integration_id: Uuid", field, old_value, new_value, created_at as "created_at!: DateTime<Utc>"
+               FROM task_events
+               WHERE task_id = $1
+               ORDER BY created_at ASC, rowid ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Like [`Self::find_by_task_id`], a page at a time starting right after `after`.
+    pub async fn find_page_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        after: Option<&CreatedKey>,
+        limit: Option<i64>,
+    ) -> Result<Page<Self>, sqlx::Error> {
+        let fetch_limit = cursor::fetch_limit(limit);
+        let after_created_at = after.map(|key| key.created_at.as_str());
+        let after_rowid = after.map(|key| key.rowid);
+        let records = sqlx::query!(
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", kind as "kind!: TaskEventKind", source as "source!: TaskEventSource", integration_id as "integration_id: Uuid", field, old_value, new_value, created_at as "created_at!: DateTime<Utc>", created_at as "created_at_key!: String", rowid as "rowid!: i64"
+               FROM task_events
+               WHERE task_id = $1
+                 AND ($2 IS NULL OR (created_at, rowid) > ($2, $3))
+               ORDER BY created_at ASC, rowid ASC
+               LIMIT COALESCE($4, -1)"#,
+            task_id,
+            after_created_at,
+            after_rowid,
+            fetch_limit
+        )
+        .fetch_all(pool)
+        .await?;
+        let rows = records
+            .into_iter()
+            .map(|rec| {
+                let key = CreatedKey {
+                    created_at: rec.created_at_key,
+                    rowid: rec.rowid,
+                };
+                let event = TaskEvent {
+                    id: rec.id,
+                    task_id: rec.task_id,
+                    kind: rec.kind,
+                    source: rec.source,
+                    integration_id: rec.integration_id,
+                    field: rec.field,
+                    old_value: rec.old_value,
+                    new_value: rec.new_value,
+                    created_at: rec.created_at,
+                };
+                (event, key)
+            })
+            .collect();
+        Ok(Page::from_rows(rows, limit))
+    }
+
+    pub async fn create<'e, E>(
+        executor: E,
+        task_id: Uuid,
+        source: TaskEventSource,
+        integration_id: Option<Uuid>,
+        data: &CreateTaskEvent,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO task_events (id, task_id, kind, source, integration_id, field, old_value, new_value)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#,
+            id,
+            task_id,
+            data.kind,
+            source,
+            integration_id,
+            data.field,
+            data.old_value,
+            data.new_value
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Record the task's creation along with its first revision. An assignee starts
+    /// watching the task, and users @mentioned in a description written here are
+    /// notified.
+    pub async fn record_created(
+        pool: &SqlitePool,
+        task: &Task,
+        source: TaskEventSource,
+        integration_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        Self::create(
+            &mut *tx,
+            task.id,
+            source,
+            integration_id,
+            &CreateTaskEvent::new(TaskEventKind::Created),
+        )
+        .await?;
+        TaskRevision::create(&mut *tx, task, source, integration_id).await?;
+        if let Some(assignee_id) = task.assignee_id {
+            TaskWatcher::watch(&mut *tx, task.id, assignee_id, WatchReason::Assigned).await?;
+        }
+        if let Some(description) = task.description.as_deref()
+            && mentions_local(source)
+        {
+            TaskMention::record(
+                &mut *tx,
+                task.id,
+                None,
+                description,
+                None,
+                DESCRIPTION_MENTION,
+            )
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Record everything that differs between two versions of a task. A changed title
+    /// or description also snapshots a new revision, a new assignee starts watching the
+    /// task, a status change notifies its watchers and newly @mentioned users are
+    /// notified.
+    pub async fn record_changes(
+        pool: &SqlitePool,
+        before: &Task,
+        after: &Task,
+        source: TaskEventSource,
+        integration_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        let events = diff_tasks(before, after);
+        if events.is_empty() {
+            return Ok(());
+        }
+        let mut tx = pool.begin().await?;
+        for event in &events {
+            Self::create(&mut *tx, after.id, source, integration_id, event).await?;
+        }
+        if before.title != after.title || before.description != after.description {
+            TaskRevision::create(&mut *tx, after, source, integration_id).await?;
+        }
+        if let Some(assignee_id) = after.assignee_id
+            && before.assignee_id != after.assignee_id
+        {
+            TaskWatcher::watch(&mut *tx, after.id, assignee_id, WatchReason::Assigned).await?;
+        }
+        if before.status != after.status {
+            Notification::notify_watchers(
+                &mut *tx,
+                after.id,
+                NotificationKind::StatusChanged,
+                &status_message(&before.status, &after.status),
+                &[],
+            )
+            .await?;
+        }
+        if let Some(description) = after.description.as_deref()
+            && before.description != after.description
+            && mentions_local(source)
+        {
+            TaskMention::record(
+                &mut *tx,
+                after.id,
+                None,
+                description,
+                None,
+                DESCRIPTION_MENTION,
+            )
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Changes to the given fields of a project's tasks, oldest first.
+    pub async fn find_field_changes_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        fields: &[&str],
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let fields = serde_json::to_string(fields).unwrap_or_default();
+        sqlx::query_as!(
+            TaskEvent,
+            r#"SELECT e.id as "id!: Uuid", e.task_id as "task_id!: Uuid", e.kind as "kind!: TaskEventKind", e.source as "source!: TaskEventSource", e.integration_id as "integration_id: Uuid", e.field, e.old_value, e.new_value, e.created_at as "created_at!: DateTime<Utc>"
+               FROM task_events e
+               JOIN tasks t ON t.id = e.task_id
+               WHERE t.project_id = $1
+                 AND e.field IN (SELECT value FROM json_each($2))
+               ORDER BY e.created_at ASC, e.rowid ASC"#,
+            project_id,
+            fields
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Record on both tasks that `merged_id` was folded into `target_id`.
+    pub async fn record_merge(
+        conn: &mut SqliteConnection,
+        target_id: Uuid,
+        merged_id: Uuid,
+        source: TaskEventSource,
+    ) -> Result<(), sqlx::Error> {
+        let merged_into =
+            CreateTaskEvent::change(TaskEventKind::Merged, "merged_into", None, Some(target_id));
+        Self::create(&mut *conn, merged_id, source, None, &merged_into).await?;
+        let merged_task = CreateTaskEvent::change(
+            TaskEventKind::Merged,
+            "merged_task_id",
+            None,
+            Some(merged_id),
+        );
+        Self::create(&mut *conn, target_id, source, None, &merged_task).await
+    }
+
+    /// Record a status change made without loading the whole task, and notify its
+    /// watchers.
+    pub async fn record_status_change(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        from: &TaskStatus,
+        to: &TaskStatus,
+        source: TaskEventSource,
+    ) -> Result<(), sqlx::Error> {
+        if from == to {
+            return Ok(());
+        }
+        let mut tx = pool.begin().await?;
+        Self::create(
+            &mut *tx,
+            task_id,
+            source,
+            None,
+            &CreateTaskEvent::change(TaskEventKind::StatusChanged, "status", Some(from), Some(to)),
+        )
+        .await?;
+        Notification::notify_watchers(
+            &mut *tx,
+            task_id,
+            NotificationKind::StatusChanged,
+            &status_message(from, to),
+            &[],
+        )
+        .await?;
+        tx.commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::task::TaskPriority;
+
+    fn task() -> Task {
+        let now = Utc::now();
+        Task {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            title: "Fix login".to_string(),
+            description: None,
+            status: TaskStatus::Todo,
+            column_id: None,
+            parent_workspace_id: None,
+            shared_task_id: None,
+            due_at: None,
+            priority: TaskPriority::Medium,
+            estimate: None,
+            assignee_id: None,
+            cover_color: None,
+            cover_attachment_id: None,
+            archived_at: None,
+            deleted_at: None,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn test_diff_tasks() {
+        let before = task();
+        assert!(diff_tasks(&before, &before.clone()).is_empty());
+
+        let mut after = before.clone();
+        after.status = TaskStatus::InReview;
+        after.description = Some("Token expiry".to_string());
+        after.priority = TaskPriority::High;
+        after.archived_at = Some(Utc::now());
+
+        let events = diff_tasks(&before, &after);
+        let summary: Vec<_> = events
+            .iter()
+            .map(|e| {
+                (
+                    e.kind,
+                    e.field.as_deref(),
+                    e.old_value.as_deref(),
+                    e.new_value.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    TaskEventKind::StatusChanged,
+                    Some("status"),
+                    Some("todo"),
+                    Some("inreview")
+                ),
+                (
+                    TaskEventKind::Edited,
+                    Some("description"),
+                    None,
+                    Some("Token expiry")
+                ),
+                (
+                    TaskEventKind::Edited,
+                    Some("priority"),
+                    Some("medium"),
+                    Some("high")
+                ),
+                (TaskEventKind::Archived, None, None, None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "task_link_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TaskLinkKind {
+    Blocks,
+    /// Never stored; the reverse of `blocks` as seen from the blocked task
+    BlockedBy,
+    RelatesTo,
+    Duplicates,
+    /// Never stored; the reverse of `duplicates` as seen from the original task
+    DuplicatedBy,
+}
+
+impl TaskLinkKind {
+    /// The same relation seen from the other task.
+    pub fn reversed(self) -> Self {
+        match self {
+            Self::Blocks => Self::BlockedBy,
+            Self::BlockedBy => Self::Blocks,
+            Self::RelatesTo => Self::RelatesTo,
+            Self::Duplicates => Self::DuplicatedBy,
+            Self::DuplicatedBy => Self::Duplicates,
+        }
+    }
+
+    /// How the relation reads in a sentence, e.g. "VK-1 is duplicated by VK-2".
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Blocks => "blocks",
+            Self::BlockedBy => "is blocked by",
+            Self::RelatesTo => "relates to",
+            Self::Duplicates => "duplicates",
+            Self::DuplicatedBy => "is duplicated by",
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskLink {
+    pub id: Uuid,
+    pub source_task_id: Uuid,
+    pub target_task_id: Uuid,
+    pub kind: TaskLinkKind,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A link as seen from one of its tasks, with the task on the other end.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct LinkedTask {
+    pub link_id: Uuid,
+    /// Relation of the viewed task to `task_id`
+    pub kind: TaskLinkKind,
+    /// `kind` as displayed, e.g. "is duplicated by"
+    pub label: String,
+    pub task_id: Uuid,
+    pub title: String,
+    pub status: TaskStatus,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateTaskLink {
+    pub task_id: Uuid,
+    pub kind: TaskLinkKind,
+}
+
+impl TaskLink {
+    /// Orient a relation from `task_id` to `other_task_id` the way it is stored:
+    /// `blocked_by` and `duplicated_by` become the reverse `blocks` and `duplicates`.
+    pub fn orient(
+        task_id: Uuid,
+        other_task_id: Uuid,
+        kind: TaskLinkKind,
+    ) -> (Uuid, Uuid, TaskLinkKind) {
+        match kind {
+            TaskLinkKind::BlockedBy | TaskLinkKind::DuplicatedBy => {
+                (other_task_id, task_id, kind.reversed())
+            }
+            kind => (task_id, other_task_id, kind),
+        }
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskLink,
+            r#"SELECT id as "id!: Uuid", source_task_id as "source_task_id!: Uuid", target_task_id as "target_task_id!: Uuid", kind as "kind!: TaskLinkKind", created_at as "created_at!: DateTime<Utc>"
+               FROM task_links
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Links touching a task in either direction, oriented from that task.
+    pub async fn find_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<LinkedTask>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT l.id as "id!: Uuid", l.source_task_id as "source_task_id!: Uuid", l.kind as "kind!: TaskLinkKind", t.id as "task_id!: Uuid", t.title, t.status as "status!: TaskStatus"
+               FROM task_links l
+               JOIN tasks t ON t.id = CASE WHEN l.source_task_id = $1 THEN l.target_task_id ELSE l.source_task_id END
+               WHERE l.source_task_id = $1 OR l.target_task_id = $1
+               ORDER BY l.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let kind = if row.source_task_id == task_id {
+                    row.kind
+                } else {
+                    row.kind.reversed()
+                };
+                LinkedTask {
+                    link_id: row.id,
+                    kind,
+                    label: kind.label().to_string(),
+                    task_id: row.task_id,
+                    title: row.title,
+                    status: row.status,
+                }
+            })
+            .collect())
+    }
+
+    /// Whether two tasks are already linked with `kind`, in either orientation for
+    /// symmetric relations.
+    pub async fn exists(
+        pool: &SqlitePool,
+        source_task_id: Uuid,
+        target_task_id: Uuid,
+        kind: TaskLinkKind,
+    ) -> Result<bool, sqlx::Error> {
+        let symmetric = kind == TaskLinkKind::RelatesTo;
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS (
+                   SELECT 1 FROM task_links
+                   WHERE kind = $3
+                     AND ((source_task_id = $1 AND target_task_id = $2)
+                       OR ($4 AND source_task_id = $2 AND target_task_id = $1))
+               ) as "exists!: bool""#,
+            source_task_id,
+            target_task_id,
+            kind,
+            symmetric
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(exists)
+    }
+
+    /// Whether `source` blocking `target` would close a cycle, i.e. `target` already
+    /// blocks `source` directly or transitively.
+    pub async fn would_create_cycle(
+        pool: &SqlitePool,
+        source_task_id: Uuid,
+        target_task_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let cycle = sqlx::query_scalar!(
+            r#"WITH RECURSIVE downstream(id) AS (
+                   SELECT $1
+                   UNION
+                   SELECT l.target_task_id
+                     FROM task_links l
+                     JOIN downstream d ON l.source_task_id = d.id
+                    WHERE l.kind = 'blocks'
+               )
+               SELECT EXISTS (SELECT 1 FROM downstream WHERE id = $2) as "cycle!: bool""#,
+            target_task_id,
+            source_task_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(cycle)
+    }
+
+    /// Why a stored link from `source_task_id` to `target_task_id` can't be added: it
+    /// exists already, would close a dependency cycle, or would mark two tasks as
+    /// duplicates of each other.
+    pub async fn find_conflict(
+        pool: &SqlitePool,
+        source_task_id: Uuid,
+        target_task_id: Uuid,
+        kind: TaskLinkKind,
+    ) -> Result<Option<&'static str>, sqlx::Error> {
+        if Self::exists(pool, source_task_id, target_task_id, kind).await? {
+            return Ok(Some("These tasks are already linked"));
+        }
+        let conflict = match kind {
+            TaskLinkKind::Blocks
+                if Self::would_create_cycle(pool, source_task_id, target_task_id).await? =>
+            {
+                Some("This dependency would create a cycle")
+            }
+            TaskLinkKind::Duplicates
+                if Self::exists(pool, target_task_id, source_task_id, kind).await? =>
+            {
+                Some("The other task is already a duplicate of this one")
+            }
+            _ => None,
+        };
+        Ok(conflict)
+    }
+
+    /// Link two tasks. `blocked_by` and `duplicated_by` are stored as the reverse link.
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        other_task_id: Uuid,
+        kind: TaskLinkKind,
+    ) -> Result<Self, sqlx::Error> {
+        let (source_task_id, target_task_id, kind) = Self::orient(task_id, other_task_id, kind);
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskLink,
+            r#"INSERT INTO task_links (id, source_task_id, target_task_id, kind)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid", source_task_id as "source_task_id!: Uuid", target_task_id as "target_task_id!: Uuid", kind as "kind!: TaskLinkKind", created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            source_task_id,
+            target_task_id,
+            kind
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_links WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reversed_is_an_involution() {
+        for kind in [
+            TaskLinkKind::Blocks,
+            TaskLinkKind::BlockedBy,
+            TaskLinkKind::RelatesTo,
+            TaskLinkKind::Duplicates,
+            TaskLinkKind::DuplicatedBy,
+        ] {
+            assert_eq!(kind.reversed().reversed(), kind);
+        }
+        assert_eq!(TaskLinkKind::Blocks.reversed(), TaskLinkKind::BlockedBy);
+    }
+
+    #[test]
+    fn test_orient_stores_reverse_kinds_forward() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(
+            TaskLink::orient(a, b, TaskLinkKind::BlockedBy),
+            (b, a, TaskLinkKind::Blocks)
+        );
+        assert_eq!(
+            TaskLink::orient(a, b, TaskLinkKind::DuplicatedBy),
+            (b, a, TaskLinkKind::Duplicates)
+        );
+        assert_eq!(
+            TaskLink::orient(a, b, TaskLinkKind::RelatesTo),
+            (a, b, TaskLinkKind::RelatesTo)
+        );
+    }
+}
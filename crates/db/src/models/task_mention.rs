@@ -0,0 +1,161 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqliteConnection, SqlitePool};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::notification::{Notification, NotificationKind};
+
+/// A user @mentioned in a task's description or one of its comments.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskMention {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub task_title: String,
+    /// The comment the mention is in; `None` for the task description
+    pub comment_id: Option<Uuid>,
+    pub user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+fn is_handle_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-')
+}
+
+/// The lowercased handles @mentioned in Markdown text, in order of first appearance.
+/// Code spans and fenced blocks are skipped, as is the `@` of an email address.
+pub fn parse_mentions(text: &str) -> Vec<String> {
+    let mut handles = Vec::new();
+    let mut in_fence = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let mut in_code = false;
+        let mut prev = None;
+        for (i, c) in line.char_indices() {
+            if c == '`' {
+                in_code = !in_code;
+            } else if c == '@' && !in_code && !prev.is_some_and(is_handle_char) {
+                let rest = &line[i + 1..];
+                let end = rest.find(|c| !is_handle_char(c)).unwrap_or(rest.len());
+                let handle = rest[..end]
+                    .trim_end_matches(['.', '-'])
+                    .to_ascii_lowercase();
+                if !handle.is_empty() && !handles.contains(&handle) {
+                    handles.push(handle);
+                }
+            }
+            prev = Some(c);
+        }
+    }
+    handles
+}
+
+impl TaskMention {
+    /// Where the user was mentioned, newest first. Trashed tasks are left out.
+    pub async fn find_by_user_id(
+        pool: &SqlitePool,
+        user_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskMention,
+            r#"SELECT m.id as "id!: Uuid", m.task_id as "task_id!: Uuid", t.project_id as "project_id!: Uuid", t.title as "task_title!: String", m.comment_id as "comment_id: Uuid", m.user_id as "user_id!: Uuid", m.created_at as "created_at!: DateTime<Utc>"
+               FROM task_mentions m
+               JOIN tasks t ON t.id = m.task_id
+               WHERE m.user_id = $1 AND t.deleted_at IS NULL
+               ORDER BY m.created_at DESC"#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Users whose handle is mentioned in `text`. A handle is the user's name with
+    /// spaces removed, compared case-insensitively; handles shared by several users
+    /// are ambiguous and ignored.
+    pub async fn find_mentioned_users(
+        conn: &mut SqliteConnection,
+        text: &str,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        let handles = parse_mentions(text);
+        if handles.is_empty() {
+            return Ok(Vec::new());
+        }
+        let handles = serde_json::to_string(&handles).unwrap_or_default();
+        sqlx::query_scalar!(
+            r#"SELECT u.id as "id!: Uuid"
+               FROM users u
+               WHERE lower(replace(u.name, ' ', '')) IN (SELECT value FROM json_each($1))
+                 AND (SELECT COUNT(*) FROM users o
+                      WHERE lower(replace(o.name, ' ', '')) = lower(replace(u.name, ' ', ''))) = 1"#,
+            handles
+        )
+        .fetch_all(conn)
+        .await
+    }
+
+    /// Store the mentions in a description (`comment_id` of `None`) or comment and
+    /// notify each user mentioned there for the first time, except `author`. Returns the
+    /// users notified.
+    pub async fn record(
+        conn: &mut SqliteConnection,
+        task_id: Uuid,
+        comment_id: Option<Uuid>,
+        text: &str,
+        author: Option<Uuid>,
+        message: &str,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        let user_ids = Self::find_mentioned_users(&mut *conn, text).await?;
+        let mut notified = Vec::new();
+        for user_id in user_ids {
+            let id = Uuid::new_v4();
+            let inserted = sqlx::query!(
+                r#"INSERT INTO task_mentions (id, task_id, comment_id, user_id)
+                   VALUES ($1, $2, $3, $4)
+                   ON CONFLICT DO NOTHING"#,
+                id,
+                task_id,
+                comment_id,
+                user_id
+            )
+            .execute(&mut *conn)
+            .await?
+            .rows_affected();
+            if inserted > 0
+                && Some(user_id) != author
+                && Notification::create(
+                    &mut *conn,
+                    user_id,
+                    task_id,
+                    NotificationKind::Mentioned,
+                    message,
+                )
+                .await?
+            {
+                notified.push(user_id);
+            }
+        }
+        Ok(notified)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mentions() {
+        let text = "Thanks @Alice, ping @bob.smith.\n\
+                    Mail carol@example.com or see `@dave`\n\
+                    ```\n@erin\n```\n\
+                    cc @alice and @-";
+        assert_eq!(parse_mentions(text), vec!["alice", "bob.smith"]);
+    }
+}
@@ -0,0 +1,176 @@
+use serde::Deserialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    task::Task,
+    task_event::{TaskEvent, TaskEventSource},
+};
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct MergeTasks {
+    /// Duplicates to fold into the target task, in the order their descriptions are
+    /// appended
+    pub task_ids: Vec<Uuid>,
+}
+
+/// The target's description followed by each merged task's, under a note naming the
+/// task it came from. `None` when no task has a description.
+pub fn merged_description(target: &Task, merged: &[Task]) -> Option<String> {
+    let mut parts: Vec<String> = target
+        .description
+        .iter()
+        .filter(|description| !description.trim().is_empty())
+        .map(|description| description.trim_end().to_string())
+        .collect();
+    for task in merged {
+        if let Some(description) = task
+            .description
+            .as_deref()
+            .filter(|description| !description.trim().is_empty())
+        {
+            parts.push(format!(
+                "_Merged from \"{}\":_\n\n{}",
+                task.title,
+                description.trim_end()
+            ));
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("\n\n---\n\n"))
+}
+
+/// Fold `merged` into `target`: their descriptions are appended to the target's, their
+/// comments, attachments, mentions and watchers move over, each is marked a duplicate of
+/// the target and archived, and the merge is recorded on both sides. Returns the
+/// updated target and the archived tasks.
+pub async fn merge(
+    pool: &SqlitePool,
+    target: &Task,
+    merged: &[Task],
+) -> Result<(Task, Vec<Task>), sqlx::Error> {
+    let description = merged_description(target, merged);
+    let mut tx = pool.begin().await?;
+    let mut archived = Vec::new();
+    for task in merged {
+        sqlx::query!(
+            "UPDATE task_comments SET task_id = $1 WHERE task_id = $2",
+            target.id,
+            task.id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE task_attachments SET task_id = $1 WHERE task_id = $2",
+            target.id,
+            task.id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE OR IGNORE task_mentions SET task_id = $1 WHERE task_id = $2",
+            target.id,
+            task.id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"INSERT OR IGNORE INTO task_watchers (task_id, user_id, reason, created_at)
+               SELECT $1, user_id, reason, created_at FROM task_watchers WHERE task_id = $2"#,
+            target.id,
+            task.id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!("DELETE FROM task_watchers WHERE task_id = $1", task.id)
+            .execute(&mut *tx)
+            .await?;
+        let link_id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT OR IGNORE INTO task_links (id, source_task_id, target_task_id, kind)
+               SELECT $1, $2, $3, 'duplicates'
+               WHERE NOT EXISTS (
+                   SELECT 1 FROM task_links
+                   WHERE source_task_id = $3 AND target_task_id = $2 AND kind = 'duplicates'
+               )"#,
+            link_id,
+            task.id,
+            target.id
+        )
+        .execute(&mut *tx)
+        .await?;
+        TaskEvent::record_merge(&mut tx, target.id, task.id, TaskEventSource::User).await?;
+        archived.push(if task.archived_at.is_some() {
+            task.clone()
+        } else {
+            Task::archive(&mut *tx, task.id).await?
+        });
+    }
+    sqlx::query!(
+        r#"UPDATE tasks SET description = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1"#,
+        target.id,
+        description
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    let updated = Task::find_by_id(pool, target.id)
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+    TaskEvent::record_changes(pool, target, &updated, TaskEventSource::User, None).await?;
+    for (before, after) in merged.iter().zip(&archived) {
+        TaskEvent::record_changes(pool, before, after, TaskEventSource::User, None).await?;
+    }
+    Ok((updated, archived))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::models::task::{TaskPriority, TaskStatus};
+
+    fn task(title: &str, description: Option<&str>) -> Task {
+        let now = Utc::now();
+        Task {
+            id: Uuid::new_v4(),
+            project_id: Uuid::nil(),
+            title: title.to_string(),
+            description: description.map(str::to_string),
+            status: TaskStatus::Todo,
+            column_id: None,
+            parent_workspace_id: None,
+            shared_task_id: None,
+            due_at: None,
+            priority: TaskPriority::default(),
+            estimate: None,
+            assignee_id: None,
+            cover_color: None,
+            cover_attachment_id: None,
+            archived_at: None,
+            deleted_at: None,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn test_merged_description() {
+        let target = task("Login fails", Some("Steps to reproduce\n"));
+        let merged = [
+            task("Can't sign in", Some("Happens on Safari")),
+            task("Login broken", None),
+        ];
+        assert_eq!(
+            merged_description(&target, &merged).as_deref(),
+            Some(
+                "Steps to reproduce\n\n---\n\n_Merged from \"Can't sign in\":_\n\nHappens on Safari"
+            )
+        );
+        assert_eq!(merged_description(&task("Empty", None), &merged[1..]), None);
+    }
+}
@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{task::Task, task_event::TaskEventSource};
+
+/// A snapshot of a task's title and description as of one edit.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskRevision {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// Starts at 1 and goes up by one with each edit
+    pub revision: i64,
+    pub title: String,
+    pub description: Option<String>,
+    pub source: TaskEventSource,
+    pub integration_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskRevision {
+    /// Newest first.
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskRevision,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", revision, title, description, source as "source!: TaskEventSource", integration_id as "integration_id: Uuid", created_at as "created_at!: DateTime<Utc>"
+               FROM task_revisions
+               WHERE task_id = $1
+               ORDER BY revision DESC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_revision(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        revision: i64,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskRevision,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", revision, title, description, source as "source!: TaskEventSource", integration_id as "integration_id: Uuid", created_at as "created_at!: DateTime<Utc>"
+               FROM task_revisions
+               WHERE task_id = $1 AND revision = $2"#,
+            task_id,
+            revision
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Snapshot the task's current title and description as its next revision.
+    pub async fn create<'e, E>(
+        executor: E,
+        task: &Task,
+        source: TaskEventSource,
+        integration_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO task_revisions (id, task_id, revision, title, description, source, integration_id)
+               VALUES ($1, $2, (SELECT COALESCE(MAX(revision), 0) + 1 FROM task_revisions WHERE task_id = $2), $3, $4, $5, $6)"#,
+            id,
+            task.id,
+            task.title,
+            task.description,
+            source,
+            integration_id
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,280 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::task::{Task, TaskPriority, TaskStatus};
+
+/// A task matching a full-text search, best match first.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskSearchHit {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub task: Task,
+    /// Excerpt around the best matching terms, which are wrapped in `**`
+    pub snippet: String,
+    /// BM25 relevance; lower is more relevant
+    pub rank: f64,
+}
+
+/// Narrowing of a search beyond the text it matches.
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilter {
+    pub project_id: Option<Uuid>,
+    /// Only tasks of these projects, e.g. those the caller can see; any project when `None`
+    pub project_ids: Option<Vec<Uuid>>,
+    pub status: Option<TaskStatus>,
+    pub priority: Option<TaskPriority>,
+    pub assignee_id: Option<Uuid>,
+    pub label_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProjectFacet {
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct StatusFacet {
+    pub status: TaskStatus,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct LabelFacet {
+    pub label_id: Uuid,
+    pub name: String,
+    pub color: String,
+    pub count: i64,
+}
+
+/// How the tasks matching a search, not only the page of hits returned, break down. Each
+/// list is largest count first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, ToSchema)]
+pub struct SearchFacets {
+    /// Every matching task
+    pub total: i64,
+    pub projects: Vec<ProjectFacet>,
+    pub statuses: Vec<StatusFacet>,
+    /// A task with several labels counts toward each
+    pub labels: Vec<LabelFacet>,
+}
+
+/// Turn free text into an FTS5 query that matches tasks containing every word, treating
+/// the last word as a prefix so results show up while typing. Words are quoted so FTS5
+/// operators in the input are searched for literally. Returns `None` when there is
+/// nothing to search for.
+pub fn match_query(text: &str) -> Option<String> {
+    let words: Vec<&str> = text
+        .split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .collect();
+    let (last, rest) = words.split_last()?;
+    let quote = |word: &str| format!("\"{}\"", word.replace('"', "\"\""));
+
+    let mut terms: Vec<String> = rest.iter().map(|word| quote(word)).collect();
+    terms.push(format!("{}*", quote(last)));
+    Some(terms.join(" "))
+}
+
+impl TaskSearchHit {
+    /// Search task titles, descriptions and comments. Titles weigh most, comments least.
+    /// Trashed tasks are left out; archived ones are included.
+    pub async fn search(
+        pool: &SqlitePool,
+        match_query: &str,
+        filter: &SearchFilter,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let project_ids = filter
+            .project_ids
+            .as_ref()
+            .map(|ids| serde_json::to_string(ids).unwrap_or_default());
+        let records = sqlx::query!(
+            r#"SELECT
+  t.id                  AS "id!: Uuid",
+  t.project_id          AS "project_id!: Uuid",
+  t.title,
+  t.description,
+  t.status              AS "status!: TaskStatus",
+  t.column_id           AS "column_id: Uuid",
+  t.parent_workspace_id AS "parent_workspace_id: Uuid",
+  t.shared_task_id      AS "shared_task_id: Uuid",
+  t.due_at              AS "due_at: DateTime<Utc>",
+  t.priority            AS "priority!: TaskPriority",
+  t.estimate            AS "estimate: f64",
+  t.assignee_id         AS "assignee_id: Uuid",
+  t.cover_color,
+  t.cover_attachment_id AS "cover_attachment_id: Uuid",
+  t.archived_at         AS "archived_at: DateTime<Utc>",
+  t.deleted_at          AS "deleted_at: DateTime<Utc>",
+  t.created_at          AS "created_at!: DateTime<Utc>",
+  t.updated_at          AS "updated_at!: DateTime<Utc>",
+  t.version             AS "version!: i64",
+  snippet(task_search, -1, '**', '**', '…', 16) AS "snippet!: String",
+  bm25(task_search, 0.0, 10.0, 4.0, 1.0)        AS "rank!: f64"
+FROM task_search
+JOIN tasks t ON t.id = task_search.task_id
+WHERE task_search MATCH $1
+  AND t.deleted_at IS NULL
+  AND ($2 IS NULL OR t.project_id = $2)
+  AND ($3 IS NULL OR lower(hex(t.project_id)) IN (
+    SELECT lower(replace(value, '-', '')) FROM json_each($3)
+  ))
+  AND ($4 IS NULL OR t.status = $4)
+  AND ($5 IS NULL OR t.priority = $5)
+  AND ($6 IS NULL OR t.assignee_id = $6)
+  AND ($7 IS NULL OR EXISTS (
+    SELECT 1 FROM task_labels tl WHERE tl.task_id = t.id AND tl.label_id = $7
+  ))
+ORDER BY bm25(task_search, 0.0, 10.0, 4.0, 1.0)
+LIMIT $8"#,
+            match_query,
+            filter.project_id,
+            project_ids,
+            filter.status,
+            filter.priority,
+            filter.assignee_id,
+            filter.label_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|rec| TaskSearchHit {
+                task: Task {
+                    id: rec.id,
+                    project_id: rec.project_id,
+                    title: rec.title,
+                    description: rec.description,
+                    status: rec.status,
+                    column_id: rec.column_id,
+                    parent_workspace_id: rec.parent_workspace_id,
+                    shared_task_id: rec.shared_task_id,
+                    due_at: rec.due_at,
+                    priority: rec.priority,
+                    estimate: rec.estimate,
+                    assignee_id: rec.assignee_id,
+                    cover_color: rec.cover_color,
+                    cover_attachment_id: rec.cover_attachment_id,
+                    archived_at: rec.archived_at,
+                    deleted_at: rec.deleted_at,
+                    created_at: rec.created_at,
+                    updated_at: rec.updated_at,
+                    version: rec.version,
+                },
+                snippet: rec.snippet,
+                rank: rec.rank,
+            })
+            .collect())
+    }
+}
+
+impl SearchFacets {
+    /// Count the tasks [`TaskSearchHit::search`] would find with no limit, per project,
+    /// status and label.
+    pub async fn count(
+        pool: &SqlitePool,
+        match_query: &str,
+        filter: &SearchFilter,
+    ) -> Result<Self, sqlx::Error> {
+        let project_ids = filter
+            .project_ids
+            .as_ref()
+            .map(|ids| serde_json::to_string(ids).unwrap_or_default());
+        let records = sqlx::query!(
+            r#"WITH matched AS (
+  SELECT DISTINCT t.id, t.project_id, t.status
+  FROM task_search
+  JOIN tasks t ON t.id = task_search.task_id
+  WHERE task_search MATCH $1
+    AND t.deleted_at IS NULL
+    AND ($2 IS NULL OR t.project_id = $2)
+    AND ($3 IS NULL OR lower(hex(t.project_id)) IN (
+      SELECT lower(replace(value, '-', '')) FROM json_each($3)
+    ))
+    AND ($4 IS NULL OR t.status = $4)
+    AND ($5 IS NULL OR t.priority = $5)
+    AND ($6 IS NULL OR t.assignee_id = $6)
+    AND ($7 IS NULL OR EXISTS (
+      SELECT 1 FROM task_labels tl WHERE tl.task_id = t.id AND tl.label_id = $7
+    ))
+)
+SELECT 'project' AS "facet!: String", m.project_id AS "id: Uuid", NULL AS "status: TaskStatus",
+       p.name AS "name: String", NULL AS "color: String", COUNT(*) AS "count!: i64"
+  FROM matched m JOIN projects p ON p.id = m.project_id
+  GROUP BY m.project_id
+UNION ALL
+SELECT 'status', NULL, m.status, NULL, NULL, COUNT(*)
+  FROM matched m
+  GROUP BY m.status
+UNION ALL
+SELECT 'label', l.id, NULL, l.name, l.color, COUNT(*)
+  FROM matched m
+  JOIN task_labels tl ON tl.task_id = m.id
+  JOIN labels l ON l.id = tl.label_id
+  GROUP BY l.id
+ORDER BY 6 DESC, 4"#,
+            match_query,
+            filter.project_id,
+            project_ids,
+            filter.status,
+            filter.priority,
+            filter.assignee_id,
+            filter.label_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut facets = SearchFacets::default();
+        for rec in records {
+            match (rec.facet.as_str(), rec.id, rec.status) {
+                ("project", Some(project_id), _) => {
+                    facets.total += rec.count;
+                    facets.projects.push(ProjectFacet {
+                        project_id,
+                        project_name: rec.name.unwrap_or_default(),
+                        count: rec.count,
+                    });
+                }
+                ("status", _, Some(status)) => facets.statuses.push(StatusFacet {
+                    status,
+                    count: rec.count,
+                }),
+                ("label", Some(label_id), _) => facets.labels.push(LabelFacet {
+                    label_id,
+                    name: rec.name.unwrap_or_default(),
+                    color: rec.color.unwrap_or_default(),
+                    count: rec.count,
+                }),
+                _ => {}
+            }
+        }
+        Ok(facets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_query_quotes_words_and_prefixes_the_last() {
+        assert_eq!(
+            match_query("login  bug").as_deref(),
+            Some("\"login\" \"bug\"*")
+        );
+        // FTS5 syntax is searched for literally
+        assert_eq!(
+            match_query("title:\"oauth NOT").as_deref(),
+            Some("\"title:\"\"oauth\" \"NOT\"*")
+        );
+        assert_eq!(match_query("  -- * "), None);
+    }
+}
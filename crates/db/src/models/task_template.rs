@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    label::Label,
+    task::{CreateTask, Task},
+    task_checklist_item::{CreateTaskChecklistItem, TaskChecklistItem},
+};
+
+/// A blueprint for tasks that are created over and over, like release checklists.
+/// The title pattern and description may contain `{placeholders}`: `date`, `year`,
+/// `month` and `week` are filled in from the creation time, anything else must be
+/// supplied when creating a task.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskTemplate {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub title_pattern: String,
+    pub description: Option<String>,
+    /// Labels applied to created tasks; labels deleted since are skipped
+    #[ts(type = "Array<string>")]
+    #[schema(value_type = Vec<Uuid>)]
+    pub label_ids: Json<Vec<Uuid>>,
+    /// Checklist item titles added to created tasks, in order
+    #[ts(type = "Array<string>")]
+    #[schema(value_type = Vec<String>)]
+    pub checklist: Json<Vec<String>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateTaskTemplate {
+    pub name: String,
+    pub title_pattern: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub label_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub checklist: Vec<String>,
+}
+
+/// An empty description clears it.
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct UpdateTaskTemplate {
+    pub name: Option<String>,
+    pub title_pattern: Option<String>,
+    pub description: Option<String>,
+    pub label_ids: Option<Vec<Uuid>>,
+    pub checklist: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateTaskFromTemplate {
+    /// Values for the template's placeholders; these take precedence over the built-in ones
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+}
+
+/// Fill in the `{placeholders}` of a template string. Braces around anything other than
+/// a placeholder name are kept as they are. Returns the names of placeholders without
+/// a value.
+pub fn render_template(
+    pattern: &str,
+    variables: &HashMap<String, String>,
+    now: DateTime<Utc>,
+) -> Result<String, Vec<String>> {
+    let builtin = |name: &str| match name {
+        "date" => Some(now.format("%Y-%m-%d").to_string()),
+        "year" => Some(now.year().to_string()),
+        "month" => Some(format!("{:02}", now.month())),
+        "week" => Some(format!("{:02}", now.iso_week().week())),
+        _ => None,
+    };
+
+    let mut rendered = String::with_capacity(pattern.len());
+    let mut missing = Vec::new();
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let name = after.find('}').map(|end| &after[..end]).filter(|name| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        });
+        let Some(name) = name else {
+            rendered.push('{');
+            rest = after;
+            continue;
+        };
+        match variables.get(name).cloned().or_else(|| builtin(name)) {
+            Some(value) => rendered.push_str(&value),
+            None => {
+                if !missing.iter().any(|m| m == name) {
+                    missing.push(name.to_string());
+                }
+            }
+        }
+        rest = &after[name.len() + 1..];
+    }
+    rendered.push_str(rest);
+
+    if missing.is_empty() {
+        Ok(rendered)
+    } else {
+        Err(missing)
+    }
+}
+
+impl TaskTemplate {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, title_pattern, description, label_ids as "label_ids!: Json<Vec<Uuid>>", checklist as "checklist!: Json<Vec<String>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_templates
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, title_pattern, description, label_ids as "label_ids!: Json<Vec<Uuid>>", checklist as "checklist!: Json<Vec<String>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_templates
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_name(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, title_pattern, description, label_ids as "label_ids!: Json<Vec<Uuid>>", checklist as "checklist!: Json<Vec<String>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_templates
+               WHERE project_id = $1 AND name = $2"#,
+            project_id,
+            name
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateTaskTemplate,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let description = data.description.as_deref().filter(|d| !d.trim().is_empty());
+        let label_ids = Json(&data.label_ids);
+        let checklist = Json(&data.checklist);
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"INSERT INTO task_templates (id, project_id, name, title_pattern, description, label_ids, checklist)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, title_pattern, description, label_ids as "label_ids!: Json<Vec<Uuid>>", checklist as "checklist!: Json<Vec<String>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.name,
+            data.title_pattern,
+            description,
+            label_ids,
+            checklist
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateTaskTemplate,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let title_pattern = data
+            .title_pattern
+            .as_ref()
+            .unwrap_or(&existing.title_pattern);
+        let description = match &data.description {
+            Some(description) if description.trim().is_empty() => None,
+            Some(description) => Some(description.clone()),
+            None => existing.description,
+        };
+        let label_ids = Json(data.label_ids.as_ref().unwrap_or(&existing.label_ids.0));
+        let checklist = Json(data.checklist.as_ref().unwrap_or(&existing.checklist.0));
+
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"UPDATE task_templates
+               SET name = $2, title_pattern = $3, description = $4, label_ids = $5, checklist = $6, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, title_pattern, description, label_ids as "label_ids!: Json<Vec<Uuid>>", checklist as "checklist!: Json<Vec<String>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            title_pattern,
+            description,
+            label_ids,
+            checklist
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_templates WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Render the title and description for a new task. Returns the names of
+    /// placeholders without a value.
+    pub fn render(
+        &self,
+        variables: &HashMap<String, String>,
+        now: DateTime<Utc>,
+    ) -> Result<(String, Option<String>), Vec<String>> {
+        let title = render_template(&self.title_pattern, variables, now);
+        let description = self
+            .description
+            .as_deref()
+            .map(|d| render_template(d, variables, now))
+            .transpose();
+        match (title, description) {
+            (Ok(title), Ok(description)) => Ok((title, description)),
+            (title, description) => {
+                let mut missing = title.err().unwrap_or_default();
+                for name in description.err().unwrap_or_default() {
+                    if !missing.contains(&name) {
+                        missing.push(name);
+                    }
+                }
+                Err(missing)
+            }
+        }
+    }
+
+    /// Create a task from this template with an already rendered title and description,
+    /// applying the template's labels and checklist.
+    pub async fn instantiate(
+        &self,
+        pool: &SqlitePool,
+        title: String,
+        description: Option<String>,
+    ) -> Result<Task, sqlx::Error> {
+        let task = Task::create(
+            pool,
+            &CreateTask::from_title_description(self.project_id, title, description),
+            Uuid::new_v4(),
+        )
+        .await?;
+
+        let project_labels = Label::find_by_project_id(pool, self.project_id).await?;
+        let label_ids: Vec<Uuid> = self
+            .label_ids
+            .iter()
+            .copied()
+            .filter(|id| project_labels.iter().any(|label| label.id == *id))
+            .collect();
+        if !label_ids.is_empty() {
+            Label::set_for_task(pool, task.id, &label_ids).await?;
+        }
+
+        for title in self.checklist.iter() {
+            TaskChecklistItem::create(
+                pool,
+                task.id,
+                &CreateTaskChecklistItem {
+                    title: title.clone(),
+                },
+            )
+            .await?;
+        }
+
+        Ok(task)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_render_template_placeholders() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 8, 12, 0, 0).unwrap();
+        let variables = HashMap::from([("version".to_string(), "1.4.0".to_string())]);
+
+        assert_eq!(
+            render_template("Release {version} ({date}, week {week})", &variables, now).unwrap(),
+            "Release 1.4.0 (2026-01-08, week 02)"
+        );
+        // Braces that are not placeholders are left alone
+        assert_eq!(
+            render_template("Fix {} and { spaced } {", &variables, now).unwrap(),
+            "Fix {} and { spaced } {"
+        );
+        assert_eq!(
+            render_template("{owner} ships {version} on {target}", &variables, now),
+            Err(vec!["owner".to_string(), "target".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_render_template_variables_override_builtins() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 8, 12, 0, 0).unwrap();
+        let variables = HashMap::from([("date".to_string(), "tomorrow".to_string())]);
+        assert_eq!(
+            render_template("Standup {date}", &variables, now).unwrap(),
+            "Standup tomorrow"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// How a user came to watch a task.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "watch_reason", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum WatchReason {
+    Manual,
+    Assigned,
+    Commented,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskWatcher {
+    pub task_id: Uuid,
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub reason: WatchReason,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct WatchTask {
+    pub user_id: Uuid,
+}
+
+impl TaskWatcher {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskWatcher,
+            r#"SELECT w.task_id as "task_id!: Uuid", w.user_id as "user_id!: Uuid", u.name as "user_name!: String", w.reason as "reason!: WatchReason", w.created_at as "created_at!: DateTime<Utc>"
+               FROM task_watchers w
+               JOIN users u ON u.id = w.user_id
+               WHERE w.task_id = $1
+               ORDER BY u.name ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Start watching the task. A user already watching keeps their original reason.
+    pub async fn watch<'e, E>(
+        executor: E,
+        task_id: Uuid,
+        user_id: Uuid,
+        reason: WatchReason,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO task_watchers (task_id, user_id, reason)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(task_id, user_id) DO NOTHING"#,
+            task_id,
+            user_id,
+            reason
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn unwatch(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM task_watchers WHERE task_id = $1 AND user_id = $2",
+            task_id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
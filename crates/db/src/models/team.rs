@@ -0,0 +1,253 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// What a member may do in a team.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    TS,
+    EnumString,
+    Display,
+    ToSchema,
+)]
+#[sqlx(type_name = "team_role", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TeamRole {
+    /// Reach the team's projects, as their project roles allow
+    Member,
+    /// Also manage the team and its members, and administer all of its projects
+    Admin,
+}
+
+impl TeamRole {
+    /// Whether holding this role grants `required`.
+    pub fn grants(self, required: TeamRole) -> bool {
+        self >= required
+    }
+}
+
+/// A group of people sharing a set of projects. Only the team's members can reach them.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct Team {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateTeam {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct TeamMember {
+    pub team_id: Uuid,
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub role: TeamRole,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct SetTeamMember {
+    pub role: TeamRole,
+}
+
+impl Team {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Team,
+            r#"SELECT id as "id!: Uuid", name, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM teams
+               ORDER BY name ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Teams the user is a member of.
+    pub async fn find_by_user_id(
+        pool: &SqlitePool,
+        user_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Team,
+            r#"SELECT t.id as "id!: Uuid", t.name, t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM teams t
+               JOIN team_members m ON m.team_id = t.id
+               WHERE m.user_id = $1
+               ORDER BY t.name ASC"#,
+            user_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Team,
+            r#"SELECT id as "id!: Uuid", name, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM teams
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create<'e, E>(executor: E, data: &CreateTeam) -> Result<Self, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            Team,
+            r#"INSERT INTO teams (id, name)
+               VALUES ($1, $2)
+               RETURNING id as "id!: Uuid", name, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.name
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM teams WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn project_ids(pool: &SqlitePool, team_id: Uuid) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT id as "id!: Uuid" FROM projects WHERE team_id = $1"#,
+            team_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Move a project into the team, or out of any team with `None`.
+    pub async fn set_project_team(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        team_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE projects SET team_id = $2 WHERE id = $1",
+            project_id,
+            team_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
+
+impl TeamMember {
+    pub async fn find_by_team_id(
+        pool: &SqlitePool,
+        team_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TeamMember,
+            r#"SELECT m.team_id as "team_id!: Uuid", m.user_id as "user_id!: Uuid", u.name as "user_name!: String", m.role as "role!: TeamRole", m.created_at as "created_at!: DateTime<Utc>", m.updated_at as "updated_at!: DateTime<Utc>"
+               FROM team_members m
+               JOIN users u ON u.id = m.user_id
+               WHERE m.team_id = $1
+               ORDER BY u.name ASC"#,
+            team_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find(
+        pool: &SqlitePool,
+        team_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TeamMember,
+            r#"SELECT m.team_id as "team_id!: Uuid", m.user_id as "user_id!: Uuid", u.name as "user_name!: String", m.role as "role!: TeamRole", m.created_at as "created_at!: DateTime<Utc>", m.updated_at as "updated_at!: DateTime<Utc>"
+               FROM team_members m
+               JOIN users u ON u.id = m.user_id
+               WHERE m.team_id = $1 AND m.user_id = $2"#,
+            team_id,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn role(
+        pool: &SqlitePool,
+        team_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<TeamRole>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT role as "role!: TeamRole" FROM team_members WHERE team_id = $1 AND user_id = $2"#,
+            team_id,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Add the user to the team, or change their role if they are already a member.
+    pub async fn set<'e, E>(
+        executor: E,
+        team_id: Uuid,
+        user_id: Uuid,
+        role: TeamRole,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO team_members (team_id, user_id, role)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(team_id, user_id) DO UPDATE SET
+                   role = excluded.role,
+                   updated_at = datetime('now', 'subsec')"#,
+            team_id,
+            user_id,
+            role
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn remove(
+        pool: &SqlitePool,
+        team_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM team_members WHERE team_id = $1 AND user_id = $2",
+            team_id,
+            user_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Time a user spent on a task. Without `ended_at` it is a running timer.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct TimeEntry {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub user_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A time entry along with what it was spent on, for reports and export.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct TimeEntryDetail {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub entry: TimeEntry,
+    pub task_title: String,
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub user_name: String,
+    /// Key of the remote issue the task is linked to, for logging the time there
+    pub external_id: Option<String>,
+    /// Counted up to now for a running timer
+    pub seconds: i64,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TimeEntryFilter {
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub user_id: Option<Uuid>,
+    /// Entries started at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Entries started before this time
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, TS, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeGroupBy {
+    #[default]
+    Task,
+    Project,
+    User,
+}
+
+/// Time spent on one task, project or user.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS, ToSchema)]
+pub struct TimeTotal {
+    pub id: Uuid,
+    pub name: String,
+    pub seconds: i64,
+    pub entries: i64,
+}
+
+/// Add up entries per task, project or user, most time first.
+pub fn summarize(details: &[TimeEntryDetail], group_by: TimeGroupBy) -> Vec<TimeTotal> {
+    let mut totals: HashMap<Uuid, TimeTotal> = HashMap::new();
+    for detail in details {
+        let (id, name) = match group_by {
+            TimeGroupBy::Task => (detail.entry.task_id, &detail.task_title),
+            TimeGroupBy::Project => (detail.project_id, &detail.project_name),
+            TimeGroupBy::User => (detail.entry.user_id, &detail.user_name),
+        };
+        let total = totals.entry(id).or_insert_with(|| TimeTotal {
+            id,
+            name: name.clone(),
+            seconds: 0,
+            entries: 0,
+        });
+        total.seconds += detail.seconds;
+        total.entries += 1;
+    }
+
+    let mut totals: Vec<TimeTotal> = totals.into_values().collect();
+    totals.sort_by(|a, b| b.seconds.cmp(&a.seconds).then_with(|| a.name.cmp(&b.name)));
+    totals
+}
+
+impl TimeEntry {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TimeEntry,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", user_id as "user_id!: Uuid", started_at as "started_at!: DateTime<Utc>", ended_at as "ended_at: DateTime<Utc>", note, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM time_entries
+               WHERE task_id = $1
+               ORDER BY started_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_running(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TimeEntry,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", user_id as "user_id!: Uuid", started_at as "started_at!: DateTime<Utc>", ended_at as "ended_at: DateTime<Utc>", note, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM time_entries
+               WHERE task_id = $1 AND user_id = $2 AND ended_at IS NULL"#,
+            task_id,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Entries matching the filter with their task, project and user, oldest first.
+    /// Entries on trashed tasks are left out.
+    pub async fn find_details(
+        pool: &SqlitePool,
+        filter: &TimeEntryFilter,
+    ) -> Result<Vec<TimeEntryDetail>, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"SELECT
+  te.id          AS "id!: Uuid",
+  te.task_id     AS "task_id!: Uuid",
+  te.user_id     AS "user_id!: Uuid",
+  te.started_at  AS "started_at!: DateTime<Utc>",
+  te.ended_at    AS "ended_at: DateTime<Utc>",
+  te.note,
+  te.created_at  AS "created_at!: DateTime<Utc>",
+  te.updated_at  AS "updated_at!: DateTime<Utc>",
+  t.title        AS "task_title!: String",
+  p.id           AS "project_id!: Uuid",
+  p.name         AS "project_name!: String",
+  u.name         AS "user_name!: String",
+  (SELECT il.external_id FROM integration_links il
+     WHERE il.task_id = t.id
+     ORDER BY il.created_at ASC
+     LIMIT 1)    AS "external_id: String",
+  CAST(ROUND((julianday(COALESCE(te.ended_at, 'now')) - julianday(te.started_at)) * 86400) AS INTEGER)
+                 AS "seconds!: i64"
+FROM time_entries te
+JOIN tasks t    ON t.id = te.task_id
+JOIN projects p ON p.id = t.project_id
+JOIN users u    ON u.id = te.user_id
+WHERE t.deleted_at IS NULL
+  AND ($1 IS NULL OR t.project_id = $1)
+  AND ($2 IS NULL OR te.task_id = $2)
+  AND ($3 IS NULL OR te.user_id = $3)
+  AND ($4 IS NULL OR datetime(te.started_at) >= datetime($4))
+  AND ($5 IS NULL OR datetime(te.started_at) < datetime($5))
+ORDER BY te.started_at ASC"#,
+            filter.project_id,
+            filter.task_id,
+            filter.user_id,
+            filter.from,
+            filter.to
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|rec| TimeEntryDetail {
+                entry: TimeEntry {
+                    id: rec.id,
+                    task_id: rec.task_id,
+                    user_id: rec.user_id,
+                    started_at: rec.started_at,
+                    ended_at: rec.ended_at,
+                    note: rec.note,
+                    created_at: rec.created_at,
+                    updated_at: rec.updated_at,
+                },
+                task_title: rec.task_title,
+                project_id: rec.project_id,
+                project_name: rec.project_name,
+                user_name: rec.user_name,
+                external_id: rec.external_id,
+                seconds: rec.seconds,
+            })
+            .collect())
+    }
+
+    /// Start a timer. Fails on the unique index if the user already has one running on
+    /// the task.
+    pub async fn start(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        user_id: Uuid,
+        note: Option<&str>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TimeEntry,
+            r#"INSERT INTO time_entries (id, task_id, user_id, note)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", user_id as "user_id!: Uuid", started_at as "started_at!: DateTime<Utc>", ended_at as "ended_at: DateTime<Utc>", note, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            user_id,
+            note
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Stop a running timer. A note replaces the one given at start.
+    pub async fn stop(
+        pool: &SqlitePool,
+        id: Uuid,
+        note: Option<&str>,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            TimeEntry,
+            r#"UPDATE time_entries
+               SET ended_at = datetime('now', 'subsec'),
+                   note = COALESCE($2, note),
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", user_id as "user_id!: Uuid", started_at as "started_at!: DateTime<Utc>", ended_at as "ended_at: DateTime<Utc>", note, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            note
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detail(task_id: Uuid, user_id: Uuid, user_name: &str, seconds: i64) -> TimeEntryDetail {
+        let now = Utc::now();
+        TimeEntryDetail {
+            entry: TimeEntry {
+                id: Uuid::new_v4(),
+                task_id,
+                user_id,
+                started_at: now,
+                ended_at: Some(now),
+                note: None,
+                created_at: now,
+                updated_at: now,
+            },
+            task_title: format!("Task {task_id}"),
+            project_id: Uuid::nil(),
+            project_name: "Website".to_string(),
+            user_name: user_name.to_string(),
+            external_id: None,
+            seconds,
+        }
+    }
+
+    #[test]
+    fn test_summarize_groups_and_sorts_by_time() {
+        let (task_a, task_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let (ada, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let details = vec![
+            detail(task_a, ada, "Ada", 600),
+            detail(task_b, bob, "Bob", 1800),
+            detail(task_a, bob, "Bob", 300),
+        ];
+
+        let by_user = summarize(&details, TimeGroupBy::User);
+        assert_eq!(
+            by_user
+                .iter()
+                .map(|t| (t.name.as_str(), t.seconds, t.entries))
+                .collect::<Vec<_>>(),
+            vec![("Bob", 2100, 2), ("Ada", 600, 1)]
+        );
+
+        let by_task = summarize(&details, TimeGroupBy::Task);
+        assert_eq!(by_task[0].id, task_b);
+        assert_eq!(by_task[1].seconds, 900);
+
+        let by_project = summarize(&details, TimeGroupBy::Project);
+        assert_eq!(by_project.len(), 1);
+        assert_eq!(by_project[0].entries, 3);
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A person tasks can be assigned to.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct User {
+    pub id: Uuid,
+    pub name: String,
+    /// Used to match assignees imported from external trackers
+    pub email: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateUser {
+    pub name: String,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct UpdateUser {
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+impl User {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid", name, email, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM users
+               ORDER BY name ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid", name, email, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM users
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_email(
+        pool: &SqlitePool,
+        email: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid", name, email, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM users
+               WHERE email = $1 COLLATE NOCASE"#,
+            email
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Find a user by display name. Returns `None` when the name is ambiguous.
+    pub async fn find_by_unique_name(
+        pool: &SqlitePool,
+        name: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let mut users = sqlx::query_as!(
+            User,
+            r#"SELECT id as "id!: Uuid", name, email, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM users
+               WHERE name = $1 COLLATE NOCASE
+               LIMIT 2"#,
+            name
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(if users.len() == 1 { users.pop() } else { None })
+    }
+
+    pub async fn create(pool: &SqlitePool, data: &CreateUser) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            User,
+            r#"INSERT INTO users (id, name, email)
+               VALUES ($1, $2, $3)
+               RETURNING id as "id!: Uuid", name, email, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.name,
+            data.email
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Update a user. An empty `email` clears it.
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateUser,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let email = match &data.email {
+            Some(email) if email.trim().is_empty() => None,
+            Some(email) => Some(email.clone()),
+            None => existing.email,
+        };
+
+        sqlx::query_as!(
+            User,
+            r#"UPDATE users
+               SET name = $2, email = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", name, email, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            email
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM users WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
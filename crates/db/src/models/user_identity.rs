@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+use super::user::User;
+
+/// An account at an OpenID Connect provider, identified by the provider's issuer URL and
+/// the account's `sub` claim.
+#[derive(Debug, Clone, FromRow)]
+pub struct UserIdentity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub issuer: String,
+    pub subject: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserIdentity {
+    /// The user who signs in with this account, if it has been used before.
+    pub async fn find_user(
+        pool: &SqlitePool,
+        issuer: &str,
+        subject: &str,
+    ) -> Result<Option<User>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT u.id as "id!: Uuid", u.name, u.email, u.created_at as "created_at!: DateTime<Utc>", u.updated_at as "updated_at!: DateTime<Utc>"
+               FROM user_identities i
+               JOIN users u ON u.id = i.user_id
+               WHERE i.issuer = $1 AND i.subject = $2"#,
+            issuer,
+            subject
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn link(
+        pool: &SqlitePool,
+        user_id: Uuid,
+        issuer: &str,
+        subject: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            UserIdentity,
+            r#"INSERT INTO user_identities (id, user_id, issuer, subject)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid", user_id as "user_id!: Uuid", issuer, subject, created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            user_id,
+            issuer,
+            subject
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
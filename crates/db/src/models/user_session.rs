@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+use super::user::User;
+
+/// A signed-in browser. The session cookie itself is never stored, only its hash.
+#[derive(Debug, Clone, FromRow)]
+pub struct UserSession {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserSession {
+    pub async fn create(
+        pool: &SqlitePool,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            UserSession,
+            r#"INSERT INTO user_sessions (id, user_id, token_hash, expires_at)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid", user_id as "user_id!: Uuid", expires_at as "expires_at!: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            user_id,
+            token_hash,
+            expires_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// The user signed in with this session, unless it has expired.
+    pub async fn find_user(
+        pool: &SqlitePool,
+        token_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<User>, sqlx::Error> {
+        sqlx::query_as!(
+            User,
+            r#"SELECT u.id as "id!: Uuid", u.name, u.email, u.created_at as "created_at!: DateTime<Utc>", u.updated_at as "updated_at!: DateTime<Utc>"
+               FROM user_sessions s
+               JOIN users u ON u.id = s.user_id
+               WHERE s.token_hash = $1 AND s.expires_at > $2"#,
+            token_hash,
+            now
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn delete_by_hash(pool: &SqlitePool, token_hash: &str) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM user_sessions WHERE token_hash = $1",
+            token_hash
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Drop expired sessions so the table doesn't grow without bound.
+    pub async fn delete_expired(pool: &SqlitePool, now: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM user_sessions WHERE expires_at <= $1", now)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
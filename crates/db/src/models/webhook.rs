@@ -0,0 +1,260 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::cursor::{self, CreatedKey, Page};
+
+/// Deliveries kept per webhook; older ones are dropped as new ones are logged.
+const DELIVERIES_KEPT: i64 = 500;
+
+/// A URL a project's activity is POSTed to. The signing secret is only shown when the
+/// webhook is created.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub url: String,
+    /// Event names to send, e.g. `task.created`; every event when empty
+    #[ts(type = "Array<string>")]
+    #[schema(value_type = Vec<String>)]
+    pub events: Json<Vec<String>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct CreateWebhook {
+    pub project_id: Uuid,
+    pub url: String,
+    #[serde(default)]
+    #[ts(optional)]
+    pub events: Option<Vec<String>>,
+}
+
+/// One attempt at sending an event to a webhook, and the reply it got.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    pub event: String,
+    /// The JSON body that was sent
+    pub payload: String,
+    /// `None` when no reply came back; see `error`
+    pub status_code: Option<i64>,
+    /// The start of the reply body
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What to log about an attempt.
+#[derive(Debug, Clone)]
+pub struct CreateWebhookDelivery {
+    pub event: String,
+    pub payload: String,
+    pub status_code: Option<i64>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+}
+
+impl Webhook {
+    /// Whether the webhook wants events named `event`.
+    pub fn wants(&self, event: &str) -> bool {
+        self.events.is_empty() || self.events.iter().any(|wanted| wanted == event)
+    }
+
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Webhook,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", url, events as "events!: Json<Vec<String>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM webhooks
+               ORDER BY created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Webhook,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", url, events as "events!: Json<Vec<String>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM webhooks
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Webhook,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", url, events as "events!: Json<Vec<String>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM webhooks
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_secret(pool: &SqlitePool, id: Uuid) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar!("SELECT secret FROM webhooks WHERE id = $1", id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateWebhook,
+        secret: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let events = Json(data.events.clone().unwrap_or_default());
+        sqlx::query_as!(
+            Webhook,
+            r#"INSERT INTO webhooks (id, project_id, url, secret, events)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", url, events as "events!: Json<Vec<String>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.url,
+            secret,
+            events
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM webhooks WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl WebhookDelivery {
+    /// Log an attempt, dropping the webhook's oldest deliveries beyond those kept.
+    pub async fn create(
+        pool: &SqlitePool,
+        webhook_id: Uuid,
+        data: &CreateWebhookDelivery,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let mut tx = pool.begin().await?;
+        let delivery = sqlx::query_as!(
+            WebhookDelivery,
+            r#"INSERT INTO webhook_deliveries (id, webhook_id, event, payload, status_code, response_body, error, duration_ms)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+               RETURNING id as "id!: Uuid", webhook_id as "webhook_id!: Uuid", event, payload, status_code, response_body, error, duration_ms as "duration_ms!: i64", created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            webhook_id,
+            data.event,
+            data.payload,
+            data.status_code,
+            data.response_body,
+            data.error,
+            data.duration_ms
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"DELETE FROM webhook_deliveries
+               WHERE webhook_id = $1
+                 AND id NOT IN (
+                     SELECT id FROM webhook_deliveries
+                     WHERE webhook_id = $1
+                     ORDER BY created_at DESC, rowid DESC
+                     LIMIT $2
+                 )"#,
+            webhook_id,
+            DELIVERIES_KEPT
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(delivery)
+    }
+
+    /// The webhook's deliveries, newest first, a page at a time starting right after
+    /// `after`.
+    pub async fn find_by_webhook_id(
+        pool: &SqlitePool,
+        webhook_id: Uuid,
+        after: Option<&CreatedKey>,
+        limit: i64,
+    ) -> Result<Page<Self>, sqlx::Error> {
+        let fetch_limit = cursor::fetch_limit(Some(limit));
+        let after_created_at = after.map(|key| key.created_at.as_str());
+        let after_rowid = after.map(|key| key.rowid);
+        let records = sqlx::query!(
+            r#"SELECT id as "id!: Uuid", webhook_id as "webhook_id!: Uuid", event, payload, status_code, response_body, error, duration_ms as "duration_ms!: i64", created_at as "created_at!: DateTime<Utc>", created_at as "created_at_key!: String", rowid as "rowid!: i64"
+               FROM webhook_deliveries
+               WHERE webhook_id = $1
+                 AND ($2 IS NULL OR (created_at, rowid) < ($2, $3))
+               ORDER BY created_at DESC, rowid DESC
+               LIMIT $4"#,
+            webhook_id,
+            after_created_at,
+            after_rowid,
+            fetch_limit
+        )
+        .fetch_all(pool)
+        .await?;
+        let rows = records
+            .into_iter()
+            .map(|rec| {
+                let key = CreatedKey {
+                    created_at: rec.created_at_key,
+                    rowid: rec.rowid,
+                };
+                let delivery = WebhookDelivery {
+                    id: rec.id,
+                    webhook_id: rec.webhook_id,
+                    event: rec.event,
+                    payload: rec.payload,
+                    status_code: rec.status_code,
+                    response_body: rec.response_body,
+                    error: rec.error,
+                    duration_ms: rec.duration_ms,
+                    created_at: rec.created_at,
+                };
+                (delivery, key)
+            })
+            .collect();
+        Ok(Page::from_rows(rows, Some(limit)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wants_every_event_when_none_listed() {
+        let now = Utc::now();
+        let mut webhook = Webhook {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            url: "https://example.com/hook".to_string(),
+            events: Json(Vec::new()),
+            created_at: now,
+            updated_at: now,
+        };
+        assert!(webhook.wants("task.created"));
+
+        webhook.events = Json(vec!["task.created".to_string()]);
+        assert!(webhook.wants("task.created"));
+        assert!(!webhook.wants("comment.created"));
+    }
+}
@@ -0,0 +1,185 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use strum::IntoEnumIterator;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+/// Most tasks a project's board column should hold at once.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WipLimit {
+    pub project_id: Uuid,
+    pub status: TaskStatus,
+    pub max_tasks: i64,
+    /// Reject moves into a full column instead of only warning about them
+    pub enforced: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+pub struct SetWipLimit {
+    pub status: TaskStatus,
+    pub max_tasks: i64,
+    /// Falls back to the project's `enforce_wip_limits` setting
+    #[serde(default)]
+    #[ts(optional)]
+    pub enforced: Option<bool>,
+}
+
+/// A board column's current task count against its limit.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, ToSchema)]
+pub struct WipColumn {
+    pub status: TaskStatus,
+    /// Tasks on the board in this column; archived and trashed tasks don't count
+    pub count: i64,
+    pub limit: Option<i64>,
+    pub enforced: bool,
+    pub exceeded: bool,
+}
+
+impl WipColumn {
+    fn new(status: TaskStatus, count: i64, limit: Option<&WipLimit>) -> Self {
+        let max_tasks = limit.map(|limit| limit.max_tasks);
+        Self {
+            status,
+            count,
+            limit: max_tasks,
+            enforced: limit.is_some_and(|limit| limit.enforced),
+            exceeded: max_tasks.is_some_and(|max| count > max),
+        }
+    }
+
+    /// Whether moving `entering` more tasks in would take the column over its limit.
+    pub fn would_exceed(&self, entering: i64) -> bool {
+        self.limit.is_some_and(|max| self.count + entering > max)
+    }
+
+    /// One column per status, in board order.
+    pub fn build(counts: &[(TaskStatus, i64)], limits: &[WipLimit]) -> Vec<Self> {
+        TaskStatus::iter()
+            .map(|status| {
+                let count = counts
+                    .iter()
+                    .find(|(s, _)| *s == status)
+                    .map_or(0, |(_, count)| *count);
+                let limit = limits.iter().find(|limit| limit.status == status);
+                Self::new(status, count, limit)
+            })
+            .collect()
+    }
+
+    pub async fn for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let counts = sqlx::query!(
+            r#"SELECT status as "status!: TaskStatus", COUNT(*) as "count!: i64"
+               FROM tasks
+               WHERE project_id = $1 AND archived_at IS NULL AND deleted_at IS NULL
+               GROUP BY status"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|rec| (rec.status, rec.count))
+        .collect::<Vec<_>>();
+        let limits = WipLimit::find_by_project_id(pool, project_id).await?;
+        Ok(Self::build(&counts, &limits))
+    }
+
+    pub async fn find(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        status: &TaskStatus,
+    ) -> Result<Self, sqlx::Error> {
+        Ok(Self::for_project(pool, project_id)
+            .await?
+            .into_iter()
+            .find(|column| column.status == *status)
+            .unwrap_or_else(|| Self::new(status.clone(), 0, None)))
+    }
+}
+
+impl WipLimit {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WipLimit,
+            r#"SELECT project_id as "project_id!: Uuid", status as "status!: TaskStatus", max_tasks, enforced as "enforced!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_wip_limits
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replace the project's limits; statuses left out have none.
+    pub async fn set_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        limits: &[SetWipLimit],
+        enforced_by_default: bool,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            "DELETE FROM project_wip_limits WHERE project_id = $1",
+            project_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        for limit in limits {
+            let enforced = limit.enforced.unwrap_or(enforced_by_default);
+            sqlx::query!(
+                r#"INSERT INTO project_wip_limits (project_id, status, max_tasks, enforced)
+                   VALUES ($1, $2, $3, $4)"#,
+                project_id,
+                limit.status,
+                limit.max_tasks,
+                enforced
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Self::find_by_project_id(pool, project_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_columns() {
+        let now = Utc::now();
+        let limits = vec![WipLimit {
+            project_id: Uuid::nil(),
+            status: TaskStatus::InProgress,
+            max_tasks: 2,
+            enforced: true,
+            created_at: now,
+            updated_at: now,
+        }];
+        let columns = WipColumn::build(
+            &[(TaskStatus::Todo, 7), (TaskStatus::InProgress, 2)],
+            &limits,
+        );
+
+        assert_eq!(columns.len(), 5);
+        assert_eq!(columns[0].count, 7);
+        assert!(!columns[0].would_exceed(100));
+        let in_progress = &columns[1];
+        assert_eq!(in_progress.status, TaskStatus::InProgress);
+        assert!(in_progress.enforced && !in_progress.exceeded);
+        assert!(in_progress.would_exceed(1));
+        assert_eq!(columns[2].count, 0);
+    }
+}
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool, Type};
 use thiserror::Error;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::{
@@ -44,7 +45,7 @@ pub enum WorkspaceStatus {
     ExecutorFailed,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS, ToSchema)]
 pub struct Workspace {
     pub id: Uuid,
     pub task_id: Uuid,
@@ -67,7 +68,7 @@ pub struct CreatePrParams<'a> {
     pub base_branch: Option<&'a str>,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct CreateFollowUpAttempt {
     pub prompt: String,
 }
@@ -79,7 +80,7 @@ pub struct AttemptResumeContext {
     pub cumulative_diffs: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct WorkspaceContext {
     pub workspace: Workspace,
     pub task: Task,
@@ -0,0 +1,75 @@
+use std::{str::FromStr, time::Duration};
+
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
+
+/// How the SQLite pool is sized and its connections set up, from `VK_DB_POOL_SIZE`,
+/// `VK_DB_BUSY_TIMEOUT_MS`, `VK_DB_WAL` and `VK_DB_SYNCHRONOUS`.
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    pub max_connections: u32,
+    /// How long a connection waits for another's write lock before `database is locked`
+    pub busy_timeout: Duration,
+    /// Write-ahead logging, so reads carry on while a sync is writing
+    pub wal: bool,
+    pub synchronous: SqliteSynchronous,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            busy_timeout: Duration::from_secs(5),
+            wal: true,
+            // Durable across crashes of the process with WAL; only a power loss can undo
+            // the last commits
+            synchronous: SqliteSynchronous::Normal,
+        }
+    }
+}
+
+impl PoolConfig {
+    /// The defaults with any `VK_DB_*` variables applied. Values that don't parse are
+    /// reported and the default kept.
+    pub fn from_env() -> Self {
+        fn parsed<T: FromStr>(name: &str) -> Option<T> {
+            let value = std::env::var(name).ok().filter(|value| !value.is_empty())?;
+            let parsed = value.parse().ok();
+            if parsed.is_none() {
+                tracing::warn!("Ignoring {name}={value}: not a valid value");
+            }
+            parsed
+        }
+
+        let defaults = Self::default();
+        let wal = utils::env_flag_or("VK_DB_WAL", defaults.wal);
+        Self {
+            max_connections: parsed::<u32>("VK_DB_POOL_SIZE")
+                .filter(|size| *size > 0)
+                .unwrap_or(defaults.max_connections),
+            busy_timeout: parsed("VK_DB_BUSY_TIMEOUT_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.busy_timeout),
+            wal,
+            synchronous: parsed("VK_DB_SYNCHRONOUS").unwrap_or(if wal {
+                defaults.synchronous
+            } else {
+                SqliteSynchronous::Full
+            }),
+        }
+    }
+
+    pub(crate) fn apply(&self, options: SqliteConnectOptions) -> SqliteConnectOptions {
+        options
+            .busy_timeout(self.busy_timeout)
+            .journal_mode(if self.wal {
+                SqliteJournalMode::Wal
+            } else {
+                SqliteJournalMode::Delete
+            })
+            .synchronous(self.synchronous)
+    }
+
+    pub(crate) fn pool_options(&self) -> SqlitePoolOptions {
+        SqlitePoolOptions::new().max_connections(self.max_connections)
+    }
+}
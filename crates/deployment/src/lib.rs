@@ -18,7 +18,10 @@ use serde_json::Value;
 use services::services::{
     analytics::{AnalyticsContext, AnalyticsService},
     approvals::Approvals,
+    attachment::{AttachmentError, AttachmentService},
     auth::AuthContext,
+    auto_archive::AutoArchiveJob,
+    backup::{BackupConfig, BackupJob},
     config::{Config, ConfigError},
     container::{ContainerError, ContainerService},
     events::{EventError, EventService},
@@ -27,11 +30,17 @@ use services::services::{
     filesystem_watcher::FilesystemWatcherError,
     git::{GitService, GitServiceError},
     image::{ImageError, ImageService},
+    integrations::IntegrationService,
+    job_runner::{JobQueue, JobRunner},
     pr_monitor::PrMonitorService,
     project::ProjectService,
     queued_message::QueuedMessageService,
+    recurrence::RecurrenceJob,
     repo::RepoService,
     share::SharePublisher,
+    sync_worker::SyncJobHandler,
+    trash::TrashPurgeJob,
+    webhook_dispatcher::{WebhookDeliveryJob, WebhookDispatcher},
     worktree_manager::WorktreeError,
 };
 use sqlx::Error as SqlxError;
@@ -64,6 +73,8 @@ pub enum DeploymentError {
     #[error(transparent)]
     Image(#[from] ImageError),
     #[error(transparent)]
+    Attachment(#[from] AttachmentError),
+    #[error(transparent)]
     Filesystem(#[from] FilesystemError),
     #[error(transparent)]
     Worktree(#[from] WorktreeError),
@@ -99,10 +110,16 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn image(&self) -> &ImageService;
 
+    fn attachment(&self) -> &AttachmentService;
+
+    fn integrations(&self) -> &IntegrationService;
+
     fn filesystem(&self) -> &FilesystemService;
 
     fn events(&self) -> &EventService;
 
+    fn jobs(&self) -> &JobQueue;
+
     fn file_search_cache(&self) -> &Arc<FileSearchCache>;
 
     fn approvals(&self) -> &Approvals;
@@ -136,6 +153,36 @@ pub trait Deployment: Clone + Send + Sync + 'static {
         PrMonitorService::spawn(db, analytics, publisher).await
     }
 
+    /// Workers for the durable job queue: sync runs, webhook deliveries, the hourly trash
+    /// purge and auto-archive, recurring tasks, and backups when `VK_BACKUP_DIR` asks for
+    /// them.
+    async fn spawn_job_runner(&self) -> tokio::task::JoinHandle<()> {
+        let runner = JobRunner::new(self.jobs().clone())
+            .handler(SyncJobHandler::new(
+                self.db().pool.clone(),
+                self.integrations().clone(),
+                self.events().activity().clone(),
+            ))
+            .handler(WebhookDeliveryJob::new(self.db().clone()))
+            .handler(TrashPurgeJob::new(self.db().clone(), self.config().clone()))
+            .handler(AutoArchiveJob::new(self.db().clone()))
+            .handler(RecurrenceJob::new(self.db().clone()));
+        let runner = match BackupConfig::from_env() {
+            Some(config) => runner.handler(BackupJob::new(self.db().clone(), config)),
+            None => runner,
+        };
+        runner.spawn().await
+    }
+
+    async fn spawn_webhook_dispatcher(&self) -> tokio::task::JoinHandle<()> {
+        WebhookDispatcher::spawn(
+            self.db().clone(),
+            self.events().activity().clone(),
+            self.jobs().clone(),
+        )
+        .await
+    }
+
     async fn track_if_analytics_allowed(&self, event_name: &str, properties: Value) {
         let analytics_enabled = self.config().read().await.analytics_enabled;
         // Track events unless user has explicitly opted out
@@ -172,6 +219,7 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                             display_name: repo.name,
                             git_repo_path: repo_path.clone(),
                         }],
+                        team_id: None,
                     };
 
                     match self
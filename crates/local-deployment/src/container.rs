@@ -21,6 +21,7 @@ use db::{
         repo::Repo,
         scratch::{DraftFollowUpData, Scratch, ScratchType},
         task::{Task, TaskStatus},
+        task_event::{TaskEvent, TaskEventSource},
         workspace::Workspace,
         workspace_repo::WorkspaceRepo,
     },
@@ -1280,6 +1281,17 @@ impl ContainerService for LocalContainerService {
         {
             match Task::update_status(&self.db.pool, ctx.task.id, TaskStatus::InReview).await {
                 Ok(_) => {
+                    if let Err(e) = TaskEvent::record_status_change(
+                        &self.db.pool,
+                        ctx.task.id,
+                        &ctx.task.status,
+                        &TaskStatus::InReview,
+                        TaskEventSource::Agent,
+                    )
+                    .await
+                    {
+                        tracing::warn!("Failed to record task status change: {e}");
+                    }
                     if let Some(publisher) = self.share_publisher()
                         && let Err(err) = publisher.update_shared_task_by_id(ctx.task.id).await
                     {
@@ -7,14 +7,17 @@ use executors::profile::ExecutorConfigs;
 use services::services::{
     analytics::{AnalyticsConfig, AnalyticsContext, AnalyticsService, generate_user_id},
     approvals::Approvals,
+    attachment::AttachmentService,
     auth::AuthContext,
     config::{Config, load_config_from_file, save_config_to_file},
     container::ContainerService,
-    events::EventService,
+    events::{BoardEventBus, EventService},
     file_search_cache::FileSearchCache,
     filesystem::FilesystemService,
     git::GitService,
     image::ImageService,
+    integrations::IntegrationService,
+    job_runner::JobQueue,
     oauth_credentials::OAuthCredentials,
     project::ProjectService,
     queued_message::QueuedMessageService,
@@ -46,8 +49,11 @@ pub struct LocalDeployment {
     project: ProjectService,
     repo: RepoService,
     image: ImageService,
+    attachment: AttachmentService,
+    integrations: IntegrationService,
     filesystem: FilesystemService,
     events: EventService,
+    jobs: JobQueue,
     file_search_cache: Arc<FileSearchCache>,
     approvals: Approvals,
     queued_message_service: QueuedMessageService,
@@ -103,11 +109,13 @@ impl Deployment for LocalDeployment {
         // Create shared components for EventService
         let events_msg_store = Arc::new(MsgStore::new());
         let events_entry_count = Arc::new(RwLock::new(0));
+        let board_events = BoardEventBus::new();
 
         // Create DB with event hooks
         let db = {
             let hook = EventService::create_hook(
                 events_msg_store.clone(),
+                board_events.clone(),
                 events_entry_count.clone(),
                 DBService::new().await?, // Temporary DB service for the hook
             );
@@ -125,6 +133,18 @@ impl Deployment for LocalDeployment {
             });
         }
 
+        let attachment = AttachmentService::new(db.clone().pool)?;
+        {
+            let attachment_service = attachment.clone();
+            tokio::spawn(async move {
+                if let Err(e) = attachment_service.delete_orphaned_files().await {
+                    tracing::error!("Failed to clean up orphaned attachments: {}", e);
+                }
+            });
+        }
+
+        let integrations = IntegrationService::new(attachment.clone());
+        let jobs = JobQueue::new(db.pool.clone());
         let approvals = Approvals::new(msg_stores.clone());
         let queued_message_service = QueuedMessageService::new();
 
@@ -185,7 +205,12 @@ impl Deployment for LocalDeployment {
         )
         .await;
 
-        let events = EventService::new(db.clone(), events_msg_store, events_entry_count);
+        let events = EventService::new(
+            db.clone(),
+            events_msg_store,
+            board_events,
+            events_entry_count,
+        );
 
         let file_search_cache = Arc::new(FileSearchCache::new());
 
@@ -199,8 +224,11 @@ impl Deployment for LocalDeployment {
             project,
             repo,
             image,
+            attachment,
+            integrations,
             filesystem,
             events,
+            jobs,
             file_search_cache,
             approvals,
             queued_message_service,
@@ -250,6 +278,14 @@ impl Deployment for LocalDeployment {
         &self.image
     }
 
+    fn attachment(&self) -> &AttachmentService {
+        &self.attachment
+    }
+
+    fn integrations(&self) -> &IntegrationService {
+        &self.integrations
+    }
+
     fn filesystem(&self) -> &FilesystemService {
         &self.filesystem
     }
@@ -258,6 +294,10 @@ impl Deployment for LocalDeployment {
         &self.events
     }
 
+    fn jobs(&self) -> &JobQueue {
+        &self.jobs
+    }
+
     fn file_search_cache(&self) -> &Arc<FileSearchCache> {
         &self.file_search_cache
     }
@@ -6,11 +6,12 @@ mod error;
 mod github;
 mod session_selector;
 
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 use anyhow::Result;
 use api::{ReviewApiClient, ReviewStatus, StartRequest};
-use clap::Parser;
+use clap::{CommandFactory, Parser};
+use clap_complete::Shell;
 use error::ReviewError;
 use github::{checkout_commit, clone_repo, get_pr_info, parse_pr_url};
 use indicatif::{ProgressBar, ProgressStyle};
@@ -40,7 +41,8 @@ const BANNER: &str = r#"
 #[command(version)]
 struct Args {
     /// GitHub PR URL (e.g., https://github.com/owner/repo/pull/123)
-    pr_url: String,
+    #[arg(required_unless_present_any = ["completions", "man"])]
+    pr_url: Option<String>,
 
     /// Enable verbose output
     #[arg(short, long, default_value_t = false)]
@@ -49,6 +51,14 @@ struct Args {
     /// API base URL
     #[arg(long, env = "REVIEW_API_URL", default_value = DEFAULT_API_URL)]
     api_url: String,
+
+    /// Print the completion script for a shell and exit
+    #[arg(long, value_name = "SHELL", hide = true)]
+    completions: Option<Shell>,
+
+    /// Write man pages into a directory and exit
+    #[arg(long, value_name = "DIR", hide = true)]
+    man: Option<PathBuf>,
 }
 
 fn show_disclaimer() {
@@ -102,6 +112,21 @@ fn create_spinner(message: &str) -> ProgressBar {
 async fn main() -> Result<()> {
     let args = Args::parse();
 
+    if let Some(shell) = args.completions {
+        clap_complete::generate(
+            shell,
+            &mut Args::command(),
+            "review",
+            &mut std::io::stdout(),
+        );
+        return Ok(());
+    }
+    if let Some(dir) = &args.man {
+        std::fs::create_dir_all(dir)?;
+        clap_mangen::generate_to(Args::command(), dir)?;
+        return Ok(());
+    }
+
     // Initialize tracing
     let filter = if args.verbose {
         EnvFilter::new("debug")
@@ -132,7 +157,8 @@ async fn run(args: Args) -> Result<(), ReviewError> {
 
     // 2. Parse PR URL
     let spinner = create_spinner("Parsing PR URL...");
-    let (owner, repo, pr_number) = parse_pr_url(&args.pr_url)?;
+    let pr_url = args.pr_url.as_deref().unwrap_or_default();
+    let (owner, repo, pr_number) = parse_pr_url(pr_url)?;
     spinner.finish_with_message(format!("PR: {owner}/{repo}#{pr_number}"));
 
     // 3. Get PR info
@@ -188,7 +214,7 @@ async fn run(args: Args) -> Result<(), ReviewError> {
     // 8. Initialize review
     let client = ReviewApiClient::new(args.api_url.clone());
     let spinner = create_spinner("Initializing review...");
-    let init_response = client.init(&args.pr_url, &email, &pr_info.title).await?;
+    let init_response = client.init(pr_url, &email, &pr_info.title).await?;
     spinner.finish_with_message(format!("Review ID: {}", init_response.review_id));
 
     // 9. Upload archive
@@ -13,6 +13,18 @@ fn main() {
         println!("cargo:rustc-env=VK_SHARED_API_BASE={}", vk_shared_api_base);
     }
 
+    // gRPC services, generated with a bundled protoc so building doesn't need one installed
+    if std::env::var_os("PROTOC").is_none() {
+        let protoc = protoc_bin_vendored::protoc_bin_path().unwrap();
+        // SAFETY: build scripts are single-threaded
+        unsafe { std::env::set_var("PROTOC", protoc) };
+    }
+    println!("cargo:rerun-if-changed=proto");
+    tonic_build::configure()
+        .build_client(false)
+        .compile_protos(&["proto/vibe_kanban/v1/api.proto"], &["proto"])
+        .unwrap();
+
     // Create frontend/dist directory if it doesn't exist
     let dist_path = Path::new("../../frontend/dist");
     if !dist_path.exists() {
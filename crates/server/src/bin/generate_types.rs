@@ -30,12 +30,182 @@ fn generate_types_content() -> String {
         db::models::tag::Tag::decl(),
         db::models::tag::CreateTag::decl(),
         db::models::tag::UpdateTag::decl(),
+        db::models::label::Label::decl(),
+        db::models::label::CreateLabel::decl(),
+        db::models::label::UpdateLabel::decl(),
+        db::models::label::SetTaskLabels::decl(),
+        db::models::custom_field::CustomFieldType::decl(),
+        db::models::custom_field::CustomField::decl(),
+        db::models::custom_field::CreateCustomField::decl(),
+        db::models::custom_field::UpdateCustomField::decl(),
+        db::models::custom_field::SetCustomFieldValue::decl(),
+        db::models::custom_field::TaskCustomFieldValue::decl(),
+        db::models::user::User::decl(),
+        db::models::user::CreateUser::decl(),
+        db::models::user::UpdateUser::decl(),
+        db::models::api_key::ApiKeyScope::decl(),
+        db::models::api_key::ApiKey::decl(),
+        db::models::api_key::CreateApiKey::decl(),
+        db::models::audit_log::AuditPrincipal::decl(),
+        db::models::audit_log::AuditEntry::decl(),
+        db::models::integration::IntegrationProvider::decl(),
+        db::models::integration::MappedField::decl(),
+        db::models::integration::FieldMappingRule::decl(),
+        db::models::integration::FieldMapping::decl(),
+        db::models::integration::IntegrationResponse::decl(),
+        db::models::integration::CreateIntegration::decl(),
+        db::models::integration::UpdateIntegration::decl(),
+        db::models::integration::IntegrationCheckStep::decl(),
+        db::models::integration::IntegrationCheck::decl(),
+        db::models::integration_link::IntegrationLink::decl(),
+        db::models::sync_job::SyncJobStatus::decl(),
+        db::models::sync_job::SyncJob::decl(),
+        db::models::sync_dead_letter::SyncDeadLetter::decl(),
+        db::models::sync_plan::SyncPlanStatus::decl(),
+        db::models::sync_plan::SyncPlanAction::decl(),
+        db::models::sync_plan::PlannedFieldChange::decl(),
+        db::models::sync_plan::SyncPlanItem::decl(),
+        db::models::sync_plan::SyncPlan::decl(),
+        db::models::sync_conflict::SyncConflictStatus::decl(),
+        db::models::sync_conflict::ConflictResolution::decl(),
+        db::models::sync_conflict::ConflictField::decl(),
+        db::models::sync_conflict::SyncConflict::decl(),
+        db::models::sync_conflict::SyncConflictQuery::decl(),
+        db::models::sync_conflict::ResolveSyncConflict::decl(),
+        db::models::sync_audit::SyncAuditEntry::decl(),
+        db::models::sync_audit::SyncAuditFilter::decl(),
         db::models::task::TaskStatus::decl(),
+        db::models::task::TaskPriority::decl(),
+        db::models::task::TaskSort::decl(),
         db::models::task::Task::decl(),
         db::models::task::TaskWithAttemptStatus::decl(),
         db::models::task::TaskRelationships::decl(),
         db::models::task::CreateTask::decl(),
+        db::models::task::CloneTask::decl(),
         db::models::task::UpdateTask::decl(),
+        db::models::task::TaskFilter::decl(),
+        db::models::task::DueDateSummary::decl(),
+        db::models::project_summary::SummaryColumn::decl(),
+        db::models::project_summary::OverdueTask::decl(),
+        db::models::project_summary::SummaryActivity::decl(),
+        db::models::project_summary::ProjectSummary::decl(),
+        db::models::project_archive::ArchivedProject::decl(),
+        db::models::project_archive::ArchivedColumn::decl(),
+        db::models::project_archive::ArchivedLabel::decl(),
+        db::models::project_archive::ArchivedCustomField::decl(),
+        db::models::project_archive::ArchivedFieldValue::decl(),
+        db::models::project_archive::ArchivedChecklistItem::decl(),
+        db::models::project_archive::ArchivedComment::decl(),
+        db::models::project_archive::ArchivedTask::decl(),
+        db::models::project_archive::ArchivedTaskLink::decl(),
+        db::models::project_archive::ArchivedIntegration::decl(),
+        db::models::project_archive::ProjectArchive::decl(),
+        db::models::project_archive::NameConflict::decl(),
+        db::models::project_archive::ImportReport::decl(),
+        db::models::quick_add::QuickAddParse::decl(),
+        db::models::quick_add::QuickAddResult::decl(),
+        db::models::burndown::BurndownPoint::decl(),
+        db::models::cumulative_flow::CumulativeFlowPoint::decl(),
+        db::models::flow_metrics::DurationStats::decl(),
+        db::models::flow_metrics::ThroughputWeek::decl(),
+        db::models::flow_metrics::FlowMetrics::decl(),
+        db::models::wip_limit::WipLimit::decl(),
+        db::models::wip_limit::SetWipLimit::decl(),
+        db::models::wip_limit::WipColumn::decl(),
+        db::models::project_column::ProjectColumn::decl(),
+        db::models::project_column::CreateProjectColumn::decl(),
+        db::models::project_column::UpdateProjectColumn::decl(),
+        db::models::project_column::ReorderProjectColumns::decl(),
+        db::models::project_column::MoveTask::decl(),
+        db::models::project_column::MoveTaskToProject::decl(),
+        db::models::project_member::ProjectRole::decl(),
+        db::models::project_member::ProjectMember::decl(),
+        db::models::project_member::SetProjectMember::decl(),
+        db::models::team::TeamRole::decl(),
+        db::models::team::Team::decl(),
+        db::models::team::CreateTeam::decl(),
+        db::models::team::TeamMember::decl(),
+        db::models::team::SetTeamMember::decl(),
+        db::models::webhook::Webhook::decl(),
+        db::models::webhook::CreateWebhook::decl(),
+        db::models::webhook::WebhookDelivery::decl(),
+        db::models::share_link::ShareMode::decl(),
+        db::models::share_link::ShareField::decl(),
+        db::models::share_link::ShareLink::decl(),
+        db::models::share_link::CreateShareLink::decl(),
+        db::models::share_link::SharedBoardTask::decl(),
+        db::models::share_link::SharedBoardColumn::decl(),
+        db::models::share_link::SharedBoard::decl(),
+        db::models::board::SwimlaneGroupBy::decl(),
+        db::models::board::ProjectSwimlane::decl(),
+        db::models::board::SetProjectSwimlane::decl(),
+        db::models::board::BoardCell::decl(),
+        db::models::board::BoardLane::decl(),
+        db::models::board::Board::decl(),
+        db::models::task_checklist_item::TaskChecklistItem::decl(),
+        db::models::task_checklist_item::CreateTaskChecklistItem::decl(),
+        db::models::task_checklist_item::UpdateTaskChecklistItem::decl(),
+        db::models::task_checklist_item::ReorderTaskChecklistItems::decl(),
+        db::models::task_comment::TaskComment::decl(),
+        db::models::task_comment::CreateTaskComment::decl(),
+        db::models::task_watcher::WatchReason::decl(),
+        db::models::task_watcher::TaskWatcher::decl(),
+        db::models::task_watcher::WatchTask::decl(),
+        db::models::notification::NotificationKind::decl(),
+        db::models::notification::Notification::decl(),
+        db::models::notification::MarkNotificationsRead::decl(),
+        db::models::task_mention::TaskMention::decl(),
+        db::models::epic::EpicKind::decl(),
+        db::models::epic::Epic::decl(),
+        db::models::epic::EpicProgress::decl(),
+        db::models::epic::EpicWithProgress::decl(),
+        db::models::epic::CreateEpic::decl(),
+        db::models::epic::UpdateEpic::decl(),
+        db::models::epic::SetTaskEpic::decl(),
+        db::models::sprint::SprintStatus::decl(),
+        db::models::sprint::SprintTaskOutcome::decl(),
+        db::models::sprint::Sprint::decl(),
+        db::models::sprint::CreateSprint::decl(),
+        db::models::sprint::UpdateSprint::decl(),
+        db::models::sprint::AddSprintTasks::decl(),
+        db::models::sprint::CloseSprint::decl(),
+        db::models::sprint::SprintTask::decl(),
+        db::models::sprint::SprintTotals::decl(),
+        db::models::sprint::SprintReport::decl(),
+        db::models::sprint::SprintVelocity::decl(),
+        db::models::project_settings::ProjectSettings::decl(),
+        db::models::project_settings::NotificationPreferences::decl(),
+        db::models::saved_view::SavedView::decl(),
+        db::models::saved_view::CreateSavedView::decl(),
+        db::models::saved_view::UpdateSavedView::decl(),
+        db::models::task_event::TaskEventKind::decl(),
+        db::models::task_merge::MergeTasks::decl(),
+        db::models::task_event::TaskEventSource::decl(),
+        db::models::task_event::TaskEvent::decl(),
+        db::models::task_revision::TaskRevision::decl(),
+        db::models::task_diff::FieldChange::decl(),
+        db::models::task_diff::TaskDiff::decl(),
+        db::models::time_entry::TimeEntry::decl(),
+        db::models::time_entry::TimeEntryDetail::decl(),
+        db::models::time_entry::TimeEntryFilter::decl(),
+        db::models::time_entry::TimeGroupBy::decl(),
+        db::models::time_entry::TimeTotal::decl(),
+        db::models::task_link::TaskLinkKind::decl(),
+        db::models::task_link::TaskLink::decl(),
+        db::models::task_link::LinkedTask::decl(),
+        db::models::task_link::CreateTaskLink::decl(),
+        db::models::task_search::TaskSearchHit::decl(),
+        db::models::task_search::ProjectFacet::decl(),
+        db::models::task_search::StatusFacet::decl(),
+        db::models::task_search::LabelFacet::decl(),
+        db::models::task_search::SearchFacets::decl(),
+        db::models::task_template::TaskTemplate::decl(),
+        db::models::task_template::CreateTaskTemplate::decl(),
+        db::models::task_template::UpdateTaskTemplate::decl(),
+        db::models::task_template::CreateTaskFromTemplate::decl(),
+        db::models::recurrence_rule::RecurrenceRule::decl(),
+        db::models::recurrence_rule::SetRecurrence::decl(),
+        db::models::task_attachment::TaskAttachment::decl(),
         db::models::scratch::DraftFollowUpData::decl(),
         db::models::scratch::ScratchPayload::decl(),
         db::models::scratch::ScratchType::decl(),
@@ -91,9 +261,18 @@ fn generate_types_content() -> String {
         utils::api::projects::RemoteProjectMembersResponse::decl(),
         server::routes::projects::CreateRemoteProjectRequest::decl(),
         server::routes::projects::LinkToExistingRequest::decl(),
+        server::routes::projects::DueDateSummaryQuery::decl(),
+        server::routes::projects::ProjectSummaryQuery::decl(),
+        server::routes::reports::BurndownQuery::decl(),
+        server::routes::reports::CumulativeFlowQuery::decl(),
+        server::routes::reports::AnalyticsQuery::decl(),
+        server::routes::project_archive::ImportQuery::decl(),
         server::routes::repo::RegisterRepoRequest::decl(),
         server::routes::repo::InitRepoRequest::decl(),
         server::routes::tags::TagSearchParams::decl(),
+        server::routes::users::AssignTask::decl(),
+        server::routes::integrations::IntegrationQuery::decl(),
+        server::routes::integrations::SyncJobsQuery::decl(),
         server::routes::oauth::TokenResponse::decl(),
         server::routes::config::UserSystemInfo::decl(),
         server::routes::config::Environment::decl(),
@@ -116,6 +295,24 @@ fn generate_types_content() -> String {
         server::routes::shared_tasks::AssignSharedTaskRequest::decl(),
         server::routes::tasks::ShareTaskResponse::decl(),
         server::routes::tasks::CreateAndStartTaskRequest::decl(),
+        server::routes::task_bulk::BulkTaskOperation::decl(),
+        server::routes::task_bulk::BulkTaskRequest::decl(),
+        server::routes::task_bulk::BulkTaskResult::decl(),
+        server::routes::task_batch::BatchTaskOperation::decl(),
+        server::routes::task_batch::BatchTaskRequest::decl(),
+        server::routes::task_quick_add::QuickAddRequest::decl(),
+        server::routes::task_batch::BatchTaskResult::decl(),
+        server::routes::task_batch::BatchTaskResponse::decl(),
+        server::routes::api_keys::CreatedApiKey::decl(),
+        server::routes::audit::AuditQuery::decl(),
+        server::routes::webhooks::WebhookQuery::decl(),
+        server::routes::webhooks::CreatedWebhook::decl(),
+        server::routes::webhooks::WebhookDeliveriesQuery::decl(),
+        server::routes::share_links::CreatedShareLink::decl(),
+        server::routes::search::SearchResults::decl(),
+        server::routes::task_revisions::TaskRevisionDiff::decl(),
+        server::routes::task_revisions::TaskDiffQuery::decl(),
+        server::routes::time_entries::TimerRequest::decl(),
         server::routes::task_attempts::pr::CreateGitHubPrRequest::decl(),
         server::routes::images::ImageResponse::decl(),
         server::routes::images::ImageMetadata::decl(),
@@ -138,6 +335,14 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::pr::GetPrCommentsQuery::decl(),
         services::services::github::UnifiedPrComment::decl(),
         server::routes::task_attempts::RepoBranchStatus::decl(),
+        services::services::markdown::RenderMarkdown::decl(),
+        services::services::markdown::RenderedMarkdown::decl(),
+        services::services::events::BoardEventKind::decl(),
+        services::services::events::BoardEvent::decl(),
+        services::services::events::BoardMessage::decl(),
+        services::services::events::ProjectEventKind::decl(),
+        services::services::events::ProjectEvent::decl(),
+        server::routes::board_ws::BoardWsQuery::decl(),
         services::services::filesystem::DirectoryEntry::decl(),
         services::services::filesystem::DirectoryListResponse::decl(),
         services::services::config::Config::decl(),
@@ -152,6 +357,12 @@ fn generate_types_content() -> String {
         services::services::config::ShowcaseState::decl(),
         services::services::git::GitBranch::decl(),
         services::services::share::SharedTaskDetails::decl(),
+        services::services::integrations::IntegrationCapability::decl(),
+        services::services::integrations::CatalogField::decl(),
+        services::services::integrations::ProviderCatalogEntry::decl(),
+        services::services::integrations::SyncSummary::decl(),
+        services::services::integrations::WebhookOutcome::decl(),
+        services::services::integrations::IntegrationHealth::decl(),
         services::services::queued_message::QueuedMessage::decl(),
         services::services::queued_message::QueueStatus::decl(),
         services::services::git::ConflictOp::decl(),
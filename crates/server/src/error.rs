@@ -5,7 +5,7 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use db::models::{
-    execution_process::ExecutionProcessError, project::ProjectError,
+    execution_process::ExecutionProcessError, integration::IntegrationError, project::ProjectError,
     project_repo::ProjectRepoError, repo::RepoError, scratch::ScratchError, session::SessionError,
     workspace::WorkspaceError,
 };
@@ -13,11 +13,14 @@ use deployment::{DeploymentError, RemoteClientNotConfigured};
 use executors::executors::ExecutorError;
 use git2::Error as Git2Error;
 use services::services::{
+    attachment::AttachmentError,
     config::{ConfigError, EditorOpenError},
     container::ContainerError,
     git::GitServiceError,
     github::GitHubServiceError,
     image::ImageError,
+    integrations::IntegrationServiceError,
+    oidc::OidcError,
     project::ProjectServiceError,
     remote_client::RemoteClientError,
     repo::RepoError as RepoServiceError,
@@ -43,6 +46,8 @@ pub enum ApiError {
     #[error(transparent)]
     ExecutionProcess(#[from] ExecutionProcessError),
     #[error(transparent)]
+    Integration(#[from] IntegrationError),
+    #[error(transparent)]
     GitService(#[from] GitServiceError),
     #[error(transparent)]
     GitHubService(#[from] GitHubServiceError),
@@ -60,6 +65,8 @@ pub enum ApiError {
     Config(#[from] ConfigError),
     #[error(transparent)]
     Image(#[from] ImageError),
+    #[error(transparent)]
+    Attachment(#[from] AttachmentError),
     #[error("Multipart error: {0}")]
     Multipart(#[from] MultipartError),
     #[error("IO error: {0}")]
@@ -76,6 +83,10 @@ pub enum ApiError {
     Conflict(String),
     #[error("Forbidden: {0}")]
     Forbidden(String),
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+    #[error("Precondition required: {0}")]
+    PreconditionRequired(String),
 }
 
 impl From<&'static str> for ApiError {
@@ -110,6 +121,10 @@ impl IntoResponse for ApiError {
                 }
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "ExecutionProcessError"),
             },
+            ApiError::Integration(err) => match err {
+                IntegrationError::NotFound => (StatusCode::NOT_FOUND, "IntegrationError"),
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "IntegrationError"),
+            },
             // Promote certain GitService errors to conflict status with concise messages
             ApiError::GitService(git_err) => match git_err {
                 services::services::git::GitServiceError::MergeConflicts(_) => {
@@ -133,6 +148,17 @@ impl IntoResponse for ApiError {
                 ImageError::NotFound => (StatusCode::NOT_FOUND, "ImageNotFound"),
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "ImageError"),
             },
+            ApiError::Attachment(err) => match err {
+                AttachmentError::UnsupportedType(_) => (
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                    "UnsupportedAttachmentType",
+                ),
+                AttachmentError::TooLarge(_, _) => {
+                    (StatusCode::PAYLOAD_TOO_LARGE, "AttachmentTooLarge")
+                }
+                AttachmentError::NotFound => (StatusCode::NOT_FOUND, "AttachmentNotFound"),
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "AttachmentError"),
+            },
             ApiError::Io(_) => (StatusCode::INTERNAL_SERVER_ERROR, "IoError"),
             ApiError::EditorOpen(err) => match err {
                 EditorOpenError::LaunchFailed { .. } => {
@@ -177,6 +203,12 @@ impl IntoResponse for ApiError {
             ApiError::BadRequest(_) => (StatusCode::BAD_REQUEST, "BadRequest"),
             ApiError::Conflict(_) => (StatusCode::CONFLICT, "ConflictError"),
             ApiError::Forbidden(_) => (StatusCode::FORBIDDEN, "ForbiddenError"),
+            ApiError::PreconditionFailed(_) => {
+                (StatusCode::PRECONDITION_FAILED, "PreconditionFailed")
+            }
+            ApiError::PreconditionRequired(_) => {
+                (StatusCode::PRECONDITION_REQUIRED, "PreconditionRequired")
+            }
         };
 
         let error_message = match &self {
@@ -199,6 +231,18 @@ impl IntoResponse for ApiError {
                 }
                 _ => format!("{}: {}", error_type, self),
             },
+            ApiError::Attachment(err) => match err {
+                AttachmentError::UnsupportedType(detail) => {
+                    format!("This file type is not supported ({detail}).")
+                }
+                AttachmentError::TooLarge(size, max) => format!(
+                    "This file is too large ({:.1} MB). Maximum file size is {:.1} MB.",
+                    *size as f64 / 1_048_576.0,
+                    *max as f64 / 1_048_576.0
+                ),
+                AttachmentError::NotFound => "Attachment not found.".to_string(),
+                _ => "Failed to process attachment. Please try again.".to_string(),
+            },
             ApiError::Multipart(_) => "Failed to upload file. Please ensure the file is valid and try again.".to_string(),
             ApiError::RemoteClient(err) => match err {
                 RemoteClientError::Auth => "Unauthorized. Please sign in again.".to_string(),
@@ -253,6 +297,8 @@ impl IntoResponse for ApiError {
             ApiError::BadRequest(msg) => msg.clone(),
             ApiError::Conflict(msg) => msg.clone(),
             ApiError::Forbidden(msg) => msg.clone(),
+            ApiError::PreconditionFailed(msg) => msg.clone(),
+            ApiError::PreconditionRequired(msg) => msg.clone(),
             _ => format!("{}: {}", error_type, self),
         };
         let response = ApiResponse::<()>::error(&error_message);
@@ -313,6 +359,55 @@ impl From<ShareError> for ApiError {
     }
 }
 
+impl From<IntegrationServiceError> for ApiError {
+    fn from(err: IntegrationServiceError) -> Self {
+        match err {
+            IntegrationServiceError::Database(db_err) => ApiError::Database(db_err),
+            IntegrationServiceError::InvalidSignature => ApiError::Unauthorized,
+            IntegrationServiceError::InvalidPayload(msg) => {
+                ApiError::BadRequest(format!("Invalid webhook payload: {msg}"))
+            }
+            IntegrationServiceError::Disabled(_) => {
+                ApiError::Conflict("Integration is disabled".to_string())
+            }
+            err @ (IntegrationServiceError::PlanNotPending(_)
+            | IntegrationServiceError::ConflictResolved(_)
+            | IntegrationServiceError::TaskWrite(_)) => ApiError::Conflict(err.to_string()),
+            IntegrationServiceError::MissingSecret(key) => {
+                ApiError::Conflict(format!("Integration secret '{key}' is not configured"))
+            }
+            IntegrationServiceError::InvalidConfig(msg) => {
+                ApiError::Conflict(format!("Invalid integration config: {msg}"))
+            }
+            err @ (IntegrationServiceError::Transport(_)
+            | IntegrationServiceError::Http { .. }
+            | IntegrationServiceError::InvalidResponse(_)) => {
+                tracing::error!(?err, "integration provider error");
+                ApiError::Conflict(err.to_string())
+            }
+        }
+    }
+}
+
+impl From<OidcError> for ApiError {
+    fn from(err: OidcError) -> Self {
+        match err {
+            OidcError::Database(db_err) => ApiError::Database(db_err),
+            OidcError::UnknownState => ApiError::BadRequest(err.to_string()),
+            OidcError::InvalidIdToken(_) => {
+                tracing::warn!(?err, "rejected OIDC sign-in");
+                ApiError::Unauthorized
+            }
+            err @ (OidcError::Transport(_)
+            | OidcError::Http { .. }
+            | OidcError::InvalidResponse(_)) => {
+                tracing::error!(?err, "OIDC provider error");
+                ApiError::Conflict(err.to_string())
+            }
+        }
+    }
+}
+
 impl From<ProjectServiceError> for ApiError {
     fn from(err: ProjectServiceError) -> Self {
         match err {
@@ -0,0 +1,234 @@
+//! GraphQL API over projects, tasks, comments, labels and integrations, so a client can
+//! fetch a whole board with just the fields it needs in one round trip. Mutations go
+//! through the REST handlers, so they validate and record activity the same way.
+
+use std::sync::OnceLock;
+
+use async_graphql::{Context, EmptySubscription, Object, Result, Schema};
+use axum::{
+    Extension, Json,
+    extract::State,
+    http::{HeaderMap, HeaderValue, header::IF_MATCH},
+};
+use db::models::{
+    api_key::ApiKey,
+    project::Project,
+    project_member::ProjectRole,
+    task::{CreateTask, Task, UpdateTask},
+    task_comment::CreateTaskComment,
+    user::User,
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    middleware::{
+        auth::AuthUser,
+        rbac::{require_role, visible_projects},
+    },
+    routes::{task_comments, tasks},
+};
+
+mod types;
+
+use types::{
+    CommentObject, CreateTaskInput, ProjectObject, TaskObject, UpdateTaskInput, UserObject,
+    deployment,
+};
+
+/// Queries nest at most this deep, which keeps a single request from walking the whole
+/// database through `task { project { tasks { project ... } } }` cycles.
+const MAX_DEPTH: usize = 10;
+
+pub type VibeSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+/// The schema is built once; each request brings the deployment, and the signed-in
+/// [`AuthUser`] and [`ApiKey`] if there are any, along as data.
+pub fn schema() -> &'static VibeSchema {
+    static SCHEMA: OnceLock<VibeSchema> = OnceLock::new();
+    SCHEMA.get_or_init(|| {
+        Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+            .limit_depth(MAX_DEPTH)
+            .finish()
+    })
+}
+
+/// The signed-in user, whose project roles limit what the request can reach.
+fn user<'a>(ctx: &Context<'a>) -> Option<&'a AuthUser> {
+    ctx.data_opt::<AuthUser>()
+}
+
+/// The API key the request was made with, which may be limited to a project or team.
+fn api_key<'a>(ctx: &Context<'a>) -> Option<&'a ApiKey> {
+    ctx.data_opt::<ApiKey>()
+}
+
+/// A task the REST routes would let the user change: trashed tasks are only reachable
+/// through the trash.
+async fn load_task(ctx: &Context<'_>, id: Uuid) -> Result<Task> {
+    let task = Task::find_by_id(&deployment(ctx).db().pool, id)
+        .await?
+        .filter(|task| task.deleted_at.is_none())
+        .ok_or_else(|| format!("Task {id} not found"))?;
+    require_role(
+        deployment(ctx),
+        user(ctx),
+        api_key(ctx),
+        task.project_id,
+        ProjectRole::Member,
+    )
+    .await?;
+    Ok(task)
+}
+
+/// The data of a successful REST response.
+fn into_data<T>(response: Json<ApiResponse<T>>) -> Result<T> {
+    response
+        .0
+        .into_data()
+        .ok_or_else(|| "Empty response".into())
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn projects(&self, ctx: &Context<'_>) -> Result<Vec<ProjectObject>> {
+        let mut projects = Project::find_all(&deployment(ctx).db().pool).await?;
+        if let Some(visible) = visible_projects(deployment(ctx), user(ctx), api_key(ctx)).await? {
+            projects.retain(|project| visible.contains(&project.id));
+        }
+        Ok(projects.into_iter().map(ProjectObject).collect())
+    }
+
+    async fn project(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<ProjectObject>> {
+        let project = Project::find_by_id(&deployment(ctx).db().pool, id).await?;
+        if project.is_some() {
+            require_role(
+                deployment(ctx),
+                user(ctx),
+                api_key(ctx),
+                id,
+                ProjectRole::Viewer,
+            )
+            .await?;
+        }
+        Ok(project.map(ProjectObject))
+    }
+
+    async fn task(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<TaskObject>> {
+        let task = Task::find_by_id(&deployment(ctx).db().pool, id)
+            .await?
+            .filter(|task| task.deleted_at.is_none());
+        if let Some(task) = &task {
+            require_role(
+                deployment(ctx),
+                user(ctx),
+                api_key(ctx),
+                task.project_id,
+                ProjectRole::Viewer,
+            )
+            .await?;
+        }
+        Ok(task.map(TaskObject))
+    }
+
+    async fn users(&self, ctx: &Context<'_>) -> Result<Vec<UserObject>> {
+        let users = User::find_all(&deployment(ctx).db().pool).await?;
+        Ok(users.into_iter().map(UserObject).collect())
+    }
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn create_task(&self, ctx: &Context<'_>, input: CreateTaskInput) -> Result<TaskObject> {
+        let mut payload =
+            CreateTask::from_title_description(input.project_id, input.title, input.description);
+        payload.status = input.status.map(Into::into).or(payload.status);
+        payload.priority = input.priority.map(Into::into);
+        payload.due_at = input.due_at;
+        payload.estimate = input.estimate;
+        payload.assignee_id = input.assignee_id;
+        let response = tasks::create_task(
+            user(ctx).cloned().map(Extension),
+            api_key(ctx).cloned().map(Extension),
+            State(deployment(ctx).clone()),
+            Json(payload),
+        )
+        .await?;
+        Ok(TaskObject(into_data(response)?))
+    }
+
+    /// Pass `if_match` with the task's `etag` to refuse the update if the task changed
+    /// since it was read, or `*` to update it whatever its version.
+    async fn update_task(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        input: UpdateTaskInput,
+        if_match: Option<String>,
+    ) -> Result<TaskObject> {
+        let task = load_task(ctx, id).await?;
+        let mut headers = HeaderMap::new();
+        if let Some(if_match) = if_match {
+            headers.insert(IF_MATCH, HeaderValue::from_str(&if_match)?);
+        }
+        let payload = UpdateTask {
+            title: input.title,
+            description: input.description,
+            status: input.status.map(Into::into),
+            parent_workspace_id: None,
+            image_ids: None,
+            due_at: input.due_at,
+            clear_due_at: None,
+            priority: input.priority.map(Into::into),
+            estimate: input.estimate,
+            clear_estimate: None,
+            cover_color: None,
+            cover_attachment_id: None,
+            clear_cover: None,
+        };
+        let (_, response) = tasks::update_task(
+            Extension(task),
+            State(deployment(ctx).clone()),
+            headers,
+            Json(payload),
+        )
+        .await?;
+        Ok(TaskObject(into_data(response)?))
+    }
+
+    async fn archive_task(&self, ctx: &Context<'_>, id: Uuid) -> Result<TaskObject> {
+        let task = load_task(ctx, id).await?;
+        let response = tasks::archive_task(Extension(task), State(deployment(ctx).clone())).await?;
+        Ok(TaskObject(into_data(response)?))
+    }
+
+    /// Move the task to the project's trash. Returns the id of the trashed task.
+    async fn delete_task(&self, ctx: &Context<'_>, id: Uuid) -> Result<Uuid> {
+        let task = load_task(ctx, id).await?;
+        tasks::delete_task(Extension(task), State(deployment(ctx).clone())).await?;
+        Ok(id)
+    }
+
+    async fn add_comment(
+        &self,
+        ctx: &Context<'_>,
+        task_id: Uuid,
+        author: String,
+        body: String,
+    ) -> Result<CommentObject> {
+        let task = load_task(ctx, task_id).await?;
+        let response = task_comments::create_task_comment(
+            user(ctx).cloned().map(Extension),
+            Extension(task),
+            State(deployment(ctx).clone()),
+            Json(CreateTaskComment { author, body }),
+        )
+        .await?;
+        Ok(CommentObject(into_data(response)?))
+    }
+}
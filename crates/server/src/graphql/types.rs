@@ -0,0 +1,371 @@
+use async_graphql::{Context, Enum, InputObject, Object, Result};
+use chrono::{DateTime, Utc};
+use db::models::{
+    integration::Integration,
+    label::Label,
+    project::Project,
+    task::{Task, TaskFilter, TaskPriority, TaskStatus},
+    task_checklist_item::TaskChecklistItem,
+    task_comment::TaskComment,
+    user::User,
+};
+use deployment::Deployment;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, routes::tasks::task_etag};
+
+/// Most tasks a single `tasks` selection returns, as on the REST task list.
+const MAX_TASKS: i64 = 500;
+
+pub(super) fn deployment<'a>(ctx: &Context<'a>) -> &'a DeploymentImpl {
+    ctx.data_unchecked::<DeploymentImpl>()
+}
+
+#[derive(Enum, Clone, Copy, PartialEq, Eq)]
+#[graphql(remote = "db::models::task::TaskStatus", name = "TaskStatus")]
+pub enum GqlTaskStatus {
+    Todo,
+    InProgress,
+    InReview,
+    Done,
+    Cancelled,
+}
+
+#[derive(Enum, Clone, Copy, PartialEq, Eq)]
+#[graphql(remote = "db::models::task::TaskPriority", name = "TaskPriority")]
+pub enum GqlTaskPriority {
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+#[derive(Enum, Clone, Copy, PartialEq, Eq)]
+#[graphql(
+    remote = "db::models::integration::IntegrationProvider",
+    name = "IntegrationProvider"
+)]
+pub enum GqlIntegrationProvider {
+    YouTrack,
+    Jira,
+    GitHub,
+}
+
+pub struct ProjectObject(pub Project);
+
+#[Object(name = "Project")]
+impl ProjectObject {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    /// Tasks on the project's board, archived and trashed ones aside, in board order
+    /// unless the filter says otherwise.
+    async fn tasks(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<TaskFilterInput>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<Vec<TaskObject>> {
+        let filter = filter.map(TaskFilter::from).unwrap_or_default();
+        let tasks = Task::find_filtered_with_attempt_status(
+            &deployment(ctx).db().pool,
+            self.0.id,
+            &filter,
+            Some(limit.unwrap_or(MAX_TASKS).clamp(1, MAX_TASKS)),
+            offset.unwrap_or(0).max(0),
+        )
+        .await?;
+        Ok(tasks
+            .into_iter()
+            .map(|task| TaskObject(task.task))
+            .collect())
+    }
+
+    async fn labels(&self, ctx: &Context<'_>) -> Result<Vec<LabelObject>> {
+        let labels = Label::find_by_project_id(&deployment(ctx).db().pool, self.0.id).await?;
+        Ok(labels.into_iter().map(LabelObject).collect())
+    }
+
+    async fn integrations(&self, ctx: &Context<'_>) -> Result<Vec<IntegrationObject>> {
+        let integrations =
+            Integration::find_by_project_id(&deployment(ctx).db().pool, self.0.id).await?;
+        Ok(integrations.into_iter().map(IntegrationObject).collect())
+    }
+}
+
+pub struct TaskObject(pub Task);
+
+#[Object(name = "Task")]
+impl TaskObject {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn project_id(&self) -> Uuid {
+        self.0.project_id
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    /// Markdown
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
+    async fn status(&self) -> GqlTaskStatus {
+        self.0.status.clone().into()
+    }
+
+    async fn column_id(&self) -> Option<Uuid> {
+        self.0.column_id
+    }
+
+    async fn due_at(&self) -> Option<DateTime<Utc>> {
+        self.0.due_at
+    }
+
+    async fn priority(&self) -> GqlTaskPriority {
+        self.0.priority.into()
+    }
+
+    async fn estimate(&self) -> Option<f64> {
+        self.0.estimate
+    }
+
+    async fn cover_color(&self) -> Option<&str> {
+        self.0.cover_color.as_deref()
+    }
+
+    async fn cover_attachment_id(&self) -> Option<Uuid> {
+        self.0.cover_attachment_id
+    }
+
+    async fn archived_at(&self) -> Option<DateTime<Utc>> {
+        self.0.archived_at
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    /// This version of the task, for `updateTask(ifMatch: ...)`
+    async fn etag(&self) -> String {
+        task_etag(&self.0)
+    }
+
+    async fn project(&self, ctx: &Context<'_>) -> Result<Option<ProjectObject>> {
+        let project = Project::find_by_id(&deployment(ctx).db().pool, self.0.project_id).await?;
+        Ok(project.map(ProjectObject))
+    }
+
+    async fn assignee(&self, ctx: &Context<'_>) -> Result<Option<UserObject>> {
+        let Some(assignee_id) = self.0.assignee_id else {
+            return Ok(None);
+        };
+        let user = User::find_by_id(&deployment(ctx).db().pool, assignee_id).await?;
+        Ok(user.map(UserObject))
+    }
+
+    /// Oldest first.
+    async fn comments(&self, ctx: &Context<'_>) -> Result<Vec<CommentObject>> {
+        let comments = TaskComment::find_by_task_id(&deployment(ctx).db().pool, self.0.id).await?;
+        Ok(comments.into_iter().map(CommentObject).collect())
+    }
+
+    async fn labels(&self, ctx: &Context<'_>) -> Result<Vec<LabelObject>> {
+        let labels = Label::find_by_task_id(&deployment(ctx).db().pool, self.0.id).await?;
+        Ok(labels.into_iter().map(LabelObject).collect())
+    }
+
+    async fn checklist(&self, ctx: &Context<'_>) -> Result<Vec<ChecklistItemObject>> {
+        let items =
+            TaskChecklistItem::find_by_task_id(&deployment(ctx).db().pool, self.0.id).await?;
+        Ok(items.into_iter().map(ChecklistItemObject).collect())
+    }
+}
+
+pub struct CommentObject(pub TaskComment);
+
+#[Object(name = "Comment")]
+impl CommentObject {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn task_id(&self) -> Uuid {
+        self.0.task_id
+    }
+
+    async fn author(&self) -> &str {
+        &self.0.author
+    }
+
+    /// Markdown
+    async fn body(&self) -> &str {
+        &self.0.body
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+}
+
+pub struct LabelObject(pub Label);
+
+#[Object(name = "Label")]
+impl LabelObject {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn project_id(&self) -> Uuid {
+        self.0.project_id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// `#rrggbb`
+    async fn color(&self) -> &str {
+        &self.0.color
+    }
+}
+
+pub struct ChecklistItemObject(pub TaskChecklistItem);
+
+#[Object(name = "ChecklistItem")]
+impl ChecklistItemObject {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    async fn done(&self) -> bool {
+        self.0.done
+    }
+
+    async fn position(&self) -> i64 {
+        self.0.position
+    }
+}
+
+pub struct UserObject(pub User);
+
+#[Object(name = "User")]
+impl UserObject {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn email(&self) -> Option<&str> {
+        self.0.email.as_deref()
+    }
+}
+
+/// An integration of a project. Its configuration and secrets are not exposed.
+pub struct IntegrationObject(pub Integration);
+
+#[Object(name = "Integration")]
+impl IntegrationObject {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn provider(&self) -> GqlIntegrationProvider {
+        self.0.provider.into()
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn base_url(&self) -> &str {
+        &self.0.base_url
+    }
+
+    async fn enabled(&self) -> bool {
+        self.0.enabled
+    }
+}
+
+#[derive(InputObject, Default)]
+pub struct TaskFilterInput {
+    pub status: Option<GqlTaskStatus>,
+    /// Text to look for in titles and descriptions
+    pub query: Option<String>,
+    pub label_id: Option<Uuid>,
+    pub priority: Option<GqlTaskPriority>,
+    pub assignee_id: Option<Uuid>,
+    pub unassigned: Option<bool>,
+    pub overdue: Option<bool>,
+}
+
+impl From<TaskFilterInput> for TaskFilter {
+    fn from(input: TaskFilterInput) -> Self {
+        TaskFilter {
+            status: input.status.map(TaskStatus::from),
+            query: input.query.filter(|query| !query.trim().is_empty()),
+            label_id: input.label_id,
+            priority: input.priority.map(TaskPriority::from),
+            assignee_id: input.assignee_id,
+            unassigned: input.unassigned,
+            overdue: input.overdue,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct CreateTaskInput {
+    pub project_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: Option<GqlTaskStatus>,
+    pub priority: Option<GqlTaskPriority>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub estimate: Option<f64>,
+    pub assignee_id: Option<Uuid>,
+}
+
+#[derive(InputObject)]
+pub struct UpdateTaskInput {
+    pub title: Option<String>,
+    /// An empty string clears the description
+    pub description: Option<String>,
+    pub status: Option<GqlTaskStatus>,
+    pub priority: Option<GqlTaskPriority>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub estimate: Option<f64>,
+}
@@ -0,0 +1,470 @@
+//! gRPC API over projects, tasks and sync runs, for services that would rather call typed
+//! protobuf methods than REST. Served on the same port as the REST API; like the GraphQL
+//! API, calls go through the REST handlers so they validate and record activity the same
+//! way. The contract is `proto/vibe_kanban/v1/api.proto`.
+
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    http::{HeaderMap, HeaderValue, header::IF_MATCH},
+    middleware::from_fn_with_state,
+};
+use chrono::{DateTime, Utc};
+use db::models::{
+    api_key::ApiKey,
+    integration::Integration,
+    project::Project,
+    project_member::ProjectRole,
+    sync_job::SyncJob,
+    task::{CreateTask, Task, TaskPriority, TaskStatus, UpdateTask},
+};
+use deployment::Deployment;
+use tonic::{Request, Response, Status, service::Routes};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{
+        audit::audit,
+        auth::{AuthUser, authenticate},
+        rate_limit::rate_limit,
+        rbac::{require_role, visible_projects},
+    },
+    routes::{integrations, tasks},
+};
+
+pub mod pb {
+    tonic::include_proto!("vibe_kanban.v1");
+}
+
+use pb::{
+    projects_server::{Projects, ProjectsServer},
+    sync_server::{Sync as SyncApi, SyncServer},
+    tasks_server::{Tasks, TasksServer},
+};
+
+/// Methods that only read, which API keys with just the read scope may call.
+const READ_METHODS: &[&str] = &["ListProjects", "GetProject", "ListTasks", "GetTask"];
+
+/// Whether `path` is a gRPC call that changes nothing. gRPC calls are all `POST`s, so
+/// the method name is what tells reads apart.
+pub fn is_read_call(path: &str) -> bool {
+    path.strip_prefix("/vibe_kanban.v1.")
+        .and_then(|rest| rest.split_once('/'))
+        .is_some_and(|(_, method)| READ_METHODS.contains(&method))
+}
+
+/// The gRPC services, behind the same rate limits, authentication and audit log as the
+/// REST API.
+/// Roles are checked per call, as the paths don't name a project.
+pub fn router(deployment: &DeploymentImpl) -> Router {
+    Routes::new(ProjectsServer::new(ProjectsService(deployment.clone())))
+        .add_service(TasksServer::new(TasksService(deployment.clone())))
+        .add_service(SyncServer::new(SyncService(deployment.clone())))
+        .into_axum_router()
+        .layer(from_fn_with_state(deployment.clone(), audit))
+        .layer(from_fn_with_state(deployment.clone(), authenticate))
+        .layer(from_fn_with_state(deployment.clone(), rate_limit))
+}
+
+fn status(err: ApiError) -> Status {
+    match err {
+        ApiError::Unauthorized => Status::unauthenticated("Unauthorized"),
+        ApiError::Forbidden(message) => Status::permission_denied(message),
+        ApiError::BadRequest(message) => Status::invalid_argument(message),
+        ApiError::Conflict(message) => Status::failed_precondition(message),
+        ApiError::PreconditionFailed(message) | ApiError::PreconditionRequired(message) => {
+            Status::failed_precondition(message)
+        }
+        ApiError::Database(sqlx::Error::RowNotFound) => Status::not_found("Not found"),
+        err => Status::internal(err.to_string()),
+    }
+}
+
+/// The data of a successful REST response.
+fn into_data<T>(response: Json<ApiResponse<T>>) -> Result<T, Status> {
+    let response = response.0;
+    if !response.is_success() {
+        return Err(Status::failed_precondition(
+            response.message().unwrap_or("Request failed").to_string(),
+        ));
+    }
+    response
+        .into_data()
+        .ok_or_else(|| Status::internal("Empty response"))
+}
+
+fn parse_id(field: &str, value: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(value).map_err(|_| Status::invalid_argument(format!("{field} must be a UUID")))
+}
+
+fn parse_time(field: &str, value: Option<String>) -> Result<Option<DateTime<Utc>>, Status> {
+    value
+        .map(|value| {
+            DateTime::parse_from_rfc3339(&value)
+                .map(|at| at.with_timezone(&Utc))
+                .map_err(|_| Status::invalid_argument(format!("{field} must be an RFC 3339 time")))
+        })
+        .transpose()
+}
+
+/// Who is calling, as found by the authentication middleware.
+struct Caller {
+    user: Option<AuthUser>,
+    api_key: Option<ApiKey>,
+}
+
+impl Caller {
+    fn of<T>(request: &Request<T>) -> Self {
+        let extensions = request.extensions();
+        Self {
+            user: extensions.get::<AuthUser>().cloned(),
+            api_key: extensions.get::<ApiKey>().cloned(),
+        }
+    }
+
+    /// Refuse unless the caller's key reaches the project and its user has `required`.
+    async fn require(
+        &self,
+        deployment: &DeploymentImpl,
+        project_id: Uuid,
+        required: ProjectRole,
+    ) -> Result<(), Status> {
+        require_role(
+            deployment,
+            self.user.as_ref(),
+            self.api_key.as_ref(),
+            project_id,
+            required,
+        )
+        .await
+        .map_err(status)
+    }
+}
+
+impl From<TaskStatus> for pb::TaskStatus {
+    fn from(status: TaskStatus) -> Self {
+        match status {
+            TaskStatus::Todo => Self::Todo,
+            TaskStatus::InProgress => Self::InProgress,
+            TaskStatus::InReview => Self::InReview,
+            TaskStatus::Done => Self::Done,
+            TaskStatus::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+/// `None` for the unspecified status.
+fn task_status(value: i32) -> Result<Option<TaskStatus>, Status> {
+    match pb::TaskStatus::try_from(value) {
+        Ok(pb::TaskStatus::Unspecified) => Ok(None),
+        Ok(pb::TaskStatus::Todo) => Ok(Some(TaskStatus::Todo)),
+        Ok(pb::TaskStatus::InProgress) => Ok(Some(TaskStatus::InProgress)),
+        Ok(pb::TaskStatus::InReview) => Ok(Some(TaskStatus::InReview)),
+        Ok(pb::TaskStatus::Done) => Ok(Some(TaskStatus::Done)),
+        Ok(pb::TaskStatus::Cancelled) => Ok(Some(TaskStatus::Cancelled)),
+        Err(_) => Err(Status::invalid_argument(format!(
+            "Unknown task status {value}"
+        ))),
+    }
+}
+
+impl From<TaskPriority> for pb::TaskPriority {
+    fn from(priority: TaskPriority) -> Self {
+        match priority {
+            TaskPriority::Low => Self::Low,
+            TaskPriority::Medium => Self::Medium,
+            TaskPriority::High => Self::High,
+            TaskPriority::Urgent => Self::Urgent,
+        }
+    }
+}
+
+/// `None` for the unspecified priority.
+fn task_priority(value: i32) -> Result<Option<TaskPriority>, Status> {
+    match pb::TaskPriority::try_from(value) {
+        Ok(pb::TaskPriority::Unspecified) => Ok(None),
+        Ok(pb::TaskPriority::Low) => Ok(Some(TaskPriority::Low)),
+        Ok(pb::TaskPriority::Medium) => Ok(Some(TaskPriority::Medium)),
+        Ok(pb::TaskPriority::High) => Ok(Some(TaskPriority::High)),
+        Ok(pb::TaskPriority::Urgent) => Ok(Some(TaskPriority::Urgent)),
+        Err(_) => Err(Status::invalid_argument(format!(
+            "Unknown task priority {value}"
+        ))),
+    }
+}
+
+impl From<Project> for pb::Project {
+    fn from(project: Project) -> Self {
+        Self {
+            id: project.id.to_string(),
+            name: project.name,
+            team_id: project.team_id.map(|id| id.to_string()),
+            created_at: project.created_at.to_rfc3339(),
+            updated_at: project.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<Task> for pb::Task {
+    fn from(task: Task) -> Self {
+        let etag = tasks::task_etag(&task);
+        Self {
+            id: task.id.to_string(),
+            project_id: task.project_id.to_string(),
+            title: task.title,
+            description: task.description,
+            status: pb::TaskStatus::from(task.status).into(),
+            priority: pb::TaskPriority::from(task.priority).into(),
+            column_id: task.column_id.map(|id| id.to_string()),
+            due_at: task.due_at.map(|at| at.to_rfc3339()),
+            estimate: task.estimate,
+            assignee_id: task.assignee_id.map(|id| id.to_string()),
+            archived_at: task.archived_at.map(|at| at.to_rfc3339()),
+            created_at: task.created_at.to_rfc3339(),
+            updated_at: task.updated_at.to_rfc3339(),
+            etag,
+        }
+    }
+}
+
+impl From<SyncJob> for pb::SyncJob {
+    fn from(job: SyncJob) -> Self {
+        Self {
+            id: job.id.to_string(),
+            integration_id: job.integration_id.to_string(),
+            status: job.status.to_string(),
+            attempts: job.attempts,
+            error: job.error,
+            created_at: job.created_at.to_rfc3339(),
+        }
+    }
+}
+
+pub struct ProjectsService(DeploymentImpl);
+
+#[tonic::async_trait]
+impl Projects for ProjectsService {
+    async fn list_projects(
+        &self,
+        request: Request<pb::ListProjectsRequest>,
+    ) -> Result<Response<pb::ListProjectsResponse>, Status> {
+        let caller = Caller::of(&request);
+        let mut projects = Project::find_all(&self.0.db().pool)
+            .await
+            .map_err(|e| status(e.into()))?;
+        if let Some(visible) =
+            visible_projects(&self.0, caller.user.as_ref(), caller.api_key.as_ref())
+                .await
+                .map_err(status)?
+        {
+            projects.retain(|project| visible.contains(&project.id));
+        }
+        Ok(Response::new(pb::ListProjectsResponse {
+            projects: projects.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn get_project(
+        &self,
+        request: Request<pb::GetProjectRequest>,
+    ) -> Result<Response<pb::Project>, Status> {
+        let caller = Caller::of(&request);
+        let id = parse_id("id", &request.get_ref().id)?;
+        caller.require(&self.0, id, ProjectRole::Viewer).await?;
+        let project = Project::find_by_id(&self.0.db().pool, id)
+            .await
+            .map_err(|e| status(e.into()))?
+            .ok_or_else(|| Status::not_found(format!("Project {id} not found")))?;
+        Ok(Response::new(project.into()))
+    }
+}
+
+pub struct TasksService(DeploymentImpl);
+
+impl TasksService {
+    /// A task the caller may reach with `required`; trashed tasks are only reachable
+    /// through the trash.
+    async fn load_task(
+        &self,
+        caller: &Caller,
+        id: &str,
+        required: ProjectRole,
+    ) -> Result<Task, Status> {
+        let id = parse_id("id", id)?;
+        let task = Task::find_by_id(&self.0.db().pool, id)
+            .await
+            .map_err(|e| status(e.into()))?
+            .filter(|task| task.deleted_at.is_none())
+            .ok_or_else(|| Status::not_found(format!("Task {id} not found")))?;
+        caller.require(&self.0, task.project_id, required).await?;
+        Ok(task)
+    }
+}
+
+#[tonic::async_trait]
+impl Tasks for TasksService {
+    async fn list_tasks(
+        &self,
+        request: Request<pb::ListTasksRequest>,
+    ) -> Result<Response<pb::ListTasksResponse>, Status> {
+        let caller = Caller::of(&request);
+        let request = request.into_inner();
+        let project_id = parse_id("project_id", &request.project_id)?;
+        caller
+            .require(&self.0, project_id, ProjectRole::Viewer)
+            .await?;
+        let query = tasks::TaskQuery {
+            project_id,
+            view_id: None,
+            status: task_status(request.status)?,
+            q: request.query,
+            label_id: None,
+            due_before: None,
+            overdue: None,
+            priority: None,
+            assignee_id: None,
+            unassigned: None,
+            custom_field_id: None,
+            custom_field_value: None,
+            sort: None,
+            limit: request.limit,
+            offset: request.offset,
+            cursor: None,
+        };
+        let (_, response) = tasks::get_tasks(State(self.0.clone()), Query(query))
+            .await
+            .map_err(status)?;
+        Ok(Response::new(pb::ListTasksResponse {
+            tasks: into_data(response)?
+                .into_iter()
+                .map(|task| task.task.into())
+                .collect(),
+        }))
+    }
+
+    async fn get_task(
+        &self,
+        request: Request<pb::GetTaskRequest>,
+    ) -> Result<Response<pb::Task>, Status> {
+        let caller = Caller::of(&request);
+        let task = self
+            .load_task(&caller, &request.get_ref().id, ProjectRole::Viewer)
+            .await?;
+        Ok(Response::new(task.into()))
+    }
+
+    async fn create_task(
+        &self,
+        request: Request<pb::CreateTaskRequest>,
+    ) -> Result<Response<pb::Task>, Status> {
+        let caller = Caller::of(&request);
+        let request = request.into_inner();
+        let project_id = parse_id("project_id", &request.project_id)?;
+        caller
+            .require(&self.0, project_id, ProjectRole::Member)
+            .await?;
+        let mut payload =
+            CreateTask::from_title_description(project_id, request.title, request.description);
+        payload.status = task_status(request.status)?.or(payload.status);
+        payload.priority = task_priority(request.priority)?;
+        payload.due_at = parse_time("due_at", request.due_at)?;
+        payload.estimate = request.estimate;
+        payload.assignee_id = request
+            .assignee_id
+            .map(|id| parse_id("assignee_id", &id))
+            .transpose()?;
+        let response = tasks::create_task(
+            caller.user.map(Extension),
+            caller.api_key.map(Extension),
+            State(self.0.clone()),
+            Json(payload),
+        )
+        .await
+        .map_err(status)?;
+        Ok(Response::new(into_data(response)?.into()))
+    }
+
+    async fn update_task(
+        &self,
+        request: Request<pb::UpdateTaskRequest>,
+    ) -> Result<Response<pb::Task>, Status> {
+        let caller = Caller::of(&request);
+        let request = request.into_inner();
+        let task = self
+            .load_task(&caller, &request.id, ProjectRole::Member)
+            .await?;
+        let mut headers = HeaderMap::new();
+        if let Some(if_match) = request.if_match {
+            let value = HeaderValue::from_str(&if_match)
+                .map_err(|_| Status::invalid_argument("if_match is not a valid ETag"))?;
+            headers.insert(IF_MATCH, value);
+        }
+        let payload = UpdateTask {
+            title: request.title,
+            description: request.description,
+            status: task_status(request.status)?,
+            parent_workspace_id: None,
+            image_ids: None,
+            due_at: parse_time("due_at", request.due_at)?,
+            clear_due_at: None,
+            priority: task_priority(request.priority)?,
+            estimate: request.estimate,
+            clear_estimate: None,
+            cover_color: None,
+            cover_attachment_id: None,
+            clear_cover: None,
+        };
+        let (_, response) = tasks::update_task(
+            Extension(task),
+            State(self.0.clone()),
+            headers,
+            Json(payload),
+        )
+        .await
+        .map_err(status)?;
+        Ok(Response::new(into_data(response)?.into()))
+    }
+
+    async fn delete_task(
+        &self,
+        request: Request<pb::DeleteTaskRequest>,
+    ) -> Result<Response<pb::DeleteTaskResponse>, Status> {
+        let caller = Caller::of(&request);
+        let task = self
+            .load_task(&caller, &request.get_ref().id, ProjectRole::Member)
+            .await?;
+        tasks::delete_task(Extension(task), State(self.0.clone()))
+            .await
+            .map_err(status)?;
+        Ok(Response::new(pb::DeleteTaskResponse {}))
+    }
+}
+
+pub struct SyncService(DeploymentImpl);
+
+#[tonic::async_trait]
+impl SyncApi for SyncService {
+    async fn trigger_sync(
+        &self,
+        request: Request<pb::TriggerSyncRequest>,
+    ) -> Result<Response<pb::SyncJob>, Status> {
+        let caller = Caller::of(&request);
+        let id = parse_id("integration_id", &request.get_ref().integration_id)?;
+        let integration = Integration::find_by_id(&self.0.db().pool, id)
+            .await
+            .map_err(|e| status(e.into()))?
+            .ok_or_else(|| Status::not_found(format!("Integration {id} not found")))?;
+        // Running a sync is up to the project's admins, as with the REST route
+        caller
+            .require(&self.0, integration.project_id, ProjectRole::Admin)
+            .await?;
+        let (_, response) =
+            integrations::trigger_sync(Extension(integration), State(self.0.clone()))
+                .await
+                .map_err(status)?;
+        Ok(Response::new(into_data(response)?.into()))
+    }
+}
@@ -1,7 +1,11 @@
 pub mod error;
+pub mod graphql;
+pub mod grpc;
 pub mod mcp;
 pub mod middleware;
+pub mod openapi;
 pub mod routes;
+pub mod telemetry;
 
 // #[cfg(feature = "cloud")]
 // type DeploymentImpl = vibe_kanban_cloud::deployment::CloudDeployment;
@@ -1,6 +1,9 @@
 use anyhow::{self, Error as AnyhowError};
 use deployment::{Deployment, DeploymentError};
-use server::{DeploymentImpl, routes};
+use server::{
+    DeploymentImpl,
+    routes::{self, health},
+};
 use services::services::container::ContainerService;
 use sqlx::Error as SqlxError;
 use strip_ansi_escapes::strip;
@@ -38,13 +41,19 @@ async fn main() -> Result<(), VibeKanbanError> {
     tracing_subscriber::registry()
         .with(tracing_subscriber::fmt::layer().with_filter(env_filter))
         .with(sentry_layer())
+        .with(server::telemetry::layer())
         .init();
 
+    // Before anything records metrics
+    routes::metrics::install();
+
     // Create asset directory if it doesn't exist
     if !asset_dir().exists() {
         std::fs::create_dir_all(asset_dir())?;
     }
 
+    // Held until exit, so `vk restore` won't swap the database out from under us
+    let _database_lock = db::backup::lock_database().map_err(AnyhowError::from)?;
     let deployment = DeploymentImpl::new().await?;
     deployment.update_sentry_scope().await?;
     deployment
@@ -62,7 +71,13 @@ async fn main() -> Result<(), VibeKanbanError> {
         .backfill_repo_names()
         .await
         .map_err(DeploymentError::from)?;
-    deployment.spawn_pr_monitor_service().await;
+    // Watched by the `/healthz` and `/readyz` probes
+    health::track_worker("pr_monitor", deployment.spawn_pr_monitor_service().await);
+    health::track_worker("job_runner", deployment.spawn_job_runner().await);
+    health::track_worker(
+        "webhook_dispatcher",
+        deployment.spawn_webhook_dispatcher().await,
+    );
     deployment
         .track_if_analytics_allowed("session_start", serde_json::json!({}))
         .await;
@@ -133,6 +148,7 @@ async fn main() -> Result<(), VibeKanbanError> {
         .await?;
 
     perform_cleanup_actions(&deployment).await;
+    server::telemetry::shutdown();
 
     Ok(())
 }
@@ -282,10 +282,25 @@ pub struct McpContext {
     pub workspace_repos: Vec<McpRepoContext>,
 }
 
+/// A client for the backend that sends the key in `VK_API_KEY`, if any, for backends
+/// that require API keys.
+fn backend_client() -> reqwest::Client {
+    let mut headers = reqwest::header::HeaderMap::new();
+    if let Ok(key) = std::env::var("VK_API_KEY")
+        && let Ok(value) = reqwest::header::HeaderValue::from_str(&format!("Bearer {key}"))
+    {
+        headers.insert(reqwest::header::AUTHORIZATION, value);
+    }
+    reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap_or_default()
+}
+
 impl TaskServer {
     pub fn new(base_url: &str) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: backend_client(),
             base_url: base_url.to_string(),
             tool_router: Self::tool_router(),
             context: None,
@@ -756,9 +771,19 @@ impl TaskServer {
             status,
             parent_workspace_id: None,
             image_ids: None,
+            due_at: None,
+            clear_due_at: None,
+            priority: None,
+            estimate: None,
+            clear_estimate: None,
+            cover_color: None,
+            cover_attachment_id: None,
+            clear_cover: None,
         };
         let url = self.url(&format!("/api/tasks/{}", task_id));
-        let updated_task: Task = match self.send_json(self.client.put(&url).json(&payload)).await {
+        // The tool updates the task as it is now, whatever changed since it was read
+        let request = self.client.put(&url).header("If-Match", "*").json(&payload);
+        let updated_task: Task = match self.send_json(request).await {
             Ok(t) => t,
             Err(e) => return Ok(e),
         };
@@ -0,0 +1,83 @@
+//! The audit log: every request that changes something is recorded after it's answered,
+//! with the user and API key behind it and the project it touched. Reads aren't recorded.
+
+use axum::{
+    extract::{OriginalUri, Request, State},
+    http::Method,
+    middleware::Next,
+    response::Response,
+};
+use db::models::{
+    api_key::ApiKey,
+    audit_log::{AuditEntry, AuditPrincipal, CreateAuditEntry},
+};
+use deployment::Deployment;
+
+use crate::{
+    DeploymentImpl,
+    middleware::{
+        auth::{AuthUser, query_project_id},
+        rbac::path_project_id,
+    },
+};
+
+fn is_mutating(request: &Request) -> bool {
+    !matches!(
+        *request.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS
+    ) && !crate::grpc::is_read_call(request.uri().path())
+}
+
+/// Record mutating requests in the audit log. Runs after authentication, so refusals by
+/// role checks are recorded too; a failure to record is logged rather than failing the
+/// request that was already handled.
+pub async fn audit(
+    State(deployment): State<DeploymentImpl>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !is_mutating(&request) {
+        return next.run(request).await;
+    }
+    let pool = &deployment.db().pool;
+    // Looked up before the request runs, while whatever it deletes still exists
+    let project_id = match path_project_id(pool, request.uri().path()).await {
+        Ok(Some(project_id)) => Some(project_id),
+        _ => query_project_id(&request),
+    };
+    let extensions = request.extensions();
+    let user = extensions
+        .get::<AuthUser>()
+        .map(|AuthUser(user)| user.clone());
+    let api_key = extensions.get::<ApiKey>().cloned();
+    let principal = match (&api_key, &user) {
+        (Some(_), _) => AuditPrincipal::ApiKey,
+        (None, Some(_)) => AuditPrincipal::Session,
+        (None, None) => AuditPrincipal::Anonymous,
+    };
+    // The path as sent, not as seen inside the `/api` nest; query strings are left out as
+    // they may carry tokens
+    let path = extensions
+        .get::<OriginalUri>()
+        .map_or(request.uri(), |OriginalUri(uri)| uri)
+        .path()
+        .to_string();
+    let method = request.method().to_string();
+
+    let response = next.run(request).await;
+    let entry = CreateAuditEntry {
+        method,
+        path,
+        status: i64::from(response.status().as_u16()),
+        principal,
+        user_id: user.as_ref().map(|user| user.id),
+        user_name: user.map(|user| user.name),
+        api_key_id: api_key.as_ref().map(|api_key| api_key.id),
+        api_key_prefix: api_key.map(|api_key| api_key.prefix),
+        project_id,
+    };
+    if let Err(e) = AuditEntry::create(pool, &entry).await {
+        tracing::warn!("Failed to record audit log entry: {e}");
+    }
+    response
+}
@@ -0,0 +1,213 @@
+use std::{collections::HashSet, fmt::Write, str::FromStr, sync::LazyLock};
+
+use axum::{
+    extract::{Request, State},
+    http::{
+        HeaderMap, Method,
+        header::{AUTHORIZATION, COOKIE},
+    },
+    middleware::Next,
+    response::Response,
+};
+use chrono::Utc;
+use db::models::{
+    api_key::{ApiKey, ApiKeyScope},
+    integration::IntegrationProvider,
+    team::Team,
+    user::User,
+    user_session::UserSession,
+};
+use deployment::Deployment;
+use rand::{Rng, distributions::Alphanumeric};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub const API_KEY_PREFIX: &str = "vk_";
+
+/// The cookie holding a signed-in browser's session token.
+pub const SESSION_COOKIE: &str = "vk_session";
+
+/// Characters of a key kept in the clear to tell keys apart.
+const DISPLAY_PREFIX_LEN: usize = 10;
+
+/// Set `VK_REQUIRE_AUTH=1` when the server is reachable by more than its local user;
+/// requests with neither a valid key nor a session are then refused.
+static REQUIRE_AUTH: LazyLock<bool> = LazyLock::new(|| utils::env_flag("VK_REQUIRE_AUTH"));
+
+/// Reachable without credentials: there has to be a way to sign in, tracker webhooks
+/// are checked against their integration's signing secret instead, and share links are
+/// their own credential. Managing outgoing webhooks under `/webhooks` is not public.
+fn is_public(path: &str) -> bool {
+    matches!(path, "/health" | "/auth/oidc/login" | "/auth/oidc/callback")
+        || path.starts_with("/share/")
+        || path
+            .strip_prefix("/webhooks/")
+            .and_then(|rest| rest.split('/').next())
+            .is_some_and(|provider| IntegrationProvider::from_str(provider).is_ok())
+}
+
+/// The person behind a request: the signed-in user, or the user an API key acts as.
+#[derive(Debug, Clone)]
+pub struct AuthUser(pub User);
+
+/// The projects of the team the request's API key is limited to, looked up when the key is
+/// checked.
+#[derive(Debug, Clone)]
+pub struct TeamProjects(pub HashSet<Uuid>);
+
+/// A new key, and the prefix shown for it afterwards.
+pub fn generate_api_key() -> (String, String) {
+    let secret: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(40)
+        .map(char::from)
+        .collect();
+    let key = format!("{API_KEY_PREFIX}{secret}");
+    let prefix = key[..DISPLAY_PREFIX_LEN].to_string();
+    (key, prefix)
+}
+
+/// A new session token for the session cookie.
+pub fn generate_session_token() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(48)
+        .map(char::from)
+        .collect()
+}
+
+/// Hash an API key or session token for storage.
+pub fn hash_token(key: &str) -> String {
+    let mut output = String::with_capacity(64);
+    for byte in Sha256::digest(key.as_bytes()) {
+        let _ = write!(output, "{:02x}", byte);
+    }
+    output
+}
+
+fn required_scope(request: &Request) -> ApiKeyScope {
+    let path = request.uri().path();
+    let reads = matches!(
+        *request.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS
+    ) || crate::grpc::is_read_call(path);
+    if path.starts_with("/api-keys")
+        || path.starts_with("/audit")
+        || ((path.starts_with("/teams") || path.starts_with("/webhooks")) && !reads)
+    {
+        ApiKeyScope::Admin
+    } else if reads {
+        ApiKeyScope::Read
+    } else {
+        ApiKeyScope::Write
+    }
+}
+
+/// The `project_id` query parameter, which list endpoints filter by.
+pub fn query_project_id(request: &Request) -> Option<Uuid> {
+    request.uri().query()?.split('&').find_map(|pair| {
+        let value = pair.strip_prefix("project_id=")?;
+        Uuid::parse_str(value).ok()
+    })
+}
+
+/// The API key from an `Authorization: Bearer vk_...` header.
+pub fn bearer_api_key(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| token.starts_with(API_KEY_PREFIX))
+}
+
+/// A cookie from the request, when set and not empty.
+pub fn cookie<'a>(headers: &'a HeaderMap, cookie_name: &str) -> Option<&'a str> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .find_map(|pair| {
+            let (name, value) = pair.trim().split_once('=')?;
+            (name == cookie_name && !value.is_empty()).then_some(value)
+        })
+}
+
+/// The session token from the request's cookies.
+pub fn session_token(headers: &HeaderMap) -> Option<&str> {
+    cookie(headers, SESSION_COOKIE)
+}
+
+/// Authenticate the request, by `Authorization: Bearer vk_...` API key or by session
+/// cookie. A key's scopes are checked here and the key is added to the request for
+/// [`authorize`](super::rbac::authorize) and the loaders, which keep a project key to its
+/// project. The user behind the request, if any, is added as an [`AuthUser`].
+pub async fn authenticate(
+    State(deployment): State<DeploymentImpl>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+    let Some(token) = bearer_api_key(request.headers()) else {
+        let user = match session_token(request.headers()) {
+            Some(session) => UserSession::find_user(pool, &hash_token(session), Utc::now()).await?,
+            None => None,
+        };
+        match user {
+            Some(user) => {
+                request.extensions_mut().insert(AuthUser(user));
+            }
+            None if *REQUIRE_AUTH && !is_public(request.uri().path()) => {
+                return Err(ApiError::Unauthorized);
+            }
+            None => {}
+        }
+        return Ok(next.run(request).await);
+    };
+
+    let api_key = ApiKey::find_by_hash(pool, &hash_token(token))
+        .await?
+        .filter(|api_key| api_key.is_active(Utc::now()))
+        .ok_or(ApiError::Unauthorized)?;
+    let required = required_scope(&request);
+    if !api_key.allows(required) {
+        return Err(ApiError::Forbidden(format!(
+            "API key '{}' lacks the {required} scope",
+            api_key.name
+        )));
+    }
+    ApiKey::touch(pool, api_key.id).await?;
+
+    // A key whose user is gone would otherwise be taken for one acting as no user
+    if let Some(user_id) = api_key.user_id {
+        let user = User::find_by_id(pool, user_id)
+            .await?
+            .ok_or(ApiError::Unauthorized)?;
+        request.extensions_mut().insert(AuthUser(user));
+    }
+    if let Some(team_id) = api_key.team_id {
+        let project_ids = Team::project_ids(pool, team_id).await?;
+        request
+            .extensions_mut()
+            .insert(TeamProjects(project_ids.into_iter().collect()));
+    }
+    request.extensions_mut().insert(api_key);
+    Ok(next.run(request).await)
+}
+
+/// Whether the request's API key, if any, may reach this project. Like
+/// [`key_reaches_project`](super::rbac::key_reaches_project), without a query, for the
+/// loaders.
+pub fn allows_project(request: &Request, project_id: Uuid) -> bool {
+    let extensions = request.extensions();
+    extensions
+        .get::<ApiKey>()
+        .and_then(|api_key| api_key.project_id)
+        .is_none_or(|allowed| allowed == project_id)
+        && extensions
+            .get::<TeamProjects>()
+            .is_none_or(|TeamProjects(allowed)| allowed.contains(&project_id))
+}
@@ -0,0 +1,139 @@
+//! Cross-origin access to the API, for serving the frontend or other clients from a
+//! different domain than the server. Off unless `VK_CORS_ORIGINS` is set; until then
+//! browsers only let pages served by the server itself call the API.
+
+use std::time::Duration;
+
+use axum::http::{HeaderName, HeaderValue, Method, header};
+use tower_http::cors::{AllowHeaders, AllowOrigin, CorsLayer};
+
+use crate::{middleware::idempotency::IDEMPOTENT_REPLAYED, routes::NEXT_CURSOR};
+
+const DEFAULT_METHODS: &[Method] = &[
+    Method::GET,
+    Method::POST,
+    Method::PUT,
+    Method::PATCH,
+    Method::DELETE,
+];
+
+/// How long browsers may cache a preflight answer when `VK_CORS_MAX_AGE` isn't set.
+const DEFAULT_MAX_AGE_SECS: u64 = 600;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CorsOrigins {
+    /// Any origin, from `*`
+    Any,
+    List(Vec<HeaderValue>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorsConfig {
+    pub origins: CorsOrigins,
+    pub methods: Vec<Method>,
+    /// Whether browsers send cookies along, for signed-in sessions
+    pub allow_credentials: bool,
+    pub max_age: Duration,
+}
+
+/// Comma-separated values, trimmed, with empty ones dropped.
+fn list(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+impl CorsConfig {
+    /// `None` unless `VK_CORS_ORIGINS` lists at least one origin. `VK_CORS_METHODS`
+    /// replaces the default methods and `VK_CORS_CREDENTIALS=true` lets cookies through.
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).ok();
+        Self::parse(
+            &var("VK_CORS_ORIGINS")?,
+            var("VK_CORS_METHODS").as_deref(),
+            var("VK_CORS_CREDENTIALS").as_deref(),
+            var("VK_CORS_MAX_AGE").as_deref(),
+        )
+    }
+
+    fn parse(
+        origins: &str,
+        methods: Option<&str>,
+        credentials: Option<&str>,
+        max_age: Option<&str>,
+    ) -> Option<Self> {
+        let origins = if origins.trim() == "*" {
+            CorsOrigins::Any
+        } else {
+            let origins: Vec<HeaderValue> = list(origins)
+                .filter_map(
+                    |origin| match HeaderValue::from_str(origin.trim_end_matches('/')) {
+                        Ok(origin) => Some(origin),
+                        Err(_) => {
+                            tracing::warn!("Ignoring invalid CORS origin '{origin}'");
+                            None
+                        }
+                    },
+                )
+                .collect();
+            if origins.is_empty() {
+                return None;
+            }
+            CorsOrigins::List(origins)
+        };
+        let methods = methods
+            .map(|methods| {
+                list(methods)
+                    .filter_map(|method| method.to_ascii_uppercase().parse::<Method>().ok())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|methods| !methods.is_empty())
+            .unwrap_or_else(|| DEFAULT_METHODS.to_vec());
+        let mut allow_credentials = credentials.and_then(utils::parse_flag).unwrap_or(false);
+        // Browsers refuse credentialed responses that allow every origin
+        if allow_credentials && origins == CorsOrigins::Any {
+            tracing::warn!(
+                "VK_CORS_CREDENTIALS needs VK_CORS_ORIGINS to list origins instead of '*'; credentials stay off"
+            );
+            allow_credentials = false;
+        }
+        let max_age = max_age
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_MAX_AGE_SECS);
+        Some(Self {
+            origins,
+            methods,
+            allow_credentials,
+            max_age: Duration::from_secs(max_age),
+        })
+    }
+
+    pub fn layer(&self) -> CorsLayer {
+        let origins = match &self.origins {
+            CorsOrigins::Any => AllowOrigin::any(),
+            CorsOrigins::List(origins) => AllowOrigin::list(origins.clone()),
+        };
+        // Any header may be sent, but a credentialed `*` is refused by browsers, so the
+        // requested ones are echoed back instead
+        let headers = if self.allow_credentials {
+            AllowHeaders::mirror_request()
+        } else {
+            AllowHeaders::any()
+        };
+        let exposed: [HeaderName; 5] = [
+            NEXT_CURSOR,
+            IDEMPOTENT_REPLAYED,
+            header::CONTENT_DISPOSITION,
+            header::ETAG,
+            header::RETRY_AFTER,
+        ];
+        CorsLayer::new()
+            .allow_origin(origins)
+            .allow_methods(self.methods.clone())
+            .allow_headers(headers)
+            .allow_credentials(self.allow_credentials)
+            .expose_headers(exposed)
+            .max_age(self.max_age)
+    }
+}
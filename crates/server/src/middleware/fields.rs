@@ -0,0 +1,88 @@
+//! `fields=` support for trimming responses: `GET /api/projects/{id}/tasks?fields=id,title,status`
+//! returns only those keys of each task. Lists are trimmed item by item and single objects
+//! key by key. Keys are matched at the top level of `data`, so nested objects come whole.
+
+use std::collections::HashSet;
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::Request,
+    http::{
+        Method,
+        header::{CONTENT_LENGTH, CONTENT_TYPE},
+    },
+    middleware::Next,
+    response::Response,
+};
+use serde_json::Value;
+
+/// The field names asked for, or `None` when the request doesn't ask for a subset.
+fn requested_fields(request: &Request) -> Option<HashSet<String>> {
+    let query = request.uri().query()?;
+    let fields: HashSet<String> = url::form_urlencoded::parse(query.as_bytes())
+        .filter(|(key, _)| key == "fields")
+        .flat_map(|(_, value)| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|field| !field.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    (!fields.is_empty()).then_some(fields)
+}
+
+fn keep_fields(value: &mut Value, fields: &HashSet<String>) {
+    match value {
+        Value::Object(object) => object.retain(|key, _| fields.contains(key)),
+        Value::Array(items) => {
+            for item in items {
+                if let Value::Object(object) = item {
+                    object.retain(|key, _| fields.contains(key));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Trim the `data` of successful JSON responses to the fields in the `fields` query
+/// parameter. Requests without it, and anything that isn't a JSON envelope, pass untouched.
+pub async fn select_fields(request: Request, next: Next) -> Response {
+    let fields = match *request.method() {
+        Method::GET | Method::HEAD => requested_fields(&request),
+        _ => None,
+    };
+    let response = next.run(request).await;
+    let Some(fields) = fields else {
+        return response;
+    };
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    if !response.status().is_success() || !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        return Response::from_parts(parts, Body::empty());
+    };
+    let Ok(mut envelope) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let Some(data) = envelope.get_mut("data") else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    keep_fields(data, &fields);
+    match serde_json::to_vec(&envelope) {
+        Ok(trimmed) => {
+            parts.headers.remove(CONTENT_LENGTH);
+            Response::from_parts(parts, Body::from(trimmed))
+        }
+        Err(_) => Response::from_parts(parts, Body::from(bytes)),
+    }
+}
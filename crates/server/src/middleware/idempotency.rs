@@ -0,0 +1,374 @@
+//! `Idempotency-Key` support for endpoints that create things, so a client retrying after a
+//! dropped connection, or a duplicated webhook, gets the first reply instead of a second
+//! task or sync.
+
+use std::mem;
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::{Request, State},
+    http::{HeaderName, HeaderValue, Method, StatusCode, header::CONTENT_TYPE},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Duration, Utc};
+use db::models::{api_key::ApiKey, idempotency_key::IdempotencyKey};
+use deployment::Deployment;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::auth::AuthUser};
+
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Set on replies replayed for a retry.
+pub const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+const MAX_KEY_LEN: usize = 255;
+
+/// Bodies larger than this aren't accepted with an idempotency key.
+const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// How long a request holds its key. Abandoned requests give the key back when they are
+/// dropped; the lease only runs out for requests a crashed server never finished.
+const LEASE_MINUTES: i64 = 5;
+
+/// Keys are kept apart per client, so two clients can't see each other's replies.
+fn principal(request: &Request) -> String {
+    if let Some(api_key) = request.extensions().get::<ApiKey>() {
+        format!("api_key:{}", api_key.id)
+    } else if let Some(AuthUser(user)) = request.extensions().get::<AuthUser>() {
+        format!("user:{}", user.id)
+    } else {
+        "local".to_string()
+    }
+}
+
+fn request_hash(method: &Method, path: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str().as_bytes());
+    hasher.update(b" ");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// A key held by the request being handled. Dropped unsettled, because the client went away
+/// or the handler panicked, it gives the key back so a retry can run.
+struct Claim {
+    pool: SqlitePool,
+    principal: String,
+    key: String,
+    locked_until: DateTime<Utc>,
+    settled: bool,
+}
+
+impl Claim {
+    /// Give the key back now, for a reply that isn't stored.
+    async fn release(mut self) -> Result<(), sqlx::Error> {
+        self.settled = true;
+        IdempotencyKey::release(&self.pool, &self.principal, &self.key, self.locked_until).await
+    }
+
+    /// Store the reply to replay for retries.
+    async fn complete(
+        mut self,
+        status_code: i64,
+        content_type: Option<&str>,
+        body: &[u8],
+    ) -> Result<(), sqlx::Error> {
+        self.settled = true;
+        IdempotencyKey::complete(
+            &self.pool,
+            &self.principal,
+            &self.key,
+            status_code,
+            content_type,
+            body,
+        )
+        .await
+    }
+}
+
+impl Drop for Claim {
+    fn drop(&mut self) {
+        if self.settled {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let pool = self.pool.clone();
+        let principal = mem::take(&mut self.principal);
+        let key = mem::take(&mut self.key);
+        let locked_until = self.locked_until;
+        runtime.spawn(async move {
+            if let Err(e) = IdempotencyKey::release(&pool, &principal, &key, locked_until).await {
+                tracing::error!("Failed to release abandoned Idempotency-Key {key}: {e}");
+            }
+        });
+    }
+}
+
+fn replay(record: IdempotencyKey) -> Response {
+    let status = record
+        .status_code
+        .and_then(|code| u16::try_from(code).ok())
+        .and_then(|code| StatusCode::from_u16(code).ok())
+        .unwrap_or(StatusCode::OK);
+    let mut response = (status, record.response_body.unwrap_or_default()).into_response();
+    let headers = response.headers_mut();
+    if let Some(content_type) = record
+        .content_type
+        .and_then(|value| HeaderValue::from_str(&value).ok())
+    {
+        headers.insert(CONTENT_TYPE, content_type);
+    }
+    headers.insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+    response
+}
+
+/// Replay the stored reply when a request comes with an `Idempotency-Key` the client has
+/// used before. Reusing a key for a different request, or while the first is still being
+/// handled, is refused with `409 Conflict`. Server errors aren't stored, and requests
+/// abandoned midway give their key back, so those can be retried for real. Keys are
+/// forgotten after a day.
+pub async fn idempotency(
+    State(deployment): State<DeploymentImpl>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    idempotent(&deployment.db().pool, request, next).await
+}
+
+async fn idempotent(pool: &SqlitePool, request: Request, next: Next) -> Result<Response, ApiError> {
+    let Some(key) = request
+        .headers()
+        .get(IDEMPOTENCY_KEY)
+        .filter(|_| !request.method().is_safe())
+    else {
+        return Ok(next.run(request).await);
+    };
+    let key = key
+        .to_str()
+        .ok()
+        .map(str::trim)
+        .filter(|key| !key.is_empty() && key.len() <= MAX_KEY_LEN)
+        .ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "Idempotency-Key must be 1 to {MAX_KEY_LEN} visible characters"
+            ))
+        })?
+        .to_string();
+
+    let principal = principal(&request);
+    let (parts, body) = request.into_parts();
+    let body = to_bytes(body, MAX_BODY_BYTES)
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Failed to read request body: {e}")))?;
+    let hash = request_hash(&parts.method, parts.uri.path(), &body);
+
+    IdempotencyKey::delete_expired(pool).await?;
+    let locked_until = Utc::now() + Duration::minutes(LEASE_MINUTES);
+    if !IdempotencyKey::begin(pool, &principal, &key, &hash, locked_until).await? {
+        let Some(record) = IdempotencyKey::find(pool, &principal, &key).await? else {
+            return Err(ApiError::Conflict(
+                "A request with this Idempotency-Key is still in progress".to_string(),
+            ));
+        };
+        if record.request_hash != hash {
+            return Err(ApiError::Conflict(
+                "Idempotency-Key was already used for a different request".to_string(),
+            ));
+        }
+        if record.status_code.is_none() {
+            return Err(ApiError::Conflict(
+                "A request with this Idempotency-Key is still in progress".to_string(),
+            ));
+        }
+        return Ok(replay(record));
+    }
+
+    let claim = Claim {
+        pool: pool.clone(),
+        principal,
+        key,
+        locked_until,
+        settled: false,
+    };
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    if response.status().is_server_error() {
+        claim.release().await?;
+        return Ok(response);
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!(
+                "Failed to read response for Idempotency-Key {}: {e}",
+                claim.key
+            );
+            claim.release().await?;
+            return Err(ApiError::Conflict(
+                "The reply couldn't be stored; retry the request".to_string(),
+            ));
+        }
+    };
+    let content_type = parts
+        .headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+    claim
+        .complete(i64::from(parts.status.as_u16()), content_type, &body)
+        .await?;
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use axum::{Router, middleware::from_fn_with_state, routing::post};
+    use sqlx::sqlite::SqlitePoolOptions;
+    use tokio::sync::Notify;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        db::migrations::MIGRATOR.run(&pool).await.unwrap();
+        pool
+    }
+
+    async fn with_pool(
+        State(pool): State<SqlitePool>,
+        request: Request,
+        next: Next,
+    ) -> Result<Response, ApiError> {
+        idempotent(&pool, request, next).await
+    }
+
+    /// A route counting the requests that reach it. With `hang`, the first one notifies it
+    /// and never finishes.
+    fn app(pool: SqlitePool, calls: Arc<AtomicUsize>, hang: Option<Arc<Notify>>) -> Router {
+        let handler = move |body: String| {
+            let calls = calls.clone();
+            let hang = hang.clone();
+            async move {
+                let call = calls.fetch_add(1, Ordering::SeqCst);
+                if call == 0
+                    && let Some(started) = hang
+                {
+                    started.notify_one();
+                    std::future::pending::<()>().await;
+                }
+                (StatusCode::CREATED, format!("task {call}: {body}"))
+            }
+        };
+        Router::new()
+            .route("/tasks", post(handler))
+            .layer(from_fn_with_state(pool, with_pool))
+    }
+
+    fn request(key: &str, body: &str) -> Request {
+        Request::builder()
+            .method(Method::POST)
+            .uri("/tasks")
+            .header(IDEMPOTENCY_KEY, key)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    async fn text(response: Response) -> String {
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_retry_replays_the_first_reply() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(pool().await, calls.clone(), None);
+
+        let first = app.clone().oneshot(request("k1", "a")).await.unwrap();
+        assert_eq!(first.status(), StatusCode::CREATED);
+        assert!(first.headers().get(IDEMPOTENT_REPLAYED).is_none());
+        let first = text(first).await;
+
+        let retry = app.oneshot(request("k1", "a")).await.unwrap();
+        assert_eq!(retry.status(), StatusCode::CREATED);
+        assert_eq!(retry.headers()[IDEMPOTENT_REPLAYED], "true");
+        assert_eq!(text(retry).await, first);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_key_reused_for_another_request_conflicts() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(pool().await, calls.clone(), None);
+
+        let first = app.clone().oneshot(request("k1", "a")).await.unwrap();
+        assert_eq!(first.status(), StatusCode::CREATED);
+        let other = app.oneshot(request("k1", "b")).await.unwrap();
+        assert_eq!(other.status(), StatusCode::CONFLICT);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_request_gives_its_key_back() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let started = Arc::new(Notify::new());
+        let app = app(pool().await, calls.clone(), Some(started.clone()));
+
+        let abandoned = tokio::spawn(app.clone().oneshot(request("k1", "a")));
+        started.notified().await;
+        let concurrent = app.clone().oneshot(request("k1", "a")).await.unwrap();
+        assert_eq!(concurrent.status(), StatusCode::CONFLICT);
+
+        // The client goes away; the key is given back in the background
+        abandoned.abort();
+        let _ = abandoned.await;
+        let mut retry = None;
+        for _ in 0..100 {
+            let response = app.clone().oneshot(request("k1", "a")).await.unwrap();
+            if response.status() != StatusCode::CONFLICT {
+                retry = Some(response);
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(retry.unwrap().status(), StatusCode::CREATED);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_lapsed_claim_is_taken_over() {
+        let pool = pool().await;
+        let lapsed = Utc::now() - Duration::seconds(1);
+        assert!(
+            IdempotencyKey::begin(&pool, "local", "k1", "stale", lapsed)
+                .await
+                .unwrap()
+        );
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(pool, calls.clone(), None);
+
+        let response = app.oneshot(request("k1", "a")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
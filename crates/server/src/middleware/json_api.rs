@@ -0,0 +1,282 @@
+//! JSON:API representation of tasks and projects, for clients that ask for it with
+//! `Accept: application/vnd.api+json`. Each task or project becomes a resource object with
+//! its foreign keys as relationships linking to the related resource, and
+//! `fields[tasks]=title,status` trims resources to the named attributes and relationships.
+//! Only responses change; requests still take the usual JSON bodies.
+
+use std::collections::{HashMap, HashSet};
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::{OriginalUri, Request},
+    http::{
+        HeaderValue, StatusCode,
+        header::{ACCEPT, CONTENT_LENGTH, CONTENT_TYPE, VARY},
+    },
+    middleware::Next,
+    response::Response,
+};
+use serde_json::{Map, Value, json};
+
+use crate::routes::NEXT_CURSOR;
+
+pub const JSON_API: &str = "application/vnd.api+json";
+
+/// A foreign key shown as a relationship instead of an attribute: the key, the relationship
+/// name, the related resource type and the path it's served under, if any.
+type Relationship = (&'static str, &'static str, &'static str, &'static str);
+
+const TASK_RELATIONSHIPS: &[Relationship] = &[
+    ("project_id", "project", "projects", "/api/projects"),
+    ("assignee_id", "assignee", "users", "/api/users"),
+    ("column_id", "column", "columns", ""),
+    ("parent_workspace_id", "parent_workspace", "workspaces", ""),
+];
+const PROJECT_RELATIONSHIPS: &[Relationship] = &[("team_id", "team", "teams", "/api/teams")];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResourceType {
+    Tasks,
+    Projects,
+}
+
+impl ResourceType {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Tasks => "tasks",
+            Self::Projects => "projects",
+        }
+    }
+
+    fn relationships(self) -> &'static [Relationship] {
+        match self {
+            Self::Tasks => TASK_RELATIONSHIPS,
+            Self::Projects => PROJECT_RELATIONSHIPS,
+        }
+    }
+
+    /// The resource type a path serves, for the task and project routes themselves; their
+    /// sub-resources like comments or columns keep the plain representation.
+    fn of_path(path: &str) -> Option<Self> {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match segments.as_slice() {
+            ["tasks"] | ["tasks", "archived" | "create-and-start"] => Some(Self::Tasks),
+            ["tasks", "stream", ..] | ["projects", "stream" | "import", ..] => None,
+            ["tasks", _] | ["tasks", _, "archive" | "unarchive"] => Some(Self::Tasks),
+            ["projects"] | ["projects", _] => Some(Self::Projects),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the client lists the JSON:API media type, without parameters as the spec asks.
+fn accepts_json_api(request: &Request) -> bool {
+    request
+        .headers()
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media_type| media_type.trim() == JSON_API)
+}
+
+/// `fields[<type>]` query parameters, by resource type.
+fn sparse_fieldsets(request: &Request) -> HashMap<String, HashSet<String>> {
+    let mut fieldsets: HashMap<String, HashSet<String>> = HashMap::new();
+    let Some(query) = request.uri().query() else {
+        return fieldsets;
+    };
+    for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+        let Some(resource_type) = key
+            .strip_prefix("fields[")
+            .and_then(|rest| rest.strip_suffix(']'))
+        else {
+            continue;
+        };
+        fieldsets
+            .entry(resource_type.to_string())
+            .or_default()
+            .extend(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|field| !field.is_empty())
+                    .map(str::to_string),
+            );
+    }
+    fieldsets
+}
+
+fn resource(
+    resource_type: ResourceType,
+    object: Map<String, Value>,
+    fields: Option<&HashSet<String>>,
+) -> Value {
+    let mut attributes = object;
+    let id = attributes
+        .remove("id")
+        .and_then(|id| id.as_str().map(str::to_string))
+        .unwrap_or_default();
+    let wanted = |name: &str| fields.is_none_or(|fields| fields.contains(name));
+
+    let mut relationships = Map::new();
+    for (key, name, related_type, related_path) in resource_type.relationships() {
+        let related_id = attributes.remove(*key).unwrap_or(Value::Null);
+        if !wanted(name) {
+            continue;
+        }
+        let data = match related_id.as_str() {
+            Some(related_id) => json!({ "type": related_type, "id": related_id }),
+            None => Value::Null,
+        };
+        let mut relationship = json!({ "data": data });
+        if let Some(related_id) = related_id.as_str()
+            && !related_path.is_empty()
+        {
+            relationship["links"] = json!({ "related": format!("{related_path}/{related_id}") });
+        }
+        relationships.insert(name.to_string(), relationship);
+    }
+    // A project's tasks aren't a key on the project, so they only get a link
+    if resource_type == ResourceType::Projects && wanted("tasks") {
+        relationships.insert(
+            "tasks".to_string(),
+            json!({ "links": { "related": format!("/api/tasks?project_id={id}") } }),
+        );
+    }
+    attributes.retain(|name, _| wanted(name));
+
+    let mut resource = json!({
+        "type": resource_type.name(),
+        "id": id,
+        "attributes": attributes,
+        "links": { "self": format!("/api/{}/{id}", resource_type.name()) },
+    });
+    if !relationships.is_empty() {
+        resource["relationships"] = Value::Object(relationships);
+    }
+    resource
+}
+
+/// The document for a successful envelope's `data`; `None` when it isn't resources.
+fn document(
+    resource_type: ResourceType,
+    data: Value,
+    fields: Option<&HashSet<String>>,
+) -> Option<Value> {
+    let data = match data {
+        Value::Object(object) => resource(resource_type, object, fields),
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| match item {
+                    Value::Object(object) => Some(resource(resource_type, object, fields)),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?,
+        ),
+        _ => return None,
+    };
+    Some(json!({ "data": data, "jsonapi": { "version": "1.1" } }))
+}
+
+fn error_document(status: StatusCode, envelope: &Value) -> Value {
+    let mut error = json!({
+        "status": status.as_str(),
+        "title": status.canonical_reason().unwrap_or("Error"),
+    });
+    if let Some(message) = envelope.get("message").and_then(Value::as_str) {
+        error["detail"] = Value::String(message.to_string());
+    }
+    json!({ "errors": [error], "jsonapi": { "version": "1.1" } })
+}
+
+/// `self` and, for paginated listings, `next` links, built from the request path as the
+/// client sent it.
+fn document_links(path_and_query: &str, next_cursor: Option<&str>) -> Value {
+    let mut links = json!({ "self": path_and_query });
+    if let Some(cursor) = next_cursor {
+        let (path, query) = path_and_query
+            .split_once('?')
+            .unwrap_or((path_and_query, ""));
+        let mut next = url::form_urlencoded::Serializer::new(String::new());
+        for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+            if key != "cursor" && key != "offset" {
+                next.append_pair(&key, &value);
+            }
+        }
+        next.append_pair("cursor", cursor);
+        links["next"] = Value::String(format!("{path}?{}", next.finish()));
+    }
+    links
+}
+
+/// Rewrite task and project responses as JSON:API documents for clients that accept them.
+/// Other requests, and anything that isn't a JSON envelope, pass untouched.
+pub async fn json_api(request: Request, next: Next) -> Response {
+    let Some(resource_type) = ResourceType::of_path(request.uri().path()) else {
+        return next.run(request).await;
+    };
+    if !accepts_json_api(&request) {
+        return next.run(request).await;
+    }
+    let fieldsets = sparse_fieldsets(&request);
+    // Links point at the API as mounted, not at the path inside the `/api` nest
+    let uri = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map_or(request.uri(), |OriginalUri(uri)| uri);
+    let path_and_query = uri
+        .path_and_query()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_default();
+    let response = next.run(request).await;
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    if !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        return Response::from_parts(parts, Body::empty());
+    };
+    let Ok(mut envelope) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let document = if parts.status.is_success() {
+        let Some(data) = envelope.get_mut("data").map(Value::take) else {
+            return Response::from_parts(parts, Body::from(bytes));
+        };
+        let fields = fieldsets.get(resource_type.name());
+        match document(resource_type, data, fields) {
+            Some(mut document) => {
+                let next_cursor = parts
+                    .headers
+                    .get(NEXT_CURSOR)
+                    .and_then(|value| value.to_str().ok());
+                document["links"] = document_links(&path_and_query, next_cursor);
+                document
+            }
+            None => return Response::from_parts(parts, Body::from(bytes)),
+        }
+    } else {
+        error_document(parts.status, &envelope)
+    };
+    match serde_json::to_vec(&document) {
+        Ok(document) => {
+            parts.headers.remove(CONTENT_LENGTH);
+            parts
+                .headers
+                .insert(CONTENT_TYPE, HeaderValue::from_static(JSON_API));
+            parts
+                .headers
+                .append(VARY, HeaderValue::from_static("accept"));
+            Response::from_parts(parts, Body::from(document))
+        }
+        Err(_) => Response::from_parts(parts, Body::from(bytes)),
+    }
+}
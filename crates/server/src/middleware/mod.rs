@@ -1,3 +1,13 @@
+pub mod audit;
+pub mod auth;
+pub mod cors;
+pub mod fields;
+pub mod idempotency;
+pub mod json_api;
+pub mod metrics;
 pub mod model_loaders;
+pub mod rate_limit;
+pub mod rbac;
+pub mod telemetry;
 
 pub use model_loaders::*;
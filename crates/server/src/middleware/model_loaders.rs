@@ -5,13 +5,13 @@ use axum::{
     response::Response,
 };
 use db::models::{
-    execution_process::ExecutionProcess, project::Project, session::Session, tag::Tag, task::Task,
-    workspace::Workspace,
+    execution_process::ExecutionProcess, integration::Integration, project::Project,
+    session::Session, tag::Tag, task::Task, user::User, workspace::Workspace,
 };
 use deployment::Deployment;
 use uuid::Uuid;
 
-use crate::DeploymentImpl;
+use crate::{DeploymentImpl, middleware::auth::allows_project};
 
 pub async fn load_project_middleware(
     State(deployment): State<DeploymentImpl>,
@@ -32,6 +32,10 @@ pub async fn load_project_middleware(
         }
     };
 
+    if !allows_project(&request, project.id) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
     // Insert the project as an extension
     let mut request = request;
     request.extensions_mut().insert(project);
@@ -48,7 +52,12 @@ pub async fn load_task_middleware(
 ) -> Result<Response, StatusCode> {
     // Load the task and validate it belongs to the project
     let task = match Task::find_by_id(&deployment.db().pool, task_id).await {
-        Ok(Some(task)) => task,
+        Ok(Some(task)) if task.deleted_at.is_none() => task,
+        Ok(Some(_)) => {
+            // Trashed tasks are only reachable through the project's trash
+            tracing::warn!("Task {} is in the trash", task_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
         Ok(None) => {
             tracing::warn!("Task {} not found", task_id);
             return Err(StatusCode::NOT_FOUND);
@@ -59,6 +68,10 @@ pub async fn load_task_middleware(
         }
     };
 
+    if !allows_project(&request, task.project_id) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
     // Insert both models as extensions
     let mut request = request;
     request.extensions_mut().insert(task);
@@ -169,3 +182,56 @@ pub async fn load_session_middleware(
     request.extensions_mut().insert(session);
     Ok(next.run(request).await)
 }
+
+pub async fn load_integration_middleware(
+    State(deployment): State<DeploymentImpl>,
+    Path(integration_id): Path<Uuid>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let integration = match Integration::find_by_id(&deployment.db().pool, integration_id).await {
+        Ok(Some(integration)) => integration,
+        Ok(None) => {
+            tracing::warn!("Integration {} not found", integration_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch integration {}: {}", integration_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    if !allows_project(&request, integration.project_id) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    request.extensions_mut().insert(integration);
+    Ok(next.run(request).await)
+}
+
+// Middleware that loads and injects User based on the user_id path parameter
+pub async fn load_user_middleware(
+    State(deployment): State<DeploymentImpl>,
+    Path(user_id): Path<Uuid>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    // Load the user from the database
+    let user = match User::find_by_id(&deployment.db().pool, user_id).await {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            tracing::warn!("User {} not found", user_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch user {}: {}", user_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    // Insert the user as an extension
+    let mut request = request;
+    request.extensions_mut().insert(user);
+
+    // Continue with the next middleware/handler
+    Ok(next.run(request).await)
+}
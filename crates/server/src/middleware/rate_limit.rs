@@ -0,0 +1,239 @@
+//! Per-client request rate limits, so a runaway script or a retry storm can't swamp the
+//! SQLite-backed server. Each valid API key, or each client address for requests without
+//! one, gets a token bucket: it may burst up to `VK_RATE_LIMIT_BURST` requests and is then
+//! refilled at `VK_RATE_LIMIT` requests a minute.
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{LazyLock, Mutex},
+    time::Instant,
+};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{StatusCode, header::RETRY_AFTER},
+    middleware::Next,
+    response::{IntoResponse, Json as ResponseJson, Response},
+};
+use chrono::Utc;
+use db::models::api_key::ApiKey;
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{
+    DeploymentImpl,
+    middleware::auth::{bearer_api_key, hash_token},
+};
+
+/// The most clients tracked at once. Idle clients are dropped first, then the one heard
+/// from longest ago.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Off unless `VK_RATE_LIMIT` is set.
+static LIMITER: LazyLock<Option<RateLimiter>> =
+    LazyLock::new(|| RateLimitConfig::from_env().map(RateLimiter::new));
+
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitConfig {
+    /// Requests a minute once a client has used up its burst
+    pub per_minute: u32,
+    /// Requests a client can make at once
+    pub burst: u32,
+}
+
+impl RateLimitConfig {
+    /// `None` unless `VK_RATE_LIMIT` is a positive number. The burst defaults to a minute's
+    /// worth of requests.
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| value.trim().parse::<u32>().ok())
+                .filter(|value| *value > 0)
+        };
+        let per_minute = var("VK_RATE_LIMIT")?;
+        Some(Self {
+            per_minute,
+            burst: var("VK_RATE_LIMIT_BURST").unwrap_or(per_minute),
+        })
+    }
+
+    fn refill_per_second(&self) -> f64 {
+        f64::from(self.per_minute) / 60.0
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    max_clients: usize,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            max_clients: MAX_TRACKED_CLIENTS,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take a request from the client's bucket, or return how many seconds until the
+    /// client may try again.
+    pub fn check(&self, client: &str, now: Instant) -> Result<(), u64> {
+        let rate = self.config.refill_per_second();
+        let burst = f64::from(self.config.burst);
+        let refilled = |bucket: &Bucket| {
+            let elapsed = now.saturating_duration_since(bucket.updated_at);
+            (bucket.tokens + elapsed.as_secs_f64() * rate).min(burst)
+        };
+
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= self.max_clients && !buckets.contains_key(client) {
+            // A full bucket is no different from a new one
+            buckets.retain(|_, bucket| refilled(bucket) < burst);
+            if buckets.len() >= self.max_clients
+                && let Some(stalest) = buckets
+                    .iter()
+                    .min_by_key(|(_, bucket)| bucket.updated_at)
+                    .map(|(client, _)| client.clone())
+            {
+                buckets.remove(&stalest);
+            }
+        }
+        let bucket = buckets.entry(client.to_string()).or_insert(Bucket {
+            tokens: burst,
+            updated_at: now,
+        });
+        bucket.tokens = refilled(bucket);
+        bucket.updated_at = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(((1.0 - bucket.tokens) / rate).ceil() as u64)
+        }
+    }
+}
+
+/// Who a request counts against: its API key, or the address it came from. Only active keys
+/// get a bucket of their own, so made-up keys can't be used to dodge the limit.
+async fn client_key(deployment: &DeploymentImpl, request: &Request) -> String {
+    if let Some(token) = bearer_api_key(request.headers()) {
+        match ApiKey::find_by_hash(&deployment.db().pool, &hash_token(token)).await {
+            Ok(Some(api_key)) if api_key.is_active(Utc::now()) => {
+                return format!("key:{}", api_key.id);
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Failed to look up API key for rate limiting: {e}"),
+        }
+    }
+    match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => format!("ip:{}", addr.ip()),
+        None => "ip:unknown".to_string(),
+    }
+}
+
+/// Refuse requests over the client's limit with `429 Too Many Requests` and a
+/// `Retry-After` header. Runs before authentication, so floods of bad keys are limited too.
+pub async fn rate_limit(
+    State(deployment): State<DeploymentImpl>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(limiter) = LIMITER.as_ref() else {
+        return next.run(request).await;
+    };
+    if request.uri().path() == "/health" {
+        return next.run(request).await;
+    }
+    let client = client_key(&deployment, &request).await;
+    match limiter.check(&client, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            tracing::debug!("Rate limited {client} for {retry_after}s");
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(RETRY_AFTER, retry_after.to_string())],
+                ResponseJson(ApiResponse::<()>::error(
+                    "Too many requests; retry after the Retry-After delay",
+                )),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn limiter(per_minute: u32, burst: u32) -> RateLimiter {
+        RateLimiter::new(RateLimitConfig { per_minute, burst })
+    }
+
+    #[test]
+    fn test_burst_then_refused() {
+        let limiter = limiter(60, 3);
+        let now = Instant::now();
+        for _ in 0..3 {
+            assert_eq!(limiter.check("a", now), Ok(()));
+        }
+        assert_eq!(limiter.check("a", now), Err(1));
+    }
+
+    #[test]
+    fn test_refills_over_time() {
+        let limiter = limiter(30, 1);
+        let now = Instant::now();
+        assert_eq!(limiter.check("a", now), Ok(()));
+        assert_eq!(limiter.check("a", now), Err(2));
+        assert_eq!(limiter.check("a", now + Duration::from_secs(1)), Err(1));
+        assert_eq!(limiter.check("a", now + Duration::from_secs(2)), Ok(()));
+    }
+
+    #[test]
+    fn test_refill_is_capped_at_burst() {
+        let limiter = limiter(60, 2);
+        let now = Instant::now();
+        assert_eq!(limiter.check("a", now), Ok(()));
+        let later = now + Duration::from_secs(3600);
+        assert_eq!(limiter.check("a", later), Ok(()));
+        assert_eq!(limiter.check("a", later), Ok(()));
+        assert_eq!(limiter.check("a", later), Err(1));
+    }
+
+    #[test]
+    fn test_clients_have_separate_buckets() {
+        let limiter = limiter(60, 1);
+        let now = Instant::now();
+        assert_eq!(limiter.check("a", now), Ok(()));
+        assert_eq!(limiter.check("a", now), Err(1));
+        assert_eq!(limiter.check("b", now), Ok(()));
+    }
+
+    #[test]
+    fn test_tracked_clients_are_capped() {
+        let limiter = RateLimiter {
+            max_clients: 3,
+            ..limiter(60, 1)
+        };
+        let now = Instant::now();
+        for (i, client) in ["a", "b", "c", "d", "e"].iter().enumerate() {
+            let at = now + Duration::from_millis(i as u64);
+            assert_eq!(limiter.check(client, at), Ok(()));
+        }
+        let buckets = limiter.buckets.lock().unwrap();
+        assert_eq!(buckets.len(), 3);
+        // The clients heard from longest ago make way
+        assert!(!buckets.contains_key("a") && !buckets.contains_key("b"));
+    }
+}
@@ -0,0 +1,661 @@
+use std::collections::HashSet;
+
+use axum::{
+    extract::{Request, State},
+    http::Method,
+    middleware::Next,
+    response::Response,
+};
+use db::models::{
+    api_key::{ApiKey, ApiKeyScope},
+    execution_process::ExecutionProcess,
+    integration::Integration,
+    project::Project,
+    project_member::{ProjectMember, ProjectRole},
+    session::Session,
+    sync_conflict::SyncConflict,
+    task::Task,
+    team::{Team, TeamMember, TeamRole},
+    webhook::Webhook,
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::auth::{AuthUser, query_project_id},
+};
+
+/// Sections of a project only its admins may change.
+const ADMIN_SECTIONS: &[&str] = &[
+    "settings",
+    "columns",
+    "wip-limits",
+    "custom-fields",
+    "members",
+    "share-links",
+    "repositories",
+    "link",
+];
+
+/// The role a request needs on the project it touches: viewers read, members edit tasks,
+/// admins manage integrations and project settings.
+pub fn required_role(method: &Method, path: &str) -> ProjectRole {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    // Exporting is for backing up and moving the whole project, which is up to its admins
+    if let ["projects", _, "export"] = segments.as_slice() {
+        return ProjectRole::Admin;
+    }
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return ProjectRole::Viewer;
+    }
+    match segments.as_slice() {
+        ["integrations", ..] | ["conflicts", ..] | ["webhooks", ..] => ProjectRole::Admin,
+        // Editing or deleting the project itself
+        ["projects", _] => ProjectRole::Admin,
+        ["projects", _, section, ..] if ADMIN_SECTIONS.contains(section) => ProjectRole::Admin,
+        _ => ProjectRole::Member,
+    }
+}
+
+/// The project a path points into, e.g. `/tasks/{task_id}/comments` is in the task's
+/// project. Paths that don't name a project, or name one that doesn't exist, give `None`
+/// and are left to the route to handle.
+pub(crate) async fn path_project_id(
+    pool: &SqlitePool,
+    path: &str,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let (resource, id) = match segments.as_slice() {
+        ["images", "task", id, ..] => ("tasks", *id),
+        [resource, id, ..] => (*resource, *id),
+        _ => return Ok(None),
+    };
+    let Ok(id) = Uuid::parse_str(id) else {
+        return Ok(None);
+    };
+    let task_id = match resource {
+        "projects" => return Ok(Some(id)),
+        "integrations" => {
+            return Ok(Integration::find_by_id(pool, id)
+                .await?
+                .map(|integration| integration.project_id));
+        }
+        "webhooks" => {
+            return Ok(Webhook::find_by_id(pool, id)
+                .await?
+                .map(|webhook| webhook.project_id));
+        }
+        "conflicts" => {
+            let Some(conflict) = SyncConflict::find_by_id(pool, id).await? else {
+                return Ok(None);
+            };
+            conflict.task_id
+        }
+        "tasks" => id,
+        "task-attempts" => match Workspace::find_by_id(pool, id).await? {
+            Some(workspace) => workspace.task_id,
+            None => return Ok(None),
+        },
+        "sessions" => match workspace_of_session(pool, id).await? {
+            Some(workspace) => workspace.task_id,
+            None => return Ok(None),
+        },
+        "execution-processes" => {
+            let Some(process) = ExecutionProcess::find_by_id(pool, id).await? else {
+                return Ok(None);
+            };
+            match workspace_of_session(pool, process.session_id).await? {
+                Some(workspace) => workspace.task_id,
+                None => return Ok(None),
+            }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Task::find_by_id(pool, task_id)
+        .await?
+        .map(|task| task.project_id))
+}
+
+async fn workspace_of_session(
+    pool: &SqlitePool,
+    session_id: Uuid,
+) -> Result<Option<Workspace>, sqlx::Error> {
+    match Session::find_by_id(pool, session_id).await? {
+        Some(session) => Workspace::find_by_id(pool, session.workspace_id).await,
+        None => Ok(None),
+    }
+}
+
+/// Whether the API key, if any, may reach the project: a key limited to a project reaches
+/// only that project, and one limited to a team only the team's projects.
+pub async fn key_reaches_project(
+    pool: &SqlitePool,
+    api_key: Option<&ApiKey>,
+    project_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let Some(api_key) = api_key else {
+        return Ok(true);
+    };
+    if api_key
+        .project_id
+        .is_some_and(|allowed| allowed != project_id)
+    {
+        return Ok(false);
+    }
+    match api_key.team_id {
+        Some(team_id) => Ok(Project::find_by_id(pool, project_id)
+            .await?
+            .is_some_and(|project| project.team_id == Some(team_id))),
+        None => Ok(true),
+    }
+}
+
+/// The most a key acting as no user may do on a project: admin keys administer it, keys
+/// that write edit its tasks, and read-only keys view it.
+fn userless_key_role(api_key: &ApiKey) -> ProjectRole {
+    if api_key.allows(ApiKeyScope::Admin) {
+        ProjectRole::Admin
+    } else if api_key.allows(ApiKeyScope::Write) {
+        ProjectRole::Member
+    } else {
+        ProjectRole::Viewer
+    }
+}
+
+/// Refuse unless the API key, if any, reaches the project and the user has at least
+/// `required` on it. A key acting as no user is held to the role its scopes imply; only
+/// local requests, with neither a user nor a key, are not limited.
+pub async fn require_role(
+    deployment: &DeploymentImpl,
+    user: Option<&AuthUser>,
+    api_key: Option<&ApiKey>,
+    project_id: Uuid,
+    required: ProjectRole,
+) -> Result<(), ApiError> {
+    check_role(&deployment.db().pool, user, api_key, project_id, required).await
+}
+
+async fn check_role(
+    pool: &SqlitePool,
+    user: Option<&AuthUser>,
+    api_key: Option<&ApiKey>,
+    project_id: Uuid,
+    required: ProjectRole,
+) -> Result<(), ApiError> {
+    if !key_reaches_project(pool, api_key, project_id).await? {
+        return Err(ApiError::Forbidden(
+            "API key is limited to another project".to_string(),
+        ));
+    }
+    let Some(AuthUser(user)) = user else {
+        return match api_key.map(|api_key| (api_key, userless_key_role(api_key))) {
+            Some((api_key, role)) if !role.grants(required) => Err(ApiError::Forbidden(format!(
+                "API key '{}' acts as no user and only has {role} access; this needs {required}",
+                api_key.name
+            ))),
+            _ => Ok(()),
+        };
+    };
+    match ProjectMember::effective_role(pool, project_id, user.id).await? {
+        Some(role) if role.grants(required) => Ok(()),
+        Some(role) => Err(ApiError::Forbidden(format!(
+            "{} is a {role} on this project; this needs {required}",
+            user.name
+        ))),
+        None => Err(ApiError::Forbidden(format!(
+            "{} is not a member of this project",
+            user.name
+        ))),
+    }
+}
+
+/// Whether the caller administers the whole install: a local request, or an API key that
+/// acts as no user, has the admin scope and isn't limited to a project or team.
+pub fn is_administrator(user: Option<&AuthUser>, api_key: Option<&ApiKey>) -> bool {
+    user.is_none()
+        && api_key.is_none_or(|api_key| {
+            api_key.project_id.is_none()
+                && api_key.team_id.is_none()
+                && api_key.allows(ApiKeyScope::Admin)
+        })
+}
+
+/// Refuse unless the request comes from the user `user_id`, or is local. What belongs to
+/// one user, such as their notifications, isn't shown to anyone else.
+pub fn require_self(
+    user: Option<&AuthUser>,
+    api_key: Option<&ApiKey>,
+    user_id: Uuid,
+) -> Result<(), ApiError> {
+    match (user, api_key) {
+        (Some(AuthUser(user)), _) if user.id == user_id => Ok(()),
+        (Some(AuthUser(user)), _) => Err(ApiError::Forbidden(format!(
+            "{} can only reach their own account",
+            user.name
+        ))),
+        (None, None) => Ok(()),
+        (None, Some(api_key)) => Err(ApiError::Forbidden(format!(
+            "API key '{}' acts as no user",
+            api_key.name
+        ))),
+    }
+}
+
+/// Refuse unless the request comes from the user `user_id` or an administrator.
+pub fn require_self_or_administrator(
+    user: Option<&AuthUser>,
+    api_key: Option<&ApiKey>,
+    user_id: Uuid,
+) -> Result<(), ApiError> {
+    if is_administrator(user, api_key) {
+        return Ok(());
+    }
+    require_self(user, api_key, user_id)
+}
+
+/// Refuse unless the user has at least `required` in the team and the API key, if any,
+/// isn't limited to a project or another team. A key acting as no user needs the admin
+/// scope to administer the team; local requests are not limited.
+pub async fn require_team_role(
+    deployment: &DeploymentImpl,
+    user: Option<&AuthUser>,
+    api_key: Option<&ApiKey>,
+    team_id: Uuid,
+    required: TeamRole,
+) -> Result<(), ApiError> {
+    if let Some(api_key) = api_key
+        && (api_key.project_id.is_some()
+            || api_key.team_id.is_some_and(|allowed| allowed != team_id))
+    {
+        return Err(ApiError::Forbidden(
+            "API key is limited to another team".to_string(),
+        ));
+    }
+    let Some(AuthUser(user)) = user else {
+        return match api_key {
+            Some(api_key) if required == TeamRole::Admin && !api_key.allows(ApiKeyScope::Admin) => {
+                Err(ApiError::Forbidden(format!(
+                    "API key '{}' acts as no user and lacks the admin scope to administer the team",
+                    api_key.name
+                )))
+            }
+            _ => Ok(()),
+        };
+    };
+    match TeamMember::role(&deployment.db().pool, team_id, user.id).await? {
+        Some(role) if role.grants(required) => Ok(()),
+        Some(role) => Err(ApiError::Forbidden(format!(
+            "{} is a {role} of this team; this needs {required}",
+            user.name
+        ))),
+        None => Err(ApiError::Forbidden(format!(
+            "{} is not a member of this team",
+            user.name
+        ))),
+    }
+}
+
+/// The projects the user and API key may see, for routes listing across projects; `None`
+/// when neither hides anything.
+pub async fn visible_projects(
+    deployment: &DeploymentImpl,
+    user: Option<&AuthUser>,
+    api_key: Option<&ApiKey>,
+) -> Result<Option<HashSet<Uuid>>, ApiError> {
+    find_visible_projects(&deployment.db().pool, user, api_key).await
+}
+
+async fn find_visible_projects(
+    pool: &SqlitePool,
+    user: Option<&AuthUser>,
+    api_key: Option<&ApiKey>,
+) -> Result<Option<HashSet<Uuid>>, ApiError> {
+    let mut visible: Option<HashSet<Uuid>> = match user {
+        Some(AuthUser(user)) => Some(
+            ProjectMember::visible_project_ids(pool, user.id)
+                .await?
+                .into_iter()
+                .collect(),
+        ),
+        None => None,
+    };
+    let allowed: Option<HashSet<Uuid>> = match api_key.map(|key| (key.project_id, key.team_id)) {
+        Some((Some(project_id), _)) => Some(HashSet::from([project_id])),
+        Some((None, Some(team_id))) => Some(
+            Team::project_ids(pool, team_id)
+                .await?
+                .into_iter()
+                .collect(),
+        ),
+        _ => None,
+    };
+    if let Some(allowed) = allowed {
+        visible = Some(match visible {
+            Some(visible) => visible.intersection(&allowed).copied().collect(),
+            None => allowed,
+        });
+    }
+    Ok(visible)
+}
+
+/// Check the user's role on the project a request touches, found from its path or its
+/// `project_id` query parameter. Routes that take the project from the request body check
+/// it themselves with [`require_role`].
+pub async fn authorize(
+    State(deployment): State<DeploymentImpl>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let path = request.uri().path();
+    let pool = &deployment.db().pool;
+    let project_ids = [
+        path_project_id(pool, path).await?,
+        query_project_id(&request),
+    ];
+    let required = required_role(request.method(), path);
+    let user = request.extensions().get::<AuthUser>();
+    let api_key = request.extensions().get::<ApiKey>();
+    for project_id in project_ids.into_iter().flatten() {
+        require_role(&deployment, user, api_key, project_id, required).await?;
+    }
+    Ok(next.run(request).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use db::models::{
+        project::CreateProject,
+        session::CreateSession,
+        task::CreateTask,
+        team::CreateTeam,
+        user::{CreateUser, User},
+        workspace::CreateWorkspace,
+    };
+    use sqlx::{sqlite::SqlitePoolOptions, types::Json};
+
+    use super::*;
+
+    async fn pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        db::migrations::MIGRATOR.run(&pool).await.unwrap();
+        pool
+    }
+
+    async fn project(pool: &SqlitePool, team_id: Option<Uuid>) -> Uuid {
+        let data = CreateProject {
+            name: "Board".to_string(),
+            repositories: Vec::new(),
+            team_id,
+        };
+        Project::create(pool, &data, Uuid::new_v4())
+            .await
+            .unwrap()
+            .id
+    }
+
+    async fn user(pool: &SqlitePool, name: &str) -> AuthUser {
+        let data = CreateUser {
+            name: name.to_string(),
+            email: None,
+        };
+        AuthUser(User::create(pool, &data).await.unwrap())
+    }
+
+    fn key(scope: ApiKeyScope, project_id: Option<Uuid>, team_id: Option<Uuid>) -> ApiKey {
+        ApiKey {
+            id: Uuid::new_v4(),
+            name: "ci".to_string(),
+            prefix: "vk_test".to_string(),
+            scopes: Json(vec![scope]),
+            user_id: None,
+            project_id,
+            team_id,
+            expires_at: None,
+            last_used_at: None,
+            revoked_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_required_role_by_method_and_section() {
+        let id = Uuid::new_v4();
+        let role = |method: Method, path: String| required_role(&method, &path);
+        assert_eq!(
+            role(Method::GET, format!("/projects/{id}/tasks")),
+            ProjectRole::Viewer
+        );
+        assert_eq!(
+            role(Method::GET, format!("/integrations/{id}")),
+            ProjectRole::Viewer
+        );
+        assert_eq!(
+            role(Method::POST, format!("/tasks/{id}/comments")),
+            ProjectRole::Member
+        );
+        assert_eq!(
+            role(Method::PUT, format!("/tasks/{id}")),
+            ProjectRole::Member
+        );
+        assert_eq!(
+            role(Method::POST, format!("/tasks/from-template/{id}")),
+            ProjectRole::Member
+        );
+        assert_eq!(
+            role(Method::PUT, format!("/projects/{id}")),
+            ProjectRole::Admin
+        );
+        assert_eq!(
+            role(Method::DELETE, format!("/projects/{id}")),
+            ProjectRole::Admin
+        );
+        assert_eq!(
+            role(Method::PUT, format!("/projects/{id}/settings")),
+            ProjectRole::Admin
+        );
+        assert_eq!(
+            role(Method::POST, format!("/projects/{id}/members/{id}")),
+            ProjectRole::Admin
+        );
+        assert_eq!(
+            role(Method::POST, format!("/integrations/{id}/sync")),
+            ProjectRole::Admin
+        );
+        assert_eq!(
+            role(Method::DELETE, format!("/webhooks/{id}")),
+            ProjectRole::Admin
+        );
+        // Exporting takes an admin even though it only reads
+        assert_eq!(
+            role(Method::GET, format!("/projects/{id}/export")),
+            ProjectRole::Admin
+        );
+    }
+
+    #[tokio::test]
+    async fn test_path_project_id_follows_resources_to_their_project() {
+        let pool = pool().await;
+        let project_id = project(&pool, None).await;
+        let data = CreateTask::from_title_description(project_id, "Task".to_string(), None);
+        let task = Task::create(&pool, &data, Uuid::new_v4()).await.unwrap();
+        let workspace = Workspace::create(
+            &pool,
+            &CreateWorkspace {
+                branch: "vk/task".to_string(),
+                agent_working_dir: None,
+            },
+            Uuid::new_v4(),
+            task.id,
+        )
+        .await
+        .unwrap();
+        let session = Session::create(
+            &pool,
+            &CreateSession { executor: None },
+            Uuid::new_v4(),
+            workspace.id,
+        )
+        .await
+        .unwrap();
+        let process_id = Uuid::new_v4();
+        sqlx::query("INSERT INTO execution_processes (id, session_id) VALUES ($1, $2)")
+            .bind(process_id)
+            .bind(session.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let found = |path: String| {
+            let pool = pool.clone();
+            async move { path_project_id(&pool, &path).await.unwrap() }
+        };
+        assert_eq!(
+            found(format!("/projects/{project_id}/settings")).await,
+            Some(project_id)
+        );
+        assert_eq!(
+            found(format!("/tasks/{}/comments", task.id)).await,
+            Some(project_id)
+        );
+        assert_eq!(
+            found(format!("/images/task/{}/upload", task.id)).await,
+            Some(project_id)
+        );
+        assert_eq!(
+            found(format!("/task-attempts/{}", workspace.id)).await,
+            Some(project_id)
+        );
+        assert_eq!(
+            found(format!("/sessions/{}/queue", session.id)).await,
+            Some(project_id)
+        );
+        assert_eq!(
+            found(format!("/execution-processes/{process_id}/stop")).await,
+            Some(project_id)
+        );
+        // Left to the routes, which check the project they load themselves
+        assert_eq!(
+            found(format!("/tasks/from-template/{}", Uuid::new_v4())).await,
+            None
+        );
+        assert_eq!(found("/tasks/quick-add".to_string()).await, None);
+        assert_eq!(found(format!("/tasks/{}", Uuid::new_v4())).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_require_role_with_members_and_scoped_keys() {
+        let pool = pool().await;
+        let open = project(&pool, None).await;
+        let team = Team::create(
+            &pool,
+            &CreateTeam {
+                name: "Core".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let in_team = project(&pool, Some(team.id)).await;
+        let viewer = user(&pool, "Vera").await;
+        ProjectMember::set(&pool, open, viewer.0.id, ProjectRole::Viewer)
+            .await
+            .unwrap();
+        let check = |user: Option<&AuthUser>, key: Option<&ApiKey>, project_id, role| {
+            let pool = pool.clone();
+            let (user, key) = (user.cloned(), key.cloned());
+            async move {
+                check_role(&pool, user.as_ref(), key.as_ref(), project_id, role)
+                    .await
+                    .is_ok()
+            }
+        };
+
+        // Local requests are not limited
+        assert!(check(None, None, in_team, ProjectRole::Admin).await);
+        assert!(check(Some(&viewer), None, open, ProjectRole::Viewer).await);
+        assert!(!check(Some(&viewer), None, open, ProjectRole::Member).await);
+        // Team projects are closed to non-members
+        assert!(!check(Some(&viewer), None, in_team, ProjectRole::Viewer).await);
+
+        let project_key = key(ApiKeyScope::Write, Some(open), None);
+        assert!(check(None, Some(&project_key), open, ProjectRole::Member).await);
+        assert!(!check(None, Some(&project_key), open, ProjectRole::Admin).await);
+        assert!(!check(None, Some(&project_key), in_team, ProjectRole::Viewer).await);
+        // A key never widens what its user may do
+        assert!(!check(Some(&viewer), Some(&project_key), open, ProjectRole::Member).await);
+
+        let team_key = key(ApiKeyScope::Read, None, Some(team.id));
+        assert!(check(None, Some(&team_key), in_team, ProjectRole::Viewer).await);
+        assert!(!check(None, Some(&team_key), in_team, ProjectRole::Member).await);
+        assert!(!check(None, Some(&team_key), open, ProjectRole::Viewer).await);
+    }
+
+    #[tokio::test]
+    async fn test_visible_projects_with_members_and_scoped_keys() {
+        let pool = pool().await;
+        let open = project(&pool, None).await;
+        let team = Team::create(
+            &pool,
+            &CreateTeam {
+                name: "Core".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let in_team = project(&pool, Some(team.id)).await;
+        let viewer = user(&pool, "Vera").await;
+        let visible = |user: Option<&AuthUser>, key: Option<&ApiKey>| {
+            let pool = pool.clone();
+            let (user, key) = (user.cloned(), key.cloned());
+            async move {
+                find_visible_projects(&pool, user.as_ref(), key.as_ref())
+                    .await
+                    .unwrap()
+            }
+        };
+
+        assert_eq!(visible(None, None).await, None);
+        assert_eq!(
+            visible(Some(&viewer), None).await,
+            Some(HashSet::from([open]))
+        );
+        let project_key = key(ApiKeyScope::Read, Some(in_team), None);
+        assert_eq!(
+            visible(None, Some(&project_key)).await,
+            Some(HashSet::from([in_team]))
+        );
+        let team_key = key(ApiKeyScope::Read, None, Some(team.id));
+        assert_eq!(
+            visible(None, Some(&team_key)).await,
+            Some(HashSet::from([in_team]))
+        );
+        // The user can't see the team's projects, so the key shows them nothing
+        assert_eq!(
+            visible(Some(&viewer), Some(&team_key)).await,
+            Some(HashSet::new())
+        );
+    }
+
+    #[test]
+    fn test_only_unlimited_admin_keys_administer() {
+        let admin = key(ApiKeyScope::Admin, None, None);
+        assert!(is_administrator(None, None));
+        assert!(is_administrator(None, Some(&admin)));
+        assert!(!is_administrator(
+            None,
+            Some(&key(ApiKeyScope::Write, None, None))
+        ));
+        assert!(!is_administrator(
+            None,
+            Some(&key(ApiKeyScope::Admin, Some(Uuid::new_v4()), None))
+        ));
+    }
+}
@@ -0,0 +1,61 @@
+//! A server span per API request for OpenTelemetry, continuing the caller's trace when the
+//! request carries a `traceparent` header. Named by method and route template, like the
+//! Prometheus series.
+
+use axum::{
+    extract::{MatchedPath, Request},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use opentelemetry::{global, propagation::Extractor};
+use tracing::{Instrument, field::Empty};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::telemetry;
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
+
+/// Outermost layer, next to [`track_requests`](super::metrics::track_requests), so the span
+/// covers authentication and rate limiting. Does nothing unless spans are exported.
+pub async fn trace_requests(request: Request, next: Next) -> Response {
+    if !telemetry::enabled() {
+        return next.run(request).await;
+    }
+    let parent = global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(request.headers()))
+    });
+    let method = request.method().clone();
+    let span = tracing::info_span!(
+        "http.request",
+        otel.name = %method,
+        otel.kind = "server",
+        otel.status_code = Empty,
+        http.request.method = %method,
+        url.path = %request.uri().path(),
+        http.route = Empty,
+        http.response.status_code = Empty,
+    );
+    span.set_parent(parent);
+
+    let response = next.run(request).instrument(span.clone()).await;
+    if let Some(route) = response.extensions().get::<MatchedPath>() {
+        span.record("http.route", route.as_str());
+        span.record("otel.name", format!("{method} {}", route.as_str()));
+    }
+    span.record("http.response.status_code", response.status().as_u16());
+    if response.status().is_server_error() {
+        span.record("otel.status_code", "ERROR");
+    }
+    response
+}
@@ -0,0 +1,327 @@
+use axum::Router;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::routes;
+
+/// The REST API, as served under `/api`.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Vibe Kanban API",
+        description = "Every JSON endpoint answers with an `ApiResponse` envelope. Failures \
+                       use the same envelope with `success: false` and a `message`, and a 4xx \
+                       or 5xx status."
+    ),
+    paths(
+        routes::api_keys::get_api_keys,
+        routes::api_keys::create_api_key,
+        routes::api_keys::revoke_api_key,
+        routes::approvals::respond_to_approval,
+        routes::audit::get_audit_log,
+        routes::board::get_board,
+        routes::board::get_swimlane,
+        routes::board::set_swimlane,
+        routes::board::clear_swimlane,
+        routes::board_ws::board_ws,
+        routes::config::get_user_system_info,
+        routes::config::update_config,
+        routes::config::get_sound,
+        routes::config::get_mcp_servers,
+        routes::config::update_mcp_servers,
+        routes::config::get_profiles,
+        routes::config::update_profiles,
+        routes::config::check_editor_availability,
+        routes::config::check_agent_availability,
+        routes::conflicts::get_conflicts,
+        routes::conflicts::get_conflict,
+        routes::conflicts::resolve_conflict,
+        routes::containers::get_context,
+        routes::custom_fields::get_custom_fields,
+        routes::custom_fields::create_custom_field,
+        routes::custom_fields::update_custom_field,
+        routes::custom_fields::delete_custom_field,
+        routes::custom_fields::get_task_custom_field_values,
+        routes::custom_fields::set_task_custom_field_value,
+        routes::custom_fields::clear_task_custom_field_value,
+        routes::epics::get_epics,
+        routes::epics::create_epic,
+        routes::epics::update_epic,
+        routes::epics::delete_epic,
+        routes::epics::get_epic_tasks,
+        routes::epics::get_task_epic,
+        routes::epics::set_task_epic,
+        routes::epics::clear_task_epic,
+        routes::events::events,
+        routes::events::project_events,
+        routes::execution_processes::get_execution_process_by_id,
+        routes::execution_processes::stop_execution_process,
+        routes::execution_processes::get_execution_process_repo_states,
+        routes::execution_processes::stream_raw_logs_ws,
+        routes::execution_processes::stream_normalized_logs_ws,
+        routes::execution_processes::stream_execution_processes_ws,
+        routes::filesystem::list_directory,
+        routes::filesystem::list_git_repos,
+        routes::graphql::graphiql,
+        routes::graphql::graphql_handler,
+        routes::health::health_check,
+        routes::images::upload_image,
+        routes::images::serve_image,
+        routes::images::delete_image,
+        routes::images::get_task_images,
+        routes::images::get_task_image_metadata,
+        routes::images::upload_task_image,
+        routes::integrations::get_integration,
+        routes::integrations::update_integration,
+        routes::integrations::delete_integration,
+        routes::integrations::trigger_sync,
+        routes::integrations::get_sync_jobs,
+        routes::integrations::get_integration_health,
+        routes::integrations::check_integration,
+        routes::integrations::get_dead_letters,
+        routes::integrations::create_sync_plan,
+        routes::integrations::get_sync_plans,
+        routes::integrations::get_integrations,
+        routes::integrations::create_integration,
+        routes::integrations::get_integration_catalog,
+        routes::integrations::get_sync_job,
+        routes::integrations::get_sync_audit_log,
+        routes::integrations::get_sync_plan,
+        routes::integrations::get_sync_plan_report,
+        routes::integrations::apply_sync_plan,
+        routes::integrations::discard_sync_plan,
+        routes::integrations::discard_dead_letter,
+        routes::integrations::retry_dead_letter,
+        routes::labels::get_labels,
+        routes::labels::create_label,
+        routes::labels::get_task_labels,
+        routes::labels::set_task_labels,
+        routes::labels::update_label,
+        routes::labels::delete_label,
+        routes::markdown::render_markdown,
+        routes::mentions::get_mentions,
+        routes::notifications::get_notifications,
+        routes::notifications::mark_notifications_read,
+        routes::oauth::handoff_init,
+        routes::oauth::handoff_complete,
+        routes::oauth::logout,
+        routes::oauth::status,
+        routes::oauth::get_token,
+        routes::oauth::get_current_user,
+        routes::oidc::login,
+        routes::oidc::callback,
+        routes::oidc::get_session,
+        routes::oidc::logout,
+        routes::organizations::list_organizations,
+        routes::organizations::create_organization,
+        routes::organizations::get_organization,
+        routes::organizations::update_organization,
+        routes::organizations::delete_organization,
+        routes::organizations::list_organization_projects,
+        routes::organizations::create_invitation,
+        routes::organizations::list_invitations,
+        routes::organizations::revoke_invitation,
+        routes::organizations::get_invitation,
+        routes::organizations::accept_invitation,
+        routes::organizations::list_members,
+        routes::organizations::remove_member,
+        routes::organizations::update_member_role,
+        routes::project_columns::get_columns,
+        routes::project_columns::create_column,
+        routes::project_columns::reorder_columns,
+        routes::project_columns::update_column,
+        routes::project_columns::delete_column,
+        routes::project_columns::move_task,
+        routes::project_members::get_project_members,
+        routes::project_members::set_project_member,
+        routes::project_members::remove_project_member,
+        routes::project_settings::get_project_settings,
+        routes::project_settings::set_project_settings,
+        routes::projects::get_project,
+        routes::projects::update_project,
+        routes::projects::delete_project,
+        routes::projects::get_project_remote_members,
+        routes::projects::search_project_files,
+        routes::projects::open_project_in_editor,
+        routes::projects::link_project_to_existing_remote,
+        routes::projects::unlink_project,
+        routes::projects::create_and_link_remote_project,
+        routes::projects::get_project_repositories,
+        routes::projects::add_project_repository,
+        routes::projects::get_project_due_summary,
+        routes::projects::get_project_summary,
+        routes::project_archive::export_project,
+        routes::project_archive::import_project,
+        routes::projects::get_projects,
+        routes::projects::create_project,
+        routes::projects::get_project_repository,
+        routes::projects::update_project_repository,
+        routes::projects::delete_project_repository,
+        routes::projects::stream_projects_ws,
+        routes::projects::get_remote_project_by_id,
+        routes::recurrence::get_task_recurrence,
+        routes::recurrence::set_task_recurrence,
+        routes::recurrence::delete_task_recurrence,
+        routes::recurrence::get_template_recurrence,
+        routes::recurrence::set_template_recurrence,
+        routes::recurrence::delete_template_recurrence,
+        routes::repo::register_repo,
+        routes::repo::init_repo,
+        routes::repo::get_repo_branches,
+        routes::reports::get_project_burndown,
+        routes::reports::get_project_cumulative_flow,
+        routes::reports::get_project_analytics,
+        routes::scratch::list_scratch,
+        routes::scratch::get_scratch,
+        routes::scratch::create_scratch,
+        routes::scratch::update_scratch,
+        routes::scratch::delete_scratch,
+        routes::scratch::stream_scratch_ws,
+        routes::search::search_tasks,
+        routes::share_links::get_share_links,
+        routes::share_links::create_share_link,
+        routes::share_links::delete_share_link,
+        routes::share_links::get_shared_board,
+        routes::shared_tasks::assign_shared_task,
+        routes::shared_tasks::delete_shared_task,
+        routes::shared_tasks::link_shared_task_to_local,
+        routes::sprints::get_sprints,
+        routes::sprints::create_sprint,
+        routes::sprints::get_sprint_velocity,
+        routes::sprints::update_sprint,
+        routes::sprints::delete_sprint,
+        routes::sprints::start_sprint,
+        routes::sprints::close_sprint,
+        routes::sprints::get_sprint_report,
+        routes::sprints::get_sprint_tasks,
+        routes::sprints::add_sprint_tasks,
+        routes::sprints::remove_sprint_task,
+        routes::tags::get_tags,
+        routes::tags::create_tag,
+        routes::tags::update_tag,
+        routes::tags::delete_tag,
+        routes::task_attachments::get_task_attachments,
+        routes::task_attachments::upload_task_attachment,
+        routes::task_attachments::delete_task_attachment,
+        routes::task_attachments::download_task_attachment,
+        routes::task_attempts::get_task_attempts,
+        routes::task_attempts::create_task_attempt,
+        routes::task_attempts::get_task_attempt,
+        routes::task_attempts::run_agent_setup,
+        routes::task_attempts::gh_cli_setup_handler,
+        routes::task_attempts::start_dev_server,
+        routes::task_attempts::run_setup_script,
+        routes::task_attempts::run_cleanup_script,
+        routes::task_attempts::get_task_attempt_branch_status,
+        routes::task_attempts::stream_task_attempt_diff_ws,
+        routes::task_attempts::merge_task_attempt,
+        routes::task_attempts::push_task_attempt_branch,
+        routes::task_attempts::force_push_task_attempt_branch,
+        routes::task_attempts::rebase_task_attempt,
+        routes::task_attempts::abort_conflicts_task_attempt,
+        routes::task_attempts::pr::create_github_pr,
+        routes::task_attempts::pr::attach_existing_pr,
+        routes::task_attempts::pr::get_pr_comments,
+        routes::task_attempts::open_task_attempt_in_editor,
+        routes::task_attempts::get_task_attempt_children,
+        routes::task_attempts::stop_task_attempt_execution,
+        routes::task_attempts::change_target_branch,
+        routes::task_attempts::rename_branch,
+        routes::task_attempts::get_task_attempt_repos,
+        routes::task_attempts::images::get_image_metadata,
+        routes::task_attempts::images::upload_image,
+        routes::task_attempts::images::serve_image,
+        routes::task_bulk::bulk_update_tasks,
+        routes::task_batch::batch_tasks,
+        routes::task_quick_add::quick_add_task,
+        routes::task_checklist::get_checklist,
+        routes::task_checklist::create_checklist_item,
+        routes::task_checklist::reorder_checklist,
+        routes::task_checklist::update_checklist_item,
+        routes::task_checklist::delete_checklist_item,
+        routes::task_clone::clone_task,
+        routes::task_comments::get_task_comments,
+        routes::task_comments::create_task_comment,
+        routes::task_comments::delete_task_comment,
+        routes::task_events::get_task_activity,
+        routes::task_links::get_task_links,
+        routes::task_links::create_task_link,
+        routes::task_links::delete_task_link,
+        routes::task_merge::merge_tasks,
+        routes::task_move::move_task_to_project,
+        routes::task_revisions::get_task_revisions,
+        routes::task_revisions::diff_task_revisions,
+        routes::task_revisions::get_task_diff,
+        routes::task_revisions::revert_task_revision,
+        routes::task_templates::get_task_templates,
+        routes::task_templates::create_task_template,
+        routes::task_templates::update_task_template,
+        routes::task_templates::delete_task_template,
+        routes::task_templates::create_task_from_template,
+        routes::tasks::get_tasks,
+        routes::tasks::create_task,
+        routes::tasks::stream_tasks_ws,
+        routes::tasks::get_archived_tasks,
+        routes::tasks::create_task_and_start,
+        routes::tasks::get_task,
+        routes::tasks::update_task,
+        routes::tasks::delete_task,
+        routes::tasks::share_task,
+        routes::tasks::archive_task,
+        routes::tasks::unarchive_task,
+        routes::teams::get_teams,
+        routes::teams::create_team,
+        routes::teams::get_team,
+        routes::teams::delete_team,
+        routes::teams::get_team_members,
+        routes::teams::set_team_member,
+        routes::teams::remove_team_member,
+        routes::teams::add_team_project,
+        routes::teams::remove_team_project,
+        routes::time_entries::get_task_time_entries,
+        routes::time_entries::start_timer,
+        routes::time_entries::stop_timer,
+        routes::time_entries::get_time_entries,
+        routes::time_entries::get_time_report,
+        routes::trash::get_trash,
+        routes::trash::purge_task,
+        routes::trash::restore_task,
+        routes::users::assign_task,
+        routes::users::unassign_task,
+        routes::users::get_users,
+        routes::users::create_user,
+        routes::users::update_user,
+        routes::users::delete_user,
+        routes::views::get_views,
+        routes::views::create_view,
+        routes::views::update_view,
+        routes::views::delete_view,
+        routes::watchers::get_task_watchers,
+        routes::watchers::watch_task,
+        routes::watchers::unwatch_task,
+        routes::webhooks::receive_webhook,
+        routes::webhooks::get_webhooks,
+        routes::webhooks::create_webhook,
+        routes::webhooks::get_webhook,
+        routes::webhooks::delete_webhook,
+        routes::webhooks::test_webhook,
+        routes::webhooks::get_webhook_deliveries,
+        routes::wip_limits::get_wip_columns,
+        routes::wip_limits::set_wip_limits,
+        routes::sessions::get_sessions,
+        routes::sessions::create_session,
+        routes::sessions::get_session,
+        routes::sessions::follow_up,
+        routes::sessions::queue::get_queue_status,
+        routes::sessions::queue::queue_message,
+        routes::sessions::queue::cancel_queued_message,
+    )
+)]
+pub struct ApiDoc;
+
+/// The spec at `/api-docs/openapi.json`, browsable at `/swagger-ui`.
+pub fn router() -> Router {
+    SwaggerUi::new("/swagger-ui")
+        .url("/api-docs/openapi.json", ApiDoc::openapi())
+        .into()
+}
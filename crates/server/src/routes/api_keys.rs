@@ -0,0 +1,248 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{delete, get},
+};
+use chrono::Utc;
+use db::models::{
+    api_key::{ApiKey, CreateApiKey},
+    project::Project,
+    project_member::ProjectRole,
+    team::{Team, TeamRole},
+};
+use deployment::Deployment;
+use serde::Serialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{
+        auth::{AuthUser, generate_api_key, hash_token},
+        rbac::{is_administrator, require_role, require_team_role},
+    },
+};
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct CreatedApiKey {
+    pub api_key: ApiKey,
+    /// The key to send as `Authorization: Bearer <key>`. It is not stored and cannot be
+    /// shown again.
+    pub key: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/api-keys",
+    tag = "api_keys",
+    responses((status = 200, body = ApiResponse<Vec<ApiKey>>))
+)]
+pub async fn get_api_keys(
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ApiKey>>>, ApiError> {
+    let mut api_keys = ApiKey::find_all(&deployment.db().pool).await?;
+    // A key limited to a project only sees that project's keys
+    if let Some(Extension(caller)) = &caller
+        && caller.project_id.is_some()
+    {
+        api_keys.retain(|api_key| api_key.project_id == caller.project_id);
+    }
+    // Likewise for a key limited to a team
+    if let Some(Extension(caller)) = &caller
+        && caller.team_id.is_some()
+    {
+        api_keys.retain(|api_key| api_key.team_id == caller.team_id);
+    }
+    Ok(ResponseJson(ApiResponse::success(api_keys)))
+}
+
+/// POST /api-keys
+/// A key used to create keys can only create keys for its own project or team, if it has
+/// one. Signed-in users need to be an admin of the project and team a key is limited to,
+/// and their keys act as them. Keys reaching every project can only be created by an
+/// administrator: locally, or with a key that reaches every project itself.
+#[utoipa::path(
+    post,
+    path = "/api/api-keys",
+    tag = "api_keys",
+    request_body = CreateApiKey,
+    responses((status = 200, body = ApiResponse<CreatedApiKey>))
+)]
+pub async fn create_api_key(
+    caller: Option<Extension<ApiKey>>,
+    user: Option<Extension<AuthUser>>,
+    State(deployment): State<DeploymentImpl>,
+    Json(mut payload): Json<CreateApiKey>,
+) -> Result<ResponseJson<ApiResponse<CreatedApiKey>>, ApiError> {
+    if payload.name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "API key name must not be empty".to_string(),
+        ));
+    }
+    if payload.scopes.is_empty() {
+        return Err(ApiError::BadRequest(
+            "An API key needs at least one scope".to_string(),
+        ));
+    }
+    if payload
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= Utc::now())
+    {
+        return Err(ApiError::BadRequest(
+            "Expiry must be in the future".to_string(),
+        ));
+    }
+    if let Some(Extension(caller)) = &caller
+        && let Some(allowed) = caller.project_id
+        && payload.project_id != Some(allowed)
+    {
+        return Err(ApiError::Forbidden(
+            "Keys limited to a project can only create keys for that project".to_string(),
+        ));
+    }
+    if let Some(Extension(caller)) = &caller
+        && let Some(allowed) = caller.team_id
+        && payload.team_id != Some(allowed)
+    {
+        return Err(ApiError::Forbidden(
+            "Keys limited to a team can only create keys for that team".to_string(),
+        ));
+    }
+    let own_id = user.as_deref().map(|AuthUser(user)| user.id);
+    if payload
+        .user_id
+        .is_some_and(|user_id| Some(user_id) != own_id)
+    {
+        return Err(ApiError::Forbidden(
+            "Keys can only act as the user creating them".to_string(),
+        ));
+    }
+    payload.user_id = own_id;
+    let admin_caller = is_administrator(user.as_deref(), caller.as_deref());
+    if payload.project_id.is_none() && payload.team_id.is_none() && !admin_caller {
+        return Err(ApiError::Forbidden(
+            "Only an administrator can create keys that reach every project; limit the key to a project or team".to_string(),
+        ));
+    }
+    let pool = &deployment.db().pool;
+    let project =
+        match payload.project_id {
+            Some(project_id) => Some(Project::find_by_id(pool, project_id).await?.ok_or_else(
+                || ApiError::BadRequest(format!("Project {project_id} does not exist")),
+            )?),
+            None => None,
+        };
+    if let Some(team_id) = payload.team_id {
+        if Team::find_by_id(pool, team_id).await?.is_none() {
+            return Err(ApiError::BadRequest(format!(
+                "Team {team_id} does not exist"
+            )));
+        }
+        if project
+            .as_ref()
+            .is_some_and(|project| project.team_id != Some(team_id))
+        {
+            return Err(ApiError::BadRequest(
+                "The key's project is not in its team".to_string(),
+            ));
+        }
+        require_team_role(
+            &deployment,
+            user.as_deref(),
+            caller.as_deref(),
+            team_id,
+            TeamRole::Admin,
+        )
+        .await?;
+    }
+    if let Some(project_id) = payload.project_id {
+        require_role(
+            &deployment,
+            user.as_deref(),
+            caller.as_deref(),
+            project_id,
+            ProjectRole::Admin,
+        )
+        .await?;
+    }
+
+    let (key, prefix) = generate_api_key();
+    let api_key = ApiKey::create(pool, &payload, &prefix, &hash_token(&key)).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "api_key_created",
+            serde_json::json!({
+                "api_key_id": api_key.id.to_string(),
+                "scopes": payload.scopes,
+                "project_scoped": payload.project_id.is_some(),
+                "team_scoped": payload.team_id.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(CreatedApiKey {
+        api_key,
+        key,
+    })))
+}
+
+/// DELETE /api-keys/{key_id}
+/// Revoke a key. Requests made with it are refused from then on.
+#[utoipa::path(
+    delete,
+    path = "/api/api-keys/{key_id}",
+    tag = "api_keys",
+    params(("key_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<ApiKey>))
+)]
+pub async fn revoke_api_key(
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Path(key_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<ApiKey>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let api_key = ApiKey::find_by_id(pool, key_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    if let Some(Extension(caller)) = &caller
+        && caller.project_id.is_some()
+        && caller.project_id != api_key.project_id
+    {
+        return Err(ApiError::Forbidden(
+            "Keys limited to a project can only revoke keys for that project".to_string(),
+        ));
+    }
+    if let Some(Extension(caller)) = &caller
+        && caller.team_id.is_some()
+        && caller.team_id != api_key.team_id
+    {
+        return Err(ApiError::Forbidden(
+            "Keys limited to a team can only revoke keys for that team".to_string(),
+        ));
+    }
+
+    let api_key = ApiKey::revoke(pool, key_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "api_key_revoked",
+            serde_json::json!({ "api_key_id": api_key.id.to_string() }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(api_key)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/api-keys", get(get_api_keys).post(create_api_key))
+        .route("/api-keys/{key_id}", delete(revoke_api_key))
+}
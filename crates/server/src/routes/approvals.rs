@@ -9,6 +9,14 @@ use utils::approvals::{ApprovalResponse, ApprovalStatus};
 
 use crate::DeploymentImpl;
 
+#[utoipa::path(
+    post,
+    path = "/api/approvals/{id}/respond",
+    tag = "approvals",
+    params(("id" = String, Path)),
+    request_body = Object,
+    responses((status = 200, description = "The approval status", body = Object))
+)]
 pub async fn respond_to_approval(
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<String>,
@@ -0,0 +1,100 @@
+use axum::{
+    Extension, Router,
+    extract::{Query, State},
+    http::HeaderMap,
+    response::Json as ResponseJson,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use db::models::{
+    api_key::ApiKey,
+    audit_log::{AuditEntry, AuditFilter, AuditPrincipal},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::AuthUser, rbac::visible_projects},
+    routes,
+};
+
+#[derive(Debug, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditQuery {
+    #[serde(default)]
+    pub user_id: Option<Uuid>,
+    #[serde(default)]
+    pub api_key_id: Option<Uuid>,
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+    #[serde(default)]
+    pub principal: Option<AuditPrincipal>,
+    /// Entries made at or after this time
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    /// Entries made before this time
+    #[serde(default)]
+    pub until: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub limit: Option<i64>,
+    /// Continue after the page whose `X-Next-Cursor` header this is
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// GET /audit
+/// Every change made through the API across all projects, newest first: who made it, with
+/// which session or API key, and how it was answered. Users and keys limited to some
+/// projects only see entries about those projects.
+#[utoipa::path(
+    get,
+    path = "/api/audit",
+    tag = "audit",
+    params(AuditQuery),
+    responses((
+        status = 200,
+        body = ApiResponse<Vec<AuditEntry>>,
+        headers(("X-Next-Cursor" = String))
+    ))
+)]
+pub async fn get_audit_log(
+    user: Option<Extension<AuthUser>>,
+    api_key: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<AuditQuery>,
+) -> Result<(HeaderMap, ResponseJson<ApiResponse<Vec<AuditEntry>>>), ApiError> {
+    if let (Some(since), Some(until)) = (query.since, query.until)
+        && since >= until
+    {
+        return Err(ApiError::BadRequest(
+            "since must be before until".to_string(),
+        ));
+    }
+    let limit = query.limit.unwrap_or(50).clamp(1, 500);
+    let after = routes::decode_cursor(query.cursor.as_deref())?;
+    let visible = visible_projects(&deployment, user.as_deref(), api_key.as_deref()).await?;
+    let filter = AuditFilter {
+        user_id: query.user_id,
+        api_key_id: query.api_key_id,
+        project_id: query.project_id,
+        principal: query.principal,
+        since: query.since,
+        until: query.until,
+        projects: visible.map(|visible| visible.into_iter().collect()),
+    };
+    let page = AuditEntry::find_page(&deployment.db().pool, &filter, after.as_ref(), limit).await?;
+    Ok((
+        routes::next_cursor_header(page.next_cursor.as_deref()),
+        ResponseJson(ApiResponse::success(page.items)),
+    ))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/audit", get(get_audit_log))
+}
@@ -0,0 +1,126 @@
+use axum::{Extension, Json, Router, extract::State, response::Json as ResponseJson, routing::get};
+use db::models::{
+    board::{Board, ProjectSwimlane, SetProjectSwimlane, SwimlaneGroupBy},
+    custom_field::CustomField,
+    project::Project,
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// GET /projects/{project_id}/board
+/// The project's tasks laid out by swimlane and column.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/board",
+    tag = "board",
+    params(("id" = uuid::Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Board>))
+)]
+pub async fn get_board(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Board>>, ApiError> {
+    let board = deployment
+        .events()
+        .board_cache()
+        .board(&deployment.db().pool, project.id)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(board)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/swimlane",
+    tag = "board",
+    params(("id" = uuid::Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Option<ProjectSwimlane>>))
+)]
+pub async fn get_swimlane(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ProjectSwimlane>>>, ApiError> {
+    let swimlane = ProjectSwimlane::find(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(swimlane)))
+}
+
+/// PUT /projects/{project_id}/swimlane
+/// Split the board into lanes by assignee, label or one of the project's custom fields.
+#[utoipa::path(
+    put,
+    path = "/api/projects/{id}/swimlane",
+    tag = "board",
+    params(("id" = uuid::Uuid, Path)),
+    request_body = SetProjectSwimlane,
+    responses((status = 200, body = ApiResponse<ProjectSwimlane>))
+)]
+pub async fn set_swimlane(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetProjectSwimlane>,
+) -> Result<ResponseJson<ApiResponse<ProjectSwimlane>>, ApiError> {
+    let pool = &deployment.db().pool;
+    match (payload.group_by, payload.custom_field_id) {
+        (SwimlaneGroupBy::CustomField, None) => {
+            return Err(ApiError::BadRequest(
+                "custom_field_id is required to group by custom field".to_string(),
+            ));
+        }
+        (SwimlaneGroupBy::CustomField, Some(field_id)) => {
+            CustomField::find_by_id(pool, field_id)
+                .await?
+                .filter(|field| field.project_id == project.id)
+                .ok_or_else(|| {
+                    ApiError::BadRequest(format!(
+                        "Custom field {field_id} does not belong to the project"
+                    ))
+                })?;
+        }
+        (group_by, Some(_)) => {
+            return Err(ApiError::BadRequest(format!(
+                "custom_field_id only applies when grouping by custom field, not {group_by}"
+            )));
+        }
+        (_, None) => {}
+    }
+
+    let swimlane = ProjectSwimlane::set(pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_swimlane_set",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "group_by": swimlane.group_by.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(swimlane)))
+}
+
+/// DELETE /projects/{project_id}/swimlane
+/// Go back to a board with a single lane.
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{id}/swimlane",
+    tag = "board",
+    params(("id" = uuid::Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn clear_swimlane(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ProjectSwimlane::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Routes nested under `/projects/{id}`, behind the project loading middleware.
+pub fn project_router() -> Router<DeploymentImpl> {
+    Router::new().route("/board", get(get_board)).route(
+        "/swimlane",
+        get(get_swimlane).put(set_swimlane).delete(clear_swimlane),
+    )
+}
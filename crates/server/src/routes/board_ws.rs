@@ -0,0 +1,107 @@
+use axum::{
+    Router,
+    extract::{
+        Query, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    response::IntoResponse,
+    routing::get,
+};
+use db::models::{project::Project, task::Task};
+use deployment::Deployment;
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use services::services::events::BoardMessage;
+use tokio::sync::broadcast::error::RecvError;
+use ts_rs::TS;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BoardWsQuery {
+    pub project_id: Uuid,
+}
+
+/// GET /ws?project_id=
+/// Keep a board in sync without polling: the project's tasks are sent first, then a
+/// created, updated, moved or deleted event for every change to them, whoever made it.
+#[utoipa::path(
+    get,
+    path = "/api/ws",
+    tag = "board_ws",
+    params(BoardWsQuery),
+    responses((status = 101, description = "Switches to a WebSocket"))
+)]
+pub async fn board_ws(
+    ws: WebSocketUpgrade,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<BoardWsQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    if Project::find_by_id(&deployment.db().pool, query.project_id)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Project {} does not exist",
+            query.project_id
+        )));
+    }
+    Ok(ws.on_upgrade(move |socket| async move {
+        if let Err(e) = handle_board_ws(socket, deployment, query.project_id).await {
+            tracing::warn!("board WS closed: {}", e);
+        }
+    }))
+}
+
+async fn snapshot(deployment: &DeploymentImpl, project_id: Uuid) -> anyhow::Result<Message> {
+    let board_events = deployment.events().board_events();
+    let tasks =
+        Task::find_by_project_id_with_attempt_status(&deployment.db().pool, project_id).await?;
+    for task in &tasks {
+        board_events.remember(task);
+    }
+    let text = serde_json::to_string(&BoardMessage::Snapshot { tasks })?;
+    Ok(Message::Text(text.into()))
+}
+
+async fn handle_board_ws(
+    socket: WebSocket,
+    deployment: DeploymentImpl,
+    project_id: Uuid,
+) -> anyhow::Result<()> {
+    // Subscribe before the snapshot so nothing that happens in between is missed
+    let mut events = deployment.events().board_events().subscribe();
+
+    let (mut sender, mut receiver) = socket.split();
+
+    // Drain (and ignore) any client->server messages so pings/pongs work
+    tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
+
+    sender
+        .send(snapshot(&deployment, project_id).await?)
+        .await?;
+    loop {
+        let msg = match events.recv().await {
+            Ok(event) if event.project_id == project_id => {
+                Message::Text(serde_json::to_string(&BoardMessage::Event(event))?.into())
+            }
+            Ok(_) => continue,
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::warn!(skipped = skipped, "board WS lagged; resyncing snapshot");
+                snapshot(&deployment, project_id).await?
+            }
+            Err(RecvError::Closed) => break,
+        };
+        if sender.send(msg).await.is_err() {
+            break; // client disconnected
+        }
+    }
+    Ok(())
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/ws", get(board_ws))
+}
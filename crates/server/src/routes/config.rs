@@ -83,6 +83,12 @@ pub struct UserSystemInfo {
 
 // TODO: update frontend, BE schema has changed, this replaces GET /config and /config/constants
 #[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/api/info",
+    tag = "config",
+    responses((status = 200, body = Object))
+)]
 async fn get_user_system_info(
     State(deployment): State<DeploymentImpl>,
 ) -> ResponseJson<ApiResponse<UserSystemInfo>> {
@@ -110,6 +116,13 @@ async fn get_user_system_info(
     ResponseJson(ApiResponse::success(user_system_info))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/config",
+    tag = "config",
+    request_body = Object,
+    responses((status = 200, body = Object))
+)]
 async fn update_config(
     State(deployment): State<DeploymentImpl>,
     Json(new_config): Json<Config>,
@@ -123,6 +136,13 @@ async fn update_config(
         ));
     }
 
+    // A zero-day retention would purge deleted tasks before they can be restored
+    if new_config.trash_retention_days == 0 {
+        return ResponseJson(ApiResponse::error(
+            "Trash retention must be at least one day.",
+        ));
+    }
+
     // Get old config state before updating
     let old_config = deployment.config().read().await.clone();
 
@@ -185,6 +205,13 @@ async fn handle_config_events(deployment: &DeploymentImpl, old: &Config, new: &C
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/sounds/{sound}",
+    tag = "config",
+    params(("sound" = String, Path)),
+    responses((status = 200, description = "The sound file", content_type = "audio/wav"))
+)]
 async fn get_sound(Path(sound): Path<SoundFile>) -> Result<Response, ApiError> {
     let sound = sound.serve().await.map_err(DeploymentError::Other)?;
     let response = Response::builder()
@@ -215,6 +242,13 @@ pub struct UpdateMcpServersBody {
     servers: HashMap<String, Value>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/mcp-config",
+    tag = "config",
+    params(("executor" = String, Query)),
+    responses((status = 200, body = Object))
+)]
 async fn get_mcp_servers(
     State(_deployment): State<DeploymentImpl>,
     Query(query): Query<McpServerQuery>,
@@ -251,6 +285,14 @@ async fn get_mcp_servers(
     })))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/mcp-config",
+    tag = "config",
+    params(("executor" = String, Query)),
+    request_body = Object,
+    responses((status = 200, body = Object))
+)]
 async fn update_mcp_servers(
     State(_deployment): State<DeploymentImpl>,
     Query(query): Query<McpServerQuery>,
@@ -385,6 +427,12 @@ pub struct ProfilesContent {
     pub path: String,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/profiles",
+    tag = "config",
+    responses((status = 200, body = Object))
+)]
 async fn get_profiles(
     State(_deployment): State<DeploymentImpl>,
 ) -> ResponseJson<ApiResponse<ProfilesContent>> {
@@ -405,6 +453,13 @@ async fn get_profiles(
     }))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/profiles",
+    tag = "config",
+    request_body(content = String, description = "Profiles JSON", content_type = "application/json"),
+    responses((status = 200, body = Object))
+)]
 async fn update_profiles(
     State(_deployment): State<DeploymentImpl>,
     body: String,
@@ -448,6 +503,13 @@ pub struct CheckEditorAvailabilityResponse {
     available: bool,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/editors/check-availability",
+    tag = "config",
+    params(("editor_type" = String, Query)),
+    responses((status = 200, body = Object))
+)]
 async fn check_editor_availability(
     State(_deployment): State<DeploymentImpl>,
     Query(query): Query<CheckEditorAvailabilityQuery>,
@@ -471,6 +533,13 @@ pub struct CheckAgentAvailabilityQuery {
     executor: BaseCodingAgent,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/agents/check-availability",
+    tag = "config",
+    params(("executor" = String, Query)),
+    responses((status = 200, body = Object))
+)]
 async fn check_agent_availability(
     State(_deployment): State<DeploymentImpl>,
     Query(query): Query<CheckAgentAvailabilityQuery>,
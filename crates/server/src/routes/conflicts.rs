@@ -0,0 +1,99 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    integration::{Integration, IntegrationError},
+    sync_conflict::{ResolveSyncConflict, SyncConflict, SyncConflictQuery},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[utoipa::path(
+    get,
+    path = "/api/conflicts",
+    tag = "conflicts",
+    params(SyncConflictQuery),
+    responses((status = 200, body = ApiResponse<Vec<SyncConflict>>))
+)]
+pub async fn get_conflicts(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<SyncConflictQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<SyncConflict>>>, ApiError> {
+    let conflicts = SyncConflict::find_filtered(&deployment.db().pool, &query).await?;
+    Ok(ResponseJson(ApiResponse::success(conflicts)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/conflicts/{conflict_id}",
+    tag = "conflicts",
+    params(("conflict_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<SyncConflict>))
+)]
+pub async fn get_conflict(
+    State(deployment): State<DeploymentImpl>,
+    Path(conflict_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<SyncConflict>>, ApiError> {
+    let conflict = SyncConflict::find_by_id(&deployment.db().pool, conflict_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    Ok(ResponseJson(ApiResponse::success(conflict)))
+}
+
+/// POST /conflicts/{id}/resolve
+/// Settle an open conflict by keeping the local task values or taking the remote ones.
+#[utoipa::path(
+    post,
+    path = "/api/conflicts/{conflict_id}/resolve",
+    tag = "conflicts",
+    params(("conflict_id" = Uuid, Path)),
+    request_body = ResolveSyncConflict,
+    responses((status = 200, body = ApiResponse<SyncConflict>))
+)]
+pub async fn resolve_conflict(
+    State(deployment): State<DeploymentImpl>,
+    Path(conflict_id): Path<Uuid>,
+    Json(payload): Json<ResolveSyncConflict>,
+) -> Result<ResponseJson<ApiResponse<SyncConflict>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let conflict = SyncConflict::find_by_id(pool, conflict_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let integration = Integration::find_by_id(pool, conflict.integration_id)
+        .await?
+        .ok_or(IntegrationError::NotFound)?;
+
+    let resolved = deployment
+        .integrations()
+        .resolve_conflict(pool, &integration, &conflict, payload.resolution)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "integration_conflict_resolved",
+            serde_json::json!({
+                "integration_id": integration.id.to_string(),
+                "provider": integration.provider.to_string(),
+                "resolution": payload.resolution.to_string(),
+                "fields": conflict.fields.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(resolved)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let inner = Router::new()
+        .route("/", get(get_conflicts))
+        .route("/{conflict_id}", get(get_conflict))
+        .route("/{conflict_id}/resolve", post(resolve_conflict));
+
+    Router::new().nest("/conflicts", inner)
+}
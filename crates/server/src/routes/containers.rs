@@ -8,15 +8,24 @@ use db::models::workspace::{Workspace, WorkspaceContext};
 use deployment::Deployment;
 use serde::{Deserialize, Serialize};
 use utils::response::ApiResponse;
+use utoipa::IntoParams;
 
 use crate::{DeploymentImpl, error::ApiError};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ContainerQuery {
     #[serde(rename = "ref")]
     pub container_ref: String,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/containers/attempt-context",
+    tag = "containers",
+    params(ContainerQuery),
+    responses((status = 200, body = ApiResponse<WorkspaceContext>))
+)]
 pub async fn get_context(
     State(deployment): State<DeploymentImpl>,
     Query(payload): Query<ContainerQuery>,
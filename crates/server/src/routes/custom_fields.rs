@@ -0,0 +1,317 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    custom_field::{
+        CreateCustomField, CustomField, CustomFieldType, SetCustomFieldValue, TaskCustomFieldValue,
+        UpdateCustomField,
+    },
+    project::Project,
+    task::Task,
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+fn validate_name(name: &str) -> Result<(), ApiError> {
+    if name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Custom field name must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Only `select` fields have options, and they must be non-empty and distinct.
+fn validate_options(field_type: CustomFieldType, options: &[String]) -> Result<(), ApiError> {
+    if field_type != CustomFieldType::Select {
+        if !options.is_empty() {
+            return Err(ApiError::BadRequest(format!(
+                "Options are only allowed on select fields, not {field_type} fields"
+            )));
+        }
+        return Ok(());
+    }
+    if options.is_empty() {
+        return Err(ApiError::BadRequest(
+            "A select field needs at least one option".to_string(),
+        ));
+    }
+    for (i, option) in options.iter().enumerate() {
+        if option.trim().is_empty() {
+            return Err(ApiError::BadRequest(
+                "Select options must not be empty".to_string(),
+            ));
+        }
+        if options[..i]
+            .iter()
+            .any(|other| other.eq_ignore_ascii_case(option))
+        {
+            return Err(ApiError::BadRequest(format!(
+                "Duplicate select option '{option}'"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Reject a name already used by another custom field in the project.
+async fn ensure_unique_name(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    name: &str,
+    except: Option<Uuid>,
+) -> Result<(), ApiError> {
+    if let Some(existing) =
+        CustomField::find_by_name(&deployment.db().pool, project_id, name).await?
+        && Some(existing.id) != except
+    {
+        return Err(ApiError::Conflict(format!(
+            "A custom field named '{name}' already exists in this project"
+        )));
+    }
+    Ok(())
+}
+
+/// Load a custom field, checking that it belongs to the project in the path.
+async fn load_field(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    field_id: Uuid,
+) -> Result<CustomField, ApiError> {
+    CustomField::find_by_id(&deployment.db().pool, field_id)
+        .await?
+        .filter(|field| field.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+fn normalize(field: &CustomField, value: &str) -> Result<String, ApiError> {
+    field.normalize_value(value).map_err(|reason| {
+        ApiError::BadRequest(format!("Invalid value for '{}': {reason}", field.name))
+    })
+}
+
+/// Normalize a task list filter value the way values of the field are stored.
+pub async fn normalize_filter_value(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    field_id: Uuid,
+    value: &str,
+) -> Result<String, ApiError> {
+    let field = CustomField::find_by_id(&deployment.db().pool, field_id)
+        .await?
+        .filter(|field| field.project_id == project_id)
+        .ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "Custom field {field_id} does not belong to the project"
+            ))
+        })?;
+    normalize(&field, value)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/custom-fields",
+    tag = "custom_fields",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<CustomField>>))
+)]
+pub async fn get_custom_fields(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<CustomField>>>, ApiError> {
+    let fields = CustomField::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(fields)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/custom-fields",
+    tag = "custom_fields",
+    params(("id" = Uuid, Path)),
+    request_body = CreateCustomField,
+    responses((status = 200, body = ApiResponse<CustomField>))
+)]
+pub async fn create_custom_field(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateCustomField>,
+) -> Result<ResponseJson<ApiResponse<CustomField>>, ApiError> {
+    validate_name(&payload.name)?;
+    validate_options(payload.field_type, &payload.options)?;
+    ensure_unique_name(&deployment, project.id, &payload.name, None).await?;
+
+    let field = CustomField::create(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "custom_field_created",
+            serde_json::json!({
+                "custom_field_id": field.id.to_string(),
+                "project_id": project.id.to_string(),
+                "field_type": field.field_type.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(field)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/custom-fields/{field_id}",
+    tag = "custom_fields",
+    params(("project_id" = Uuid, Path), ("field_id" = Uuid, Path)),
+    request_body = UpdateCustomField,
+    responses((status = 200, body = ApiResponse<CustomField>))
+)]
+pub async fn update_custom_field(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, field_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateCustomField>,
+) -> Result<ResponseJson<ApiResponse<CustomField>>, ApiError> {
+    let field = load_field(&deployment, project_id, field_id).await?;
+    if let Some(name) = &payload.name {
+        validate_name(name)?;
+        ensure_unique_name(&deployment, project_id, name, Some(field.id)).await?;
+    }
+    if let Some(options) = &payload.options {
+        validate_options(field.field_type, options)?;
+    }
+
+    let field = CustomField::update(&deployment.db().pool, field.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(field)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/custom-fields/{field_id}",
+    tag = "custom_fields",
+    params(("project_id" = Uuid, Path), ("field_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_custom_field(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, field_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let field = load_field(&deployment, project_id, field_id).await?;
+    CustomField::delete(&deployment.db().pool, field.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "custom_field_deleted",
+            serde_json::json!({
+                "custom_field_id": field.id.to_string(),
+                "project_id": project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/custom-fields",
+    tag = "custom_fields",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<TaskCustomFieldValue>>))
+)]
+pub async fn get_task_custom_field_values(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskCustomFieldValue>>>, ApiError> {
+    let values = CustomField::find_values_for_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(values)))
+}
+
+/// Load a task and one of its project's custom fields from the path.
+async fn load_task_field(
+    deployment: &DeploymentImpl,
+    task_id: Uuid,
+    field_id: Uuid,
+) -> Result<(Task, CustomField), ApiError> {
+    let task = Task::find_by_id(&deployment.db().pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let field = load_field(deployment, task.project_id, field_id).await?;
+    Ok((task, field))
+}
+
+/// PUT /tasks/{task_id}/custom-fields/{field_id}
+/// Set the task's value for a field of its project, validated against the field's type.
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}/custom-fields/{field_id}",
+    tag = "custom_fields",
+    params(("task_id" = Uuid, Path), ("field_id" = Uuid, Path)),
+    request_body = SetCustomFieldValue,
+    responses((status = 200, body = ApiResponse<Vec<TaskCustomFieldValue>>))
+)]
+pub async fn set_task_custom_field_value(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, field_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<SetCustomFieldValue>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskCustomFieldValue>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let (task, field) = load_task_field(&deployment, task_id, field_id).await?;
+    let value = normalize(&field, &payload.value)?;
+
+    CustomField::set_value(pool, task.id, field.id, &value).await?;
+    let values = CustomField::find_values_for_task(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(values)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/custom-fields/{field_id}",
+    tag = "custom_fields",
+    params(("task_id" = Uuid, Path), ("field_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<TaskCustomFieldValue>>))
+)]
+pub async fn clear_task_custom_field_value(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, field_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskCustomFieldValue>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let (task, field) = load_task_field(&deployment, task_id, field_id).await?;
+
+    CustomField::clear_value(pool, task.id, field.id).await?;
+    let values = CustomField::find_values_for_task(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(values)))
+}
+
+/// Routes nested under `/projects/{id}`, behind the project loading middleware.
+pub fn project_router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/custom-fields",
+        get(get_custom_fields).post(create_custom_field),
+    )
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new().route("/custom-fields", get(get_task_custom_field_values))
+}
+
+/// Routes nested under `/projects`. The project loader only understands a single path
+/// parameter, so these load the custom field themselves.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/{project_id}/custom-fields/{field_id}",
+        put(update_custom_field).delete(delete_custom_field),
+    )
+}
+
+/// Routes nested under `/tasks`, loading the task and custom field from the path.
+pub fn task_value_router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/{task_id}/custom-fields/{field_id}",
+        put(set_task_custom_field_value).delete(clear_task_custom_field_value),
+    )
+}
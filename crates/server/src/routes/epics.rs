@@ -0,0 +1,244 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    epic::{CreateEpic, Epic, EpicWithProgress, SetTaskEpic, UpdateEpic},
+    project::Project,
+    task::Task,
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+fn validate_title(title: Option<&str>) -> Result<(), ApiError> {
+    if title.is_some_and(|title| title.trim().is_empty()) {
+        return Err(ApiError::BadRequest(
+            "Epic title must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Load an epic, checking that it belongs to the project in the path.
+async fn load_epic(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    epic_id: Uuid,
+) -> Result<Epic, ApiError> {
+    Epic::find_by_id(&deployment.db().pool, epic_id)
+        .await?
+        .filter(|epic| epic.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+/// GET /projects/{project_id}/epics
+/// The project's epics and milestones with their progress, soonest due first.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/epics",
+    tag = "epics",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<EpicWithProgress>>))
+)]
+pub async fn get_epics(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<EpicWithProgress>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let mut progress = Epic::find_progress_by_project_id(pool, project.id).await?;
+    let epics = Epic::find_by_project_id(pool, project.id)
+        .await?
+        .into_iter()
+        .map(|epic| EpicWithProgress {
+            progress: progress.remove(&epic.id).unwrap_or_default(),
+            epic,
+        })
+        .collect();
+    Ok(ResponseJson(ApiResponse::success(epics)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/epics",
+    tag = "epics",
+    params(("id" = Uuid, Path)),
+    request_body = CreateEpic,
+    responses((status = 200, body = ApiResponse<Epic>))
+)]
+pub async fn create_epic(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateEpic>,
+) -> Result<ResponseJson<ApiResponse<Epic>>, ApiError> {
+    validate_title(Some(&payload.title))?;
+    let epic = Epic::create(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "epic_created",
+            serde_json::json!({
+                "epic_id": epic.id.to_string(),
+                "project_id": project.id.to_string(),
+                "kind": epic.kind.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(epic)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/epics/{epic_id}",
+    tag = "epics",
+    params(("project_id" = Uuid, Path), ("epic_id" = Uuid, Path)),
+    request_body = UpdateEpic,
+    responses((status = 200, body = ApiResponse<Epic>))
+)]
+pub async fn update_epic(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, epic_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateEpic>,
+) -> Result<ResponseJson<ApiResponse<Epic>>, ApiError> {
+    let epic = load_epic(&deployment, project_id, epic_id).await?;
+    validate_title(payload.title.as_deref())?;
+    let epic = Epic::update(&deployment.db().pool, epic.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(epic)))
+}
+
+/// DELETE /projects/{project_id}/epics/{epic_id}
+/// The epic's tasks are kept.
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/epics/{epic_id}",
+    tag = "epics",
+    params(("project_id" = Uuid, Path), ("epic_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_epic(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, epic_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let epic = load_epic(&deployment, project_id, epic_id).await?;
+    Epic::delete(&deployment.db().pool, epic.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "epic_deleted",
+            serde_json::json!({
+                "epic_id": epic.id.to_string(),
+                "project_id": project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// GET /projects/{project_id}/epics/{epic_id}/tasks
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/epics/{epic_id}/tasks",
+    tag = "epics",
+    params(("project_id" = Uuid, Path), ("epic_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<Task>>))
+)]
+pub async fn get_epic_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, epic_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let epic = load_epic(&deployment, project_id, epic_id).await?;
+    let tasks = Epic::find_tasks(&deployment.db().pool, epic.id).await?;
+    Ok(ResponseJson(ApiResponse::success(tasks)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/epic",
+    tag = "epics",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Option<Epic>>))
+)]
+pub async fn get_task_epic(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<Epic>>>, ApiError> {
+    let epic = Epic::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(epic)))
+}
+
+/// PUT /tasks/{task_id}/epic
+/// Move the task into an epic of its project, out of any other.
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}/epic",
+    tag = "epics",
+    params(("task_id" = Uuid, Path)),
+    request_body = SetTaskEpic,
+    responses((status = 200, body = ApiResponse<Epic>))
+)]
+pub async fn set_task_epic(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetTaskEpic>,
+) -> Result<ResponseJson<ApiResponse<Epic>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let epic = Epic::find_by_id(pool, payload.epic_id)
+        .await?
+        .filter(|epic| epic.project_id == task.project_id)
+        .ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "Epic {} does not belong to the task's project",
+                payload.epic_id
+            ))
+        })?;
+
+    Epic::add_task(pool, epic.id, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(epic)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/epic",
+    tag = "epics",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn clear_task_epic(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    Epic::remove_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Routes nested under `/projects/{id}`, behind the project loading middleware.
+pub fn project_router() -> Router<DeploymentImpl> {
+    Router::new().route("/epics", get(get_epics).post(create_epic))
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/epic",
+        get(get_task_epic)
+            .put(set_task_epic)
+            .delete(clear_task_epic),
+    )
+}
+
+/// Routes nested under `/projects`. The project loader only understands a single path
+/// parameter, so these load the epic themselves.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/{project_id}/epics/{epic_id}",
+            put(update_epic).delete(delete_epic),
+        )
+        .route("/{project_id}/epics/{epic_id}/tasks", get(get_epic_tasks))
+}
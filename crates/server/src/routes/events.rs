@@ -1,17 +1,29 @@
+use std::convert::Infallible;
+
 use axum::{
-    BoxError, Router,
+    BoxError, Extension, Router,
     extract::State,
+    http::HeaderMap,
     response::{
         Sse,
         sse::{Event, KeepAlive},
     },
     routing::get,
 };
+use db::models::project::Project;
 use deployment::Deployment;
-use futures_util::TryStreamExt;
+use futures_util::{StreamExt, TryStreamExt, stream};
+use services::services::events::{ProjectEvent, Replay};
+use tokio::sync::broadcast::error::RecvError;
 
 use crate::DeploymentImpl;
 
+#[utoipa::path(
+    get,
+    path = "/api/events",
+    tag = "events",
+    responses((status = 200, description = "Server-sent events", content_type = "text/event-stream"))
+)]
 pub async fn events(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<Sse<impl futures_util::Stream<Item = Result<Event, BoxError>>>, axum::http::StatusCode>
@@ -21,8 +33,77 @@ pub async fn events(
     Ok(Sse::new(stream.map_err(|e| -> BoxError { e.into() })).keep_alive(KeepAlive::default()))
 }
 
+fn project_event(event: &ProjectEvent) -> Event {
+    Event::default()
+        .id(event.id.to_string())
+        .event(event.kind.as_str())
+        .json_data(event)
+        .unwrap_or_else(|_| Event::default().comment("unserializable event"))
+}
+
+/// Tells the client that events were missed and it has to refetch what it shows.
+fn reset_event() -> Event {
+    Event::default().event("reset").data("{}")
+}
+
+/// GET /projects/{id}/events
+/// Task, comment and sync activity of a project as server-sent events, each named
+/// after its kind (`task.moved`, `comment.created`, ...) with the event id set. A
+/// client reconnecting with `Last-Event-ID` gets what it missed, or a `reset` event
+/// when that is no longer available.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/events",
+    tag = "events",
+    params(("id" = uuid::Uuid, Path)),
+    responses((status = 200, description = "Server-sent project events", content_type = "text/event-stream"))
+)]
+pub async fn project_events(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+) -> Sse<impl futures_util::Stream<Item = Result<Event, Infallible>>> {
+    let last_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok());
+    let project_id = project.id;
+    let (replay, receiver) = deployment.events().activity().subscribe(last_id);
+
+    let replayed: Vec<Event> = match replay {
+        Replay::Events(events) => events
+            .iter()
+            .filter(|event| event.project_id == project_id)
+            .map(project_event)
+            .collect(),
+        Replay::Gap => vec![reset_event()],
+    };
+    let live = stream::unfold(receiver, move |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) if event.project_id == project_id => {
+                    return Some((project_event(&event), receiver));
+                }
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(skipped = skipped, "project events lagged; sending reset");
+                    return Some((reset_event(), receiver));
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Sse::new(stream::iter(replayed).chain(live).map(Ok)).keep_alive(KeepAlive::default())
+}
+
 pub fn router(_: &DeploymentImpl) -> Router<DeploymentImpl> {
     let events_router = Router::new().route("/", get(events));
 
     Router::new().nest("/events", events_router)
 }
+
+/// Routes nested under `/projects/{id}`, behind the project loading middleware.
+pub fn project_router() -> Router<DeploymentImpl> {
+    Router::new().route("/events", get(project_events))
+}
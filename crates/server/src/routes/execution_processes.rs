@@ -18,11 +18,13 @@ use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::Deserialize;
 use services::services::container::ContainerService;
 use utils::{log_msg::LogMsg, response::ApiResponse};
+use utoipa::IntoParams;
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_execution_process_middleware};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ExecutionProcessQuery {
     pub workspace_id: Uuid,
     /// If true, include soft-deleted (dropped) processes in results/stream
@@ -30,6 +32,13 @@ pub struct ExecutionProcessQuery {
     pub show_soft_deleted: Option<bool>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/execution-processes/{id}",
+    tag = "execution_processes",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<ExecutionProcess>))
+)]
 pub async fn get_execution_process_by_id(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(_deployment): State<DeploymentImpl>,
@@ -37,6 +46,13 @@ pub async fn get_execution_process_by_id(
     Ok(ResponseJson(ApiResponse::success(execution_process)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/execution-processes/{id}/raw-logs/ws",
+    tag = "execution_processes",
+    params(("id" = Uuid, Path)),
+    responses((status = 101, description = "Switches to a WebSocket"))
+)]
 pub async fn stream_raw_logs_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -120,6 +136,13 @@ async fn handle_raw_logs_ws(
     Ok(())
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/execution-processes/{id}/normalized-logs/ws",
+    tag = "execution_processes",
+    params(("id" = Uuid, Path)),
+    responses((status = 101, description = "Switches to a WebSocket"))
+)]
 pub async fn stream_normalized_logs_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -166,6 +189,13 @@ async fn handle_normalized_logs_ws(
     Ok(())
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/execution-processes/{id}/stop",
+    tag = "execution_processes",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
 pub async fn stop_execution_process(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(deployment): State<DeploymentImpl>,
@@ -178,6 +208,13 @@ pub async fn stop_execution_process(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/execution-processes/stream/ws",
+    tag = "execution_processes",
+    params(ExecutionProcessQuery),
+    responses((status = 101, description = "Switches to a WebSocket"))
+)]
 pub async fn stream_execution_processes_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -233,6 +270,13 @@ async fn handle_execution_processes_ws(
     Ok(())
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/execution-processes/{id}/repo-states",
+    tag = "execution_processes",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<ExecutionProcessRepoState>>))
+)]
 pub async fn get_execution_process_repo_states(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(deployment): State<DeploymentImpl>,
@@ -8,14 +8,23 @@ use deployment::Deployment;
 use serde::Deserialize;
 use services::services::filesystem::{DirectoryEntry, DirectoryListResponse, FilesystemError};
 use utils::response::ApiResponse;
+use utoipa::IntoParams;
 
 use crate::{DeploymentImpl, error::ApiError};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListDirectoryQuery {
     path: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/filesystem/directory",
+    tag = "filesystem",
+    params(ListDirectoryQuery),
+    responses((status = 200, body = ApiResponse<DirectoryListResponse>))
+)]
 pub async fn list_directory(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<ListDirectoryQuery>,
@@ -38,6 +47,13 @@ pub async fn list_directory(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/filesystem/git-repos",
+    tag = "filesystem",
+    params(ListDirectoryQuery),
+    responses((status = 200, body = ApiResponse<Vec<DirectoryEntry>>))
+)]
 pub async fn list_git_repos(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<ListDirectoryQuery>,
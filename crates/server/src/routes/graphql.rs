@@ -0,0 +1,46 @@
+use async_graphql::http::GraphiQLSource;
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::{Extension, Router, extract::State, response::Html, routing::get};
+use db::models::api_key::ApiKey;
+
+use crate::{DeploymentImpl, graphql, middleware::auth::AuthUser};
+
+/// POST /graphql
+#[utoipa::path(
+    post,
+    path = "/api/graphql",
+    tag = "graphql",
+    request_body(content = Object, description = "GraphQL request", content_type = "application/json"),
+    responses((status = 200, description = "GraphQL response", body = Object))
+)]
+pub async fn graphql_handler(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    let mut request = request.into_inner().data(deployment);
+    if let Some(Extension(user)) = user {
+        request = request.data(user);
+    }
+    if let Some(Extension(api_key)) = caller {
+        request = request.data(api_key);
+    }
+    graphql::schema().execute(request).await.into()
+}
+
+/// GET /graphql
+/// An in-browser IDE for exploring the schema and trying out queries.
+#[utoipa::path(
+    get,
+    path = "/api/graphql",
+    tag = "graphql",
+    responses((status = 200, description = "GraphiQL playground", body = String, content_type = "text/html"))
+)]
+pub async fn graphiql() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint("/api/graphql").finish())
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/graphql", get(graphiql).post(graphql_handler))
+}
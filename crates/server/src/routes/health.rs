@@ -1,6 +1,192 @@
-use axum::response::Json;
+use std::{
+    sync::{LazyLock, Mutex},
+    time::Duration,
+};
+
+use axum::{Router, extract::State, http::StatusCode, response::Json, routing::get};
+use db::models::integration::Integration;
+use deployment::Deployment;
+use futures_util::future::join_all;
+use serde::Serialize;
+use tokio::{task::JoinHandle, time::timeout};
 use utils::response::ApiResponse;
 
+use crate::DeploymentImpl;
+
+/// How long a single readiness check may take before it counts as failed.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Background workers that run for as long as the server does; one that has finished has
+/// crashed or panicked.
+static WORKERS: LazyLock<Mutex<Vec<(&'static str, JoinHandle<()>)>>> =
+    LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// Also probe every enabled integration's remote API on readiness checks. Off unless
+/// the operator turns it on, since `/readyz` takes no credentials.
+static CHECK_INTEGRATIONS: LazyLock<bool> =
+    LazyLock::new(|| utils::env_flag("VK_READYZ_INTEGRATIONS"));
+
+/// Watch a background worker for the health probes.
+pub fn track_worker(name: &'static str, handle: JoinHandle<()>) {
+    WORKERS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .push((name, handle));
+}
+
+#[derive(Debug, Serialize)]
+pub struct HealthCheck {
+    pub name: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl HealthCheck {
+    fn pass(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ok: true,
+            detail: None,
+        }
+    }
+
+    fn fail(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ok: false,
+            detail: Some(detail.into()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub ok: bool,
+    pub checks: Vec<HealthCheck>,
+}
+
+/// `200` when every check passed, `503` otherwise, which is all probes look at.
+fn report(checks: Vec<HealthCheck>) -> (StatusCode, Json<HealthReport>) {
+    let ok = checks.iter().all(|check| check.ok);
+    let status = if ok {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(HealthReport { ok, checks }))
+}
+
+fn worker_checks() -> Vec<HealthCheck> {
+    WORKERS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .iter()
+        .map(|(name, handle)| {
+            let name = format!("worker:{name}");
+            if handle.is_finished() {
+                HealthCheck::fail(name, "stopped")
+            } else {
+                HealthCheck::pass(name)
+            }
+        })
+        .collect()
+}
+
+async fn database_check(deployment: &DeploymentImpl) -> HealthCheck {
+    let ping = sqlx::query("SELECT 1").execute(&deployment.db().pool);
+    match timeout(CHECK_TIMEOUT, ping).await {
+        Ok(Ok(_)) => HealthCheck::pass("database"),
+        Ok(Err(e)) => HealthCheck::fail("database", e.to_string()),
+        Err(_) => HealthCheck::fail("database", "timed out"),
+    }
+}
+
+async fn migrations_check(deployment: &DeploymentImpl) -> HealthCheck {
+    match timeout(CHECK_TIMEOUT, deployment.db().pending_migrations()).await {
+        Ok(Ok(pending)) if pending.is_empty() => HealthCheck::pass("migrations"),
+        Ok(Ok(pending)) => HealthCheck::fail(
+            "migrations",
+            format!(
+                "pending: {}",
+                pending
+                    .iter()
+                    .map(i64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ),
+        Ok(Err(e)) => HealthCheck::fail("migrations", e.to_string()),
+        Err(_) => HealthCheck::fail("migrations", "timed out"),
+    }
+}
+
+/// Whether each enabled integration's remote API accepts an authenticated request.
+async fn integration_checks(deployment: &DeploymentImpl) -> Vec<HealthCheck> {
+    let pool = &deployment.db().pool;
+    let integrations = match Integration::find_all(pool).await {
+        Ok(integrations) => integrations,
+        Err(e) => return vec![HealthCheck::fail("integrations", e.to_string())],
+    };
+    let probes = integrations
+        .iter()
+        .filter(|integration| integration.enabled)
+        .map(|integration| async move {
+            let name = format!("integration:{}", integration.id);
+            let health = deployment.integrations().health(pool, integration);
+            match timeout(CHECK_TIMEOUT, health).await {
+                Ok(Ok(health)) if health.reachable != Some(false) => HealthCheck::pass(name),
+                Ok(Ok(health)) => HealthCheck::fail(
+                    name,
+                    health
+                        .reachability_error
+                        .unwrap_or_else(|| "unreachable".to_string()),
+                ),
+                Ok(Err(e)) => HealthCheck::fail(name, e.to_string()),
+                Err(_) => HealthCheck::fail(name, "timed out"),
+            }
+        });
+    join_all(probes).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/health",
+    tag = "health",
+    responses((status = 200, body = ApiResponse<String>))
+)]
 pub async fn health_check() -> Json<ApiResponse<String>> {
     Json(ApiResponse::success("OK".to_string()))
 }
+
+/// GET /healthz
+/// Liveness: the server answers and its background workers are still running. Nothing
+/// outside the process is checked, so a slow database doesn't get the server restarted.
+pub async fn healthz() -> (StatusCode, Json<HealthReport>) {
+    report(worker_checks())
+}
+
+/// GET /readyz
+/// Readiness: the database answers and is fully migrated and the background workers are
+/// running. With `VK_READYZ_INTEGRATIONS` set, every enabled integration must be reachable
+/// too.
+pub async fn readyz(State(deployment): State<DeploymentImpl>) -> (StatusCode, Json<HealthReport>) {
+    let mut checks = vec![
+        database_check(&deployment).await,
+        migrations_check(&deployment).await,
+    ];
+    checks.extend(worker_checks());
+    if *CHECK_INTEGRATIONS {
+        checks.extend(integration_checks(&deployment).await);
+    }
+    report(checks)
+}
+
+/// Kubernetes-style probes, served at the root rather than under `/api` and left out of
+/// authentication and rate limits so probes need no credentials.
+pub fn probes(deployment: DeploymentImpl) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .with_state(deployment)
+}
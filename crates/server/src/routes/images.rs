@@ -21,11 +21,12 @@ use tokio::fs::File;
 use tokio_util::io::ReaderStream;
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct ImageResponse {
     pub id: Uuid,
     pub file_path: String, // relative path to display in markdown
@@ -54,14 +55,15 @@ impl ImageResponse {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ImageMetadataQuery {
     /// Path relative to worktree root, e.g., ".vibe-images/screenshot.png"
     pub path: String,
 }
 
 /// Metadata response for image files, used for rendering in WYSIWYG editor
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct ImageMetadata {
     pub exists: bool,
@@ -72,6 +74,13 @@ pub struct ImageMetadata {
     pub proxy_url: Option<String>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/images/upload",
+    tag = "images",
+    request_body(content_type = "multipart/form-data", description = "The file to upload"),
+    responses((status = 200, body = ApiResponse<ImageResponse>))
+)]
 pub async fn upload_image(
     State(deployment): State<DeploymentImpl>,
     multipart: Multipart,
@@ -125,6 +134,14 @@ pub(crate) async fn process_image_upload(
     Err(ApiError::Image(ImageError::NotFound))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/images/task/{task_id}/upload",
+    tag = "images",
+    params(("task_id" = Uuid, Path)),
+    request_body(content_type = "multipart/form-data", description = "The file to upload"),
+    responses((status = 200, body = ApiResponse<ImageResponse>))
+)]
 pub async fn upload_task_image(
     Path(task_id): Path<Uuid>,
     State(deployment): State<DeploymentImpl>,
@@ -139,6 +156,13 @@ pub async fn upload_task_image(
 }
 
 /// Serve an image file by ID
+#[utoipa::path(
+    get,
+    path = "/api/images/{id}/file",
+    tag = "images",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, description = "The image file", content_type = "application/octet-stream"))
+)]
 pub async fn serve_image(
     Path(image_id): Path<Uuid>,
     State(deployment): State<DeploymentImpl>,
@@ -172,6 +196,13 @@ pub async fn serve_image(
     Ok(response)
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/images/{id}",
+    tag = "images",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
 pub async fn delete_image(
     Path(image_id): Path<Uuid>,
     State(deployment): State<DeploymentImpl>,
@@ -181,6 +212,13 @@ pub async fn delete_image(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/images/task/{task_id}",
+    tag = "images",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<ImageResponse>>))
+)]
 pub async fn get_task_images(
     Path(task_id): Path<Uuid>,
     State(deployment): State<DeploymentImpl>,
@@ -192,6 +230,13 @@ pub async fn get_task_images(
 
 /// Get metadata for an image associated with a task.
 /// The path should be in the format `.vibe-images/{uuid}.{ext}`.
+#[utoipa::path(
+    get,
+    path = "/api/images/task/{task_id}/metadata",
+    tag = "images",
+    params(("task_id" = Uuid, Path), ImageMetadataQuery),
+    responses((status = 200, body = ApiResponse<ImageMetadata>))
+)]
 pub async fn get_task_image_metadata(
     Path(task_id): Path<Uuid>,
     State(deployment): State<DeploymentImpl>,
@@ -0,0 +1,653 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{delete, get, post},
+};
+use db::models::{
+    api_key::ApiKey,
+    integration::{
+        CreateIntegration, FieldMapping, Integration, IntegrationCheck, IntegrationError,
+        IntegrationResponse, UpdateIntegration,
+    },
+    project::{Project, ProjectError},
+    project_member::ProjectRole,
+    sync_audit::{SyncAuditEntry, SyncAuditFilter},
+    sync_dead_letter::SyncDeadLetter,
+    sync_job::SyncJob,
+    sync_plan::{SyncPlan, SyncPlanStatus},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::{
+    integrations::{
+        IntegrationHealth, IntegrationService, ProviderCatalogEntry, SyncSummary, plan,
+    },
+    sync_worker::queue_sync,
+};
+use ts_rs::TS;
+use url::Url;
+use utils::response::ApiResponse;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{
+        auth::AuthUser,
+        idempotency::idempotency,
+        load_integration_middleware,
+        rbac::{require_role, visible_projects},
+    },
+    routes,
+};
+
+#[derive(Debug, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IntegrationQuery {
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+}
+
+fn validate_name(name: &str) -> Result<(), ApiError> {
+    if name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Integration name must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_base_url(base_url: &str) -> Result<(), ApiError> {
+    match Url::parse(base_url) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(()),
+        _ => Err(ApiError::BadRequest(format!(
+            "Invalid integration base URL: {base_url}"
+        ))),
+    }
+}
+
+fn validate_field_mapping(mapping: &FieldMapping) -> Result<(), ApiError> {
+    mapping
+        .validate()
+        .map_err(|e| ApiError::BadRequest(format!("Invalid field mapping: {e}")))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/integrations",
+    tag = "integrations",
+    params(IntegrationQuery),
+    responses((status = 200, body = ApiResponse<Vec<IntegrationResponse>>))
+)]
+pub async fn get_integrations(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<IntegrationQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<IntegrationResponse>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let mut integrations = match query.project_id {
+        Some(project_id) => Integration::find_by_project_id(pool, project_id).await?,
+        None => Integration::find_all(pool).await?,
+    };
+    if let Some(visible) = visible_projects(&deployment, user.as_deref(), caller.as_deref()).await?
+    {
+        integrations.retain(|integration| visible.contains(&integration.project_id));
+    }
+
+    Ok(ResponseJson(ApiResponse::success(
+        integrations.iter().map(Integration::redacted).collect(),
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/integrations/catalog",
+    tag = "integrations",
+    responses((status = 200, body = ApiResponse<Vec<ProviderCatalogEntry>>))
+)]
+pub async fn get_integration_catalog() -> ResponseJson<ApiResponse<Vec<ProviderCatalogEntry>>> {
+    ResponseJson(ApiResponse::success(IntegrationService::catalog()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/integrations/{integration_id}",
+    tag = "integrations",
+    params(("integration_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<IntegrationResponse>))
+)]
+pub async fn get_integration(
+    Extension(integration): Extension<Integration>,
+) -> Result<ResponseJson<ApiResponse<IntegrationResponse>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(integration.redacted())))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/integrations",
+    tag = "integrations",
+    request_body = CreateIntegration,
+    responses((status = 200, body = ApiResponse<IntegrationResponse>))
+)]
+pub async fn create_integration(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateIntegration>,
+) -> Result<ResponseJson<ApiResponse<IntegrationResponse>>, ApiError> {
+    validate_name(&payload.name)?;
+    validate_base_url(&payload.base_url)?;
+    if let Some(mapping) = &payload.field_mapping {
+        validate_field_mapping(mapping)?;
+    }
+
+    let pool = &deployment.db().pool;
+    Project::find_by_id(pool, payload.project_id)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+    require_role(
+        &deployment,
+        user.as_deref(),
+        caller.as_deref(),
+        payload.project_id,
+        ProjectRole::Admin,
+    )
+    .await?;
+
+    let integration = Integration::create(pool, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "integration_created",
+            serde_json::json!({
+                "integration_id": integration.id.to_string(),
+                "project_id": integration.project_id.to_string(),
+                "provider": integration.provider.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(integration.redacted())))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/integrations/{integration_id}",
+    tag = "integrations",
+    params(("integration_id" = Uuid, Path)),
+    request_body = UpdateIntegration,
+    responses((status = 200, body = ApiResponse<IntegrationResponse>))
+)]
+pub async fn update_integration(
+    Extension(integration): Extension<Integration>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateIntegration>,
+) -> Result<ResponseJson<ApiResponse<IntegrationResponse>>, ApiError> {
+    if let Some(name) = &payload.name {
+        validate_name(name)?;
+    }
+    if let Some(base_url) = &payload.base_url {
+        validate_base_url(base_url)?;
+    }
+    if let Some(mapping) = &payload.field_mapping {
+        validate_field_mapping(mapping)?;
+    }
+
+    let updated = Integration::update(&deployment.db().pool, integration.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "integration_updated",
+            serde_json::json!({
+                "integration_id": updated.id.to_string(),
+                "provider": updated.provider.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(updated.redacted())))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/integrations/{integration_id}",
+    tag = "integrations",
+    params(("integration_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_integration(
+    Extension(integration): Extension<Integration>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = Integration::delete(&deployment.db().pool, integration.id).await?;
+    if rows_affected == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "integration_deleted",
+            serde_json::json!({
+                "integration_id": integration.id.to_string(),
+                "provider": integration.provider.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Queue a sync run for the integration. If a run is already queued or running it is
+/// returned instead of enqueuing a duplicate. With an `Idempotency-Key` header, a retried
+/// request gets the first reply.
+#[utoipa::path(
+    post,
+    path = "/api/integrations/{integration_id}/sync",
+    tag = "integrations",
+    params(("integration_id" = Uuid, Path), ("Idempotency-Key" = Option<String>, Header)),
+    responses((status = 202, description = "Sync job queued", body = ApiResponse<SyncJob>))
+)]
+pub async fn trigger_sync(
+    Extension(integration): Extension<Integration>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<(StatusCode, ResponseJson<ApiResponse<SyncJob>>), ApiError> {
+    if !integration.enabled {
+        return Err(ApiError::Conflict("Integration is disabled".to_string()));
+    }
+
+    let pool = &deployment.db().pool;
+    if let Some(job) = SyncJob::find_active_for_integration(pool, integration.id).await? {
+        return Ok((
+            StatusCode::ACCEPTED,
+            ResponseJson(ApiResponse::success(job)),
+        ));
+    }
+
+    let job = queue_sync(pool, deployment.jobs(), integration.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "integration_sync_queued",
+            serde_json::json!({
+                "integration_id": integration.id.to_string(),
+                "provider": integration.provider.to_string(),
+            }),
+        )
+        .await;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        ResponseJson(ApiResponse::success(job)),
+    ))
+}
+
+#[derive(Debug, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SyncJobsQuery {
+    #[serde(default)]
+    pub limit: Option<i64>,
+    /// Continue after the page whose `X-Next-Cursor` header this is
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// GET /integrations/{integration_id}/jobs
+/// The integration's sync runs, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/integrations/{integration_id}/jobs",
+    tag = "integrations",
+    params(("integration_id" = Uuid, Path), SyncJobsQuery),
+    responses((
+        status = 200,
+        body = ApiResponse<Vec<SyncJob>>,
+        headers(("X-Next-Cursor" = String))
+    ))
+)]
+pub async fn get_sync_jobs(
+    Extension(integration): Extension<Integration>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<SyncJobsQuery>,
+) -> Result<(HeaderMap, ResponseJson<ApiResponse<Vec<SyncJob>>>), ApiError> {
+    let limit = query.limit.unwrap_or(50).clamp(1, 500);
+    let after = routes::decode_cursor(query.cursor.as_deref())?;
+    let page = SyncJob::find_by_integration_id(
+        &deployment.db().pool,
+        integration.id,
+        after.as_ref(),
+        limit,
+    )
+    .await?;
+    Ok((
+        routes::next_cursor_header(page.next_cursor.as_deref()),
+        ResponseJson(ApiResponse::success(page.items)),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/integrations/jobs/{job_id}",
+    tag = "integrations",
+    params(("job_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<SyncJob>))
+)]
+pub async fn get_sync_job(
+    State(deployment): State<DeploymentImpl>,
+    Path(job_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<SyncJob>>, ApiError> {
+    let job = SyncJob::find_by_id(&deployment.db().pool, job_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    Ok(ResponseJson(ApiResponse::success(job)))
+}
+
+/// Dry-run a sync: fetch remote issues and store the resulting plan without changing tasks.
+#[utoipa::path(
+    post,
+    path = "/api/integrations/{integration_id}/plan",
+    tag = "integrations",
+    params(("integration_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<SyncPlan>))
+)]
+pub async fn create_sync_plan(
+    Extension(integration): Extension<Integration>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<SyncPlan>>, ApiError> {
+    let plan = deployment
+        .integrations()
+        .plan(&deployment.db().pool, &integration)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "integration_sync_planned",
+            serde_json::json!({
+                "integration_id": integration.id.to_string(),
+                "provider": integration.provider.to_string(),
+                "items": plan.items.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(plan)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/integrations/{integration_id}/plans",
+    tag = "integrations",
+    params(("integration_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<SyncPlan>>))
+)]
+pub async fn get_sync_plans(
+    Extension(integration): Extension<Integration>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<SyncPlan>>>, ApiError> {
+    let plans = SyncPlan::find_by_integration_id(&deployment.db().pool, integration.id).await?;
+    Ok(ResponseJson(ApiResponse::success(plans)))
+}
+
+async fn load_plan(
+    deployment: &DeploymentImpl,
+    plan_id: Uuid,
+) -> Result<(SyncPlan, Integration), ApiError> {
+    let pool = &deployment.db().pool;
+    let plan = SyncPlan::find_by_id(pool, plan_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let integration = Integration::find_by_id(pool, plan.integration_id)
+        .await?
+        .ok_or(IntegrationError::NotFound)?;
+    Ok((plan, integration))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/integrations/plans/{plan_id}",
+    tag = "integrations",
+    params(("plan_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<SyncPlan>))
+)]
+pub async fn get_sync_plan(
+    State(deployment): State<DeploymentImpl>,
+    Path(plan_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<SyncPlan>>, ApiError> {
+    let (plan, _) = load_plan(&deployment, plan_id).await?;
+    Ok(ResponseJson(ApiResponse::success(plan)))
+}
+
+/// Plain-text diff report of a plan
+#[utoipa::path(
+    get,
+    path = "/api/integrations/plans/{plan_id}/report",
+    tag = "integrations",
+    params(("plan_id" = Uuid, Path)),
+    responses((status = 200, description = "Plain-text diff report", body = String, content_type = "text/plain"))
+)]
+pub async fn get_sync_plan_report(
+    State(deployment): State<DeploymentImpl>,
+    Path(plan_id): Path<Uuid>,
+) -> Result<String, ApiError> {
+    let (plan, integration) = load_plan(&deployment, plan_id).await?;
+    Ok(plan::render(&integration, &plan))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/integrations/plans/{plan_id}/apply",
+    tag = "integrations",
+    params(("plan_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<SyncSummary>))
+)]
+pub async fn apply_sync_plan(
+    State(deployment): State<DeploymentImpl>,
+    Path(plan_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<SyncSummary>>, ApiError> {
+    let (plan, integration) = load_plan(&deployment, plan_id).await?;
+    let summary = deployment
+        .integrations()
+        .apply_plan(&deployment.db().pool, &integration, &plan)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "integration_sync_plan_applied",
+            serde_json::json!({
+                "integration_id": integration.id.to_string(),
+                "provider": integration.provider.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(summary)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/integrations/plans/{plan_id}/discard",
+    tag = "integrations",
+    params(("plan_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn discard_sync_plan(
+    State(deployment): State<DeploymentImpl>,
+    Path(plan_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let (plan, _) = load_plan(&deployment, plan_id).await?;
+    if !SyncPlan::finish(&deployment.db().pool, plan.id, SyncPlanStatus::Discarded).await? {
+        return Err(ApiError::Conflict("Sync plan is not pending".to_string()));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/integrations/{integration_id}/dead-letters",
+    tag = "integrations",
+    params(("integration_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<SyncDeadLetter>>))
+)]
+pub async fn get_dead_letters(
+    Extension(integration): Extension<Integration>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<SyncDeadLetter>>>, ApiError> {
+    let dead_letters =
+        SyncDeadLetter::find_by_integration_id(&deployment.db().pool, integration.id).await?;
+    Ok(ResponseJson(ApiResponse::success(dead_letters)))
+}
+
+/// Replay a dead-lettered item. Responds with `null` once it imports, or the dead letter
+/// with the new error when it fails again.
+#[utoipa::path(
+    post,
+    path = "/api/integrations/dead-letters/{dead_letter_id}/retry",
+    tag = "integrations",
+    params(("dead_letter_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Option<SyncDeadLetter>>))
+)]
+pub async fn retry_dead_letter(
+    State(deployment): State<DeploymentImpl>,
+    Path(dead_letter_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Option<SyncDeadLetter>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let dead_letter = SyncDeadLetter::find_by_id(pool, dead_letter_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let integration = Integration::find_by_id(pool, dead_letter.integration_id)
+        .await?
+        .ok_or(IntegrationError::NotFound)?;
+
+    let remaining = deployment
+        .integrations()
+        .retry_dead_letter(pool, &integration, &dead_letter)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "integration_dead_letter_retried",
+            serde_json::json!({
+                "integration_id": integration.id.to_string(),
+                "provider": integration.provider.to_string(),
+                "resolved": remaining.is_none(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(remaining)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/integrations/dead-letters/{dead_letter_id}",
+    tag = "integrations",
+    params(("dead_letter_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn discard_dead_letter(
+    State(deployment): State<DeploymentImpl>,
+    Path(dead_letter_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = SyncDeadLetter::delete(&deployment.db().pool, dead_letter_id).await?;
+    if rows_affected == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/integrations/{integration_id}/health",
+    tag = "integrations",
+    params(("integration_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<IntegrationHealth>))
+)]
+pub async fn get_integration_health(
+    Extension(integration): Extension<Integration>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<IntegrationHealth>>, ApiError> {
+    let health = deployment
+        .integrations()
+        .health(&deployment.db().pool, &integration)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(health)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/integrations/{integration_id}/check",
+    tag = "integrations",
+    params(("integration_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<IntegrationCheck>))
+)]
+pub async fn check_integration(
+    Extension(integration): Extension<Integration>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<IntegrationCheck>>, ApiError> {
+    let check = deployment.integrations().check(&integration).await;
+    Ok(ResponseJson(ApiResponse::success(check)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/integrations/audit",
+    tag = "integrations",
+    params(SyncAuditFilter),
+    responses((status = 200, body = ApiResponse<Vec<SyncAuditEntry>>))
+)]
+pub async fn get_sync_audit_log(
+    State(deployment): State<DeploymentImpl>,
+    Query(filter): Query<SyncAuditFilter>,
+) -> Result<ResponseJson<ApiResponse<Vec<SyncAuditEntry>>>, ApiError> {
+    let entries = SyncAuditEntry::find_filtered(&deployment.db().pool, &filter).await?;
+    Ok(ResponseJson(ApiResponse::success(entries)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let integration_router = Router::new()
+        .route(
+            "/",
+            get(get_integration)
+                .put(update_integration)
+                .delete(delete_integration),
+        )
+        .route(
+            "/sync",
+            post(trigger_sync).layer(from_fn_with_state(deployment.clone(), idempotency)),
+        )
+        .route("/jobs", get(get_sync_jobs))
+        .route("/health", get(get_integration_health))
+        .route("/check", post(check_integration))
+        .route("/dead-letters", get(get_dead_letters))
+        .route("/plan", post(create_sync_plan))
+        .route("/plans", get(get_sync_plans))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_integration_middleware,
+        ));
+
+    let inner = Router::new()
+        .route("/", get(get_integrations).post(create_integration))
+        .route("/catalog", get(get_integration_catalog))
+        .route("/jobs/{job_id}", get(get_sync_job))
+        .route("/audit", get(get_sync_audit_log))
+        .route("/plans/{plan_id}", get(get_sync_plan))
+        .route("/plans/{plan_id}/report", get(get_sync_plan_report))
+        .route("/plans/{plan_id}/apply", post(apply_sync_plan))
+        .route("/plans/{plan_id}/discard", post(discard_sync_plan))
+        .route(
+            "/dead-letters/{dead_letter_id}",
+            delete(discard_dead_letter),
+        )
+        .route(
+            "/dead-letters/{dead_letter_id}/retry",
+            post(retry_dead_letter),
+        )
+        .nest("/{integration_id}", integration_router);
+
+    Router::new().nest("/integrations", inner)
+}
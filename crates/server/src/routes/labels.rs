@@ -0,0 +1,223 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    label::{CreateLabel, Label, SetTaskLabels, UpdateLabel},
+    project::Project,
+    task::Task,
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+fn validate_label(name: Option<&str>, color: Option<&str>) -> Result<(), ApiError> {
+    if name.is_some_and(|name| name.trim().is_empty()) {
+        return Err(ApiError::BadRequest(
+            "Label name must not be empty".to_string(),
+        ));
+    }
+    if let Some(color) = color
+        && !Label::is_valid_color(color)
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid label color '{color}', expected #rrggbb"
+        )));
+    }
+    Ok(())
+}
+
+/// Reject a name already used by another label in the project.
+async fn ensure_unique_name(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    name: &str,
+    except: Option<Uuid>,
+) -> Result<(), ApiError> {
+    if let Some(existing) = Label::find_by_name(&deployment.db().pool, project_id, name).await?
+        && Some(existing.id) != except
+    {
+        return Err(ApiError::Conflict(format!(
+            "A label named '{name}' already exists in this project"
+        )));
+    }
+    Ok(())
+}
+
+/// Load a label, checking that it belongs to the project in the path.
+async fn load_label(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    label_id: Uuid,
+) -> Result<Label, ApiError> {
+    Label::find_by_id(&deployment.db().pool, label_id)
+        .await?
+        .filter(|label| label.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/labels",
+    tag = "labels",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<Label>>))
+)]
+pub async fn get_labels(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Label>>>, ApiError> {
+    let labels = Label::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/labels",
+    tag = "labels",
+    params(("id" = Uuid, Path)),
+    request_body = CreateLabel,
+    responses((status = 200, body = ApiResponse<Label>))
+)]
+pub async fn create_label(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateLabel>,
+) -> Result<ResponseJson<ApiResponse<Label>>, ApiError> {
+    validate_label(Some(&payload.name), payload.color.as_deref())?;
+    ensure_unique_name(&deployment, project.id, &payload.name, None).await?;
+
+    let label = Label::create(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "label_created",
+            serde_json::json!({
+                "label_id": label.id.to_string(),
+                "project_id": project.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(label)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/labels/{label_id}",
+    tag = "labels",
+    params(("project_id" = Uuid, Path), ("label_id" = Uuid, Path)),
+    request_body = UpdateLabel,
+    responses((status = 200, body = ApiResponse<Label>))
+)]
+pub async fn update_label(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, label_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateLabel>,
+) -> Result<ResponseJson<ApiResponse<Label>>, ApiError> {
+    let label = load_label(&deployment, project_id, label_id).await?;
+    validate_label(payload.name.as_deref(), payload.color.as_deref())?;
+    if let Some(name) = &payload.name {
+        ensure_unique_name(&deployment, project_id, name, Some(label.id)).await?;
+    }
+
+    let label = Label::update(&deployment.db().pool, label.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(label)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/labels/{label_id}",
+    tag = "labels",
+    params(("project_id" = Uuid, Path), ("label_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_label(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, label_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let label = load_label(&deployment, project_id, label_id).await?;
+    Label::delete(&deployment.db().pool, label.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "label_deleted",
+            serde_json::json!({
+                "label_id": label.id.to_string(),
+                "project_id": project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/labels",
+    tag = "labels",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<Label>>))
+)]
+pub async fn get_task_labels(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Label>>>, ApiError> {
+    let labels = Label::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+/// PUT /tasks/{task_id}/labels
+/// Replace the task's labels. Every label must belong to the task's project.
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}/labels",
+    tag = "labels",
+    params(("task_id" = Uuid, Path)),
+    request_body = SetTaskLabels,
+    responses((status = 200, body = ApiResponse<Vec<Label>>))
+)]
+pub async fn set_task_labels(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetTaskLabels>,
+) -> Result<ResponseJson<ApiResponse<Vec<Label>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project_labels = Label::find_by_project_id(pool, task.project_id).await?;
+    if let Some(unknown) = payload
+        .label_ids
+        .iter()
+        .find(|id| !project_labels.iter().any(|label| label.id == **id))
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Label {unknown} does not belong to the task's project"
+        )));
+    }
+
+    Label::set_for_task(pool, task.id, &payload.label_ids).await?;
+    let labels = Label::find_by_task_id(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+/// Routes nested under `/projects/{id}`, behind the project loading middleware.
+pub fn project_router() -> Router<DeploymentImpl> {
+    Router::new().route("/labels", get(get_labels).post(create_label))
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new().route("/labels", get(get_task_labels).put(set_task_labels))
+}
+
+/// Routes nested under `/projects`. The project loader only understands a single path
+/// parameter, so these load the label themselves.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/{project_id}/labels/{label_id}",
+        put(update_label).delete(delete_label),
+    )
+}
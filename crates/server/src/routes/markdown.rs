@@ -0,0 +1,40 @@
+use axum::{Json, Router, extract::State, response::Json as ResponseJson, routing::post};
+use db::models::project::Project;
+use deployment::Deployment;
+use services::services::markdown::{self, LinkTargets, RenderMarkdown, RenderedMarkdown};
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// POST /markdown/render
+/// Render task or comment Markdown to sanitized HTML, so every client shows it the same
+/// way: fenced code is highlighted and known @mentions and issue keys are linked.
+#[utoipa::path(
+    post,
+    path = "/api/markdown/render",
+    tag = "markdown",
+    request_body = RenderMarkdown,
+    responses((status = 200, body = ApiResponse<RenderedMarkdown>))
+)]
+pub async fn render_markdown(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RenderMarkdown>,
+) -> Result<ResponseJson<ApiResponse<RenderedMarkdown>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if let Some(project_id) = payload.project_id
+        && Project::find_by_id(pool, project_id).await?.is_none()
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Project {project_id} does not exist"
+        )));
+    }
+    let targets = LinkTargets::load(pool, payload.project_id).await?;
+    let html = markdown::render(&payload.markdown, &targets);
+    Ok(ResponseJson(ApiResponse::success(RenderedMarkdown {
+        html,
+    })))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/markdown/render", post(render_markdown))
+}
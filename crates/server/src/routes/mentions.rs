@@ -0,0 +1,35 @@
+use axum::{Extension, Router, extract::State, response::Json as ResponseJson, routing::get};
+use db::models::{api_key::ApiKey, task_mention::TaskMention, user::User};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::AuthUser, rbac::require_self},
+};
+
+/// GET /users/{user_id}/mentions
+/// Tasks whose description or comments @mention the user, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/mentions",
+    tag = "mentions",
+    params(("user_id" = uuid::Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<TaskMention>>))
+)]
+pub async fn get_mentions(
+    Extension(user): Extension<User>,
+    auth_user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskMention>>>, ApiError> {
+    require_self(auth_user.as_deref(), caller.as_deref(), user.id)?;
+    let mentions = TaskMention::find_by_user_id(&deployment.db().pool, user.id).await?;
+    Ok(ResponseJson(ApiResponse::success(mentions)))
+}
+
+/// Routes nested under `/users/{user_id}`, behind the user loading middleware.
+pub fn user_router() -> Router<DeploymentImpl> {
+    Router::new().route("/mentions", get(get_mentions))
+}
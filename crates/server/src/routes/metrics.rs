@@ -0,0 +1,83 @@
+//! Prometheus metrics at `/metrics`, on when `VK_METRICS=1`: HTTP request counts and
+//! latencies, SQLite pool usage, sync runs and webhook deliveries, for alerting on
+//! failing integrations.
+
+use std::{
+    sync::{LazyLock, OnceLock},
+    time::Duration,
+};
+
+use axum::{
+    Router,
+    extract::State,
+    http::{StatusCode, header::CONTENT_TYPE},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use deployment::Deployment;
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+
+use crate::DeploymentImpl;
+
+/// Histogram buckets, in seconds, for request latencies and sync run durations.
+const DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 300.0,
+];
+
+const UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+static ENABLED: LazyLock<bool> = LazyLock::new(|| utils::env_flag("VK_METRICS"));
+
+static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Install the Prometheus recorder when `VK_METRICS` asks for it. Until then, and without
+/// it, recording a metric does nothing. Call once, before the workers start.
+pub fn install() {
+    if !*ENABLED || HANDLE.get().is_some() {
+        return;
+    }
+    let recorder = PrometheusBuilder::new()
+        .set_buckets(DURATION_BUCKETS)
+        .and_then(|builder| builder.install_recorder());
+    match recorder {
+        Ok(handle) => {
+            // Histogram samples pile up between scrapes unless drained
+            let upkeep = handle.clone();
+            tokio::spawn(async move {
+                loop {
+                    tokio::time::sleep(UPKEEP_INTERVAL).await;
+                    upkeep.run_upkeep();
+                }
+            });
+            let _ = HANDLE.set(handle);
+            tracing::info!("Serving Prometheus metrics at /metrics");
+        }
+        Err(e) => tracing::error!("Failed to install the Prometheus recorder: {}", e),
+    }
+}
+
+/// GET /metrics
+/// Everything recorded so far in the Prometheus text format, with the database pool's
+/// gauges read at the time of the scrape.
+async fn render(State(deployment): State<DeploymentImpl>) -> Response {
+    let Some(handle) = HANDLE.get() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let pool = &deployment.db().pool;
+    metrics::gauge!("db_pool_connections").set(pool.size() as f64);
+    metrics::gauge!("db_pool_idle_connections").set(pool.num_idle() as f64);
+    metrics::gauge!("db_pool_max_connections").set(pool.options().get_max_connections() as f64);
+    (
+        [(CONTENT_TYPE, "text/plain; version=0.0.4")],
+        handle.render(),
+    )
+        .into_response()
+}
+
+/// Served at the root next to the health probes, without authentication, so a scraper
+/// needs no credentials.
+pub fn router(deployment: DeploymentImpl) -> Router {
+    Router::new()
+        .route("/metrics", get(render))
+        .with_state(deployment)
+}
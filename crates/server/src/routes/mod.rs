@@ -1,32 +1,99 @@
+use std::net::SocketAddr;
+
 use axum::{
     Router,
-    routing::{IntoMakeService, get},
+    extract::connect_info::IntoMakeServiceWithConnectInfo,
+    http::{HeaderMap, HeaderName, HeaderValue},
+    middleware::{from_fn, from_fn_with_state},
+    routing::get,
 };
+use db::cursor;
+use serde::de::DeserializeOwned;
+use tower_http::compression::CompressionLayer;
 
-use crate::DeploymentImpl;
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{
+        audit::audit,
+        auth::authenticate,
+        cors::CorsConfig,
+        fields::select_fields,
+        json_api::json_api,
+        metrics::{record_route, track_requests},
+        rate_limit::rate_limit,
+        rbac::authorize,
+        telemetry::trace_requests,
+    },
+};
 
+pub mod api_keys;
 pub mod approvals;
+pub mod audit;
+pub mod board;
+pub mod board_ws;
 pub mod config;
+pub mod conflicts;
 pub mod containers;
+pub mod custom_fields;
+pub mod epics;
 pub mod filesystem;
 // pub mod github;
 pub mod events;
 pub mod execution_processes;
 pub mod frontend;
+pub mod graphql;
 pub mod health;
 pub mod images;
+pub mod integrations;
+pub mod labels;
+pub mod markdown;
+pub mod mentions;
+pub mod metrics;
+pub mod notifications;
 pub mod oauth;
+pub mod oidc;
 pub mod organizations;
+pub mod project_archive;
+pub mod project_columns;
+pub mod project_members;
+pub mod project_settings;
 pub mod projects;
+pub mod recurrence;
 pub mod repo;
+pub mod reports;
 pub mod scratch;
+pub mod search;
 pub mod sessions;
+pub mod share_links;
 pub mod shared_tasks;
+pub mod sprints;
 pub mod tags;
+pub mod task_attachments;
 pub mod task_attempts;
+pub mod task_batch;
+pub mod task_bulk;
+pub mod task_checklist;
+pub mod task_clone;
+pub mod task_comments;
+pub mod task_events;
+pub mod task_links;
+pub mod task_merge;
+pub mod task_move;
+pub mod task_quick_add;
+pub mod task_revisions;
+pub mod task_templates;
 pub mod tasks;
+pub mod teams;
+pub mod time_entries;
+pub mod trash;
+pub mod users;
+pub mod views;
+pub mod watchers;
+pub mod webhooks;
+pub mod wip_limits;
 
-pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
+pub fn router(deployment: DeploymentImpl) -> IntoMakeServiceWithConnectInfo<Router, SocketAddr> {
     // Create routers with different middleware layers
     let base_routes = Router::new()
         .route("/health", get(health::health_check))
@@ -34,24 +101,89 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(containers::router(&deployment))
         .merge(projects::router(&deployment))
         .merge(tasks::router(&deployment))
+        .merge(search::router())
+        .merge(markdown::router())
+        .merge(graphql::router())
+        .merge(board_ws::router())
+        .merge(time_entries::router())
         .merge(shared_tasks::router())
+        .merge(share_links::public_router())
         .merge(task_attempts::router(&deployment))
         .merge(execution_processes::router(&deployment))
         .merge(tags::router(&deployment))
+        .merge(users::router(&deployment))
+        .merge(teams::router())
+        .merge(integrations::router(&deployment))
+        .merge(webhooks::router())
+        .merge(conflicts::router())
         .merge(oauth::router())
+        .merge(oidc::router())
         .merge(organizations::router())
         .merge(filesystem::router())
         .merge(repo::router())
         .merge(events::router(&deployment))
         .merge(approvals::router())
+        .merge(api_keys::router())
+        .merge(audit::router())
         .merge(scratch::router(&deployment))
         .merge(sessions::router(&deployment))
         .nest("/images", images::routes())
-        .with_state(deployment);
+        .route_layer(from_fn(record_route))
+        // Layers run bottom-up: rate limit first, then authenticate, record changes in the
+        // audit log, check roles, and trim the response to the requested fields last.
+        // JSON:API clients get every response rewritten, refusals included. Every request
+        // is counted and timed for `/metrics`, by the route it matched, and traced when
+        // OpenTelemetry export is on
+        .layer(from_fn(select_fields))
+        .layer(from_fn_with_state(deployment.clone(), authorize))
+        .layer(from_fn_with_state(deployment.clone(), audit))
+        .layer(from_fn_with_state(deployment.clone(), authenticate))
+        .layer(from_fn_with_state(deployment.clone(), rate_limit))
+        .layer(from_fn(json_api))
+        .layer(from_fn(track_requests))
+        .layer(from_fn(trace_requests))
+        .with_state(deployment.clone());
 
-    Router::new()
+    let app = Router::new()
         .route("/", get(frontend::serve_frontend_root))
         .route("/{*path}", get(frontend::serve_frontend))
         .nest("/api", base_routes)
-        .into_make_service()
+        .merge(crate::openapi::router())
+        .merge(health::probes(deployment.clone()))
+        .merge(metrics::router(deployment.clone()))
+        // gRPC calls are routed by their `/vibe_kanban.v1.<Service>/<Method>` paths
+        .merge(crate::grpc::router(&deployment))
+        // gzip or brotli, as the client accepts; event streams and images are left alone
+        .layer(CompressionLayer::new());
+    // Outermost, so preflight requests are answered before authentication
+    let app = match CorsConfig::from_env() {
+        Some(cors) => app.layer(cors.layer()),
+        None => app,
+    };
+    // Rate limits for requests without an API key go by the client's address
+    app.into_make_service_with_connect_info::<SocketAddr>()
+}
+
+/// Response header of cursor-paginated listings naming the cursor of the next page; absent
+/// on the last page.
+pub const NEXT_CURSOR: HeaderName = HeaderName::from_static("x-next-cursor");
+
+pub(crate) fn next_cursor_header(next_cursor: Option<&str>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Some(value) = next_cursor.and_then(|cursor| HeaderValue::from_str(cursor).ok()) {
+        headers.insert(NEXT_CURSOR, value);
+    }
+    headers
+}
+
+/// The sort key a `cursor` query parameter points after, refusing cursors that weren't
+/// handed out by the same listing.
+pub(crate) fn decode_cursor<K: DeserializeOwned>(
+    cursor: Option<&str>,
+) -> Result<Option<K>, ApiError> {
+    cursor
+        .map(|cursor| {
+            cursor::decode(cursor).ok_or_else(|| ApiError::BadRequest("Invalid cursor".to_string()))
+        })
+        .transpose()
 }
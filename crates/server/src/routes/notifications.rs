@@ -0,0 +1,80 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    api_key::ApiKey,
+    notification::{MarkNotificationsRead, Notification},
+    user::User,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use utils::response::ApiResponse;
+use utoipa::IntoParams;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::AuthUser, rbac::require_self},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NotificationQuery {
+    #[serde(default)]
+    pub unread_only: bool,
+}
+
+/// GET /users/{user_id}/notifications
+/// Activity on the tasks the user watches, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/notifications",
+    tag = "notifications",
+    params(("user_id" = uuid::Uuid, Path), NotificationQuery),
+    responses((status = 200, body = ApiResponse<Vec<Notification>>))
+)]
+pub async fn get_notifications(
+    Extension(user): Extension<User>,
+    auth_user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<NotificationQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<Notification>>>, ApiError> {
+    require_self(auth_user.as_deref(), caller.as_deref(), user.id)?;
+    let notifications =
+        Notification::find_by_user_id(&deployment.db().pool, user.id, query.unread_only).await?;
+    Ok(ResponseJson(ApiResponse::success(notifications)))
+}
+
+/// POST /users/{user_id}/notifications/read
+/// Returns how many notifications were newly marked read.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/notifications/read",
+    tag = "notifications",
+    params(("user_id" = uuid::Uuid, Path)),
+    request_body = MarkNotificationsRead,
+    responses((status = 200, body = ApiResponse<u64>))
+)]
+pub async fn mark_notifications_read(
+    Extension(user): Extension<User>,
+    auth_user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<MarkNotificationsRead>,
+) -> Result<ResponseJson<ApiResponse<u64>>, ApiError> {
+    require_self(auth_user.as_deref(), caller.as_deref(), user.id)?;
+    let marked =
+        Notification::mark_read(&deployment.db().pool, user.id, payload.ids.as_deref()).await?;
+    Ok(ResponseJson(ApiResponse::success(marked)))
+}
+
+/// Routes nested under `/users/{user_id}`, behind the user loading middleware.
+pub fn user_router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/notifications", get(get_notifications))
+        .route("/notifications/read", post(mark_notifications_read))
+}
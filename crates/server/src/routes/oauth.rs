@@ -60,6 +60,13 @@ struct HandoffInitResponseBody {
     authorize_url: String,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/handoff/init",
+    tag = "oauth",
+    request_body = Object,
+    responses((status = 200, body = Object))
+)]
 async fn handoff_init(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<HandoffInitPayload>,
@@ -98,6 +105,13 @@ struct HandoffCompleteQuery {
     error: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/auth/handoff/complete",
+    tag = "oauth",
+    params(("handoff_id" = Uuid, Query), ("app_code" = Option<String>, Query), ("error" = Option<String>, Query)),
+    responses((status = 200, description = "Page closing the login window", body = String, content_type = "text/html"))
+)]
 async fn handoff_complete(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<HandoffCompleteQuery>,
@@ -222,6 +236,12 @@ async fn handoff_complete(
     )))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout",
+    tag = "oauth",
+    responses((status = 204, description = "Logged out"))
+)]
 async fn logout(State(deployment): State<DeploymentImpl>) -> Result<StatusCode, ApiError> {
     let auth_context = deployment.auth_context();
 
@@ -239,6 +259,12 @@ async fn logout(State(deployment): State<DeploymentImpl>) -> Result<StatusCode,
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/auth/status",
+    tag = "oauth",
+    responses((status = 200, body = Object))
+)]
 async fn status(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<StatusResponse>>, ApiError> {
@@ -261,6 +287,12 @@ async fn status(
 }
 
 /// Returns the current access token (auto-refreshes if needed)
+#[utoipa::path(
+    get,
+    path = "/api/auth/token",
+    tag = "oauth",
+    responses((status = 200, body = Object))
+)]
 async fn get_token(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<TokenResponse>>, ApiError> {
@@ -281,6 +313,12 @@ async fn get_token(
     })))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/auth/user",
+    tag = "oauth",
+    responses((status = 200, body = Object))
+)]
 async fn get_current_user(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<CurrentUserResponse>>, ApiError> {
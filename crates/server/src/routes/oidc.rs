@@ -0,0 +1,198 @@
+use std::sync::LazyLock;
+
+use axum::{
+    Extension, Router,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode, header::SET_COOKIE},
+    response::{IntoResponse, Json as ResponseJson, Redirect},
+    routing::{get, post},
+};
+use chrono::{Duration, Utc};
+use db::models::{user::User, user_session::UserSession};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::oidc::{LOGIN_TIMEOUT, OidcService};
+use utils::response::ApiResponse;
+use utoipa::IntoParams;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::auth::{
+        AuthUser, SESSION_COOKIE, cookie, generate_session_token, hash_token, session_token,
+    },
+};
+
+/// How long a sign-in lasts.
+const SESSION_TTL_DAYS: i64 = 30;
+
+/// Holds the `state` of the browser's sign-in until the provider sends it back, so a
+/// callback can't be completed in a browser that didn't start it.
+const LOGIN_STATE_COOKIE: &str = "vk_oidc_state";
+
+/// Configured from `VK_OIDC_*` environment variables; `None` when sign-in is off.
+static OIDC: LazyLock<Option<OidcService>> = LazyLock::new(OidcService::from_env);
+
+fn oidc() -> Result<&'static OidcService, ApiError> {
+    OIDC.as_ref()
+        .ok_or_else(|| ApiError::Conflict("OpenID Connect sign-in is not configured".to_string()))
+}
+
+fn set_cookie(name: &str, value: &str, max_age: Duration) -> String {
+    let mut cookie = format!(
+        "{name}={value}; Path=/; HttpOnly; SameSite=Lax; Max-Age={}",
+        max_age.num_seconds()
+    );
+    // Mark the cookie secure when the server is reached over HTTPS
+    if OIDC
+        .as_ref()
+        .is_some_and(|oidc| oidc.config().redirect_url.starts_with("https://"))
+    {
+        cookie.push_str("; Secure");
+    }
+    cookie
+}
+
+fn session_cookie(value: &str, max_age: Duration) -> String {
+    set_cookie(SESSION_COOKIE, value, max_age)
+}
+
+fn login_state_cookie(value: &str, max_age: Duration) -> String {
+    set_cookie(LOGIN_STATE_COOKIE, value, max_age)
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OidcCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    /// Set instead of `code` when the provider refused the sign-in
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+/// GET /auth/oidc/login
+/// Send the browser to the identity provider to sign in, remembering the sign-in in a
+/// cookie.
+#[utoipa::path(
+    get,
+    path = "/api/auth/oidc/login",
+    tag = "oidc",
+    responses((status = 303, description = "Redirects to the identity provider"))
+)]
+pub async fn login() -> Result<impl IntoResponse, ApiError> {
+    let login = oidc()?.begin_login().await?;
+    let max_age = Duration::from_std(LOGIN_TIMEOUT).unwrap_or_else(|_| Duration::minutes(10));
+    Ok((
+        [(SET_COOKIE, login_state_cookie(&login.state, max_age))],
+        Redirect::to(&login.url),
+    ))
+}
+
+/// GET /auth/oidc/callback
+/// Where the identity provider sends the browser back to. Only the browser that started
+/// the sign-in can finish it. Signs the user in with a session cookie, creating the user
+/// on their first sign-in, and returns to the app.
+#[utoipa::path(
+    get,
+    path = "/api/auth/oidc/callback",
+    tag = "oidc",
+    params(OidcCallbackQuery),
+    responses((status = 303, description = "Sets the session cookie and redirects to the app"))
+)]
+pub async fn callback(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+    Query(query): Query<OidcCallbackQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let oidc = oidc()?;
+    if let Some(error) = query.error {
+        let description = query.error_description.unwrap_or_default();
+        return Err(ApiError::BadRequest(
+            format!("Sign-in failed: {error} {description}")
+                .trim_end()
+                .to_string(),
+        ));
+    }
+    let (Some(code), Some(state)) = (query.code, query.state) else {
+        return Err(ApiError::BadRequest(
+            "Missing code or state in callback".to_string(),
+        ));
+    };
+    if cookie(&headers, LOGIN_STATE_COOKIE) != Some(state.as_str()) {
+        return Err(ApiError::BadRequest(
+            "Sign-in was started in another browser; sign in again".to_string(),
+        ));
+    }
+
+    let profile = oidc.complete_login(&code, &state).await?;
+    let pool = &deployment.db().pool;
+    let user = OidcService::resolve_user(pool, &profile).await?;
+
+    let now = Utc::now();
+    let ttl = Duration::days(SESSION_TTL_DAYS);
+    UserSession::delete_expired(pool, now).await?;
+    let token = generate_session_token();
+    UserSession::create(pool, user.id, &hash_token(&token), now + ttl).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "oidc_login",
+            serde_json::json!({ "user_id": user.id.to_string() }),
+        )
+        .await;
+
+    Ok((
+        [
+            (SET_COOKIE, session_cookie(&token, ttl)),
+            (SET_COOKIE, login_state_cookie("", Duration::zero())),
+        ],
+        Redirect::to("/"),
+    ))
+}
+
+/// GET /auth/oidc/session
+/// The signed-in user; 401 when the request has no session.
+#[utoipa::path(
+    get,
+    path = "/api/auth/oidc/session",
+    tag = "oidc",
+    responses((status = 200, body = ApiResponse<User>))
+)]
+pub async fn get_session(
+    user: Option<Extension<AuthUser>>,
+) -> Result<ResponseJson<ApiResponse<User>>, ApiError> {
+    let Some(Extension(AuthUser(user))) = user else {
+        return Err(ApiError::Unauthorized);
+    };
+    Ok(ResponseJson(ApiResponse::success(user)))
+}
+
+/// POST /auth/oidc/logout
+/// End the session and clear its cookie.
+#[utoipa::path(
+    post,
+    path = "/api/auth/oidc/logout",
+    tag = "oidc",
+    responses((status = 204, description = "Signed out"))
+)]
+pub async fn logout(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    if let Some(token) = session_token(&headers) {
+        UserSession::delete_by_hash(&deployment.db().pool, &hash_token(token)).await?;
+    }
+    Ok((
+        StatusCode::NO_CONTENT,
+        [(SET_COOKIE, session_cookie("", Duration::zero()))],
+    ))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/auth/oidc/login", get(login))
+        .route("/auth/oidc/callback", get(callback))
+        .route("/auth/oidc/session", get(get_session))
+        .route("/auth/oidc/logout", post(logout))
+}
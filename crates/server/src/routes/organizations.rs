@@ -56,6 +56,13 @@ pub fn router() -> Router<DeploymentImpl> {
         )
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/organizations/{org_id}/projects",
+    tag = "organizations",
+    params(("org_id" = Uuid, Path)),
+    responses((status = 200, body = Object))
+)]
 async fn list_organization_projects(
     State(deployment): State<DeploymentImpl>,
     Path(org_id): Path<Uuid>,
@@ -67,6 +74,12 @@ async fn list_organization_projects(
     Ok(ResponseJson(ApiResponse::success(response.projects)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/organizations",
+    tag = "organizations",
+    responses((status = 200, body = Object))
+)]
 async fn list_organizations(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<ListOrganizationsResponse>>, ApiError> {
@@ -77,6 +90,13 @@ async fn list_organizations(
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/organizations/{id}",
+    tag = "organizations",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = Object))
+)]
 async fn get_organization(
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<Uuid>,
@@ -88,6 +108,13 @@ async fn get_organization(
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/organizations",
+    tag = "organizations",
+    request_body = Object,
+    responses((status = 200, body = Object))
+)]
 async fn create_organization(
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<CreateOrganizationRequest>,
@@ -108,6 +135,14 @@ async fn create_organization(
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
+#[utoipa::path(
+    patch,
+    path = "/api/organizations/{id}",
+    tag = "organizations",
+    params(("id" = Uuid, Path)),
+    request_body = Object,
+    responses((status = 200, body = Object))
+)]
 async fn update_organization(
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<Uuid>,
@@ -120,6 +155,13 @@ async fn update_organization(
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/organizations/{id}",
+    tag = "organizations",
+    params(("id" = Uuid, Path)),
+    responses((status = 204, description = "Deleted"))
+)]
 async fn delete_organization(
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<Uuid>,
@@ -131,6 +173,14 @@ async fn delete_organization(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/organizations/{org_id}/invitations",
+    tag = "organizations",
+    params(("org_id" = Uuid, Path)),
+    request_body = Object,
+    responses((status = 200, body = Object))
+)]
 async fn create_invitation(
     State(deployment): State<DeploymentImpl>,
     Path(org_id): Path<Uuid>,
@@ -154,6 +204,13 @@ async fn create_invitation(
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/organizations/{org_id}/invitations",
+    tag = "organizations",
+    params(("org_id" = Uuid, Path)),
+    responses((status = 200, body = Object))
+)]
 async fn list_invitations(
     State(deployment): State<DeploymentImpl>,
     Path(org_id): Path<Uuid>,
@@ -165,6 +222,13 @@ async fn list_invitations(
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/invitations/{token}",
+    tag = "organizations",
+    params(("token" = String, Path)),
+    responses((status = 200, body = Object))
+)]
 async fn get_invitation(
     State(deployment): State<DeploymentImpl>,
     Path(token): Path<String>,
@@ -176,6 +240,14 @@ async fn get_invitation(
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/organizations/{org_id}/invitations/revoke",
+    tag = "organizations",
+    params(("org_id" = Uuid, Path)),
+    request_body = Object,
+    responses((status = 204, description = "Revoked"))
+)]
 async fn revoke_invitation(
     State(deployment): State<DeploymentImpl>,
     Path(org_id): Path<Uuid>,
@@ -190,6 +262,13 @@ async fn revoke_invitation(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/invitations/{token}/accept",
+    tag = "organizations",
+    params(("token" = String, Path)),
+    responses((status = 200, body = Object))
+)]
 async fn accept_invitation(
     State(deployment): State<DeploymentImpl>,
     Path(invitation_token): Path<String>,
@@ -201,6 +280,13 @@ async fn accept_invitation(
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/organizations/{org_id}/members",
+    tag = "organizations",
+    params(("org_id" = Uuid, Path)),
+    responses((status = 200, body = Object))
+)]
 async fn list_members(
     State(deployment): State<DeploymentImpl>,
     Path(org_id): Path<Uuid>,
@@ -212,6 +298,13 @@ async fn list_members(
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/organizations/{org_id}/members/{user_id}",
+    tag = "organizations",
+    params(("org_id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses((status = 204, description = "Removed"))
+)]
 async fn remove_member(
     State(deployment): State<DeploymentImpl>,
     Path((org_id, user_id)): Path<(Uuid, Uuid)>,
@@ -223,6 +316,14 @@ async fn remove_member(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    patch,
+    path = "/api/organizations/{org_id}/members/{user_id}/role",
+    tag = "organizations",
+    params(("org_id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    request_body = Object,
+    responses((status = 200, body = Object))
+)]
 async fn update_member_role(
     State(deployment): State<DeploymentImpl>,
     Path((org_id, user_id)): Path<(Uuid, Uuid)>,
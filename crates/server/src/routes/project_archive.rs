@@ -0,0 +1,177 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    http::{HeaderMap, HeaderValue, header},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    api_key::ApiKey,
+    project::Project,
+    project_archive::{
+        ArchiveImportError, ImportOptions, ImportReport, NameConflict, ProjectArchive,
+    },
+    project_member::{ProjectMember, ProjectRole},
+    team::TeamRole,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::IntoParams;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::AuthUser, rbac::require_team_role},
+};
+
+/// A file name for the project's archive, keeping only characters safe in any file system.
+fn archive_file_name(project: &Project) -> String {
+    let slug: String = project
+        .name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let slug = slug
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let slug = if slug.is_empty() { "project" } else { &slug };
+    format!("{slug}-{}.vk.json", chrono::Utc::now().format("%Y%m%d"))
+}
+
+/// GET /projects/{project_id}/export
+/// The whole project as a versioned JSON archive: settings, columns, labels, custom
+/// fields, tasks with their checklists and comments, task links and integration configs.
+/// Secrets, attachments and activity history are left out. The body is the bare archive,
+/// not wrapped in the usual response envelope, so it can be saved and imported as-is.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/export",
+    tag = "projects",
+    params(("id" = uuid::Uuid, Path)),
+    responses((status = 200, body = ProjectArchive))
+)]
+pub async fn export_project(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<(HeaderMap, Json<ProjectArchive>), ApiError> {
+    let archive = ProjectArchive::export(&deployment.db().pool, &project).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_exported",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "task_count": archive.tasks.len(),
+            }),
+        )
+        .await;
+
+    let mut headers = HeaderMap::new();
+    if let Ok(disposition) = HeaderValue::from_str(&format!(
+        "attachment; filename=\"{}\"",
+        archive_file_name(&project)
+    )) {
+        headers.insert(header::CONTENT_DISPOSITION, disposition);
+    }
+    Ok((headers, Json(archive)))
+}
+
+#[derive(Debug, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportQuery {
+    /// Only check the archive and report what would be created
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Name of the new project, the archived name when omitted
+    #[serde(default)]
+    pub name: Option<String>,
+    /// What to do when a project already has the name, `rename` when omitted
+    #[serde(default)]
+    pub on_conflict: Option<NameConflict>,
+}
+
+/// POST /projects/import
+/// Create a project from an archive made by the export endpoint. Everything gets new
+/// ids, so an archive can be imported any number of times, including next to the project
+/// it came from. Assignees are matched to existing users, integrations come in disabled
+/// until their secrets are entered again, and with `dry_run` nothing is kept.
+#[utoipa::path(
+    post,
+    path = "/api/projects/import",
+    tag = "projects",
+    params(ImportQuery),
+    request_body = ProjectArchive,
+    responses((status = 200, body = ApiResponse<ImportReport>))
+)]
+pub async fn import_project(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ImportQuery>,
+    Json(archive): Json<ProjectArchive>,
+) -> Result<ResponseJson<ApiResponse<ImportReport>>, ApiError> {
+    let team_id = caller.as_ref().and_then(|Extension(key)| key.team_id);
+    if let Some(team_id) = team_id {
+        require_team_role(
+            &deployment,
+            user.as_deref(),
+            caller.as_deref(),
+            team_id,
+            TeamRole::Member,
+        )
+        .await?;
+    }
+    let pool = &deployment.db().pool;
+    let options = ImportOptions {
+        name: query.name,
+        on_conflict: query.on_conflict.unwrap_or_default(),
+        team_id,
+        dry_run: query.dry_run,
+    };
+    let report = archive
+        .import(pool, &options)
+        .await
+        .map_err(|err| match err {
+            ArchiveImportError::Database(err) => ApiError::Database(err),
+            ArchiveImportError::Invalid(_) => ApiError::BadRequest(err.to_string()),
+            ArchiveImportError::NameTaken(_) => ApiError::Conflict(err.to_string()),
+        })?;
+
+    if let Some(project) = &report.project {
+        if let Some(Extension(AuthUser(user))) = &user {
+            ProjectMember::set(pool, project.id, user.id, ProjectRole::Admin).await?;
+        }
+        deployment
+            .track_if_analytics_allowed(
+                "project_imported",
+                serde_json::json!({
+                    "project_id": project.id.to_string(),
+                    "task_count": report.tasks,
+                    "archive_version": archive.version,
+                }),
+            )
+            .await;
+    }
+
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+/// Routes nested under `/projects/{id}`, behind the project loading middleware.
+pub fn project_router() -> Router<DeploymentImpl> {
+    Router::new().route("/export", get(export_project))
+}
+
+/// Routes nested under `/projects`.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/import", post(import_project))
+}
@@ -0,0 +1,339 @@
+use std::collections::HashSet;
+
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, post, put},
+};
+use db::models::{
+    board,
+    project::Project,
+    project_column::{
+        self, CreateProjectColumn, MoveTask, ProjectColumn, ReorderProjectColumns,
+        UpdateProjectColumn,
+    },
+    task::Task,
+    task_event::{TaskEvent, TaskEventSource},
+};
+use deployment::Deployment;
+use services::services::share::ShareError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::{tasks::ensure_shared_task_auth, wip_limits},
+};
+
+fn validate_name(name: &str) -> Result<(), ApiError> {
+    if name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Column name must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Load a column, checking that it belongs to the project in the path.
+async fn load_column(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    column_id: Uuid,
+) -> Result<ProjectColumn, ApiError> {
+    ProjectColumn::find_by_id(&deployment.db().pool, column_id)
+        .await?
+        .filter(|column| column.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+fn last_in_category(column: &ProjectColumn) -> ApiError {
+    ApiError::Conflict(format!(
+        "'{}' is the only '{}' column; add another one first",
+        column.name, column.category
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/columns",
+    tag = "project_columns",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<ProjectColumn>>))
+)]
+pub async fn get_columns(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectColumn>>>, ApiError> {
+    let columns = ProjectColumn::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(columns)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/columns",
+    tag = "project_columns",
+    params(("id" = Uuid, Path)),
+    request_body = CreateProjectColumn,
+    responses((status = 200, body = ApiResponse<ProjectColumn>))
+)]
+pub async fn create_column(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateProjectColumn>,
+) -> Result<ResponseJson<ApiResponse<ProjectColumn>>, ApiError> {
+    validate_name(&payload.name)?;
+
+    let column = ProjectColumn::create(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_column_created",
+            serde_json::json!({
+                "column_id": column.id.to_string(),
+                "project_id": project.id.to_string(),
+                "category": column.category.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(column)))
+}
+
+/// PUT /projects/{project_id}/columns/order
+/// Reorder the board. The payload must list every column of the project exactly once.
+#[utoipa::path(
+    put,
+    path = "/api/projects/{id}/columns/order",
+    tag = "project_columns",
+    params(("id" = Uuid, Path)),
+    request_body = ReorderProjectColumns,
+    responses((status = 200, body = ApiResponse<Vec<ProjectColumn>>))
+)]
+pub async fn reorder_columns(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ReorderProjectColumns>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectColumn>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let current: HashSet<Uuid> = ProjectColumn::find_by_project_id(pool, project.id)
+        .await?
+        .into_iter()
+        .map(|column| column.id)
+        .collect();
+    let requested: HashSet<Uuid> = payload.column_ids.iter().copied().collect();
+    if requested.len() != payload.column_ids.len() || requested != current {
+        return Err(ApiError::BadRequest(
+            "column_ids must list every column of the project exactly once".to_string(),
+        ));
+    }
+
+    ProjectColumn::reorder(pool, project.id, &payload.column_ids).await?;
+    let columns = ProjectColumn::find_by_project_id(pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(columns)))
+}
+
+/// PUT /projects/{project_id}/columns/{column_id}
+/// Rename a column or change its category. A new category becomes the status of every
+/// task in the column.
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/columns/{column_id}",
+    tag = "project_columns",
+    params(("project_id" = Uuid, Path), ("column_id" = Uuid, Path)),
+    request_body = UpdateProjectColumn,
+    responses((status = 200, body = ApiResponse<ProjectColumn>))
+)]
+pub async fn update_column(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, column_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateProjectColumn>,
+) -> Result<ResponseJson<ApiResponse<ProjectColumn>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let column = load_column(&deployment, project_id, column_id).await?;
+    if let Some(name) = &payload.name {
+        validate_name(name)?;
+    }
+    let category = payload
+        .category
+        .clone()
+        .unwrap_or_else(|| column.category.clone());
+    let recategorized = category != column.category;
+    if recategorized {
+        let columns = ProjectColumn::find_by_project_id(pool, project_id).await?;
+        if ProjectColumn::sibling(&columns, &column).is_none() {
+            return Err(last_in_category(&column));
+        }
+    }
+
+    let name = payload.name.as_deref().unwrap_or(&column.name);
+    let updated = ProjectColumn::update(pool, column.id, name, &category).await?;
+
+    if recategorized {
+        let publisher = deployment.share_publisher().ok();
+        for task_id in ProjectColumn::find_task_ids(pool, column.id).await? {
+            let Some(before) = Task::find_by_id(pool, task_id).await? else {
+                continue;
+            };
+            let task = Task::move_to_column(pool, task_id, column.id, category.clone()).await?;
+            TaskEvent::record_changes(pool, &before, &task, TaskEventSource::User, None).await?;
+            if task.shared_task_id.is_some()
+                && let Some(publisher) = &publisher
+                && let Err(e) = publisher.update_shared_task(&task).await
+            {
+                tracing::warn!("Failed to publish status of shared task {}: {}", task.id, e);
+            }
+        }
+    }
+
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+/// DELETE /projects/{project_id}/columns/{column_id}
+/// Delete a column. Its tasks move to the next column of the same category.
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/columns/{column_id}",
+    tag = "project_columns",
+    params(("project_id" = Uuid, Path), ("column_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_column(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, column_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let column = load_column(&deployment, project_id, column_id).await?;
+    let columns = ProjectColumn::find_by_project_id(pool, project_id).await?;
+    let Some(fallback) = ProjectColumn::sibling(&columns, &column) else {
+        return Err(last_in_category(&column));
+    };
+
+    ProjectColumn::delete(pool, column.id, fallback.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_column_deleted",
+            serde_json::json!({
+                "column_id": column.id.to_string(),
+                "project_id": project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// POST /tasks/{task_id}/move
+/// Move a task to a column and position on the board. Its status becomes the column's
+/// category, subject to that status's WIP limit.
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/move",
+    tag = "project_columns",
+    params(("task_id" = Uuid, Path)),
+    request_body = MoveTask,
+    responses((status = 200, body = ApiResponse<Task>))
+)]
+pub async fn move_task(
+    Extension(existing_task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<MoveTask>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    ensure_shared_task_auth(&existing_task, &deployment).await?;
+    let pool = &deployment.db().pool;
+    let columns = ProjectColumn::find_by_project_id(pool, existing_task.project_id).await?;
+    let column = match (payload.column_id, &payload.status) {
+        (Some(column_id), status) => {
+            let column = columns
+                .iter()
+                .find(|column| column.id == column_id)
+                .ok_or_else(|| {
+                    ApiError::BadRequest(format!(
+                        "Column {column_id} does not belong to the task's project"
+                    ))
+                })?;
+            if let Some(status) = status
+                && *status != column.category
+            {
+                return Err(ApiError::BadRequest(format!(
+                    "Column '{}' holds {} tasks, not {status}",
+                    column.name, column.category
+                )));
+            }
+            Some(column)
+        }
+        (None, Some(status)) if *status != existing_task.status => {
+            columns.iter().find(|column| column.category == *status)
+        }
+        (None, _) => board::column_for(&columns, &existing_task),
+    }
+    .cloned()
+    .ok_or_else(|| ApiError::BadRequest("The project has no column to move to".to_string()))?;
+
+    let wip_warning = if column.category != existing_task.status
+        && existing_task.archived_at.is_none()
+    {
+        wip_limits::check_move(&deployment, existing_task.project_id, &column.category, 1).await?
+    } else {
+        None
+    };
+
+    let task = if existing_task.column_id != Some(column.id)
+        || column.category != existing_task.status
+    {
+        let task = Task::move_to_column(pool, existing_task.id, column.id, column.category).await?;
+        TaskEvent::record_changes(pool, &existing_task, &task, TaskEventSource::User, None).await?;
+        task
+    } else {
+        existing_task.clone()
+    };
+
+    let others = ProjectColumn::find_task_ranks(pool, column.id, task.id).await?;
+    let position = payload.position.unwrap_or(others.len());
+    let ranks: Vec<f64> = others.iter().map(|(_, rank)| *rank).collect();
+    let rank = match project_column::rank_at(&ranks, position) {
+        Some(rank) => rank,
+        None => {
+            let task_ids: Vec<Uuid> = others.iter().map(|(id, _)| *id).collect();
+            ProjectColumn::renumber_tasks(pool, &task_ids).await?;
+            let ranks: Vec<f64> = (0..task_ids.len()).map(|rank| rank as f64).collect();
+            project_column::rank_at(&ranks, position).unwrap_or_default()
+        }
+    };
+    let task = Task::update_rank(pool, task.id, rank).await?;
+
+    if task.shared_task_id.is_some() && task.status != existing_task.status {
+        let Ok(publisher) = deployment.share_publisher() else {
+            return Err(ShareError::MissingConfig("share publisher unavailable").into());
+        };
+        publisher.update_shared_task(&task).await?;
+    }
+
+    Ok(ResponseJson(match wip_warning {
+        Some(warning) => ApiResponse::success_with_message(task, &warning),
+        None => ApiResponse::success(task),
+    }))
+}
+
+/// Routes nested under `/projects/{id}`, behind the project loading middleware.
+pub fn project_router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/columns", get(get_columns).post(create_column))
+        .route("/columns/order", put(reorder_columns))
+}
+
+/// Routes nested under `/projects`. The project loader only understands a single path
+/// parameter, so these load the column themselves.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/{project_id}/columns/{column_id}",
+        put(update_column).delete(delete_column),
+    )
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new().route("/move", post(move_task))
+}
@@ -0,0 +1,134 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    project::{Project, ProjectError},
+    project_member::{ProjectMember, ProjectRole, SetProjectMember},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::users};
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/members",
+    tag = "project_members",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<ProjectMember>>))
+)]
+pub async fn get_project_members(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectMember>>>, ApiError> {
+    let members = ProjectMember::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(members)))
+}
+
+/// Refuse changes that would leave a project with members but no admin to manage them.
+async fn ensure_admin_remains(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    user_id: Uuid,
+    new_role: Option<ProjectRole>,
+) -> Result<(), ApiError> {
+    let members = ProjectMember::find_by_project_id(&deployment.db().pool, project_id).await?;
+    let others = members.iter().filter(|member| member.user_id != user_id);
+    let admin_remains = new_role == Some(ProjectRole::Admin)
+        || others
+            .clone()
+            .any(|member| member.role == ProjectRole::Admin);
+    let has_members = new_role.is_some() || others.count() > 0;
+    if has_members && !admin_remains {
+        return Err(ApiError::Conflict(
+            "A project with members needs at least one admin".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// PUT /projects/{project_id}/members/{user_id}
+/// Add a user to the project or change their role. Once a project has members, other
+/// signed-in users can no longer reach it, so the first member must be an admin.
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/members/{user_id}",
+    tag = "project_members",
+    params(("project_id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    request_body = SetProjectMember,
+    responses((status = 200, body = ApiResponse<ProjectMember>))
+)]
+pub async fn set_project_member(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, user_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<SetProjectMember>,
+) -> Result<ResponseJson<ApiResponse<ProjectMember>>, ApiError> {
+    let pool = &deployment.db().pool;
+    Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+    users::ensure_exists(&deployment, user_id).await?;
+    ensure_admin_remains(&deployment, project_id, user_id, Some(payload.role)).await?;
+
+    ProjectMember::set(pool, project_id, user_id, payload.role).await?;
+    let member = ProjectMember::find(pool, project_id, user_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_member_set",
+            serde_json::json!({
+                "project_id": project_id.to_string(),
+                "role": payload.role,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(member)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/members/{user_id}",
+    tag = "project_members",
+    params(("project_id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn remove_project_member(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ensure_admin_remains(&deployment, project_id, user_id, None).await?;
+    let removed = ProjectMember::remove(&deployment.db().pool, project_id, user_id).await?;
+    if removed == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_member_removed",
+            serde_json::json!({ "project_id": project_id.to_string() }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Routes nested under `/projects/{id}`, behind the project loading middleware.
+pub fn project_router() -> Router<DeploymentImpl> {
+    Router::new().route("/members", get(get_project_members))
+}
+
+/// Routes nested under `/projects`. The project loader only understands a single path
+/// parameter, so these load the project themselves.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/{project_id}/members/{user_id}",
+        put(set_project_member).delete(remove_project_member),
+    )
+}
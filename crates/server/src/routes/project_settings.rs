@@ -0,0 +1,65 @@
+use axum::{Extension, Json, Router, extract::State, response::Json as ResponseJson, routing::get};
+use db::models::{project::Project, project_settings::ProjectSettings};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/settings",
+    tag = "project_settings",
+    params(("id" = uuid::Uuid, Path)),
+    responses((status = 200, body = ApiResponse<ProjectSettings>))
+)]
+pub async fn get_project_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectSettings>>, ApiError> {
+    let settings = deployment
+        .events()
+        .board_cache()
+        .settings(&deployment.db().pool, project.id)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// PUT /projects/{project_id}/settings
+/// Replace the project's settings. Settings left out are reset to their defaults.
+#[utoipa::path(
+    put,
+    path = "/api/projects/{id}/settings",
+    tag = "project_settings",
+    params(("id" = uuid::Uuid, Path)),
+    request_body = ProjectSettings,
+    responses((status = 200, body = ApiResponse<ProjectSettings>))
+)]
+pub async fn set_project_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ProjectSettings>,
+) -> Result<ResponseJson<ApiResponse<ProjectSettings>>, ApiError> {
+    payload.validate().map_err(ApiError::BadRequest)?;
+    let settings = ProjectSettings::set(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_settings_updated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "state_mapping_count": settings.state_mappings.len(),
+                "auto_archive": settings.auto_archive_after_days.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Routes nested under `/projects/{id}`, behind the project loading middleware.
+pub fn project_router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/settings",
+        get(get_project_settings).put(set_project_settings),
+    )
+}
@@ -12,10 +12,16 @@ use axum::{
     response::{IntoResponse, Json as ResponseJson},
     routing::{get, post},
 };
+use chrono::{Duration, Utc};
 use db::models::{
+    api_key::ApiKey,
     project::{CreateProject, Project, ProjectError, SearchResult, UpdateProject},
+    project_member::{ProjectMember, ProjectRole},
     project_repo::{CreateProjectRepo, ProjectRepo, UpdateProjectRepo},
+    project_summary::ProjectSummary,
     repo::Repo,
+    task::{DueDateSummary, Task},
+    team::{Team, TeamRole},
 };
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
@@ -29,28 +35,60 @@ use utils::{
     api::projects::{RemoteProject, RemoteProjectMembersResponse},
     response::ApiResponse,
 };
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{
+        auth::AuthUser,
+        load_project_middleware,
+        rbac::{require_team_role, visible_projects},
+    },
+    routes::{
+        board, custom_fields, epics, events, labels, project_archive, project_columns,
+        project_members, project_settings, recurrence, reports, share_links, sprints,
+        task_templates, trash, views, wip_limits,
+    },
+};
 
-#[derive(Deserialize, TS)]
+#[derive(Deserialize, TS, ToSchema)]
 pub struct LinkToExistingRequest {
     pub remote_project_id: Uuid,
 }
 
-#[derive(Deserialize, TS)]
+#[derive(Deserialize, TS, ToSchema)]
 pub struct CreateRemoteProjectRequest {
     pub organization_id: Uuid,
     pub name: String,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects",
+    tag = "projects",
+    responses((status = 200, body = ApiResponse<Vec<Project>>))
+)]
 pub async fn get_projects(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<Vec<Project>>>, ApiError> {
-    let projects = Project::find_all(&deployment.db().pool).await?;
+    let mut projects = Project::find_all(&deployment.db().pool).await?;
+    if let Some(visible) = visible_projects(&deployment, user.as_deref(), caller.as_deref()).await?
+    {
+        projects.retain(|project| visible.contains(&project.id));
+    }
     Ok(ResponseJson(ApiResponse::success(projects)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects/stream/ws",
+    tag = "projects",
+    responses((status = 101, description = "Switches to a WebSocket"))
+)]
 pub async fn stream_projects_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -93,12 +131,27 @@ async fn handle_projects_ws(socket: WebSocket, deployment: DeploymentImpl) -> an
     Ok(())
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Project>))
+)]
 pub async fn get_project(
     Extension(project): Extension<Project>,
 ) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
     Ok(ResponseJson(ApiResponse::success(project)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/link",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    request_body = LinkToExistingRequest,
+    responses((status = 200, body = ApiResponse<Project>))
+)]
 pub async fn link_project_to_existing_remote(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -113,6 +166,14 @@ pub async fn link_project_to_existing_remote(
     Ok(ResponseJson(ApiResponse::success(updated_project)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/link/create",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    request_body = CreateRemoteProjectRequest,
+    responses((status = 200, body = ApiResponse<Project>))
+)]
 pub async fn create_and_link_remote_project(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -140,6 +201,13 @@ pub async fn create_and_link_remote_project(
     Ok(ResponseJson(ApiResponse::success(updated_project)))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{id}/link",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Project>))
+)]
 pub async fn unlink_project(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -152,6 +220,13 @@ pub async fn unlink_project(
     Ok(ResponseJson(ApiResponse::success(updated_project)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/remote-projects/{remote_project_id}",
+    tag = "projects",
+    params(("remote_project_id" = Uuid, Path)),
+    responses((status = 200, body = Object))
+)]
 pub async fn get_remote_project_by_id(
     State(deployment): State<DeploymentImpl>,
     Path(remote_project_id): Path<Uuid>,
@@ -163,6 +238,13 @@ pub async fn get_remote_project_by_id(
     Ok(ResponseJson(ApiResponse::success(remote_project)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/remote/members",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = Object))
+)]
 pub async fn get_project_remote_members(
     State(deployment): State<DeploymentImpl>,
     Extension(project): Extension<Project>,
@@ -215,11 +297,43 @@ async fn apply_remote_project_link(
     Ok(updated_project)
 }
 
+/// A signed-in creator becomes the project's admin. Projects created with a key limited to
+/// a team go into that team.
+#[utoipa::path(
+    post,
+    path = "/api/projects",
+    tag = "projects",
+    request_body = CreateProject,
+    responses((status = 200, body = ApiResponse<Project>))
+)]
 pub async fn create_project(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
     State(deployment): State<DeploymentImpl>,
-    Json(payload): Json<CreateProject>,
+    Json(mut payload): Json<CreateProject>,
 ) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
     tracing::debug!("Creating project '{}'", payload.name);
+    if payload.team_id.is_none() {
+        payload.team_id = caller.as_ref().and_then(|Extension(key)| key.team_id);
+    }
+    if let Some(team_id) = payload.team_id {
+        if Team::find_by_id(&deployment.db().pool, team_id)
+            .await?
+            .is_none()
+        {
+            return Err(ApiError::BadRequest(format!(
+                "Team {team_id} does not exist"
+            )));
+        }
+        require_team_role(
+            &deployment,
+            user.as_deref(),
+            caller.as_deref(),
+            team_id,
+            TeamRole::Member,
+        )
+        .await?;
+    }
     let repo_count = payload.repositories.len();
 
     match deployment
@@ -228,6 +342,16 @@ pub async fn create_project(
         .await
     {
         Ok(project) => {
+            if let Some(Extension(AuthUser(user))) = &user {
+                ProjectMember::set(
+                    &deployment.db().pool,
+                    project.id,
+                    user.id,
+                    ProjectRole::Admin,
+                )
+                .await?;
+            }
+
             // Track project creation event
             deployment
                 .track_if_analytics_allowed(
@@ -261,6 +385,14 @@ pub async fn create_project(
     }
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/projects/{id}",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    request_body = UpdateProject,
+    responses((status = 200, body = ApiResponse<Project>))
+)]
 pub async fn update_project(
     Extension(existing_project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -279,6 +411,13 @@ pub async fn update_project(
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{id}",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
 pub async fn delete_project(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -311,17 +450,26 @@ pub async fn delete_project(
     }
 }
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, ToSchema)]
 pub struct OpenEditorRequest {
     editor_type: Option<String>,
+    #[schema(value_type = Option<String>)]
     git_repo_path: Option<PathBuf>,
 }
 
-#[derive(Debug, serde::Serialize, ts_rs::TS)]
+#[derive(Debug, serde::Serialize, ts_rs::TS, ToSchema)]
 pub struct OpenEditorResponse {
     pub url: Option<String>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/open-editor",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    request_body = Option<OpenEditorRequest>,
+    responses((status = 200, body = ApiResponse<OpenEditorResponse>))
+)]
 pub async fn open_project_in_editor(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -380,6 +528,13 @@ pub async fn open_project_in_editor(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/search",
+    tag = "projects",
+    params(("id" = Uuid, Path), SearchQuery),
+    responses((status = 200, body = ApiResponse<Vec<SearchResult>>))
+)]
 pub async fn search_project_files(
     State(deployment): State<DeploymentImpl>,
     Extension(project): Extension<Project>,
@@ -420,6 +575,86 @@ pub async fn search_project_files(
     }
 }
 
+#[derive(Debug, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DueDateSummaryQuery {
+    /// Size of the upcoming window in days, 7 when omitted
+    #[serde(default)]
+    pub days: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/due-summary",
+    tag = "projects",
+    params(("id" = Uuid, Path), DueDateSummaryQuery),
+    responses((status = 200, body = ApiResponse<DueDateSummary>))
+)]
+pub async fn get_project_due_summary(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<DueDateSummaryQuery>,
+) -> Result<ResponseJson<ApiResponse<DueDateSummary>>, ApiError> {
+    let days = query.days.unwrap_or(7);
+    if !(0..=365).contains(&days) {
+        return Err(ApiError::BadRequest(
+            "days must be between 0 and 365".to_string(),
+        ));
+    }
+    let until = Utc::now() + Duration::days(days);
+    let summary = Task::due_date_summary(&deployment.db().pool, project.id, until).await?;
+    Ok(ResponseJson(ApiResponse::success(summary)))
+}
+
+#[derive(Debug, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProjectSummaryQuery {
+    /// Overdue tasks to list, 5 when omitted
+    #[serde(default)]
+    pub overdue: Option<i64>,
+    /// Recent changes to list, 10 when omitted
+    #[serde(default)]
+    pub activity: Option<i64>,
+}
+
+/// Most overdue tasks or changes a summary lists.
+const SUMMARY_MAX_ITEMS: i64 = 50;
+
+/// GET /projects/{project_id}/summary
+/// Task counts per column, the most overdue tasks and the latest changes in one compact
+/// payload, for embedding the project's status in dashboards and chat previews.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/summary",
+    tag = "projects",
+    params(("id" = Uuid, Path), ProjectSummaryQuery),
+    responses((status = 200, body = ApiResponse<ProjectSummary>))
+)]
+pub async fn get_project_summary(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ProjectSummaryQuery>,
+) -> Result<ResponseJson<ApiResponse<ProjectSummary>>, ApiError> {
+    let overdue = query.overdue.unwrap_or(5);
+    let activity = query.activity.unwrap_or(10);
+    if !(0..=SUMMARY_MAX_ITEMS).contains(&overdue) || !(0..=SUMMARY_MAX_ITEMS).contains(&activity) {
+        return Err(ApiError::BadRequest(format!(
+            "overdue and activity must be between 0 and {SUMMARY_MAX_ITEMS}"
+        )));
+    }
+    let summary =
+        ProjectSummary::for_project(&deployment.db().pool, &project, overdue as usize, activity)
+            .await?;
+    Ok(ResponseJson(ApiResponse::success(summary)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/repositories",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<Repo>>))
+)]
 pub async fn get_project_repositories(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -431,6 +666,14 @@ pub async fn get_project_repositories(
     Ok(ResponseJson(ApiResponse::success(repositories)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/repositories",
+    tag = "projects",
+    params(("id" = Uuid, Path)),
+    request_body = CreateProjectRepo,
+    responses((status = 200, body = ApiResponse<Repo>))
+)]
 pub async fn add_project_repository(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -515,6 +758,13 @@ pub async fn add_project_repository(
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/repositories/{repo_id}",
+    tag = "projects",
+    params(("project_id" = Uuid, Path), ("repo_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
 pub async fn delete_project_repository(
     State(deployment): State<DeploymentImpl>,
     Path((project_id, repo_id)): Path<(Uuid, Uuid)>,
@@ -555,6 +805,13 @@ pub async fn delete_project_repository(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/repositories/{repo_id}",
+    tag = "projects",
+    params(("project_id" = Uuid, Path), ("repo_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<ProjectRepo>))
+)]
 pub async fn get_project_repository(
     State(deployment): State<DeploymentImpl>,
     Path((project_id, repo_id)): Path<(Uuid, Uuid)>,
@@ -568,6 +825,14 @@ pub async fn get_project_repository(
     }
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/repositories/{repo_id}",
+    tag = "projects",
+    params(("project_id" = Uuid, Path), ("repo_id" = Uuid, Path)),
+    request_body = UpdateProjectRepo,
+    responses((status = 200, body = ApiResponse<ProjectRepo>))
+)]
 pub async fn update_project_repository(
     State(deployment): State<DeploymentImpl>,
     Path((project_id, repo_id)): Path<(Uuid, Uuid)>,
@@ -600,6 +865,24 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             "/repositories",
             get(get_project_repositories).post(add_project_repository),
         )
+        .route("/due-summary", get(get_project_due_summary))
+        .route("/summary", get(get_project_summary))
+        .merge(labels::project_router())
+        .merge(custom_fields::project_router())
+        .merge(task_templates::project_router())
+        .merge(trash::project_router())
+        .merge(reports::project_router())
+        .merge(wip_limits::project_router())
+        .merge(project_columns::project_router())
+        .merge(board::project_router())
+        .merge(epics::project_router())
+        .merge(sprints::project_router())
+        .merge(project_settings::project_router())
+        .merge(project_members::project_router())
+        .merge(share_links::project_router())
+        .merge(project_archive::project_router())
+        .merge(views::project_router())
+        .merge(events::project_router())
         .layer(from_fn_with_state(
             deployment.clone(),
             load_project_middleware,
@@ -614,6 +897,18 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
                 .delete(delete_project_repository),
         )
         .route("/stream/ws", get(stream_projects_ws))
+        .merge(labels::router())
+        .merge(custom_fields::router())
+        .merge(project_columns::router())
+        .merge(project_members::router())
+        .merge(share_links::router())
+        .merge(project_archive::router())
+        .merge(task_templates::router())
+        .merge(recurrence::router())
+        .merge(trash::router())
+        .merge(epics::router())
+        .merge(sprints::router())
+        .merge(views::router())
         .nest("/{id}", project_id_router);
 
     Router::new().nest("/projects", projects_router).route(
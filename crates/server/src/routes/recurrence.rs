@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use chrono::Utc;
+use db::models::{
+    recurrence_rule::{Recurrence, RecurrenceRule, SetRecurrence},
+    task::Task,
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::task_templates::load_template};
+
+/// Parse a rule, returning it in canonical form.
+fn parse_rule(rrule: &str) -> Result<Recurrence, ApiError> {
+    rrule
+        .parse::<Recurrence>()
+        .map_err(|e| ApiError::BadRequest(format!("Invalid recurrence rule: {e}")))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/recurrence",
+    tag = "recurrence",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Option<RecurrenceRule>>))
+)]
+pub async fn get_task_recurrence(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<RecurrenceRule>>>, ApiError> {
+    let rule = RecurrenceRule::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+/// PUT /tasks/{task_id}/recurrence
+/// Make the task recurring: once it is done or cancelled, the next occurrence is created
+/// and the rule moves to it.
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}/recurrence",
+    tag = "recurrence",
+    params(("task_id" = Uuid, Path)),
+    request_body = SetRecurrence,
+    responses((status = 200, body = ApiResponse<RecurrenceRule>))
+)]
+pub async fn set_task_recurrence(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetRecurrence>,
+) -> Result<ResponseJson<ApiResponse<RecurrenceRule>>, ApiError> {
+    let recurrence = parse_rule(&payload.rrule)?;
+    let rule =
+        RecurrenceRule::set_for_task(&deployment.db().pool, task.id, &recurrence.to_string())
+            .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_recurrence_set",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/recurrence",
+    tag = "recurrence",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_task_recurrence(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let rule = RecurrenceRule::find_by_task_id(pool, task.id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    RecurrenceRule::delete(pool, rule.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/task-templates/{template_id}/recurrence",
+    tag = "recurrence",
+    params(("project_id" = Uuid, Path), ("template_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Option<RecurrenceRule>>))
+)]
+pub async fn get_template_recurrence(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, template_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Option<RecurrenceRule>>>, ApiError> {
+    let template = load_template(&deployment, project_id, template_id).await?;
+    let rule = RecurrenceRule::find_by_template_id(&deployment.db().pool, template.id).await?;
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+/// PUT /projects/{project_id}/task-templates/{template_id}/recurrence
+/// Create tasks from the template on a schedule. Scheduled runs have no one to ask for
+/// placeholder values, so only the built-in placeholders may be used.
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/task-templates/{template_id}/recurrence",
+    tag = "recurrence",
+    params(("project_id" = Uuid, Path), ("template_id" = Uuid, Path)),
+    request_body = SetRecurrence,
+    responses((status = 200, body = ApiResponse<RecurrenceRule>))
+)]
+pub async fn set_template_recurrence(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, template_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<SetRecurrence>,
+) -> Result<ResponseJson<ApiResponse<RecurrenceRule>>, ApiError> {
+    let template = load_template(&deployment, project_id, template_id).await?;
+    let recurrence = parse_rule(&payload.rrule)?;
+    let now = Utc::now();
+    if let Err(missing) = template.render(&HashMap::new(), now) {
+        return Err(ApiError::BadRequest(format!(
+            "Scheduled templates can only use built-in placeholders; no value for {}",
+            missing.join(", ")
+        )));
+    }
+
+    let next_run_at = match payload.start_at {
+        Some(start_at) if start_at > now => start_at,
+        Some(start_at) => recurrence.next_after_now(start_at, now).ok_or_else(|| {
+            ApiError::BadRequest("The recurrence rule has no runs after now".to_string())
+        })?,
+        None => recurrence.next_after(now).ok_or_else(|| {
+            ApiError::BadRequest("The recurrence rule has no runs after now".to_string())
+        })?,
+    };
+    if recurrence.until.is_some_and(|until| next_run_at > until) {
+        return Err(ApiError::BadRequest(
+            "The first run is after the rule's UNTIL".to_string(),
+        ));
+    }
+
+    let rule = RecurrenceRule::set_for_template(
+        &deployment.db().pool,
+        template.id,
+        &recurrence.to_string(),
+        next_run_at,
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_template_recurrence_set",
+            serde_json::json!({
+                "template_id": template.id.to_string(),
+                "project_id": project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/task-templates/{template_id}/recurrence",
+    tag = "recurrence",
+    params(("project_id" = Uuid, Path), ("template_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_template_recurrence(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, template_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let template = load_template(&deployment, project_id, template_id).await?;
+    let rule = RecurrenceRule::find_by_template_id(pool, template.id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    RecurrenceRule::delete(pool, rule.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/recurrence",
+        get(get_task_recurrence)
+            .put(set_task_recurrence)
+            .delete(delete_task_recurrence),
+    )
+}
+
+/// Routes nested under `/projects`. The project loader only understands a single path
+/// parameter, so these load the template themselves.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/{project_id}/task-templates/{template_id}/recurrence",
+        get(get_template_recurrence)
+            .put(set_template_recurrence)
+            .delete(delete_template_recurrence),
+    )
+}
@@ -10,24 +10,32 @@ use serde::Deserialize;
 use services::services::git::GitBranch;
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct RegisterRepoRequest {
     pub path: String,
     pub display_name: Option<String>,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct InitRepoRequest {
     pub parent_path: String,
     pub folder_name: String,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/repos",
+    tag = "repo",
+    request_body = RegisterRepoRequest,
+    responses((status = 200, body = ApiResponse<Repo>))
+)]
 pub async fn register_repo(
     State(deployment): State<DeploymentImpl>,
     ResponseJson(payload): ResponseJson<RegisterRepoRequest>,
@@ -44,6 +52,13 @@ pub async fn register_repo(
     Ok(ResponseJson(ApiResponse::success(repo)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/repos/init",
+    tag = "repo",
+    request_body = InitRepoRequest,
+    responses((status = 200, body = ApiResponse<Repo>))
+)]
 pub async fn init_repo(
     State(deployment): State<DeploymentImpl>,
     ResponseJson(payload): ResponseJson<InitRepoRequest>,
@@ -61,6 +76,13 @@ pub async fn init_repo(
     Ok(ResponseJson(ApiResponse::success(repo)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/repos/{repo_id}/branches",
+    tag = "repo",
+    params(("repo_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<GitBranch>>))
+)]
 pub async fn get_repo_branches(
     State(deployment): State<DeploymentImpl>,
     Path(repo_id): Path<Uuid>,
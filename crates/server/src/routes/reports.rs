@@ -0,0 +1,233 @@
+use axum::{
+    Extension, Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use chrono::{DateTime, Duration, Utc};
+use db::models::{
+    burndown::{self, BurndownPoint},
+    cumulative_flow::CumulativeFlowPoint,
+    flow_metrics::FlowMetrics,
+    project::Project,
+    project_settings::ProjectSettings,
+    sprint::Sprint,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BurndownQuery {
+    /// How far back to go, e.g. `14d` or `2w`; the project's `burndown_range` setting when
+    /// omitted
+    #[serde(default)]
+    pub range: Option<String>,
+}
+
+/// Range a cumulative flow diagram covers when none is given.
+const DEFAULT_CUMULATIVE_FLOW_RANGE: &str = "30d";
+
+#[derive(Debug, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CumulativeFlowQuery {
+    /// How far back to go, e.g. `30d` or `8w`; 30 days when neither this nor `since` is
+    /// given
+    #[serde(default)]
+    pub range: Option<String>,
+    /// Start of an explicit date range, instead of `range`
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    /// End of the range; now when omitted
+    #[serde(default)]
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// Range flow metrics cover when none is given.
+const DEFAULT_ANALYTICS_RANGE: &str = "12w";
+
+#[derive(Debug, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AnalyticsQuery {
+    /// How far back to look for finished tasks, e.g. `30d` or `12w`; 12 weeks when omitted
+    #[serde(default)]
+    pub range: Option<String>,
+    /// Start of an explicit date range, instead of `range`
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    /// End of the range, with `range` or `since`; now when omitted
+    #[serde(default)]
+    pub until: Option<DateTime<Utc>>,
+    /// Only this sprint's tasks, from its start to its end or now, whichever is first
+    #[serde(default)]
+    pub sprint: Option<Uuid>,
+}
+
+/// GET /projects/{project_id}/burndown?range=14d
+/// GET /projects/{project_id}/burndown?since=...&until=...
+/// GET /projects/{project_id}/burndown?sprint=...
+/// Remaining estimate and task count of the project's tasks, or a sprint's, at the start
+/// of each day in the range and at its end, replayed from the activity log.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/burndown",
+    tag = "reports",
+    params(("id" = uuid::Uuid, Path), BurndownQuery),
+    responses((status = 200, body = ApiResponse<Vec<BurndownPoint>>))
+)]
+pub async fn get_project_burndown(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<BurndownQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<BurndownPoint>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let now = Utc::now();
+    if let Some(sprint_id) = query.sprint {
+        if query.range.is_some() || query.since.is_some() || query.until.is_some() {
+            return Err(ApiError::BadRequest(
+                "sprint can't be combined with range, since or until".to_string(),
+            ));
+        }
+        let sprint = Sprint::find_by_id(pool, sprint_id)
+            .await?
+            .filter(|sprint| sprint.project_id == project.id)
+            .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+        let since = sprint.started_at.unwrap_or(sprint.starts_at);
+        let until = sprint.closed_at.unwrap_or(sprint.ends_at).min(now);
+        // Nothing to replay before a sprint starts
+        if since > until {
+            return Ok(ResponseJson(ApiResponse::success(vec![])));
+        }
+        check_span(since, until)?;
+        let points = BurndownPoint::for_sprint(pool, &sprint, since, until).await?;
+        return Ok(ResponseJson(ApiResponse::success(points)));
+    }
+
+    let range = match (query.range, query.since) {
+        (None, None) => Some(
+            ProjectSettings::find(pool, project.id)
+                .await?
+                .burndown_range,
+        ),
+        (range, _) => range,
+    };
+    let (since, until) = report_span(range.as_deref(), query.since, query.until)?;
+    let points = BurndownPoint::for_project(pool, project.id, since, until).await?;
+    Ok(ResponseJson(ApiResponse::success(points)))
+}
+
+/// The span a report covers: from `since`, or `range` back from `until`, which defaults
+/// to now and is capped at it.
+fn report_span(
+    range: Option<&str>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), ApiError> {
+    let now = Utc::now();
+    let until = until.map_or(now, |until| until.min(now));
+    match (range, since) {
+        (Some(_), Some(_)) => Err(ApiError::BadRequest(
+            "range can't be combined with since".to_string(),
+        )),
+        (_, Some(since)) => {
+            if since >= until {
+                return Err(ApiError::BadRequest(
+                    "since must be before until and now".to_string(),
+                ));
+            }
+            check_span(since, until)?;
+            Ok((since, until))
+        }
+        (range, None) => {
+            let range = range.and_then(burndown::parse_range).ok_or_else(|| {
+                ApiError::BadRequest(format!(
+                    "range must be a number of days or weeks such as 14d or 2w, at most {} days",
+                    burndown::MAX_RANGE_DAYS
+                ))
+            })?;
+            Ok((until - range, until))
+        }
+    }
+}
+
+/// Keep an explicit range to as many daily points as a `range` allows.
+fn check_span(since: DateTime<Utc>, until: DateTime<Utc>) -> Result<(), ApiError> {
+    if until - since > Duration::days(burndown::MAX_RANGE_DAYS) {
+        return Err(ApiError::BadRequest(format!(
+            "A report can cover at most {} days",
+            burndown::MAX_RANGE_DAYS
+        )));
+    }
+    Ok(())
+}
+
+/// GET /projects/{project_id}/analytics?range=12w
+/// Lead time, cycle time and weekly throughput of the tasks finished in the range, with
+/// percentiles, replayed from the activity log.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/analytics",
+    tag = "reports",
+    params(("id" = uuid::Uuid, Path), AnalyticsQuery),
+    responses((status = 200, body = ApiResponse<FlowMetrics>))
+)]
+pub async fn get_project_analytics(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<AnalyticsQuery>,
+) -> Result<ResponseJson<ApiResponse<FlowMetrics>>, ApiError> {
+    let range = query.range.as_deref().unwrap_or(DEFAULT_ANALYTICS_RANGE);
+    let range = burndown::parse_range(range).ok_or_else(|| {
+        ApiError::BadRequest(format!(
+            "range must be a number of days or weeks such as 30d or 12w, at most {} days",
+            burndown::MAX_RANGE_DAYS
+        ))
+    })?;
+    let until = Utc::now();
+    let metrics =
+        FlowMetrics::for_project(&deployment.db().pool, project.id, until - range, until).await?;
+    Ok(ResponseJson(ApiResponse::success(metrics)))
+}
+
+/// GET /projects/{project_id}/cumulative-flow?range=30d
+/// How many of the project's tasks were in each status at the start of each day in the
+/// range and at its end, replayed from the activity log, for a cumulative flow diagram.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/cumulative-flow",
+    tag = "reports",
+    params(("id" = uuid::Uuid, Path), CumulativeFlowQuery),
+    responses((status = 200, body = ApiResponse<Vec<CumulativeFlowPoint>>))
+)]
+pub async fn get_project_cumulative_flow(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<CumulativeFlowQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<CumulativeFlowPoint>>>, ApiError> {
+    let range = match (query.range, query.since) {
+        (None, None) => Some(DEFAULT_CUMULATIVE_FLOW_RANGE.to_string()),
+        (range, _) => range,
+    };
+    let (since, until) = report_span(range.as_deref(), query.since, query.until)?;
+    let points = CumulativeFlowPoint::for_project(
+        &deployment.db().pool,
+        project.id,
+        &burndown::sample_times(since, until),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(points)))
+}
+
+/// Routes nested under `/projects/{id}`, behind the project loading middleware.
+pub fn project_router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/burndown", get(get_project_burndown))
+        .route("/cumulative-flow", get(get_project_cumulative_flow))
+        .route("/analytics", get(get_project_analytics))
+}
@@ -23,6 +23,12 @@ pub struct ScratchPath {
     id: Uuid,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/scratch",
+    tag = "scratch",
+    responses((status = 200, body = ApiResponse<Vec<Scratch>>))
+)]
 pub async fn list_scratch(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<Vec<Scratch>>>, ApiError> {
@@ -30,6 +36,13 @@ pub async fn list_scratch(
     Ok(ResponseJson(ApiResponse::success(scratch_items)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/scratch/{scratch_type}/{id}",
+    tag = "scratch",
+    params(("scratch_type" = String, Path), ("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Scratch>))
+)]
 pub async fn get_scratch(
     State(deployment): State<DeploymentImpl>,
     Path(ScratchPath { scratch_type, id }): Path<ScratchPath>,
@@ -40,6 +53,14 @@ pub async fn get_scratch(
     Ok(ResponseJson(ApiResponse::success(scratch)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/scratch/{scratch_type}/{id}",
+    tag = "scratch",
+    params(("scratch_type" = String, Path), ("id" = Uuid, Path)),
+    request_body = CreateScratch,
+    responses((status = 200, body = ApiResponse<Scratch>))
+)]
 pub async fn create_scratch(
     State(deployment): State<DeploymentImpl>,
     Path(ScratchPath { scratch_type, id }): Path<ScratchPath>,
@@ -64,6 +85,14 @@ pub async fn create_scratch(
     Ok(ResponseJson(ApiResponse::success(scratch)))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/scratch/{scratch_type}/{id}",
+    tag = "scratch",
+    params(("scratch_type" = String, Path), ("id" = Uuid, Path)),
+    request_body = UpdateScratch,
+    responses((status = 200, body = ApiResponse<Scratch>))
+)]
 pub async fn update_scratch(
     State(deployment): State<DeploymentImpl>,
     Path(ScratchPath { scratch_type, id }): Path<ScratchPath>,
@@ -89,6 +118,13 @@ pub async fn update_scratch(
     Ok(ResponseJson(ApiResponse::success(scratch)))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/scratch/{scratch_type}/{id}",
+    tag = "scratch",
+    params(("scratch_type" = String, Path), ("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
 pub async fn delete_scratch(
     State(deployment): State<DeploymentImpl>,
     Path(ScratchPath { scratch_type, id }): Path<ScratchPath>,
@@ -100,6 +136,13 @@ pub async fn delete_scratch(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/scratch/{scratch_type}/{id}/stream/ws",
+    tag = "scratch",
+    params(("scratch_type" = String, Path), ("id" = Uuid, Path)),
+    responses((status = 101, description = "Switches to a WebSocket"))
+)]
 pub async fn stream_scratch_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -0,0 +1,116 @@
+use axum::{
+    Extension, Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::{
+    api_key::ApiKey,
+    task::{TaskPriority, TaskStatus},
+    task_search::{self, SearchFacets, SearchFilter, TaskSearchHit},
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::AuthUser, rbac::visible_projects},
+};
+
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 100;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    pub q: String,
+    /// Only search this project's tasks
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+    #[serde(default)]
+    pub status: Option<TaskStatus>,
+    #[serde(default)]
+    pub priority: Option<TaskPriority>,
+    #[serde(default)]
+    pub assignee_id: Option<Uuid>,
+    /// Only tasks carrying this label
+    #[serde(default)]
+    pub label_id: Option<Uuid>,
+    #[serde(default)]
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct SearchResults {
+    /// The best matches, up to `limit`
+    pub hits: Vec<TaskSearchHit>,
+    /// Counts over every match, for narrowing the search further
+    pub facets: SearchFacets,
+}
+
+/// GET /search?q=...&project_id=...&status=...&label_id=...&limit=...
+/// Full-text search over task titles, descriptions and comments across every project the
+/// caller can see, best match first, with match counts per project, status and label.
+#[utoipa::path(
+    get,
+    path = "/api/search",
+    tag = "search",
+    params(SearchQuery),
+    responses((status = 200, body = ApiResponse<SearchResults>))
+)]
+pub async fn search_tasks(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<SearchQuery>,
+) -> Result<ResponseJson<ApiResponse<SearchResults>>, ApiError> {
+    if query.q.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Search query must not be empty".to_string(),
+        ));
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    if !(1..=MAX_LIMIT).contains(&limit) {
+        return Err(ApiError::BadRequest(format!(
+            "limit must be between 1 and {MAX_LIMIT}"
+        )));
+    }
+
+    // Input made only of punctuation has no words to match
+    let Some(match_query) = task_search::match_query(&query.q) else {
+        return Ok(ResponseJson(ApiResponse::success(SearchResults {
+            hits: vec![],
+            facets: SearchFacets::default(),
+        })));
+    };
+    // Narrowed in the query rather than afterwards, so hidden projects neither crowd out
+    // visible hits nor show up in the counts
+    let project_ids = visible_projects(&deployment, user.as_deref(), caller.as_deref())
+        .await?
+        .map(|visible| visible.into_iter().collect());
+    let filter = SearchFilter {
+        project_id: query.project_id,
+        project_ids,
+        status: query.status,
+        priority: query.priority,
+        assignee_id: query.assignee_id,
+        label_id: query.label_id,
+    };
+    let pool = &deployment.db().pool;
+    let hits = TaskSearchHit::search(pool, &match_query, &filter, limit).await?;
+    let facets = SearchFacets::count(pool, &match_query, &filter).await?;
+
+    Ok(ResponseJson(ApiResponse::success(SearchResults {
+        hits,
+        facets,
+    })))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/search", get(search_tasks))
+}
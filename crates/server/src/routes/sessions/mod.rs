@@ -26,6 +26,7 @@ use services::services::container::ContainerService;
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{
@@ -33,17 +34,25 @@ use crate::{
     routes::task_attempts::util::restore_worktrees_to_process,
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SessionQuery {
     pub workspace_id: Uuid,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct CreateSessionRequest {
     pub workspace_id: Uuid,
     pub executor: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/sessions",
+    tag = "sessions",
+    params(SessionQuery),
+    responses((status = 200, body = ApiResponse<Vec<Session>>))
+)]
 pub async fn get_sessions(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<SessionQuery>,
@@ -53,12 +62,26 @@ pub async fn get_sessions(
     Ok(ResponseJson(ApiResponse::success(sessions)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/sessions/{session_id}",
+    tag = "sessions",
+    params(("session_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Session>))
+)]
 pub async fn get_session(
     Extension(session): Extension<Session>,
 ) -> Result<ResponseJson<ApiResponse<Session>>, ApiError> {
     Ok(ResponseJson(ApiResponse::success(session)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/sessions",
+    tag = "sessions",
+    request_body = CreateSessionRequest,
+    responses((status = 200, body = ApiResponse<Session>))
+)]
 pub async fn create_session(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateSessionRequest>,
@@ -85,7 +108,7 @@ pub async fn create_session(
     Ok(ResponseJson(ApiResponse::success(session)))
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct CreateFollowUpAttempt {
     pub prompt: String,
     pub variant: Option<String>,
@@ -104,6 +127,14 @@ pub struct CreateFollowUpAttempt {
     pub perform_git_reset: Option<bool>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/sessions/{session_id}/follow-up",
+    tag = "sessions",
+    params(("session_id" = Uuid, Path)),
+    request_body = CreateFollowUpAttempt,
+    responses((status = 200, body = ApiResponse<ExecutionProcess>))
+)]
 pub async fn follow_up(
     Extension(session): Extension<Session>,
     State(deployment): State<DeploymentImpl>,
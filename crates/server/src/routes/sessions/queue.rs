@@ -8,11 +8,12 @@ use serde::Deserialize;
 use services::services::queued_message::QueueStatus;
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::ToSchema;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_session_middleware};
 
 /// Request body for queueing a follow-up message
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct QueueMessageRequest {
     pub message: String,
     pub variant: Option<String>,
@@ -27,6 +28,14 @@ pub struct QueueMessageRequest {
 }
 
 /// Queue a follow-up message to be executed when the current execution finishes
+#[utoipa::path(
+    post,
+    path = "/api/sessions/{session_id}/queue",
+    tag = "sessions",
+    params(("session_id" = uuid::Uuid, Path)),
+    request_body = QueueMessageRequest,
+    responses((status = 200, body = ApiResponse<QueueStatus>))
+)]
 pub async fn queue_message(
     Extension(session): Extension<Session>,
     State(deployment): State<DeploymentImpl>,
@@ -58,6 +67,13 @@ pub async fn queue_message(
 }
 
 /// Cancel a queued follow-up message
+#[utoipa::path(
+    delete,
+    path = "/api/sessions/{session_id}/queue",
+    tag = "sessions",
+    params(("session_id" = uuid::Uuid, Path)),
+    responses((status = 200, body = ApiResponse<QueueStatus>))
+)]
 pub async fn cancel_queued_message(
     Extension(session): Extension<Session>,
     State(deployment): State<DeploymentImpl>,
@@ -80,6 +96,13 @@ pub async fn cancel_queued_message(
 }
 
 /// Get the current queue status for a session's workspace
+#[utoipa::path(
+    get,
+    path = "/api/sessions/{session_id}/queue",
+    tag = "sessions",
+    params(("session_id" = uuid::Uuid, Path)),
+    responses((status = 200, body = ApiResponse<QueueStatus>))
+)]
 pub async fn get_queue_status(
     Extension(session): Extension<Session>,
     State(deployment): State<DeploymentImpl>,
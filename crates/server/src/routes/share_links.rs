@@ -0,0 +1,197 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{delete, get},
+};
+use chrono::Utc;
+use db::models::{
+    project::{Project, ProjectError},
+    share_link::{CreateShareLink, ShareLink, ShareMode, SharedBoard},
+};
+use deployment::Deployment;
+use serde::Serialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::auth::{generate_session_token, hash_token},
+};
+
+/// Characters of a token kept in the clear to tell links apart.
+const DISPLAY_PREFIX_LEN: usize = 8;
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct CreatedShareLink {
+    pub share_link: ShareLink,
+    /// Open the board at `/api/share/<token>`. It is not stored and cannot be shown again.
+    pub token: String,
+}
+
+/// GET /projects/{project_id}/share-links
+/// The project's share links, newest first. Tokens are not included.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/share-links",
+    tag = "share_links",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<ShareLink>>))
+)]
+pub async fn get_share_links(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ShareLink>>>, ApiError> {
+    let links = ShareLink::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(links)))
+}
+
+/// POST /projects/{project_id}/share-links
+/// Make a read-only link to the board for people without an account. Snapshot links keep
+/// showing the board as it is now; live links show it as it is when opened.
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/share-links",
+    tag = "share_links",
+    params(("id" = Uuid, Path)),
+    request_body = CreateShareLink,
+    responses((status = 200, body = ApiResponse<CreatedShareLink>))
+)]
+pub async fn create_share_link(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateShareLink>,
+) -> Result<ResponseJson<ApiResponse<CreatedShareLink>>, ApiError> {
+    if payload
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= Utc::now())
+    {
+        return Err(ApiError::BadRequest(
+            "Expiry must be in the future".to_string(),
+        ));
+    }
+    let pool = &deployment.db().pool;
+    let mode = payload.mode.unwrap_or_default();
+    let snapshot = match mode {
+        ShareMode::Snapshot => Some(
+            SharedBoard::for_project(
+                pool,
+                &project,
+                payload.fields.as_deref().unwrap_or_default(),
+            )
+            .await?,
+        ),
+        ShareMode::Live => None,
+    };
+    let token = generate_session_token();
+    let prefix: String = token.chars().take(DISPLAY_PREFIX_LEN).collect();
+    let share_link = ShareLink::create(
+        pool,
+        project.id,
+        &payload,
+        &prefix,
+        &hash_token(&token),
+        snapshot.as_ref(),
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "share_link_created",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "mode": mode,
+                "expires": share_link.expires_at.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(CreatedShareLink {
+        share_link,
+        token,
+    })))
+}
+
+/// DELETE /projects/{project_id}/share-links/{link_id}
+/// Stop the link from working.
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/share-links/{link_id}",
+    tag = "share_links",
+    params(("project_id" = Uuid, Path), ("link_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_share_link(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, link_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let removed = ShareLink::delete(&deployment.db().pool, project_id, link_id).await?;
+    if removed == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "share_link_deleted",
+            serde_json::json!({ "project_id": project_id.to_string() }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// GET /share/{token}
+/// The shared board, without signing in. Expired and deleted links are refused.
+#[utoipa::path(
+    get,
+    path = "/api/share/{token}",
+    tag = "share_links",
+    params(("token" = String, Path)),
+    responses((status = 200, body = ApiResponse<SharedBoard>))
+)]
+pub async fn get_shared_board(
+    State(deployment): State<DeploymentImpl>,
+    Path(token): Path<String>,
+) -> Result<ResponseJson<ApiResponse<SharedBoard>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let Some((link, snapshot)) = ShareLink::find_by_token_hash(pool, &hash_token(&token))
+        .await?
+        .filter(|(link, _)| !link.is_expired(Utc::now()))
+    else {
+        return Err(ApiError::Forbidden(
+            "This share link is invalid or has expired".to_string(),
+        ));
+    };
+    let board = match snapshot {
+        Some(board) => board,
+        None => {
+            let project = Project::find_by_id(pool, link.project_id)
+                .await?
+                .ok_or(ProjectError::ProjectNotFound)?;
+            SharedBoard::for_project(pool, &project, &link.fields).await?
+        }
+    };
+    Ok(ResponseJson(ApiResponse::success(board)))
+}
+
+/// Routes nested under `/projects/{id}`, behind the project loading middleware.
+pub fn project_router() -> Router<DeploymentImpl> {
+    Router::new().route("/share-links", get(get_share_links).post(create_share_link))
+}
+
+/// Routes nested under `/projects`. The project loader only understands a single path
+/// parameter, so these load the project themselves.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/{project_id}/share-links/{link_id}",
+        delete(delete_share_link),
+    )
+}
+
+/// The shared boards themselves, reachable without credentials.
+pub fn public_router() -> Router<DeploymentImpl> {
+    Router::new().route("/share/{token}", get(get_shared_board))
+}
@@ -0,0 +1,437 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{delete, get, post, put},
+};
+use chrono::{DateTime, Utc};
+use db::models::{
+    project::Project,
+    sprint::{
+        AddSprintTasks, CloseSprint, CreateSprint, Sprint, SprintReport, SprintStatus, SprintTask,
+        SprintTotals, SprintVelocity, UpdateSprint,
+    },
+    task::Task,
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+fn validate_sprint(
+    name: Option<&str>,
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+) -> Result<(), ApiError> {
+    if name.is_some_and(|name| name.trim().is_empty()) {
+        return Err(ApiError::BadRequest(
+            "Sprint name must not be empty".to_string(),
+        ));
+    }
+    if ends_at <= starts_at {
+        return Err(ApiError::BadRequest(
+            "Sprint must end after it starts".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Load a sprint, checking that it belongs to the project in the path.
+async fn load_sprint(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    sprint_id: Uuid,
+) -> Result<Sprint, ApiError> {
+    Sprint::find_by_id(&deployment.db().pool, sprint_id)
+        .await?
+        .filter(|sprint| sprint.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+fn ensure_open(sprint: &Sprint) -> Result<(), ApiError> {
+    if sprint.status == SprintStatus::Closed {
+        return Err(ApiError::Conflict(format!(
+            "Sprint '{}' is closed",
+            sprint.name
+        )));
+    }
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/sprints",
+    tag = "sprints",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<Sprint>>))
+)]
+pub async fn get_sprints(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Sprint>>>, ApiError> {
+    let sprints = Sprint::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(sprints)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/sprints",
+    tag = "sprints",
+    params(("id" = Uuid, Path)),
+    request_body = CreateSprint,
+    responses((status = 200, body = ApiResponse<Sprint>))
+)]
+pub async fn create_sprint(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateSprint>,
+) -> Result<ResponseJson<ApiResponse<Sprint>>, ApiError> {
+    validate_sprint(Some(&payload.name), payload.starts_at, payload.ends_at)?;
+    let sprint = Sprint::create(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "sprint_created",
+            serde_json::json!({
+                "sprint_id": sprint.id.to_string(),
+                "project_id": project.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(sprint)))
+}
+
+/// GET /projects/{project_id}/sprints/velocity
+/// Totals of each closed sprint, oldest first.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/sprints/velocity",
+    tag = "sprints",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<SprintVelocity>>))
+)]
+pub async fn get_sprint_velocity(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<SprintVelocity>>>, ApiError> {
+    let velocity = Sprint::find_velocity(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(velocity)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/sprints/{sprint_id}",
+    tag = "sprints",
+    params(("project_id" = Uuid, Path), ("sprint_id" = Uuid, Path)),
+    request_body = UpdateSprint,
+    responses((status = 200, body = ApiResponse<Sprint>))
+)]
+pub async fn update_sprint(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, sprint_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateSprint>,
+) -> Result<ResponseJson<ApiResponse<Sprint>>, ApiError> {
+    let sprint = load_sprint(&deployment, project_id, sprint_id).await?;
+    ensure_open(&sprint)?;
+    validate_sprint(
+        payload.name.as_deref(),
+        payload.starts_at.unwrap_or(sprint.starts_at),
+        payload.ends_at.unwrap_or(sprint.ends_at),
+    )?;
+    let sprint = Sprint::update(&deployment.db().pool, &sprint, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(sprint)))
+}
+
+/// DELETE /projects/{project_id}/sprints/{sprint_id}
+/// The sprint's tasks are kept.
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/sprints/{sprint_id}",
+    tag = "sprints",
+    params(("project_id" = Uuid, Path), ("sprint_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_sprint(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, sprint_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let sprint = load_sprint(&deployment, project_id, sprint_id).await?;
+    Sprint::delete(&deployment.db().pool, sprint.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "sprint_deleted",
+            serde_json::json!({
+                "sprint_id": sprint.id.to_string(),
+                "project_id": project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// POST /projects/{project_id}/sprints/{sprint_id}/start
+/// Only one sprint of a project can be active at a time.
+#[utoipa::path(
+    post,
+    path = "/api/projects/{project_id}/sprints/{sprint_id}/start",
+    tag = "sprints",
+    params(("project_id" = Uuid, Path), ("sprint_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Sprint>))
+)]
+pub async fn start_sprint(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, sprint_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Sprint>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let sprint = load_sprint(&deployment, project_id, sprint_id).await?;
+    if sprint.status != SprintStatus::Planned {
+        return Err(ApiError::Conflict(format!(
+            "Sprint '{}' is already {}",
+            sprint.name, sprint.status
+        )));
+    }
+    if let Some(active) = Sprint::find_active(pool, project_id).await? {
+        return Err(ApiError::Conflict(format!(
+            "Sprint '{}' is still active; close it first",
+            active.name
+        )));
+    }
+
+    let sprint = Sprint::start(pool, sprint.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "sprint_started",
+            serde_json::json!({
+                "sprint_id": sprint.id.to_string(),
+                "project_id": project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(sprint)))
+}
+
+/// POST /projects/{project_id}/sprints/{sprint_id}/close
+/// Record what became of each task and roll the unfinished ones into the next planned
+/// sprint. Returns the closed sprint's report.
+#[utoipa::path(
+    post,
+    path = "/api/projects/{project_id}/sprints/{sprint_id}/close",
+    tag = "sprints",
+    params(("project_id" = Uuid, Path), ("sprint_id" = Uuid, Path)),
+    request_body = CloseSprint,
+    responses((status = 200, body = ApiResponse<SprintReport>))
+)]
+pub async fn close_sprint(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, sprint_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<CloseSprint>,
+) -> Result<ResponseJson<ApiResponse<SprintReport>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let sprint = load_sprint(&deployment, project_id, sprint_id).await?;
+    if sprint.status != SprintStatus::Active {
+        return Err(ApiError::Conflict(format!(
+            "Only an active sprint can be closed; '{}' is {}",
+            sprint.name, sprint.status
+        )));
+    }
+    let next = match payload.next_sprint_id {
+        Some(next_id) => {
+            let next = Sprint::find_by_id(pool, next_id)
+                .await?
+                .filter(|next| {
+                    next.project_id == project_id
+                        && next.id != sprint.id
+                        && next.status == SprintStatus::Planned
+                })
+                .ok_or_else(|| {
+                    ApiError::BadRequest(format!(
+                        "Sprint {next_id} is not a planned sprint of the project"
+                    ))
+                })?;
+            Some(next)
+        }
+        None => Sprint::find_next_planned(pool, project_id, sprint.id).await?,
+    };
+
+    let carried_over = Sprint::close(pool, sprint.id, next.as_ref().map(|next| next.id)).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "sprint_closed",
+            serde_json::json!({
+                "sprint_id": sprint.id.to_string(),
+                "project_id": project_id.to_string(),
+                "carried_over": carried_over,
+            }),
+        )
+        .await;
+
+    let report = sprint_report(&deployment, sprint.id).await?;
+    let message = match next {
+        Some(next) if carried_over > 0 => Some(format!(
+            "{carried_over} unfinished task(s) moved to sprint '{}'",
+            next.name
+        )),
+        None if carried_over > 0 => Some(format!(
+            "{carried_over} unfinished task(s) returned to the backlog; no planned sprint to move them to"
+        )),
+        _ => None,
+    };
+    Ok(ResponseJson(match message {
+        Some(message) => ApiResponse::success_with_message(report, &message),
+        None => ApiResponse::success(report),
+    }))
+}
+
+async fn sprint_report(
+    deployment: &DeploymentImpl,
+    sprint_id: Uuid,
+) -> Result<SprintReport, ApiError> {
+    let pool = &deployment.db().pool;
+    let sprint = Sprint::find_by_id(pool, sprint_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let tasks = Sprint::find_tasks(pool, sprint.id).await?;
+    Ok(SprintReport {
+        totals: SprintTotals::from_tasks(&tasks),
+        sprint,
+        tasks,
+    })
+}
+
+/// GET /projects/{project_id}/sprints/{sprint_id}/report
+/// The sprint's tasks and totals, by recorded outcome once it is closed.
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/sprints/{sprint_id}/report",
+    tag = "sprints",
+    params(("project_id" = Uuid, Path), ("sprint_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<SprintReport>))
+)]
+pub async fn get_sprint_report(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, sprint_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<SprintReport>>, ApiError> {
+    let sprint = load_sprint(&deployment, project_id, sprint_id).await?;
+    let report = sprint_report(&deployment, sprint.id).await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{project_id}/sprints/{sprint_id}/tasks",
+    tag = "sprints",
+    params(("project_id" = Uuid, Path), ("sprint_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<SprintTask>>))
+)]
+pub async fn get_sprint_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, sprint_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Vec<SprintTask>>>, ApiError> {
+    let sprint = load_sprint(&deployment, project_id, sprint_id).await?;
+    let tasks = Sprint::find_tasks(&deployment.db().pool, sprint.id).await?;
+    Ok(ResponseJson(ApiResponse::success(tasks)))
+}
+
+/// POST /projects/{project_id}/sprints/{sprint_id}/tasks
+/// Add tasks of the project to an open sprint. A task is in at most one open sprint.
+#[utoipa::path(
+    post,
+    path = "/api/projects/{project_id}/sprints/{sprint_id}/tasks",
+    tag = "sprints",
+    params(("project_id" = Uuid, Path), ("sprint_id" = Uuid, Path)),
+    request_body = AddSprintTasks,
+    responses((status = 200, body = ApiResponse<Vec<SprintTask>>))
+)]
+pub async fn add_sprint_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, sprint_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<AddSprintTasks>,
+) -> Result<ResponseJson<ApiResponse<Vec<SprintTask>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let sprint = load_sprint(&deployment, project_id, sprint_id).await?;
+    ensure_open(&sprint)?;
+    for &task_id in &payload.task_ids {
+        Task::find_by_id(pool, task_id)
+            .await?
+            .filter(|task| task.project_id == project_id && task.deleted_at.is_none())
+            .ok_or_else(|| {
+                ApiError::BadRequest(format!("Task {task_id} does not belong to the project"))
+            })?;
+        if let Some(other) = Sprint::find_open_for_task(pool, task_id).await?
+            && other.id != sprint.id
+        {
+            return Err(ApiError::Conflict(format!(
+                "Task {task_id} is already in sprint '{}'",
+                other.name
+            )));
+        }
+    }
+
+    Sprint::add_tasks(pool, sprint.id, &payload.task_ids).await?;
+    let tasks = Sprint::find_tasks(pool, sprint.id).await?;
+    Ok(ResponseJson(ApiResponse::success(tasks)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/sprints/{sprint_id}/tasks/{task_id}",
+    tag = "sprints",
+    params(("project_id" = Uuid, Path), ("sprint_id" = Uuid, Path), ("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn remove_sprint_task(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, sprint_id, task_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let sprint = load_sprint(&deployment, project_id, sprint_id).await?;
+    ensure_open(&sprint)?;
+    let rows_affected = Sprint::remove_task(&deployment.db().pool, sprint.id, task_id).await?;
+    if rows_affected == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Routes nested under `/projects/{id}`, behind the project loading middleware.
+pub fn project_router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/sprints", get(get_sprints).post(create_sprint))
+        .route("/sprints/velocity", get(get_sprint_velocity))
+}
+
+/// Routes nested under `/projects`. The project loader only understands a single path
+/// parameter, so these load the sprint themselves.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/{project_id}/sprints/{sprint_id}",
+            put(update_sprint).delete(delete_sprint),
+        )
+        .route(
+            "/{project_id}/sprints/{sprint_id}/start",
+            post(start_sprint),
+        )
+        .route(
+            "/{project_id}/sprints/{sprint_id}/close",
+            post(close_sprint),
+        )
+        .route(
+            "/{project_id}/sprints/{sprint_id}/report",
+            get(get_sprint_report),
+        )
+        .route(
+            "/{project_id}/sprints/{sprint_id}/tasks",
+            get(get_sprint_tasks).post(add_sprint_tasks),
+        )
+        .route(
+            "/{project_id}/sprints/{sprint_id}/tasks/{task_id}",
+            delete(remove_sprint_task),
+        )
+}
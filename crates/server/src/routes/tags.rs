@@ -10,15 +10,24 @@ use deployment::Deployment;
 use serde::Deserialize;
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::IntoParams;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_tag_middleware};
 
-#[derive(Deserialize, TS)]
+#[derive(Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct TagSearchParams {
     #[serde(default)]
     pub search: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/tags",
+    tag = "tags",
+    params(TagSearchParams),
+    responses((status = 200, body = ApiResponse<Vec<Tag>>))
+)]
 pub async fn get_tags(
     State(deployment): State<DeploymentImpl>,
     Query(params): Query<TagSearchParams>,
@@ -34,6 +43,13 @@ pub async fn get_tags(
     Ok(ResponseJson(ApiResponse::success(tags)))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/tags",
+    tag = "tags",
+    request_body = CreateTag,
+    responses((status = 200, body = ApiResponse<Tag>))
+)]
 pub async fn create_tag(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateTag>,
@@ -53,6 +69,14 @@ pub async fn create_tag(
     Ok(ResponseJson(ApiResponse::success(tag)))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/tags/{tag_id}",
+    tag = "tags",
+    params(("tag_id" = uuid::Uuid, Path)),
+    request_body = UpdateTag,
+    responses((status = 200, body = ApiResponse<Tag>))
+)]
 pub async fn update_tag(
     Extension(tag): Extension<Tag>,
     State(deployment): State<DeploymentImpl>,
@@ -73,6 +97,13 @@ pub async fn update_tag(
     Ok(ResponseJson(ApiResponse::success(updated_tag)))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/tags/{tag_id}",
+    tag = "tags",
+    params(("tag_id" = uuid::Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
 pub async fn delete_tag(
     Extension(tag): Extension<Tag>,
     State(deployment): State<DeploymentImpl>,
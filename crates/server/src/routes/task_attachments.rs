@@ -0,0 +1,185 @@
+use axum::{
+    Extension, Router,
+    body::Body,
+    extract::{DefaultBodyLimit, Multipart, Path, State},
+    http::{StatusCode, header},
+    response::{Json as ResponseJson, Response},
+    routing::{delete, get},
+};
+use db::models::{task::Task, task_attachment::TaskAttachment};
+use deployment::Deployment;
+use services::services::attachment::AttachmentError;
+use tokio::fs::File;
+use tokio_util::io::ReaderStream;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Request bodies may carry some multipart overhead on top of the largest file.
+const MAX_UPLOAD_BODY_BYTES: usize = 51 * 1024 * 1024;
+
+/// Load an attachment, checking that it belongs to the task in the path.
+async fn load_attachment(
+    deployment: &DeploymentImpl,
+    task_id: Uuid,
+    attachment_id: Uuid,
+) -> Result<TaskAttachment, ApiError> {
+    TaskAttachment::find_by_id(&deployment.db().pool, attachment_id)
+        .await?
+        .filter(|attachment| attachment.task_id == task_id)
+        .ok_or(ApiError::Attachment(AttachmentError::NotFound))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/attachments",
+    tag = "task_attachments",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<TaskAttachment>>))
+)]
+pub async fn get_task_attachments(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskAttachment>>>, ApiError> {
+    let attachments = TaskAttachment::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(attachments)))
+}
+
+/// POST /tasks/{task_id}/attachments
+/// Multipart upload with the file in a `file` field.
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/attachments",
+    tag = "task_attachments",
+    params(("task_id" = Uuid, Path)),
+    request_body(content_type = "multipart/form-data", description = "The file to upload"),
+    responses((status = 200, body = ApiResponse<TaskAttachment>))
+)]
+pub async fn upload_task_attachment(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    mut multipart: Multipart,
+) -> Result<ResponseJson<ApiResponse<TaskAttachment>>, ApiError> {
+    while let Some(field) = multipart.next_field().await? {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let filename = field
+            .file_name()
+            .map(|s| s.to_string())
+            .ok_or(ApiError::BadRequest(
+                "Attachment has no file name".to_string(),
+            ))?;
+        let content_type = field.content_type().map(|s| s.to_string());
+        let data = field.bytes().await?;
+
+        let attachment = deployment
+            .attachment()
+            .store_attachment(task.id, &data, &filename, content_type.as_deref(), None)
+            .await?;
+
+        deployment
+            .track_if_analytics_allowed(
+                "task_attachment_uploaded",
+                serde_json::json!({
+                    "task_id": task.id.to_string(),
+                    "size_bytes": attachment.size_bytes,
+                    "mime_type": attachment.mime_type,
+                }),
+            )
+            .await;
+
+        return Ok(ResponseJson(ApiResponse::success(attachment)));
+    }
+
+    Err(ApiError::BadRequest(
+        "Multipart body has no 'file' field".to_string(),
+    ))
+}
+
+/// Serve an attachment's content. Always sent as a download so uploaded files are never
+/// rendered inline by the browser.
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/attachments/{attachment_id}/file",
+    tag = "task_attachments",
+    params(("task_id" = Uuid, Path), ("attachment_id" = Uuid, Path)),
+    responses((status = 200, description = "The attachment file", content_type = "application/octet-stream"))
+)]
+pub async fn download_task_attachment(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Response, ApiError> {
+    let attachment = load_attachment(&deployment, task_id, attachment_id).await?;
+    let file = File::open(deployment.attachment().get_absolute_path(&attachment)).await?;
+    let metadata = file.metadata().await?;
+
+    let filename = attachment
+        .original_name
+        .replace(['"', '\\', '\r', '\n'], "_");
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, &attachment.mime_type)
+        .header(header::CONTENT_LENGTH, metadata.len())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{filename}\""),
+        )
+        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+        .body(Body::from_stream(ReaderStream::new(file)))
+        .map_err(|e| ApiError::Io(std::io::Error::other(e)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/attachments/{attachment_id}",
+    tag = "task_attachments",
+    params(("task_id" = Uuid, Path), ("attachment_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_task_attachment(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let attachment = load_attachment(&deployment, task_id, attachment_id).await?;
+    deployment
+        .attachment()
+        .delete_attachment(attachment.id)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attachment_deleted",
+            serde_json::json!({
+                "task_id": task_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/attachments",
+        get(get_task_attachments)
+            .post(upload_task_attachment)
+            .layer(DefaultBodyLimit::max(MAX_UPLOAD_BODY_BYTES)),
+    )
+}
+
+/// Routes nested under `/tasks`. The task loader only understands a single path
+/// parameter, so these load the attachment themselves.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/{task_id}/attachments/{attachment_id}",
+            delete(delete_task_attachment),
+        )
+        .route(
+            "/{task_id}/attachments/{attachment_id}/file",
+            get(download_task_attachment),
+        )
+}
@@ -28,6 +28,7 @@ use db::models::{
     repo::{Repo, RepoError},
     session::{CreateSession, Session},
     task::{Task, TaskRelationships, TaskStatus},
+    task_event::{TaskEvent, TaskEventSource},
     workspace::{CreateWorkspace, Workspace, WorkspaceError},
     workspace_repo::{CreateWorkspaceRepo, RepoWithTargetBranch, WorkspaceRepo},
 };
@@ -50,6 +51,7 @@ use services::services::{
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{
@@ -57,19 +59,19 @@ use crate::{
     routes::task_attempts::gh_cli_setup::GhCliSetupError,
 };
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct RebaseTaskAttemptRequest {
     pub repo_id: Uuid,
     pub old_base_branch: Option<String>,
     pub new_base_branch: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct AbortConflictsRequest {
     pub repo_id: Uuid,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum GitOperationError {
@@ -77,17 +79,26 @@ pub enum GitOperationError {
     RebaseInProgress,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct TaskAttemptQuery {
     pub task_id: Option<Uuid>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct DiffStreamQuery {
     #[serde(default)]
     pub stats_only: bool,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts",
+    tag = "task_attempts",
+    params(TaskAttemptQuery),
+    responses((status = 200, body = ApiResponse<Vec<Workspace>>))
+)]
 pub async fn get_task_attempts(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<TaskAttemptQuery>,
@@ -97,34 +108,50 @@ pub async fn get_task_attempts(
     Ok(ResponseJson(ApiResponse::success(workspaces)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Workspace>))
+)]
 pub async fn get_task_attempt(
     Extension(workspace): Extension<Workspace>,
 ) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
     Ok(ResponseJson(ApiResponse::success(workspace)))
 }
 
-#[derive(Debug, Serialize, Deserialize, ts_rs::TS)]
+#[derive(Debug, Serialize, Deserialize, ts_rs::TS, ToSchema)]
 pub struct CreateTaskAttemptBody {
     pub task_id: Uuid,
+    #[schema(value_type = Object)]
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
 }
 
-#[derive(Debug, Serialize, Deserialize, ts_rs::TS)]
+#[derive(Debug, Serialize, Deserialize, ts_rs::TS, ToSchema)]
 pub struct WorkspaceRepoInput {
     pub repo_id: Uuid,
     pub target_branch: String,
 }
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct RunAgentSetupRequest {
+    #[schema(value_type = Object)]
     pub executor_profile_id: ExecutorProfileId,
 }
 
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 pub struct RunAgentSetupResponse {}
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts",
+    tag = "task_attempts",
+    request_body = CreateTaskAttemptBody,
+    responses((status = 200, body = ApiResponse<Workspace>))
+)]
 pub async fn create_task_attempt(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateTaskAttemptBody>,
@@ -207,6 +234,14 @@ pub async fn create_task_attempt(
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/run-agent-setup",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = RunAgentSetupRequest,
+    responses((status = 200, body = ApiResponse<RunAgentSetupResponse>))
+)]
 pub async fn run_agent_setup(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -239,6 +274,13 @@ pub async fn run_agent_setup(
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/diff/ws",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path), DiffStreamQuery),
+    responses((status = 101, description = "Switches to a WebSocket"))
+)]
 pub async fn stream_task_attempt_diff_ws(
     ws: WebSocketUpgrade,
     Query(params): Query<DiffStreamQuery>,
@@ -300,17 +342,25 @@ async fn handle_task_attempt_diff_ws(
     Ok(())
 }
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct MergeTaskAttemptRequest {
     pub repo_id: Uuid,
 }
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct PushTaskAttemptRequest {
     pub repo_id: Uuid,
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/merge",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = MergeTaskAttemptRequest,
+    responses((status = 200, body = ApiResponse<()>))
+)]
 pub async fn merge_task_attempt(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -368,6 +418,14 @@ pub async fn merge_task_attempt(
     )
     .await?;
     Task::update_status(pool, task.id, TaskStatus::Done).await?;
+    TaskEvent::record_status_change(
+        pool,
+        task.id,
+        &task.status,
+        &TaskStatus::Done,
+        TaskEventSource::User,
+    )
+    .await?;
 
     // Stop any running dev servers for this workspace
     let dev_servers =
@@ -423,6 +481,14 @@ pub async fn merge_task_attempt(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/push",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = PushTaskAttemptRequest,
+    responses((status = 200, body = ApiResponse<(), PushError>))
+)]
 pub async fn push_task_attempt_branch(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -461,6 +527,14 @@ pub async fn push_task_attempt_branch(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/push/force",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = PushTaskAttemptRequest,
+    responses((status = 200, body = ApiResponse<(), PushError>))
+)]
 pub async fn force_push_task_attempt_branch(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -493,24 +567,32 @@ pub async fn force_push_task_attempt_branch(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum PushError {
     ForcePushRequired,
 }
 
-#[derive(serde::Deserialize, TS)]
+#[derive(serde::Deserialize, TS, ToSchema)]
 pub struct OpenEditorRequest {
     editor_type: Option<String>,
     file_path: Option<String>,
 }
 
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 pub struct OpenEditorResponse {
     pub url: Option<String>,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/open-editor",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = OpenEditorRequest,
+    responses((status = 200, body = ApiResponse<OpenEditorResponse>))
+)]
 pub async fn open_task_attempt_in_editor(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -579,7 +661,7 @@ pub async fn open_task_attempt_in_editor(
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 pub struct BranchStatus {
     pub commits_behind: Option<usize>,
     pub commits_ahead: Option<usize>,
@@ -599,7 +681,7 @@ pub struct BranchStatus {
     pub conflicted_files: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 pub struct RepoBranchStatus {
     pub repo_id: Uuid,
     pub repo_name: String,
@@ -607,6 +689,13 @@ pub struct RepoBranchStatus {
     pub status: BranchStatus,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/branch-status",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<RepoBranchStatus>>))
+)]
 pub async fn get_task_attempt_branch_status(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -738,30 +827,30 @@ pub async fn get_task_attempt_branch_status(
     Ok(ResponseJson(ApiResponse::success(results)))
 }
 
-#[derive(serde::Deserialize, Debug, TS)]
+#[derive(serde::Deserialize, Debug, TS, ToSchema)]
 pub struct ChangeTargetBranchRequest {
     pub repo_id: Uuid,
     pub new_target_branch: String,
 }
 
-#[derive(serde::Serialize, Debug, TS)]
+#[derive(serde::Serialize, Debug, TS, ToSchema)]
 pub struct ChangeTargetBranchResponse {
     pub repo_id: Uuid,
     pub new_target_branch: String,
     pub status: (usize, usize),
 }
 
-#[derive(serde::Deserialize, Debug, TS)]
+#[derive(serde::Deserialize, Debug, TS, ToSchema)]
 pub struct RenameBranchRequest {
     pub new_branch_name: String,
 }
 
-#[derive(serde::Serialize, Debug, TS)]
+#[derive(serde::Serialize, Debug, TS, ToSchema)]
 pub struct RenameBranchResponse {
     pub branch: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum RenameBranchError {
@@ -774,6 +863,14 @@ pub enum RenameBranchError {
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/change-target-branch",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = ChangeTargetBranchRequest,
+    responses((status = 200, body = ApiResponse<ChangeTargetBranchResponse>))
+)]
 pub async fn change_target_branch(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -827,6 +924,14 @@ pub async fn change_target_branch(
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/rename-branch",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = RenameBranchRequest,
+    responses((status = 200, body = ApiResponse<RenameBranchResponse, RenameBranchError>))
+)]
 pub async fn rename_branch(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -967,6 +1072,14 @@ pub async fn rename_branch(
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/rebase",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = RebaseTaskAttemptRequest,
+    responses((status = 200, body = ApiResponse<(), GitOperationError>))
+)]
 pub async fn rebase_task_attempt(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1064,6 +1177,14 @@ pub async fn rebase_task_attempt(
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/conflicts/abort",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = AbortConflictsRequest,
+    responses((status = 200, body = ApiResponse<()>))
+)]
 pub async fn abort_conflicts_task_attempt(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1088,6 +1209,13 @@ pub async fn abort_conflicts_task_attempt(
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/start-dev-server",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
 pub async fn start_dev_server(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1204,6 +1332,13 @@ pub async fn start_dev_server(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/children",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<TaskRelationships>))
+)]
 pub async fn get_task_attempt_children(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1234,6 +1369,13 @@ pub async fn get_task_attempt_children(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/stop",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
 pub async fn stop_task_attempt_execution(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1252,7 +1394,7 @@ pub async fn stop_task_attempt_execution(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum RunScriptError {
@@ -1261,6 +1403,13 @@ pub enum RunScriptError {
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/run-setup-script",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<ExecutionProcess, RunScriptError>))
+)]
 pub async fn run_setup_script(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1345,6 +1494,13 @@ pub async fn run_setup_script(
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/run-cleanup-script",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<ExecutionProcess, RunScriptError>))
+)]
 pub async fn run_cleanup_script(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1429,6 +1585,13 @@ pub async fn run_cleanup_script(
 }
 
 #[axum::debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/gh-cli-setup",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<ExecutionProcess, GhCliSetupError>))
+)]
 pub async fn gh_cli_setup_handler(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -1465,6 +1628,13 @@ pub async fn gh_cli_setup_handler(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/repos",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<RepoWithTargetBranch>>))
+)]
 pub async fn get_task_attempt_repos(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -16,11 +16,12 @@ use executors::{
 use serde::{Deserialize, Serialize};
 use services::services::container::ContainerService;
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::error::ApiError;
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[ts(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum GhCliSetupError {
     BrewMissing,
@@ -16,6 +16,7 @@ use services::services::{container::ContainerService, image::ImageError};
 use tokio::fs::File;
 use tokio_util::io::ReaderStream;
 use utils::response::ApiResponse;
+use utoipa::IntoParams;
 use uuid::Uuid;
 
 use crate::{
@@ -25,7 +26,8 @@ use crate::{
     routes::images::{ImageMetadata, ImageResponse, process_image_upload},
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ImageMetadataQuery {
     /// Path relative to worktree root, e.g., ".vibe-images/screenshot.png"
     pub path: String,
@@ -33,6 +35,14 @@ pub struct ImageMetadataQuery {
 
 /// Upload an image and immediately copy it to the workspace's worktree.
 /// This allows images to be available in the container before follow-up is sent.
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/images/upload",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body(content_type = "multipart/form-data", description = "The file to upload"),
+    responses((status = 200, body = ApiResponse<ImageResponse>))
+)]
 pub async fn upload_image(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -60,6 +70,13 @@ pub async fn upload_image(
 }
 
 /// Get metadata about an image in the workspace's worktree.
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/images/metadata",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path), ImageMetadataQuery),
+    responses((status = 200, body = ApiResponse<ImageMetadata>))
+)]
 pub async fn get_image_metadata(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -140,6 +157,13 @@ pub async fn get_image_metadata(
 }
 
 /// Serve an image file from the workspace's .vibe-images folder.
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/images/file/{path}",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path), ("path" = String, Path)),
+    responses((status = 200, description = "The image file", content_type = "application/octet-stream"))
+)]
 pub async fn serve_image(
     axum::extract::Path((_id, path)): axum::extract::Path<(Uuid, String)>,
     Extension(workspace): Extension<Workspace>,
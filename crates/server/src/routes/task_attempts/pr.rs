@@ -11,6 +11,7 @@ use db::models::{
     repo::{Repo, RepoError},
     session::{CreateSession, Session},
     task::{Task, TaskStatus},
+    task_event::{TaskEvent, TaskEventSource},
     workspace::{Workspace, WorkspaceError},
     workspace_repo::WorkspaceRepo,
 };
@@ -28,11 +29,12 @@ use services::services::{
 };
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct CreateGitHubPrRequest {
     pub title: String,
     pub body: Option<String>,
@@ -43,7 +45,7 @@ pub struct CreateGitHubPrRequest {
     pub auto_generate_description: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum CreatePrError {
@@ -54,7 +56,7 @@ pub enum CreatePrError {
     TargetBranchNotFound { branch: String },
 }
 
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 pub struct AttachPrResponse {
     pub pr_attached: bool,
     pub pr_url: Option<String>,
@@ -62,17 +64,17 @@ pub struct AttachPrResponse {
     pub pr_status: Option<MergeStatus>,
 }
 
-#[derive(Debug, Deserialize, Serialize, TS)]
+#[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
 pub struct AttachExistingPrRequest {
     pub repo_id: Uuid,
 }
 
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, TS, ToSchema)]
 pub struct PrCommentsResponse {
     pub comments: Vec<UnifiedPrComment>,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum GetPrCommentsError {
@@ -81,7 +83,8 @@ pub enum GetPrCommentsError {
     GithubCliNotLoggedIn,
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct GetPrCommentsQuery {
     pub repo_id: Uuid,
 }
@@ -185,6 +188,14 @@ async fn trigger_pr_description_follow_up(
     Ok(())
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/pr",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = CreateGitHubPrRequest,
+    responses((status = 200, body = ApiResponse<String, CreatePrError>))
+)]
 pub async fn create_github_pr(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -360,6 +371,14 @@ pub async fn create_github_pr(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/task-attempts/{id}/pr/attach",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path)),
+    request_body = AttachExistingPrRequest,
+    responses((status = 200, body = ApiResponse<AttachPrResponse>))
+)]
 pub async fn attach_existing_pr(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -427,6 +446,14 @@ pub async fn attach_existing_pr(
         // If PR is merged, mark task as done
         if matches!(pr_info.status, MergeStatus::Merged) {
             Task::update_status(pool, task.id, TaskStatus::Done).await?;
+            TaskEvent::record_status_change(
+                pool,
+                task.id,
+                &task.status,
+                &TaskStatus::Done,
+                TaskEventSource::User,
+            )
+            .await?;
 
             // Try broadcast update to other users in organization
             if let Ok(publisher) = deployment.share_publisher() {
@@ -461,6 +488,13 @@ pub async fn attach_existing_pr(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/task-attempts/{id}/pr/comments",
+    tag = "task_attempts",
+    params(("id" = Uuid, Path), GetPrCommentsQuery),
+    responses((status = 200, body = ApiResponse<PrCommentsResponse, GetPrCommentsError>))
+)]
 pub async fn get_pr_comments(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
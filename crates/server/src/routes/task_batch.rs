@@ -0,0 +1,448 @@
+use std::collections::{HashMap, hash_map::Entry};
+
+use axum::{
+    Extension, Json, Router, extract::State, response::Json as ResponseJson, routing::post,
+};
+use db::models::{
+    api_key::ApiKey,
+    image::TaskImage,
+    project::Project,
+    project_member::ProjectRole,
+    task::{CreateTask, Task, UpdateTask},
+    task_event::{TaskEvent, TaskEventSource},
+    wip_limit::WipColumn,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::container::ContainerService;
+use sqlx::{SqliteConnection, SqlitePool};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::AuthUser, rbac::require_role},
+    routes::{
+        tasks::{apply_update, ensure_shared_task_auth, validate_cover, validate_estimate},
+        users, wip_limits,
+    },
+};
+
+const MAX_BATCH_OPERATIONS: usize = 500;
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BatchTaskOperation {
+    Create {
+        task: CreateTask,
+    },
+    Update {
+        task_id: Uuid,
+        changes: UpdateTask,
+    },
+    /// Moves the task to the trash
+    Delete {
+        task_id: Uuid,
+    },
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct BatchTaskRequest {
+    pub operations: Vec<BatchTaskOperation>,
+    /// Apply every operation or none of them. Otherwise each operation that can be
+    /// applied is, whatever happens to the others.
+    #[serde(default)]
+    pub transactional: bool,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct BatchTaskResult {
+    /// Position of the operation in the request
+    pub index: usize,
+    pub ok: bool,
+    /// The task as the operation left it
+    pub task: Option<Task>,
+    /// Why the operation was not applied
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct BatchTaskResponse {
+    /// Whether anything was written; false when a transactional batch was rolled back
+    pub committed: bool,
+    pub results: Vec<BatchTaskResult>,
+}
+
+/// An operation that passed validation, with the task it changes as it was before.
+enum Prepared {
+    Create(CreateTask),
+    Update(Task, UpdateTask),
+    Delete(Task),
+}
+
+/// Turn the rejection of a single operation into its error, keeping server errors fatal.
+fn rejection(result: Result<(), ApiError>) -> Result<Option<String>, ApiError> {
+    match result {
+        Ok(()) => Ok(None),
+        Err(
+            ApiError::BadRequest(message)
+            | ApiError::Conflict(message)
+            | ApiError::Forbidden(message),
+        ) => Ok(Some(message)),
+        Err(e) => Err(e),
+    }
+}
+
+async fn check_create(
+    deployment: &DeploymentImpl,
+    user: Option<&AuthUser>,
+    api_key: Option<&ApiKey>,
+    task: &CreateTask,
+) -> Result<(), ApiError> {
+    if Project::find_by_id(&deployment.db().pool, task.project_id)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Project {} does not exist",
+            task.project_id
+        )));
+    }
+    require_role(
+        deployment,
+        user,
+        api_key,
+        task.project_id,
+        ProjectRole::Member,
+    )
+    .await?;
+    validate_estimate(task.estimate)?;
+    if let Some(assignee_id) = task.assignee_id {
+        users::ensure_exists(deployment, assignee_id).await?;
+    }
+    Ok(())
+}
+
+async fn check_update(
+    deployment: &DeploymentImpl,
+    user: Option<&AuthUser>,
+    api_key: Option<&ApiKey>,
+    task: &Task,
+    changes: &UpdateTask,
+) -> Result<(), ApiError> {
+    require_role(
+        deployment,
+        user,
+        api_key,
+        task.project_id,
+        ProjectRole::Member,
+    )
+    .await?;
+    if ensure_shared_task_auth(task, deployment).await.is_err() {
+        return Err(ApiError::BadRequest(
+            "Sign in to update shared tasks".to_string(),
+        ));
+    }
+    validate_estimate(changes.estimate)?;
+    validate_cover(deployment, task, changes).await
+}
+
+async fn check_delete(
+    deployment: &DeploymentImpl,
+    user: Option<&AuthUser>,
+    api_key: Option<&ApiKey>,
+    task: &Task,
+) -> Result<(), ApiError> {
+    require_role(
+        deployment,
+        user,
+        api_key,
+        task.project_id,
+        ProjectRole::Member,
+    )
+    .await?;
+    if task.shared_task_id.is_some() {
+        return Err(ApiError::BadRequest(
+            "Shared tasks must be deleted individually".to_string(),
+        ));
+    }
+    if deployment
+        .container()
+        .has_running_processes(task.id)
+        .await?
+    {
+        return Err(ApiError::Conflict(
+            "Task has running execution processes".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Load the task an operation changes, unless it is missing or an earlier operation
+/// already changes it.
+async fn claim(
+    pool: &SqlitePool,
+    touched: &mut HashMap<Uuid, usize>,
+    task_id: Uuid,
+    index: usize,
+) -> Result<Result<Task, String>, ApiError> {
+    match touched.entry(task_id) {
+        Entry::Occupied(entry) => {
+            return Ok(Err(format!(
+                "Task is already changed by operation {}",
+                entry.get()
+            )));
+        }
+        Entry::Vacant(entry) => {
+            entry.insert(index);
+        }
+    }
+    Ok(match Task::find_by_id(pool, task_id).await? {
+        Some(task) if task.deleted_at.is_none() => Ok(task),
+        _ => Err("Task not found".to_string()),
+    })
+}
+
+async fn apply(conn: &mut SqliteConnection, operation: &Prepared) -> Result<Task, sqlx::Error> {
+    match operation {
+        Prepared::Create(task) => Task::create(&mut *conn, task, Uuid::new_v4()).await,
+        Prepared::Update(task, changes) => apply_update(conn, task, changes).await,
+        Prepared::Delete(task) => Task::trash(&mut *conn, task.id).await,
+    }
+}
+
+/// POST /tasks/batch
+/// Create, update and delete tasks in one request, e.g. for importers and scripted
+/// migrations. Every operation gets a result in request order. A transactional batch
+/// is rolled back as a whole when any operation fails; otherwise only the failing
+/// operations are skipped.
+#[utoipa::path(
+    post,
+    path = "/api/tasks/batch",
+    tag = "task_batch",
+    request_body = BatchTaskRequest,
+    responses((status = 200, body = ApiResponse<BatchTaskResponse>))
+)]
+pub async fn batch_tasks(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<BatchTaskRequest>,
+) -> Result<ResponseJson<ApiResponse<BatchTaskResponse>>, ApiError> {
+    if payload.operations.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one operation is required".to_string(),
+        ));
+    }
+    if payload.operations.len() > MAX_BATCH_OPERATIONS {
+        return Err(ApiError::BadRequest(format!(
+            "At most {MAX_BATCH_OPERATIONS} operations can be sent at once"
+        )));
+    }
+
+    let pool = &deployment.db().pool;
+    let user = user.as_deref();
+    let api_key = caller.as_deref();
+    let mut errors: Vec<Option<String>> = Vec::with_capacity(payload.operations.len());
+    let mut prepared: Vec<(usize, Prepared)> = Vec::new();
+    // The operation that changes each existing task, as checks run against the stored task
+    let mut touched: HashMap<Uuid, usize> = HashMap::new();
+    // Per project and status, the column and how many updated tasks are entering it
+    let mut wip_columns: HashMap<(Uuid, String), (WipColumn, i64)> = HashMap::new();
+    for (index, operation) in payload.operations.into_iter().enumerate() {
+        let (error, operation) = match operation {
+            BatchTaskOperation::Create { task } => (
+                rejection(check_create(&deployment, user, api_key, &task).await)?,
+                Some(Prepared::Create(task)),
+            ),
+            BatchTaskOperation::Update { task_id, changes } => {
+                match claim(pool, &mut touched, task_id, index).await? {
+                    Err(error) => (Some(error), None),
+                    Ok(task) => {
+                        let mut error = rejection(
+                            check_update(&deployment, user, api_key, &task, &changes).await,
+                        )?;
+                        // Archived tasks are off the board, so they don't take up a column's
+                        // WIP limit
+                        if error.is_none()
+                            && let Some(status) = &changes.status
+                            && *status != task.status
+                            && task.archived_at.is_none()
+                        {
+                            let (column, entering) =
+                                match wip_columns.entry((task.project_id, status.to_string())) {
+                                    Entry::Occupied(entry) => entry.into_mut(),
+                                    Entry::Vacant(entry) => entry.insert((
+                                        WipColumn::find(pool, task.project_id, status).await?,
+                                        0,
+                                    )),
+                                };
+                            if column.enforced && column.would_exceed(*entering + 1) {
+                                error = Some(wip_limits::limit_message(column));
+                            } else {
+                                *entering += 1;
+                            }
+                        }
+                        (error, Some(Prepared::Update(task, changes)))
+                    }
+                }
+            }
+            BatchTaskOperation::Delete { task_id } => {
+                match claim(pool, &mut touched, task_id, index).await? {
+                    Err(error) => (Some(error), None),
+                    Ok(task) => (
+                        rejection(check_delete(&deployment, user, api_key, &task).await)?,
+                        Some(Prepared::Delete(task)),
+                    ),
+                }
+            }
+        };
+        if let Some(operation) = operation
+            && error.is_none()
+        {
+            prepared.push((index, operation));
+        }
+        errors.push(error);
+    }
+
+    let mut tasks: Vec<Option<Task>> = vec![None; errors.len()];
+    let mut applied: Vec<(usize, Prepared)> = Vec::new();
+    let committed = if payload.transactional {
+        if let Some(failed) = errors.iter().position(Option::is_some) {
+            for error in errors.iter_mut().filter(|error| error.is_none()) {
+                *error = Some(format!("Not applied because operation {failed} failed"));
+            }
+            false
+        } else {
+            let mut tx = pool.begin().await?;
+            let mut failed = None;
+            for (index, operation) in &prepared {
+                match apply(&mut tx, operation).await {
+                    Ok(task) => tasks[*index] = Some(task),
+                    Err(e) => {
+                        failed = Some((*index, e));
+                        break;
+                    }
+                }
+            }
+            match failed {
+                None => {
+                    tx.commit().await?;
+                    applied = prepared;
+                    true
+                }
+                Some((failed, e)) => {
+                    tx.rollback().await?;
+                    tasks.iter_mut().for_each(|task| *task = None);
+                    for (index, error) in errors.iter_mut().enumerate() {
+                        *error = Some(if index == failed {
+                            e.to_string()
+                        } else {
+                            format!("Not applied because operation {failed} failed")
+                        });
+                    }
+                    false
+                }
+            }
+        }
+    } else {
+        for (index, operation) in prepared {
+            let mut tx = pool.begin().await?;
+            match apply(&mut tx, &operation).await {
+                Ok(task) => {
+                    tx.commit().await?;
+                    tasks[index] = Some(task);
+                    applied.push((index, operation));
+                }
+                Err(e) => {
+                    tx.rollback().await?;
+                    errors[index] = Some(e.to_string());
+                }
+            }
+        }
+        !applied.is_empty()
+    };
+
+    // History, images and shared task updates follow once the changes are stored
+    let mut shared = Vec::new();
+    for (index, operation) in &applied {
+        let Some(after) = &tasks[*index] else {
+            continue;
+        };
+        match operation {
+            Prepared::Create(create) => {
+                TaskEvent::record_created(pool, after, TaskEventSource::User, None).await?;
+                if let Some(image_ids) = &create.image_ids {
+                    TaskImage::associate_many_dedup(pool, after.id, image_ids).await?;
+                }
+            }
+            Prepared::Update(before, changes) => {
+                TaskEvent::record_changes(pool, before, after, TaskEventSource::User, None).await?;
+                if let Some(image_ids) = &changes.image_ids {
+                    TaskImage::delete_by_task_id(pool, after.id).await?;
+                    TaskImage::associate_many_dedup(pool, after.id, image_ids).await?;
+                }
+                if after.shared_task_id.is_some() {
+                    shared.push(after);
+                }
+            }
+            Prepared::Delete(before) => {
+                TaskEvent::record_changes(pool, before, after, TaskEventSource::User, None).await?;
+            }
+        }
+    }
+    if !shared.is_empty()
+        && let Ok(publisher) = deployment.share_publisher()
+    {
+        for task in shared {
+            if let Err(e) = publisher.update_shared_task(task).await {
+                tracing::warn!("Failed to publish shared task {}: {}", task.id, e);
+            }
+        }
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "tasks_batch_applied",
+            serde_json::json!({
+                "operation_count": errors.len(),
+                "applied_count": applied.len(),
+                "transactional": payload.transactional,
+            }),
+        )
+        .await;
+
+    // Advisory limits don't stop the change, but the caller hears about them
+    let warnings: Vec<String> = wip_columns
+        .values()
+        .filter(|(column, entering)| !column.enforced && column.would_exceed(*entering))
+        .map(|(column, _)| wip_limits::limit_message(column))
+        .collect();
+    let response = BatchTaskResponse {
+        committed,
+        results: errors
+            .into_iter()
+            .zip(tasks)
+            .enumerate()
+            .map(|(index, (error, task))| BatchTaskResult {
+                index,
+                ok: error.is_none(),
+                task,
+                error,
+            })
+            .collect(),
+    };
+    Ok(ResponseJson(if warnings.is_empty() || !committed {
+        ApiResponse::success(response)
+    } else {
+        ApiResponse::success_with_message(response, &warnings.join("; "))
+    }))
+}
+
+/// Routes nested under `/tasks`.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/batch", post(batch_tasks))
+}
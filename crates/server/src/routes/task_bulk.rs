@@ -0,0 +1,300 @@
+use std::collections::{HashMap, HashSet, hash_map::Entry};
+
+use axum::{
+    Extension, Json, Router, extract::State, response::Json as ResponseJson, routing::post,
+};
+use db::models::{
+    api_key::ApiKey,
+    label::Label,
+    project_member::ProjectRole,
+    task::{Task, TaskStatus},
+    task_event::{TaskEvent, TaskEventSource},
+    wip_limit::WipColumn,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::container::ContainerService;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::AuthUser, rbac::require_role},
+    routes::{tasks::ensure_shared_task_auth, users, wip_limits},
+};
+
+const MAX_BULK_TASKS: usize = 500;
+
+#[derive(Debug, Clone, Deserialize, TS, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BulkTaskOperation {
+    SetStatus {
+        status: TaskStatus,
+    },
+    AddLabel {
+        label_id: Uuid,
+    },
+    /// A null assignee unassigns the tasks
+    Assign {
+        assignee_id: Option<Uuid>,
+    },
+    Archive,
+    /// Moves the tasks to the trash
+    Delete,
+}
+
+impl BulkTaskOperation {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::SetStatus { .. } => "set_status",
+            Self::AddLabel { .. } => "add_label",
+            Self::Assign { .. } => "assign",
+            Self::Archive => "archive",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct BulkTaskRequest {
+    pub task_ids: Vec<Uuid>,
+    pub operation: BulkTaskOperation,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct BulkTaskResult {
+    pub task_id: Uuid,
+    pub ok: bool,
+    /// Why the operation was not applied to this task
+    pub error: Option<String>,
+}
+
+/// Check whether the operation can be applied to a task, returning the reason if not.
+async fn check_task(
+    deployment: &DeploymentImpl,
+    user: Option<&AuthUser>,
+    api_key: Option<&ApiKey>,
+    task: &Task,
+    operation: &BulkTaskOperation,
+    label: Option<&Label>,
+) -> Result<Option<String>, ApiError> {
+    match require_role(
+        deployment,
+        user,
+        api_key,
+        task.project_id,
+        ProjectRole::Member,
+    )
+    .await
+    {
+        Ok(()) => {}
+        Err(ApiError::Forbidden(message)) => return Ok(Some(message)),
+        Err(e) => return Err(e),
+    }
+    match operation {
+        BulkTaskOperation::AddLabel { .. } => {
+            if label.is_some_and(|label| label.project_id != task.project_id) {
+                return Ok(Some(
+                    "Label belongs to a different project than the task".to_string(),
+                ));
+            }
+        }
+        BulkTaskOperation::SetStatus { .. } => {
+            if ensure_shared_task_auth(task, deployment).await.is_err() {
+                return Ok(Some("Sign in to update shared tasks".to_string()));
+            }
+        }
+        BulkTaskOperation::Delete => {
+            if task.shared_task_id.is_some() {
+                return Ok(Some(
+                    "Shared tasks must be deleted individually".to_string(),
+                ));
+            }
+            if deployment
+                .container()
+                .has_running_processes(task.id)
+                .await?
+            {
+                return Ok(Some("Task has running execution processes".to_string()));
+            }
+        }
+        BulkTaskOperation::Assign { .. } | BulkTaskOperation::Archive => {}
+    }
+    Ok(None)
+}
+
+/// POST /tasks/bulk
+/// Apply one operation to many tasks. Tasks the operation cannot be applied to are
+/// reported and skipped; the rest are changed together in a single transaction.
+#[utoipa::path(
+    post,
+    path = "/api/tasks/bulk",
+    tag = "task_bulk",
+    request_body = BulkTaskRequest,
+    responses((status = 200, body = ApiResponse<Vec<BulkTaskResult>>))
+)]
+pub async fn bulk_update_tasks(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<BulkTaskRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<BulkTaskResult>>>, ApiError> {
+    let mut seen = HashSet::new();
+    let task_ids: Vec<Uuid> = payload
+        .task_ids
+        .into_iter()
+        .filter(|id| seen.insert(*id))
+        .collect();
+    if task_ids.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one task id is required".to_string(),
+        ));
+    }
+    if task_ids.len() > MAX_BULK_TASKS {
+        return Err(ApiError::BadRequest(format!(
+            "At most {MAX_BULK_TASKS} tasks can be changed at once"
+        )));
+    }
+
+    let pool = &deployment.db().pool;
+    let operation = payload.operation;
+    let label = match &operation {
+        BulkTaskOperation::AddLabel { label_id } => Some(
+            Label::find_by_id(pool, *label_id)
+                .await?
+                .ok_or_else(|| ApiError::BadRequest(format!("Label {label_id} does not exist")))?,
+        ),
+        BulkTaskOperation::Assign {
+            assignee_id: Some(assignee_id),
+        } => {
+            users::ensure_exists(&deployment, *assignee_id).await?;
+            None
+        }
+        _ => None,
+    };
+
+    let mut results = Vec::with_capacity(task_ids.len());
+    let mut applicable = Vec::new();
+    // Per project, the column a status change moves tasks into and how many are entering
+    let mut wip_columns: HashMap<Uuid, (WipColumn, i64)> = HashMap::new();
+    for task_id in task_ids {
+        let error = match Task::find_by_id(pool, task_id).await? {
+            Some(task) if task.deleted_at.is_none() => {
+                let mut error = check_task(
+                    &deployment,
+                    user.as_deref(),
+                    caller.as_deref(),
+                    &task,
+                    &operation,
+                    label.as_ref(),
+                )
+                .await?;
+                if error.is_none()
+                    && let BulkTaskOperation::SetStatus { status } = &operation
+                    && *status != task.status
+                    && task.archived_at.is_none()
+                {
+                    let (column, entering) = match wip_columns.entry(task.project_id) {
+                        Entry::Occupied(entry) => entry.into_mut(),
+                        Entry::Vacant(entry) => {
+                            entry.insert((WipColumn::find(pool, task.project_id, status).await?, 0))
+                        }
+                    };
+                    if column.enforced && column.would_exceed(*entering + 1) {
+                        error = Some(wip_limits::limit_message(column));
+                    } else {
+                        *entering += 1;
+                    }
+                }
+                if error.is_none() {
+                    applicable.push(task);
+                }
+                error
+            }
+            _ => Some("Task not found".to_string()),
+        };
+        results.push(BulkTaskResult {
+            task_id,
+            ok: error.is_none(),
+            error,
+        });
+    }
+
+    let mut tx = pool.begin().await?;
+    for task in &applicable {
+        match &operation {
+            BulkTaskOperation::SetStatus { status } => {
+                Task::update_status(&mut *tx, task.id, status.clone()).await?;
+            }
+            BulkTaskOperation::AddLabel { label_id } => {
+                Label::add_to_task(&mut *tx, task.id, *label_id).await?;
+            }
+            BulkTaskOperation::Assign { assignee_id } => {
+                Task::update_assignee(&mut *tx, task.id, *assignee_id).await?;
+            }
+            BulkTaskOperation::Archive => {
+                Task::archive(&mut *tx, task.id).await?;
+            }
+            BulkTaskOperation::Delete => {
+                Task::trash(&mut *tx, task.id).await?;
+            }
+        }
+    }
+    tx.commit().await?;
+
+    for before in &applicable {
+        if let Some(after) = Task::find_by_id(pool, before.id).await? {
+            TaskEvent::record_changes(pool, before, &after, TaskEventSource::User, None).await?;
+        }
+    }
+
+    // Status changes to shared tasks are published once they are committed
+    if matches!(operation, BulkTaskOperation::SetStatus { .. })
+        && applicable.iter().any(|task| task.shared_task_id.is_some())
+        && let Ok(publisher) = deployment.share_publisher()
+    {
+        for task in applicable
+            .iter()
+            .filter(|task| task.shared_task_id.is_some())
+        {
+            let result = match Task::find_by_id(pool, task.id).await? {
+                Some(task) => publisher.update_shared_task(&task).await,
+                None => Ok(()),
+            };
+            if let Err(e) = result {
+                tracing::warn!("Failed to publish status of shared task {}: {}", task.id, e);
+            }
+        }
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "tasks_bulk_updated",
+            serde_json::json!({
+                "operation": operation.name(),
+                "task_count": results.len(),
+                "applied_count": applicable.len(),
+            }),
+        )
+        .await;
+
+    // Advisory limits don't stop the change, but the caller hears about them
+    let warnings: Vec<String> = wip_columns
+        .values()
+        .filter(|(column, entering)| !column.enforced && column.would_exceed(*entering))
+        .map(|(column, _)| wip_limits::limit_message(column))
+        .collect();
+    Ok(ResponseJson(if warnings.is_empty() {
+        ApiResponse::success(results)
+    } else {
+        ApiResponse::success_with_message(results, &warnings.join("; "))
+    }))
+}
+
+/// Routes nested under `/tasks`.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/bulk", post(bulk_update_tasks))
+}
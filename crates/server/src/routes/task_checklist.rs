@@ -0,0 +1,180 @@
+use std::collections::HashSet;
+
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    task::Task,
+    task_checklist_item::{
+        CreateTaskChecklistItem, ReorderTaskChecklistItems, TaskChecklistItem,
+        UpdateTaskChecklistItem,
+    },
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+fn validate_title(title: Option<&str>) -> Result<(), ApiError> {
+    if title.is_some_and(|title| title.trim().is_empty()) {
+        return Err(ApiError::BadRequest(
+            "Checklist item title must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Load a checklist item, checking that it belongs to the task in the path.
+async fn load_item(
+    deployment: &DeploymentImpl,
+    task_id: Uuid,
+    item_id: Uuid,
+) -> Result<TaskChecklistItem, ApiError> {
+    TaskChecklistItem::find_by_id(&deployment.db().pool, item_id)
+        .await?
+        .filter(|item| item.task_id == task_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/checklist",
+    tag = "task_checklist",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<TaskChecklistItem>>))
+)]
+pub async fn get_checklist(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskChecklistItem>>>, ApiError> {
+    let items = TaskChecklistItem::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(items)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/checklist",
+    tag = "task_checklist",
+    params(("task_id" = Uuid, Path)),
+    request_body = CreateTaskChecklistItem,
+    responses((status = 200, body = ApiResponse<TaskChecklistItem>))
+)]
+pub async fn create_checklist_item(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskChecklistItem>,
+) -> Result<ResponseJson<ApiResponse<TaskChecklistItem>>, ApiError> {
+    validate_title(Some(&payload.title))?;
+    let item = TaskChecklistItem::create(&deployment.db().pool, task.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_checklist_item_created",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(item)))
+}
+
+/// PUT /tasks/{task_id}/checklist/order
+/// Reorder the checklist. The payload must list every item of the task exactly once.
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}/checklist/order",
+    tag = "task_checklist",
+    params(("task_id" = Uuid, Path)),
+    request_body = ReorderTaskChecklistItems,
+    responses((status = 200, body = ApiResponse<Vec<TaskChecklistItem>>))
+)]
+pub async fn reorder_checklist(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ReorderTaskChecklistItems>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskChecklistItem>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let current: HashSet<Uuid> = TaskChecklistItem::find_by_task_id(pool, task.id)
+        .await?
+        .into_iter()
+        .map(|item| item.id)
+        .collect();
+    let requested: HashSet<Uuid> = payload.item_ids.iter().copied().collect();
+    if requested.len() != payload.item_ids.len() || requested != current {
+        return Err(ApiError::BadRequest(
+            "item_ids must list every checklist item of the task exactly once".to_string(),
+        ));
+    }
+
+    TaskChecklistItem::reorder(pool, task.id, &payload.item_ids).await?;
+    let items = TaskChecklistItem::find_by_task_id(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(items)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}/checklist/{item_id}",
+    tag = "task_checklist",
+    params(("task_id" = Uuid, Path), ("item_id" = Uuid, Path)),
+    request_body = UpdateTaskChecklistItem,
+    responses((status = 200, body = ApiResponse<TaskChecklistItem>))
+)]
+pub async fn update_checklist_item(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, item_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateTaskChecklistItem>,
+) -> Result<ResponseJson<ApiResponse<TaskChecklistItem>>, ApiError> {
+    let item = load_item(&deployment, task_id, item_id).await?;
+    validate_title(payload.title.as_deref())?;
+
+    let item = TaskChecklistItem::update(&deployment.db().pool, item.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(item)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/checklist/{item_id}",
+    tag = "task_checklist",
+    params(("task_id" = Uuid, Path), ("item_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_checklist_item(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, item_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let item = load_item(&deployment, task_id, item_id).await?;
+    TaskChecklistItem::delete(&deployment.db().pool, item.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_checklist_item_deleted",
+            serde_json::json!({
+                "task_id": task_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/checklist", get(get_checklist).post(create_checklist_item))
+        .route("/checklist/order", put(reorder_checklist))
+}
+
+/// Routes nested under `/tasks`. The task loader only understands a single path
+/// parameter, so these load the item themselves.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/{task_id}/checklist/{item_id}",
+        put(update_checklist_item).delete(delete_checklist_item),
+    )
+}
@@ -0,0 +1,159 @@
+use axum::{
+    Extension, Json, Router, extract::State, response::Json as ResponseJson, routing::post,
+};
+use db::models::{
+    api_key::ApiKey,
+    label::Label,
+    project::Project,
+    project_member::ProjectRole,
+    task::{CloneTask, CreateTask, Task},
+    task_attachment::TaskAttachment,
+    task_checklist_item::{CreateTaskChecklistItem, TaskChecklistItem},
+    task_event::{TaskEvent, TaskEventSource},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::AuthUser, rbac::require_role},
+};
+
+/// POST /tasks/{task_id}/clone
+/// Create a copy of the task, in its project or another one.
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/clone",
+    tag = "task_clone",
+    params(("task_id" = Uuid, Path)),
+    request_body = CloneTask,
+    responses((status = 200, body = ApiResponse<Task>))
+)]
+pub async fn clone_task(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CloneTask>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let project_id = payload.project_id.unwrap_or(task.project_id);
+    if project_id != task.project_id {
+        if Project::find_by_id(pool, project_id).await?.is_none() {
+            return Err(ApiError::BadRequest(format!(
+                "Project {project_id} does not exist"
+            )));
+        }
+        // The source project was checked on the way in; the copy needs a member there too
+        require_role(
+            &deployment,
+            user.as_deref(),
+            caller.as_deref(),
+            project_id,
+            ProjectRole::Member,
+        )
+        .await?;
+    }
+    let title = match &payload.title {
+        Some(title) if title.trim().is_empty() => {
+            return Err(ApiError::BadRequest(
+                "Task title must not be empty".to_string(),
+            ));
+        }
+        Some(title) => title.trim().to_string(),
+        None => task.title.clone(),
+    };
+    if let Some(shared_task_id) = payload.shared_task_id
+        && Task::find_by_shared_task_id(pool, shared_task_id)
+            .await?
+            .is_some()
+    {
+        return Err(ApiError::Conflict(format!(
+            "Shared task {shared_task_id} is already linked to a task"
+        )));
+    }
+
+    let mut create =
+        CreateTask::from_title_description(project_id, title, task.description.clone());
+    create.shared_task_id = payload.shared_task_id;
+    create.due_at = task.due_at;
+    create.priority = Some(task.priority);
+    create.estimate = task.estimate;
+    create.assignee_id = task.assignee_id;
+    let clone = Task::create(pool, &create, Uuid::new_v4()).await?;
+    TaskEvent::record_created(pool, &clone, TaskEventSource::User, None).await?;
+
+    if payload.copy_checklist {
+        for item in TaskChecklistItem::find_by_task_id(pool, task.id).await? {
+            TaskChecklistItem::create(
+                pool,
+                clone.id,
+                &CreateTaskChecklistItem { title: item.title },
+            )
+            .await?;
+        }
+    }
+    if payload.copy_labels {
+        for label in Label::find_by_task_id(pool, task.id).await? {
+            let label_id = if project_id == task.project_id {
+                Some(label.id)
+            } else {
+                Label::find_by_name(pool, project_id, &label.name)
+                    .await?
+                    .map(|label| label.id)
+            };
+            if let Some(label_id) = label_id {
+                Label::add_to_task(pool, clone.id, label_id).await?;
+            }
+        }
+    }
+    let mut cover_attachment_id = None;
+    if payload.copy_attachments {
+        for attachment in TaskAttachment::find_by_task_id(pool, task.id).await? {
+            let copy = deployment
+                .attachment()
+                .copy_attachment(&attachment, clone.id)
+                .await?;
+            if task.cover_attachment_id == Some(attachment.id) {
+                cover_attachment_id = Some(copy.id);
+            }
+        }
+    }
+    // The cover image comes along only when its attachment was copied
+    let clone = if task.cover_color.is_some() || cover_attachment_id.is_some() {
+        Task::update_cover(
+            pool,
+            clone.id,
+            task.cover_color.as_deref(),
+            cover_attachment_id,
+        )
+        .await?
+    } else {
+        clone
+    };
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_cloned",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "clone_id": clone.id.to_string(),
+                "project_id": project_id.to_string(),
+                "cross_project": project_id != task.project_id,
+                "copy_checklist": payload.copy_checklist,
+                "copy_labels": payload.copy_labels,
+                "copy_attachments": payload.copy_attachments,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(clone)))
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new().route("/clone", post(clone_task))
+}
@@ -0,0 +1,177 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{delete, get},
+};
+use db::models::{
+    notification::{self, Notification, NotificationKind},
+    task::Task,
+    task_comment::{CreateTaskComment, TaskComment},
+    task_mention::TaskMention,
+    task_watcher::{TaskWatcher, WatchReason},
+    user::User,
+};
+use deployment::Deployment;
+use services::services::events::ProjectEventKind;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::auth::AuthUser};
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/comments",
+    tag = "task_comments",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<TaskComment>>))
+)]
+pub async fn get_task_comments(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskComment>>>, ApiError> {
+    let comments = TaskComment::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(comments)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/comments",
+    tag = "task_comments",
+    params(("task_id" = Uuid, Path)),
+    request_body = CreateTaskComment,
+    responses((status = 200, body = ApiResponse<TaskComment>))
+)]
+pub async fn create_task_comment(
+    user: Option<Extension<AuthUser>>,
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(mut payload): Json<CreateTaskComment>,
+) -> Result<ResponseJson<ApiResponse<TaskComment>>, ApiError> {
+    if let Some(Extension(AuthUser(user))) = &user {
+        payload.author = user.name.clone();
+    }
+    if payload.author.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Comment author must not be empty".to_string(),
+        ));
+    }
+    if payload.body.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Comment body must not be empty".to_string(),
+        ));
+    }
+
+    let pool = &deployment.db().pool;
+    let comment = TaskComment::create(pool, task.id, &payload).await?;
+
+    // Signed-in authors are known; otherwise authors are free text, and one that names a
+    // single user makes them a watcher
+    let author = match user {
+        Some(Extension(AuthUser(user))) => Some(user),
+        None => User::find_by_unique_name(pool, payload.author.trim()).await?,
+    };
+    if let Some(author) = &author {
+        TaskWatcher::watch(pool, task.id, author.id, WatchReason::Commented).await?;
+    }
+    let author_id = author.map(|author| author.id);
+    let excerpt = notification::excerpt(&comment.body);
+
+    // Mentioned users hear about the comment once, as a mention
+    let mut conn = pool.acquire().await?;
+    let mut skip = TaskMention::record(
+        &mut conn,
+        task.id,
+        Some(comment.id),
+        &comment.body,
+        author_id,
+        &format!("{} mentioned you: {excerpt}", comment.author),
+    )
+    .await?;
+    skip.extend(author_id);
+    Notification::notify_watchers(
+        &mut *conn,
+        task.id,
+        NotificationKind::Commented,
+        &format!("{} commented: {excerpt}", comment.author),
+        &skip,
+    )
+    .await?;
+
+    deployment.events().activity().publish(
+        task.project_id,
+        ProjectEventKind::CommentCreated,
+        Some(task.id),
+        serde_json::to_value(&comment).unwrap_or_default(),
+    );
+    deployment
+        .track_if_analytics_allowed(
+            "task_comment_created",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(comment)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/comments/{comment_id}",
+    tag = "task_comments",
+    params(("task_id" = Uuid, Path), ("comment_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_task_comment(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = Task::find_by_id(pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    // Comments are only reachable through the task they belong to
+    TaskComment::find_by_id(pool, comment_id)
+        .await?
+        .filter(|comment| comment.task_id == task.id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+
+    TaskComment::delete(pool, comment_id).await?;
+
+    deployment.events().activity().publish(
+        task.project_id,
+        ProjectEventKind::CommentDeleted,
+        Some(task.id),
+        serde_json::json!({ "id": comment_id }),
+    );
+    deployment
+        .track_if_analytics_allowed(
+            "task_comment_deleted",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/comments",
+        get(get_task_comments).post(create_task_comment),
+    )
+}
+
+/// Routes nested under `/tasks`. The task loader only understands a single path
+/// parameter, so these load the task themselves.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/{task_id}/comments/{comment_id}",
+        delete(delete_task_comment),
+    )
+}
@@ -0,0 +1,72 @@
+use axum::{
+    Extension, Router,
+    extract::{Query, State},
+    http::HeaderMap,
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::{task::Task, task_event::TaskEvent};
+use deployment::Deployment;
+use serde::Deserialize;
+use utils::response::ApiResponse;
+use utoipa::IntoParams;
+
+use crate::{DeploymentImpl, error::ApiError, routes};
+
+const MAX_LIMIT: i64 = 500;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskActivityQuery {
+    /// Page size; the whole feed when omitted
+    #[serde(default)]
+    pub limit: Option<i64>,
+    /// Continue after the page whose `X-Next-Cursor` header this is
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// GET /tasks/{task_id}/activity
+/// Everything that happened to the task, oldest first.
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/activity",
+    tag = "task_events",
+    params(("task_id" = uuid::Uuid, Path), TaskActivityQuery),
+    responses((
+        status = 200,
+        body = ApiResponse<Vec<TaskEvent>>,
+        headers(("X-Next-Cursor" = String))
+    ))
+)]
+pub async fn get_task_activity(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskActivityQuery>,
+) -> Result<(HeaderMap, ResponseJson<ApiResponse<Vec<TaskEvent>>>), ApiError> {
+    if query
+        .limit
+        .is_some_and(|limit| !(1..=MAX_LIMIT).contains(&limit))
+    {
+        return Err(ApiError::BadRequest(format!(
+            "limit must be between 1 and {MAX_LIMIT}"
+        )));
+    }
+    let after = routes::decode_cursor(query.cursor.as_deref())?;
+    let page = TaskEvent::find_page_by_task_id(
+        &deployment.db().pool,
+        task.id,
+        after.as_ref(),
+        query.limit,
+    )
+    .await?;
+    Ok((
+        routes::next_cursor_header(page.next_cursor.as_deref()),
+        ResponseJson(ApiResponse::success(page.items)),
+    ))
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new().route("/activity", get(get_task_activity))
+}
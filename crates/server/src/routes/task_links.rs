@@ -0,0 +1,123 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{delete, get},
+};
+use db::models::{
+    task::Task,
+    task_link::{CreateTaskLink, LinkedTask, TaskLink},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/links",
+    tag = "task_links",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<LinkedTask>>))
+)]
+pub async fn get_task_links(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<LinkedTask>>>, ApiError> {
+    let links = TaskLink::find_for_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(links)))
+}
+
+/// POST /tasks/{task_id}/links
+/// Link the task to another. Dependencies that would form a cycle and tasks marked as
+/// duplicates of each other are rejected.
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/links",
+    tag = "task_links",
+    params(("task_id" = Uuid, Path)),
+    request_body = CreateTaskLink,
+    responses((status = 200, body = ApiResponse<TaskLink>))
+)]
+pub async fn create_task_link(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskLink>,
+) -> Result<ResponseJson<ApiResponse<TaskLink>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if payload.task_id == task.id {
+        return Err(ApiError::BadRequest(
+            "A task cannot be linked to itself".to_string(),
+        ));
+    }
+    if Task::find_by_id(pool, payload.task_id).await?.is_none() {
+        return Err(ApiError::BadRequest(format!(
+            "Task {} does not exist",
+            payload.task_id
+        )));
+    }
+
+    let (source, target, kind) = TaskLink::orient(task.id, payload.task_id, payload.kind);
+    if let Some(conflict) = TaskLink::find_conflict(pool, source, target, kind).await? {
+        return Err(ApiError::Conflict(conflict.to_string()));
+    }
+
+    let link = TaskLink::create(pool, task.id, payload.task_id, payload.kind).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_link_created",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "kind": payload.kind.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(link)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/links/{link_id}",
+    tag = "task_links",
+    params(("task_id" = Uuid, Path), ("link_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_task_link(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, link_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    // Links are reachable through either of their tasks
+    let link = TaskLink::find_by_id(pool, link_id)
+        .await?
+        .filter(|link| link.source_task_id == task_id || link.target_task_id == task_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+
+    TaskLink::delete(pool, link.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_link_deleted",
+            serde_json::json!({
+                "task_id": task_id.to_string(),
+                "kind": link.kind.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new().route("/links", get(get_task_links).post(create_task_link))
+}
+
+/// Routes nested under `/tasks`. The task loader only understands a single path
+/// parameter, so these load the link themselves.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/{task_id}/links/{link_id}", delete(delete_task_link))
+}
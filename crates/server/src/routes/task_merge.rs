@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+
+use axum::{
+    Extension, Json, Router, extract::State, response::Json as ResponseJson, routing::post,
+};
+use db::models::{
+    task::Task,
+    task_merge::{self, MergeTasks},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// POST /tasks/{task_id}/merge
+/// Fold duplicate tasks into this one. Their descriptions are appended to the task's,
+/// their comments, attachments and watchers move over, and they are archived.
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/merge",
+    tag = "task_merge",
+    params(("task_id" = uuid::Uuid, Path)),
+    request_body = MergeTasks,
+    responses((status = 200, body = ApiResponse<Task>))
+)]
+pub async fn merge_tasks(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<MergeTasks>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if payload.task_ids.is_empty() {
+        return Err(ApiError::BadRequest(
+            "task_ids must not be empty".to_string(),
+        ));
+    }
+    if task.deleted_at.is_some() {
+        return Err(ApiError::BadRequest(
+            "Cannot merge into a task in the trash".to_string(),
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    let mut merged = Vec::with_capacity(payload.task_ids.len());
+    for &task_id in &payload.task_ids {
+        if task_id == task.id {
+            return Err(ApiError::BadRequest(
+                "A task cannot be merged into itself".to_string(),
+            ));
+        }
+        if !seen.insert(task_id) {
+            return Err(ApiError::BadRequest(format!(
+                "Task {task_id} is listed more than once"
+            )));
+        }
+        let source = Task::find_by_id(pool, task_id)
+            .await?
+            .ok_or_else(|| ApiError::BadRequest(format!("Task {task_id} does not exist")))?;
+        if source.project_id != task.project_id {
+            return Err(ApiError::BadRequest(format!(
+                "Task {task_id} belongs to another project"
+            )));
+        }
+        if source.deleted_at.is_some() {
+            return Err(ApiError::BadRequest(format!(
+                "Task {task_id} is in the trash"
+            )));
+        }
+        merged.push(source);
+    }
+
+    let (updated, _) = task_merge::merge(pool, &task, &merged).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "tasks_merged",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+                "merged_count": merged.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new().route("/merge", post(merge_tasks))
+}
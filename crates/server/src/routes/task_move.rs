@@ -0,0 +1,114 @@
+use axum::{
+    Extension, Json, Router, extract::State, response::Json as ResponseJson, routing::post,
+};
+use db::models::{
+    api_key::ApiKey,
+    project::Project,
+    project_column::{MoveTaskToProject, ProjectColumn},
+    project_member::ProjectRole,
+    task::Task,
+    task_event::{TaskEvent, TaskEventSource},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::AuthUser, rbac::require_role},
+    routes::wip_limits,
+};
+
+/// POST /tasks/{task_id}/move-to-project
+/// Move the task, with its comments, attachments and history, to another project. The
+/// task's column is remapped onto the target board, which sets its status there.
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/move-to-project",
+    tag = "task_move",
+    params(("task_id" = uuid::Uuid, Path)),
+    request_body = MoveTaskToProject,
+    responses((status = 200, body = ApiResponse<Task>))
+)]
+pub async fn move_task_to_project(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<MoveTaskToProject>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if payload.project_id == task.project_id {
+        return Err(ApiError::BadRequest(
+            "Task is already in this project".to_string(),
+        ));
+    }
+    if Project::find_by_id(pool, payload.project_id)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Project {} does not exist",
+            payload.project_id
+        )));
+    }
+    // The source project was checked on the way in; the task needs a member on both sides
+    require_role(
+        &deployment,
+        user.as_deref(),
+        caller.as_deref(),
+        payload.project_id,
+        ProjectRole::Member,
+    )
+    .await?;
+
+    let column = match task.column_id {
+        Some(column_id) => ProjectColumn::find_by_id(pool, column_id).await?,
+        None => None,
+    };
+    let target_columns = ProjectColumn::find_by_project_id(pool, payload.project_id).await?;
+    let target = ProjectColumn::map_to_project(
+        &target_columns,
+        column.as_ref(),
+        &task.status,
+        &payload.column_mapping,
+    )
+    .map_err(ApiError::BadRequest)?;
+
+    let warning = if task.archived_at.is_none() {
+        wip_limits::check_move(&deployment, payload.project_id, &target.category, 1).await?
+    } else {
+        None
+    };
+    let moved = Task::move_to_project(
+        pool,
+        task.id,
+        payload.project_id,
+        target.id,
+        target.category.clone(),
+    )
+    .await?;
+    TaskEvent::record_changes(pool, &task, &moved, TaskEventSource::User, None).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_moved_to_project",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "from_project_id": task.project_id.to_string(),
+                "to_project_id": moved.project_id.to_string(),
+                "status_changed": moved.status != task.status,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(match warning {
+        Some(warning) => ApiResponse::success_with_message(moved, &warning),
+        None => ApiResponse::success(moved),
+    }))
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new().route("/move-to-project", post(move_task_to_project))
+}
@@ -0,0 +1,105 @@
+use axum::{
+    Extension, Json, Router, extract::State, response::Json as ResponseJson, routing::post,
+};
+use chrono::{NaiveDate, Utc};
+use db::models::{
+    api_key::ApiKey,
+    project_member::ProjectRole,
+    quick_add::{QuickAddError, QuickAddResult},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::AuthUser, rbac::require_role},
+};
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct QuickAddRequest {
+    pub project_id: Uuid,
+    /// e.g. `Fix login bug #bug @alice !high ^friday`
+    pub text: String,
+    /// The caller's date, which `^today` and `^friday` count from; the server's UTC
+    /// date when left out
+    #[serde(default)]
+    #[ts(optional)]
+    pub today: Option<NaiveDate>,
+    /// Parse the line and look up its labels and assignee without creating the task
+    #[serde(default)]
+    #[ts(optional)]
+    pub dry_run: Option<bool>,
+}
+
+impl From<QuickAddError> for ApiError {
+    fn from(err: QuickAddError) -> Self {
+        match err {
+            QuickAddError::Database(err) => ApiError::Database(err),
+            QuickAddError::NoTitle => ApiError::BadRequest(err.to_string()),
+        }
+    }
+}
+
+/// POST /tasks/quick-add
+/// Create a task from a single line: `#label`, `@user`, `!priority` and `^due` tokens
+/// set its labels, assignee, priority and due date, and the rest becomes the title.
+/// Labels and users the tokens don't match are reported and left off the task.
+#[utoipa::path(
+    post,
+    path = "/api/tasks/quick-add",
+    tag = "task_quick_add",
+    request_body = QuickAddRequest,
+    responses((status = 200, body = ApiResponse<QuickAddResult>))
+)]
+pub async fn quick_add_task(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<QuickAddRequest>,
+) -> Result<ResponseJson<ApiResponse<QuickAddResult>>, ApiError> {
+    require_role(
+        &deployment,
+        user.as_deref(),
+        caller.as_deref(),
+        payload.project_id,
+        ProjectRole::Member,
+    )
+    .await?;
+    let dry_run = payload.dry_run.unwrap_or(false);
+    let result = QuickAddResult::create(
+        &deployment.db().pool,
+        payload.project_id,
+        &payload.text,
+        payload.today.unwrap_or_else(|| Utc::now().date_naive()),
+        dry_run,
+    )
+    .await?;
+
+    if let Some(task) = &result.task {
+        deployment
+            .track_if_analytics_allowed(
+                "task_quick_added",
+                serde_json::json!({
+                    "task_id": task.id.to_string(),
+                    "project_id": task.project_id,
+                    "label_count": result.parsed.labels.len(),
+                    "has_assignee": task.assignee_id.is_some(),
+                    "has_priority": result.parsed.priority.is_some(),
+                    "has_due_date": task.due_at.is_some(),
+                }),
+            )
+            .await;
+    }
+
+    Ok(ResponseJson(ApiResponse::success(result)))
+}
+
+/// Routes nested under `/tasks`.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/quick-add", post(quick_add_task))
+}
@@ -0,0 +1,247 @@
+use axum::{
+    Extension, Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    custom_field::CustomField,
+    integration_link::IntegrationLink,
+    task::Task,
+    task_diff::{DiffSide, FieldValues, TaskDiff, revision_values, task_values},
+    task_event::{TaskEvent, TaskEventSource},
+    task_revision::TaskRevision,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::share::ShareError;
+use ts_rs::TS;
+use utils::{diff::create_unified_diff, response::ApiResponse};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::tasks::ensure_shared_task_auth};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RevisionDiffQuery {
+    pub from: i64,
+    pub to: i64,
+}
+
+/// Unified diffs between two revisions of a task, in the format the diff viewer takes.
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct TaskRevisionDiff {
+    pub from: i64,
+    pub to: i64,
+    /// `None` when the title is the same in both revisions
+    pub title: Option<String>,
+    /// `None` when the description is the same in both revisions
+    pub description: Option<String>,
+}
+
+async fn load_revision(
+    deployment: &DeploymentImpl,
+    task_id: Uuid,
+    revision: i64,
+) -> Result<TaskRevision, ApiError> {
+    TaskRevision::find_by_revision(&deployment.db().pool, task_id, revision)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+fn diff_field(name: &str, old: &str, new: &str) -> Option<String> {
+    (old != new).then(|| create_unified_diff(name, old, new))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/revisions",
+    tag = "task_revisions",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<TaskRevision>>))
+)]
+pub async fn get_task_revisions(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskRevision>>>, ApiError> {
+    let revisions = TaskRevision::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(revisions)))
+}
+
+/// GET /tasks/{task_id}/revisions/diff?from=...&to=...
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/revisions/diff",
+    tag = "task_revisions",
+    params(("task_id" = Uuid, Path), RevisionDiffQuery),
+    responses((status = 200, body = ApiResponse<TaskRevisionDiff>))
+)]
+pub async fn diff_task_revisions(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<RevisionDiffQuery>,
+) -> Result<ResponseJson<ApiResponse<TaskRevisionDiff>>, ApiError> {
+    let from = load_revision(&deployment, task.id, query.from).await?;
+    let to = load_revision(&deployment, task.id, query.to).await?;
+
+    Ok(ResponseJson(ApiResponse::success(TaskRevisionDiff {
+        from: from.revision,
+        to: to.revision,
+        title: diff_field("title", &from.title, &to.title),
+        description: diff_field(
+            "description",
+            from.description.as_deref().unwrap_or_default(),
+            to.description.as_deref().unwrap_or_default(),
+        ),
+    })))
+}
+
+#[derive(Debug, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskDiffQuery {
+    /// A revision number, `local` for the task as it is now, or `remote` for the linked
+    /// issue as of the last sync
+    pub from: String,
+    /// Same choices as `from`; `local` when omitted
+    #[serde(default)]
+    pub to: Option<String>,
+    /// The integration whose issue `remote` means, for tasks linked through several; the
+    /// first linked one when omitted
+    #[serde(default)]
+    pub integration_id: Option<Uuid>,
+}
+
+async fn side_values(
+    deployment: &DeploymentImpl,
+    task: &Task,
+    side: DiffSide,
+    integration_id: Option<Uuid>,
+) -> Result<FieldValues, ApiError> {
+    let pool = &deployment.db().pool;
+    match side {
+        DiffSide::Revision(revision) => Ok(revision_values(
+            &load_revision(deployment, task.id, revision).await?,
+        )),
+        DiffSide::Local => {
+            let custom_fields = CustomField::find_values_for_task(pool, task.id).await?;
+            Ok(task_values(task, &custom_fields))
+        }
+        DiffSide::Remote => {
+            let link = IntegrationLink::find_by_task_id(pool, task.id)
+                .await?
+                .into_iter()
+                .find(|link| integration_id.is_none_or(|id| link.integration_id == id))
+                .ok_or_else(|| {
+                    ApiError::BadRequest("The task is not linked to an external issue".to_string())
+                })?;
+            let Some(values) = link.remote_values else {
+                return Err(ApiError::BadRequest(
+                    "The linked issue has not been synced yet".to_string(),
+                ));
+            };
+            Ok(values.0.into_iter().collect())
+        }
+    }
+}
+
+/// GET /tasks/{task_id}/diff?from=...&to=...
+/// Field-level changes between two revisions, a revision and the task as it is now, or
+/// the task and its linked issue as of the last sync, for showing what a sync changed.
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/diff",
+    tag = "task_revisions",
+    params(("task_id" = Uuid, Path), TaskDiffQuery),
+    responses((status = 200, body = ApiResponse<TaskDiff>))
+)]
+pub async fn get_task_diff(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskDiffQuery>,
+) -> Result<ResponseJson<ApiResponse<TaskDiff>>, ApiError> {
+    let from: DiffSide = query.from.parse().map_err(ApiError::BadRequest)?;
+    let to: DiffSide = match query.to.as_deref() {
+        Some(to) => to.parse().map_err(ApiError::BadRequest)?,
+        None => DiffSide::Local,
+    };
+    let from_values = side_values(&deployment, &task, from, query.integration_id).await?;
+    let to_values = side_values(&deployment, &task, to, query.integration_id).await?;
+    Ok(ResponseJson(ApiResponse::success(TaskDiff::between(
+        from,
+        &from_values,
+        to,
+        &to_values,
+    ))))
+}
+
+/// POST /tasks/{task_id}/revisions/{revision}/revert
+/// Restore the title and description from an earlier revision. The revert is itself
+/// recorded as a new revision, so it can be undone the same way.
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/revisions/{revision}/revert",
+    tag = "task_revisions",
+    params(("task_id" = Uuid, Path), ("revision" = i64, Path)),
+    responses((status = 200, body = ApiResponse<Task>))
+)]
+pub async fn revert_task_revision(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, revision)): Path<(Uuid, i64)>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let existing_task = Task::find_by_id(pool, task_id)
+        .await?
+        .filter(|task| task.deleted_at.is_none())
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let revision = load_revision(&deployment, task_id, revision).await?;
+    ensure_shared_task_auth(&existing_task, &deployment).await?;
+
+    let task = Task::update(
+        pool,
+        existing_task.id,
+        existing_task.project_id,
+        revision.title,
+        revision.description,
+        existing_task.status.clone(),
+        existing_task.parent_workspace_id,
+    )
+    .await?;
+    TaskEvent::record_changes(pool, &existing_task, &task, TaskEventSource::User, None).await?;
+
+    if task.shared_task_id.is_some() {
+        let Ok(publisher) = deployment.share_publisher() else {
+            return Err(ShareError::MissingConfig("share publisher unavailable").into());
+        };
+        publisher.update_shared_task(&task).await?;
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_revision_reverted",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "revision": revision.revision,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/revisions", get(get_task_revisions))
+        .route("/revisions/diff", get(diff_task_revisions))
+        .route("/diff", get(get_task_diff))
+}
+
+/// Routes nested under `/tasks`. These load the task themselves since the task loader
+/// only understands a single path parameter.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/{task_id}/revisions/{revision}/revert",
+        post(revert_task_revision),
+    )
+}
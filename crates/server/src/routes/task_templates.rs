@@ -0,0 +1,289 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, post, put},
+};
+use chrono::Utc;
+use db::models::{
+    api_key::ApiKey,
+    label::Label,
+    project::Project,
+    project_member::ProjectRole,
+    task::Task,
+    task_event::{TaskEvent, TaskEventSource},
+    task_template::{CreateTaskFromTemplate, CreateTaskTemplate, TaskTemplate, UpdateTaskTemplate},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{auth::AuthUser, rbac::require_role},
+};
+
+fn validate_template(
+    name: Option<&str>,
+    title_pattern: Option<&str>,
+    checklist: Option<&[String]>,
+) -> Result<(), ApiError> {
+    if name.is_some_and(|name| name.trim().is_empty()) {
+        return Err(ApiError::BadRequest(
+            "Template name must not be empty".to_string(),
+        ));
+    }
+    if title_pattern.is_some_and(|pattern| pattern.trim().is_empty()) {
+        return Err(ApiError::BadRequest(
+            "Template title pattern must not be empty".to_string(),
+        ));
+    }
+    if checklist.is_some_and(|items| items.iter().any(|item| item.trim().is_empty())) {
+        return Err(ApiError::BadRequest(
+            "Checklist items must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Every default label must belong to the template's project.
+async fn validate_labels(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    label_ids: &[Uuid],
+) -> Result<(), ApiError> {
+    let project_labels = Label::find_by_project_id(&deployment.db().pool, project_id).await?;
+    if let Some(unknown) = label_ids
+        .iter()
+        .find(|id| !project_labels.iter().any(|label| label.id == **id))
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Label {unknown} does not belong to the template's project"
+        )));
+    }
+    Ok(())
+}
+
+/// Reject a name already used by another template in the project.
+async fn ensure_unique_name(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    name: &str,
+    except: Option<Uuid>,
+) -> Result<(), ApiError> {
+    if let Some(existing) =
+        TaskTemplate::find_by_name(&deployment.db().pool, project_id, name).await?
+        && Some(existing.id) != except
+    {
+        return Err(ApiError::Conflict(format!(
+            "A template named '{name}' already exists in this project"
+        )));
+    }
+    Ok(())
+}
+
+/// Load a template, checking that it belongs to the project in the path.
+pub async fn load_template(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    template_id: Uuid,
+) -> Result<TaskTemplate, ApiError> {
+    TaskTemplate::find_by_id(&deployment.db().pool, template_id)
+        .await?
+        .filter(|template| template.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/task-templates",
+    tag = "task_templates",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<TaskTemplate>>))
+)]
+pub async fn get_task_templates(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskTemplate>>>, ApiError> {
+    let templates = TaskTemplate::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(templates)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/task-templates",
+    tag = "task_templates",
+    params(("id" = Uuid, Path)),
+    request_body = CreateTaskTemplate,
+    responses((status = 200, body = ApiResponse<TaskTemplate>))
+)]
+pub async fn create_task_template(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskTemplate>,
+) -> Result<ResponseJson<ApiResponse<TaskTemplate>>, ApiError> {
+    validate_template(
+        Some(&payload.name),
+        Some(&payload.title_pattern),
+        Some(&payload.checklist),
+    )?;
+    validate_labels(&deployment, project.id, &payload.label_ids).await?;
+    ensure_unique_name(&deployment, project.id, &payload.name, None).await?;
+
+    let template = TaskTemplate::create(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_template_created",
+            serde_json::json!({
+                "template_id": template.id.to_string(),
+                "project_id": project.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/task-templates/{template_id}",
+    tag = "task_templates",
+    params(("project_id" = Uuid, Path), ("template_id" = Uuid, Path)),
+    request_body = UpdateTaskTemplate,
+    responses((status = 200, body = ApiResponse<TaskTemplate>))
+)]
+pub async fn update_task_template(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, template_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateTaskTemplate>,
+) -> Result<ResponseJson<ApiResponse<TaskTemplate>>, ApiError> {
+    let template = load_template(&deployment, project_id, template_id).await?;
+    validate_template(
+        payload.name.as_deref(),
+        payload.title_pattern.as_deref(),
+        payload.checklist.as_deref(),
+    )?;
+    if let Some(label_ids) = &payload.label_ids {
+        validate_labels(&deployment, project_id, label_ids).await?;
+    }
+    if let Some(name) = &payload.name {
+        ensure_unique_name(&deployment, project_id, name, Some(template.id)).await?;
+    }
+
+    let template = TaskTemplate::update(&deployment.db().pool, template.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/task-templates/{template_id}",
+    tag = "task_templates",
+    params(("project_id" = Uuid, Path), ("template_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_task_template(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, template_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let template = load_template(&deployment, project_id, template_id).await?;
+    TaskTemplate::delete(&deployment.db().pool, template.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_template_deleted",
+            serde_json::json!({
+                "template_id": template.id.to_string(),
+                "project_id": project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// POST /tasks/from-template/{template_id}
+/// Create a task in the template's project, filling in its placeholders and applying
+/// its labels and checklist.
+#[utoipa::path(
+    post,
+    path = "/api/tasks/from-template/{template_id}",
+    tag = "task_templates",
+    params(("template_id" = Uuid, Path)),
+    request_body = CreateTaskFromTemplate,
+    responses((status = 200, body = ApiResponse<Task>))
+)]
+pub async fn create_task_from_template(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Path(template_id): Path<Uuid>,
+    Json(payload): Json<CreateTaskFromTemplate>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let template = TaskTemplate::find_by_id(pool, template_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    // The path names no project, so the template's is checked here
+    require_role(
+        &deployment,
+        user.as_deref(),
+        caller.as_deref(),
+        template.project_id,
+        ProjectRole::Member,
+    )
+    .await?;
+
+    let (title, description) =
+        template
+            .render(&payload.variables, Utc::now())
+            .map_err(|missing| {
+                ApiError::BadRequest(format!(
+                    "Missing values for template placeholders: {}",
+                    missing.join(", ")
+                ))
+            })?;
+    let task = template.instantiate(pool, title, description).await?;
+    TaskEvent::record_created(pool, &task, TaskEventSource::User, None).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_created",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id,
+                "has_description": task.description.is_some(),
+                "has_images": false,
+                "template_id": template.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
+/// Routes nested under `/projects/{id}`, behind the project loading middleware.
+pub fn project_router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/task-templates",
+        get(get_task_templates).post(create_task_template),
+    )
+}
+
+/// Routes nested under `/projects`. The project loader only understands a single path
+/// parameter, so these load the template themselves.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/{project_id}/task-templates/{template_id}",
+        put(update_task_template).delete(delete_task_template),
+    )
+}
+
+/// Routes nested under `/tasks`.
+pub fn from_template_router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/from-template/{template_id}",
+        post(create_task_from_template),
+    )
+}
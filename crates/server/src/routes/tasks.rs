@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::sync::LazyLock;
 
 use anyhow;
 use axum::{
@@ -7,16 +7,27 @@ use axum::{
         Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
-    http::StatusCode,
+    http::{
+        HeaderMap, HeaderValue,
+        header::{ETAG, IF_MATCH},
+    },
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
     routing::{delete, get, post, put},
 };
+use chrono::{DateTime, Utc};
 use db::models::{
+    api_key::ApiKey,
     image::TaskImage,
+    label::Label,
     project::{Project, ProjectError},
-    repo::Repo,
-    task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
+    project_member::ProjectRole,
+    task::{
+        CreateTask, Task, TaskCursor, TaskFilter, TaskPriority, TaskSort, TaskStatus,
+        TaskWithAttemptStatus, UpdateTask,
+    },
+    task_attachment::TaskAttachment,
+    task_event::{TaskEvent, TaskEventSource},
     workspace::{CreateWorkspace, Workspace},
     workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
 };
@@ -24,35 +35,179 @@ use deployment::Deployment;
 use executors::profile::ExecutorProfileId;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
-use services::services::{
-    container::ContainerService, share::ShareError, workspace_manager::WorkspaceManager,
-};
-use sqlx::Error as SqlxError;
+use services::services::{container::ContainerService, share::ShareError};
+use sqlx::{Error as SqlxError, SqliteConnection};
 use ts_rs::TS;
 use utils::{api::oauth::LoginStatus, response::ApiResponse};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_task_middleware,
-    routes::task_attempts::WorkspaceRepoInput,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{
+        auth::AuthUser, idempotency::idempotency, load_task_middleware, rbac::require_role,
+    },
+    routes::{
+        self, custom_fields, epics, labels, project_columns, recurrence, task_attachments,
+        task_attempts::WorkspaceRepoInput, task_batch, task_bulk, task_checklist, task_clone,
+        task_comments, task_events, task_links, task_merge, task_move, task_quick_add,
+        task_revisions, task_templates, time_entries, users, views, watchers, wip_limits,
+    },
 };
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct TaskQuery {
     pub project_id: Uuid,
+    /// Saved view whose filter fills in the criteria not given here
+    #[serde(default)]
+    pub view_id: Option<Uuid>,
+    #[serde(default)]
+    pub status: Option<TaskStatus>,
+    /// Text to look for in titles and descriptions
+    #[serde(default)]
+    pub q: Option<String>,
+    #[serde(default)]
+    pub label_id: Option<Uuid>,
+    #[serde(default)]
+    pub due_before: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub overdue: Option<bool>,
+    #[serde(default)]
+    pub priority: Option<TaskPriority>,
+    #[serde(default)]
+    pub assignee_id: Option<Uuid>,
+    #[serde(default)]
+    pub unassigned: Option<bool>,
+    #[serde(default)]
+    pub custom_field_id: Option<Uuid>,
+    #[serde(default)]
+    pub custom_field_value: Option<String>,
+    #[serde(default)]
+    pub sort: Option<TaskSort>,
+    /// Page size; all matching tasks when omitted
+    #[serde(default)]
+    pub limit: Option<i64>,
+    /// Matching tasks to skip, in sort order
+    #[serde(default)]
+    pub offset: Option<i64>,
+    /// Continue after the page whose `X-Next-Cursor` header this is, listed with the same
+    /// sort; stable while tasks are added or moved, unlike `offset`
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
+const MAX_LIMIT: i64 = 500;
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks",
+    tag = "tasks",
+    params(TaskQuery),
+    responses((
+        status = 200,
+        body = ApiResponse<Vec<TaskWithAttemptStatus>>,
+        headers(("X-Next-Cursor" = String))
+    ))
+)]
 pub async fn get_tasks(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<TaskQuery>,
-) -> Result<ResponseJson<ApiResponse<Vec<TaskWithAttemptStatus>>>, ApiError> {
-    let tasks =
-        Task::find_by_project_id_with_attempt_status(&deployment.db().pool, query.project_id)
-            .await?;
+) -> Result<
+    (
+        HeaderMap,
+        ResponseJson<ApiResponse<Vec<TaskWithAttemptStatus>>>,
+    ),
+    ApiError,
+> {
+    if query
+        .limit
+        .is_some_and(|limit| !(1..=MAX_LIMIT).contains(&limit))
+    {
+        return Err(ApiError::BadRequest(format!(
+            "limit must be between 1 and {MAX_LIMIT}"
+        )));
+    }
+    let offset = query.offset.unwrap_or(0);
+    if offset < 0 {
+        return Err(ApiError::BadRequest(
+            "offset must not be negative".to_string(),
+        ));
+    }
+    let after: Option<TaskCursor> = routes::decode_cursor(query.cursor.as_deref())?;
+    if after.is_some() && offset != 0 {
+        return Err(ApiError::BadRequest(
+            "cursor and offset cannot be combined".to_string(),
+        ));
+    }
+    let view_filter = match query.view_id {
+        Some(view_id) => {
+            views::load_view(&deployment, query.project_id, view_id)
+                .await?
+                .filter
+                .0
+        }
+        None => TaskFilter::default(),
+    };
+    let custom_field_id = query.custom_field_id.or(view_filter.custom_field_id);
+    let custom_field_value = match (custom_field_id, &query.custom_field_value) {
+        (Some(field_id), Some(value)) => Some(
+            custom_fields::normalize_filter_value(&deployment, query.project_id, field_id, value)
+                .await?,
+        ),
+        (None, Some(_)) => {
+            return Err(ApiError::BadRequest(
+                "custom_field_value requires custom_field_id".to_string(),
+            ));
+        }
+        _ => None,
+    };
+    let filter = TaskFilter {
+        status: query.status,
+        query: query.q.filter(|q| !q.trim().is_empty()),
+        label_id: query.label_id,
+        due_before: query.due_before,
+        overdue: query.overdue,
+        priority: query.priority,
+        assignee_id: query.assignee_id,
+        unassigned: query.unassigned,
+        custom_field_id,
+        custom_field_value,
+        sort: query.sort,
+    }
+    .or(&view_filter);
+    if after
+        .as_ref()
+        .is_some_and(|after| after.sort != filter.sort.unwrap_or_default())
+    {
+        return Err(ApiError::BadRequest(
+            "cursor belongs to a listing with another sort".to_string(),
+        ));
+    }
+    let page = Task::find_page_with_attempt_status(
+        &deployment.db().pool,
+        query.project_id,
+        &filter,
+        after.as_ref(),
+        query.limit,
+        offset,
+    )
+    .await?;
 
-    Ok(ResponseJson(ApiResponse::success(tasks)))
+    Ok((
+        routes::next_cursor_header(page.next_cursor.as_deref()),
+        ResponseJson(ApiResponse::success(page.items)),
+    ))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/tasks/stream/ws",
+    tag = "tasks",
+    params(TaskQuery),
+    responses((status = 101, description = "Switches to a WebSocket"))
+)]
 pub async fn stream_tasks_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -100,17 +255,133 @@ async fn handle_tasks_ws(
     Ok(())
 }
 
+/// Task updates must say which version of the task they were made against, or `*` for
+/// any; set `VK_REQUIRE_IF_MATCH=0` to let updates without `If-Match` through.
+static REQUIRE_IF_MATCH: LazyLock<bool> =
+    LazyLock::new(|| utils::env_flag_or("VK_REQUIRE_IF_MATCH", true));
+
+/// The task's `ETag`: its version, so any change, by a user or a sync, gives a new one.
+pub(crate) fn task_etag(task: &Task) -> String {
+    format!("\"{}\"", task.version)
+}
+
+fn etag_header(task: &Task) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Ok(value) = HeaderValue::from_str(&task_etag(task)) {
+        headers.insert(ETAG, value);
+    }
+    headers
+}
+
+/// The task versions the request's `If-Match` names; `None` when it allows any, with `*`
+/// or, unless required, by leaving the header out. Tags that aren't versions match none.
+fn if_match_versions(headers: &HeaderMap) -> Result<Option<Vec<i64>>, ApiError> {
+    let Some(if_match) = headers.get(IF_MATCH) else {
+        if *REQUIRE_IF_MATCH {
+            return Err(ApiError::PreconditionRequired(
+                "Send If-Match with the task's ETag, or * for any version, to update it"
+                    .to_string(),
+            ));
+        }
+        return Ok(None);
+    };
+    let if_match = if_match
+        .to_str()
+        .map_err(|_| ApiError::BadRequest("Invalid If-Match header".to_string()))?;
+    let tags = if_match.split(',').map(str::trim);
+    if tags.clone().any(|tag| tag == "*") {
+        return Ok(None);
+    }
+    Ok(Some(
+        tags.filter_map(|tag| tag.trim_matches('"').parse().ok())
+            .collect(),
+    ))
+}
+
+/// GET /tasks/{task_id}
+/// The `ETag` header identifies this version of the task; send it back as `If-Match` when
+/// updating.
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}",
+    tag = "tasks",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Task>, headers(("ETag" = String))))
+)]
 pub async fn get_task(
     Extension(task): Extension<Task>,
     State(_deployment): State<DeploymentImpl>,
-) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
-    Ok(ResponseJson(ApiResponse::success(task)))
+) -> Result<(HeaderMap, ResponseJson<ApiResponse<Task>>), ApiError> {
+    Ok((etag_header(&task), ResponseJson(ApiResponse::success(task))))
+}
+
+/// Estimates are sizes, so they can't be negative.
+pub(crate) fn validate_estimate(estimate: Option<f64>) -> Result<(), ApiError> {
+    match estimate {
+        Some(estimate) if !estimate.is_finite() || estimate < 0.0 => Err(ApiError::BadRequest(
+            "Estimate must be a non-negative number".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// A cover color must be `#rrggbb` and a cover image one of the task's image attachments.
+pub(crate) async fn validate_cover(
+    deployment: &DeploymentImpl,
+    task: &Task,
+    payload: &UpdateTask,
+) -> Result<(), ApiError> {
+    if let Some(color) = &payload.cover_color
+        && !Label::is_valid_color(color)
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid cover color '{color}', expected #rrggbb"
+        )));
+    }
+    if let Some(attachment_id) = payload.cover_attachment_id {
+        let attachment = TaskAttachment::find_by_id(&deployment.db().pool, attachment_id)
+            .await?
+            .filter(|attachment| attachment.task_id == task.id)
+            .ok_or_else(|| {
+                ApiError::BadRequest(format!(
+                    "Attachment {attachment_id} does not belong to this task"
+                ))
+            })?;
+        if !attachment.mime_type.starts_with("image/") {
+            return Err(ApiError::BadRequest(format!(
+                "Attachment '{}' is not an image",
+                attachment.original_name
+            )));
+        }
+    }
+    Ok(())
 }
 
+/// POST /tasks
+/// Send an `Idempotency-Key` header to make retries safe: a repeated request gets the first
+/// reply instead of creating another task.
+#[utoipa::path(
+    post,
+    path = "/api/tasks",
+    tag = "tasks",
+    params(("Idempotency-Key" = Option<String>, Header)),
+    request_body = CreateTask,
+    responses((status = 200, body = ApiResponse<Task>))
+)]
 pub async fn create_task(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateTask>,
 ) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    require_role(
+        &deployment,
+        user.as_deref(),
+        caller.as_deref(),
+        payload.project_id,
+        ProjectRole::Member,
+    )
+    .await?;
     let id = Uuid::new_v4();
 
     tracing::debug!(
@@ -119,7 +390,13 @@ pub async fn create_task(
         payload.project_id
     );
 
+    validate_estimate(payload.estimate)?;
+    if let Some(assignee_id) = payload.assignee_id {
+        users::ensure_exists(&deployment, assignee_id).await?;
+    }
+
     let task = Task::create(&deployment.db().pool, &payload, id).await?;
+    TaskEvent::record_created(&deployment.db().pool, &task, TaskEventSource::User, None).await?;
 
     if let Some(image_ids) = &payload.image_ids {
         TaskImage::associate_many_dedup(&deployment.db().pool, task.id, image_ids).await?;
@@ -140,27 +417,53 @@ pub async fn create_task(
     Ok(ResponseJson(ApiResponse::success(task)))
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Deserialize, TS, ToSchema)]
 pub struct CreateAndStartTaskRequest {
     pub task: CreateTask,
+    #[schema(value_type = Object)]
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
 }
 
+/// POST /tasks/create-and-start
+/// Takes an `Idempotency-Key` header, like [`create_task`].
+#[utoipa::path(
+    post,
+    path = "/api/tasks/create-and-start",
+    tag = "tasks",
+    params(("Idempotency-Key" = Option<String>, Header)),
+    request_body = CreateAndStartTaskRequest,
+    responses((status = 200, body = ApiResponse<TaskWithAttemptStatus>))
+)]
 pub async fn create_task_and_start(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateAndStartTaskRequest>,
 ) -> Result<ResponseJson<ApiResponse<TaskWithAttemptStatus>>, ApiError> {
+    require_role(
+        &deployment,
+        user.as_deref(),
+        caller.as_deref(),
+        payload.task.project_id,
+        ProjectRole::Member,
+    )
+    .await?;
     if payload.repos.is_empty() {
         return Err(ApiError::BadRequest(
             "At least one repository is required".to_string(),
         ));
     }
+    validate_estimate(payload.task.estimate)?;
+    if let Some(assignee_id) = payload.task.assignee_id {
+        users::ensure_exists(&deployment, assignee_id).await?;
+    }
 
     let pool = &deployment.db().pool;
 
     let task_id = Uuid::new_v4();
     let task = Task::create(pool, &payload.task, task_id).await?;
+    TaskEvent::record_created(pool, &task, TaskEventSource::User, None).await?;
 
     if let Some(image_ids) = &payload.task.image_ids {
         TaskImage::associate_many_dedup(pool, task.id, image_ids).await?;
@@ -243,37 +546,66 @@ pub async fn create_task_and_start(
         has_in_progress_attempt: is_attempt_running,
         last_attempt_failed: false,
         executor: payload.executor_profile_id.executor.to_string(),
+        checklist_total: 0,
+        checklist_done: 0,
+        comment_count: 0,
+        attachment_count: 0,
+        is_blocked: false,
     })))
 }
 
+/// PUT /tasks/{task_id}
+/// `If-Match` names the `ETag` the update was made against; the update is refused with
+/// `412` if the task changed since, checked in the same transaction that writes it. The
+/// reply carries the updated task's `ETag`.
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}",
+    tag = "tasks",
+    params(("task_id" = Uuid, Path), ("If-Match" = String, Header)),
+    request_body = UpdateTask,
+    responses(
+        (status = 200, body = ApiResponse<Task>, headers(("ETag" = String))),
+        (status = 412, description = "The task changed since the If-Match ETag was read"),
+        (status = 428, description = "If-Match is required and was not sent")
+    )
+)]
 pub async fn update_task(
     Extension(existing_task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
-
+    headers: HeaderMap,
     Json(payload): Json<UpdateTask>,
-) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+) -> Result<(HeaderMap, ResponseJson<ApiResponse<Task>>), ApiError> {
+    let versions = if_match_versions(&headers)?;
     ensure_shared_task_auth(&existing_task, &deployment).await?;
+    validate_estimate(payload.estimate)?;
+    validate_cover(&deployment, &existing_task, &payload).await?;
 
-    // Use existing values if not provided in update
-    let title = payload.title.unwrap_or(existing_task.title);
-    let description = match payload.description {
-        Some(s) if s.trim().is_empty() => None, // Empty string = clear description
-        Some(s) => Some(s),                     // Non-empty string = update description
-        None => existing_task.description,      // Field omitted = keep existing
+    // Archived tasks are off the board, so they don't take up a column's WIP limit
+    let wip_warning = match &payload.status {
+        Some(status) if *status != existing_task.status && existing_task.archived_at.is_none() => {
+            wip_limits::check_move(&deployment, existing_task.project_id, status, 1).await?
+        }
+        _ => None,
     };
-    let status = payload.status.unwrap_or(existing_task.status);
-    let parent_workspace_id = payload
-        .parent_workspace_id
-        .or(existing_task.parent_workspace_id);
 
-    let task = Task::update(
+    let mut tx = deployment.db().pool.begin().await?;
+    if let Some(versions) = &versions
+        && !Task::claim_version(&mut *tx, existing_task.id, versions).await?
+    {
+        return Err(ApiError::PreconditionFailed(format!(
+            "Task {} changed since it was read; fetch it again",
+            existing_task.id
+        )));
+    }
+    let task = apply_update(&mut tx, &existing_task, &payload).await?;
+    tx.commit().await?;
+    TaskEvent::record_changes(
         &deployment.db().pool,
-        existing_task.id,
-        existing_task.project_id,
-        title,
-        description,
-        status,
-        parent_workspace_id,
+        &existing_task,
+        &task,
+        TaskEventSource::User,
+        None,
     )
     .await?;
 
@@ -290,10 +622,94 @@ pub async fn update_task(
         publisher.update_shared_task(&task).await?;
     }
 
-    Ok(ResponseJson(ApiResponse::success(task)))
+    let headers = etag_header(&task);
+    Ok((
+        headers,
+        ResponseJson(match wip_warning {
+            Some(warning) => ApiResponse::success_with_message(task, &warning),
+            None => ApiResponse::success(task),
+        }),
+    ))
 }
 
-async fn ensure_shared_task_auth(
+/// Write the fields of an update that were given over those of the existing task.
+pub(crate) async fn apply_update(
+    conn: &mut SqliteConnection,
+    existing_task: &Task,
+    payload: &UpdateTask,
+) -> Result<Task, SqlxError> {
+    // Use existing values if not provided in update
+    let title = payload
+        .title
+        .clone()
+        .unwrap_or_else(|| existing_task.title.clone());
+    let description = match &payload.description {
+        Some(s) if s.trim().is_empty() => None, // Empty string = clear description
+        Some(s) => Some(s.clone()),             // Non-empty string = update description
+        None => existing_task.description.clone(), // Field omitted = keep existing
+    };
+    let status = payload
+        .status
+        .clone()
+        .unwrap_or_else(|| existing_task.status.clone());
+    let parent_workspace_id = payload
+        .parent_workspace_id
+        .or(existing_task.parent_workspace_id);
+
+    let task = Task::update(
+        &mut *conn,
+        existing_task.id,
+        existing_task.project_id,
+        title,
+        description,
+        status,
+        parent_workspace_id,
+    )
+    .await?;
+
+    let task = if payload.clear_due_at == Some(true) {
+        Task::update_due_at(&mut *conn, task.id, None).await?
+    } else if payload.due_at.is_some() && payload.due_at != task.due_at {
+        Task::update_due_at(&mut *conn, task.id, payload.due_at).await?
+    } else {
+        task
+    };
+    let task = match payload.priority {
+        Some(priority) if priority != task.priority => {
+            Task::update_priority(&mut *conn, task.id, priority).await?
+        }
+        _ => task,
+    };
+    let task = if payload.clear_estimate == Some(true) {
+        Task::update_estimate(&mut *conn, task.id, None).await?
+    } else if payload.estimate.is_some() && payload.estimate != task.estimate {
+        Task::update_estimate(&mut *conn, task.id, payload.estimate).await?
+    } else {
+        task
+    };
+    if payload.clear_cover == Some(true) {
+        Task::update_cover(&mut *conn, task.id, None, None).await
+    } else if payload.cover_color.is_some() || payload.cover_attachment_id.is_some() {
+        let cover_color = payload
+            .cover_color
+            .as_deref()
+            .map(str::to_ascii_lowercase)
+            .or_else(|| task.cover_color.clone());
+        let cover_attachment_id = payload.cover_attachment_id.or(task.cover_attachment_id);
+        Task::update_cover(
+            &mut *conn,
+            task.id,
+            cover_color.as_deref(),
+            cover_attachment_id,
+        )
+        .await
+    } else {
+        Ok(task)
+    }
+}
+
+/// Changes to a shared task are published, which needs a signed-in user.
+pub async fn ensure_shared_task_auth(
     existing_task: &Task,
     deployment: &local_deployment::LocalDeployment,
 ) -> Result<(), ApiError> {
@@ -308,10 +724,20 @@ async fn ensure_shared_task_auth(
     Ok(())
 }
 
+/// DELETE /tasks/{task_id}
+/// Move the task to its project's trash. It can be restored from there until the
+/// retention period passes and it is purged along with its workspaces.
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}",
+    tag = "tasks",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
 pub async fn delete_task(
     Extension(task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
-) -> Result<(StatusCode, ResponseJson<ApiResponse<()>>), ApiError> {
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
     ensure_shared_task_auth(&task, &deployment).await?;
 
     // Validate no running execution processes
@@ -325,114 +751,145 @@ pub async fn delete_task(
 
     let pool = &deployment.db().pool;
 
-    // Gather task attempts data needed for background cleanup
-    let attempts = Workspace::fetch_all(pool, Some(task.id))
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch task attempts for task {}: {}", task.id, e);
-            ApiError::Workspace(e)
-        })?;
-
-    let repositories = WorkspaceRepo::find_unique_repos_for_task(pool, task.id).await?;
-
-    // Collect workspace directories that need cleanup
-    let workspace_dirs: Vec<PathBuf> = attempts
-        .iter()
-        .filter_map(|attempt| attempt.container_ref.as_ref().map(PathBuf::from))
-        .collect();
-
+    // Sharing stops when the task is deleted; a restored task comes back as a local task
     if let Some(shared_task_id) = task.shared_task_id {
         let Ok(publisher) = deployment.share_publisher() else {
             return Err(ShareError::MissingConfig("share publisher unavailable").into());
         };
         publisher.delete_shared_task(shared_task_id).await?;
+        Task::set_shared_task_id(pool, task.id, None).await?;
     }
 
-    // Use a transaction to ensure atomicity: either all operations succeed or all are rolled back
-    let mut tx = pool.begin().await?;
-
-    // Nullify parent_workspace_id for all child tasks before deletion
-    // This breaks parent-child relationships to avoid foreign key constraint violations
-    let mut total_children_affected = 0u64;
-    for attempt in &attempts {
-        let children_affected =
-            Task::nullify_children_by_workspace_id(&mut *tx, attempt.id).await?;
-        total_children_affected += children_affected;
-    }
+    let trashed = Task::trash(pool, task.id).await?;
+    TaskEvent::record_changes(pool, &task, &trashed, TaskEventSource::User, None).await?;
 
-    // Delete task from database (FK CASCADE will handle task_attempts)
-    let rows_affected = Task::delete(&mut *tx, task.id).await?;
-
-    if rows_affected == 0 {
-        return Err(ApiError::Database(SqlxError::RowNotFound));
-    }
+    deployment
+        .track_if_analytics_allowed(
+            "task_deleted",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
 
-    // Commit the transaction - if this fails, all changes are rolled back
-    tx.commit().await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
 
-    if total_children_affected > 0 {
-        tracing::info!(
-            "Nullified {} child task references before deleting task {}",
-            total_children_affected,
-            task.id
-        );
-    }
+/// POST /tasks/{task_id}/archive
+/// Take the task off the board without deleting it.
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/archive",
+    tag = "tasks",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Task>))
+)]
+pub async fn archive_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let archived = Task::archive(&deployment.db().pool, task.id).await?;
+    TaskEvent::record_changes(
+        &deployment.db().pool,
+        &task,
+        &archived,
+        TaskEventSource::User,
+        None,
+    )
+    .await?;
+    let task = archived;
 
     deployment
         .track_if_analytics_allowed(
-            "task_deleted",
+            "task_archived",
             serde_json::json!({
                 "task_id": task.id.to_string(),
                 "project_id": task.project_id.to_string(),
-                "attempt_count": attempts.len(),
+                "status": task.status.to_string(),
             }),
         )
         .await;
 
-    let task_id = task.id;
-    let pool = pool.clone();
-    tokio::spawn(async move {
-        tracing::info!(
-            "Starting background cleanup for task {} ({} workspaces, {} repos)",
-            task_id,
-            workspace_dirs.len(),
-            repositories.len()
-        );
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
 
-        for workspace_dir in &workspace_dirs {
-            if let Err(e) = WorkspaceManager::cleanup_workspace(workspace_dir, &repositories).await
-            {
-                tracing::error!(
-                    "Background workspace cleanup failed for task {} at {}: {}",
-                    task_id,
-                    workspace_dir.display(),
-                    e
-                );
-            }
-        }
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/unarchive",
+    tag = "tasks",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Task>))
+)]
+pub async fn unarchive_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let unarchived = Task::unarchive(&deployment.db().pool, task.id).await?;
+    TaskEvent::record_changes(
+        &deployment.db().pool,
+        &task,
+        &unarchived,
+        TaskEventSource::User,
+        None,
+    )
+    .await?;
+    let task = unarchived;
 
-        match Repo::delete_orphaned(&pool).await {
-            Ok(count) if count > 0 => {
-                tracing::info!("Deleted {} orphaned repo records", count);
-            }
-            Err(e) => {
-                tracing::error!("Failed to delete orphaned repos: {}", e);
-            }
-            _ => {}
-        }
+    deployment
+        .track_if_analytics_allowed(
+            "task_unarchived",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
 
-        tracing::info!("Background cleanup completed for task {}", task_id);
-    });
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
 
-    // Return 202 Accepted to indicate deletion was scheduled
-    Ok((StatusCode::ACCEPTED, ResponseJson(ApiResponse::success(()))))
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ArchivedTaskQuery {
+    pub project_id: Uuid,
+    #[serde(default)]
+    pub search: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+/// GET /tasks/archived?project_id=...&search=...
+#[utoipa::path(
+    get,
+    path = "/api/tasks/archived",
+    tag = "tasks",
+    params(ArchivedTaskQuery),
+    responses((status = 200, body = ApiResponse<Vec<Task>>))
+)]
+pub async fn get_archived_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ArchivedTaskQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let tasks = Task::find_archived(
+        &deployment.db().pool,
+        query.project_id,
+        query.search.as_deref(),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(tasks)))
+}
+
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct ShareTaskResponse {
     pub shared_task_id: Uuid,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/share",
+    tag = "tasks",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<ShareTaskResponse>))
+)]
 pub async fn share_task(
     Extension(task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
@@ -464,19 +921,95 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_actions_router = Router::new()
         .route("/", put(update_task))
         .route("/", delete(delete_task))
-        .route("/share", post(share_task));
+        .route("/share", post(share_task))
+        .route("/archive", post(archive_task))
+        .route("/unarchive", post(unarchive_task));
 
     let task_id_router = Router::new()
         .route("/", get(get_task))
         .merge(task_actions_router)
+        .merge(task_comments::task_router())
+        .merge(task_events::task_router())
+        .merge(task_revisions::task_router())
+        .merge(time_entries::task_router())
+        .merge(task_checklist::task_router())
+        .merge(task_links::task_router())
+        .merge(task_attachments::task_router())
+        .merge(labels::task_router())
+        .merge(custom_fields::task_router())
+        .merge(recurrence::task_router())
+        .merge(users::task_router())
+        .merge(project_columns::task_router())
+        .merge(watchers::task_router())
+        .merge(epics::task_router())
+        .merge(task_clone::task_router())
+        .merge(task_move::task_router())
+        .merge(task_merge::task_router())
         .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
 
     let inner = Router::new()
-        .route("/", get(get_tasks).post(create_task))
+        .route(
+            "/",
+            get(get_tasks)
+                .post(create_task)
+                .layer(from_fn_with_state(deployment.clone(), idempotency)),
+        )
         .route("/stream/ws", get(stream_tasks_ws))
-        .route("/create-and-start", post(create_task_and_start))
+        .route("/archived", get(get_archived_tasks))
+        .route(
+            "/create-and-start",
+            post(create_task_and_start).layer(from_fn_with_state(deployment.clone(), idempotency)),
+        )
+        .merge(task_comments::router())
+        .merge(task_checklist::router())
+        .merge(task_links::router())
+        .merge(task_revisions::router())
+        .merge(task_attachments::router())
+        .merge(custom_fields::task_value_router())
+        .merge(task_templates::from_template_router())
+        .merge(task_bulk::router())
+        .merge(task_quick_add::router())
+        .merge(task_batch::router())
+        .merge(watchers::router())
         .nest("/{task_id}", task_id_router);
 
     // mount under /projects/:project_id/tasks
     Router::new().nest("/tasks", inner)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn if_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_MATCH, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_if_match_versions() {
+        assert_eq!(
+            if_match_versions(&if_match("\"3\"")).unwrap(),
+            Some(vec![3])
+        );
+        assert_eq!(
+            if_match_versions(&if_match("\"3\", \"4\"")).unwrap(),
+            Some(vec![3, 4])
+        );
+        assert_eq!(if_match_versions(&if_match("*")).unwrap(), None);
+        // An ETag from before tasks had versions matches none
+        assert_eq!(
+            if_match_versions(&if_match("\"9f86d081884c7d659a2feaa0\"")).unwrap(),
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn test_if_match_is_required() {
+        assert!(matches!(
+            if_match_versions(&HeaderMap::new()),
+            Err(ApiError::PreconditionRequired(_))
+        ));
+    }
+}
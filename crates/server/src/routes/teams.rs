@@ -0,0 +1,433 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    api_key::ApiKey,
+    project::{Project, ProjectError},
+    project_member::ProjectRole,
+    team::{CreateTeam, SetTeamMember, Team, TeamMember, TeamRole},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{
+        auth::AuthUser,
+        rbac::{require_role, require_team_role},
+    },
+    routes::users,
+};
+
+async fn find_team(deployment: &DeploymentImpl, team_id: Uuid) -> Result<Team, ApiError> {
+    Team::find_by_id(&deployment.db().pool, team_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+/// Moving projects between teams changes what keys limited to a team can reach, so only
+/// unrestricted keys may do it.
+fn ensure_unrestricted_key(caller: Option<&ApiKey>) -> Result<(), ApiError> {
+    if caller.is_some_and(|key| key.project_id.is_some() || key.team_id.is_some()) {
+        return Err(ApiError::Forbidden(
+            "Keys limited to a project or team can't move projects between teams".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// GET /teams
+/// The signed-in user's teams, or every team for requests without a user.
+#[utoipa::path(
+    get,
+    path = "/api/teams",
+    tag = "teams",
+    responses((status = 200, body = ApiResponse<Vec<Team>>))
+)]
+pub async fn get_teams(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Team>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let mut teams = match &user {
+        Some(Extension(AuthUser(user))) => Team::find_by_user_id(pool, user.id).await?,
+        None => Team::find_all(pool).await?,
+    };
+    // A key limited to a team only sees that team
+    if let Some(Extension(caller)) = &caller
+        && (caller.project_id.is_some() || caller.team_id.is_some())
+    {
+        teams.retain(|team| caller.project_id.is_none() && caller.team_id == Some(team.id));
+    }
+    Ok(ResponseJson(ApiResponse::success(teams)))
+}
+
+/// POST /teams
+/// A signed-in creator becomes the team's admin.
+#[utoipa::path(
+    post,
+    path = "/api/teams",
+    tag = "teams",
+    request_body = CreateTeam,
+    responses((status = 200, body = ApiResponse<Team>))
+)]
+pub async fn create_team(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTeam>,
+) -> Result<ResponseJson<ApiResponse<Team>>, ApiError> {
+    if payload.name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Team name must not be empty".to_string(),
+        ));
+    }
+    if caller
+        .as_deref()
+        .is_some_and(|key| key.project_id.is_some() || key.team_id.is_some())
+    {
+        return Err(ApiError::Forbidden(
+            "Keys limited to a project or team can't create teams".to_string(),
+        ));
+    }
+
+    let mut tx = deployment.db().pool.begin().await?;
+    let team = Team::create(&mut *tx, &payload).await?;
+    if let Some(Extension(AuthUser(user))) = &user {
+        TeamMember::set(&mut *tx, team.id, user.id, TeamRole::Admin).await?;
+    }
+    tx.commit().await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "team_created",
+            serde_json::json!({ "team_id": team.id.to_string() }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(team)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/teams/{team_id}",
+    tag = "teams",
+    params(("team_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Team>))
+)]
+pub async fn get_team(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Path(team_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Team>>, ApiError> {
+    let team = find_team(&deployment, team_id).await?;
+    require_team_role(
+        &deployment,
+        user.as_deref(),
+        caller.as_deref(),
+        team_id,
+        TeamRole::Member,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(team)))
+}
+
+/// DELETE /teams/{team_id}
+/// Only teams without projects can be deleted; move the projects out first.
+#[utoipa::path(
+    delete,
+    path = "/api/teams/{team_id}",
+    tag = "teams",
+    params(("team_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_team(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Path(team_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    find_team(&deployment, team_id).await?;
+    require_team_role(
+        &deployment,
+        user.as_deref(),
+        caller.as_deref(),
+        team_id,
+        TeamRole::Admin,
+    )
+    .await?;
+    let pool = &deployment.db().pool;
+    if !Team::project_ids(pool, team_id).await?.is_empty() {
+        return Err(ApiError::Conflict(
+            "Move the team's projects out before deleting it".to_string(),
+        ));
+    }
+    Team::delete(pool, team_id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "team_deleted",
+            serde_json::json!({ "team_id": team_id.to_string() }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/teams/{team_id}/members",
+    tag = "teams",
+    params(("team_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<TeamMember>>))
+)]
+pub async fn get_team_members(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Path(team_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<TeamMember>>>, ApiError> {
+    find_team(&deployment, team_id).await?;
+    require_team_role(
+        &deployment,
+        user.as_deref(),
+        caller.as_deref(),
+        team_id,
+        TeamRole::Member,
+    )
+    .await?;
+    let members = TeamMember::find_by_team_id(&deployment.db().pool, team_id).await?;
+    Ok(ResponseJson(ApiResponse::success(members)))
+}
+
+/// Refuse changes that would leave a team with members but no admin to manage them.
+async fn ensure_admin_remains(
+    deployment: &DeploymentImpl,
+    team_id: Uuid,
+    user_id: Uuid,
+    new_role: Option<TeamRole>,
+) -> Result<(), ApiError> {
+    let members = TeamMember::find_by_team_id(&deployment.db().pool, team_id).await?;
+    let others = members.iter().filter(|member| member.user_id != user_id);
+    let admin_remains = new_role == Some(TeamRole::Admin)
+        || others.clone().any(|member| member.role == TeamRole::Admin);
+    let has_members = new_role.is_some() || others.count() > 0;
+    if has_members && !admin_remains {
+        return Err(ApiError::Conflict(
+            "A team with members needs at least one admin".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// PUT /teams/{team_id}/members/{user_id}
+/// Add a user to the team or change their role.
+#[utoipa::path(
+    put,
+    path = "/api/teams/{team_id}/members/{user_id}",
+    tag = "teams",
+    params(("team_id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    request_body = SetTeamMember,
+    responses((status = 200, body = ApiResponse<TeamMember>))
+)]
+pub async fn set_team_member(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Path((team_id, user_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<SetTeamMember>,
+) -> Result<ResponseJson<ApiResponse<TeamMember>>, ApiError> {
+    find_team(&deployment, team_id).await?;
+    require_team_role(
+        &deployment,
+        user.as_deref(),
+        caller.as_deref(),
+        team_id,
+        TeamRole::Admin,
+    )
+    .await?;
+    users::ensure_exists(&deployment, user_id).await?;
+    ensure_admin_remains(&deployment, team_id, user_id, Some(payload.role)).await?;
+
+    let pool = &deployment.db().pool;
+    TeamMember::set(pool, team_id, user_id, payload.role).await?;
+    let member = TeamMember::find(pool, team_id, user_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "team_member_set",
+            serde_json::json!({
+                "team_id": team_id.to_string(),
+                "role": payload.role,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(member)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/teams/{team_id}/members/{user_id}",
+    tag = "teams",
+    params(("team_id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn remove_team_member(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Path((team_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    require_team_role(
+        &deployment,
+        user.as_deref(),
+        caller.as_deref(),
+        team_id,
+        TeamRole::Admin,
+    )
+    .await?;
+    ensure_admin_remains(&deployment, team_id, user_id, None).await?;
+    let removed = TeamMember::remove(&deployment.db().pool, team_id, user_id).await?;
+    if removed == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "team_member_removed",
+            serde_json::json!({ "team_id": team_id.to_string() }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// PUT /teams/{team_id}/projects/{project_id}
+/// Move a project into the team. Only the team's members can reach it from then on. Needs
+/// admin rights on both the team and the project.
+#[utoipa::path(
+    put,
+    path = "/api/teams/{team_id}/projects/{project_id}",
+    tag = "teams",
+    params(("team_id" = Uuid, Path), ("project_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Project>))
+)]
+pub async fn add_team_project(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Path((team_id, project_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    ensure_unrestricted_key(caller.as_deref())?;
+    find_team(&deployment, team_id).await?;
+    let pool = &deployment.db().pool;
+    Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+    require_team_role(
+        &deployment,
+        user.as_deref(),
+        caller.as_deref(),
+        team_id,
+        TeamRole::Admin,
+    )
+    .await?;
+    require_role(
+        &deployment,
+        user.as_deref(),
+        caller.as_deref(),
+        project_id,
+        ProjectRole::Admin,
+    )
+    .await?;
+
+    Team::set_project_team(pool, project_id, Some(team_id)).await?;
+    let project = Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "team_project_added",
+            serde_json::json!({
+                "team_id": team_id.to_string(),
+                "project_id": project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+/// DELETE /teams/{team_id}/projects/{project_id}
+/// Take a project out of the team, opening it to everyone its project roles allow.
+#[utoipa::path(
+    delete,
+    path = "/api/teams/{team_id}/projects/{project_id}",
+    tag = "teams",
+    params(("team_id" = Uuid, Path), ("project_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Project>))
+)]
+pub async fn remove_team_project(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Path((team_id, project_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    ensure_unrestricted_key(caller.as_deref())?;
+    require_team_role(
+        &deployment,
+        user.as_deref(),
+        caller.as_deref(),
+        team_id,
+        TeamRole::Admin,
+    )
+    .await?;
+    let pool = &deployment.db().pool;
+    let project = Project::find_by_id(pool, project_id)
+        .await?
+        .filter(|project| project.team_id == Some(team_id))
+        .ok_or(ProjectError::ProjectNotFound)?;
+
+    Team::set_project_team(pool, project.id, None).await?;
+    let project = Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "team_project_removed",
+            serde_json::json!({
+                "team_id": team_id.to_string(),
+                "project_id": project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/teams", get(get_teams).post(create_team))
+        .route("/teams/{team_id}", get(get_team).delete(delete_team))
+        .route("/teams/{team_id}/members", get(get_team_members))
+        .route(
+            "/teams/{team_id}/members/{user_id}",
+            put(set_team_member).delete(remove_team_member),
+        )
+        .route(
+            "/teams/{team_id}/projects/{project_id}",
+            put(add_team_project).delete(remove_team_project),
+        )
+}
@@ -0,0 +1,174 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    task::Task,
+    time_entry::{self, TimeEntry, TimeEntryDetail, TimeEntryFilter, TimeGroupBy, TimeTotal},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::users};
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct TimerRequest {
+    pub user_id: Uuid,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TimeReportQuery {
+    #[serde(default)]
+    pub group_by: TimeGroupBy,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/time-entries",
+    tag = "time_entries",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<TimeEntry>>))
+)]
+pub async fn get_task_time_entries(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TimeEntry>>>, ApiError> {
+    let entries = TimeEntry::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(entries)))
+}
+
+/// POST /tasks/{task_id}/timer/start
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/timer/start",
+    tag = "time_entries",
+    params(("task_id" = Uuid, Path)),
+    request_body = TimerRequest,
+    responses((status = 200, body = ApiResponse<TimeEntry>))
+)]
+pub async fn start_timer(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<TimerRequest>,
+) -> Result<ResponseJson<ApiResponse<TimeEntry>>, ApiError> {
+    let pool = &deployment.db().pool;
+    users::ensure_exists(&deployment, payload.user_id).await?;
+    if TimeEntry::find_running(pool, task.id, payload.user_id)
+        .await?
+        .is_some()
+    {
+        return Err(ApiError::Conflict(
+            "A timer is already running for this user on this task".to_string(),
+        ));
+    }
+
+    let entry = TimeEntry::start(pool, task.id, payload.user_id, payload.note.as_deref()).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_timer_started",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(entry)))
+}
+
+/// POST /tasks/{task_id}/timer/stop
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/timer/stop",
+    tag = "time_entries",
+    params(("task_id" = Uuid, Path)),
+    request_body = TimerRequest,
+    responses((status = 200, body = ApiResponse<TimeEntry>))
+)]
+pub async fn stop_timer(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<TimerRequest>,
+) -> Result<ResponseJson<ApiResponse<TimeEntry>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let Some(running) = TimeEntry::find_running(pool, task.id, payload.user_id).await? else {
+        return Err(ApiError::BadRequest(
+            "No timer is running for this user on this task".to_string(),
+        ));
+    };
+
+    let entry = TimeEntry::stop(pool, running.id, payload.note.as_deref()).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_timer_stopped",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(entry)))
+}
+
+/// GET /time-entries?project_id=...&task_id=...&user_id=...&from=...&to=...
+/// Entries with their task, project, user and linked issue, for export to trackers.
+#[utoipa::path(
+    get,
+    path = "/api/time-entries",
+    tag = "time_entries",
+    params(TimeEntryFilter),
+    responses((status = 200, body = ApiResponse<Vec<TimeEntryDetail>>))
+)]
+pub async fn get_time_entries(
+    State(deployment): State<DeploymentImpl>,
+    Query(filter): Query<TimeEntryFilter>,
+) -> Result<ResponseJson<ApiResponse<Vec<TimeEntryDetail>>>, ApiError> {
+    let entries = TimeEntry::find_details(&deployment.db().pool, &filter).await?;
+    Ok(ResponseJson(ApiResponse::success(entries)))
+}
+
+/// GET /time-entries/report?group_by=task|project|user&...
+/// Time spent per task, project or user, taking the same filters as the entry list.
+#[utoipa::path(
+    get,
+    path = "/api/time-entries/report",
+    tag = "time_entries",
+    params(TimeEntryFilter, TimeReportQuery),
+    responses((status = 200, body = ApiResponse<Vec<TimeTotal>>))
+)]
+pub async fn get_time_report(
+    State(deployment): State<DeploymentImpl>,
+    Query(filter): Query<TimeEntryFilter>,
+    Query(query): Query<TimeReportQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<TimeTotal>>>, ApiError> {
+    let entries = TimeEntry::find_details(&deployment.db().pool, &filter).await?;
+    Ok(ResponseJson(ApiResponse::success(time_entry::summarize(
+        &entries,
+        query.group_by,
+    ))))
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/time-entries", get(get_task_time_entries))
+        .route("/timer/start", post(start_timer))
+        .route("/timer/stop", post(stop_timer))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/time-entries", get(get_time_entries))
+        .route("/time-entries/report", get(get_time_report))
+}
@@ -0,0 +1,122 @@
+use axum::{
+    Extension, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{delete, get, post},
+};
+use db::models::{
+    project::Project,
+    task::Task,
+    task_event::{TaskEvent, TaskEventSource},
+};
+use deployment::Deployment;
+use services::services::trash;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Load a trashed task, checking that it belongs to the project in the path.
+async fn load_trashed_task(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    task_id: Uuid,
+) -> Result<Task, ApiError> {
+    Task::find_by_id(&deployment.db().pool, task_id)
+        .await?
+        .filter(|task| task.project_id == project_id && task.deleted_at.is_some())
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/trash",
+    tag = "trash",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<Task>>))
+)]
+pub async fn get_trash(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let tasks = Task::find_trashed(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(tasks)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/projects/{project_id}/trash/{task_id}/restore",
+    tag = "trash",
+    params(("project_id" = Uuid, Path), ("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Task>))
+)]
+pub async fn restore_task(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, task_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let task = load_trashed_task(&deployment, project_id, task_id).await?;
+    let restored = Task::restore(&deployment.db().pool, task.id).await?;
+    TaskEvent::record_changes(
+        &deployment.db().pool,
+        &task,
+        &restored,
+        TaskEventSource::User,
+        None,
+    )
+    .await?;
+    let task = restored;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_restored",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
+/// DELETE /projects/{project_id}/trash/{task_id}
+/// Purge a trashed task now instead of waiting for the retention period.
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/trash/{task_id}",
+    tag = "trash",
+    params(("project_id" = Uuid, Path), ("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn purge_task(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, task_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let task = load_trashed_task(&deployment, project_id, task_id).await?;
+    trash::purge_task(&deployment.db().pool, &task).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_purged",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Routes nested under `/projects/{id}`, behind the project loading middleware.
+pub fn project_router() -> Router<DeploymentImpl> {
+    Router::new().route("/trash", get(get_trash))
+}
+
+/// Routes nested under `/projects`. Trashed tasks are hidden from the task loader, so
+/// these load the task themselves.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/{project_id}/trash/{task_id}", delete(purge_task))
+        .route("/{project_id}/trash/{task_id}/restore", post(restore_task))
+}
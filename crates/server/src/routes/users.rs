@@ -0,0 +1,276 @@
+use axum::{
+    Extension, Json, Router,
+    extract::State,
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    api_key::ApiKey,
+    task::Task,
+    task_event::{TaskEvent, TaskEventSource},
+    user::{CreateUser, UpdateUser, User},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{
+        auth::AuthUser,
+        load_user_middleware,
+        rbac::{is_administrator, require_self_or_administrator},
+    },
+    routes::{mentions, notifications},
+};
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct AssignTask {
+    pub user_id: Uuid,
+}
+
+fn validate_name(name: Option<&str>) -> Result<(), ApiError> {
+    if name.is_some_and(|name| name.trim().is_empty()) {
+        return Err(ApiError::BadRequest(
+            "User name must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Reject an email already used by another user.
+async fn ensure_unique_email(
+    deployment: &DeploymentImpl,
+    email: &str,
+    except: Option<Uuid>,
+) -> Result<(), ApiError> {
+    if let Some(existing) = User::find_by_email(&deployment.db().pool, email).await?
+        && Some(existing.id) != except
+    {
+        return Err(ApiError::Conflict(format!(
+            "A user with email '{email}' already exists"
+        )));
+    }
+    Ok(())
+}
+
+/// Reject assignment to a user that does not exist.
+pub async fn ensure_exists(deployment: &DeploymentImpl, user_id: Uuid) -> Result<(), ApiError> {
+    if User::find_by_id(&deployment.db().pool, user_id)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::BadRequest(format!(
+            "User {user_id} does not exist"
+        )));
+    }
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users",
+    tag = "users",
+    responses((status = 200, body = ApiResponse<Vec<User>>))
+)]
+pub async fn get_users(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<User>>>, ApiError> {
+    let users = User::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(users)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users",
+    tag = "users",
+    request_body = CreateUser,
+    responses((status = 200, body = ApiResponse<User>))
+)]
+pub async fn create_user(
+    auth_user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateUser>,
+) -> Result<ResponseJson<ApiResponse<User>>, ApiError> {
+    if !is_administrator(auth_user.as_deref(), caller.as_deref()) {
+        return Err(ApiError::Forbidden(
+            "Only an administrator can create users".to_string(),
+        ));
+    }
+    validate_name(Some(&payload.name))?;
+    if let Some(email) = &payload.email {
+        ensure_unique_email(&deployment, email, None).await?;
+    }
+
+    let user = User::create(&deployment.db().pool, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "user_created",
+            serde_json::json!({
+                "user_id": user.id.to_string(),
+                "has_email": user.email.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(user)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/users/{user_id}",
+    tag = "users",
+    params(("user_id" = Uuid, Path)),
+    request_body = UpdateUser,
+    responses((status = 200, body = ApiResponse<User>))
+)]
+pub async fn update_user(
+    Extension(user): Extension<User>,
+    auth_user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateUser>,
+) -> Result<ResponseJson<ApiResponse<User>>, ApiError> {
+    require_self_or_administrator(auth_user.as_deref(), caller.as_deref(), user.id)?;
+    validate_name(payload.name.as_deref())?;
+    if let Some(email) = payload.email.as_deref().filter(|e| !e.trim().is_empty()) {
+        ensure_unique_email(&deployment, email, Some(user.id)).await?;
+    }
+
+    let user = User::update(&deployment.db().pool, user.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(user)))
+}
+
+/// DELETE /users/{user_id}
+/// Tasks assigned to the user become unassigned.
+#[utoipa::path(
+    delete,
+    path = "/api/users/{user_id}",
+    tag = "users",
+    params(("user_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_user(
+    Extension(user): Extension<User>,
+    auth_user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    require_self_or_administrator(auth_user.as_deref(), caller.as_deref(), user.id)?;
+    let rows_affected = User::delete(&deployment.db().pool, user.id).await?;
+    if rows_affected == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "user_deleted",
+            serde_json::json!({
+                "user_id": user.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// PUT /tasks/{task_id}/assignee
+#[utoipa::path(
+    put,
+    path = "/api/tasks/{task_id}/assignee",
+    tag = "users",
+    params(("task_id" = Uuid, Path)),
+    request_body = AssignTask,
+    responses((status = 200, body = ApiResponse<Task>))
+)]
+pub async fn assign_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<AssignTask>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    ensure_exists(&deployment, payload.user_id).await?;
+    let assigned =
+        Task::update_assignee(&deployment.db().pool, task.id, Some(payload.user_id)).await?;
+    TaskEvent::record_changes(
+        &deployment.db().pool,
+        &task,
+        &assigned,
+        TaskEventSource::User,
+        None,
+    )
+    .await?;
+    let task = assigned;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_assigned",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "user_id": payload.user_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
+/// DELETE /tasks/{task_id}/assignee
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/assignee",
+    tag = "users",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Task>))
+)]
+pub async fn unassign_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let unassigned = Task::update_assignee(&deployment.db().pool, task.id, None).await?;
+    TaskEvent::record_changes(
+        &deployment.db().pool,
+        &task,
+        &unassigned,
+        TaskEventSource::User,
+        None,
+    )
+    .await?;
+    let task = unassigned;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_unassigned",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new().route("/assignee", put(assign_task).delete(unassign_task))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let user_router = Router::new()
+        .route("/", put(update_user).delete(delete_user))
+        .merge(notifications::user_router())
+        .merge(mentions::user_router())
+        .layer(from_fn_with_state(deployment.clone(), load_user_middleware));
+
+    let inner = Router::new()
+        .route("/", get(get_users).post(create_user))
+        .nest("/{user_id}", user_router);
+
+    Router::new().nest("/users", inner)
+}
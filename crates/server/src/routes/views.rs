@@ -0,0 +1,195 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    project::Project,
+    saved_view::{CreateSavedView, SavedView, UpdateSavedView},
+    task::TaskFilter,
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::custom_fields};
+
+fn validate_name(name: &str) -> Result<(), ApiError> {
+    if name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "View name must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Reject a name already used by another view in the project.
+async fn ensure_unique_name(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    name: &str,
+    except: Option<Uuid>,
+) -> Result<(), ApiError> {
+    if let Some(existing) = SavedView::find_by_name(&deployment.db().pool, project_id, name).await?
+        && Some(existing.id) != except
+    {
+        return Err(ApiError::Conflict(format!(
+            "A view named '{name}' already exists in this project"
+        )));
+    }
+    Ok(())
+}
+
+/// Load a view, checking that it belongs to the project.
+pub async fn load_view(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    view_id: Uuid,
+) -> Result<SavedView, ApiError> {
+    SavedView::find_by_id(&deployment.db().pool, view_id)
+        .await?
+        .filter(|view| view.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+/// Store a filter the way the task list expects it: a blank text query is dropped and a
+/// custom field value is normalized for its field.
+async fn normalize_filter(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    mut filter: TaskFilter,
+) -> Result<TaskFilter, ApiError> {
+    filter.query = filter.query.filter(|query| !query.trim().is_empty());
+    filter.custom_field_value = match (filter.custom_field_id, &filter.custom_field_value) {
+        (Some(field_id), Some(value)) => Some(
+            custom_fields::normalize_filter_value(deployment, project_id, field_id, value).await?,
+        ),
+        (None, Some(_)) => {
+            return Err(ApiError::BadRequest(
+                "custom_field_value requires custom_field_id".to_string(),
+            ));
+        }
+        _ => None,
+    };
+    Ok(filter)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/views",
+    tag = "views",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<SavedView>>))
+)]
+pub async fn get_views(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<SavedView>>>, ApiError> {
+    let views = SavedView::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(views)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/projects/{id}/views",
+    tag = "views",
+    params(("id" = Uuid, Path)),
+    request_body = CreateSavedView,
+    responses((status = 200, body = ApiResponse<SavedView>))
+)]
+pub async fn create_view(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateSavedView>,
+) -> Result<ResponseJson<ApiResponse<SavedView>>, ApiError> {
+    let name = payload.name.trim();
+    validate_name(name)?;
+    ensure_unique_name(&deployment, project.id, name, None).await?;
+    let filter = normalize_filter(&deployment, project.id, payload.filter).await?;
+    let view = SavedView::create(&deployment.db().pool, project.id, name, &filter).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "view_created",
+            serde_json::json!({
+                "view_id": view.id.to_string(),
+                "project_id": project.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(view)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/projects/{project_id}/views/{view_id}",
+    tag = "views",
+    params(("project_id" = Uuid, Path), ("view_id" = Uuid, Path)),
+    request_body = UpdateSavedView,
+    responses((status = 200, body = ApiResponse<SavedView>))
+)]
+pub async fn update_view(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, view_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateSavedView>,
+) -> Result<ResponseJson<ApiResponse<SavedView>>, ApiError> {
+    let view = load_view(&deployment, project_id, view_id).await?;
+    let name = match &payload.name {
+        Some(name) => {
+            let name = name.trim();
+            validate_name(name)?;
+            ensure_unique_name(&deployment, project_id, name, Some(view.id)).await?;
+            name
+        }
+        None => view.name.as_str(),
+    };
+    let filter = match payload.filter {
+        Some(filter) => normalize_filter(&deployment, project_id, filter).await?,
+        None => view.filter.0.clone(),
+    };
+    let view = SavedView::update(&deployment.db().pool, view.id, name, &filter).await?;
+    Ok(ResponseJson(ApiResponse::success(view)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{project_id}/views/{view_id}",
+    tag = "views",
+    params(("project_id" = Uuid, Path), ("view_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_view(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, view_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let view = load_view(&deployment, project_id, view_id).await?;
+    SavedView::delete(&deployment.db().pool, view.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "view_deleted",
+            serde_json::json!({
+                "view_id": view.id.to_string(),
+                "project_id": project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Routes nested under `/projects/{id}`, behind the project loading middleware.
+pub fn project_router() -> Router<DeploymentImpl> {
+    Router::new().route("/views", get(get_views).post(create_view))
+}
+
+/// Routes nested under `/projects`. The project loader only understands a single path
+/// parameter, so these load the view themselves.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/{project_id}/views/{view_id}",
+        put(update_view).delete(delete_view),
+    )
+}
@@ -0,0 +1,104 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{delete, get},
+};
+use db::models::{
+    task::Task,
+    task_watcher::{TaskWatcher, WatchReason, WatchTask},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::users};
+
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{task_id}/watchers",
+    tag = "watchers",
+    params(("task_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<TaskWatcher>>))
+)]
+pub async fn get_task_watchers(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskWatcher>>>, ApiError> {
+    let watchers = TaskWatcher::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(watchers)))
+}
+
+/// POST /tasks/{task_id}/watchers
+/// Subscribe a user to the task's status changes and comments.
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{task_id}/watchers",
+    tag = "watchers",
+    params(("task_id" = Uuid, Path)),
+    request_body = WatchTask,
+    responses((status = 200, body = ApiResponse<Vec<TaskWatcher>>))
+)]
+pub async fn watch_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<WatchTask>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskWatcher>>>, ApiError> {
+    users::ensure_exists(&deployment, payload.user_id).await?;
+    let pool = &deployment.db().pool;
+    TaskWatcher::watch(pool, task.id, payload.user_id, WatchReason::Manual).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_watched",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "user_id": payload.user_id.to_string(),
+            }),
+        )
+        .await;
+
+    let watchers = TaskWatcher::find_by_task_id(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(watchers)))
+}
+
+/// DELETE /tasks/{task_id}/watchers/{user_id}
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{task_id}/watchers/{user_id}",
+    tag = "watchers",
+    params(("task_id" = Uuid, Path), ("user_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn unwatch_task(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = TaskWatcher::unwatch(&deployment.db().pool, task_id, user_id).await?;
+    if rows_affected == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_unwatched",
+            serde_json::json!({
+                "task_id": task_id.to_string(),
+                "user_id": user_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Routes nested under `/tasks/{task_id}`, behind the task loading middleware.
+pub fn task_router() -> Router<DeploymentImpl> {
+    Router::new().route("/watchers", get(get_task_watchers).post(watch_task))
+}
+
+/// Routes nested under `/tasks`. The task loader only understands a single path
+/// parameter, so these take the task id from the path.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/{task_id}/watchers/{user_id}", delete(unwatch_task))
+}
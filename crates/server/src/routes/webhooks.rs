@@ -0,0 +1,353 @@
+use axum::{
+    Extension, Json, Router,
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    api_key::ApiKey,
+    integration::{Integration, IntegrationError, IntegrationProvider},
+    project::{Project, ProjectError},
+    project_member::ProjectRole,
+    webhook::{CreateWebhook, Webhook, WebhookDelivery},
+};
+use deployment::Deployment;
+use rand::{Rng, distributions::Alphanumeric};
+use serde::{Deserialize, Serialize};
+use services::services::{
+    events::ProjectEventKind,
+    integrations::{IntegrationServiceError, WebhookOutcome},
+    webhook_dispatcher::WebhookDispatcher,
+};
+use ts_rs::TS;
+use url::Url;
+use utils::response::ApiResponse;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{
+        auth::AuthUser,
+        rbac::{require_role, visible_projects},
+    },
+    routes,
+};
+
+/// POST /webhooks/{provider}/{integration_id}
+/// Receives issue events pushed by a provider. The body is verified against the
+/// integration's `webhook_secret` before anything is parsed.
+#[utoipa::path(
+    post,
+    path = "/api/webhooks/{provider}/{integration_id}",
+    tag = "webhooks",
+    params(("provider" = IntegrationProvider, Path), ("integration_id" = Uuid, Path)),
+    request_body(content = Object, description = "Provider webhook payload", content_type = "application/json"),
+    responses((status = 200, body = ApiResponse<WebhookOutcome>))
+)]
+pub async fn receive_webhook(
+    State(deployment): State<DeploymentImpl>,
+    Path((provider, integration_id)): Path<(IntegrationProvider, Uuid)>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<ResponseJson<ApiResponse<WebhookOutcome>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let integration = Integration::find_by_id(pool, integration_id)
+        .await?
+        .filter(|integration| integration.provider == provider)
+        .ok_or(IntegrationError::NotFound)?;
+
+    let result = deployment
+        .integrations()
+        .handle_webhook(pool, &integration, &headers, &body)
+        .await;
+    metrics::counter!(
+        "integration_webhooks_total",
+        "provider" => provider.to_string(),
+        "outcome" => match &result {
+            Ok(outcome) => outcome.as_str(),
+            Err(IntegrationServiceError::InvalidSignature) => "rejected",
+            Err(_) => "error",
+        }
+    )
+    .increment(1);
+    let outcome = result?;
+
+    tracing::info!(
+        integration_id = %integration.id,
+        provider = %provider,
+        ?outcome,
+        "processed integration webhook"
+    );
+
+    Ok(ResponseJson(ApiResponse::success(outcome)))
+}
+
+#[derive(Debug, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WebhookQuery {
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct CreatedWebhook {
+    pub webhook: Webhook,
+    /// Deliveries carry `X-VK-Signature: sha256=<HMAC-SHA256 of the body>` keyed with this.
+    /// It cannot be shown again.
+    pub secret: String,
+}
+
+#[derive(Debug, Deserialize, TS, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WebhookDeliveriesQuery {
+    #[serde(default)]
+    pub limit: Option<i64>,
+    /// Continue after the page whose `X-Next-Cursor` header this is
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+fn validate_url(url: &str) -> Result<(), ApiError> {
+    match Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(()),
+        _ => Err(ApiError::BadRequest(format!("Invalid webhook URL: {url}"))),
+    }
+}
+
+fn validate_events(events: &[String]) -> Result<(), ApiError> {
+    for event in events {
+        if !ProjectEventKind::ALL
+            .iter()
+            .any(|kind| kind.as_str() == event)
+        {
+            return Err(ApiError::BadRequest(format!("Unknown event: {event}")));
+        }
+    }
+    Ok(())
+}
+
+async fn load_webhook(deployment: &DeploymentImpl, webhook_id: Uuid) -> Result<Webhook, ApiError> {
+    Webhook::find_by_id(&deployment.db().pool, webhook_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+/// GET /webhooks
+/// Outgoing webhooks of the projects the caller can see, optionally of one project.
+#[utoipa::path(
+    get,
+    path = "/api/webhooks",
+    tag = "webhooks",
+    params(WebhookQuery),
+    responses((status = 200, body = ApiResponse<Vec<Webhook>>))
+)]
+pub async fn get_webhooks(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<WebhookQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<Webhook>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let mut webhooks = match query.project_id {
+        Some(project_id) => Webhook::find_by_project_id(pool, project_id).await?,
+        None => Webhook::find_all(pool).await?,
+    };
+    if let Some(visible) = visible_projects(&deployment, user.as_deref(), caller.as_deref()).await?
+    {
+        webhooks.retain(|webhook| visible.contains(&webhook.project_id));
+    }
+    Ok(ResponseJson(ApiResponse::success(webhooks)))
+}
+
+/// POST /webhooks
+/// Start sending a project's activity to a URL. Needs admin on the project.
+#[utoipa::path(
+    post,
+    path = "/api/webhooks",
+    tag = "webhooks",
+    request_body = CreateWebhook,
+    responses((status = 200, body = ApiResponse<CreatedWebhook>))
+)]
+pub async fn create_webhook(
+    user: Option<Extension<AuthUser>>,
+    caller: Option<Extension<ApiKey>>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateWebhook>,
+) -> Result<ResponseJson<ApiResponse<CreatedWebhook>>, ApiError> {
+    validate_url(&payload.url)?;
+    validate_events(payload.events.as_deref().unwrap_or_default())?;
+
+    let pool = &deployment.db().pool;
+    Project::find_by_id(pool, payload.project_id)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+    require_role(
+        &deployment,
+        user.as_deref(),
+        caller.as_deref(),
+        payload.project_id,
+        ProjectRole::Admin,
+    )
+    .await?;
+
+    let secret: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(40)
+        .map(char::from)
+        .collect();
+    let webhook = Webhook::create(pool, &payload, &secret).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "webhook_created",
+            serde_json::json!({
+                "webhook_id": webhook.id.to_string(),
+                "project_id": webhook.project_id.to_string(),
+                "event_count": webhook.events.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(CreatedWebhook {
+        webhook,
+        secret,
+    })))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/{webhook_id}",
+    tag = "webhooks",
+    params(("webhook_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Webhook>))
+)]
+pub async fn get_webhook(
+    State(deployment): State<DeploymentImpl>,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Webhook>>, ApiError> {
+    let webhook = load_webhook(&deployment, webhook_id).await?;
+    Ok(ResponseJson(ApiResponse::success(webhook)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/webhooks/{webhook_id}",
+    tag = "webhooks",
+    params(("webhook_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<()>))
+)]
+pub async fn delete_webhook(
+    State(deployment): State<DeploymentImpl>,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let webhook = load_webhook(&deployment, webhook_id).await?;
+    Webhook::delete(&deployment.db().pool, webhook.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "webhook_deleted",
+            serde_json::json!({
+                "webhook_id": webhook.id.to_string(),
+                "project_id": webhook.project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// POST /webhooks/{webhook_id}/test
+/// Send a `ping` event right away and return how the delivery went.
+#[utoipa::path(
+    post,
+    path = "/api/webhooks/{webhook_id}/test",
+    tag = "webhooks",
+    params(("webhook_id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<WebhookDelivery>))
+)]
+pub async fn test_webhook(
+    State(deployment): State<DeploymentImpl>,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<WebhookDelivery>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let webhook = load_webhook(&deployment, webhook_id).await?;
+    let secret = Webhook::find_secret(pool, webhook.id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let delivery = WebhookDispatcher::new()
+        .ping(pool, &webhook, &secret)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(delivery)))
+}
+
+/// GET /webhooks/{webhook_id}/deliveries
+/// Attempts to deliver events to the webhook with the replies they got, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/{webhook_id}/deliveries",
+    tag = "webhooks",
+    params(("webhook_id" = Uuid, Path), WebhookDeliveriesQuery),
+    responses((
+        status = 200,
+        body = ApiResponse<Vec<WebhookDelivery>>,
+        headers(("X-Next-Cursor" = String))
+    ))
+)]
+pub async fn get_webhook_deliveries(
+    State(deployment): State<DeploymentImpl>,
+    Path(webhook_id): Path<Uuid>,
+    Query(query): Query<WebhookDeliveriesQuery>,
+) -> Result<(HeaderMap, ResponseJson<ApiResponse<Vec<WebhookDelivery>>>), ApiError> {
+    let webhook = load_webhook(&deployment, webhook_id).await?;
+    let limit = query.limit.unwrap_or(50).clamp(1, 500);
+    let after = routes::decode_cursor(query.cursor.as_deref())?;
+    let page = WebhookDelivery::find_by_webhook_id(
+        &deployment.db().pool,
+        webhook.id,
+        after.as_ref(),
+        limit,
+    )
+    .await?;
+    Ok((
+        routes::next_cursor_header(page.next_cursor.as_deref()),
+        ResponseJson(ApiResponse::success(page.items)),
+    ))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let mut router = Router::new()
+        .route("/webhooks", get(get_webhooks).post(create_webhook))
+        .route(
+            "/webhooks/{webhook_id}",
+            get(get_webhook).delete(delete_webhook),
+        )
+        .route("/webhooks/{webhook_id}/test", post(test_webhook))
+        .route(
+            "/webhooks/{webhook_id}/deliveries",
+            get(get_webhook_deliveries),
+        );
+    // Inbound routes name their provider, so they don't share a path parameter with the
+    // webhook id above
+    for provider in [
+        IntegrationProvider::YouTrack,
+        IntegrationProvider::Jira,
+        IntegrationProvider::GitHub,
+    ] {
+        router = router.route(
+            &format!("/webhooks/{provider}/{{integration_id}}"),
+            post(
+                move |state: State<DeploymentImpl>,
+                      Path(integration_id): Path<Uuid>,
+                      headers: HeaderMap,
+                      body: Bytes| {
+                    receive_webhook(state, Path((provider, integration_id)), headers, body)
+                },
+            ),
+        );
+    }
+    router
+}
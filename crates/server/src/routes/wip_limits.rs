@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+
+use axum::{Extension, Json, Router, extract::State, response::Json as ResponseJson, routing::get};
+use db::models::{
+    project::Project,
+    project_settings::ProjectSettings,
+    task::TaskStatus,
+    wip_limit::{SetWipLimit, WipColumn, WipLimit},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// The message for moving tasks into a column that can't take them.
+pub fn limit_message(column: &WipColumn) -> String {
+    format!(
+        "Column '{}' is over its WIP limit of {}",
+        column.status,
+        column.limit.unwrap_or_default()
+    )
+}
+
+/// Check a move of `entering` tasks into a status column. Fails when the column has an
+/// enforced limit that would be exceeded, and returns a warning when the limit is only
+/// advisory.
+pub async fn check_move(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    status: &TaskStatus,
+    entering: i64,
+) -> Result<Option<String>, ApiError> {
+    let column = WipColumn::find(&deployment.db().pool, project_id, status).await?;
+    if !column.would_exceed(entering) {
+        return Ok(None);
+    }
+    if column.enforced {
+        return Err(ApiError::Conflict(limit_message(&column)));
+    }
+    Ok(Some(limit_message(&column)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/wip-limits",
+    tag = "wip_limits",
+    params(("id" = Uuid, Path)),
+    responses((status = 200, body = ApiResponse<Vec<WipColumn>>))
+)]
+pub async fn get_wip_columns(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<WipColumn>>>, ApiError> {
+    let columns = WipColumn::for_project(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(columns)))
+}
+
+/// PUT /projects/{project_id}/wip-limits
+/// Replace the project's WIP limits. Statuses left out have no limit, and limits without
+/// an `enforced` flag follow the project settings.
+#[utoipa::path(
+    put,
+    path = "/api/projects/{id}/wip-limits",
+    tag = "wip_limits",
+    params(("id" = Uuid, Path)),
+    request_body = Vec<SetWipLimit>,
+    responses((status = 200, body = ApiResponse<Vec<WipColumn>>))
+)]
+pub async fn set_wip_limits(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<Vec<SetWipLimit>>,
+) -> Result<ResponseJson<ApiResponse<Vec<WipColumn>>>, ApiError> {
+    let mut seen = HashSet::new();
+    for limit in &payload {
+        if limit.max_tasks < 1 {
+            return Err(ApiError::BadRequest(format!(
+                "WIP limit for '{}' must be at least 1",
+                limit.status
+            )));
+        }
+        if !seen.insert(limit.status.to_string()) {
+            return Err(ApiError::BadRequest(format!(
+                "Status '{}' has more than one WIP limit",
+                limit.status
+            )));
+        }
+    }
+
+    let pool = &deployment.db().pool;
+    let settings = ProjectSettings::find(pool, project.id).await?;
+    WipLimit::set_for_project(pool, project.id, &payload, settings.enforce_wip_limits).await?;
+    let columns = WipColumn::for_project(pool, project.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "wip_limits_updated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "limit_count": payload.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(columns)))
+}
+
+/// Routes nested under `/projects/{id}`, behind the project loading middleware.
+pub fn project_router() -> Router<DeploymentImpl> {
+    Router::new().route("/wip-limits", get(get_wip_columns).put(set_wip_limits))
+}
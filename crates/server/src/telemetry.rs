@@ -0,0 +1,99 @@
+//! OpenTelemetry tracing, on when `OTEL_EXPORTER_OTLP_ENDPOINT` is set. The spans of API
+//! requests, background jobs and provider HTTP calls are exported over OTLP, with SQL
+//! statements as events on the span that ran them, so a slow sync run can be followed
+//! down to the call that held it up.
+//!
+//! The standard variables configure it: `OTEL_EXPORTER_OTLP_PROTOCOL` (`grpc`, the
+//! default, `http/protobuf` or `http/json`), `OTEL_SERVICE_NAME`, `OTEL_RESOURCE_ATTRIBUTES`,
+//! `OTEL_TRACES_SAMPLER` and the exporter's headers and timeouts.
+
+use std::sync::OnceLock;
+
+use opentelemetry::{global, trace::TracerProvider as _};
+use opentelemetry_otlp::{Protocol, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{Resource, propagation::TraceContextPropagator, trace::SdkTracerProvider};
+use tracing::Subscriber;
+use tracing_subscriber::{EnvFilter, Layer, registry::LookupSpan};
+
+/// What is exported: this workspace's spans, and each SQL statement sqlx logs.
+const EXPORT_FILTER: &str = "warn,server=info,services=info,db=info,deployment=info,local_deployment=info,sqlx::query=debug";
+
+static PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+/// Whether spans are being exported.
+pub fn enabled() -> bool {
+    PROVIDER.get().is_some()
+}
+
+fn endpoint_configured() -> bool {
+    [
+        "OTEL_EXPORTER_OTLP_ENDPOINT",
+        "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+    ]
+    .iter()
+    .any(|name| std::env::var(name).is_ok_and(|value| !value.is_empty()))
+}
+
+fn exporter() -> Result<SpanExporter, opentelemetry_otlp::ExporterBuildError> {
+    let protocol = std::env::var("OTEL_EXPORTER_OTLP_TRACES_PROTOCOL")
+        .or_else(|_| std::env::var("OTEL_EXPORTER_OTLP_PROTOCOL"))
+        .unwrap_or_default();
+    match protocol.as_str() {
+        "http/protobuf" => SpanExporter::builder()
+            .with_http()
+            .with_protocol(Protocol::HttpBinary)
+            .build(),
+        "http/json" => SpanExporter::builder()
+            .with_http()
+            .with_protocol(Protocol::HttpJson)
+            .build(),
+        _ => SpanExporter::builder().with_tonic().build(),
+    }
+}
+
+/// The layer exporting spans, when an OTLP endpoint is configured. Incoming requests'
+/// `traceparent` headers are honored, so a caller's trace continues into the server.
+pub fn layer<S>() -> Option<impl Layer<S>>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    if !endpoint_configured() {
+        return None;
+    }
+    let exporter = match exporter() {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            eprintln!("Failed to set up the OTLP span exporter: {e}");
+            return None;
+        }
+    };
+
+    let resource = if std::env::var("OTEL_SERVICE_NAME").is_ok() {
+        Resource::builder().build()
+    } else {
+        Resource::builder().with_service_name("vibe-kanban").build()
+    };
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource)
+        .build();
+    let tracer = provider.tracer("vibe-kanban");
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_tracer_provider(provider.clone());
+    let _ = PROVIDER.set(provider);
+
+    Some(
+        tracing_opentelemetry::layer()
+            .with_tracer(tracer)
+            .with_filter(EnvFilter::new(EXPORT_FILTER)),
+    )
+}
+
+/// Export the spans still buffered; call before exiting.
+pub fn shutdown() {
+    if let Some(provider) = PROVIDER.get()
+        && let Err(e) = provider.shutdown()
+    {
+        tracing::warn!("Failed to flush OpenTelemetry spans: {}", e);
+    }
+}
@@ -6,6 +6,7 @@ use dashmap::DashMap;
 use db::models::{
     execution_process::ExecutionProcess,
     task::{Task, TaskStatus},
+    task_event::{TaskEvent, TaskEventSource},
 };
 use executors::{
     approvals::ToolCallMetadata,
@@ -173,7 +174,7 @@ impl Approvals {
             ) && let Ok(ctx) =
                 ExecutionProcess::load_context(pool, tool_ctx.execution_process_id).await
                 && ctx.task.status == TaskStatus::InReview
-                && let Err(e) = Task::update_status(pool, ctx.task.id, TaskStatus::InProgress).await
+                && let Err(e) = move_task(pool, &ctx.task, TaskStatus::InProgress).await
             {
                 tracing::warn!(
                     "Failed to update task status to InProgress after approval response: {}",
@@ -261,7 +262,7 @@ impl Approvals {
 pub(crate) async fn ensure_task_in_review(pool: &SqlitePool, execution_process_id: Uuid) {
     if let Ok(ctx) = ExecutionProcess::load_context(pool, execution_process_id).await
         && ctx.task.status == TaskStatus::InProgress
-        && let Err(e) = Task::update_status(pool, ctx.task.id, TaskStatus::InReview).await
+        && let Err(e) = move_task(pool, &ctx.task, TaskStatus::InReview).await
     {
         tracing::warn!(
             "Failed to update task status to InReview for approval request: {}",
@@ -270,6 +271,12 @@ pub(crate) async fn ensure_task_in_review(pool: &SqlitePool, execution_process_i
     }
 }
 
+async fn move_task(pool: &SqlitePool, task: &Task, status: TaskStatus) -> Result<(), SqlxError> {
+    Task::update_status(pool, task.id, status.clone()).await?;
+    TaskEvent::record_status_change(pool, task.id, &task.status, &status, TaskEventSource::Agent)
+        .await
+}
+
 /// Find a matching tool use entry that hasn't been assigned to an approval yet
 /// Matches by tool call id from tool metadata
 fn find_matching_tool_use(
@@ -0,0 +1,272 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use db::models::task_attachment::{CreateTaskAttachment, TaskAttachment};
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AttachmentError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Unsupported attachment type: {0}")]
+    UnsupportedType(String),
+
+    #[error("Attachment too large: {0} bytes (max: {1} bytes)")]
+    TooLarge(u64, u64),
+
+    #[error("Attachment not found")]
+    NotFound,
+}
+
+/// Where an imported attachment came from, so re-imports are skipped.
+#[derive(Debug, Clone, Copy)]
+pub struct AttachmentSource<'a> {
+    pub integration_id: Uuid,
+    pub external_id: &'a str,
+}
+
+/// Extensions accepted as attachments and the content type they are served with.
+/// Anything that could run in the browser (HTML, SVG, scripts) is left out.
+const ALLOWED_TYPES: &[(&str, &str)] = &[
+    ("pdf", "application/pdf"),
+    ("txt", "text/plain"),
+    ("log", "text/plain"),
+    ("md", "text/markdown"),
+    ("csv", "text/csv"),
+    ("json", "application/json"),
+    ("xml", "application/xml"),
+    ("yaml", "application/yaml"),
+    ("yml", "application/yaml"),
+    ("zip", "application/zip"),
+    ("gz", "application/gzip"),
+    ("tar", "application/x-tar"),
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("doc", "application/msword"),
+    (
+        "docx",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    ),
+    ("xls", "application/vnd.ms-excel"),
+    (
+        "xlsx",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    ),
+    (
+        "pptx",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    ),
+];
+
+/// Resolve the content type of an upload from its file name. A declared content type
+/// must agree with the extension, except for the generic `application/octet-stream`
+/// that many clients send for every file.
+pub fn resolve_content_type(
+    filename: &str,
+    declared: Option<&str>,
+) -> Result<&'static str, AttachmentError> {
+    let extension = Path::new(filename)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    let Some((_, mime_type)) = ALLOWED_TYPES.iter().find(|(ext, _)| *ext == extension) else {
+        return Err(AttachmentError::UnsupportedType(if extension.is_empty() {
+            filename.to_string()
+        } else {
+            format!(".{extension}")
+        }));
+    };
+
+    let declared = declared
+        .and_then(|d| d.split(';').next())
+        .map(|d| d.trim().to_lowercase());
+    match declared.as_deref() {
+        None | Some("") | Some("application/octet-stream") => Ok(mime_type),
+        Some(declared) if declared == *mime_type => Ok(mime_type),
+        // text/* files are commonly declared as text/plain
+        Some("text/plain") if mime_type.starts_with("text/") => Ok(mime_type),
+        Some(declared) => Err(AttachmentError::UnsupportedType(format!(
+            "{declared} does not match .{extension}"
+        ))),
+    }
+}
+
+#[derive(Clone)]
+pub struct AttachmentService {
+    cache_dir: PathBuf,
+    pool: SqlitePool,
+    max_size_bytes: u64,
+}
+
+impl AttachmentService {
+    pub fn new(pool: SqlitePool) -> Result<Self, AttachmentError> {
+        let cache_dir = utils::cache_dir().join("attachments");
+        fs::create_dir_all(&cache_dir)?;
+        Ok(Self {
+            cache_dir,
+            pool,
+            max_size_bytes: 50 * 1024 * 1024, // 50MB default
+        })
+    }
+
+    pub fn max_size_bytes(&self) -> u64 {
+        self.max_size_bytes
+    }
+
+    pub async fn store_attachment(
+        &self,
+        task_id: Uuid,
+        data: &[u8],
+        original_filename: &str,
+        declared_type: Option<&str>,
+        source: Option<AttachmentSource<'_>>,
+    ) -> Result<TaskAttachment, AttachmentError> {
+        let file_size = data.len() as u64;
+        if file_size > self.max_size_bytes {
+            return Err(AttachmentError::TooLarge(file_size, self.max_size_bytes));
+        }
+        let mime_type = resolve_content_type(original_filename, declared_type)?;
+
+        let hash = format!("{:x}", Sha256::digest(data));
+        let new_filename = Uuid::new_v4().to_string();
+        fs::write(self.cache_dir.join(&new_filename), data)?;
+
+        let created = TaskAttachment::create(
+            &self.pool,
+            &CreateTaskAttachment {
+                task_id,
+                file_path: new_filename.clone(),
+                original_name: original_filename.to_string(),
+                mime_type: mime_type.to_string(),
+                size_bytes: file_size as i64,
+                hash,
+                integration_id: source.map(|s| s.integration_id),
+                external_id: source.map(|s| s.external_id.to_string()),
+            },
+        )
+        .await;
+        if created.is_err() {
+            let _ = fs::remove_file(self.cache_dir.join(&new_filename));
+        }
+        Ok(created?)
+    }
+
+    /// Copy an attachment's file and record onto another task. The copy is not tied to the
+    /// integration the original was imported from.
+    pub async fn copy_attachment(
+        &self,
+        attachment: &TaskAttachment,
+        task_id: Uuid,
+    ) -> Result<TaskAttachment, AttachmentError> {
+        let new_filename = Uuid::new_v4().to_string();
+        fs::copy(
+            self.get_absolute_path(attachment),
+            self.cache_dir.join(&new_filename),
+        )?;
+
+        let created = TaskAttachment::create(
+            &self.pool,
+            &CreateTaskAttachment {
+                task_id,
+                file_path: new_filename.clone(),
+                original_name: attachment.original_name.clone(),
+                mime_type: attachment.mime_type.clone(),
+                size_bytes: attachment.size_bytes,
+                hash: attachment.hash.clone(),
+                integration_id: None,
+                external_id: None,
+            },
+        )
+        .await;
+        if created.is_err() {
+            let _ = fs::remove_file(self.cache_dir.join(&new_filename));
+        }
+        Ok(created?)
+    }
+
+    pub fn get_absolute_path(&self, attachment: &TaskAttachment) -> PathBuf {
+        self.cache_dir.join(&attachment.file_path)
+    }
+
+    pub async fn delete_attachment(&self, id: Uuid) -> Result<(), AttachmentError> {
+        let attachment = TaskAttachment::find_by_id(&self.pool, id)
+            .await?
+            .ok_or(AttachmentError::NotFound)?;
+        TaskAttachment::delete(&self.pool, id).await?;
+
+        let file_path = self.get_absolute_path(&attachment);
+        if file_path.exists() {
+            fs::remove_file(file_path)?;
+        }
+        Ok(())
+    }
+
+    /// Remove files left behind by attachments deleted along with their task.
+    pub async fn delete_orphaned_files(&self) -> Result<(), AttachmentError> {
+        let known: HashSet<String> = TaskAttachment::find_all_file_paths(&self.pool)
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut deleted_count = 0;
+        for entry in fs::read_dir(&self.cache_dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if known.contains(&name) || !entry.file_type()?.is_file() {
+                continue;
+            }
+            match fs::remove_file(entry.path()) {
+                Ok(()) => deleted_count += 1,
+                Err(e) => tracing::error!("Failed to delete orphaned attachment {}: {}", name, e),
+            }
+        }
+
+        tracing::info!(
+            "Attachment cleanup completed: {} orphaned files deleted",
+            deleted_count
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_content_type_from_extension() {
+        assert_eq!(
+            resolve_content_type("Report.PDF", None).unwrap(),
+            "application/pdf"
+        );
+        assert_eq!(
+            resolve_content_type("trace.log", Some("application/octet-stream")).unwrap(),
+            "text/plain"
+        );
+        assert_eq!(
+            resolve_content_type("notes.md", Some("text/plain; charset=utf-8")).unwrap(),
+            "text/markdown"
+        );
+    }
+
+    #[test]
+    fn test_resolve_content_type_rejects_unknown_or_mismatched() {
+        assert!(resolve_content_type("setup.exe", None).is_err());
+        assert!(resolve_content_type("page.html", Some("text/html")).is_err());
+        assert!(resolve_content_type("Makefile", None).is_err());
+        assert!(resolve_content_type("report.pdf", Some("text/html")).is_err());
+    }
+}
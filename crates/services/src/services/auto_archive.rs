@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use db::{
+    DBService,
+    models::{
+        job::Job,
+        task::Task,
+        task_event::{TaskEvent, TaskEventSource},
+    },
+};
+use sqlx::SqlitePool;
+use tracing::{error, info};
+
+use crate::services::job_runner::JobHandler;
+
+/// Hourly job that archives done and cancelled tasks once they have gone unchanged for as
+/// long as their project's `auto_archive_after_days` setting allows.
+pub struct AutoArchiveJob {
+    db: DBService,
+}
+
+impl AutoArchiveJob {
+    pub fn new(db: DBService) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl JobHandler for AutoArchiveJob {
+    fn kind(&self) -> &'static str {
+        "auto_archive"
+    }
+
+    async fn run(&self, _job: &Job) -> Result<(), String> {
+        let pool = &self.db.pool;
+        let tasks = Task::find_auto_archivable(pool)
+            .await
+            .map_err(|e| format!("Failed to load tasks to auto-archive: {e}"))?;
+
+        for task in tasks {
+            match archive_task(pool, &task).await {
+                Ok(()) => info!("Auto-archived task {}", task.id),
+                Err(e) => error!("Failed to auto-archive task {}: {}", task.id, e),
+            }
+        }
+        Ok(())
+    }
+
+    fn schedule(&self) -> Option<Duration> {
+        Some(Duration::from_secs(60 * 60))
+    }
+}
+
+async fn archive_task(pool: &SqlitePool, task: &Task) -> Result<(), sqlx::Error> {
+    let archived = Task::archive(pool, task.id).await?;
+    TaskEvent::record_changes(pool, task, &archived, TaskEventSource::System, None).await
+}
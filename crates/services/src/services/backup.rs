@@ -0,0 +1,111 @@
+use std::{path::PathBuf, time::Duration};
+
+use async_trait::async_trait;
+use db::{DBService, models::job::Job};
+use tracing::{error, info};
+
+use crate::services::job_runner::JobHandler;
+
+const FILE_PREFIX: &str = "vk-";
+const FILE_SUFFIX: &str = ".db.gz";
+
+/// Scheduled database backups, enabled by setting `VK_BACKUP_DIR`.
+#[derive(Debug, Clone)]
+pub struct BackupConfig {
+    pub dir: PathBuf,
+    pub interval: Duration,
+    /// Backups kept in `dir`; older ones are deleted
+    pub keep: usize,
+}
+
+impl BackupConfig {
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let hours = var("VK_BACKUP_INTERVAL_HOURS")
+            .and_then(|value| value.parse::<u64>().ok())
+            .filter(|hours| *hours > 0)
+            .unwrap_or(24);
+        Some(Self {
+            dir: PathBuf::from(var("VK_BACKUP_DIR")?),
+            interval: Duration::from_secs(hours * 60 * 60),
+            keep: var("VK_BACKUP_KEEP")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(7),
+        })
+    }
+}
+
+/// Scheduled job writing a gzipped copy of the database to the backup directory, keeping
+/// the newest few.
+pub struct BackupJob {
+    db: DBService,
+    config: BackupConfig,
+}
+
+impl BackupJob {
+    pub fn new(db: DBService, config: BackupConfig) -> Self {
+        Self { db, config }
+    }
+}
+
+#[async_trait]
+impl JobHandler for BackupJob {
+    fn kind(&self) -> &'static str {
+        "backup"
+    }
+
+    async fn run(&self, _job: &Job) -> Result<(), String> {
+        std::fs::create_dir_all(&self.config.dir).map_err(|e| {
+            format!(
+                "Failed to create backup directory {}: {e}",
+                self.config.dir.display()
+            )
+        })?;
+        let path = self.config.dir.join(format!(
+            "{FILE_PREFIX}{}{FILE_SUFFIX}",
+            chrono::Utc::now().format("%Y%m%d-%H%M%S")
+        ));
+        let bytes = self
+            .db
+            .backup_to(&path)
+            .await
+            .map_err(|e| format!("Failed to back up the database: {e}"))?;
+        info!(
+            "Backed up the database to {} ({bytes} bytes)",
+            path.display()
+        );
+        self.prune();
+        Ok(())
+    }
+
+    fn schedule(&self) -> Option<Duration> {
+        Some(self.config.interval)
+    }
+}
+
+impl BackupJob {
+    /// Delete all but the newest `keep` backups; the timestamped names sort by age.
+    fn prune(&self) {
+        let Ok(entries) = std::fs::read_dir(&self.config.dir) else {
+            return;
+        };
+        let mut backups: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        name.starts_with(FILE_PREFIX) && name.ends_with(FILE_SUFFIX)
+                    })
+            })
+            .collect();
+        backups.sort();
+        let excess = backups.len().saturating_sub(self.config.keep.max(1));
+        for path in backups.into_iter().take(excess) {
+            match std::fs::remove_file(&path) {
+                Ok(()) => info!("Deleted old backup {}", path.display()),
+                Err(e) => error!("Failed to delete old backup {}: {}", path.display(), e),
+            }
+        }
+    }
+}
@@ -23,6 +23,7 @@ use db::{
         repo::Repo,
         session::{CreateSession, Session, SessionError},
         task::{Task, TaskStatus},
+        task_event::{TaskEvent, TaskEventSource},
         workspace::{Workspace, WorkspaceError},
         workspace_repo::WorkspaceRepo,
     },
@@ -169,6 +170,17 @@ pub trait ContainerService {
     ) {
         match Task::update_status(&self.db().pool, ctx.task.id, TaskStatus::InReview).await {
             Ok(_) => {
+                if let Err(e) = TaskEvent::record_status_change(
+                    &self.db().pool,
+                    ctx.task.id,
+                    &ctx.task.status,
+                    &TaskStatus::InReview,
+                    TaskEventSource::Agent,
+                )
+                .await
+                {
+                    tracing::warn!("Failed to record task status change: {e}");
+                }
                 if let Some(publisher) = share_publisher
                     && let Err(err) = publisher.update_shared_task_by_id(ctx.task.id).await
                 {
@@ -280,6 +292,17 @@ pub trait ContainerService {
             {
                 match Task::update_status(&self.db().pool, task.id, TaskStatus::InReview).await {
                     Ok(_) => {
+                        if let Err(e) = TaskEvent::record_status_change(
+                            &self.db().pool,
+                            task.id,
+                            &task.status,
+                            &TaskStatus::InReview,
+                            TaskEventSource::Agent,
+                        )
+                        .await
+                        {
+                            tracing::warn!("Failed to record task status change: {e}");
+                        }
                         if let Some(publisher) = self.share_publisher()
                             && let Err(err) = publisher.update_shared_task_by_id(task.id).await
                         {
@@ -997,10 +1020,20 @@ pub trait ContainerService {
             .parent_task(&self.db().pool)
             .await?
             .ok_or(SqlxError::RowNotFound)?;
+        let mut task_status = task.status.clone();
         if task.status != TaskStatus::InProgress
             && run_reason != &ExecutionProcessRunReason::DevServer
         {
             Task::update_status(&self.db().pool, task.id, TaskStatus::InProgress).await?;
+            TaskEvent::record_status_change(
+                &self.db().pool,
+                task.id,
+                &task.status,
+                &TaskStatus::InProgress,
+                TaskEventSource::Agent,
+            )
+            .await?;
+            task_status = TaskStatus::InProgress;
 
             if let Some(publisher) = self.share_publisher()
                 && let Err(err) = publisher.update_shared_task_by_id(task.id).await
@@ -1118,6 +1151,14 @@ pub trait ContainerService {
                 );
             }
             Task::update_status(&self.db().pool, task.id, TaskStatus::InReview).await?;
+            TaskEvent::record_status_change(
+                &self.db().pool,
+                task.id,
+                &task_status,
+                &TaskStatus::InReview,
+                TaskEventSource::Agent,
+            )
+            .await?;
 
             // Emit stderr error message
             let log_message = LogMsg::Stderr(format!("Failed to start execution: {start_error}"));
@@ -1,4 +1,10 @@
-use std::{str::FromStr, sync::Arc};
+use std::{
+    str::FromStr,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
 
 use db::{
     DBService,
@@ -13,6 +19,12 @@ use tokio::sync::RwLock;
 use utils::msg_store::MsgStore;
 use uuid::Uuid;
 
+#[path = "events/activity.rs"]
+pub mod activity;
+#[path = "events/board.rs"]
+pub mod board;
+#[path = "events/board_cache.rs"]
+pub mod board_cache;
 #[path = "events/patches.rs"]
 pub mod patches;
 #[path = "events/streams.rs"]
@@ -20,6 +32,9 @@ mod streams;
 #[path = "events/types.rs"]
 pub mod types;
 
+pub use activity::{ProjectActivityLog, ProjectEvent, ProjectEventKind, Replay};
+pub use board::{BoardEvent, BoardEventBus, BoardEventKind, BoardMessage};
+pub use board_cache::BoardCache;
 pub use patches::{
     execution_process_patch, project_patch, scratch_patch, task_patch, workspace_patch,
 };
@@ -28,6 +43,9 @@ pub use types::{EventError, EventPatch, EventPatchInner, HookTables, RecordTypes
 #[derive(Clone)]
 pub struct EventService {
     msg_store: Arc<MsgStore>,
+    board_events: BoardEventBus,
+    board_cache: BoardCache,
+    activity: ProjectActivityLog,
     db: DBService,
     #[allow(dead_code)]
     entry_count: Arc<RwLock<usize>>,
@@ -35,9 +53,20 @@ pub struct EventService {
 
 impl EventService {
     /// Creates a new EventService that will work with a DBService configured with hooks
-    pub fn new(db: DBService, msg_store: Arc<MsgStore>, entry_count: Arc<RwLock<usize>>) -> Self {
+    pub fn new(
+        db: DBService,
+        msg_store: Arc<MsgStore>,
+        board_events: BoardEventBus,
+        entry_count: Arc<RwLock<usize>>,
+    ) -> Self {
+        let activity = ProjectActivityLog::new();
+        activity.follow(&board_events);
+        let board_cache = BoardCache::new(board_events.clone());
         Self {
             msg_store,
+            board_events,
+            board_cache,
+            activity,
             db,
             entry_count,
         }
@@ -80,6 +109,7 @@ impl EventService {
     /// Creates the hook function that should be used with DBService::new_with_after_connect
     pub fn create_hook(
         msg_store: Arc<MsgStore>,
+        board_events: BoardEventBus,
         entry_count: Arc<RwLock<usize>>,
         db_service: DBService,
     ) -> impl for<'a> Fn(
@@ -91,13 +121,32 @@ impl EventService {
     + 'static {
         move |conn: &mut sqlx::sqlite::SqliteConnection| {
             let msg_store_for_hook = msg_store.clone();
+            let board_events_for_hook = board_events.clone();
             let entry_count_for_hook = entry_count.clone();
             let db_for_hook = db_service.clone();
             Box::pin(async move {
                 let mut handle = conn.lock_handle().await?;
                 let runtime_handle = tokio::runtime::Handle::current();
+                // Whether this connection's open transaction wrote to a board table
+                let board_written = Arc::new(AtomicBool::new(false));
+                handle.set_commit_hook({
+                    let board_events = board_events_for_hook.clone();
+                    let board_written = board_written.clone();
+                    move || {
+                        if board_written.swap(false, Ordering::Relaxed) {
+                            board_events.note_commit();
+                        }
+                        // Let the commit go ahead
+                        true
+                    }
+                });
+                handle.set_rollback_hook({
+                    let board_written = board_written.clone();
+                    move || board_written.store(false, Ordering::Relaxed)
+                });
                 handle.set_preupdate_hook({
                     let msg_store_for_preupdate = msg_store_for_hook.clone();
+                    let board_events_for_preupdate = board_events_for_hook.clone();
                     move |preupdate: sqlx::sqlite::PreupdateHookResult<'_>| {
                         if preupdate.operation != SqliteOperation::Delete {
                             return;
@@ -110,6 +159,13 @@ impl EventService {
                                 {
                                     let patch = task_patch::remove(task_id);
                                     msg_store_for_preupdate.push_patch(patch);
+                                    if let Ok(value) = preupdate.get_old_column_value(1)
+                                        && let Ok(project_id) =
+                                            <Uuid as Decode<Sqlite>>::decode(value)
+                                    {
+                                        board_events_for_preupdate
+                                            .publish_deleted(project_id, task_id);
+                                    }
                                 }
                             }
                             "projects" => {
@@ -158,7 +214,11 @@ impl EventService {
                     let runtime_handle = runtime_handle.clone();
                     let entry_count_for_hook = entry_count_for_hook.clone();
                     let msg_store_for_hook = msg_store_for_hook.clone();
+                    let board_events_for_hook = board_events_for_hook.clone();
                     let db = db_for_hook.clone();
+                    if BoardEventBus::is_board_table(hook.table) {
+                        board_written.store(true, Ordering::Relaxed);
+                    }
 
                     if let Ok(table) = HookTables::from_str(hook.table) {
                         let rowid = hook.rowid;
@@ -257,6 +317,14 @@ impl EventService {
 
                             // Handle task-related operations with direct patches
                             match &record_type {
+                                RecordTypes::Task(task)
+                                    if task.archived_at.is_some() || task.deleted_at.is_some() =>
+                                {
+                                    // Archived and trashed tasks leave the board
+                                    msg_store_for_hook.push_patch(task_patch::remove(task.id));
+                                    board_events_for_hook.publish_removed(task.project_id, task.id);
+                                    return;
+                                }
                                 RecordTypes::Task(task) => {
                                     // Convert Task to TaskWithAttemptStatus
                                     if let Ok(task_list) =
@@ -269,15 +337,18 @@ impl EventService {
                                             task_list.into_iter().find(|t| t.id == task.id)
                                     {
                                         let patch = match hook.operation {
-                                            SqliteOperation::Insert => {
+                                            // An add also overwrites an existing entry, and
+                                            // brings back tasks restored from the archive
+                                            SqliteOperation::Insert | SqliteOperation::Update => {
                                                 task_patch::add(&task_with_status)
                                             }
-                                            SqliteOperation::Update => {
-                                                task_patch::replace(&task_with_status)
-                                            }
                                             _ => task_patch::replace(&task_with_status), // fallback
                                         };
                                         msg_store_for_hook.push_patch(patch);
+                                        board_events_for_hook.publish_task(
+                                            task_with_status,
+                                            matches!(hook.operation, SqliteOperation::Insert),
+                                        );
                                         return;
                                     }
                                 }
@@ -445,4 +516,16 @@ impl EventService {
     pub fn msg_store(&self) -> &Arc<MsgStore> {
         &self.msg_store
     }
+
+    pub fn board_events(&self) -> &BoardEventBus {
+        &self.board_events
+    }
+
+    pub fn board_cache(&self) -> &BoardCache {
+        &self.board_cache
+    }
+
+    pub fn activity(&self) -> &ProjectActivityLog {
+        &self.activity
+    }
 }
@@ -0,0 +1,299 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    mpsc,
+};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::board::{BoardEventBus, BoardEventKind};
+
+/// Events kept for clients resuming with `Last-Event-ID`; older ones are gone and a
+/// client that missed them has to refetch.
+const ACTIVITY_BACKLOG: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+pub enum ProjectEventKind {
+    #[serde(rename = "task.created")]
+    TaskCreated,
+    #[serde(rename = "task.updated")]
+    TaskUpdated,
+    #[serde(rename = "task.moved")]
+    TaskMoved,
+    #[serde(rename = "task.deleted")]
+    TaskDeleted,
+    #[serde(rename = "comment.created")]
+    CommentCreated,
+    #[serde(rename = "comment.deleted")]
+    CommentDeleted,
+    #[serde(rename = "sync.succeeded")]
+    SyncSucceeded,
+    #[serde(rename = "sync.failed")]
+    SyncFailed,
+}
+
+impl ProjectEventKind {
+    pub const ALL: [ProjectEventKind; 8] = [
+        Self::TaskCreated,
+        Self::TaskUpdated,
+        Self::TaskMoved,
+        Self::TaskDeleted,
+        Self::CommentCreated,
+        Self::CommentDeleted,
+        Self::SyncSucceeded,
+        Self::SyncFailed,
+    ];
+
+    /// The SSE event name, the same string the kind serializes to.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TaskCreated => "task.created",
+            Self::TaskUpdated => "task.updated",
+            Self::TaskMoved => "task.moved",
+            Self::TaskDeleted => "task.deleted",
+            Self::CommentCreated => "comment.created",
+            Self::CommentDeleted => "comment.deleted",
+            Self::SyncSucceeded => "sync.succeeded",
+            Self::SyncFailed => "sync.failed",
+        }
+    }
+}
+
+impl From<BoardEventKind> for ProjectEventKind {
+    fn from(kind: BoardEventKind) -> Self {
+        match kind {
+            BoardEventKind::Created => Self::TaskCreated,
+            BoardEventKind::Updated => Self::TaskUpdated,
+            BoardEventKind::Moved => Self::TaskMoved,
+            BoardEventKind::Deleted => Self::TaskDeleted,
+        }
+    }
+}
+
+/// A normalized domain event of a project.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ProjectEvent {
+    /// Increases by one per event, across projects, since the server started
+    #[ts(type = "number")]
+    pub id: u64,
+    pub project_id: Uuid,
+    pub kind: ProjectEventKind,
+    pub task_id: Option<Uuid>,
+    /// The task, comment or sync run the event is about; null when a task left its board
+    #[ts(type = "JsonValue")]
+    pub data: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What a resuming subscriber missed.
+pub enum Replay {
+    Events(Vec<ProjectEvent>),
+    /// The requested event is no longer kept, or was sent before a restart
+    Gap,
+}
+
+struct Backlog {
+    next_id: u64,
+    events: VecDeque<ProjectEvent>,
+    /// Subscribers that get every event however far behind they fall
+    unbounded: Vec<mpsc::UnboundedSender<ProjectEvent>>,
+}
+
+/// Project activity with sequential ids and a bounded backlog, so an SSE client that
+/// reconnects picks up where it left off.
+#[derive(Clone)]
+pub struct ProjectActivityLog {
+    backlog: Arc<Mutex<Backlog>>,
+    sender: broadcast::Sender<ProjectEvent>,
+}
+
+impl Default for ProjectActivityLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectActivityLog {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(ACTIVITY_BACKLOG);
+        Self {
+            backlog: Arc::new(Mutex::new(Backlog {
+                next_id: 1,
+                events: VecDeque::with_capacity(ACTIVITY_BACKLOG),
+                unbounded: Vec::new(),
+            })),
+            sender,
+        }
+    }
+
+    pub fn publish(
+        &self,
+        project_id: Uuid,
+        kind: ProjectEventKind,
+        task_id: Option<Uuid>,
+        data: Value,
+    ) {
+        let mut backlog = self.backlog.lock().unwrap();
+        let event = ProjectEvent {
+            id: backlog.next_id,
+            project_id,
+            kind,
+            task_id,
+            data,
+            created_at: Utc::now(),
+        };
+        backlog.next_id += 1;
+        if backlog.events.len() == ACTIVITY_BACKLOG {
+            backlog.events.pop_front();
+        }
+        backlog.events.push_back(event.clone());
+        backlog
+            .unbounded
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+        // Sent under the lock so replays and live events never overlap or reorder
+        let _ = self.sender.send(event);
+    }
+
+    /// Live events of every project, queued without bound instead of dropped when the
+    /// receiver falls behind, for consumers that must see each one.
+    pub fn subscribe_unbounded(&self) -> mpsc::UnboundedReceiver<ProjectEvent> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.backlog.lock().unwrap().unbounded.push(sender);
+        receiver
+    }
+
+    /// Live events of every project, and the events after `last_id` that were already
+    /// published. Callers filter by `project_id`.
+    pub fn subscribe(&self, last_id: Option<u64>) -> (Replay, broadcast::Receiver<ProjectEvent>) {
+        let backlog = self.backlog.lock().unwrap();
+        let receiver = self.sender.subscribe();
+        let Some(last_id) = last_id else {
+            return (Replay::Events(Vec::new()), receiver);
+        };
+        let oldest = backlog
+            .events
+            .front()
+            .map_or(backlog.next_id, |event| event.id);
+        let replay = if last_id >= backlog.next_id || last_id + 1 < oldest {
+            Replay::Gap
+        } else {
+            Replay::Events(
+                backlog
+                    .events
+                    .iter()
+                    .filter(|event| event.id > last_id)
+                    .cloned()
+                    .collect(),
+            )
+        };
+        (replay, receiver)
+    }
+
+    /// Record every board change as task activity for as long as the bus lives.
+    pub fn follow(&self, board_events: &BoardEventBus) {
+        let log = self.clone();
+        let mut receiver = board_events.subscribe();
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => log.publish(
+                        event.project_id,
+                        event.kind.into(),
+                        Some(event.task_id),
+                        event
+                            .task
+                            .map(|task| serde_json::to_value(task).unwrap_or_default())
+                            .unwrap_or_default(),
+                    ),
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped = skipped, "project activity missed board events");
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::broadcast::error::TryRecvError;
+
+    use super::*;
+
+    fn ids(replay: Replay) -> Option<Vec<u64>> {
+        match replay {
+            Replay::Events(events) => Some(events.iter().map(|event| event.id).collect()),
+            Replay::Gap => None,
+        }
+    }
+
+    #[test]
+    fn test_subscribe_replays_after_last_event_id() {
+        let log = ProjectActivityLog::new();
+        for _ in 0..ACTIVITY_BACKLOG + 5 {
+            log.publish(
+                Uuid::nil(),
+                ProjectEventKind::TaskUpdated,
+                None,
+                Value::Null,
+            );
+        }
+        let last = (ACTIVITY_BACKLOG + 5) as u64;
+
+        assert_eq!(ids(log.subscribe(None).0), Some(vec![]));
+        assert_eq!(ids(log.subscribe(Some(last)).0), Some(vec![]));
+        assert_eq!(
+            ids(log.subscribe(Some(last - 2)).0),
+            Some(vec![last - 1, last])
+        );
+        // The first kept event is 6, so resuming after 5 is still complete
+        assert_eq!(
+            ids(log.subscribe(Some(5)).0).map(|ids| ids.len()),
+            Some(ACTIVITY_BACKLOG)
+        );
+        assert!(ids(log.subscribe(Some(4)).0).is_none());
+        // An id from before a restart
+        assert!(ids(log.subscribe(Some(last + 10)).0).is_none());
+    }
+
+    #[test]
+    fn test_unbounded_subscriber_misses_nothing() {
+        let log = ProjectActivityLog::new();
+        let mut receiver = log.subscribe_unbounded();
+        let (_, mut lagging) = log.subscribe(None);
+        for _ in 0..ACTIVITY_BACKLOG * 2 {
+            log.publish(
+                Uuid::nil(),
+                ProjectEventKind::TaskUpdated,
+                None,
+                Value::Null,
+            );
+        }
+
+        assert!(matches!(lagging.try_recv(), Err(TryRecvError::Lagged(_))));
+        let received: Vec<u64> = std::iter::from_fn(|| receiver.try_recv().ok())
+            .map(|event| event.id)
+            .collect();
+        assert_eq!(
+            received,
+            (1..=(ACTIVITY_BACKLOG * 2) as u64).collect::<Vec<_>>()
+        );
+
+        drop(receiver);
+        log.publish(
+            Uuid::nil(),
+            ProjectEventKind::TaskUpdated,
+            None,
+            Value::Null,
+        );
+        assert!(log.backlog.lock().unwrap().unbounded.is_empty());
+    }
+}
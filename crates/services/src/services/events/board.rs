@@ -0,0 +1,199 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+
+use dashmap::DashMap;
+use db::models::task::{Task, TaskStatus, TaskWithAttemptStatus};
+use serde::Serialize;
+use tokio::sync::broadcast;
+use ts_rs::TS;
+use uuid::Uuid;
+
+type Placement = (Option<Uuid>, TaskStatus);
+
+/// Events a slow subscriber may fall behind by before it starts missing some.
+const BOARD_EVENT_CAPACITY: usize = 1024;
+
+/// Tables a project's board or settings are built from.
+const BOARD_TABLES: &[&str] = &[
+    "tasks",
+    "workspaces",
+    "sessions",
+    "execution_processes",
+    "project_columns",
+    "project_swimlanes",
+    "project_settings",
+    "labels",
+    "task_labels",
+    "custom_fields",
+    "task_custom_field_values",
+    "users",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum BoardEventKind {
+    Created,
+    Updated,
+    /// Changed column or status
+    Moved,
+    /// Archived, trashed or deleted; the task has left the board
+    Deleted,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct BoardEvent {
+    pub kind: BoardEventKind,
+    pub project_id: Uuid,
+    pub task_id: Uuid,
+    /// The task as the board shows it; absent for deletions
+    pub task: Option<TaskWithAttemptStatus>,
+}
+
+/// In-process bus of board changes, fed by the database hooks, so every open board of a
+/// project can apply the same change without polling.
+#[derive(Clone)]
+pub struct BoardEventBus {
+    sender: broadcast::Sender<BoardEvent>,
+    /// Last known column and status of each task, to tell moves from edits; `None` for
+    /// archived or trashed tasks, which may come back. Deleted tasks are dropped.
+    placements: Arc<DashMap<Uuid, Option<Placement>>>,
+    /// Counts committed writes to the board tables, so cached boards can tell they're out
+    /// of date
+    generation: Arc<AtomicU64>,
+}
+
+impl Default for BoardEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BoardEventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(BOARD_EVENT_CAPACITY);
+        Self {
+            sender,
+            placements: Arc::new(DashMap::new()),
+            generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Whether a write to `table` may change a board or its settings.
+    pub fn is_board_table(table: &str) -> bool {
+        BOARD_TABLES.contains(&table)
+    }
+
+    /// Note a committed transaction that wrote to a board table. Called from the database's
+    /// commit hook rather than its update hook, so a board read between a write and its
+    /// commit can't be cached under the new generation.
+    pub fn note_commit(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Changes whenever a board or its settings may have changed.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    /// Remember where a task sits without publishing anything, e.g. for the tasks a
+    /// subscriber was just sent as a snapshot.
+    pub fn remember(&self, task: &Task) {
+        self.placements
+            .insert(task.id, Some((task.column_id, task.status.clone())));
+    }
+
+    /// Publish an insert or update of a task that is on its board.
+    pub fn publish_task(&self, task: TaskWithAttemptStatus, inserted: bool) {
+        let placement = (task.column_id, task.status.clone());
+        let previous = self.placements.insert(task.id, Some(placement.clone()));
+        let kind = match previous {
+            _ if inserted => BoardEventKind::Created,
+            // Restored from the archive or the trash
+            Some(None) => BoardEventKind::Created,
+            Some(Some(previous)) if previous != placement => BoardEventKind::Moved,
+            // Not seen since startup, so there is nothing to compare against
+            Some(Some(_)) | None => BoardEventKind::Updated,
+        };
+        self.send(BoardEvent {
+            kind,
+            project_id: task.project_id,
+            task_id: task.id,
+            task: Some(task),
+        });
+    }
+
+    /// Publish that a task was archived or trashed, and so left its board.
+    pub fn publish_removed(&self, project_id: Uuid, task_id: Uuid) {
+        self.placements.insert(task_id, None);
+        self.send_removed(project_id, task_id);
+    }
+
+    /// Publish that a task was deleted for good, forgetting where it sat.
+    pub fn publish_deleted(&self, project_id: Uuid, task_id: Uuid) {
+        self.placements.remove(&task_id);
+        self.send_removed(project_id, task_id);
+    }
+
+    fn send_removed(&self, project_id: Uuid, task_id: Uuid) {
+        self.send(BoardEvent {
+            kind: BoardEventKind::Deleted,
+            project_id,
+            task_id,
+            task: None,
+        });
+    }
+
+    fn send(&self, event: BoardEvent) {
+        // Nobody listening is not an error
+        let _ = self.sender.send(event);
+    }
+
+    /// Live events of every project; callers filter by `project_id`.
+    pub fn subscribe(&self) -> broadcast::Receiver<BoardEvent> {
+        self.sender.subscribe()
+    }
+}
+
+/// A frame of the board WebSocket: a snapshot of the board first, and again whenever the
+/// subscriber fell too far behind, then one event per change.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BoardMessage {
+    Snapshot { tasks: Vec<TaskWithAttemptStatus> },
+    Event(BoardEvent),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deleted_tasks_are_forgotten() {
+        let bus = BoardEventBus::new();
+        let project_id = Uuid::new_v4();
+        let (archived, deleted) = (Uuid::new_v4(), Uuid::new_v4());
+
+        bus.publish_removed(project_id, archived);
+        bus.publish_removed(project_id, deleted);
+        bus.publish_deleted(project_id, deleted);
+
+        assert!(matches!(
+            bus.placements.get(&archived).as_deref(),
+            Some(None)
+        ));
+        assert!(!bus.placements.contains_key(&deleted));
+    }
+
+    #[test]
+    fn test_generation_counts_commits() {
+        let bus = BoardEventBus::new();
+        let generation = bus.generation();
+        bus.note_commit();
+        assert_eq!(bus.generation(), generation + 1);
+        assert!(BoardEventBus::is_board_table("tasks"));
+        assert!(!BoardEventBus::is_board_table("scratch"));
+    }
+}
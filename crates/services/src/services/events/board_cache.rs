@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use db::models::{board::Board, project_settings::ProjectSettings};
+use moka::future::Cache;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use super::board::BoardEventBus;
+
+/// Projects whose board, and separately whose settings, are kept at once.
+const BOARD_CACHE_CAPACITY: u64 = 256;
+
+/// Read-through cache of each project's board and settings, so clients polling the same
+/// board share one build of it instead of each scanning its tables.
+///
+/// Entries are keyed by the bus's write generation: any write through the hooked pool to a
+/// table they are built from makes the next read rebuild them. Writes the hooks don't see,
+/// such as `vk --local` from another process, show once the entry's time to live is up.
+#[derive(Clone)]
+pub struct BoardCache {
+    boards: Cache<(Uuid, u64), Board>,
+    settings: Cache<(Uuid, u64), ProjectSettings>,
+    board_events: BoardEventBus,
+    enabled: bool,
+}
+
+impl BoardCache {
+    /// Entries live for `VK_BOARD_CACHE_TTL_SECS`, 10 seconds by default; 0 turns the
+    /// cache off.
+    pub fn new(board_events: BoardEventBus) -> Self {
+        let ttl = std::env::var("VK_BOARD_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(10);
+        let time_to_live = Duration::from_secs(ttl.max(1));
+        Self {
+            boards: Cache::builder()
+                .max_capacity(BOARD_CACHE_CAPACITY)
+                .time_to_live(time_to_live)
+                .build(),
+            settings: Cache::builder()
+                .max_capacity(BOARD_CACHE_CAPACITY)
+                .time_to_live(time_to_live)
+                .build(),
+            board_events,
+            enabled: ttl > 0,
+        }
+    }
+
+    pub async fn board(&self, pool: &SqlitePool, project_id: Uuid) -> Result<Board, sqlx::Error> {
+        if !self.enabled {
+            return Board::for_project(pool, project_id).await;
+        }
+        // Taken before reading, so a write made meanwhile leaves this entry behind
+        let key = (project_id, self.board_events.generation());
+        if let Some(board) = self.boards.get(&key).await {
+            return Ok(board);
+        }
+        let board = Board::for_project(pool, project_id).await?;
+        self.boards.insert(key, board.clone()).await;
+        Ok(board)
+    }
+
+    pub async fn settings(
+        &self,
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<ProjectSettings, sqlx::Error> {
+        if !self.enabled {
+            return ProjectSettings::find(pool, project_id).await;
+        }
+        let key = (project_id, self.board_events.generation());
+        if let Some(settings) = self.settings.get(&key).await {
+            return Ok(settings);
+        }
+        let settings = ProjectSettings::find(pool, project_id).await?;
+        self.settings.insert(key, settings.clone()).await;
+        Ok(settings)
+    }
+}
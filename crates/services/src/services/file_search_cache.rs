@@ -20,6 +20,7 @@ use thiserror::Error;
 use tokio::sync::mpsc;
 use tracing::{error, info, warn};
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
 use super::{
     file_ranker::{FileRanker, FileStats},
@@ -29,7 +30,7 @@ use super::{
 /// Search mode for different use cases
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "lowercase")]
-#[derive(Default)]
+#[derive(Default, ToSchema)]
 pub enum SearchMode {
     #[default]
     TaskForm, // Default: exclude ignored files (clean results)
@@ -37,7 +38,8 @@ pub enum SearchMode {
 }
 
 /// Search query parameters for typed Axum extraction
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SearchQuery {
     pub q: String,
     #[serde(default)]
@@ -9,6 +9,7 @@ use thiserror::Error;
 use tokio::task;
 use tracing::info;
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 mod cli;
 
@@ -16,7 +17,7 @@ use cli::{GhCli, GhCliError, PrComment, PrReviewComment};
 pub use cli::{PrCommentAuthor, ReviewCommentUser};
 
 /// Unified PR comment that can be either a general comment or review comment
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
 #[serde(tag = "comment_type", rename_all = "snake_case")]
 #[ts(tag = "comment_type", rename_all = "snake_case")]
 pub enum UnifiedPrComment {
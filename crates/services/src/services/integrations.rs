@@ -0,0 +1,1758 @@
+//! Issue-tracker integrations: provider clients and the sync pipeline that mirrors
+//! remote issues into project tasks.
+
+mod github;
+mod jira;
+mod mapping;
+pub mod plan;
+pub mod rate_limit;
+pub mod webhooks;
+mod youtrack;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use axum::http::HeaderMap;
+use chrono::{DateTime, Utc};
+use db::models::{
+    custom_field::CustomField,
+    integration::{Integration, IntegrationCheck, IntegrationCheckStep, IntegrationProvider},
+    integration_link::{IntegrationLink, RemoteValues},
+    project_column::ProjectColumn,
+    project_settings::ProjectSettings,
+    sync_audit::{CreateSyncAuditEntry, SyncAuditEntry},
+    sync_conflict::{ConflictField, ConflictResolution, SyncConflict},
+    sync_dead_letter::SyncDeadLetter,
+    sync_job::{SyncJob, SyncJobStatus},
+    sync_plan::{SyncPlan, SyncPlanStatus},
+    task::{ExternalTaskUpsert, Task, TaskPriority, TaskStatus, UpsertedTask},
+    task_attachment::TaskAttachment,
+    task_comment::TaskComment,
+    task_event::{TaskEvent, TaskEventSource},
+    task_link::{TaskLink, TaskLinkKind},
+    user::User,
+};
+use rate_limit::{HostRateLimiter, RateLimitedClient};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use thiserror::Error;
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use webhooks::{WEBHOOK_SECRET_KEY, WebhookEvent};
+
+use super::attachment::{AttachmentError, AttachmentService, AttachmentSource};
+
+#[derive(Debug, Error)]
+pub enum IntegrationServiceError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("HTTP request failed: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("{provider} returned HTTP {status}: {body}")]
+    Http {
+        provider: IntegrationProvider,
+        status: u16,
+        body: String,
+    },
+    #[error("Missing secret '{0}'")]
+    MissingSecret(&'static str),
+    #[error("Invalid integration config: {0}")]
+    InvalidConfig(String),
+    #[error("Unexpected response from provider: {0}")]
+    InvalidResponse(String),
+    #[error("Integration {0} is disabled")]
+    Disabled(Uuid),
+    #[error("Invalid webhook signature")]
+    InvalidSignature,
+    #[error("Invalid webhook payload: {0}")]
+    InvalidPayload(String),
+    #[error("Sync plan {0} has already been applied or discarded")]
+    PlanNotPending(Uuid),
+    #[error("Sync conflict {0} has already been resolved")]
+    ConflictResolved(Uuid),
+    #[error("Could not write the task: {0}")]
+    TaskWrite(String),
+}
+
+/// A remote issue normalized into the fields VK tracks.
+#[derive(Debug, Clone)]
+pub struct RemoteIssue {
+    pub external_id: String,
+    pub url: Option<String>,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    /// The workflow state as the provider names it, e.g. "Code Review". The task goes in
+    /// the project column of the same name when that column's category matches `status`
+    pub state: Option<String>,
+    /// `None` when the provider has no priority for the issue; the task's is kept
+    pub priority: Option<TaskPriority>,
+    pub assignee: Option<RemoteAssignee>,
+    /// Scalar custom field values by name, imported into the project's custom fields of
+    /// the same name
+    pub custom_fields: BTreeMap<String, String>,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// The provider payload the issue was parsed from
+    pub raw: Value,
+}
+
+/// The person a remote issue is assigned to, matched to a local user by email and then
+/// by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteAssignee {
+    pub name: String,
+    pub email: Option<String>,
+}
+
+impl RemoteAssignee {
+    /// Build from a single mapped value, treated as an email when it looks like one.
+    pub fn from_identifier(value: String) -> Self {
+        let email = value.contains('@').then(|| value.clone());
+        Self { name: value, email }
+    }
+
+    /// Stable identity recorded in the sync baseline.
+    fn key(&self) -> &str {
+        self.email.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// A comment on a remote issue, imported as a task comment.
+#[derive(Debug, Clone)]
+pub struct RemoteComment {
+    pub external_id: String,
+    pub author: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A file attached to a remote issue, imported as a task attachment.
+#[derive(Debug, Clone)]
+pub struct RemoteAttachment {
+    pub external_id: String,
+    pub filename: String,
+    pub content_type: Option<String>,
+    /// Reported size, used to skip oversized files before downloading them
+    pub size_bytes: Option<u64>,
+    pub url: String,
+}
+
+/// A link from a remote issue to another issue of the same tracker, imported as a task
+/// link once both issues have tasks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteIssueLink {
+    /// Relation of the issue to `external_id`
+    pub kind: TaskLinkKind,
+    pub external_id: String,
+}
+
+/// Result of a cheap authenticated request against the provider API.
+#[derive(Debug, Clone, Default)]
+pub struct ProviderProbe {
+    /// Credential expiry, when the provider reports one
+    pub token_expires_at: Option<DateTime<Utc>>,
+}
+
+#[async_trait]
+pub trait IssueProvider: Send + Sync {
+    /// Fetch the raw payload of every issue in scope for the integration's configured
+    /// project/query. Payloads are parsed one at a time so a bad item can be dead-lettered.
+    async fn fetch_issues(&self) -> Result<Vec<Value>, IntegrationServiceError>;
+
+    /// Normalize a single raw issue payload.
+    fn parse_issue(&self, raw: Value) -> Result<RemoteIssue, IntegrationServiceError>;
+
+    /// Check that the API is reachable and the credentials are accepted.
+    async fn probe(&self) -> Result<ProviderProbe, IntegrationServiceError>;
+
+    /// Check that the configured project, query or repository can be read. Providers whose
+    /// probe already reads it have nothing more to check.
+    async fn check_scope(&self) -> Result<(), IntegrationServiceError> {
+        Ok(())
+    }
+
+    /// Fetch the comments on an issue. Providers without comment import return none.
+    async fn fetch_comments(
+        &self,
+        _issue: &RemoteIssue,
+    ) -> Result<Vec<RemoteComment>, IntegrationServiceError> {
+        Ok(Vec::new())
+    }
+
+    /// Attachments listed in an issue payload. Providers without attachment import
+    /// return none.
+    fn attachments(&self, _issue: &RemoteIssue) -> Vec<RemoteAttachment> {
+        Vec::new()
+    }
+
+    /// Links to other issues listed in an issue payload. Providers without link import
+    /// return none.
+    fn links(&self, _issue: &RemoteIssue) -> Vec<RemoteIssueLink> {
+        Vec::new()
+    }
+
+    /// Download the content of an attachment returned by [`Self::attachments`].
+    async fn download_attachment(
+        &self,
+        attachment: &RemoteAttachment,
+    ) -> Result<Vec<u8>, IntegrationServiceError> {
+        Err(IntegrationServiceError::InvalidResponse(format!(
+            "attachment '{}' cannot be downloaded from this provider",
+            attachment.filename
+        )))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum IntegrationCapability {
+    /// Remote issues can be imported as tasks
+    Pull,
+    /// Task changes can be written back to the remote tracker
+    Push,
+    /// The provider can push issue events to the webhook gateway
+    Webhooks,
+}
+
+/// A setting a provider reads from an integration's `config` or `secrets`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct CatalogField {
+    pub key: String,
+    pub label: String,
+    pub required: bool,
+    pub description: String,
+}
+
+impl CatalogField {
+    fn new(key: &str, label: &str, required: bool, description: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            label: label.to_string(),
+            required,
+            description: description.to_string(),
+        }
+    }
+}
+
+/// Setup metadata for a provider type, used to build integration forms.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct ProviderCatalogEntry {
+    pub provider: IntegrationProvider,
+    pub display_name: String,
+    /// Hint for the `base_url` field
+    pub base_url_placeholder: String,
+    pub config: Vec<CatalogField>,
+    pub secrets: Vec<CatalogField>,
+    pub capabilities: Vec<IntegrationCapability>,
+}
+
+/// Counts of what a sync run did, stored as the job result.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, ToSchema)]
+pub struct SyncSummary {
+    pub fetched: usize,
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    /// Items that could not be imported and were dead-lettered
+    pub failed: usize,
+}
+
+impl SyncSummary {
+    fn count(&mut self, outcomes: &[Option<ApplyOutcome>]) {
+        for outcome in outcomes {
+            match outcome {
+                Some(ApplyOutcome::Created) => self.created += 1,
+                Some(ApplyOutcome::Updated) => self.updated += 1,
+                Some(ApplyOutcome::Unchanged) => self.unchanged += 1,
+                None => self.failed += 1,
+            }
+        }
+    }
+}
+
+/// What an inbound webhook delivery did to the linked task.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookOutcome {
+    Created,
+    Updated,
+    Unchanged,
+    Unlinked,
+    Ignored,
+}
+
+impl WebhookOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Updated => "updated",
+            Self::Unchanged => "unchanged",
+            Self::Unlinked => "unlinked",
+            Self::Ignored => "ignored",
+        }
+    }
+}
+
+/// Point-in-time health report for an integration.
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct IntegrationHealth {
+    pub integration_id: Uuid,
+    pub enabled: bool,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<DateTime<Utc>>,
+    /// From the provider when it reports one, otherwise the `token_expires_at` config value
+    pub token_expires_at: Option<DateTime<Utc>>,
+    /// Whether the remote API accepted an authenticated request; null when not probed
+    pub reachable: Option<bool>,
+    pub reachability_error: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct IntegrationService {
+    http: RateLimitedClient,
+    attachments: AttachmentService,
+}
+
+impl IntegrationService {
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+    pub fn new(attachments: AttachmentService) -> Self {
+        let http = Client::builder()
+            .timeout(Self::REQUEST_TIMEOUT)
+            .user_agent(concat!("vibe-kanban/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default();
+        // One limiter for every provider client, so concurrent syncs and health probes
+        // against the same host share a budget
+        Self {
+            http: RateLimitedClient::new(http, HostRateLimiter::default()),
+            attachments,
+        }
+    }
+
+    /// Every provider type the server can sync with.
+    pub fn catalog() -> Vec<ProviderCatalogEntry> {
+        vec![
+            youtrack::catalog_entry(),
+            jira::catalog_entry(),
+            github::catalog_entry(),
+        ]
+    }
+
+    pub fn provider_for(
+        &self,
+        integration: &Integration,
+    ) -> Result<Box<dyn IssueProvider>, IntegrationServiceError> {
+        let http = self.http.clone();
+        Ok(match integration.provider {
+            IntegrationProvider::YouTrack => {
+                Box::new(youtrack::YouTrackProvider::new(http, integration)?)
+            }
+            IntegrationProvider::Jira => Box::new(jira::JiraProvider::new(http, integration)?),
+            IntegrationProvider::GitHub => {
+                Box::new(github::GitHubProvider::new(http, integration)?)
+            }
+        })
+    }
+
+    /// Report sync history and probe the remote API. Disabled integrations are not probed.
+    pub async fn health(
+        &self,
+        pool: &SqlitePool,
+        integration: &Integration,
+    ) -> Result<IntegrationHealth, IntegrationServiceError> {
+        let last_success =
+            SyncJob::find_last_finished(pool, integration.id, SyncJobStatus::Succeeded).await?;
+        let last_failure =
+            SyncJob::find_last_finished(pool, integration.id, SyncJobStatus::Failed).await?;
+
+        let configured_expiry = integration
+            .config
+            .get("token_expires_at")
+            .and_then(Value::as_str)
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+
+        let probe = if integration.enabled {
+            let result = match self.provider_for(integration) {
+                Ok(provider) => provider.probe().await,
+                Err(e) => Err(e),
+            };
+            Some(result)
+        } else {
+            None
+        };
+
+        let (reachable, reachability_error, token_expires_at) = match probe {
+            Some(Ok(probe)) => (
+                Some(true),
+                None,
+                probe.token_expires_at.or(configured_expiry),
+            ),
+            Some(Err(e)) => (Some(false), Some(e.to_string()), configured_expiry),
+            None => (None, None, configured_expiry),
+        };
+
+        Ok(IntegrationHealth {
+            integration_id: integration.id,
+            enabled: integration.enabled,
+            last_success_at: last_success.and_then(|job| job.finished_at),
+            last_error: last_failure.as_ref().and_then(|job| job.error.clone()),
+            last_error_at: last_failure.and_then(|job| job.finished_at),
+            token_expires_at,
+            reachable,
+            reachability_error,
+            checked_at: Utc::now(),
+        })
+    }
+
+    /// Check the config, the credentials and then the configured scope, stopping at the
+    /// first failure. Unlike [`Self::health`] this also checks disabled integrations, so
+    /// they can be verified before they are enabled.
+    pub async fn check(&self, integration: &Integration) -> IntegrationCheck {
+        fn step(name: &str, result: Result<(), IntegrationServiceError>) -> IntegrationCheckStep {
+            IntegrationCheckStep {
+                name: name.to_string(),
+                passed: result.is_ok(),
+                error: result.err().map(|e| e.to_string()),
+            }
+        }
+
+        let mut steps = Vec::new();
+        match self.provider_for(integration) {
+            Err(e) => steps.push(step("config", Err(e))),
+            Ok(provider) => {
+                steps.push(step("config", Ok(())));
+                let credentials = provider.probe().await.map(|_| ());
+                let accepted = credentials.is_ok();
+                steps.push(step("credentials", credentials));
+                if accepted {
+                    steps.push(step("scope", provider.check_scope().await));
+                }
+            }
+        }
+
+        IntegrationCheck {
+            integration_id: integration.id,
+            name: integration.name.clone(),
+            provider: integration.provider,
+            enabled: integration.enabled,
+            passed: steps.iter().all(|step| step.passed),
+            steps,
+            checked_at: Utc::now(),
+        }
+    }
+
+    /// Pull every remote issue for the integration and create or update the linked tasks.
+    /// Every field change is written to the sync audit log under `run_id`.
+    #[tracing::instrument(
+        name = "integration.sync",
+        skip_all,
+        fields(integration_id = %integration.id, provider = %integration.provider)
+    )]
+    pub async fn sync(
+        &self,
+        pool: &SqlitePool,
+        integration: &Integration,
+        run_id: Option<Uuid>,
+    ) -> Result<SyncSummary, IntegrationServiceError> {
+        if !integration.enabled {
+            return Err(IntegrationServiceError::Disabled(integration.id));
+        }
+
+        let provider = self.provider_for(integration)?;
+        let payloads = provider.fetch_issues().await?;
+        let mut summary = SyncSummary {
+            fetched: payloads.len(),
+            ..Default::default()
+        };
+
+        let outcomes = self
+            .import_payloads(pool, integration, run_id, provider.as_ref(), payloads)
+            .await?;
+        summary.count(&outcomes);
+
+        tracing::info!(
+            integration_id = %integration.id,
+            provider = %integration.provider,
+            fetched = summary.fetched,
+            created = summary.created,
+            updated = summary.updated,
+            failed = summary.failed,
+            "integration sync finished"
+        );
+        Ok(summary)
+    }
+
+    /// Fetch remote issues and persist what a sync would change, without touching tasks.
+    pub async fn plan(
+        &self,
+        pool: &SqlitePool,
+        integration: &Integration,
+    ) -> Result<SyncPlan, IntegrationServiceError> {
+        if !integration.enabled {
+            return Err(IntegrationServiceError::Disabled(integration.id));
+        }
+
+        let provider = self.provider_for(integration)?;
+        let mut items = Vec::new();
+        for raw in provider.fetch_issues().await? {
+            let mut issue = match provider.parse_issue(raw) {
+                Ok(issue) => issue,
+                Err(e) => {
+                    tracing::warn!(
+                        integration_id = %integration.id,
+                        error = %e,
+                        "skipping unparseable issue in sync plan"
+                    );
+                    continue;
+                }
+            };
+            Self::map_issue(pool, integration, &mut issue).await?;
+            if let Some(item) = plan::plan_issue(pool, integration, &issue).await? {
+                items.push(item);
+            }
+        }
+
+        Ok(SyncPlan::create(pool, integration.id, &items).await?)
+    }
+
+    /// Apply a pending plan from its stored payloads, exactly as reviewed. Conflicting
+    /// fields go to the conflict ledger and failing items are dead-lettered like in a
+    /// regular run.
+    pub async fn apply_plan(
+        &self,
+        pool: &SqlitePool,
+        integration: &Integration,
+        plan: &SyncPlan,
+    ) -> Result<SyncSummary, IntegrationServiceError> {
+        if !integration.enabled {
+            return Err(IntegrationServiceError::Disabled(integration.id));
+        }
+        let provider = self.provider_for(integration)?;
+        if !SyncPlan::finish(pool, plan.id, SyncPlanStatus::Applied).await? {
+            return Err(IntegrationServiceError::PlanNotPending(plan.id));
+        }
+
+        let payloads: Vec<Value> = plan.items.iter().map(|item| item.payload.clone()).collect();
+        let mut summary = SyncSummary {
+            fetched: payloads.len(),
+            ..Default::default()
+        };
+        let outcomes = self
+            .import_payloads(pool, integration, None, provider.as_ref(), payloads)
+            .await?;
+        summary.count(&outcomes);
+        Ok(summary)
+    }
+
+    /// Import the remote comments of an issue onto its linked task.
+    async fn import_comments(
+        pool: &SqlitePool,
+        integration: &Integration,
+        provider: &dyn IssueProvider,
+        issue: &RemoteIssue,
+    ) -> Result<usize, IntegrationServiceError> {
+        let Some(link) =
+            IntegrationLink::find_by_external_id(pool, integration.id, &issue.external_id).await?
+        else {
+            return Ok(0);
+        };
+
+        let comments = provider.fetch_comments(issue).await?;
+        for comment in &comments {
+            TaskComment::upsert_imported(
+                pool,
+                link.task_id,
+                integration.id,
+                &comment.external_id,
+                &comment.author,
+                &comment.body,
+                comment.created_at,
+            )
+            .await?;
+        }
+        Ok(comments.len())
+    }
+
+    /// Download and store the attachments of an issue that were not imported before.
+    /// Attachments that are too large or of an unsupported type are skipped.
+    async fn import_attachments(
+        &self,
+        pool: &SqlitePool,
+        integration: &Integration,
+        provider: &dyn IssueProvider,
+        issue: &RemoteIssue,
+    ) -> Result<usize, IntegrationServiceError> {
+        let remote = provider.attachments(issue);
+        if remote.is_empty() {
+            return Ok(0);
+        }
+        let Some(link) =
+            IntegrationLink::find_by_external_id(pool, integration.id, &issue.external_id).await?
+        else {
+            return Ok(0);
+        };
+
+        let mut imported = 0;
+        for attachment in &remote {
+            if TaskAttachment::find_by_external_id(pool, integration.id, &attachment.external_id)
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+            if attachment
+                .size_bytes
+                .is_some_and(|size| size > self.attachments.max_size_bytes())
+            {
+                tracing::debug!(
+                    external_id = %issue.external_id,
+                    attachment = %attachment.filename,
+                    "skipping oversized remote attachment"
+                );
+                continue;
+            }
+
+            let data = provider.download_attachment(attachment).await?;
+            let source = AttachmentSource {
+                integration_id: integration.id,
+                external_id: &attachment.external_id,
+            };
+            match self
+                .attachments
+                .store_attachment(
+                    link.task_id,
+                    &data,
+                    &attachment.filename,
+                    attachment.content_type.as_deref(),
+                    Some(source),
+                )
+                .await
+            {
+                Ok(_) => imported += 1,
+                Err(e @ (AttachmentError::UnsupportedType(_) | AttachmentError::TooLarge(..))) => {
+                    tracing::debug!(
+                        external_id = %issue.external_id,
+                        attachment = %attachment.filename,
+                        error = %e,
+                        "skipping remote attachment"
+                    );
+                }
+                Err(e) => {
+                    return Err(IntegrationServiceError::InvalidResponse(format!(
+                        "failed to store attachment '{}': {e}",
+                        attachment.filename
+                    )));
+                }
+            }
+        }
+        Ok(imported)
+    }
+
+    /// Link the issue's task to the tasks of the issues it links to. Issues not imported
+    /// yet are skipped; both sides list the link, so it is made when the other one is.
+    /// Links that exist already or contradict existing ones are skipped too.
+    async fn import_links(
+        pool: &SqlitePool,
+        integration: &Integration,
+        provider: &dyn IssueProvider,
+        issue: &RemoteIssue,
+    ) -> Result<usize, IntegrationServiceError> {
+        let remote = provider.links(issue);
+        if remote.is_empty() {
+            return Ok(0);
+        }
+        let Some(link) =
+            IntegrationLink::find_by_external_id(pool, integration.id, &issue.external_id).await?
+        else {
+            return Ok(0);
+        };
+
+        let mut imported = 0;
+        for remote_link in &remote {
+            let Some(other) = IntegrationLink::find_by_external_id(
+                pool,
+                integration.id,
+                &remote_link.external_id,
+            )
+            .await?
+            else {
+                continue;
+            };
+            if other.task_id == link.task_id {
+                continue;
+            }
+            let (source, target, kind) =
+                TaskLink::orient(link.task_id, other.task_id, remote_link.kind);
+            if TaskLink::find_conflict(pool, source, target, kind)
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+            TaskLink::create(pool, link.task_id, other.task_id, remote_link.kind).await?;
+            imported += 1;
+        }
+        Ok(imported)
+    }
+
+    /// Map a parsed issue onto the project: its state mappings first, then the
+    /// integration's field mapping, which can still override the status.
+    async fn map_issue(
+        pool: &SqlitePool,
+        integration: &Integration,
+        issue: &mut RemoteIssue,
+    ) -> Result<(), sqlx::Error> {
+        let settings = ProjectSettings::find(pool, integration.project_id).await?;
+        mapping::apply_states(&settings, issue);
+        mapping::apply(&integration.field_mapping, issue);
+        Ok(())
+    }
+
+    /// Parse, map and apply a run's raw issue payloads, writing their tasks in bulk. A
+    /// failing item is dead-lettered with its payload and reported as `None` so the run
+    /// can continue.
+    #[tracing::instrument(name = "integration.import", skip_all, fields(items = payloads.len()))]
+    async fn import_payloads(
+        &self,
+        pool: &SqlitePool,
+        integration: &Integration,
+        run_id: Option<Uuid>,
+        provider: &dyn IssueProvider,
+        payloads: Vec<Value>,
+    ) -> Result<Vec<Option<ApplyOutcome>>, IntegrationServiceError> {
+        let settings = ProjectSettings::find(pool, integration.project_id).await?;
+        let mut outcomes = Vec::with_capacity(payloads.len());
+        let mut prepared = Vec::with_capacity(payloads.len());
+        for raw in payloads {
+            let mut issue = match provider.parse_issue(raw.clone()) {
+                Ok(issue) => issue,
+                Err(e) => {
+                    Self::dead_letter(pool, integration, run_id, None, &raw, &e).await?;
+                    outcomes.push(None);
+                    continue;
+                }
+            };
+            mapping::apply_states(&settings, &mut issue);
+            mapping::apply(&integration.field_mapping, &mut issue);
+            let external_id = issue.external_id.clone();
+            match Self::prepare_issue(pool, integration, issue).await {
+                Ok(issue) => prepared.push((issue, raw)),
+                Err(e) => {
+                    let e: IntegrationServiceError = e.into();
+                    Self::dead_letter(pool, integration, run_id, Some(&external_id), &raw, &e)
+                        .await?;
+                    outcomes.push(None);
+                }
+            }
+        }
+
+        let issues: Vec<&PreparedIssue> = prepared.iter().map(|(issue, _)| issue).collect();
+        let written = Self::write_tasks(pool, integration, &issues).await;
+        for ((prepared, raw), written) in prepared.iter().zip(written) {
+            let issue = &prepared.issue;
+            let result = match written {
+                Ok((task, outcome)) => {
+                    Self::finish_issue(pool, integration, run_id, prepared, task, outcome).await
+                }
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(outcome) => {
+                    SyncDeadLetter::resolve(pool, integration.id, &issue.external_id).await?;
+                    self.import_extras(pool, integration, provider, issue).await;
+                    outcomes.push(Some(outcome));
+                }
+                Err(e) => {
+                    let external_id = Some(issue.external_id.as_str());
+                    Self::dead_letter(pool, integration, run_id, external_id, raw, &e).await?;
+                    outcomes.push(None);
+                }
+            }
+        }
+        Ok(outcomes)
+    }
+
+    /// Import an applied issue's comments, attachments and links. They are secondary;
+    /// failing to import them does not fail the issue.
+    async fn import_extras(
+        &self,
+        pool: &SqlitePool,
+        integration: &Integration,
+        provider: &dyn IssueProvider,
+        issue: &RemoteIssue,
+    ) {
+        if let Err(e) = Self::import_comments(pool, integration, provider, issue).await {
+            tracing::warn!(
+                integration_id = %integration.id,
+                external_id = %issue.external_id,
+                error = %e,
+                "failed to import issue comments"
+            );
+        }
+        if let Err(e) = self
+            .import_attachments(pool, integration, provider, issue)
+            .await
+        {
+            tracing::warn!(
+                integration_id = %integration.id,
+                external_id = %issue.external_id,
+                error = %e,
+                "failed to import issue attachments"
+            );
+        }
+        if let Err(e) = Self::import_links(pool, integration, provider, issue).await {
+            tracing::warn!(
+                integration_id = %integration.id,
+                external_id = %issue.external_id,
+                error = %e,
+                "failed to import issue links"
+            );
+        }
+    }
+
+    async fn dead_letter(
+        pool: &SqlitePool,
+        integration: &Integration,
+        run_id: Option<Uuid>,
+        external_id: Option<&str>,
+        raw: &Value,
+        error: &IntegrationServiceError,
+    ) -> Result<(), IntegrationServiceError> {
+        tracing::warn!(
+            integration_id = %integration.id,
+            external_id = external_id.unwrap_or("<unknown>"),
+            %error,
+            "failed to import remote issue; dead-lettering"
+        );
+        SyncDeadLetter::record(
+            pool,
+            integration.id,
+            run_id,
+            external_id,
+            raw,
+            &error.to_string(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Replay a dead-lettered payload. Returns `None` once the item imports, or the dead
+    /// letter with the new error when it fails again.
+    pub async fn retry_dead_letter(
+        &self,
+        pool: &SqlitePool,
+        integration: &Integration,
+        dead_letter: &SyncDeadLetter,
+    ) -> Result<Option<SyncDeadLetter>, IntegrationServiceError> {
+        let provider = self.provider_for(integration)?;
+        let result = match provider.parse_issue(dead_letter.payload.0.clone()) {
+            Ok(mut issue) => {
+                Self::map_issue(pool, integration, &mut issue).await?;
+                Self::apply_issue(pool, integration, None, &issue).await
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(_) => {
+                SyncDeadLetter::delete(pool, dead_letter.id).await?;
+                Ok(None)
+            }
+            Err(e) => Ok(Some(
+                SyncDeadLetter::record_retry_failure(pool, dead_letter.id, &e.to_string()).await?,
+            )),
+        }
+    }
+
+    /// Verify and apply a single webhook delivery for the integration.
+    pub async fn handle_webhook(
+        &self,
+        pool: &SqlitePool,
+        integration: &Integration,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<WebhookOutcome, IntegrationServiceError> {
+        if !integration.enabled {
+            return Err(IntegrationServiceError::Disabled(integration.id));
+        }
+        let secret = integration
+            .secret(WEBHOOK_SECRET_KEY)
+            .ok_or(IntegrationServiceError::MissingSecret(WEBHOOK_SECRET_KEY))?;
+        if !webhooks::verify_request(integration.provider, secret, headers, body) {
+            return Err(IntegrationServiceError::InvalidSignature);
+        }
+
+        match webhooks::parse_event(integration, headers, body)? {
+            WebhookEvent::IssueChanged(mut issue) => {
+                Self::map_issue(pool, integration, &mut issue).await?;
+                let outcome = Self::apply_issue(pool, integration, None, &issue).await?;
+                Ok(match outcome {
+                    ApplyOutcome::Created => WebhookOutcome::Created,
+                    ApplyOutcome::Updated => WebhookOutcome::Updated,
+                    ApplyOutcome::Unchanged => WebhookOutcome::Unchanged,
+                })
+            }
+            WebhookEvent::IssueDeleted { external_id } => {
+                Self::unlink_issue(pool, integration, &external_id).await
+            }
+            WebhookEvent::Ignored(reason) => {
+                tracing::debug!(
+                    integration_id = %integration.id,
+                    %reason,
+                    "ignoring webhook delivery"
+                );
+                Ok(WebhookOutcome::Ignored)
+            }
+        }
+    }
+
+    /// Drop the link to a remotely deleted issue. The local task is kept.
+    async fn unlink_issue(
+        pool: &SqlitePool,
+        integration: &Integration,
+        external_id: &str,
+    ) -> Result<WebhookOutcome, IntegrationServiceError> {
+        let Some(link) =
+            IntegrationLink::find_by_external_id(pool, integration.id, external_id).await?
+        else {
+            return Ok(WebhookOutcome::Ignored);
+        };
+
+        IntegrationLink::delete(pool, link.id).await?;
+        SyncAuditEntry::create(
+            pool,
+            &CreateSyncAuditEntry {
+                integration_id: integration.id,
+                run_id: None,
+                task_id: link.task_id,
+                provider: integration.provider,
+                external_id: external_id.to_string(),
+                field: "external_id".to_string(),
+                old_value: Some(external_id.to_string()),
+                new_value: None,
+            },
+        )
+        .await?;
+        Ok(WebhookOutcome::Unlinked)
+    }
+
+    /// Resolve an open conflict by keeping the local values or taking the remote ones.
+    /// Either way the remote values become the new baseline for those fields, so the
+    /// same edits are not reported again on the next sync.
+    pub async fn resolve_conflict(
+        &self,
+        pool: &SqlitePool,
+        integration: &Integration,
+        conflict: &SyncConflict,
+        resolution: ConflictResolution,
+    ) -> Result<SyncConflict, IntegrationServiceError> {
+        let Some(resolved) = SyncConflict::resolve(pool, conflict.id, resolution).await? else {
+            return Err(IntegrationServiceError::ConflictResolved(conflict.id));
+        };
+
+        if resolution == ConflictResolution::Remote
+            && let Some(task) = Task::find_by_id(pool, conflict.task_id).await?
+        {
+            let mut fields = TaskFields::from(&task);
+            for field in conflict.fields.iter() {
+                fields.set(&field.field, field.remote_value.clone());
+            }
+            let updated = fields.save(pool, &task).await?;
+            TaskEvent::record_changes(
+                pool,
+                &task,
+                &updated,
+                TaskEventSource::Sync,
+                Some(integration.id),
+            )
+            .await?;
+
+            for field in conflict.fields.iter() {
+                SyncAuditEntry::create(
+                    pool,
+                    &CreateSyncAuditEntry {
+                        integration_id: integration.id,
+                        run_id: None,
+                        task_id: task.id,
+                        provider: integration.provider,
+                        external_id: conflict.external_id.clone(),
+                        field: field.field.clone(),
+                        old_value: field.local_value.clone(),
+                        new_value: field.remote_value.clone(),
+                    },
+                )
+                .await?;
+            }
+        }
+
+        if let Some(link) =
+            IntegrationLink::find_by_external_id(pool, integration.id, &conflict.external_id)
+                .await?
+        {
+            let mut baseline = link.remote_values.map(|v| v.0).unwrap_or_default();
+            for field in conflict.fields.iter() {
+                baseline.insert(field.field.clone(), field.remote_value.clone());
+            }
+            IntegrationLink::update_remote_values(pool, link.id, &baseline).await?;
+        }
+
+        Ok(resolved)
+    }
+
+    /// Apply a remote issue to its linked task. Fields edited on both sides since the last
+    /// sync are left untouched and recorded in the conflict ledger.
+    async fn apply_issue(
+        pool: &SqlitePool,
+        integration: &Integration,
+        run_id: Option<Uuid>,
+        issue: &RemoteIssue,
+    ) -> Result<ApplyOutcome, IntegrationServiceError> {
+        let prepared = Self::prepare_issue(pool, integration, issue.clone()).await?;
+        let written = Self::write_tasks(pool, integration, &[&prepared])
+            .await
+            .pop()
+            .unwrap_or(Err(sqlx::Error::RowNotFound.into()));
+        let (task, outcome) = written?;
+        Self::finish_issue(pool, integration, run_id, &prepared, task, outcome).await
+    }
+
+    /// Work out which of the issue's fields change on its linked task, and which conflict
+    /// with local edits, before anything is written.
+    async fn prepare_issue(
+        pool: &SqlitePool,
+        integration: &Integration,
+        issue: RemoteIssue,
+    ) -> Result<PreparedIssue, sqlx::Error> {
+        let link =
+            IntegrationLink::find_by_external_id(pool, integration.id, &issue.external_id).await?;
+        let before = match &link {
+            Some(link) => Task::find_by_id(pool, link.task_id).await?,
+            None => None,
+        };
+        let baseline = link.and_then(|link| link.remote_values).map(|v| v.0);
+
+        let (changes, conflicts) = match &before {
+            Some(_) => reconcile(field_changes(before.as_ref(), &issue), baseline.as_ref()),
+            None => (field_changes(None, &issue), Vec::new()),
+        };
+        Ok(PreparedIssue {
+            issue,
+            before,
+            baseline,
+            changes,
+            conflicts,
+        })
+    }
+
+    /// Create or update the tasks of prepared issues with one bulk upsert, returning each
+    /// issue's task and what happened to it, in order. When the bulk write fails, the
+    /// tasks are written one at a time so only the failing ones fail.
+    #[tracing::instrument(name = "integration.write_tasks", skip_all, fields(tasks = issues.len()))]
+    async fn write_tasks(
+        pool: &SqlitePool,
+        integration: &Integration,
+        issues: &[&PreparedIssue],
+    ) -> Vec<Result<(Task, ApplyOutcome), IntegrationServiceError>> {
+        let rows: Vec<ExternalTaskUpsert> =
+            issues.iter().filter_map(|issue| issue.upsert()).collect();
+        let upsert = |rows: Vec<ExternalTaskUpsert>| {
+            Task::bulk_upsert_by_external_id(pool, integration.id, integration.project_id, &rows)
+        };
+        let mut written: HashMap<String, Result<UpsertedTask, String>> = HashMap::new();
+        match upsert(rows.clone()).await {
+            Ok(upserted) => {
+                for task in upserted {
+                    written.insert(task.external_id.clone(), Ok(task));
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    integration_id = %integration.id,
+                    error = %e,
+                    "bulk task upsert failed; writing tasks one at a time"
+                );
+                for row in rows {
+                    let external_id = row.external_id.clone();
+                    let result = match upsert(vec![row]).await {
+                        Ok(mut upserted) => upserted.pop().ok_or_else(|| {
+                            "the linked task belongs to another project".to_string()
+                        }),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    written.insert(external_id, result);
+                }
+            }
+        }
+
+        issues
+            .iter()
+            .map(|issue| match (&issue.before, issue.upsert().is_some()) {
+                (Some(task), false) => Ok((task.clone(), ApplyOutcome::Unchanged)),
+                _ => match written.get(&issue.issue.external_id) {
+                    Some(Ok(upserted)) if upserted.created => {
+                        Ok((upserted.task.clone(), ApplyOutcome::Created))
+                    }
+                    Some(Ok(upserted)) => Ok((upserted.task.clone(), ApplyOutcome::Updated)),
+                    Some(Err(message)) => Err(IntegrationServiceError::TaskWrite(message.clone())),
+                    None => Err(IntegrationServiceError::TaskWrite(
+                        "the linked task belongs to another project".to_string(),
+                    )),
+                },
+            })
+            .collect()
+    }
+
+    /// Everything after the task's own fields are written: custom fields, assignee, column,
+    /// task events, the link's new baseline, the audit log and conflicts.
+    async fn finish_issue(
+        pool: &SqlitePool,
+        integration: &Integration,
+        run_id: Option<Uuid>,
+        prepared: &PreparedIssue,
+        task: Task,
+        outcome: ApplyOutcome,
+    ) -> Result<ApplyOutcome, IntegrationServiceError> {
+        let PreparedIssue {
+            issue,
+            before,
+            baseline,
+            changes,
+            conflicts,
+        } = prepared;
+        let custom_fields_changed =
+            Self::import_custom_fields(pool, integration, run_id, &task, issue, baseline.as_ref())
+                .await?;
+        let assigned =
+            Self::import_assignee(pool, integration, run_id, &task, issue, baseline.as_ref())
+                .await?;
+        let (task, outcome) = match (assigned, outcome) {
+            (Some(task), ApplyOutcome::Unchanged) => (task, ApplyOutcome::Updated),
+            (Some(task), outcome) => (task, outcome),
+            (None, ApplyOutcome::Unchanged) if custom_fields_changed => {
+                (task, ApplyOutcome::Updated)
+            }
+            (None, outcome) => (task, outcome),
+        };
+        let (task, outcome) = match (Self::place_in_column(pool, &task, issue).await?, outcome) {
+            (Some(task), ApplyOutcome::Unchanged) => (task, ApplyOutcome::Updated),
+            (Some(task), outcome) => (task, outcome),
+            (None, outcome) => (task, outcome),
+        };
+        match before {
+            Some(before) => {
+                TaskEvent::record_changes(
+                    pool,
+                    before,
+                    &task,
+                    TaskEventSource::Sync,
+                    Some(integration.id),
+                )
+                .await?
+            }
+            None => {
+                TaskEvent::record_created(pool, &task, TaskEventSource::Sync, Some(integration.id))
+                    .await?
+            }
+        }
+
+        // Conflicting fields keep their old base until the conflict is resolved, so the
+        // local edit is still recognized as one on the next sync
+        let mut baseline = remote_values(issue);
+        for conflict in conflicts {
+            baseline.insert(conflict.field.clone(), conflict.base_value.clone());
+        }
+        IntegrationLink::upsert(
+            pool,
+            integration.id,
+            task.id,
+            &issue.external_id,
+            issue.url.as_deref(),
+            issue.updated_at,
+            &baseline,
+        )
+        .await?;
+
+        for (field, old_value, new_value) in changes.iter().cloned() {
+            SyncAuditEntry::create(
+                pool,
+                &CreateSyncAuditEntry {
+                    integration_id: integration.id,
+                    run_id,
+                    task_id: task.id,
+                    provider: integration.provider,
+                    external_id: issue.external_id.clone(),
+                    field: field.to_string(),
+                    old_value,
+                    new_value,
+                },
+            )
+            .await?;
+        }
+
+        if !conflicts.is_empty() {
+            tracing::info!(
+                integration_id = %integration.id,
+                external_id = %issue.external_id,
+                fields = conflicts.len(),
+                "recorded sync conflict"
+            );
+            SyncConflict::record(pool, integration.id, task.id, &issue.external_id, conflicts)
+                .await?;
+        }
+
+        Ok(outcome)
+    }
+
+    /// Assign the task to the local user matching the issue's assignee. Only an unassigned
+    /// task is filled in, and only when the remote assignee changed since the last sync,
+    /// so local assignments and unassignments are never overwritten. Unknown assignees are
+    /// skipped rather than creating users.
+    async fn import_assignee(
+        pool: &SqlitePool,
+        integration: &Integration,
+        run_id: Option<Uuid>,
+        task: &Task,
+        issue: &RemoteIssue,
+        baseline: Option<&RemoteValues>,
+    ) -> Result<Option<Task>, IntegrationServiceError> {
+        let Some(assignee) = &issue.assignee else {
+            return Ok(None);
+        };
+        let unchanged = baseline
+            .and_then(|baseline| baseline.get("assignee"))
+            .is_some_and(|base| base.as_deref() == Some(assignee.key()));
+        if task.assignee_id.is_some() || unchanged {
+            return Ok(None);
+        }
+
+        let user = match &assignee.email {
+            Some(email) => User::find_by_email(pool, email).await?,
+            None => None,
+        };
+        let user = match user {
+            Some(user) => user,
+            None => match User::find_by_unique_name(pool, &assignee.name).await? {
+                Some(user) => user,
+                None => {
+                    tracing::debug!(
+                        external_id = %issue.external_id,
+                        assignee = %assignee.key(),
+                        "no local user matches remote assignee"
+                    );
+                    return Ok(None);
+                }
+            },
+        };
+
+        let task = Task::update_assignee(pool, task.id, Some(user.id)).await?;
+        SyncAuditEntry::create(
+            pool,
+            &CreateSyncAuditEntry {
+                integration_id: integration.id,
+                run_id,
+                task_id: task.id,
+                provider: integration.provider,
+                external_id: issue.external_id.clone(),
+                field: "assignee".to_string(),
+                old_value: None,
+                new_value: Some(user.name),
+            },
+        )
+        .await?;
+        Ok(Some(task))
+    }
+
+    /// Put the task in the project column named after the issue's workflow state. The
+    /// status is left to the regular field sync, so only a column of the task's current
+    /// status is used.
+    async fn place_in_column(
+        pool: &SqlitePool,
+        task: &Task,
+        issue: &RemoteIssue,
+    ) -> Result<Option<Task>, IntegrationServiceError> {
+        let Some(state) = issue.state.as_deref() else {
+            return Ok(None);
+        };
+        let columns = ProjectColumn::find_by_project_id(pool, task.project_id).await?;
+        match ProjectColumn::for_state(&columns, state) {
+            Some(column) if column.category == task.status && task.column_id != Some(column.id) => {
+                let task =
+                    Task::move_to_column(pool, task.id, column.id, column.category.clone()).await?;
+                Ok(Some(task))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Copy the issue's custom field values into the project's custom fields of the same
+    /// name, ignoring case. A value is only written when it changed remotely since the
+    /// last sync, so local edits stand until the remote value changes again. Values that
+    /// do not fit the local field's type are skipped. Returns whether any value changed.
+    async fn import_custom_fields(
+        pool: &SqlitePool,
+        integration: &Integration,
+        run_id: Option<Uuid>,
+        task: &Task,
+        issue: &RemoteIssue,
+        baseline: Option<&RemoteValues>,
+    ) -> Result<bool, IntegrationServiceError> {
+        if issue.custom_fields.is_empty() {
+            return Ok(false);
+        }
+        let fields = CustomField::find_by_project_id(pool, task.project_id).await?;
+        let current = CustomField::find_values_for_task(pool, task.id).await?;
+
+        let mut changed = false;
+        for (name, remote) in &issue.custom_fields {
+            let Some(field) = fields.iter().find(|f| f.name.eq_ignore_ascii_case(name)) else {
+                continue;
+            };
+            let key = custom_field_key(name);
+            let unchanged = baseline
+                .and_then(|baseline| baseline.get(&key))
+                .is_some_and(|base| base.as_deref() == Some(remote.as_str()));
+            if unchanged {
+                continue;
+            }
+            let value = match field.normalize_value(remote) {
+                Ok(value) => value,
+                Err(reason) => {
+                    tracing::debug!(
+                        external_id = %issue.external_id,
+                        field = %field.name,
+                        "skipping remote custom field value: {reason}"
+                    );
+                    continue;
+                }
+            };
+            let old_value = current
+                .iter()
+                .find(|v| v.field_id == field.id)
+                .map(|v| v.value.clone());
+            if old_value.as_deref() == Some(value.as_str()) {
+                continue;
+            }
+
+            CustomField::set_value(pool, task.id, field.id, &value).await?;
+            SyncAuditEntry::create(
+                pool,
+                &CreateSyncAuditEntry {
+                    integration_id: integration.id,
+                    run_id,
+                    task_id: task.id,
+                    provider: integration.provider,
+                    external_id: issue.external_id.clone(),
+                    field: key,
+                    old_value,
+                    new_value: Some(value),
+                },
+            )
+            .await?;
+            changed = true;
+        }
+        Ok(changed)
+    }
+}
+
+/// Sync baseline key of a remote custom field.
+fn custom_field_key(name: &str) -> String {
+    format!("custom:{name}")
+}
+
+type FieldChange = (&'static str, Option<String>, Option<String>);
+
+/// The issue's values for every field the sync tracks.
+fn remote_values(issue: &RemoteIssue) -> RemoteValues {
+    let mut values = RemoteValues::from([
+        ("title".to_string(), Some(issue.title.clone())),
+        ("description".to_string(), issue.description.clone()),
+        ("status".to_string(), Some(issue.status.to_string())),
+    ]);
+    if let Some(priority) = issue.priority {
+        values.insert("priority".to_string(), Some(priority.to_string()));
+    }
+    if let Some(assignee) = &issue.assignee {
+        values.insert("assignee".to_string(), Some(assignee.key().to_string()));
+    }
+    for (name, value) in &issue.custom_fields {
+        values.insert(custom_field_key(name), Some(value.clone()));
+    }
+    values
+}
+
+/// Fields the issue would change on the task, or every populated field for a new task.
+fn field_changes(task: Option<&Task>, issue: &RemoteIssue) -> Vec<FieldChange> {
+    let (title, description, status, priority) = match task {
+        Some(task) => (
+            Some(task.title.clone()),
+            task.description.clone(),
+            Some(task.status.to_string()),
+            Some(task.priority.to_string()),
+        ),
+        None => (None, None, None, None),
+    };
+    let mut candidates = vec![
+        ("title", title, Some(issue.title.clone())),
+        ("description", description, issue.description.clone()),
+        ("status", status, Some(issue.status.to_string())),
+    ];
+    if let Some(remote) = issue.priority {
+        candidates.push(("priority", priority, Some(remote.to_string())));
+    }
+    candidates
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .collect()
+}
+
+/// Three-way merge of field changes against the remote values of the last sync. A change
+/// is applied when the task still holds the base value, dropped when only the task was
+/// edited, and reported as a conflict when both sides moved away from the base. Without
+/// a baseline every change is applied.
+fn reconcile(
+    changes: Vec<FieldChange>,
+    baseline: Option<&RemoteValues>,
+) -> (Vec<FieldChange>, Vec<ConflictField>) {
+    let Some(baseline) = baseline else {
+        return (changes, Vec::new());
+    };
+
+    let mut apply = Vec::new();
+    let mut conflicts = Vec::new();
+    for (field, local, remote) in changes {
+        let Some(base) = baseline.get(field) else {
+            apply.push((field, local, remote));
+            continue;
+        };
+        if *base == local {
+            apply.push((field, local, remote));
+        } else if *base != remote {
+            conflicts.push(ConflictField {
+                field: field.to_string(),
+                base_value: base.clone(),
+                local_value: local,
+                remote_value: remote,
+            });
+        }
+    }
+    (apply, conflicts)
+}
+
+/// A remote issue with its linked task as it was before the sync, and the fields the
+/// sync changes or leaves in conflict.
+struct PreparedIssue {
+    issue: RemoteIssue,
+    before: Option<Task>,
+    baseline: Option<RemoteValues>,
+    changes: Vec<FieldChange>,
+    conflicts: Vec<ConflictField>,
+}
+
+impl PreparedIssue {
+    /// The row to upsert for the issue's task; `None` when a linked task has nothing to
+    /// change.
+    fn upsert(&self) -> Option<ExternalTaskUpsert> {
+        let fields = match &self.before {
+            Some(_) if self.changes.is_empty() => return None,
+            Some(task) => {
+                let mut fields = TaskFields::from(task);
+                for (field, _, value) in &self.changes {
+                    fields.set(field, value.clone());
+                }
+                fields
+            }
+            None => TaskFields {
+                title: self.issue.title.clone(),
+                description: self.issue.description.clone(),
+                status: self.issue.status.clone(),
+                priority: self.issue.priority.unwrap_or_default(),
+            },
+        };
+        Some(ExternalTaskUpsert {
+            external_id: self.issue.external_id.clone(),
+            title: fields.title,
+            description: fields.description,
+            status: fields.status,
+            priority: fields.priority,
+        })
+    }
+}
+
+/// The synced fields of a task, edited in place before a single `Task::update`.
+struct TaskFields {
+    title: String,
+    description: Option<String>,
+    status: TaskStatus,
+    priority: TaskPriority,
+}
+
+impl From<&Task> for TaskFields {
+    fn from(task: &Task) -> Self {
+        Self {
+            title: task.title.clone(),
+            description: task.description.clone(),
+            status: task.status.clone(),
+            priority: task.priority,
+        }
+    }
+}
+
+impl TaskFields {
+    fn set(&mut self, field: &str, value: Option<String>) {
+        match field {
+            "title" => {
+                if let Some(title) = value {
+                    self.title = title;
+                }
+            }
+            "description" => self.description = value,
+            "status" => {
+                if let Some(status) = value.and_then(|v| v.parse().ok()) {
+                    self.status = status;
+                }
+            }
+            "priority" => {
+                if let Some(priority) = value.and_then(|v| v.parse().ok()) {
+                    self.priority = priority;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    async fn save(self, pool: &SqlitePool, task: &Task) -> Result<Task, sqlx::Error> {
+        let priority = self.priority;
+        let updated = Task::update(
+            pool,
+            task.id,
+            task.project_id,
+            self.title,
+            self.description,
+            self.status,
+            task.parent_workspace_id,
+        )
+        .await?;
+        if priority == updated.priority {
+            return Ok(updated);
+        }
+        Task::update_priority(pool, task.id, priority).await
+    }
+}
+
+enum ApplyOutcome {
+    Created,
+    Updated,
+    Unchanged,
+}
+
+/// Translate a tracker's priority name (Jira's Highest..Lowest, YouTrack's
+/// Show-stopper..Minor) into a task priority.
+fn priority_from_name(name: &str) -> Option<TaskPriority> {
+    match name.trim().to_lowercase().as_str() {
+        "urgent" | "highest" | "blocker" | "critical" | "show-stopper" => {
+            Some(TaskPriority::Urgent)
+        }
+        "high" | "major" => Some(TaskPriority::High),
+        "medium" | "normal" => Some(TaskPriority::Medium),
+        "low" | "lowest" | "minor" | "trivial" => Some(TaskPriority::Low),
+        _ => None,
+    }
+}
+
+/// Read a required string setting from the integration's config object.
+fn config_str<'a>(
+    integration: &'a Integration,
+    key: &str,
+) -> Result<&'a str, IntegrationServiceError> {
+    integration
+        .config
+        .get(key)
+        .and_then(Value::as_str)
+        .filter(|s| !s.trim().is_empty())
+        .ok_or_else(|| IntegrationServiceError::InvalidConfig(format!("'{key}' is required")))
+}
+
+fn base_url(integration: &Integration) -> &str {
+    integration.base_url.trim_end_matches('/')
+}
+
+async fn error_for_status(
+    provider: IntegrationProvider,
+    response: reqwest::Response,
+) -> Result<reqwest::Response, IntegrationServiceError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(IntegrationServiceError::Http {
+        provider,
+        status: status.as_u16(),
+        body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn baseline(title: &str, status: &str) -> RemoteValues {
+        RemoteValues::from([
+            ("title".to_string(), Some(title.to_string())),
+            ("status".to_string(), Some(status.to_string())),
+        ])
+    }
+
+    fn change(field: &'static str, local: &str, remote: &str) -> FieldChange {
+        (field, Some(local.to_string()), Some(remote.to_string()))
+    }
+
+    #[test]
+    fn test_reconcile_applies_remote_only_edits() {
+        let base = baseline("Old", "todo");
+        let (apply, conflicts) = reconcile(vec![change("title", "Old", "New")], Some(&base));
+        assert_eq!(apply, vec![change("title", "Old", "New")]);
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_reconcile_keeps_local_only_edits() {
+        let base = baseline("Old", "todo");
+        let (apply, conflicts) = reconcile(vec![change("title", "Local", "Old")], Some(&base));
+        assert!(apply.is_empty());
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_reconcile_reports_edits_on_both_sides() {
+        let base = baseline("Old", "todo");
+        let (apply, conflicts) = reconcile(
+            vec![
+                change("title", "Local", "Remote"),
+                change("status", "todo", "done"),
+            ],
+            Some(&base),
+        );
+        assert_eq!(apply, vec![change("status", "todo", "done")]);
+        assert_eq!(
+            conflicts,
+            vec![ConflictField {
+                field: "title".to_string(),
+                base_value: Some("Old".to_string()),
+                local_value: Some("Local".to_string()),
+                remote_value: Some("Remote".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_priority_from_name() {
+        assert_eq!(priority_from_name("Highest"), Some(TaskPriority::Urgent));
+        assert_eq!(
+            priority_from_name("Show-stopper"),
+            Some(TaskPriority::Urgent)
+        );
+        assert_eq!(priority_from_name(" Major "), Some(TaskPriority::High));
+        assert_eq!(priority_from_name("Normal"), Some(TaskPriority::Medium));
+        assert_eq!(priority_from_name("Trivial"), Some(TaskPriority::Low));
+        assert_eq!(priority_from_name("P3"), None);
+    }
+
+    #[test]
+    fn test_reconcile_without_baseline_applies_everything() {
+        let changes = vec![change("title", "Local", "Remote")];
+        let (apply, conflicts) = reconcile(changes.clone(), None);
+        assert_eq!(apply, changes);
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_youtrack_custom_field_values() {
+        let raw = serde_json::json!({
+            "idReadable": "VK-7",
+            "summary": "Crash on start",
+            "customFields": [
+                { "$type": "SingleEnumIssueCustomField", "name": "Component", "value": { "name": "Backend" } },
+                { "$type": "DateIssueCustomField", "name": "Target", "value": 1767744000000i64 },
+                { "$type": "SimpleIssueCustomField", "name": "Points", "value": 3 },
+                { "$type": "MultiEnumIssueCustomField", "name": "Tags", "value": [{ "name": "a" }] },
+                { "$type": "SingleEnumIssueCustomField", "name": "Empty", "value": null }
+            ]
+        });
+        let issue = youtrack::YouTrackProvider::to_remote_issue("https://yt.example", raw).unwrap();
+        assert_eq!(
+            issue.custom_fields,
+            BTreeMap::from([
+                ("Component".to_string(), "Backend".to_string()),
+                ("Points".to_string(), "3".to_string()),
+                ("Target".to_string(), "2026-01-07".to_string()),
+            ])
+        );
+        assert_eq!(
+            remote_values(&issue).get("custom:Component"),
+            Some(&Some("Backend".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_issue_links() {
+        let jira = serde_json::json!({
+            "fields": {
+                "issuelinks": [
+                    { "type": { "name": "Duplicate" }, "outwardIssue": { "key": "VK-1" } },
+                    { "type": { "name": "Blocks" }, "inwardIssue": { "key": "VK-2" } },
+                    { "type": { "name": "Cloners" }, "outwardIssue": { "key": "VK-3" } }
+                ]
+            }
+        });
+        assert_eq!(
+            jira::issue_links(&jira),
+            vec![
+                RemoteIssueLink {
+                    kind: TaskLinkKind::Duplicates,
+                    external_id: "VK-1".to_string(),
+                },
+                RemoteIssueLink {
+                    kind: TaskLinkKind::BlockedBy,
+                    external_id: "VK-2".to_string(),
+                },
+                RemoteIssueLink {
+                    kind: TaskLinkKind::RelatesTo,
+                    external_id: "VK-3".to_string(),
+                },
+            ]
+        );
+
+        let youtrack = serde_json::json!({
+            "links": [
+                { "direction": "INWARD", "linkType": { "name": "Duplicate" }, "issues": [{ "idReadable": "VK-4" }] },
+                { "direction": "OUTWARD", "linkType": { "name": "Depend" }, "issues": [{ "idReadable": "VK-5" }] },
+                { "direction": "OUTWARD", "linkType": { "name": "Subtask" }, "issues": [{ "idReadable": "VK-6" }] },
+                { "direction": "BOTH", "linkType": { "name": "Relates" }, "issues": [] }
+            ]
+        });
+        assert_eq!(
+            youtrack::issue_links(&youtrack),
+            vec![
+                RemoteIssueLink {
+                    kind: TaskLinkKind::DuplicatedBy,
+                    external_id: "VK-4".to_string(),
+                },
+                RemoteIssueLink {
+                    kind: TaskLinkKind::Blocks,
+                    external_id: "VK-5".to_string(),
+                },
+            ]
+        );
+    }
+}
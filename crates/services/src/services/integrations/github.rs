@@ -0,0 +1,291 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use axum::http::HeaderMap;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use db::models::{
+    integration::{Integration, IntegrationProvider},
+    task::TaskStatus,
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{
+    CatalogField, IntegrationCapability, IntegrationServiceError, IssueProvider,
+    ProviderCatalogEntry, ProviderProbe, RemoteAssignee, RemoteComment, RemoteIssue, base_url,
+    config_str, error_for_status,
+    rate_limit::RateLimitedClient,
+    webhooks::{WEBHOOK_SECRET_KEY, WebhookEvent},
+};
+
+const PAGE_SIZE: usize = 100;
+const MAX_PAGES: usize = 50;
+
+/// GitHub Issues client. `base_url` is the API root (`https://api.github.com` or a GHES
+/// `/api/v3` URL). Config: `repository` as `owner/name`. Secret: optional `token`.
+pub struct GitHubProvider {
+    http: RateLimitedClient,
+    base_url: String,
+    repository: String,
+    token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubIssue {
+    number: i64,
+    title: String,
+    body: Option<String>,
+    state: String,
+    html_url: Option<String>,
+    updated_at: Option<DateTime<Utc>>,
+    assignee: Option<GitHubUser>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubComment {
+    id: i64,
+    body: Option<String>,
+    user: Option<GitHubUser>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubUser {
+    login: String,
+}
+
+impl GitHubProvider {
+    pub fn new(
+        http: RateLimitedClient,
+        integration: &Integration,
+    ) -> Result<Self, IntegrationServiceError> {
+        let repository = config_str(integration, "repository")?;
+        if repository.split('/').count() != 2 {
+            return Err(IntegrationServiceError::InvalidConfig(
+                "'repository' must be in the form owner/name".to_string(),
+            ));
+        }
+        Ok(Self {
+            http,
+            base_url: base_url(integration).to_string(),
+            repository: repository.to_string(),
+            token: integration.secret("token").map(str::to_string),
+        })
+    }
+
+    pub(super) fn to_remote_issue(raw: Value) -> Result<RemoteIssue, IntegrationServiceError> {
+        let issue: GitHubIssue = serde_json::from_value(raw.clone())
+            .map_err(|e| IntegrationServiceError::InvalidResponse(e.to_string()))?;
+        Ok(RemoteIssue {
+            external_id: issue.number.to_string(),
+            url: issue.html_url,
+            title: issue.title,
+            description: issue.body.filter(|b| !b.trim().is_empty()),
+            status: if issue.state == "closed" {
+                TaskStatus::Done
+            } else {
+                TaskStatus::Todo
+            },
+            state: None,
+            priority: None,
+            // GitHub does not expose emails here; match on the login
+            assignee: issue.assignee.map(|user| RemoteAssignee {
+                name: user.login,
+                email: None,
+            }),
+            custom_fields: BTreeMap::new(),
+            updated_at: issue.updated_at,
+            raw,
+        })
+    }
+}
+
+pub(super) fn catalog_entry() -> ProviderCatalogEntry {
+    ProviderCatalogEntry {
+        provider: IntegrationProvider::GitHub,
+        display_name: "GitHub".to_string(),
+        base_url_placeholder: "https://api.github.com".to_string(),
+        config: vec![CatalogField::new(
+            "repository",
+            "Repository",
+            true,
+            "Repository to import issues from, as owner/name.",
+        )],
+        secrets: vec![
+            CatalogField::new(
+                "token",
+                "Access token",
+                false,
+                "Personal access token. Required for private repositories.",
+            ),
+            CatalogField::new(
+                WEBHOOK_SECRET_KEY,
+                "Webhook secret",
+                false,
+                "Secret configured on the repository webhook.",
+            ),
+        ],
+        capabilities: vec![IntegrationCapability::Pull, IntegrationCapability::Webhooks],
+    }
+}
+
+/// Normalize an `issues` webhook delivery. Other event types (including `ping`) and
+/// deliveries for a different repository than the configured one are ignored.
+pub(super) fn parse_webhook(
+    integration: &Integration,
+    headers: &HeaderMap,
+    payload: Value,
+) -> Result<WebhookEvent, IntegrationServiceError> {
+    let event = headers
+        .get("X-GitHub-Event")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("unknown");
+    if event != "issues" {
+        return Ok(WebhookEvent::Ignored(format!("GitHub event '{event}'")));
+    }
+
+    let repository = payload["repository"]["full_name"].as_str().unwrap_or("");
+    let configured = config_str(integration, "repository")?;
+    if !repository.eq_ignore_ascii_case(configured) {
+        return Ok(WebhookEvent::Ignored(format!(
+            "issue event for repository '{repository}'"
+        )));
+    }
+
+    let action = payload["action"].as_str().unwrap_or("").to_string();
+    let issue = payload
+        .get("issue")
+        .cloned()
+        .ok_or_else(|| IntegrationServiceError::InvalidPayload("missing 'issue'".to_string()))?;
+    if action == "deleted" {
+        let number = issue["number"].as_i64().ok_or_else(|| {
+            IntegrationServiceError::InvalidPayload("missing 'issue.number'".to_string())
+        })?;
+        return Ok(WebhookEvent::IssueDeleted {
+            external_id: number.to_string(),
+        });
+    }
+    Ok(WebhookEvent::IssueChanged(GitHubProvider::to_remote_issue(
+        issue,
+    )?))
+}
+
+#[async_trait]
+impl IssueProvider for GitHubProvider {
+    async fn fetch_issues(&self) -> Result<Vec<Value>, IntegrationServiceError> {
+        let mut issues = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let page = page.to_string();
+            let per_page = PAGE_SIZE.to_string();
+            let mut request = self
+                .http
+                .get(format!(
+                    "{}/repos/{}/issues",
+                    self.base_url, self.repository
+                ))
+                .header("Accept", "application/vnd.github+json")
+                .query(&[
+                    ("state", "all"),
+                    ("per_page", per_page.as_str()),
+                    ("page", page.as_str()),
+                ]);
+            if let Some(token) = &self.token {
+                request = request.bearer_auth(token);
+            }
+            let batch: Vec<Value> =
+                error_for_status(IntegrationProvider::GitHub, self.http.send(request).await?)
+                    .await?
+                    .json()
+                    .await?;
+            let done = batch.len() < PAGE_SIZE;
+            // The issues endpoint also lists pull requests; those are not tasks
+            issues.extend(
+                batch
+                    .into_iter()
+                    .filter(|i| i.get("pull_request").is_none()),
+            );
+            if done {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    fn parse_issue(&self, raw: Value) -> Result<RemoteIssue, IntegrationServiceError> {
+        GitHubProvider::to_remote_issue(raw)
+    }
+
+    async fn probe(&self) -> Result<ProviderProbe, IntegrationServiceError> {
+        let mut request = self
+            .http
+            .get(format!("{}/repos/{}", self.base_url, self.repository))
+            .header("Accept", "application/vnd.github+json");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response =
+            error_for_status(IntegrationProvider::GitHub, self.http.send(request).await?).await?;
+        // Fine-grained and expiring classic tokens report their expiry on every response
+        let token_expires_at = response
+            .headers()
+            .get("github-authentication-token-expiration")
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_token_expiration);
+        Ok(ProviderProbe { token_expires_at })
+    }
+
+    async fn fetch_comments(
+        &self,
+        issue: &RemoteIssue,
+    ) -> Result<Vec<RemoteComment>, IntegrationServiceError> {
+        // Issue payloads carry a comment count; skip the request for uncommented issues
+        if issue.raw["comments"].as_i64() == Some(0) {
+            return Ok(Vec::new());
+        }
+
+        let mut comments = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let page = page.to_string();
+            let per_page = PAGE_SIZE.to_string();
+            let mut request = self
+                .http
+                .get(format!(
+                    "{}/repos/{}/issues/{}/comments",
+                    self.base_url, self.repository, issue.external_id
+                ))
+                .header("Accept", "application/vnd.github+json")
+                .query(&[("per_page", per_page.as_str()), ("page", page.as_str())]);
+            if let Some(token) = &self.token {
+                request = request.bearer_auth(token);
+            }
+            let batch: Vec<GitHubComment> =
+                error_for_status(IntegrationProvider::GitHub, self.http.send(request).await?)
+                    .await?
+                    .json()
+                    .await?;
+            let done = batch.len() < PAGE_SIZE;
+            comments.extend(batch.into_iter().map(|comment| {
+                RemoteComment {
+                    external_id: comment.id.to_string(),
+                    author: comment
+                        .user
+                        .map(|user| user.login)
+                        .unwrap_or_else(|| "ghost".to_string()),
+                    body: comment.body.unwrap_or_default(),
+                    created_at: comment.created_at,
+                }
+            }));
+            if done {
+                break;
+            }
+        }
+        Ok(comments)
+    }
+}
+
+/// GitHub formats token expiry as `2024-01-31 09:15:00 UTC`.
+fn parse_token_expiration(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value.trim_end_matches(" UTC"), "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|dt| dt.and_utc())
+}
@@ -0,0 +1,395 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use db::models::{
+    integration::{Integration, IntegrationProvider},
+    task::TaskStatus,
+    task_link::TaskLinkKind,
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{
+    CatalogField, IntegrationCapability, IntegrationServiceError, IssueProvider,
+    ProviderCatalogEntry, ProviderProbe, RemoteAssignee, RemoteAttachment, RemoteIssue,
+    RemoteIssueLink, base_url, config_str, error_for_status, priority_from_name,
+    rate_limit::RateLimitedClient,
+    webhooks::{WEBHOOK_SECRET_KEY, WebhookEvent},
+};
+
+const PAGE_SIZE: usize = 100;
+const MAX_PAGES: usize = 50;
+
+/// Jira Cloud/Server REST v2 client. Config: `project` (key) and optional `jql`.
+/// Secrets: `email` and `api_token` (basic auth).
+pub struct JiraProvider {
+    http: RateLimitedClient,
+    base_url: String,
+    email: String,
+    api_token: String,
+    jql: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    issues: Vec<Value>,
+    total: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraIssue {
+    key: String,
+    fields: JiraFields,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraFields {
+    summary: Option<String>,
+    description: Option<String>,
+    updated: Option<String>,
+    status: Option<JiraStatus>,
+    priority: Option<JiraPriority>,
+    assignee: Option<JiraUser>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraPriority {
+    name: String,
+}
+
+/// `emailAddress` is omitted when the user's profile visibility hides it.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraUser {
+    display_name: String,
+    email_address: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraAttachment {
+    id: String,
+    filename: String,
+    mime_type: Option<String>,
+    size: Option<u64>,
+    content: String,
+}
+
+/// One entry of `issuelinks`; exactly one of the issues is set, on the side the link
+/// points to.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraIssueLink {
+    #[serde(rename = "type")]
+    link_type: JiraIssueLinkType,
+    inward_issue: Option<JiraLinkedIssue>,
+    outward_issue: Option<JiraLinkedIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraIssueLinkType {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraLinkedIssue {
+    key: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraStatus {
+    name: Option<String>,
+    status_category: Option<JiraStatusCategory>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraStatusCategory {
+    key: String,
+}
+
+impl JiraProvider {
+    pub fn new(
+        http: RateLimitedClient,
+        integration: &Integration,
+    ) -> Result<Self, IntegrationServiceError> {
+        let email = integration
+            .secret("email")
+            .ok_or(IntegrationServiceError::MissingSecret("email"))?
+            .to_string();
+        let api_token = integration
+            .secret("api_token")
+            .ok_or(IntegrationServiceError::MissingSecret("api_token"))?
+            .to_string();
+        let jql = match integration.config.get("jql").and_then(Value::as_str) {
+            Some(jql) if !jql.trim().is_empty() => jql.to_string(),
+            _ => format!(
+                "project = \"{}\" ORDER BY updated DESC",
+                config_str(integration, "project")?
+            ),
+        };
+        Ok(Self {
+            http,
+            base_url: base_url(integration).to_string(),
+            email,
+            api_token,
+            jql,
+        })
+    }
+
+    pub(super) fn to_remote_issue(
+        base_url: &str,
+        raw: Value,
+    ) -> Result<RemoteIssue, IntegrationServiceError> {
+        let issue: JiraIssue = serde_json::from_value(raw.clone())
+            .map_err(|e| IntegrationServiceError::InvalidResponse(e.to_string()))?;
+        let (state, category) = match issue.fields.status {
+            Some(status) => (status.name, status.status_category.map(|c| c.key)),
+            None => (None, None),
+        };
+        let status = match category.as_deref() {
+            Some("done") => TaskStatus::Done,
+            Some("indeterminate") => TaskStatus::InProgress,
+            _ => TaskStatus::Todo,
+        };
+        Ok(RemoteIssue {
+            url: Some(format!("{}/browse/{}", base_url, issue.key)),
+            title: issue.fields.summary.unwrap_or_else(|| issue.key.clone()),
+            description: issue.fields.description.filter(|d| !d.trim().is_empty()),
+            status,
+            state,
+            priority: issue
+                .fields
+                .priority
+                .and_then(|p| priority_from_name(&p.name)),
+            assignee: issue.fields.assignee.map(|user| RemoteAssignee {
+                name: user.display_name,
+                email: user.email_address,
+            }),
+            // Jira only identifies custom fields by id (`customfield_10010`), so there is
+            // no name to match against the project's custom fields
+            custom_fields: BTreeMap::new(),
+            updated_at: issue
+                .fields
+                .updated
+                .as_deref()
+                .and_then(parse_jira_timestamp),
+            external_id: issue.key,
+            raw,
+        })
+    }
+}
+
+/// Links in an issue payload. Jira's built-in "Duplicate" and "Blocks" types keep their
+/// direction; every other type becomes `relates_to`.
+pub(super) fn issue_links(raw: &Value) -> Vec<RemoteIssueLink> {
+    let Some(items) = raw["fields"]["issuelinks"].as_array() else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| serde_json::from_value::<JiraIssueLink>(item.clone()).ok())
+        .filter_map(|link| {
+            // An outward link reads "this issue <outward> the other", e.g. "duplicates"
+            let (outward, other) = match (link.outward_issue, link.inward_issue) {
+                (Some(other), _) => (true, other),
+                (None, Some(other)) => (false, other),
+                (None, None) => return None,
+            };
+            let kind = match (link.link_type.name.to_lowercase().as_str(), outward) {
+                ("duplicate", true) => TaskLinkKind::Duplicates,
+                ("duplicate", false) => TaskLinkKind::DuplicatedBy,
+                ("blocks", true) => TaskLinkKind::Blocks,
+                ("blocks", false) => TaskLinkKind::BlockedBy,
+                _ => TaskLinkKind::RelatesTo,
+            };
+            Some(RemoteIssueLink {
+                kind,
+                external_id: other.key,
+            })
+        })
+        .collect()
+}
+
+/// Jira timestamps look like `2024-01-31T09:15:00.000+0000`.
+fn parse_jira_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z")
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+pub(super) fn catalog_entry() -> ProviderCatalogEntry {
+    ProviderCatalogEntry {
+        provider: IntegrationProvider::Jira,
+        display_name: "Jira".to_string(),
+        base_url_placeholder: "https://your-domain.atlassian.net".to_string(),
+        config: vec![
+            CatalogField::new(
+                "project",
+                "Project key",
+                true,
+                "Key of the Jira project to import. Ignored when JQL is set.",
+            ),
+            CatalogField::new(
+                "jql",
+                "JQL",
+                false,
+                "JQL query selecting the issues to import.",
+            ),
+        ],
+        secrets: vec![
+            CatalogField::new("email", "Account email", true, "Email of the Jira account."),
+            CatalogField::new("api_token", "API token", true, "Atlassian API token."),
+            CatalogField::new(
+                WEBHOOK_SECRET_KEY,
+                "Webhook secret",
+                false,
+                "Secret configured on the Jira webhook.",
+            ),
+        ],
+        capabilities: vec![IntegrationCapability::Pull, IntegrationCapability::Webhooks],
+    }
+}
+
+/// Normalize a Jira webhook delivery. Only `jira:issue_*` events carry an issue;
+/// everything else is ignored.
+pub(super) fn parse_webhook(
+    integration: &Integration,
+    payload: Value,
+) -> Result<WebhookEvent, IntegrationServiceError> {
+    let event = payload["webhookEvent"]
+        .as_str()
+        .unwrap_or("unknown")
+        .to_string();
+    let issue = match event.as_str() {
+        "jira:issue_created" | "jira:issue_updated" | "jira:issue_deleted" => {
+            payload.get("issue").cloned().ok_or_else(|| {
+                IntegrationServiceError::InvalidPayload("missing 'issue'".to_string())
+            })?
+        }
+        _ => return Ok(WebhookEvent::Ignored(format!("Jira event '{event}'"))),
+    };
+    if event == "jira:issue_deleted" {
+        let key = issue["key"].as_str().ok_or_else(|| {
+            IntegrationServiceError::InvalidPayload("missing 'issue.key'".to_string())
+        })?;
+        return Ok(WebhookEvent::IssueDeleted {
+            external_id: key.to_string(),
+        });
+    }
+    Ok(WebhookEvent::IssueChanged(JiraProvider::to_remote_issue(
+        base_url(integration),
+        issue,
+    )?))
+}
+
+#[async_trait]
+impl IssueProvider for JiraProvider {
+    async fn fetch_issues(&self) -> Result<Vec<Value>, IntegrationServiceError> {
+        let mut issues = Vec::new();
+        for page in 0..MAX_PAGES {
+            let start_at = (page * PAGE_SIZE).to_string();
+            let max_results = PAGE_SIZE.to_string();
+            let request = self
+                .http
+                .get(format!("{}/rest/api/2/search", self.base_url))
+                .basic_auth(&self.email, Some(&self.api_token))
+                .header("Accept", "application/json")
+                .query(&[
+                    ("jql", self.jql.as_str()),
+                    // Custom fields are included so field mappings can read them
+                    ("fields", "*navigable,attachment"),
+                    ("startAt", start_at.as_str()),
+                    ("maxResults", max_results.as_str()),
+                ]);
+            let response = self.http.send(request).await?;
+            let body: SearchResponse = error_for_status(IntegrationProvider::Jira, response)
+                .await?
+                .json()
+                .await?;
+            let fetched = body.issues.len();
+            issues.extend(body.issues);
+            if fetched == 0 || issues.len() >= body.total {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    fn parse_issue(&self, raw: Value) -> Result<RemoteIssue, IntegrationServiceError> {
+        JiraProvider::to_remote_issue(&self.base_url, raw)
+    }
+
+    async fn probe(&self) -> Result<ProviderProbe, IntegrationServiceError> {
+        let request = self
+            .http
+            .get(format!("{}/rest/api/2/myself", self.base_url))
+            .basic_auth(&self.email, Some(&self.api_token))
+            .header("Accept", "application/json");
+        let response = self.http.send(request).await?;
+        error_for_status(IntegrationProvider::Jira, response).await?;
+        Ok(ProviderProbe::default())
+    }
+
+    async fn check_scope(&self) -> Result<(), IntegrationServiceError> {
+        // JQL naming a project the account can't see fails with 400
+        let request = self
+            .http
+            .get(format!("{}/rest/api/2/search", self.base_url))
+            .basic_auth(&self.email, Some(&self.api_token))
+            .header("Accept", "application/json")
+            .query(&[
+                ("jql", self.jql.as_str()),
+                ("fields", "id"),
+                ("maxResults", "0"),
+            ]);
+        let response = self.http.send(request).await?;
+        error_for_status(IntegrationProvider::Jira, response).await?;
+        Ok(())
+    }
+
+    fn links(&self, issue: &RemoteIssue) -> Vec<RemoteIssueLink> {
+        issue_links(&issue.raw)
+    }
+
+    fn attachments(&self, issue: &RemoteIssue) -> Vec<RemoteAttachment> {
+        let Some(items) = issue.raw["fields"]["attachment"].as_array() else {
+            return Vec::new();
+        };
+        items
+            .iter()
+            .filter_map(|item| serde_json::from_value::<JiraAttachment>(item.clone()).ok())
+            .map(|attachment| RemoteAttachment {
+                external_id: attachment.id,
+                filename: attachment.filename,
+                content_type: attachment.mime_type,
+                size_bytes: attachment.size,
+                url: attachment.content,
+            })
+            .collect()
+    }
+
+    async fn download_attachment(
+        &self,
+        attachment: &RemoteAttachment,
+    ) -> Result<Vec<u8>, IntegrationServiceError> {
+        // Credentials are only ever sent to the configured Jira instance
+        if !attachment.url.starts_with(&format!("{}/", self.base_url)) {
+            return Err(IntegrationServiceError::InvalidResponse(format!(
+                "attachment URL '{}' is outside {}",
+                attachment.url, self.base_url
+            )));
+        }
+        let request = self
+            .http
+            .get(&attachment.url)
+            .basic_auth(&self.email, Some(&self.api_token));
+        let response = self.http.send(request).await?;
+        Ok(error_for_status(IntegrationProvider::Jira, response)
+            .await?
+            .bytes()
+            .await?
+            .to_vec())
+    }
+}
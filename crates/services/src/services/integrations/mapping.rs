@@ -0,0 +1,192 @@
+//! Applies an integration's declarative [`FieldMapping`] to normalized remote issues.
+
+use std::str::FromStr;
+
+use db::models::{
+    integration::{FieldMapping, MappedField},
+    project_settings::ProjectSettings,
+    task::{TaskPriority, TaskStatus},
+};
+use serde_json::Value;
+
+use super::{RemoteAssignee, RemoteIssue};
+
+/// Overwrite the mapped fields of `issue` with values resolved from its raw payload.
+pub(super) fn apply(mapping: &FieldMapping, issue: &mut RemoteIssue) {
+    for rule in &mapping.rules {
+        let value = match lookup(&issue.raw, &rule.source).and_then(scalar_to_string) {
+            Some(remote) => Some(rule.values.get(&remote).cloned().unwrap_or(remote)),
+            None => rule.default.clone(),
+        };
+
+        match rule.target {
+            MappedField::Title => {
+                if let Some(title) = value {
+                    issue.title = title;
+                }
+            }
+            MappedField::Description => issue.description = value,
+            MappedField::Status => match value.as_deref().map(TaskStatus::from_str) {
+                Some(Ok(status)) => issue.status = status,
+                Some(Err(_)) => tracing::debug!(
+                    external_id = %issue.external_id,
+                    source = %rule.source,
+                    "mapped status value is not a task status; keeping provider status"
+                ),
+                None => {}
+            },
+            MappedField::Priority => match value.as_deref().map(TaskPriority::from_str) {
+                Some(Ok(priority)) => issue.priority = Some(priority),
+                Some(Err(_)) => tracing::debug!(
+                    external_id = %issue.external_id,
+                    source = %rule.source,
+                    "mapped priority value is not a task priority; keeping provider priority"
+                ),
+                None => {}
+            },
+            MappedField::Assignee => {
+                if let Some(value) = value {
+                    issue.assignee = Some(RemoteAssignee::from_identifier(value));
+                }
+            }
+        }
+    }
+}
+
+/// Set the status of `issue` from the project's state mappings when its workflow state
+/// has one.
+pub(super) fn apply_states(settings: &ProjectSettings, issue: &mut RemoteIssue) {
+    if let Some(status) = issue
+        .state
+        .as_deref()
+        .and_then(|state| settings.mapped_status(state))
+    {
+        issue.status = status.clone();
+    }
+}
+
+/// Resolve a dot-separated path. Numeric segments index arrays and `key=value` segments
+/// select the first array element whose `key` field equals `value`.
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(value, |current, segment| {
+        if let Some((key, expected)) = segment.split_once('=') {
+            return current.as_array()?.iter().find(|item| {
+                item.get(key).and_then(scalar_to_string).as_deref() == Some(expected)
+            });
+        }
+        match current {
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => current.get(segment),
+        }
+    })
+}
+
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.trim().is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use db::models::integration::FieldMappingRule;
+    use serde_json::json;
+
+    use super::*;
+
+    fn issue(raw: Value) -> RemoteIssue {
+        RemoteIssue {
+            external_id: "VK-1".to_string(),
+            url: None,
+            title: "Original".to_string(),
+            description: None,
+            status: TaskStatus::Todo,
+            state: None,
+            priority: None,
+            assignee: None,
+            custom_fields: Default::default(),
+            updated_at: None,
+            raw,
+        }
+    }
+
+    fn rule(source: &str, target: MappedField) -> FieldMappingRule {
+        FieldMappingRule {
+            source: source.to_string(),
+            target,
+            values: HashMap::new(),
+            default: None,
+        }
+    }
+
+    #[test]
+    fn test_lookup_paths() {
+        let raw = json!({
+            "fields": { "labels": ["a", "b"] },
+            "customFields": [
+                { "name": "Priority", "value": { "name": "Major" } },
+                { "name": "State", "value": { "name": "In Progress" } }
+            ]
+        });
+        assert_eq!(lookup(&raw, "fields.labels.1"), Some(&json!("b")));
+        assert_eq!(
+            lookup(&raw, "customFields.name=State.value.name"),
+            Some(&json!("In Progress"))
+        );
+        assert_eq!(lookup(&raw, "customFields.name=Missing.value"), None);
+        assert_eq!(lookup(&raw, "fields.missing"), None);
+    }
+
+    #[test]
+    fn test_status_translation_and_default() {
+        let mut status = rule("customFields.name=State.value.name", MappedField::Status);
+        status.values = HashMap::from([("In Progress".to_string(), "inprogress".to_string())]);
+        let mut description = rule("fields.summary_text", MappedField::Description);
+        description.default = Some("No description".to_string());
+        let mapping = FieldMapping {
+            rules: vec![status, description],
+        };
+
+        let mut issue = issue(json!({
+            "customFields": [{ "name": "State", "value": { "name": "In Progress" } }]
+        }));
+        apply(&mapping, &mut issue);
+
+        assert_eq!(issue.status, TaskStatus::InProgress);
+        assert_eq!(issue.description.as_deref(), Some("No description"));
+    }
+
+    #[test]
+    fn test_untranslatable_status_keeps_provider_value() {
+        let mapping = FieldMapping {
+            rules: vec![rule("state", MappedField::Status)],
+        };
+        let mut issue = issue(json!({ "state": "Triage" }));
+        issue.status = TaskStatus::Done;
+        apply(&mapping, &mut issue);
+
+        assert_eq!(issue.status, TaskStatus::Done);
+    }
+
+    #[test]
+    fn test_assignee_mapping_detects_email() {
+        let mapping = FieldMapping {
+            rules: vec![rule("fields.owner", MappedField::Assignee)],
+        };
+        let mut issue = issue(json!({ "fields": { "owner": "ada@example.com" } }));
+        apply(&mapping, &mut issue);
+
+        assert_eq!(
+            issue.assignee,
+            Some(RemoteAssignee {
+                name: "ada@example.com".to_string(),
+                email: Some("ada@example.com".to_string()),
+            })
+        );
+    }
+}
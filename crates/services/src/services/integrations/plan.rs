@@ -0,0 +1,131 @@
+//! Dry-run sync plans: classify what a sync would do to each task without touching it,
+//! and render stored plans as a readable diff report.
+
+use db::models::{
+    integration::Integration,
+    integration_link::IntegrationLink,
+    sync_plan::{PlannedFieldChange, SyncPlan, SyncPlanAction, SyncPlanItem},
+    task::{Task, TaskStatus},
+};
+use sqlx::SqlitePool;
+
+use super::{IntegrationServiceError, RemoteIssue, field_changes, reconcile};
+
+/// Longest value shown in a rendered report before it is truncated.
+const MAX_RENDERED_VALUE: usize = 60;
+
+/// Plan a single issue, or `None` when applying it would change nothing.
+pub(super) async fn plan_issue(
+    pool: &SqlitePool,
+    integration: &Integration,
+    issue: &RemoteIssue,
+) -> Result<Option<SyncPlanItem>, IntegrationServiceError> {
+    let link =
+        IntegrationLink::find_by_external_id(pool, integration.id, &issue.external_id).await?;
+    let task = match &link {
+        Some(link) => Task::find_by_id(pool, link.task_id).await?,
+        None => None,
+    };
+
+    let mut changes = field_changes(task.as_ref(), issue);
+    let action = match &task {
+        None => SyncPlanAction::Create,
+        Some(_) => {
+            let baseline = link.and_then(|link| link.remote_values).map(|v| v.0);
+            let (apply, conflicts) = reconcile(changes.clone(), baseline.as_ref());
+            if !conflicts.is_empty() {
+                SyncPlanAction::Conflict
+            } else {
+                // Fields only edited locally are kept, so they are not planned changes
+                changes = apply;
+                let closes = changes.iter().any(|(field, _, _)| *field == "status")
+                    && matches!(issue.status, TaskStatus::Done | TaskStatus::Cancelled);
+                if closes {
+                    SyncPlanAction::Close
+                } else {
+                    SyncPlanAction::Update
+                }
+            }
+        }
+    };
+    if changes.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(SyncPlanItem {
+        action,
+        external_id: issue.external_id.clone(),
+        task_id: task.map(|task| task.id),
+        title: issue.title.clone(),
+        changes: changes
+            .into_iter()
+            .map(|(field, old_value, new_value)| PlannedFieldChange {
+                field: field.to_string(),
+                old_value,
+                new_value,
+            })
+            .collect(),
+        payload: issue.raw.clone(),
+    }))
+}
+
+/// Render a plan as a plain-text diff report.
+pub fn render(integration: &Integration, plan: &SyncPlan) -> String {
+    let mut report = format!(
+        "Sync plan for {} ({}): {} to create, {} to update, {} to close, {} conflicts\n",
+        integration.name,
+        integration.provider,
+        plan.count(SyncPlanAction::Create),
+        plan.count(SyncPlanAction::Update),
+        plan.count(SyncPlanAction::Close),
+        plan.count(SyncPlanAction::Conflict),
+    );
+
+    for item in plan.items.iter() {
+        let marker = match item.action {
+            SyncPlanAction::Create => '+',
+            SyncPlanAction::Update => '~',
+            SyncPlanAction::Close => 'x',
+            SyncPlanAction::Conflict => '!',
+        };
+        report.push_str(&format!("\n{marker} {} {}\n", item.external_id, item.title));
+        for change in &item.changes {
+            report.push_str(&format!(
+                "    {}: {} -> {}\n",
+                change.field,
+                render_value(change.old_value.as_deref()),
+                render_value(change.new_value.as_deref()),
+            ));
+        }
+    }
+    report
+}
+
+fn render_value(value: Option<&str>) -> String {
+    let Some(value) = value else {
+        return "(none)".to_string();
+    };
+    let single_line = value.replace('\n', " ");
+    if single_line.chars().count() > MAX_RENDERED_VALUE {
+        let truncated: String = single_line.chars().take(MAX_RENDERED_VALUE).collect();
+        format!("\"{truncated}…\"")
+    } else {
+        format!("\"{single_line}\"")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_value() {
+        assert_eq!(render_value(None), "(none)");
+        assert_eq!(render_value(Some("a\nb")), "\"a b\"");
+
+        let long = "x".repeat(MAX_RENDERED_VALUE + 10);
+        let rendered = render_value(Some(&long));
+        assert!(rendered.ends_with("…\""));
+        assert_eq!(rendered.chars().count(), MAX_RENDERED_VALUE + 3);
+    }
+}
@@ -0,0 +1,209 @@
+//! Outbound rate limiting shared by every provider client, so one aggressive sync cannot
+//! exhaust a tracker's rate limit for the whole server.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use tracing::{Instrument, field::Empty};
+
+/// Requests allowed in a burst against a single host.
+const BURST: f64 = 10.0;
+/// Sustained requests per second against a single host.
+const REFILL_PER_SEC: f64 = 5.0;
+/// Pause applied after a 429 that carries no usable `Retry-After`.
+const DEFAULT_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+    /// Set when the host answered 429; no tokens are handed out before this
+    blocked_until: Option<Instant>,
+}
+
+impl TokenBucket {
+    fn new(now: Instant) -> Self {
+        Self {
+            tokens: BURST,
+            refilled_at: now,
+            blocked_until: None,
+        }
+    }
+
+    /// Take a token, or return how long to wait before one is available.
+    fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        if let Some(until) = self.blocked_until {
+            if now < until {
+                return Err(until - now);
+            }
+            self.blocked_until = None;
+        }
+
+        let elapsed = now
+            .saturating_duration_since(self.refilled_at)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * REFILL_PER_SEC).min(BURST);
+        self.refilled_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / REFILL_PER_SEC,
+            ))
+        }
+    }
+
+    fn block(&mut self, now: Instant, duration: Duration) {
+        self.tokens = 0.0;
+        self.refilled_at = now + duration;
+        self.blocked_until = Some(now + duration);
+    }
+}
+
+/// Token buckets keyed by host, shared across clones.
+#[derive(Debug, Clone, Default)]
+pub struct HostRateLimiter {
+    buckets: Arc<Mutex<HashMap<String, TokenBucket>>>,
+}
+
+impl HostRateLimiter {
+    /// Wait until a request to `host` is allowed.
+    pub async fn acquire(&self, host: &str) {
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap();
+                let now = Instant::now();
+                let bucket = buckets
+                    .entry(host.to_string())
+                    .or_insert_with(|| TokenBucket::new(now));
+                match bucket.try_take(now) {
+                    Ok(()) => return,
+                    Err(wait) => wait,
+                }
+            };
+            tracing::debug!(host, ?wait, "integration rate limit reached; waiting");
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Stop handing out tokens for `host` until `duration` has passed.
+    pub fn back_off(&self, host: &str, duration: Duration) {
+        let mut buckets = self.buckets.lock().unwrap();
+        let now = Instant::now();
+        buckets
+            .entry(host.to_string())
+            .or_insert_with(|| TokenBucket::new(now))
+            .block(now, duration);
+    }
+}
+
+/// HTTP client for provider APIs that routes every request through the shared limiter.
+#[derive(Debug, Clone)]
+pub struct RateLimitedClient {
+    http: Client,
+    limiter: HostRateLimiter,
+}
+
+impl RateLimitedClient {
+    pub fn new(http: Client, limiter: HostRateLimiter) -> Self {
+        Self { http, limiter }
+    }
+
+    pub fn get(&self, url: impl reqwest::IntoUrl) -> RequestBuilder {
+        self.http.get(url)
+    }
+
+    /// Send a request once the target host has budget. A 429 response pauses the host for
+    /// its `Retry-After` period (or a default back-off) and is returned to the caller. The
+    /// `http.client` span covers the wait for budget too, so a throttled host shows up in
+    /// the trace of a slow sync.
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response, reqwest::Error> {
+        let request = request.build()?;
+        let host = request.url().host_str().unwrap_or_default().to_string();
+        let span = tracing::info_span!(
+            "http.client",
+            otel.name = %request.method(),
+            otel.kind = "client",
+            otel.status_code = Empty,
+            http.request.method = %request.method(),
+            server.address = %host,
+            url.path = %request.url().path(),
+            http.response.status_code = Empty,
+        );
+        async {
+            self.limiter.acquire(&host).await;
+
+            let response = match self.http.execute(request).await {
+                Ok(response) => response,
+                Err(e) => {
+                    tracing::Span::current().record("otel.status_code", "ERROR");
+                    return Err(e);
+                }
+            };
+            let span = tracing::Span::current();
+            span.record("http.response.status_code", response.status().as_u16());
+            if response.status().is_client_error() || response.status().is_server_error() {
+                span.record("otel.status_code", "ERROR");
+            }
+            if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                let retry_after = response
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.trim().parse::<u64>().ok())
+                    .map(Duration::from_secs)
+                    .unwrap_or(DEFAULT_BACKOFF);
+                tracing::warn!(%host, ?retry_after, "provider rate limited us; backing off");
+                self.limiter.back_off(&host, retry_after);
+            }
+            Ok(response)
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_allows_burst_then_waits() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(start);
+        for _ in 0..BURST as usize {
+            assert!(bucket.try_take(start).is_ok());
+        }
+        let wait = bucket.try_take(start).unwrap_err();
+        assert!(wait <= Duration::from_secs_f64(1.0 / REFILL_PER_SEC));
+    }
+
+    #[test]
+    fn test_bucket_refills_over_time() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(start);
+        for _ in 0..BURST as usize {
+            bucket.try_take(start).unwrap();
+        }
+        let later = start + Duration::from_secs_f64(1.0 / REFILL_PER_SEC);
+        assert!(bucket.try_take(later).is_ok());
+        assert!(bucket.try_take(later).is_err());
+    }
+
+    #[test]
+    fn test_block_holds_until_expiry() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(start);
+        bucket.block(start, Duration::from_secs(5));
+
+        assert!(bucket.try_take(start + Duration::from_secs(4)).is_err());
+        let after = start + Duration::from_secs(5) + Duration::from_secs_f64(1.0 / REFILL_PER_SEC);
+        assert!(bucket.try_take(after).is_ok());
+    }
+}
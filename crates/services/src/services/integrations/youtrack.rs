@@ -0,0 +1,345 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use db::models::{
+    integration::{Integration, IntegrationProvider},
+    task::TaskStatus,
+    task_link::TaskLinkKind,
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{
+    CatalogField, IntegrationCapability, IntegrationServiceError, IssueProvider,
+    ProviderCatalogEntry, ProviderProbe, RemoteAssignee, RemoteIssue, RemoteIssueLink, base_url,
+    config_str, error_for_status, priority_from_name,
+    rate_limit::RateLimitedClient,
+    webhooks::{WEBHOOK_SECRET_KEY, WebhookEvent},
+};
+
+const PAGE_SIZE: usize = 100;
+const MAX_PAGES: usize = 50;
+const ISSUE_FIELDS: &str = "idReadable,summary,description,updated,resolved,customFields($type,name,value(name,login,email,text)),links(direction,linkType(name),issues(idReadable))";
+
+/// YouTrack REST client. Config: `project` (short name) and optional `query`
+/// overriding the default `project: <project>` search. Secret: `token`.
+pub struct YouTrackProvider {
+    http: RateLimitedClient,
+    base_url: String,
+    token: String,
+    query: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct YouTrackIssue {
+    id_readable: String,
+    summary: Option<String>,
+    description: Option<String>,
+    updated: Option<i64>,
+    resolved: Option<i64>,
+    #[serde(default)]
+    custom_fields: Vec<YouTrackCustomField>,
+}
+
+#[derive(Debug, Deserialize)]
+struct YouTrackCustomField {
+    #[serde(rename = "$type", default)]
+    field_type: String,
+    name: String,
+    value: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct YouTrackIssueLink {
+    /// `OUTWARD`, `INWARD`, or `BOTH` for undirected link types
+    direction: String,
+    link_type: YouTrackLinkType,
+    #[serde(default)]
+    issues: Vec<YouTrackLinkedIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct YouTrackLinkType {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct YouTrackLinkedIssue {
+    id_readable: String,
+}
+
+impl YouTrackCustomField {
+    /// The value as text: enum, state and user fields by name, text fields by content,
+    /// dates as `YYYY-MM-DD`. Multi-value and other structured fields have none.
+    fn scalar_value(&self) -> Option<String> {
+        let value = self.value.as_ref()?;
+        let text = match value {
+            Value::String(s) => s.clone(),
+            // Dates are epoch milliseconds
+            Value::Number(n) if self.field_type == "DateIssueCustomField" => {
+                DateTime::<Utc>::from_timestamp_millis(n.as_i64()?)?
+                    .format("%Y-%m-%d")
+                    .to_string()
+            }
+            Value::Number(n) => n.to_string(),
+            Value::Object(_) => value
+                .get("name")
+                .or_else(|| value.get("text"))?
+                .as_str()?
+                .to_string(),
+            _ => return None,
+        };
+        (!text.trim().is_empty()).then_some(text)
+    }
+}
+
+/// Links in an issue payload. "Duplicate" and "Depend" keep their direction, with
+/// "depends on" read as blocked by; subtasks are left out and every other type becomes
+/// `relates_to`.
+pub(super) fn issue_links(raw: &Value) -> Vec<RemoteIssueLink> {
+    let Some(items) = raw["links"].as_array() else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| serde_json::from_value::<YouTrackIssueLink>(item.clone()).ok())
+        .flat_map(|link| {
+            let kind = match (
+                link.link_type.name.to_lowercase().as_str(),
+                link.direction.as_str(),
+            ) {
+                ("subtask", _) => return Vec::new(),
+                ("duplicate", "OUTWARD") => TaskLinkKind::Duplicates,
+                ("duplicate", "INWARD") => TaskLinkKind::DuplicatedBy,
+                // Outward reads "is required for"
+                ("depend", "OUTWARD") => TaskLinkKind::Blocks,
+                ("depend", "INWARD") => TaskLinkKind::BlockedBy,
+                _ => TaskLinkKind::RelatesTo,
+            };
+            link.issues
+                .into_iter()
+                .map(|issue| RemoteIssueLink {
+                    kind,
+                    external_id: issue.id_readable,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl YouTrackProvider {
+    pub fn new(
+        http: RateLimitedClient,
+        integration: &Integration,
+    ) -> Result<Self, IntegrationServiceError> {
+        let token = integration
+            .secret("token")
+            .ok_or(IntegrationServiceError::MissingSecret("token"))?
+            .to_string();
+        let query = match integration.config.get("query").and_then(Value::as_str) {
+            Some(query) if !query.trim().is_empty() => query.to_string(),
+            _ => format!("project: {{{}}}", config_str(integration, "project")?),
+        };
+        Ok(Self {
+            http,
+            base_url: base_url(integration).to_string(),
+            token,
+            query,
+        })
+    }
+
+    pub(super) fn to_remote_issue(
+        base_url: &str,
+        raw: Value,
+    ) -> Result<RemoteIssue, IntegrationServiceError> {
+        let issue: YouTrackIssue = serde_json::from_value(raw.clone())
+            .map_err(|e| IntegrationServiceError::InvalidResponse(e.to_string()))?;
+        // Priority is a regular enum custom field; its value is `{ "name": ... }`
+        let priority = issue
+            .custom_fields
+            .iter()
+            .find(|field| field.name == "Priority")
+            .and_then(|field| field.value.as_ref()?.get("name")?.as_str())
+            .and_then(priority_from_name);
+        // Assignee is a user custom field; its value is `{ "name": ..., "email": ... }`
+        let assignee = issue
+            .custom_fields
+            .iter()
+            .find(|field| field.name == "Assignee")
+            .and_then(|field| {
+                let user = field.value.as_ref()?;
+                Some(RemoteAssignee {
+                    name: user.get("name")?.as_str()?.to_string(),
+                    email: user
+                        .get("email")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                })
+            });
+        let custom_fields: BTreeMap<String, String> = issue
+            .custom_fields
+            .iter()
+            .filter_map(|field| Some((field.name.clone(), field.scalar_value()?)))
+            .collect();
+        // YouTrack's default workflow keeps the state in a custom field named "State"
+        let state = custom_fields.get("State").cloned();
+        Ok(RemoteIssue {
+            url: Some(format!("{}/issue/{}", base_url, issue.id_readable)),
+            title: issue.summary.unwrap_or_else(|| issue.id_readable.clone()),
+            description: issue.description.filter(|d| !d.trim().is_empty()),
+            status: if issue.resolved.is_some() {
+                TaskStatus::Done
+            } else {
+                TaskStatus::Todo
+            },
+            state,
+            priority,
+            assignee,
+            custom_fields,
+            updated_at: issue
+                .updated
+                .and_then(DateTime::<Utc>::from_timestamp_millis),
+            external_id: issue.id_readable,
+            raw,
+        })
+    }
+}
+
+pub(super) fn catalog_entry() -> ProviderCatalogEntry {
+    ProviderCatalogEntry {
+        provider: IntegrationProvider::YouTrack,
+        display_name: "YouTrack".to_string(),
+        base_url_placeholder: "https://example.youtrack.cloud".to_string(),
+        config: vec![
+            CatalogField::new(
+                "project",
+                "Project",
+                true,
+                "Short name of the YouTrack project to import. Ignored when a query is set.",
+            ),
+            CatalogField::new(
+                "query",
+                "Query",
+                false,
+                "YouTrack search query selecting the issues to import.",
+            ),
+        ],
+        secrets: vec![
+            CatalogField::new(
+                "token",
+                "Permanent token",
+                true,
+                "YouTrack permanent token.",
+            ),
+            CatalogField::new(
+                WEBHOOK_SECRET_KEY,
+                "Webhook secret",
+                false,
+                "Key a workflow rule uses to sign deliveries in the X-VK-Signature header.",
+            ),
+        ],
+        capabilities: vec![IntegrationCapability::Pull, IntegrationCapability::Webhooks],
+    }
+}
+
+/// YouTrack has no native webhooks, so deliveries come from a workflow rule posting
+/// `{"event": "created" | "updated" | "deleted", "issue": {...}}` where `issue` carries
+/// the same fields the REST client requests.
+pub(super) fn parse_webhook(
+    integration: &Integration,
+    payload: Value,
+) -> Result<WebhookEvent, IntegrationServiceError> {
+    let event = payload["event"].as_str().unwrap_or("updated").to_string();
+    let issue = payload
+        .get("issue")
+        .cloned()
+        .ok_or_else(|| IntegrationServiceError::InvalidPayload("missing 'issue'".to_string()))?;
+    match event.as_str() {
+        "created" | "updated" => Ok(WebhookEvent::IssueChanged(
+            YouTrackProvider::to_remote_issue(base_url(integration), issue)?,
+        )),
+        "deleted" => {
+            let external_id = issue["idReadable"].as_str().ok_or_else(|| {
+                IntegrationServiceError::InvalidPayload("missing 'issue.idReadable'".to_string())
+            })?;
+            Ok(WebhookEvent::IssueDeleted {
+                external_id: external_id.to_string(),
+            })
+        }
+        other => Ok(WebhookEvent::Ignored(format!("YouTrack event '{other}'"))),
+    }
+}
+
+#[async_trait]
+impl IssueProvider for YouTrackProvider {
+    async fn fetch_issues(&self) -> Result<Vec<Value>, IntegrationServiceError> {
+        let mut issues = Vec::new();
+        for page in 0..MAX_PAGES {
+            let skip = (page * PAGE_SIZE).to_string();
+            let top = PAGE_SIZE.to_string();
+            let request = self
+                .http
+                .get(format!("{}/api/issues", self.base_url))
+                .bearer_auth(&self.token)
+                .header("Accept", "application/json")
+                .query(&[
+                    ("query", self.query.as_str()),
+                    ("fields", ISSUE_FIELDS),
+                    ("$skip", skip.as_str()),
+                    ("$top", top.as_str()),
+                ]);
+            let response = self.http.send(request).await?;
+            let batch: Vec<Value> = error_for_status(IntegrationProvider::YouTrack, response)
+                .await?
+                .json()
+                .await?;
+            let done = batch.len() < PAGE_SIZE;
+            issues.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    fn parse_issue(&self, raw: Value) -> Result<RemoteIssue, IntegrationServiceError> {
+        YouTrackProvider::to_remote_issue(&self.base_url, raw)
+    }
+
+    fn links(&self, issue: &RemoteIssue) -> Vec<RemoteIssueLink> {
+        issue_links(&issue.raw)
+    }
+
+    async fn probe(&self) -> Result<ProviderProbe, IntegrationServiceError> {
+        let request = self
+            .http
+            .get(format!("{}/api/users/me", self.base_url))
+            .bearer_auth(&self.token)
+            .header("Accept", "application/json")
+            .query(&[("fields", "id")]);
+        let response = self.http.send(request).await?;
+        error_for_status(IntegrationProvider::YouTrack, response).await?;
+        Ok(ProviderProbe::default())
+    }
+
+    async fn check_scope(&self) -> Result<(), IntegrationServiceError> {
+        // An unknown project in the query is rejected rather than matching nothing
+        let request = self
+            .http
+            .get(format!("{}/api/issues", self.base_url))
+            .bearer_auth(&self.token)
+            .header("Accept", "application/json")
+            .query(&[
+                ("query", self.query.as_str()),
+                ("fields", "id"),
+                ("$top", "1"),
+            ]);
+        let response = self.http.send(request).await?;
+        error_for_status(IntegrationProvider::YouTrack, response).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,277 @@
+//! Durable background jobs. Work is queued as rows in the `jobs` table and run by a pool
+//! of workers, so it survives a restart; failed attempts are retried with exponential
+//! backoff, and handlers with a schedule are queued again each time a run finishes.
+
+use std::{collections::HashMap, panic::AssertUnwindSafe, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use db::models::job::{CreateJob, Job};
+use futures::FutureExt;
+use serde_json::json;
+use sqlx::SqlitePool;
+use tokio::{sync::Notify, task::JoinSet, time::sleep};
+use tracing::{Instrument, error, info, warn};
+
+/// How often idle workers look for due jobs when nothing wakes them sooner.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Delay before the first retry; it doubles with each further attempt.
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(30);
+
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(60 * 60);
+
+/// How long finished jobs are kept before `prune_jobs` deletes them.
+const FINISHED_JOB_RETENTION_DAYS: i64 = 7;
+
+/// Runs the jobs of one `kind`.
+#[async_trait]
+pub trait JobHandler: Send + Sync {
+    fn kind(&self) -> &'static str;
+
+    /// Run one attempt of the job. An error fails the attempt; it is retried with backoff
+    /// until the job runs out of attempts.
+    async fn run(&self, job: &Job) -> Result<(), String>;
+
+    /// Run the handler this often, without a payload. The next run is queued when one
+    /// finishes, so runs never overlap and a restart doesn't reset the clock.
+    fn schedule(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Queues jobs for the runner and wakes an idle worker to pick them up.
+#[derive(Clone)]
+pub struct JobQueue {
+    pool: SqlitePool,
+    wake: Arc<Notify>,
+}
+
+impl JobQueue {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            pool,
+            wake: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Queue a job; `None` when its `unique_key` is taken by a pending job.
+    pub async fn enqueue(&self, data: &CreateJob) -> Result<Option<Job>, sqlx::Error> {
+        let job = Job::create(&self.pool, data).await?;
+        if job.is_some() {
+            self.notify();
+        }
+        Ok(job)
+    }
+
+    /// Wake a worker for jobs created with [`Job::create`] directly, e.g. in a transaction.
+    pub fn notify(&self) {
+        self.wake.notify_one();
+    }
+}
+
+/// The delay before retrying a job whose `attempts`th attempt failed.
+fn retry_delay(attempts: i64) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(exponent))
+        .min(RETRY_MAX_DELAY)
+}
+
+fn schedule_key(kind: &str) -> String {
+    format!("schedule:{kind}")
+}
+
+/// Pool of workers running queued jobs with the handler for their kind.
+pub struct JobRunner {
+    queue: JobQueue,
+    handlers: HashMap<&'static str, Arc<dyn JobHandler>>,
+    workers: usize,
+}
+
+impl JobRunner {
+    /// Runs `VK_JOB_WORKERS` jobs at a time, 4 by default. Old finished jobs are pruned
+    /// daily.
+    pub fn new(queue: JobQueue) -> Self {
+        let workers = std::env::var("VK_JOB_WORKERS")
+            .ok()
+            .and_then(|value| value.parse::<usize>().ok())
+            .filter(|workers| *workers > 0)
+            .unwrap_or(4);
+        let prune = PruneJobs {
+            pool: queue.pool.clone(),
+        };
+        Self {
+            queue,
+            handlers: HashMap::new(),
+            workers,
+        }
+        .handler(prune)
+    }
+
+    pub fn handler(mut self, handler: impl JobHandler + 'static) -> Self {
+        self.handlers.insert(handler.kind(), Arc::new(handler));
+        self
+    }
+
+    pub async fn spawn(self) -> tokio::task::JoinHandle<()> {
+        let runner = Arc::new(self);
+        tokio::spawn(async move {
+            runner.start().await;
+        })
+    }
+
+    async fn start(self: Arc<Self>) {
+        let pool = &self.queue.pool;
+        match Job::requeue_interrupted(pool).await {
+            Ok(count) if count > 0 => info!("Requeued {} interrupted jobs", count),
+            Ok(_) => {}
+            Err(e) => error!("Failed to requeue interrupted jobs: {}", e),
+        }
+        for handler in self.handlers.values() {
+            if handler.schedule().is_some() {
+                self.schedule_next(handler.kind(), Duration::ZERO).await;
+            }
+        }
+
+        info!("Starting job runner with {} workers", self.workers);
+        let mut workers = JoinSet::new();
+        for _ in 0..self.workers {
+            workers.spawn(self.clone().work());
+        }
+        // Workers only stop by panicking outside a job; replace them
+        while let Some(result) = workers.join_next().await {
+            error!("Job worker stopped: {:?}", result.err());
+            workers.spawn(self.clone().work());
+        }
+    }
+
+    async fn work(self: Arc<Self>) {
+        let pool = &self.queue.pool;
+        loop {
+            match Job::claim_next(pool).await {
+                Ok(Some(job)) => {
+                    self.run(job).await;
+                    continue;
+                }
+                Ok(None) => {}
+                Err(e) => error!("Failed to claim job: {}", e),
+            }
+            let wait = match Job::next_run_at(pool).await {
+                Ok(Some(run_at)) => (run_at - Utc::now()).to_std().unwrap_or_default(),
+                _ => POLL_INTERVAL,
+            };
+            tokio::select! {
+                _ = self.queue.wake.notified() => {}
+                _ = sleep(wait.clamp(Duration::from_millis(250), POLL_INTERVAL)) => {}
+            }
+        }
+    }
+
+    async fn run(&self, job: Job) {
+        let pool = &self.queue.pool;
+        let Some(handler) = self.handlers.get(job.kind.as_str()) else {
+            let message = format!("No handler for job kind '{}'", job.kind);
+            warn!("Job {} failed: {}", job.id, message);
+            if let Err(e) = Job::mark_failed(pool, job.id, &message).await {
+                error!("Failed to record outcome of job {}: {}", job.id, e);
+            }
+            return;
+        };
+
+        let span = tracing::info_span!(
+            "job",
+            otel.name = %job.kind,
+            job.id = %job.id,
+            job.attempt = job.attempts,
+        );
+        let result = AssertUnwindSafe(handler.run(&job).instrument(span))
+            .catch_unwind()
+            .await
+            .unwrap_or_else(|_| Err("The job panicked".to_string()));
+        let finished = result.is_ok() || job.is_last_attempt();
+        let recorded = match result {
+            Ok(()) => Job::mark_succeeded(pool, job.id).await,
+            Err(message) if job.is_last_attempt() => {
+                warn!(
+                    "Job {} ({}) failed after {} attempts: {}",
+                    job.id, job.kind, job.attempts, message
+                );
+                Job::mark_failed(pool, job.id, &message).await
+            }
+            Err(message) => {
+                let delay = retry_delay(job.attempts);
+                warn!(
+                    "Job {} ({}) failed, retrying in {:?}: {}",
+                    job.id, job.kind, delay, message
+                );
+                let run_at = Utc::now() + chrono::Duration::from_std(delay).unwrap_or_default();
+                Job::retry_at(pool, job.id, &message, run_at).await
+            }
+        };
+        if let Err(e) = recorded {
+            error!("Failed to record outcome of job {}: {}", job.id, e);
+        }
+
+        if finished
+            && job.unique_key.as_deref() == Some(schedule_key(handler.kind()).as_str())
+            && let Some(every) = handler.schedule()
+        {
+            self.schedule_next(handler.kind(), every).await;
+        }
+    }
+
+    /// Queue the scheduled handler's next run, unless one is already pending.
+    async fn schedule_next(&self, kind: &'static str, after: Duration) {
+        let data = CreateJob {
+            max_attempts: 1,
+            unique_key: Some(schedule_key(kind)),
+            run_at: Some(Utc::now() + chrono::Duration::from_std(after).unwrap_or_default()),
+            ..CreateJob::new(kind, json!({}))
+        };
+        if let Err(e) = self.queue.enqueue(&data).await {
+            error!("Failed to schedule the next {} job: {}", kind, e);
+        }
+    }
+}
+
+/// Deletes jobs that finished more than a week ago.
+struct PruneJobs {
+    pool: SqlitePool,
+}
+
+#[async_trait]
+impl JobHandler for PruneJobs {
+    fn kind(&self) -> &'static str {
+        "prune_jobs"
+    }
+
+    async fn run(&self, _job: &Job) -> Result<(), String> {
+        let cutoff = Utc::now() - chrono::Duration::days(FINISHED_JOB_RETENTION_DAYS);
+        let deleted = Job::delete_finished_before(&self.pool, cutoff)
+            .await
+            .map_err(|e| e.to_string())?;
+        if deleted > 0 {
+            info!("Deleted {} finished jobs", deleted);
+        }
+        Ok(())
+    }
+
+    fn schedule(&self) -> Option<Duration> {
+        Some(Duration::from_secs(24 * 60 * 60))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_an_hour() {
+        assert_eq!(retry_delay(1), Duration::from_secs(30));
+        assert_eq!(retry_delay(2), Duration::from_secs(60));
+        assert_eq!(retry_delay(4), Duration::from_secs(240));
+        assert_eq!(retry_delay(20), RETRY_MAX_DELAY);
+        assert_eq!(retry_delay(0), RETRY_BASE_DELAY);
+    }
+}
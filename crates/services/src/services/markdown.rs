@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+
+use ammonia::Builder;
+use db::models::{
+    integration::{Integration, IntegrationProvider},
+    integration_link::IntegrationLink,
+    user::User,
+};
+use once_cell::sync::Lazy;
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd, html};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use syntect::{
+    html::{ClassStyle, ClassedHTMLGenerator},
+    parsing::SyntaxSet,
+    util::LinesWithEndings,
+};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Prefix of the CSS classes put on highlighted code, so a client's stylesheet can
+/// theme them without clashing with its own classes.
+pub const HIGHLIGHT_CLASS_PREFIX: &str = "hl-";
+
+static SYNTAXES: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+
+/// An @handle or an issue key such as `#12` or `PROJ-34`. The character before it is
+/// checked separately, since the regex crate has no lookbehind.
+static REFERENCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"@[A-Za-z0-9_.\-]+|#[0-9]+|[A-Z][A-Z0-9_]*-[0-9]+").unwrap());
+
+static SANITIZER: Lazy<Builder<'static>> = Lazy::new(|| {
+    let mut builder = Builder::default();
+    builder
+        .add_tags(["input"])
+        .add_tag_attributes("a", ["class"])
+        .add_tag_attributes("code", ["class"])
+        .add_tag_attributes("span", ["class", "data-user-id"])
+        .add_tag_attributes("input", ["type", "checked", "disabled"])
+        .link_rel(Some("noopener noreferrer nofollow"));
+    builder
+});
+
+#[derive(Debug, Deserialize, TS, ToSchema)]
+pub struct RenderMarkdown {
+    pub markdown: String,
+    /// Project whose linked issues are autolinked; only @mentions are linked when
+    /// omitted
+    #[serde(default)]
+    #[ts(optional)]
+    pub project_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, TS, ToSchema)]
+pub struct RenderedMarkdown {
+    /// Sanitized HTML. Highlighted code tokens carry `hl-` prefixed classes, mentions
+    /// are `span.mention` with a `data-user-id` and issue references `a.issue-link`
+    pub html: String,
+}
+
+/// What references in the text can be linked to.
+#[derive(Debug, Default)]
+pub struct LinkTargets {
+    /// Lowercased mention handle -> user
+    pub users: HashMap<String, Uuid>,
+    /// Issue key as written in text (`#12` on GitHub, `PROJ-34` elsewhere) -> issue URL
+    pub issues: HashMap<String, String>,
+}
+
+impl LinkTargets {
+    /// Users by handle, the same way mentions are resolved, and the issues linked to
+    /// tasks of `project_id`.
+    pub async fn load(pool: &SqlitePool, project_id: Option<Uuid>) -> Result<Self, sqlx::Error> {
+        let mut targets = Self::default();
+        let mut ambiguous = Vec::new();
+        for user in User::find_all(pool).await? {
+            let handle = user.name.replace(' ', "").to_ascii_lowercase();
+            if targets.users.insert(handle.clone(), user.id).is_some() {
+                ambiguous.push(handle);
+            }
+        }
+        for handle in ambiguous {
+            targets.users.remove(&handle);
+        }
+
+        if let Some(project_id) = project_id {
+            for integration in Integration::find_by_project_id(pool, project_id).await? {
+                for link in IntegrationLink::find_by_integration_id(pool, integration.id).await? {
+                    let Some(url) = link.external_url else {
+                        continue;
+                    };
+                    let key = match integration.provider {
+                        IntegrationProvider::GitHub => format!("#{}", link.external_id),
+                        _ => link.external_id,
+                    };
+                    targets.issues.insert(key, url);
+                }
+            }
+        }
+        Ok(targets)
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape `text`, turning known @mentions and issue keys into links.
+fn autolink(text: &str, targets: &LinkTargets) -> String {
+    let is_word_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '@');
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for found in REFERENCE.find_iter(text) {
+        if text[..found.start()]
+            .chars()
+            .next_back()
+            .is_some_and(is_word_char)
+        {
+            continue;
+        }
+        let reference = found.as_str();
+        let replacement = if let Some(handle) = reference.strip_prefix('@') {
+            let handle = handle.trim_end_matches(['.', '-']);
+            targets
+                .users
+                .get(&handle.to_ascii_lowercase())
+                .map(|user_id| {
+                    (
+                        1 + handle.len(),
+                        format!(
+                            "<span class=\"mention\" data-user-id=\"{user_id}\">@{}</span>",
+                            escape(handle)
+                        ),
+                    )
+                })
+        } else if text[found.end()..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            None
+        } else {
+            targets.issues.get(reference).map(|url| {
+                (
+                    reference.len(),
+                    format!(
+                        "<a class=\"issue-link\" href=\"{}\">{}</a>",
+                        escape(url),
+                        escape(reference)
+                    ),
+                )
+            })
+        };
+        if let Some((len, html)) = replacement {
+            out.push_str(&escape(&text[last..found.start()]));
+            out.push_str(&html);
+            last = found.start() + len;
+        }
+    }
+    out.push_str(&escape(&text[last..]));
+    out
+}
+
+/// A fenced code block, highlighted when its language is known.
+fn highlight(code: &str, language: &str) -> String {
+    let body = SYNTAXES
+        .find_syntax_by_token(language)
+        .filter(|_| !language.is_empty())
+        .and_then(|syntax| {
+            let mut generator = ClassedHTMLGenerator::new_with_class_style(
+                syntax,
+                &SYNTAXES,
+                ClassStyle::SpacedPrefixed {
+                    prefix: HIGHLIGHT_CLASS_PREFIX,
+                },
+            );
+            for line in LinesWithEndings::from(code) {
+                generator
+                    .parse_html_for_line_which_includes_newline(line)
+                    .ok()?;
+            }
+            Some(generator.finalize())
+        });
+    let class = if language.is_empty() {
+        String::new()
+    } else {
+        format!(" class=\"language-{}\"", escape(language))
+    };
+    format!(
+        "<pre><code{class}>{}</code></pre>\n",
+        body.unwrap_or_else(|| escape(code))
+    )
+}
+
+/// Render task or comment Markdown (CommonMark with tables, strikethrough and task
+/// lists) to sanitized HTML, highlighting fenced code and linking the references in
+/// `targets`. Text inside code, links and image descriptions is left alone.
+pub fn render(markdown: &str, targets: &LinkTargets) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut events = Vec::new();
+    let mut link_depth = 0usize;
+    let mut code_block: Option<(String, String)> = None;
+    for event in Parser::new_ext(markdown, options) {
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split_whitespace().next().unwrap_or("").to_string()
+                    }
+                    CodeBlockKind::Indented => String::new(),
+                };
+                code_block = Some((language, String::new()));
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                if let Some((language, code)) = code_block.take() {
+                    events.push(Event::Html(CowStr::from(highlight(&code, &language))));
+                }
+            }
+            Event::Text(text) if code_block.is_some() => {
+                if let Some((_, code)) = code_block.as_mut() {
+                    code.push_str(&text);
+                }
+            }
+            Event::Start(Tag::Link { .. } | Tag::Image { .. }) => {
+                link_depth += 1;
+                events.push(event);
+            }
+            Event::End(TagEnd::Link | TagEnd::Image) => {
+                link_depth = link_depth.saturating_sub(1);
+                events.push(event);
+            }
+            Event::Text(text) if link_depth == 0 => {
+                events.push(Event::InlineHtml(CowStr::from(autolink(&text, targets))));
+            }
+            event => events.push(event),
+        }
+    }
+
+    let mut unsafe_html = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut unsafe_html, events.into_iter());
+    SANITIZER.clean(&unsafe_html).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets() -> LinkTargets {
+        LinkTargets {
+            users: HashMap::from([("alice".to_string(), Uuid::nil())]),
+            issues: HashMap::from([
+                (
+                    "#12".to_string(),
+                    "https://github.com/o/r/issues/12".to_string(),
+                ),
+                (
+                    "PROJ-3".to_string(),
+                    "https://jira.example.com/browse/PROJ-3".to_string(),
+                ),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_render_autolinks_references() {
+        let html = render(
+            "Ask @Alice. about #12 and PROJ-3, not #123, XPROJ-3, bob@alice or @carol",
+            &targets(),
+        );
+        assert!(html.contains(&format!(
+            "<span class=\"mention\" data-user-id=\"{}\">@Alice</span>.",
+            Uuid::nil()
+        )));
+        assert!(html.contains("href=\"https://github.com/o/r/issues/12\""));
+        assert!(html.contains(">#12</a> and"));
+        assert!(html.contains(">PROJ-3</a>,"));
+        assert!(html.contains("not #123, XPROJ-3, bob@alice or @carol"));
+    }
+
+    #[test]
+    fn test_render_leaves_code_and_links_alone() {
+        let html = render(
+            "`@alice` [see #12](https://example.com)\n\n```\n@alice #12\n```",
+            &targets(),
+        );
+        assert!(!html.contains("mention"));
+        assert!(!html.contains("issue-link"));
+        assert!(html.contains("<code>@alice</code>"));
+        assert!(html.contains("<pre><code>@alice #12\n</code></pre>"));
+    }
+
+    #[test]
+    fn test_render_highlights_and_sanitizes() {
+        let html = render(
+            "<script>alert(1)</script><img src=x onerror=alert(1)>\n\n```rust\nfn main() {}\n```",
+            &LinkTargets::default(),
+        );
+        assert!(!html.contains("script"));
+        assert!(!html.contains("onerror"));
+        assert!(html.contains("<code class=\"language-rust\">"));
+        assert!(html.contains("<span class=\"hl-source hl-rust\">"));
+    }
+}
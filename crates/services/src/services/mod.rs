@@ -1,6 +1,9 @@
 pub mod analytics;
 pub mod approvals;
+pub mod attachment;
 pub mod auth;
+pub mod auto_archive;
+pub mod backup;
 pub mod config;
 pub mod container;
 pub mod diff_stream;
@@ -12,13 +15,21 @@ pub mod filesystem_watcher;
 pub mod git;
 pub mod github;
 pub mod image;
+pub mod integrations;
+pub mod job_runner;
+pub mod markdown;
 pub mod notification;
 pub mod oauth_credentials;
+pub mod oidc;
 pub mod pr_monitor;
 pub mod project;
 pub mod queued_message;
+pub mod recurrence;
 pub mod remote_client;
 pub mod repo;
 pub mod share;
+pub mod sync_worker;
+pub mod trash;
+pub mod webhook_dispatcher;
 pub mod workspace_manager;
 pub mod worktree_manager;
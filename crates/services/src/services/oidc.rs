@@ -0,0 +1,454 @@
+//! OpenID Connect sign-in: the authorization code flow with PKCE against a configurable
+//! issuer such as Google, Keycloak or Okta. Signing in links the provider account to a
+//! local user, creating one the first time.
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use db::models::{
+    user::{CreateUser, User},
+    user_identity::UserIdentity,
+};
+use reqwest::Client;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::sync::OnceCell;
+use url::Url;
+use uuid::Uuid;
+
+/// How long a user has to finish signing in at the provider.
+pub const LOGIN_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Sign-ins waiting on the provider at once; past this the oldest is forgotten.
+const MAX_PENDING_LOGINS: usize = 1_000;
+
+const DEFAULT_SCOPES: &str = "openid email profile";
+
+#[derive(Debug, Error)]
+pub enum OidcError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("HTTP request failed: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("OIDC provider returned HTTP {status}: {body}")]
+    Http { status: u16, body: String },
+    #[error("Unexpected response from OIDC provider: {0}")]
+    InvalidResponse(String),
+    #[error("Sign-in expired or was already completed")]
+    UnknownState,
+    #[error("Invalid ID token: {0}")]
+    InvalidIdToken(String),
+}
+
+/// Read from `VK_OIDC_*` environment variables; sign-in is off unless the issuer, client
+/// ID, client secret and redirect URL are all set.
+#[derive(Clone)]
+pub struct OidcConfig {
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+    /// Where the provider sends the browser back to: this server's
+    /// `/api/auth/oidc/callback`
+    pub redirect_url: String,
+    pub scopes: String,
+}
+
+impl OidcConfig {
+    pub fn from_env() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        Some(Self {
+            issuer: var("VK_OIDC_ISSUER")?.trim_end_matches('/').to_string(),
+            client_id: var("VK_OIDC_CLIENT_ID")?,
+            client_secret: var("VK_OIDC_CLIENT_SECRET")?,
+            redirect_url: var("VK_OIDC_REDIRECT_URL")?,
+            scopes: var("VK_OIDC_SCOPES").unwrap_or_else(|| DEFAULT_SCOPES.to_string()),
+        })
+    }
+}
+
+/// The parts of the provider's `/.well-known/openid-configuration` sign-in needs.
+#[derive(Debug, Clone, Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Audience {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Audience {
+    fn contains(&self, client_id: &str) -> bool {
+        match self {
+            Self::One(aud) => aud == client_id,
+            Self::Many(auds) => auds.iter().any(|aud| aud == client_id),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct IdTokenClaims {
+    iss: String,
+    sub: String,
+    aud: Audience,
+    exp: i64,
+    nonce: Option<String>,
+    email: Option<String>,
+    email_verified: Option<bool>,
+    name: Option<String>,
+    preferred_username: Option<String>,
+}
+
+/// Who signed in, as the provider describes them.
+#[derive(Debug, Clone)]
+pub struct OidcProfile {
+    pub issuer: String,
+    pub subject: String,
+    pub name: String,
+    /// Only set when the provider hasn't said it is unverified
+    pub email: Option<String>,
+    /// Whether the provider vouched for the email; only then is it used to find an
+    /// existing user
+    pub email_verified: bool,
+}
+
+/// A sign-in sent to the provider.
+pub struct LoginStart {
+    /// Where to send the browser
+    pub url: String,
+    /// The `state` the provider will hand back, for the browser to prove it started the
+    /// sign-in
+    pub state: String,
+}
+
+struct PendingLogin {
+    nonce: String,
+    code_verifier: String,
+    started_at: Instant,
+}
+
+pub struct OidcService {
+    config: OidcConfig,
+    http: Client,
+    metadata: OnceCell<ProviderMetadata>,
+    pending: Mutex<HashMap<String, PendingLogin>>,
+}
+
+impl OidcService {
+    pub fn new(config: OidcConfig) -> Self {
+        Self {
+            config,
+            http: Client::new(),
+            metadata: OnceCell::new(),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// `None` when sign-in isn't configured.
+    pub fn from_env() -> Option<Self> {
+        OidcConfig::from_env().map(Self::new)
+    }
+
+    pub fn config(&self) -> &OidcConfig {
+        &self.config
+    }
+
+    async fn metadata(&self) -> Result<&ProviderMetadata, OidcError> {
+        self.metadata
+            .get_or_try_init(|| async {
+                let url = format!("{}/.well-known/openid-configuration", self.config.issuer);
+                let response = self.http.get(&url).send().await?;
+                let status = response.status();
+                if !status.is_success() {
+                    return Err(OidcError::Http {
+                        status: status.as_u16(),
+                        body: response.text().await.unwrap_or_default(),
+                    });
+                }
+                response
+                    .json::<ProviderMetadata>()
+                    .await
+                    .map_err(|e| OidcError::InvalidResponse(e.to_string()))
+            })
+            .await
+    }
+
+    /// Start signing in: remember the attempt and return the provider URL to send the
+    /// browser to.
+    pub async fn begin_login(&self) -> Result<LoginStart, OidcError> {
+        let metadata = self.metadata().await?;
+        let state = random_token();
+        let nonce = random_token();
+        let code_verifier = format!("{}{}", random_token(), random_token());
+        let code_challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()));
+
+        let mut url = Url::parse(&metadata.authorization_endpoint)
+            .map_err(|e| OidcError::InvalidResponse(format!("authorization_endpoint: {e}")))?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.config.client_id)
+            .append_pair("redirect_uri", &self.config.redirect_url)
+            .append_pair("scope", &self.config.scopes)
+            .append_pair("state", &state)
+            .append_pair("nonce", &nonce)
+            .append_pair("code_challenge", &code_challenge)
+            .append_pair("code_challenge_method", "S256");
+
+        self.remember(
+            state.clone(),
+            PendingLogin {
+                nonce,
+                code_verifier,
+                started_at: Instant::now(),
+            },
+        );
+        Ok(LoginStart {
+            url: url.into(),
+            state,
+        })
+    }
+
+    fn remember(&self, state: String, login: PendingLogin) {
+        let mut pending = self.pending.lock().unwrap();
+        pending.retain(|_, login| login.started_at.elapsed() < LOGIN_TIMEOUT);
+        if pending.len() >= MAX_PENDING_LOGINS
+            && let Some(oldest) = pending
+                .iter()
+                .min_by_key(|(_, login)| login.started_at)
+                .map(|(state, _)| state.clone())
+        {
+            pending.remove(&oldest);
+        }
+        pending.insert(state, login);
+    }
+
+    /// Finish signing in with the code the provider redirected back with.
+    pub async fn complete_login(&self, code: &str, state: &str) -> Result<OidcProfile, OidcError> {
+        let login = self
+            .pending
+            .lock()
+            .unwrap()
+            .remove(state)
+            .filter(|login| login.started_at.elapsed() < LOGIN_TIMEOUT)
+            .ok_or(OidcError::UnknownState)?;
+        let metadata = self.metadata().await?;
+
+        let response = self
+            .http
+            .post(&metadata.token_endpoint)
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", self.config.redirect_url.as_str()),
+                ("client_id", self.config.client_id.as_str()),
+                ("client_secret", self.config.client_secret.as_str()),
+                ("code_verifier", login.code_verifier.as_str()),
+            ])
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(OidcError::Http {
+                status: status.as_u16(),
+                body: response.text().await.unwrap_or_default(),
+            });
+        }
+        let id_token = response
+            .json::<TokenResponse>()
+            .await
+            .map_err(|e| OidcError::InvalidResponse(e.to_string()))?
+            .id_token
+            .ok_or_else(|| OidcError::InvalidResponse("no id_token in response".to_string()))?;
+
+        // The token came straight from the token endpoint over TLS, so the claims are
+        // checked but the signature isn't (OpenID Connect Core 3.1.3.7)
+        let claims = decode_claims(&id_token)?;
+        if claims.iss.trim_end_matches('/') != metadata.issuer.trim_end_matches('/') {
+            return Err(OidcError::InvalidIdToken(format!(
+                "issued by {}",
+                claims.iss
+            )));
+        }
+        if !claims.aud.contains(&self.config.client_id) {
+            return Err(OidcError::InvalidIdToken(
+                "issued for another client".to_string(),
+            ));
+        }
+        if claims.nonce.as_deref() != Some(login.nonce.as_str()) {
+            return Err(OidcError::InvalidIdToken("nonce mismatch".to_string()));
+        }
+        if DateTime::<Utc>::from_timestamp(claims.exp, 0).is_none_or(|exp| exp <= Utc::now()) {
+            return Err(OidcError::InvalidIdToken("expired".to_string()));
+        }
+
+        let email = claims
+            .email
+            .filter(|_| claims.email_verified != Some(false));
+        let name = claims
+            .name
+            .or(claims.preferred_username)
+            .or_else(|| email.clone())
+            .unwrap_or_else(|| claims.sub.clone());
+        Ok(OidcProfile {
+            issuer: self.config.issuer.clone(),
+            subject: claims.sub,
+            name,
+            email,
+            email_verified: claims.email_verified == Some(true),
+        })
+    }
+
+    /// The local user for a provider account. The first sign-in creates a user rather than
+    /// taking over one with the same email, since users can change their email through the
+    /// API. The email is kept when the provider verified it and no other user has it.
+    pub async fn resolve_user(pool: &SqlitePool, profile: &OidcProfile) -> Result<User, OidcError> {
+        if let Some(user) = UserIdentity::find_user(pool, &profile.issuer, &profile.subject).await?
+        {
+            return Ok(user);
+        }
+        let email = match profile.email.as_ref().filter(|_| profile.email_verified) {
+            Some(email) if User::find_by_email(pool, email).await?.is_none() => Some(email.clone()),
+            _ => None,
+        };
+        let user = User::create(
+            pool,
+            &CreateUser {
+                name: profile.name.clone(),
+                email,
+            },
+        )
+        .await?;
+        UserIdentity::link(pool, user.id, &profile.issuer, &profile.subject).await?;
+        Ok(user)
+    }
+}
+
+fn random_token() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+fn decode_claims(id_token: &str) -> Result<IdTokenClaims, OidcError> {
+    let payload = id_token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| OidcError::InvalidIdToken("not a JWT".to_string()))?;
+    let bytes = URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|e| OidcError::InvalidIdToken(e.to_string()))?;
+    serde_json::from_slice(&bytes).map_err(|e| OidcError::InvalidIdToken(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_claims_accepts_single_and_multiple_audiences() {
+        let encode = |claims: serde_json::Value| {
+            format!(
+                "e30.{}.sig",
+                URL_SAFE_NO_PAD.encode(claims.to_string().as_bytes())
+            )
+        };
+        let single = decode_claims(&encode(serde_json::json!({
+            "iss": "https://issuer", "sub": "42", "aud": "vk", "exp": 0
+        })))
+        .unwrap();
+        assert!(single.aud.contains("vk"));
+
+        let many = decode_claims(&encode(serde_json::json!({
+            "iss": "https://issuer", "sub": "42", "aud": ["other", "vk"], "exp": 0
+        })))
+        .unwrap();
+        assert!(many.aud.contains("vk"));
+        assert!(!many.aud.contains("nope"));
+        assert!(decode_claims("not-a-jwt").is_err());
+    }
+
+    #[test]
+    fn test_pending_logins_are_capped() {
+        let service = OidcService::new(OidcConfig {
+            issuer: "https://issuer".to_string(),
+            client_id: "vk".to_string(),
+            client_secret: "secret".to_string(),
+            redirect_url: "http://localhost/api/auth/oidc/callback".to_string(),
+            scopes: DEFAULT_SCOPES.to_string(),
+        });
+        let started_at = Instant::now();
+        for i in 0..=MAX_PENDING_LOGINS {
+            service.remember(
+                format!("state-{i}"),
+                PendingLogin {
+                    nonce: String::new(),
+                    code_verifier: String::new(),
+                    started_at: started_at + Duration::from_millis(i as u64),
+                },
+            );
+        }
+        let pending = service.pending.lock().unwrap();
+        assert_eq!(pending.len(), MAX_PENDING_LOGINS);
+        assert!(!pending.contains_key("state-0"));
+        assert!(pending.contains_key(&format!("state-{MAX_PENDING_LOGINS}")));
+    }
+
+    #[tokio::test]
+    async fn test_new_accounts_are_not_linked_by_email() {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        db::migrations::MIGRATOR.run(&pool).await.unwrap();
+        let existing = User::create(
+            &pool,
+            &CreateUser {
+                name: "Ada".to_string(),
+                email: Some("ada@example.com".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        let profile = |subject: &str, email: &str, email_verified| OidcProfile {
+            issuer: "https://issuer".to_string(),
+            subject: subject.to_string(),
+            name: "Ada".to_string(),
+            email: Some(email.to_string()),
+            email_verified,
+        };
+
+        let taken = OidcService::resolve_user(&pool, &profile("1", "ada@example.com", true))
+            .await
+            .unwrap();
+        assert_ne!(taken.id, existing.id);
+        assert_eq!(taken.email, None);
+        let unverified = OidcService::resolve_user(&pool, &profile("2", "bob@example.com", false))
+            .await
+            .unwrap();
+        assert_eq!(unverified.email, None);
+        let verified = OidcService::resolve_user(&pool, &profile("3", "cy@example.com", true))
+            .await
+            .unwrap();
+        assert_eq!(verified.email.as_deref(), Some("cy@example.com"));
+        // Signing in again finds the same user
+        let again = OidcService::resolve_user(&pool, &profile("3", "cy@example.com", true))
+            .await
+            .unwrap();
+        assert_eq!(again.id, verified.id);
+    }
+}
@@ -5,6 +5,7 @@ use db::{
     models::{
         merge::{Merge, MergeStatus, PrMerge},
         task::{Task, TaskStatus},
+        task_event::{TaskEvent, TaskEventSource},
         workspace::{Workspace, WorkspaceError},
     },
 };
@@ -128,7 +129,18 @@ impl PrMonitorService {
                     "PR #{} was merged, updating task {} to done",
                     pr_merge.pr_info.number, workspace.task_id
                 );
+                let previous = Task::find_by_id(&self.db.pool, workspace.task_id).await?;
                 Task::update_status(&self.db.pool, workspace.task_id, TaskStatus::Done).await?;
+                if let Some(previous) = previous {
+                    TaskEvent::record_status_change(
+                        &self.db.pool,
+                        previous.id,
+                        &previous.status,
+                        &TaskStatus::Done,
+                        TaskEventSource::System,
+                    )
+                    .await?;
+                }
 
                 // Track analytics event
                 if let Some(analytics) = &self.analytics
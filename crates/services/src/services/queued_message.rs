@@ -5,10 +5,11 @@ use dashmap::DashMap;
 use db::models::scratch::DraftFollowUpData;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 /// Represents a queued follow-up message for a session
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct QueuedMessage {
     /// The session this message is queued for
@@ -20,7 +21,7 @@ pub struct QueuedMessage {
 }
 
 /// Status of the queue for a session (for frontend display)
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "status", rename_all = "snake_case")]
 #[ts(export)]
 pub enum QueueStatus {
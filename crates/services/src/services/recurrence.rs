@@ -0,0 +1,212 @@
+use std::{collections::HashMap, time::Duration};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use db::{
+    DBService,
+    models::{
+        custom_field::CustomField,
+        job::Job,
+        label::Label,
+        recurrence_rule::RecurrenceRule,
+        task::{CreateTask, Task},
+        task_checklist_item::{CreateTaskChecklistItem, TaskChecklistItem},
+        task_event::{TaskEvent, TaskEventSource},
+        task_template::TaskTemplate,
+    },
+};
+use sqlx::SqlitePool;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::services::job_runner::JobHandler;
+
+/// Job run every minute for recurring work. Closed tasks with a recurrence rule get their
+/// next occurrence, and templates with a schedule get a new task when it is due. Runs
+/// missed while the app was closed are caught up with a single task, not one per run.
+pub struct RecurrenceJob {
+    db: DBService,
+}
+
+#[async_trait]
+impl JobHandler for RecurrenceJob {
+    fn kind(&self) -> &'static str {
+        "recurrence"
+    }
+
+    async fn run(&self, _job: &Job) -> Result<(), String> {
+        self.run_once(Utc::now()).await;
+        Ok(())
+    }
+
+    fn schedule(&self) -> Option<Duration> {
+        Some(Duration::from_secs(60))
+    }
+}
+
+impl RecurrenceJob {
+    pub fn new(db: DBService) -> Self {
+        Self { db }
+    }
+
+    async fn run_once(&self, now: DateTime<Utc>) {
+        let pool = &self.db.pool;
+        match RecurrenceRule::find_closed_task_rules(pool).await {
+            Ok(rules) => {
+                for rule in rules {
+                    if let Err(e) = Self::continue_series(pool, &rule, now).await {
+                        error!(
+                            "Failed to create next occurrence for rule {}: {}",
+                            rule.id, e
+                        );
+                    }
+                }
+            }
+            Err(e) => error!("Failed to load recurring tasks: {}", e),
+        }
+
+        match RecurrenceRule::find_due_template_rules(pool, now).await {
+            Ok(rules) => {
+                for rule in rules {
+                    if let Err(e) = Self::run_template(pool, &rule, now).await {
+                        error!(
+                            "Failed to run scheduled template for rule {}: {}",
+                            rule.id, e
+                        );
+                    }
+                }
+            }
+            Err(e) => error!("Failed to load scheduled templates: {}", e),
+        }
+    }
+
+    /// Create the next occurrence of a closed recurring task and move the rule to it.
+    /// Occurrences are spaced from the due date, so a late completion does not shift the
+    /// series; undated tasks recur without a due date.
+    async fn continue_series(
+        pool: &SqlitePool,
+        rule: &RecurrenceRule,
+        now: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        let Some(task_id) = rule.task_id else {
+            return Ok(());
+        };
+        let Some(task) = Task::find_by_id(pool, task_id).await? else {
+            return Ok(());
+        };
+        let recurrence = match rule.recurrence() {
+            Ok(recurrence) => recurrence,
+            Err(e) => {
+                warn!("Dropping invalid recurrence rule {}: {}", rule.id, e);
+                RecurrenceRule::delete(pool, rule.id).await?;
+                return Ok(());
+            }
+        };
+
+        let anchor = task.due_at.unwrap_or(task.updated_at);
+        let Some(next) = recurrence.next_after_now(anchor, now) else {
+            info!("Recurring task {} reached the end of its rule", task.id);
+            RecurrenceRule::delete(pool, rule.id).await?;
+            return Ok(());
+        };
+
+        let occurrence = Self::create_occurrence(pool, &task, task.due_at.map(|_| next)).await?;
+        RecurrenceRule::move_to_task(pool, rule.id, occurrence.id).await?;
+        info!(
+            "Created occurrence {} of recurring task {}",
+            occurrence.id, task.id
+        );
+        Ok(())
+    }
+
+    /// Copy a task's details, labels, custom field values and (unchecked) checklist into
+    /// a new open task.
+    async fn create_occurrence(
+        pool: &SqlitePool,
+        task: &Task,
+        due_at: Option<DateTime<Utc>>,
+    ) -> Result<Task, sqlx::Error> {
+        let mut create = CreateTask::from_title_description(
+            task.project_id,
+            task.title.clone(),
+            task.description.clone(),
+        );
+        create.due_at = due_at;
+        create.priority = Some(task.priority);
+        create.assignee_id = task.assignee_id;
+        let occurrence = Task::create(pool, &create, Uuid::new_v4()).await?;
+        TaskEvent::record_created(pool, &occurrence, TaskEventSource::System, None).await?;
+
+        let label_ids: Vec<_> = Label::find_by_task_id(pool, task.id)
+            .await?
+            .into_iter()
+            .map(|label| label.id)
+            .collect();
+        if !label_ids.is_empty() {
+            Label::set_for_task(pool, occurrence.id, &label_ids).await?;
+        }
+        for value in CustomField::find_values_for_task(pool, task.id).await? {
+            CustomField::set_value(pool, occurrence.id, value.field_id, &value.value).await?;
+        }
+        for item in TaskChecklistItem::find_by_task_id(pool, task.id).await? {
+            TaskChecklistItem::create(
+                pool,
+                occurrence.id,
+                &CreateTaskChecklistItem { title: item.title },
+            )
+            .await?;
+        }
+        Ok(occurrence)
+    }
+
+    /// Create a task from a scheduled template and move its schedule to the next run.
+    async fn run_template(
+        pool: &SqlitePool,
+        rule: &RecurrenceRule,
+        now: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        let Some(template_id) = rule.template_id else {
+            return Ok(());
+        };
+        let Some(template) = TaskTemplate::find_by_id(pool, template_id).await? else {
+            return Ok(());
+        };
+        let recurrence = match rule.recurrence() {
+            Ok(recurrence) => recurrence,
+            Err(e) => {
+                warn!("Dropping invalid recurrence rule {}: {}", rule.id, e);
+                RecurrenceRule::delete(pool, rule.id).await?;
+                return Ok(());
+            }
+        };
+
+        // Templates may have gained placeholders since the schedule was set
+        match template.render(&HashMap::new(), now) {
+            Ok((title, description)) => {
+                let task = template.instantiate(pool, title, description).await?;
+                TaskEvent::record_created(pool, &task, TaskEventSource::System, None).await?;
+                info!(
+                    "Created task {} from scheduled template {}",
+                    task.id, template.id
+                );
+            }
+            Err(missing) => warn!(
+                "Skipping scheduled run of template {}: no value for {}",
+                template.id,
+                missing.join(", ")
+            ),
+        }
+
+        let scheduled = rule.next_run_at.unwrap_or(now);
+        match recurrence.next_after_now(scheduled, now) {
+            Some(next) => RecurrenceRule::update_next_run_at(pool, rule.id, next).await,
+            None => {
+                info!(
+                    "Schedule of template {} reached the end of its rule",
+                    template.id
+                );
+                RecurrenceRule::delete(pool, rule.id).await.map(|_| ())
+            }
+        }
+    }
+}
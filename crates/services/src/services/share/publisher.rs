@@ -3,11 +3,13 @@ use db::{
     models::{
         project::Project,
         task::{CreateTask, Task, TaskStatus},
+        task_event::{TaskEvent, TaskEventSource},
     },
 };
 use remote::routes::tasks::{
     AssignSharedTaskRequest, CreateSharedTaskRequest, SharedTaskResponse, UpdateSharedTaskRequest,
 };
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use super::{ShareError, status};
@@ -19,7 +21,7 @@ pub struct SharePublisher {
     client: RemoteClient,
 }
 
-#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, ts_rs::TS)]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, ts_rs::TS, ToSchema)]
 pub struct SharedTaskDetails {
     pub id: Uuid,
     pub project_id: Uuid,
@@ -145,6 +147,7 @@ impl SharePublisher {
 
         let id = Uuid::new_v4();
         let task = Task::create(&self.db.pool, &create_task, id).await?;
+        TaskEvent::record_created(&self.db.pool, &task, TaskEventSource::Sync, None).await?;
 
         Ok(Some(task))
     }
@@ -0,0 +1,194 @@
+use std::time::Instant;
+
+use async_trait::async_trait;
+use db::models::{
+    integration::Integration,
+    job::{CreateJob, Job},
+    sync_job::SyncJob,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::SqlitePool;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use crate::services::{
+    events::{ProjectActivityLog, ProjectEventKind},
+    integrations::{IntegrationService, SyncSummary},
+    job_runner::{JobHandler, JobQueue},
+};
+
+/// Attempts at a sync run before it is marked failed.
+const SYNC_MAX_ATTEMPTS: i64 = 3;
+
+/// Count the finished attempt, how long it took, and what it did to tasks, by provider.
+fn record_run(integration: &Integration, summary: Option<&SyncSummary>, started: Instant) {
+    let provider = integration.provider.to_string();
+    let outcome = if summary.is_some() {
+        "succeeded"
+    } else {
+        "failed"
+    };
+    metrics::counter!(
+        "integration_sync_runs_total",
+        "provider" => provider.clone(),
+        "outcome" => outcome
+    )
+    .increment(1);
+    metrics::histogram!("integration_sync_duration_seconds", "provider" => provider.clone())
+        .record(started.elapsed().as_secs_f64());
+    if let Some(summary) = summary {
+        for (result, count) in [
+            ("created", summary.created),
+            ("updated", summary.updated),
+            ("unchanged", summary.unchanged),
+            ("failed", summary.failed),
+        ] {
+            metrics::counter!(
+                "integration_sync_items_total",
+                "provider" => provider.clone(),
+                "result" => result
+            )
+            .increment(count as u64);
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SyncJobPayload {
+    sync_job_id: Uuid,
+}
+
+/// Queue a sync run for the integration, with the job that runs it.
+pub async fn queue_sync(
+    pool: &SqlitePool,
+    jobs: &JobQueue,
+    integration_id: Uuid,
+) -> Result<SyncJob, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let sync_job = SyncJob::enqueue(&mut *tx, integration_id).await?;
+    let payload = serde_json::to_value(SyncJobPayload {
+        sync_job_id: sync_job.id,
+    })
+    .unwrap_or_default();
+    let data = CreateJob {
+        max_attempts: SYNC_MAX_ATTEMPTS,
+        ..CreateJob::new(SyncJobHandler::KIND, payload)
+    };
+    Job::create(&mut *tx, &data).await?;
+    tx.commit().await?;
+    jobs.notify();
+    Ok(sync_job)
+}
+
+/// Runs queued `sync_jobs`. A failed run is requeued until it is out of attempts, and
+/// only then reported as failed.
+pub struct SyncJobHandler {
+    pool: SqlitePool,
+    integrations: IntegrationService,
+    activity: ProjectActivityLog,
+}
+
+impl SyncJobHandler {
+    pub const KIND: &'static str = "integration_sync";
+
+    pub fn new(
+        pool: SqlitePool,
+        integrations: IntegrationService,
+        activity: ProjectActivityLog,
+    ) -> Self {
+        Self {
+            pool,
+            integrations,
+            activity,
+        }
+    }
+}
+
+#[async_trait]
+impl JobHandler for SyncJobHandler {
+    fn kind(&self) -> &'static str {
+        Self::KIND
+    }
+
+    async fn run(&self, job: &Job) -> Result<(), String> {
+        let pool = &self.pool;
+        let payload: SyncJobPayload =
+            serde_json::from_value(job.payload.0.clone()).map_err(|e| e.to_string())?;
+        // Gone with its integration
+        let Some(sync_job) = SyncJob::find_by_id(pool, payload.sync_job_id)
+            .await
+            .map_err(|e| e.to_string())?
+        else {
+            return Ok(());
+        };
+        SyncJob::mark_running(pool, sync_job.id)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let integration = Integration::find_by_id(pool, sync_job.integration_id).await;
+        let project_id = match &integration {
+            Ok(Some(integration)) => Some(integration.project_id),
+            _ => None,
+        };
+        let outcome = match integration {
+            Ok(Some(integration)) => {
+                let started = Instant::now();
+                let outcome = self
+                    .integrations
+                    .sync(pool, &integration, Some(sync_job.id))
+                    .await
+                    .map_err(|e| e.to_string());
+                record_run(&integration, outcome.as_ref().ok(), started);
+                outcome
+            }
+            Ok(None) => Err(format!("Integration {} not found", sync_job.integration_id)),
+            Err(e) => Err(e.to_string()),
+        };
+
+        let recorded = match &outcome {
+            Ok(summary) => {
+                let result = serde_json::to_value(summary).unwrap_or_default();
+                if let Some(project_id) = project_id {
+                    self.activity.publish(
+                        project_id,
+                        ProjectEventKind::SyncSucceeded,
+                        None,
+                        json!({
+                            "job_id": sync_job.id,
+                            "integration_id": sync_job.integration_id,
+                            "summary": result,
+                        }),
+                    );
+                }
+                SyncJob::mark_succeeded(pool, sync_job.id, &result).await
+            }
+            Err(message) if !job.is_last_attempt() => {
+                SyncJob::requeue(pool, sync_job.id, message).await
+            }
+            Err(message) => {
+                warn!("Sync job {} failed: {}", sync_job.id, message);
+                if let Some(project_id) = project_id {
+                    self.activity.publish(
+                        project_id,
+                        ProjectEventKind::SyncFailed,
+                        None,
+                        json!({
+                            "job_id": sync_job.id,
+                            "integration_id": sync_job.integration_id,
+                            "error": message,
+                        }),
+                    );
+                }
+                SyncJob::mark_failed(pool, sync_job.id, message).await
+            }
+        };
+        if let Err(e) = recorded {
+            error!(
+                "Failed to record outcome of sync job {}: {}",
+                sync_job.id, e
+            );
+        }
+        outcome.map(|_| ())
+    }
+}
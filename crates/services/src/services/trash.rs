@@ -0,0 +1,139 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use db::{
+    DBService,
+    models::{
+        job::Job,
+        repo::Repo,
+        task::Task,
+        workspace::{Workspace, WorkspaceError},
+        workspace_repo::WorkspaceRepo,
+    },
+};
+use sqlx::SqlitePool;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+use crate::services::{
+    config::Config, job_runner::JobHandler, workspace_manager::WorkspaceManager,
+};
+
+/// Hourly job that permanently deletes tasks that have been in the trash for longer than
+/// the configured retention period.
+pub struct TrashPurgeJob {
+    db: DBService,
+    config: Arc<RwLock<Config>>,
+}
+
+impl TrashPurgeJob {
+    pub fn new(db: DBService, config: Arc<RwLock<Config>>) -> Self {
+        Self { db, config }
+    }
+}
+
+#[async_trait]
+impl JobHandler for TrashPurgeJob {
+    fn kind(&self) -> &'static str {
+        "trash_purge"
+    }
+
+    async fn run(&self, _job: &Job) -> Result<(), String> {
+        let retention_days = self.config.read().await.trash_retention_days.max(1);
+        let cutoff = Utc::now() - chrono::Duration::days(retention_days.into());
+        let tasks = Task::find_trashed_before(&self.db.pool, cutoff)
+            .await
+            .map_err(|e| format!("Failed to load expired trashed tasks: {e}"))?;
+
+        for task in tasks {
+            match purge_task(&self.db.pool, &task).await {
+                Ok(()) => info!(
+                    "Purged task {} after {} days in the trash",
+                    task.id, retention_days
+                ),
+                Err(e) => error!("Failed to purge trashed task {}: {}", task.id, e),
+            }
+        }
+        Ok(())
+    }
+
+    fn schedule(&self) -> Option<Duration> {
+        Some(Duration::from_secs(60 * 60))
+    }
+}
+
+/// Permanently delete a task along with its workspaces. Worktrees are cleaned up in the
+/// background once the rows are gone.
+pub async fn purge_task(pool: &SqlitePool, task: &Task) -> Result<(), WorkspaceError> {
+    let attempts = Workspace::fetch_all(pool, Some(task.id)).await?;
+    let repositories = WorkspaceRepo::find_unique_repos_for_task(pool, task.id).await?;
+
+    // Collect workspace directories that need cleanup
+    let workspace_dirs: Vec<PathBuf> = attempts
+        .iter()
+        .filter_map(|attempt| attempt.container_ref.as_ref().map(PathBuf::from))
+        .collect();
+
+    // Use a transaction to ensure atomicity: either all operations succeed or all are rolled back
+    let mut tx = pool.begin().await?;
+
+    // Nullify parent_workspace_id for all child tasks before deletion
+    // This breaks parent-child relationships to avoid foreign key constraint violations
+    let mut total_children_affected = 0u64;
+    for attempt in &attempts {
+        total_children_affected +=
+            Task::nullify_children_by_workspace_id(&mut *tx, attempt.id).await?;
+    }
+
+    // Delete task from database (FK CASCADE will handle task_attempts)
+    if Task::delete(&mut *tx, task.id).await? == 0 {
+        return Err(WorkspaceError::TaskNotFound);
+    }
+
+    tx.commit().await?;
+
+    if total_children_affected > 0 {
+        info!(
+            "Nullified {} child task references before deleting task {}",
+            total_children_affected, task.id
+        );
+    }
+
+    let task_id = task.id;
+    let pool = pool.clone();
+    tokio::spawn(async move {
+        info!(
+            "Starting background cleanup for task {} ({} workspaces, {} repos)",
+            task_id,
+            workspace_dirs.len(),
+            repositories.len()
+        );
+
+        for workspace_dir in &workspace_dirs {
+            if let Err(e) = WorkspaceManager::cleanup_workspace(workspace_dir, &repositories).await
+            {
+                error!(
+                    "Background workspace cleanup failed for task {} at {}: {}",
+                    task_id,
+                    workspace_dir.display(),
+                    e
+                );
+            }
+        }
+
+        match Repo::delete_orphaned(&pool).await {
+            Ok(count) if count > 0 => {
+                info!("Deleted {} orphaned repo records", count);
+            }
+            Err(e) => {
+                error!("Failed to delete orphaned repos: {}", e);
+            }
+            _ => {}
+        }
+
+        info!("Background cleanup completed for task {}", task_id);
+    });
+
+    Ok(())
+}
@@ -0,0 +1,283 @@
+//! Outgoing webhooks: every project event is POSTed as JSON to the project's webhooks that
+//! want it, signed like GitHub's deliveries, and each attempt is logged with its reply.
+//! Deliveries are queued as jobs, so a receiver that is down gets them retried.
+
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use db::{
+    DBService,
+    models::{
+        job::{CreateJob, Job},
+        webhook::{CreateWebhookDelivery, Webhook, WebhookDelivery},
+    },
+};
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sha2::Sha256;
+use sqlx::SqlitePool;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::services::{
+    events::{ProjectActivityLog, ProjectEvent},
+    job_runner::{JobHandler, JobQueue},
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Event sent by the test endpoint, so receivers can tell it apart from real activity.
+pub const PING_EVENT: &str = "ping";
+
+/// Reply bytes kept in the delivery log.
+const RESPONSE_BODY_LIMIT: usize = 4096;
+
+/// Attempts at delivering an event before giving up on it.
+const DELIVERY_MAX_ATTEMPTS: i64 = 5;
+
+const DELIVERY_JOB_KIND: &str = "webhook_delivery";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DeliveryPayload {
+    webhook_id: Uuid,
+    event: String,
+    payload: Value,
+}
+
+/// `sha256=<hex>` HMAC of the body, keyed with the webhook's secret.
+pub fn signature(secret: &str, body: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+fn truncate(mut body: String) -> String {
+    if body.len() > RESPONSE_BODY_LIMIT {
+        let mut end = RESPONSE_BODY_LIMIT;
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        body.truncate(end);
+    }
+    body
+}
+
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    http: Client,
+}
+
+impl Default for WebhookDispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WebhookDispatcher {
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+    pub fn new() -> Self {
+        let http = Client::builder()
+            .timeout(Self::REQUEST_TIMEOUT)
+            .user_agent(concat!("vibe-kanban/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default();
+        Self { http }
+    }
+
+    /// Queue deliveries of project activity to webhooks for as long as the log lives.
+    /// Events wait for the dispatcher however far behind it falls, so none are skipped.
+    pub async fn spawn(
+        db: DBService,
+        activity: ProjectActivityLog,
+        jobs: JobQueue,
+    ) -> tokio::task::JoinHandle<()> {
+        let mut receiver = activity.subscribe_unbounded();
+        tokio::spawn(async move {
+            info!("Starting webhook dispatcher");
+            while let Some(event) = receiver.recv().await {
+                Self::dispatch(&db.pool, &jobs, &event).await;
+            }
+        })
+    }
+
+    /// Queue a delivery of the event to each of its project's webhooks that wants it. The
+    /// job runner sends them side by side, so a slow receiver doesn't hold up the others.
+    async fn dispatch(pool: &SqlitePool, jobs: &JobQueue, event: &ProjectEvent) {
+        let webhooks = match Webhook::find_by_project_id(pool, event.project_id).await {
+            Ok(webhooks) => webhooks,
+            Err(e) => {
+                error!(
+                    "Failed to load webhooks of project {}: {}",
+                    event.project_id, e
+                );
+                return;
+            }
+        };
+        let name = event.kind.as_str();
+        let payload = serde_json::to_value(event).unwrap_or_default();
+        for webhook in webhooks.into_iter().filter(|w| w.wants(name)) {
+            let delivery = DeliveryPayload {
+                webhook_id: webhook.id,
+                event: name.to_string(),
+                payload: payload.clone(),
+            };
+            let data = CreateJob {
+                max_attempts: DELIVERY_MAX_ATTEMPTS,
+                ..CreateJob::new(
+                    DELIVERY_JOB_KIND,
+                    serde_json::to_value(delivery).unwrap_or_default(),
+                )
+            };
+            if let Err(e) = jobs.enqueue(&data).await {
+                error!("Failed to queue delivery to webhook {}: {}", webhook.id, e);
+            }
+        }
+    }
+
+    /// Send a `ping` so a receiver can be checked without waiting for activity.
+    pub async fn ping(
+        &self,
+        pool: &SqlitePool,
+        webhook: &Webhook,
+        secret: &str,
+    ) -> Result<WebhookDelivery, sqlx::Error> {
+        let payload = json!({
+            "id": 0,
+            "project_id": webhook.project_id,
+            "kind": PING_EVENT,
+            "task_id": null,
+            "data": { "webhook_id": webhook.id },
+            "created_at": Utc::now(),
+        });
+        self.deliver(pool, webhook, secret, PING_EVENT, &payload)
+            .await
+    }
+
+    /// POST one event and log how it went. Failures to reach the receiver are logged as
+    /// deliveries, not returned.
+    pub async fn deliver(
+        &self,
+        pool: &SqlitePool,
+        webhook: &Webhook,
+        secret: &str,
+        event: &str,
+        payload: &Value,
+    ) -> Result<WebhookDelivery, sqlx::Error> {
+        let body = payload.to_string();
+        let started = Instant::now();
+        let response = self
+            .http
+            .post(&webhook.url)
+            .header("Content-Type", "application/json")
+            .header("X-VK-Event", event)
+            .header("X-VK-Delivery", Uuid::new_v4().to_string())
+            .header("X-VK-Signature", signature(secret, body.as_bytes()))
+            .body(body.clone())
+            .send()
+            .await;
+        let (status_code, response_body, error) = match response {
+            Ok(response) => {
+                let status = response.status();
+                let text = response.text().await.unwrap_or_default();
+                let error = (!status.is_success()).then(|| format!("Receiver returned {status}"));
+                (Some(status.as_u16() as i64), Some(truncate(text)), error)
+            }
+            Err(e) => (None, None, Some(e.to_string())),
+        };
+        metrics::counter!(
+            "webhook_deliveries_total",
+            "event" => event.to_string(),
+            "outcome" => if error.is_none() { "delivered" } else { "failed" }
+        )
+        .increment(1);
+        WebhookDelivery::create(
+            pool,
+            webhook.id,
+            &CreateWebhookDelivery {
+                event: event.to_string(),
+                payload: body,
+                status_code,
+                response_body,
+                error,
+                duration_ms: started.elapsed().as_millis() as i64,
+            },
+        )
+        .await
+    }
+}
+
+/// Sends one queued delivery. A delivery the receiver didn't accept fails the attempt, so
+/// it is retried with backoff; every attempt shows in the delivery log.
+pub struct WebhookDeliveryJob {
+    db: DBService,
+    dispatcher: WebhookDispatcher,
+}
+
+impl WebhookDeliveryJob {
+    pub fn new(db: DBService) -> Self {
+        Self {
+            db,
+            dispatcher: WebhookDispatcher::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl JobHandler for WebhookDeliveryJob {
+    fn kind(&self) -> &'static str {
+        DELIVERY_JOB_KIND
+    }
+
+    async fn run(&self, job: &Job) -> Result<(), String> {
+        let pool = &self.db.pool;
+        let delivery: DeliveryPayload =
+            serde_json::from_value(job.payload.0.clone()).map_err(|e| e.to_string())?;
+        let webhook = Webhook::find_by_id(pool, delivery.webhook_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        let secret = Webhook::find_secret(pool, delivery.webhook_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        let (Some(webhook), Some(secret)) = (webhook, secret) else {
+            // Deleted since the event
+            return Ok(());
+        };
+
+        let logged = self
+            .dispatcher
+            .deliver(pool, &webhook, &secret, &delivery.event, &delivery.payload)
+            .await
+            .map_err(|e| format!("Failed to log delivery to webhook {}: {e}", webhook.id))?;
+        match logged.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::integrations::webhooks::verify_hmac_sha256;
+
+    #[test]
+    fn test_signature_verifies_like_inbound_deliveries() {
+        let body = br#"{"kind":"task.created"}"#;
+        let header = signature("s3cret", body);
+        assert!(verify_hmac_sha256(b"s3cret", &header, body));
+        assert!(!verify_hmac_sha256(b"other", &header, body));
+    }
+
+    #[test]
+    fn test_truncate_keeps_char_boundaries() {
+        let body = "é".repeat(RESPONSE_BODY_LIMIT);
+        let kept = truncate(body);
+        assert!(kept.len() <= RESPONSE_BODY_LIMIT);
+        assert!(kept.chars().all(|c| c == 'é'));
+        assert_eq!(truncate("ok".to_string()), "ok");
+    }
+}
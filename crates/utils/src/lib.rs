@@ -46,6 +46,28 @@ pub fn is_wsl2() -> bool {
     })
 }
 
+/// Whether a flag's value turns it on (`1`, `true`) or off (`0`, `false`).
+pub fn parse_flag(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" => Some(true),
+        "0" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// A flag set in the environment, or `default` when it is unset or not a flag value.
+pub fn env_flag_or(name: &str, default: bool) -> bool {
+    env::var(name)
+        .ok()
+        .and_then(|value| parse_flag(&value))
+        .unwrap_or(default)
+}
+
+/// A flag that is off unless the environment sets it to `1` or `true`.
+pub fn env_flag(name: &str) -> bool {
+    env_flag_or(name, false)
+}
+
 pub fn cache_dir() -> std::path::PathBuf {
     let proj = if cfg!(debug_assertions) {
         ProjectDirs::from("ai", "bloop-dev", env!("CARGO_PKG_NAME"))
@@ -98,3 +120,18 @@ pub async fn get_powershell_script()
 
     Ok(script_path)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_flag() {
+        assert_eq!(parse_flag("1"), Some(true));
+        assert_eq!(parse_flag(" TRUE "), Some(true));
+        assert_eq!(parse_flag("0"), Some(false));
+        assert_eq!(parse_flag("false"), Some(false));
+        assert_eq!(parse_flag("yes"), None);
+        assert_eq!(parse_flag(""), None);
+    }
+}
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct ApiResponse<T, E = T> {
     success: bool,
     data: Option<T>,
@@ -20,6 +21,16 @@ impl<T, E> ApiResponse<T, E> {
         }
     }
 
+    /// Creates a successful response, with `data` and a `message` such as a warning.
+    pub fn success_with_message(data: T, message: &str) -> Self {
+        ApiResponse {
+            success: true,
+            data: Some(data),
+            message: Some(message.to_string()),
+            error_data: None,
+        }
+    }
+
     /// Creates an error response, with `message` and no data.
     pub fn error(message: &str) -> Self {
         ApiResponse {
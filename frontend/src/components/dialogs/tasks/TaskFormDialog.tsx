@@ -175,6 +175,7 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
             parent_workspace_id: null,
             image_ids: images.length > 0 ? images.map((img) => img.id) : null,
           },
+          version: props.task.version,
         },
         { onSuccess: () => modal.remove() }
       );
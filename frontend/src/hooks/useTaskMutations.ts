@@ -68,8 +68,15 @@ export function useTaskMutations(projectId?: string) {
   });
 
   const updateTask = useMutation({
-    mutationFn: ({ taskId, data }: { taskId: string; data: UpdateTask }) =>
-      tasksApi.update(taskId, data),
+    mutationFn: ({
+      taskId,
+      data,
+      version,
+    }: {
+      taskId: string;
+      data: UpdateTask;
+      version?: bigint;
+    }) => tasksApi.update(taskId, data, version),
     onSuccess: (updatedTask: Task) => {
       invalidateQueries(updatedTask.id);
     },
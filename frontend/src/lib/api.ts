@@ -410,9 +410,15 @@ export const tasksApi = {
     return handleApiResponse<TaskWithAttemptStatus>(response);
   },
 
-  update: async (taskId: string, data: UpdateTask): Promise<Task> => {
+  // Pass the task's version to refuse the update if the task changed since it was read
+  update: async (
+    taskId: string,
+    data: UpdateTask,
+    version?: bigint
+  ): Promise<Task> => {
     const response = await makeRequest(`/api/tasks/${taskId}`, {
       method: 'PUT',
+      headers: { 'If-Match': version === undefined ? '*' : `"${version}"` },
       body: JSON.stringify(data),
     });
     return handleApiResponse<Task>(response);
@@ -757,13 +757,17 @@ export function ProjectTasks() {
       if (!task || task.status === newStatus) return;
 
       try {
-        await tasksApi.update(draggedTaskId, {
-          title: task.title,
-          description: task.description,
-          status: newStatus,
-          parent_workspace_id: task.parent_workspace_id,
-          image_ids: null,
-        });
+        await tasksApi.update(
+          draggedTaskId,
+          {
+            title: task.title,
+            description: task.description,
+            status: newStatus,
+            parent_workspace_id: task.parent_workspace_id,
+            image_ids: null,
+          },
+          task.version
+        );
       } catch (err) {
         console.error('Failed to update task status:', err);
       }
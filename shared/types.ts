@@ -12,47 +12,1005 @@ export type SharedTask = { id: string, organization_id: string, project_id: stri
 
 export type UserData = { user_id: string, first_name: string | null, last_name: string | null, username: string | null, };
 
-export type Project = { id: string, name: string, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, remote_project_id: string | null, created_at: Date, updated_at: Date, };
+export type Project = { id: string, name: string, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, remote_project_id: string | null, 
+/**
+ * The team whose members can reach the project; open to everyone when null
+ */
+team_id: string | null, created_at: Date, updated_at: Date, };
+
+export type CreateProject = { name: string, repositories: Array<CreateProjectRepo>, team_id?: string, };
+
+export type UpdateProject = { name: string | null, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, };
+
+export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, };
+
+export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
+
+export type Repo = { id: string, path: string, name: string, display_name: string, created_at: Date, updated_at: Date, };
+
+export type ProjectRepo = { id: string, project_id: string, repo_id: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, };
+
+export type CreateProjectRepo = { display_name: string, git_repo_path: string, };
+
+export type UpdateProjectRepo = { setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean | null, };
+
+export type WorkspaceRepo = { id: string, workspace_id: string, repo_id: string, target_branch: string, created_at: Date, updated_at: Date, };
+
+export type CreateWorkspaceRepo = { repo_id: string, target_branch: string, };
+
+export type RepoWithTargetBranch = { target_branch: string, id: string, path: string, name: string, display_name: string, created_at: Date, updated_at: Date, };
+
+export type Tag = { id: string, tag_name: string, content: string, created_at: string, updated_at: string, };
+
+export type CreateTag = { tag_name: string, content: string, };
+
+export type UpdateTag = { tag_name: string | null, content: string | null, };
+
+export type Label = { id: string, project_id: string, name: string, 
+/**
+ * Hex color, `#rrggbb`
+ */
+color: string, created_at: string, updated_at: string, };
+
+export type CreateLabel = { name: string, color: string | null, };
+
+export type UpdateLabel = { name: string | null, color: string | null, };
+
+export type SetTaskLabels = { label_ids: Array<string>, };
+
+export type CustomFieldType = "text" | "number" | "select" | "date";
+
+export type CustomField = { id: string, project_id: string, name: string, field_type: CustomFieldType, 
+/**
+ * Allowed values of a `select` field; empty for other types
+ */
+options: Array<string>, created_at: string, updated_at: string, };
+
+export type CreateCustomField = { name: string, field_type: CustomFieldType, options: Array<string>, };
+
+export type UpdateCustomField = { name: string | null, options: Array<string> | null, };
+
+export type SetCustomFieldValue = { value: string, };
+
+export type TaskCustomFieldValue = { field_id: string, name: string, field_type: CustomFieldType, value: string, updated_at: string, };
+
+export type User = { id: string, name: string, 
+/**
+ * Used to match assignees imported from external trackers
+ */
+email: string | null, created_at: string, updated_at: string, };
+
+export type CreateUser = { name: string, email: string | null, };
+
+export type UpdateUser = { name: string | null, email: string | null, };
+
+export type ApiKeyScope = "read" | "write" | "admin";
+
+export type ApiKey = { id: string, name: string, 
+/**
+ * The first characters of the key, e.g. `vk_3fZk9q`
+ */
+prefix: string, scopes: Array<ApiKeyScope>, 
+/**
+ * The user requests made with the key act as. Without one, the key is held to the
+ * project role its scopes imply
+ */
+user_id: string | null, 
+/**
+ * The only project the key can reach; all projects when null
+ */
+project_id: string | null, 
+/**
+ * The only team whose projects the key can reach; all teams when null
+ */
+team_id: string | null, expires_at: string | null, last_used_at: string | null, revoked_at: string | null, created_at: string, };
+
+export type CreateApiKey = { name: string, scopes: Array<ApiKeyScope>, 
+/**
+ * Only the caller's own id; keys created by a signed-in user act as them anyway
+ */
+user_id?: string, project_id?: string, team_id?: string, expires_at?: string, };
+
+export type AuditPrincipal = "session" | "api_key" | "anonymous";
+
+export type AuditEntry = { id: string, method: string, path: string, 
+/**
+ * HTTP status of the response; refused requests are kept too
+ */
+status: bigint, principal: AuditPrincipal, user_id: string | null, 
+/**
+ * The user's name at the time, kept after the user is deleted
+ */
+user_name: string | null, api_key_id: string | null, api_key_prefix: string | null, 
+/**
+ * The project the request touched, when its path or query names one
+ */
+project_id: string | null, created_at: string, };
+
+export type IntegrationProvider = "youtrack" | "jira" | "github";
+
+export type MappedField = "title" | "description" | "status" | "priority" | "assignee";
+
+export type FieldMappingRule = { 
+/**
+ * Dot-separated path into the raw provider payload, e.g. `fields.customfield_10010.value`.
+ * A `key=value` segment selects the first array element whose `key` equals `value`.
+ */
+source: string, target: MappedField, 
+/**
+ * Remote value -> VK value translations; unlisted values pass through unchanged
+ */
+values: { [key in string]?: string }, 
+/**
+ * Used when the remote value is missing or empty
+ */
+default: string | null, };
+
+export type FieldMapping = { rules: Array<FieldMappingRule>, };
+
+export type IntegrationResponse = { id: string, project_id: string, provider: IntegrationProvider, name: string, base_url: string, config: JsonValue, secrets: { [key in string]?: string }, enabled: boolean, field_mapping: FieldMapping, created_at: string, updated_at: string, };
+
+export type CreateIntegration = { project_id: string, provider: IntegrationProvider, name: string, base_url: string, config: JsonValue | null, secrets: { [key in string]?: string } | null, enabled: boolean | null, field_mapping: FieldMapping | null, };
+
+export type UpdateIntegration = { name: string | null, base_url: string | null, config: JsonValue | null, 
+/**
+ * Secrets to change. A `null` value removes the secret and a value equal to the
+ * redaction placeholder keeps the stored one, so redacted responses can be sent back as-is.
+ */
+secrets: { [key in string]?: string | null } | null, enabled: boolean | null, 
+/**
+ * Replaces the whole mapping when set
+ */
+field_mapping: FieldMapping | null, };
+
+export type IntegrationCheckStep = { name: string, passed: boolean, error: string | null, };
+
+export type IntegrationCheck = { integration_id: string, name: string, provider: IntegrationProvider, enabled: boolean, 
+/**
+ * In the order they ran; the steps after a failed one are left out
+ */
+steps: Array<IntegrationCheckStep>, passed: boolean, checked_at: string, };
+
+export type IntegrationLink = { id: string, integration_id: string, task_id: string, external_id: string, external_url: string | null, remote_updated_at: string | null, 
+/**
+ * Remote field values as of the last sync, the base for three-way conflict detection
+ */
+remote_values: { [key in string]?: string | null } | null, created_at: string, updated_at: string, };
+
+export type SyncJobStatus = "queued" | "running" | "succeeded" | "failed";
+
+export type SyncJob = { id: string, integration_id: string, status: SyncJobStatus, attempts: bigint, 
+/**
+ * Provider-specific run summary, set once the job succeeds
+ */
+result: JsonValue | null, error: string | null, started_at: string | null, finished_at: string | null, created_at: string, updated_at: string, };
+
+export type SyncDeadLetter = { id: string, integration_id: string, run_id: string | null, external_id: string | null, 
+/**
+ * The raw provider payload, replayed as-is on retry
+ */
+payload: JsonValue, error: string, attempts: bigint, created_at: string, updated_at: string, };
+
+export type SyncPlanStatus = "pending" | "applied" | "discarded";
+
+export type SyncPlanAction = "create" | "update" | "close" | "conflict";
+
+export type PlannedFieldChange = { field: string, old_value: string | null, new_value: string | null, };
+
+export type SyncPlanItem = { action: SyncPlanAction, external_id: string, task_id: string | null, title: string, changes: Array<PlannedFieldChange>, 
+/**
+ * The raw provider payload the item was planned from, re-applied as-is
+ */
+payload: JsonValue, };
+
+export type SyncPlan = { id: string, integration_id: string, status: SyncPlanStatus, items: Array<SyncPlanItem>, created_at: string, updated_at: string, };
+
+export type SyncConflictStatus = "open" | "resolved";
+
+export type ConflictResolution = "local" | "remote";
+
+export type ConflictField = { field: string, 
+/**
+ * The remote value as of the last sync
+ */
+base_value: string | null, local_value: string | null, remote_value: string | null, };
+
+export type SyncConflict = { id: string, integration_id: string, task_id: string, external_id: string, fields: Array<ConflictField>, status: SyncConflictStatus, resolution: ConflictResolution | null, resolved_at: string | null, created_at: string, updated_at: string, };
+
+export type SyncConflictQuery = { integration_id: string | null, task_id: string | null, status: SyncConflictStatus | null, };
+
+export type ResolveSyncConflict = { resolution: ConflictResolution, };
+
+export type SyncAuditEntry = { id: string, integration_id: string, run_id: string | null, task_id: string, provider: IntegrationProvider, external_id: string, field: string, old_value: string | null, new_value: string | null, created_at: string, };
+
+export type SyncAuditFilter = { integration_id: string | null, task_id: string | null, run_id: string | null, limit: bigint | null, };
+
+export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
+
+export type TaskPriority = "low" | "medium" | "high" | "urgent";
+
+export type TaskSort = "rank" | "created_at" | "priority" | "due_at";
+
+export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, 
+/**
+ * Board column the task sits in; its category always matches `status`
+ */
+column_id: string | null, parent_workspace_id: string | null, shared_task_id: string | null, due_at: string | null, priority: TaskPriority, 
+/**
+ * Size of the task in whatever unit the project estimates in, e.g. story points
+ */
+estimate: number | null, assignee_id: string | null, 
+/**
+ * Card color on the board, as `#rrggbb`
+ */
+cover_color: string | null, 
+/**
+ * Image attachment of the task shown on top of its card
+ */
+cover_attachment_id: string | null, 
+/**
+ * Set while the task is archived; archived tasks are left off the board
+ */
+archived_at: string | null, 
+/**
+ * Set while the task is in the trash
+ */
+deleted_at: string | null, created_at: string, updated_at: string, 
+/**
+ * Goes up with every change to the task; its `ETag`, sent back as `If-Match` to update
+ * only the version that was read
+ */
+version: bigint, };
+
+export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, last_attempt_failed: boolean, executor: string, 
+/**
+ * Checklist completion rollup
+ */
+checklist_total: bigint, checklist_done: bigint, comment_count: bigint, attachment_count: bigint, 
+/**
+ * Blocked by at least one task that is not done or cancelled
+ */
+is_blocked: boolean, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, 
+/**
+ * Board column the task sits in; its category always matches `status`
+ */
+column_id: string | null, parent_workspace_id: string | null, shared_task_id: string | null, due_at: string | null, priority: TaskPriority, 
+/**
+ * Size of the task in whatever unit the project estimates in, e.g. story points
+ */
+estimate: number | null, assignee_id: string | null, 
+/**
+ * Card color on the board, as `#rrggbb`
+ */
+cover_color: string | null, 
+/**
+ * Image attachment of the task shown on top of its card
+ */
+cover_attachment_id: string | null, 
+/**
+ * Set while the task is archived; archived tasks are left off the board
+ */
+archived_at: string | null, 
+/**
+ * Set while the task is in the trash
+ */
+deleted_at: string | null, created_at: string, updated_at: string, };
+
+export type TaskRelationships = { parent_task: Task | null, current_workspace: Workspace, children: Array<Task>, };
+
+export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, shared_task_id: string | null, due_at?: string, priority?: TaskPriority, estimate?: number, assignee_id?: string, };
+
+export type CloneTask = { 
+/**
+ * Project to create the clone in; the task's own project when omitted
+ */
+project_id?: string, 
+/**
+ * Title of the clone; the task's title when omitted
+ */
+title?: string, 
+/**
+ * Copy the checklist, with every item unchecked
+ */
+copy_checklist: boolean, 
+/**
+ * Copy the labels. In another project, labels are matched by name and those it
+ * doesn't have are skipped
+ */
+copy_labels: boolean, copy_attachments: boolean, 
+/**
+ * Shared task to link the clone to; it must not be linked to another task yet
+ */
+shared_task_id?: string, };
+
+export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, due_at?: string, 
+/**
+ * Remove the due date; takes precedence over `due_at`
+ */
+clear_due_at?: boolean, priority?: TaskPriority, estimate?: number, 
+/**
+ * Remove the estimate; takes precedence over `estimate`
+ */
+clear_estimate?: boolean, 
+/**
+ * `#rrggbb`
+ */
+cover_color?: string, 
+/**
+ * An image attachment of the task
+ */
+cover_attachment_id?: string, 
+/**
+ * Remove the cover color and image; takes precedence over the cover fields
+ */
+clear_cover?: boolean, };
+
+export type TaskFilter = { status: TaskStatus | null, 
+/**
+ * Only tasks whose title, description or comments contain these words, the last one
+ * as a prefix; searched through the full-text index
+ */
+query: string | null, 
+/**
+ * Only tasks carrying this label
+ */
+label_id: string | null, 
+/**
+ * Only tasks due strictly before this time
+ */
+due_before: string | null, 
+/**
+ * Only open tasks whose due date has passed
+ */
+overdue: boolean | null, priority: TaskPriority | null, assignee_id: string | null, 
+/**
+ * Only tasks without an assignee
+ */
+unassigned: boolean | null, 
+/**
+ * Only tasks with a value for this custom field
+ */
+custom_field_id: string | null, 
+/**
+ * With `custom_field_id`, only tasks whose value equals this normalized value
+ */
+custom_field_value: string | null, sort: TaskSort | null, };
+
+export type DueDateSummary = { overdue: bigint, 
+/**
+ * Due between now and the end of the requested window
+ */
+upcoming: bigint, upcoming_until: string, };
+
+export type SummaryColumn = { column_id: string, name: string, category: TaskStatus, task_count: bigint, };
+
+export type OverdueTask = { id: string, title: string, status: TaskStatus, due_at: string, priority: TaskPriority, assignee_id: string | null, };
+
+export type SummaryActivity = { task_id: string, task_title: string, kind: TaskEventKind, source: TaskEventSource, field: string | null, new_value: string | null, created_at: string, };
+
+export type ProjectSummary = { project_id: string, project_name: string, 
+/**
+ * Tasks on the board; archived and trashed tasks aside
+ */
+task_count: bigint, 
+/**
+ * Tasks not done or cancelled
+ */
+open_count: bigint, overdue_count: bigint, 
+/**
+ * Tasks per board column, in column order
+ */
+columns: Array<SummaryColumn>, 
+/**
+ * The open tasks whose due date passed longest ago
+ */
+overdue: Array<OverdueTask>, 
+/**
+ * The latest task changes, newest first
+ */
+recent_activity: Array<SummaryActivity>, generated_at: string, };
+
+export type ArchivedProject = { id: string, name: string, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, };
+
+export type ArchivedColumn = { id: string, name: string, category: TaskStatus, position: bigint, };
+
+export type ArchivedLabel = { id: string, name: string, color: string, };
+
+export type ArchivedCustomField = { id: string, name: string, field_type: CustomFieldType, options: Array<string>, };
+
+export type ArchivedFieldValue = { field_id: string, value: string, };
+
+export type ArchivedChecklistItem = { title: string, done: boolean, };
+
+export type ArchivedComment = { author: string, body: string, created_at: string, };
+
+export type ArchivedTask = { id: string, title: string, description: string | null, status: TaskStatus, column_id: string | null, due_at: string | null, priority: TaskPriority, estimate: number | null, 
+/**
+ * Order on the board within the task's column
+ */
+rank: number, 
+/**
+ * Users are matched by email, then by name, when importing
+ */
+assignee_email: string | null, assignee_name: string | null, cover_color: string | null, archived_at: string | null, created_at: string, label_ids: Array<string>, custom_fields: Array<ArchivedFieldValue>, checklist: Array<ArchivedChecklistItem>, comments: Array<ArchivedComment>, };
+
+export type ArchivedTaskLink = { source_task_id: string, target_task_id: string, kind: TaskLinkKind, };
+
+export type ArchivedIntegration = { id: string, provider: IntegrationProvider, name: string, base_url: string, config: JsonValue, field_mapping: FieldMapping, enabled: boolean, 
+/**
+ * Names of the secrets the integration had
+ */
+secret_keys: Array<string>, };
+
+export type ProjectArchive = { 
+/**
+ * Always `vibe-kanban.project`
+ */
+format: string, version: bigint, exported_at: string, project: ArchivedProject, settings: ProjectSettings, columns: Array<ArchivedColumn>, labels: Array<ArchivedLabel>, custom_fields: Array<ArchivedCustomField>, 
+/**
+ * Archived tasks included; trashed ones left out
+ */
+tasks: Array<ArchivedTask>, links: Array<ArchivedTaskLink>, integrations: Array<ArchivedIntegration>, };
+
+export type NameConflict = "rename" | "fail";
+
+export type ImportReport = { dry_run: boolean, 
+/**
+ * The new project; `None` on a dry run
+ */
+project: Project | null, project_name: string, 
+/**
+ * Whether the name was changed because another project had it
+ */
+renamed: boolean, columns: bigint, labels: bigint, custom_fields: bigint, tasks: bigint, checklist_items: bigint, comments: bigint, links: bigint, 
+/**
+ * Integrations are created disabled, as their secrets have to be entered again
+ */
+integrations: bigint, 
+/**
+ * Assignees no user matched; their tasks are left unassigned
+ */
+unmatched_assignees: Array<string>, 
+/**
+ * References the archive made to things it doesn't contain, which were left out
+ */
+skipped: Array<string>, };
+
+export type QuickAddParse = { 
+/**
+ * The line with its tokens taken out
+ */
+title: string, 
+/**
+ * Names from `#label` tokens
+ */
+labels: Array<string>, 
+/**
+ * Handle from the first `@user` token
+ */
+assignee: string | null, 
+/**
+ * From the first `!low`, `!medium`, `!high` or `!urgent` token
+ */
+priority: TaskPriority | null, 
+/**
+ * End of the day named by the first `^due` token
+ */
+due_at: string | null, };
+
+export type QuickAddResult = { 
+/**
+ * The new task; `None` for a dry run
+ */
+task: Task | null, parsed: QuickAddParse, 
+/**
+ * Label names the project has no label for; they were left off the task
+ */
+unmatched_labels: Array<string>, 
+/**
+ * The handle when it matched no user, or several; the task was left unassigned
+ */
+unmatched_assignee: string | null, };
+
+export type BurndownPoint = { at: string, 
+/**
+ * Estimate of the tasks that were not done or cancelled yet
+ */
+remaining: number, 
+/**
+ * Estimate of every task that existed, finished or not
+ */
+total: number, 
+/**
+ * Tasks not done or cancelled yet
+ */
+remaining_count: bigint, 
+/**
+ * Every task that existed, finished or not
+ */
+total_count: bigint, };
+
+export type CumulativeFlowPoint = { at: string, todo: bigint, inprogress: bigint, inreview: bigint, done: bigint, cancelled: bigint, };
+
+export type DurationStats = { 
+/**
+ * Tasks measured
+ */
+count: bigint, 
+/**
+ * `None` when no task was measured, as are the percentiles
+ */
+mean_hours: number | null, p50_hours: number | null, p75_hours: number | null, p85_hours: number | null, p95_hours: number | null, };
+
+export type ThroughputWeek = { week_start: string, completed: bigint, };
+
+export type FlowMetrics = { since: string, until: string, 
+/**
+ * From creation to done
+ */
+lead_time: DurationStats, 
+/**
+ * From work first starting, i.e. moving to in progress or in review, to done. Tasks
+ * that went straight to done are left out.
+ */
+cycle_time: DurationStats, 
+/**
+ * One entry per week overlapping the range, oldest first
+ */
+throughput: Array<ThroughputWeek>, };
+
+export type WipLimit = { project_id: string, status: TaskStatus, max_tasks: bigint, 
+/**
+ * Reject moves into a full column instead of only warning about them
+ */
+enforced: boolean, created_at: string, updated_at: string, };
+
+export type SetWipLimit = { status: TaskStatus, max_tasks: bigint, 
+/**
+ * Falls back to the project's `enforce_wip_limits` setting
+ */
+enforced?: boolean, };
+
+export type WipColumn = { status: TaskStatus, 
+/**
+ * Tasks on the board in this column; archived and trashed tasks don't count
+ */
+count: bigint, limit: bigint | null, enforced: boolean, exceeded: boolean, };
+
+export type ProjectColumn = { id: string, project_id: string, name: string, 
+/**
+ * Status of the tasks in this column
+ */
+category: TaskStatus, position: bigint, created_at: string, updated_at: string, };
+
+export type CreateProjectColumn = { name: string, category: TaskStatus, };
+
+export type UpdateProjectColumn = { name: string | null, category: TaskStatus | null, };
+
+export type ReorderProjectColumns = { 
+/**
+ * Every column of the project, in the desired order
+ */
+column_ids: Array<string>, };
+
+export type MoveTask = { column_id?: string, status?: TaskStatus, 
+/**
+ * Zero-based index among the column's other tasks; the end of the column when
+ * omitted
+ */
+position?: number, };
+
+export type MoveTaskToProject = { project_id: string, 
+/**
+ * Target project column for a column of the task's project. Columns left out go to
+ * the target column of the same name, or else the first one of the same status
+ */
+column_mapping: { [key in string]?: string }, };
+
+export type ProjectRole = "viewer" | "member" | "admin";
+
+export type ProjectMember = { project_id: string, user_id: string, user_name: string, role: ProjectRole, created_at: string, updated_at: string, };
+
+export type SetProjectMember = { role: ProjectRole, };
+
+export type TeamRole = "member" | "admin";
+
+export type Team = { id: string, name: string, created_at: string, updated_at: string, };
+
+export type CreateTeam = { name: string, };
+
+export type TeamMember = { team_id: string, user_id: string, user_name: string, role: TeamRole, created_at: string, updated_at: string, };
+
+export type SetTeamMember = { role: TeamRole, };
+
+export type Webhook = { id: string, project_id: string, url: string, 
+/**
+ * Event names to send, e.g. `task.created`; every event when empty
+ */
+events: Array<string>, created_at: string, updated_at: string, };
+
+export type CreateWebhook = { project_id: string, url: string, events?: Array<string>, };
+
+export type WebhookDelivery = { id: string, webhook_id: string, event: string, 
+/**
+ * The JSON body that was sent
+ */
+payload: string, 
+/**
+ * `None` when no reply came back; see `error`
+ */
+status_code: bigint | null, 
+/**
+ * The start of the reply body
+ */
+response_body: string | null, error: string | null, duration_ms: bigint, created_at: string, };
+
+export type ShareMode = "live" | "snapshot";
+
+export type ShareField = "description" | "assignee" | "due_at" | "priority" | "estimate" | "progress";
+
+export type ShareLink = { id: string, project_id: string, 
+/**
+ * The first characters of the token, to tell links apart
+ */
+prefix: string, mode: ShareMode, fields: Array<ShareField>, 
+/**
+ * The link stops working after this; never when null
+ */
+expires_at: string | null, created_at: string, };
+
+export type CreateShareLink = { mode?: ShareMode, 
+/**
+ * Only the title and status are shown when empty
+ */
+fields?: Array<ShareField>, expires_at?: string, };
+
+export type SharedBoardTask = { id: string, title: string, status: TaskStatus, description: string | null, assignee_name: string | null, due_at: string | null, priority: TaskPriority | null, estimate: number | null, checklist_total: bigint | null, checklist_done: bigint | null, };
+
+export type SharedBoardColumn = { name: string, category: TaskStatus, tasks: Array<SharedBoardTask>, };
+
+export type SharedBoard = { project_name: string, columns: Array<SharedBoardColumn>, 
+/**
+ * When the board was read; the link's creation time for snapshots
+ */
+generated_at: string, };
+
+export type SwimlaneGroupBy = "assignee" | "label" | "custom_field";
+
+export type ProjectSwimlane = { project_id: string, group_by: SwimlaneGroupBy, 
+/**
+ * The field lanes are drawn from when grouping by custom field
+ */
+custom_field_id: string | null, created_at: string, updated_at: string, };
+
+export type SetProjectSwimlane = { group_by: SwimlaneGroupBy, custom_field_id?: string, };
+
+export type BoardCell = { column_id: string, tasks: Array<TaskWithAttemptStatus>, };
+
+export type BoardLane = { 
+/**
+ * The user id, label id or field value the lane stands for; `None` for the lane of
+ * tasks without one, and for the only lane of a board without swimlanes
+ */
+key: string | null, name: string, 
+/**
+ * One cell per board column, in column order
+ */
+cells: Array<BoardCell>, };
+
+export type Board = { columns: Array<ProjectColumn>, swimlane: ProjectSwimlane | null, lanes: Array<BoardLane>, };
+
+export type TaskChecklistItem = { id: string, task_id: string, title: string, done: boolean, 
+/**
+ * Zero-based order within the task's checklist
+ */
+position: bigint, created_at: string, updated_at: string, };
+
+export type CreateTaskChecklistItem = { title: string, };
+
+export type UpdateTaskChecklistItem = { title: string | null, done: boolean | null, };
+
+export type ReorderTaskChecklistItems = { 
+/**
+ * Every item of the task, in the desired order
+ */
+item_ids: Array<string>, };
+
+export type TaskComment = { id: string, task_id: string, author: string, 
+/**
+ * Markdown
+ */
+body: string, 
+/**
+ * Set for comments imported from an integration
+ */
+integration_id: string | null, external_id: string | null, created_at: string, updated_at: string, };
+
+export type CreateTaskComment = { 
+/**
+ * Ignored when the request is signed in; the signed-in user is the author
+ */
+author: string, body: string, };
+
+export type WatchReason = "manual" | "assigned" | "commented";
+
+export type TaskWatcher = { task_id: string, user_id: string, user_name: string, reason: WatchReason, created_at: string, };
+
+export type WatchTask = { user_id: string, };
+
+export type NotificationKind = "status_changed" | "commented" | "mentioned";
+
+export type Notification = { id: string, user_id: string, task_id: string, task_title: string, kind: NotificationKind, message: string, read_at: string | null, created_at: string, };
+
+export type MarkNotificationsRead = { 
+/**
+ * Notifications to mark; all of the user's when omitted
+ */
+ids?: Array<string>, };
+
+export type TaskMention = { id: string, task_id: string, project_id: string, task_title: string, 
+/**
+ * The comment the mention is in; `None` for the task description
+ */
+comment_id: string | null, user_id: string, created_at: string, };
+
+export type EpicKind = "epic" | "milestone";
+
+export type Epic = { id: string, project_id: string, kind: EpicKind, title: string, description: string | null, due_at: string | null, created_at: string, updated_at: string, };
+
+export type EpicProgress = { total: bigint, done: bigint, 
+/**
+ * Sum of the tasks' estimates
+ */
+estimate_total: number, 
+/**
+ * Estimate of the tasks that are not done yet
+ */
+estimate_remaining: number, };
 
-export type CreateProject = { name: string, repositories: Array<CreateProjectRepo>, };
+export type EpicWithProgress = { progress: EpicProgress, id: string, project_id: string, kind: EpicKind, title: string, description: string | null, due_at: string | null, created_at: string, updated_at: string, };
 
-export type UpdateProject = { name: string | null, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, };
+export type CreateEpic = { kind?: EpicKind, title: string, description?: string, due_at?: string, };
 
-export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, };
+export type UpdateEpic = { kind?: EpicKind, title?: string, 
+/**
+ * An empty description removes it
+ */
+description?: string, due_at?: string, 
+/**
+ * Remove the due date; takes precedence over `due_at`
+ */
+clear_due_at?: boolean, };
 
-export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
+export type SetTaskEpic = { epic_id: string, };
 
-export type Repo = { id: string, path: string, name: string, display_name: string, created_at: Date, updated_at: Date, };
+export type SprintStatus = "planned" | "active" | "closed";
 
-export type ProjectRepo = { id: string, project_id: string, repo_id: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, };
+export type SprintTaskOutcome = "completed" | "carried_over" | "dropped";
 
-export type CreateProjectRepo = { display_name: string, git_repo_path: string, };
+export type Sprint = { id: string, project_id: string, name: string, goal: string | null, status: SprintStatus, starts_at: string, ends_at: string, 
+/**
+ * When the sprint was actually started and closed
+ */
+started_at: string | null, closed_at: string | null, created_at: string, updated_at: string, };
 
-export type UpdateProjectRepo = { setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean | null, };
+export type CreateSprint = { name: string, goal?: string, starts_at: string, ends_at: string, };
 
-export type WorkspaceRepo = { id: string, workspace_id: string, repo_id: string, target_branch: string, created_at: Date, updated_at: Date, };
+export type UpdateSprint = { name?: string, 
+/**
+ * An empty goal removes it
+ */
+goal?: string, starts_at?: string, ends_at?: string, };
 
-export type CreateWorkspaceRepo = { repo_id: string, target_branch: string, };
+export type AddSprintTasks = { task_ids: Array<string>, };
 
-export type RepoWithTargetBranch = { target_branch: string, id: string, path: string, name: string, display_name: string, created_at: Date, updated_at: Date, };
+export type CloseSprint = { 
+/**
+ * Planned sprint that receives the unfinished tasks; the project's next planned
+ * sprint when omitted. Without one they go back to the backlog.
+ */
+next_sprint_id?: string, };
 
-export type Tag = { id: string, tag_name: string, content: string, created_at: string, updated_at: string, };
+export type SprintTask = { task_id: string, title: string, status: TaskStatus, estimate: number | null, 
+/**
+ * Recorded when the sprint closed
+ */
+outcome: SprintTaskOutcome | null, };
 
-export type CreateTag = { tag_name: string, content: string, };
+export type SprintTotals = { tasks: bigint, completed: bigint, carried_over: bigint, dropped: bigint, estimate: number, estimate_completed: number, };
 
-export type UpdateTag = { tag_name: string | null, content: string | null, };
+export type SprintReport = { sprint: Sprint, totals: SprintTotals, tasks: Array<SprintTask>, };
 
-export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
+export type SprintVelocity = { sprint_id: string, name: string, ends_at: string, totals: SprintTotals, };
+
+export type ProjectSettings = { 
+/**
+ * Remote workflow states mapped to a task status, matched case-insensitively. Applied
+ * to every integration of the project before its own field mapping
+ */
+state_mappings: { [key in string]?: TaskStatus }, 
+/**
+ * Whether WIP limits set without an explicit `enforced` flag reject moves
+ */
+enforce_wip_limits: boolean, 
+/**
+ * Archive done and cancelled tasks that haven't changed for this many days
+ */
+auto_archive_after_days: bigint | null, notifications: NotificationPreferences, 
+/**
+ * Range of the burndown report when the request doesn't give one, e.g. `14d` or `2w`
+ */
+burndown_range: string, };
 
-export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, shared_task_id: string | null, created_at: string, updated_at: string, };
+export type NotificationPreferences = { status_changes: boolean, comments: boolean, mentions: boolean, };
 
-export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, last_attempt_failed: boolean, executor: string, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, shared_task_id: string | null, created_at: string, updated_at: string, };
+export type SavedView = { id: string, project_id: string, name: string, filter: TaskFilter, created_at: string, updated_at: string, };
 
-export type TaskRelationships = { parent_task: Task | null, current_workspace: Workspace, children: Array<Task>, };
+export type CreateSavedView = { name: string, filter: TaskFilter, };
+
+export type UpdateSavedView = { name?: string, 
+/**
+ * Replaces the whole filter
+ */
+filter?: TaskFilter, };
+
+export type TaskEventKind = "created" | "status_changed" | "edited" | "assignee_changed" | "archived" | "unarchived" | "deleted" | "restored" | "merged";
+
+export type TaskEventSource = "user" | "agent" | "sync" | "system";
+
+export type TaskEvent = { id: string, task_id: string, kind: TaskEventKind, source: TaskEventSource, integration_id: string | null, field: string | null, old_value: string | null, new_value: string | null, created_at: string, };
+
+export type MergeTasks = { 
+/**
+ * Duplicates to fold into the target task, in the order their descriptions are
+ * appended
+ */
+task_ids: Array<string>, };
+
+export type TaskRevision = { id: string, task_id: string, 
+/**
+ * Starts at 1 and goes up by one with each edit
+ */
+revision: bigint, title: string, description: string | null, source: TaskEventSource, integration_id: string | null, created_at: string, };
+
+export type FieldChange = { field: string, from: string | null, to: string | null, };
+
+export type TaskDiff = { from: string, to: string, 
+/**
+ * Only fields both sides have are compared; revisions hold just the title and
+ * description
+ */
+changes: Array<FieldChange>, };
+
+export type TimeEntry = { id: string, task_id: string, user_id: string, started_at: string, ended_at: string | null, note: string | null, created_at: string, updated_at: string, };
+
+export type TimeEntryDetail = { id: string, task_id: string, user_id: string, started_at: string, ended_at: string | null, note: string | null, created_at: string, updated_at: string, task_title: string, project_id: string, project_name: string, user_name: string, 
+/**
+ * Key of the remote issue the task is linked to, for logging the time there
+ */
+external_id: string | null, 
+/**
+ * Counted up to now for a running timer
+ */
+seconds: bigint, };
+
+export type TimeEntryFilter = { project_id: string | null, task_id: string | null, user_id: string | null, 
+/**
+ * Entries started at or after this time
+ */
+from: string | null, 
+/**
+ * Entries started before this time
+ */
+to: string | null, };
+
+export type TimeGroupBy = "task" | "project" | "user";
+
+export type TimeTotal = { id: string, name: string, seconds: bigint, entries: bigint, };
+
+export type TaskLinkKind = "blocks" | "blocked_by" | "relates_to" | "duplicates" | "duplicated_by";
+
+export type TaskLink = { id: string, source_task_id: string, target_task_id: string, kind: TaskLinkKind, created_at: string, };
+
+export type LinkedTask = { link_id: string, 
+/**
+ * Relation of the viewed task to `task_id`
+ */
+kind: TaskLinkKind, 
+/**
+ * `kind` as displayed, e.g. "is duplicated by"
+ */
+label: string, task_id: string, title: string, status: TaskStatus, };
+
+export type CreateTaskLink = { task_id: string, kind: TaskLinkKind, };
+
+export type TaskSearchHit = { 
+/**
+ * Excerpt around the best matching terms, which are wrapped in `**`
+ */
+snippet: string, 
+/**
+ * BM25 relevance; lower is more relevant
+ */
+rank: number, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, 
+/**
+ * Board column the task sits in; its category always matches `status`
+ */
+column_id: string | null, parent_workspace_id: string | null, shared_task_id: string | null, due_at: string | null, priority: TaskPriority, 
+/**
+ * Size of the task in whatever unit the project estimates in, e.g. story points
+ */
+estimate: number | null, assignee_id: string | null, 
+/**
+ * Card color on the board, as `#rrggbb`
+ */
+cover_color: string | null, 
+/**
+ * Image attachment of the task shown on top of its card
+ */
+cover_attachment_id: string | null, 
+/**
+ * Set while the task is archived; archived tasks are left off the board
+ */
+archived_at: string | null, 
+/**
+ * Set while the task is in the trash
+ */
+deleted_at: string | null, created_at: string, updated_at: string, };
+
+export type ProjectFacet = { project_id: string, project_name: string, count: bigint, };
+
+export type StatusFacet = { status: TaskStatus, count: bigint, };
 
-export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, shared_task_id: string | null, };
+export type LabelFacet = { label_id: string, name: string, color: string, count: bigint, };
 
-export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, };
+export type SearchFacets = { 
+/**
+ * Every matching task
+ */
+total: bigint, projects: Array<ProjectFacet>, statuses: Array<StatusFacet>, 
+/**
+ * A task with several labels counts toward each
+ */
+labels: Array<LabelFacet>, };
+
+export type TaskTemplate = { id: string, project_id: string, name: string, title_pattern: string, description: string | null, 
+/**
+ * Labels applied to created tasks; labels deleted since are skipped
+ */
+label_ids: Array<string>, 
+/**
+ * Checklist item titles added to created tasks, in order
+ */
+checklist: Array<string>, created_at: string, updated_at: string, };
+
+export type CreateTaskTemplate = { name: string, title_pattern: string, description: string | null, label_ids: Array<string>, checklist: Array<string>, };
+
+export type UpdateTaskTemplate = { name: string | null, title_pattern: string | null, description: string | null, label_ids: Array<string> | null, checklist: Array<string> | null, };
+
+export type CreateTaskFromTemplate = { 
+/**
+ * Values for the template's placeholders; these take precedence over the built-in ones
+ */
+variables: { [key in string]?: string }, };
+
+export type RecurrenceRule = { id: string, task_id: string | null, template_id: string | null, rrule: string, 
+/**
+ * When the next task is created from the template; unused for task rules
+ */
+next_run_at: string | null, created_at: string, updated_at: string, };
+
+export type SetRecurrence = { rrule: string, 
+/**
+ * First run of a template schedule; defaults to the rule's next occurrence from now
+ */
+start_at?: string, };
+
+export type TaskAttachment = { id: string, task_id: string, original_name: string, mime_type: string, size_bytes: bigint, hash: string, 
+/**
+ * Set for attachments imported from an integration
+ */
+integration_id: string | null, external_id: string | null, created_at: string, updated_at: string, };
 
 export type DraftFollowUpData = { message: string, variant: string | null, 
 /**
@@ -191,12 +1149,92 @@ export type CreateRemoteProjectRequest = { organization_id: string, name: string
 
 export type LinkToExistingRequest = { remote_project_id: string, };
 
+export type DueDateSummaryQuery = { 
+/**
+ * Size of the upcoming window in days, 7 when omitted
+ */
+days: bigint | null, };
+
+export type ProjectSummaryQuery = { 
+/**
+ * Overdue tasks to list, 5 when omitted
+ */
+overdue: bigint | null, 
+/**
+ * Recent changes to list, 10 when omitted
+ */
+activity: bigint | null, };
+
+export type BurndownQuery = { 
+/**
+ * How far back to go, e.g. `14d` or `2w`; the project's `burndown_range` setting when
+ * omitted
+ */
+range: string | null, 
+/**
+ * Start of an explicit date range, instead of `range`
+ */
+since: string | null, 
+/**
+ * End of the range, with `range` or `since`; now when omitted
+ */
+until: string | null, 
+/**
+ * Only this sprint's tasks, from its start to its end or now, whichever is first
+ */
+sprint: string | null, };
+
+export type CumulativeFlowQuery = { 
+/**
+ * How far back to go, e.g. `30d` or `8w`; 30 days when neither this nor `since` is
+ * given
+ */
+range: string | null, 
+/**
+ * Start of an explicit date range, instead of `range`
+ */
+since: string | null, 
+/**
+ * End of the range; now when omitted
+ */
+until: string | null, };
+
+export type AnalyticsQuery = { 
+/**
+ * How far back to look for finished tasks, e.g. `30d` or `12w`; 12 weeks when omitted
+ */
+range: string | null, };
+
+export type ImportQuery = { 
+/**
+ * Only check the archive and report what would be created
+ */
+dry_run: boolean, 
+/**
+ * Name of the new project, the archived name when omitted
+ */
+name: string | null, 
+/**
+ * What to do when a project already has the name, `rename` when omitted
+ */
+on_conflict: NameConflict | null, };
+
 export type RegisterRepoRequest = { path: string, display_name: string | null, };
 
 export type InitRepoRequest = { parent_path: string, folder_name: string, };
 
 export type TagSearchParams = { search: string | null, };
 
+export type AssignTask = { user_id: string, };
+
+export type IntegrationQuery = { project_id: string | null, };
+
+export type SyncJobsQuery = { limit: bigint | null, 
+/**
+ * Continue after the page whose `X-Next-Cursor` header this is
+ */
+cursor: string | null, };
+
 export type TokenResponse = { access_token: string, expires_at: string | null, };
 
 export type UserSystemInfo = { config: Config, analytics_user_id: string, login_status: LoginStatus, environment: Environment, 
@@ -254,6 +1292,140 @@ export type ShareTaskResponse = { shared_task_id: string, };
 
 export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
 
+export type BulkTaskOperation = { "type": "set_status", status: TaskStatus, } | { "type": "add_label", label_id: string, } | { "type": "assign", assignee_id: string | null, } | { "type": "archive" } | { "type": "delete" };
+
+export type BulkTaskRequest = { task_ids: Array<string>, operation: BulkTaskOperation, };
+
+export type BulkTaskResult = { task_id: string, ok: boolean, 
+/**
+ * Why the operation was not applied to this task
+ */
+error: string | null, };
+
+export type BatchTaskOperation = { "op": "create", task: CreateTask, } | { "op": "update", task_id: string, changes: UpdateTask, } | { "op": "delete", task_id: string, };
+
+export type BatchTaskRequest = { operations: Array<BatchTaskOperation>, 
+/**
+ * Apply every operation or none of them. Otherwise each operation that can be
+ * applied is, whatever happens to the others.
+ */
+transactional: boolean, };
+
+export type QuickAddRequest = { project_id: string, 
+/**
+ * e.g. `Fix login bug #bug @alice !high ^friday`
+ */
+text: string, 
+/**
+ * The caller's date, which `^today` and `^friday` count from; the server's UTC
+ * date when left out
+ */
+today?: string, 
+/**
+ * Parse the line and look up its labels and assignee without creating the task
+ */
+dry_run?: boolean, };
+
+export type BatchTaskResult = { 
+/**
+ * Position of the operation in the request
+ */
+index: number, ok: boolean, 
+/**
+ * The task as the operation left it
+ */
+task: Task | null, 
+/**
+ * Why the operation was not applied
+ */
+error: string | null, };
+
+export type BatchTaskResponse = { 
+/**
+ * Whether anything was written; false when a transactional batch was rolled back
+ */
+committed: boolean, results: Array<BatchTaskResult>, };
+
+export type CreatedApiKey = { api_key: ApiKey, 
+/**
+ * The key to send as `Authorization: Bearer <key>`. It is not stored and cannot be
+ * shown again.
+ */
+key: string, };
+
+export type AuditQuery = { user_id: string | null, api_key_id: string | null, project_id: string | null, principal: AuditPrincipal | null, 
+/**
+ * Entries made at or after this time
+ */
+since: string | null, 
+/**
+ * Entries made before this time
+ */
+until: string | null, limit: bigint | null, 
+/**
+ * Continue after the page whose `X-Next-Cursor` header this is
+ */
+cursor: string | null, };
+
+export type WebhookQuery = { project_id: string | null, };
+
+export type CreatedWebhook = { webhook: Webhook, 
+/**
+ * Deliveries carry `X-VK-Signature: sha256=<HMAC-SHA256 of the body>` keyed with this.
+ * It cannot be shown again.
+ */
+secret: string, };
+
+export type WebhookDeliveriesQuery = { limit: bigint | null, 
+/**
+ * Continue after the page whose `X-Next-Cursor` header this is
+ */
+cursor: string | null, };
+
+export type CreatedShareLink = { share_link: ShareLink, 
+/**
+ * Open the board at `/api/share/<token>`. It is not stored and cannot be shown again.
+ */
+token: string, };
+
+export type SearchResults = { 
+/**
+ * The best matches, up to `limit`
+ */
+hits: Array<TaskSearchHit>, 
+/**
+ * Counts over every match, for narrowing the search further
+ */
+facets: SearchFacets, };
+
+export type TimerRequest = { user_id: string, note: string | null, };
+
+export type TaskRevisionDiff = { from: bigint, to: bigint, 
+/**
+ * `None` when the title is the same in both revisions
+ */
+title: string | null, 
+/**
+ * `None` when the description is the same in both revisions
+ */
+description: string | null, };
+
+export type TaskDiffQuery = { 
+/**
+ * A revision number, `local` for the task as it is now, or `remote` for the linked
+ * issue as of the last sync
+ */
+from: string, 
+/**
+ * Same choices as `from`; `local` when omitted
+ */
+to: string | null, 
+/**
+ * The integration whose issue `remote` means, for tasks linked through several; the
+ * first linked one when omitted
+ */
+integration_id: string | null, };
+
 export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
 
 export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
@@ -322,11 +1494,53 @@ conflict_op: ConflictOp | null,
  */
 conflicted_files: Array<string>, };
 
+export type RenderMarkdown = { markdown: string, 
+/**
+ * Project whose linked issues are autolinked; only @mentions are linked when
+ * omitted
+ */
+project_id?: string, };
+
+export type RenderedMarkdown = { 
+/**
+ * Sanitized HTML. Highlighted code tokens carry `hl-` prefixed classes, mentions
+ * are `span.mention` with a `data-user-id` and issue references `a.issue-link`
+ */
+html: string, };
+
+export type BoardEventKind = "created" | "updated" | "moved" | "deleted";
+
+export type BoardEvent = { kind: BoardEventKind, project_id: string, task_id: string, 
+/**
+ * The task as the board shows it; absent for deletions
+ */
+task: TaskWithAttemptStatus | null, };
+
+export type BoardMessage = { "type": "snapshot", tasks: Array<TaskWithAttemptStatus>, } | { "type": "event" } & BoardEvent;
+
+export type BoardWsQuery = { project_id: string, };
+
+export type ProjectEventKind = "task.created" | "task.updated" | "task.moved" | "task.deleted" | "comment.created" | "comment.deleted" | "sync.succeeded" | "sync.failed";
+
+export type ProjectEvent = { 
+/**
+ * Increases by one per event, across projects, since the server started
+ */
+id: number, project_id: string, kind: ProjectEventKind, task_id: string | null, 
+/**
+ * The task, comment or sync run the event is about; null when a task left its board
+ */
+data: JsonValue, created_at: string, };
+
 export type DirectoryEntry = { name: string, path: string, is_directory: boolean, is_git_repo: boolean, last_modified: bigint | null, };
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, };
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, 
+/**
+ * Days a deleted task stays in the trash before it is purged for good
+ */
+trash_retention_days: number, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -350,6 +1564,34 @@ export type GitBranch = { name: string, is_current: boolean, is_remote: boolean,
 
 export type SharedTaskDetails = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, };
 
+export type IntegrationCapability = "pull" | "push" | "webhooks";
+
+export type CatalogField = { key: string, label: string, required: boolean, description: string, };
+
+export type ProviderCatalogEntry = { provider: IntegrationProvider, display_name: string, 
+/**
+ * Hint for the `base_url` field
+ */
+base_url_placeholder: string, config: Array<CatalogField>, secrets: Array<CatalogField>, capabilities: Array<IntegrationCapability>, };
+
+export type SyncSummary = { fetched: number, created: number, updated: number, unchanged: number, 
+/**
+ * Items that could not be imported and were dead-lettered
+ */
+failed: number, };
+
+export type WebhookOutcome = "created" | "updated" | "unchanged" | "unlinked" | "ignored";
+
+export type IntegrationHealth = { integration_id: string, enabled: boolean, last_success_at: string | null, last_error: string | null, last_error_at: string | null, 
+/**
+ * From the provider when it reports one, otherwise the `token_expires_at` config value
+ */
+token_expires_at: string | null, 
+/**
+ * Whether the remote API accepted an authenticated request; null when not probed
+ */
+reachable: boolean | null, reachability_error: string | null, checked_at: string, };
+
 export type QueuedMessage = { 
 /**
  * The session this message is queued for